@@ -0,0 +1,292 @@
+//! CPI interface for the Open Badges program
+//!
+//! Lets grading contracts, LMS programs and other on-chain systems issue
+//! credentials when their own completion events fire. The calling program
+//! owns a PDA that is the authority of an Open Badges issuer profile and
+//! signs `issue_achievement_credential_cpi` for it with `invoke_signed`.
+//! The issuer's CPI policy (`set_cpi_policy`) must allow issuance and list
+//! the calling program.
+//!
+//! Instruction data and account metas come from the program crate's
+//! always-generated `instruction` and `accounts` modules, so the wire format
+//! cannot drift from the program. The program's own `cpi` feature and
+//! `declare_program!` are not usable yet: several view contexts have no
+//! accounts.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_lang::InstructionData;
+
+pub use open_badges::program::OpenBadges;
+pub use open_badges::results::ResultInput;
+pub use open_badges::{AchievementCredential, ID};
+
+/// Program-owned addresses `issue_achievement_credential_cpi` expects
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IssueAddresses {
+    /// Issuer profile owned by the authority
+    pub issuer: Pubkey,
+    /// Credential account to be created
+    pub credential: Pubkey,
+    /// Recipient's opt-out PDA
+    pub recipient_opt_out: Pubkey,
+    /// Achievement counters PDA
+    pub achievement_stats: Pubkey,
+    /// Recipient's credential index PDA
+    pub credential_index: Pubkey,
+    /// Index page PDA the credential is appended to
+    pub credential_index_page: Pubkey,
+    /// Achievement's recipient roster PDA
+    pub recipient_roster: Pubkey,
+    /// Roster page PDA the recipient is appended to
+    pub recipient_roster_page: Pubkey,
+    /// Issuer event sequence PDA
+    pub issuer_stats: Pubkey,
+    /// Issuer CPI policy PDA, which must allow the calling program
+    pub cpi_policy: Pubkey,
+    /// Program config PDA holding the pause switch
+    pub program_config: Pubkey,
+    /// Program treasury PDA receiving the issuance fee
+    pub treasury: Pubkey,
+}
+
+impl IssueAddresses {
+    /// Derive the addresses for issuing `achievement` to `recipient`
+    /// `index_page` and `roster_page` are the `next_page()` of the recipient's
+    /// credential index and the achievement's roster, read from those accounts
+    pub fn derive(authority: &Pubkey, achievement: &Pubkey, recipient: &Pubkey, index_page: u32, roster_page: u32) -> Self {
+        let (issuer, _) = open_badges::pda::find_issuer_pda(authority, &ID);
+        let (credential, _) = open_badges::pda::find_credential_pda(achievement, &issuer, recipient, &ID);
+        let (recipient_opt_out, _) = Pubkey::find_program_address(
+            &[open_badges::opt_out::OPT_OUT_SEED, recipient.as_ref()],
+            &ID,
+        );
+        let (achievement_stats, _) = Pubkey::find_program_address(
+            &[open_badges::stats::ACHIEVEMENT_STATS_SEED, achievement.as_ref()],
+            &ID,
+        );
+        let (credential_index, _) = Pubkey::find_program_address(
+            &[open_badges::credential_index::CREDENTIAL_INDEX_SEED, recipient.as_ref()],
+            &ID,
+        );
+        let (credential_index_page, _) =
+            open_badges::credential_index::find_index_page_address(recipient, index_page, &ID);
+        let (recipient_roster, _) = Pubkey::find_program_address(
+            &[open_badges::recipient_roster::RECIPIENT_ROSTER_SEED, achievement.as_ref()],
+            &ID,
+        );
+        let (recipient_roster_page, _) =
+            open_badges::recipient_roster::find_roster_page_address(achievement, roster_page, &ID);
+        let (issuer_stats, _) = Pubkey::find_program_address(
+            &[open_badges::stats::ISSUER_STATS_SEED, issuer.as_ref()],
+            &ID,
+        );
+        let (cpi_policy, _) = Pubkey::find_program_address(
+            &[open_badges::cpi_guard::CPI_POLICY_SEED, issuer.as_ref()],
+            &ID,
+        );
+        let (program_config, _) = open_badges::program_config::find_program_config_address(&ID);
+        let (treasury, _) = Pubkey::find_program_address(&[open_badges::treasury::TREASURY_SEED], &ID);
+        Self {
+            issuer,
+            credential,
+            recipient_opt_out,
+            achievement_stats,
+            credential_index,
+            credential_index_page,
+            recipient_roster,
+            recipient_roster_page,
+            issuer_stats,
+            cpi_policy,
+            program_config,
+            treasury,
+        }
+    }
+}
+
+/// Accounts of `issue_achievement_credential_cpi`
+pub struct IssueAccounts<'info> {
+    pub credential: AccountInfo<'info>,
+    pub achievement: AccountInfo<'info>,
+    pub issuer: AccountInfo<'info>,
+    /// Program config PDA, read for the pause switch
+    pub program_config: AccountInfo<'info>,
+    /// Program treasury receiving the issuance fee, charged to `payer`
+    pub treasury: AccountInfo<'info>,
+    /// Payer's token account, when the config sets a token fee
+    pub fee_token_account: Option<AccountInfo<'info>>,
+    /// Treasury-owned token account, when the config sets a token fee
+    pub treasury_token_account: Option<AccountInfo<'info>>,
+    /// SPL Token program, when the config sets a token fee
+    pub token_program: Option<AccountInfo<'info>>,
+    pub recipient_opt_out: AccountInfo<'info>,
+    /// Approved assessment result, when the achievement is moderated
+    pub result_entry: Option<AccountInfo<'info>>,
+    /// Grading rubric, when results carry achieved levels
+    pub rubric: Option<AccountInfo<'info>>,
+    /// Issuer CPI policy allowing the calling program to issue
+    pub cpi_policy: AccountInfo<'info>,
+    /// Instructions sysvar, read to identify the calling program
+    pub instructions: AccountInfo<'info>,
+    pub achievement_stats: AccountInfo<'info>,
+    pub credential_index: AccountInfo<'info>,
+    pub credential_index_page: AccountInfo<'info>,
+    pub recipient_roster: AccountInfo<'info>,
+    pub recipient_roster_page: AccountInfo<'info>,
+    pub issuer_stats: AccountInfo<'info>,
+    /// Issuer authority, usually a PDA of the calling program
+    pub authority: AccountInfo<'info>,
+    pub payer: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+}
+
+impl<'info> IssueAccounts<'info> {
+    fn to_instruction(&self, recipient: Pubkey, results: Vec<ResultInput>) -> Instruction {
+        let accounts = open_badges::accounts::IssueAchievementCredentialCpi {
+            credential: self.credential.key(),
+            achievement: self.achievement.key(),
+            issuer: self.issuer.key(),
+            program_config: self.program_config.key(),
+            treasury: self.treasury.key(),
+            fee_token_account: self.fee_token_account.as_ref().map(|a| a.key()),
+            treasury_token_account: self.treasury_token_account.as_ref().map(|a| a.key()),
+            token_program: self.token_program.as_ref().map(|a| a.key()),
+            recipient_opt_out: self.recipient_opt_out.key(),
+            result_entry: self.result_entry.as_ref().map(|a| a.key()),
+            rubric: self.rubric.as_ref().map(|a| a.key()),
+            cpi_policy: Some(self.cpi_policy.key()),
+            instructions: Some(self.instructions.key()),
+            achievement_stats: self.achievement_stats.key(),
+            credential_index: self.credential_index.key(),
+            credential_index_page: self.credential_index_page.key(),
+            recipient_roster: self.recipient_roster.key(),
+            recipient_roster_page: self.recipient_roster_page.key(),
+            issuer_stats: self.issuer_stats.key(),
+            authority: self.authority.key(),
+            payer: self.payer.key(),
+            system_program: self.system_program.key(),
+        };
+        let data = open_badges::instruction::IssueAchievementCredentialCpi {
+            recipient_pubkey: recipient,
+            results,
+        };
+        Instruction {
+            program_id: ID,
+            accounts: accounts.to_account_metas(None),
+            data: data.data(),
+        }
+    }
+
+    fn to_account_infos(&self, open_badges_program: &AccountInfo<'info>) -> Vec<AccountInfo<'info>> {
+        let mut infos = vec![
+            self.credential.clone(),
+            self.achievement.clone(),
+            self.issuer.clone(),
+            self.program_config.clone(),
+            self.treasury.clone(),
+            self.recipient_opt_out.clone(),
+            self.cpi_policy.clone(),
+            self.instructions.clone(),
+            self.achievement_stats.clone(),
+            self.credential_index.clone(),
+            self.credential_index_page.clone(),
+            self.recipient_roster.clone(),
+            self.recipient_roster_page.clone(),
+            self.issuer_stats.clone(),
+            self.authority.clone(),
+            self.payer.clone(),
+            self.system_program.clone(),
+            // Absent optional accounts are passed as the program id
+            open_badges_program.clone(),
+        ];
+        infos.extend(self.result_entry.iter().cloned());
+        infos.extend(self.rubric.iter().cloned());
+        infos.extend(self.fee_token_account.iter().cloned());
+        infos.extend(self.treasury_token_account.iter().cloned());
+        infos.extend(self.token_program.iter().cloned());
+        infos
+    }
+}
+
+/// Issue a credential with the calling program's PDA as issuer authority
+pub fn issue_credential_signed<'info>(
+    open_badges_program: &AccountInfo<'info>,
+    accounts: IssueAccounts<'info>,
+    authority_seeds: &[&[&[u8]]],
+    recipient: Pubkey,
+    results: Vec<ResultInput>,
+) -> Result<()> {
+    require_keys_eq!(open_badges_program.key(), ID, ErrorCode::InvalidProgramId);
+    let ix = accounts.to_instruction(recipient, results);
+    invoke_signed(&ix, &accounts.to_account_infos(open_badges_program), authority_seeds)
+        .map_err(Into::into)
+}
+
+/// Accounts of `initialize_issuer_pda_authority`
+pub struct InitializeIssuerAccounts<'info> {
+    pub issuer: AccountInfo<'info>,
+    /// Issuer authority, a PDA of the calling program
+    pub authority: AccountInfo<'info>,
+    pub payer: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+}
+
+/// Create an issuer profile owned by the calling program's PDA
+///
+/// `authority_seeds` are the PDA's seeds with its bump last; they sign the
+/// CPI and let the program check the authority really is this program's PDA.
+pub fn initialize_issuer_signed<'info>(
+    open_badges_program: &AccountInfo<'info>,
+    accounts: InitializeIssuerAccounts<'info>,
+    authority_seeds: &[&[u8]],
+    calling_program: Pubkey,
+    name: String,
+    url: Option<String>,
+) -> Result<()> {
+    require_keys_eq!(open_badges_program.key(), ID, ErrorCode::InvalidProgramId);
+    let metas = open_badges::accounts::InitializeIssuerPdaAuthority {
+        issuer: accounts.issuer.key(),
+        authority: accounts.authority.key(),
+        payer: accounts.payer.key(),
+        system_program: accounts.system_program.key(),
+    };
+    let data = open_badges::instruction::InitializeIssuerPdaAuthority {
+        name,
+        url,
+        email: None,
+        image: None,
+        authority_program: calling_program,
+        authority_seeds: authority_seeds.iter().map(|seed| seed.to_vec()).collect(),
+    };
+    let ix = Instruction {
+        program_id: ID,
+        accounts: metas.to_account_metas(None),
+        data: data.data(),
+    };
+    let infos = [accounts.issuer, accounts.authority, accounts.payer, accounts.system_program];
+    invoke_signed(&ix, &infos, &[authority_seeds]).map_err(Into::into)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_addresses_match_program_seeds() {
+        let authority = Pubkey::new_unique();
+        let achievement = Pubkey::new_unique();
+        let recipient = Pubkey::new_unique();
+        let addresses = IssueAddresses::derive(&authority, &achievement, &recipient, 2, 1);
+
+        let (issuer, _) = open_badges::pda::find_issuer_pda(&authority, &ID);
+        assert_eq!(addresses.issuer, issuer);
+        let (credential, _) = open_badges::pda::find_credential_pda(&achievement, &issuer, &recipient, &ID);
+        assert_eq!(addresses.credential, credential);
+        assert_ne!(addresses.issuer_stats, addresses.recipient_opt_out);
+        let (index_page, _) = open_badges::credential_index::find_index_page_address(&recipient, 2, &ID);
+        assert_eq!(addresses.credential_index_page, index_page);
+        let (roster_page, _) = open_badges::recipient_roster::find_roster_page_address(&achievement, 1, &ID);
+        assert_eq!(addresses.recipient_roster_page, roster_page);
+    }
+}
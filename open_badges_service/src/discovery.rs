@@ -0,0 +1,132 @@
+//! Service Description Document
+//!
+//! `GET /ims/ob/v3p0/discovery` returns an OpenAPI 3.0 document describing
+//! the service: its endpoints, the OAuth 2.0 authorization code flow used
+//! to obtain tokens, and the `x-imssf-*` extensions that consumers use to
+//! register and show the service to users.
+
+use crate::scopes;
+
+/// Base path of every Open Badges API 3.0 endpoint
+pub const BASE_PATH: &str = "/ims/ob/v3p0";
+
+/// How the deployment describes itself in the discovery document
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ServiceConfig {
+    /// Public URL the service is reached at, without `BASE_PATH`
+    pub base_url: String,
+    /// Name shown to users choosing a service
+    pub title: String,
+    /// Terms of service page
+    pub terms_of_service_url: String,
+    /// Privacy policy page
+    pub privacy_policy_url: String,
+    /// Logo shown to users choosing a service
+    pub image_url: Option<String>,
+    /// OAuth 2.0 dynamic client registration endpoint
+    pub registration_url: String,
+    /// OAuth 2.0 authorization endpoint
+    pub authorization_url: String,
+    /// OAuth 2.0 token endpoint
+    pub token_url: String,
+}
+
+fn operation(operation_id: &str, tag: &str, scope: Option<&str>) -> serde_json::Value {
+    let mut operation = serde_json::json!({
+        "operationId": operation_id,
+        "tags": [tag],
+        "responses": {
+            "200": { "description": "Success" },
+            "default": { "description": "Imsx_StatusInfo describing the failure" },
+        },
+    });
+    if let Some(scope) = scope {
+        operation["security"] = serde_json::json!([{ "OAuth2ACG": [scope] }]);
+    }
+    operation
+}
+
+/// OpenAPI service description of the endpoints this crate serves
+pub fn document(config: &ServiceConfig) -> serde_json::Value {
+    let scopes: serde_json::Map<String, serde_json::Value> = scopes::ALL
+        .iter()
+        .map(|(scope, description)| (scope.to_string(), serde_json::json!(description)))
+        .collect();
+    let mut info = serde_json::json!({
+        "title": config.title,
+        "version": "3.0",
+        "termsOfService": config.terms_of_service_url,
+        "x-imssf-privacyPolicyUrl": config.privacy_policy_url,
+    });
+    if let Some(image_url) = &config.image_url {
+        info["x-imssf-image"] = serde_json::json!(image_url);
+    }
+
+    serde_json::json!({
+        "openapi": "3.0.1",
+        "info": info,
+        "servers": [{ "url": format!("{}{}", config.base_url.trim_end_matches('/'), BASE_PATH) }],
+        "tags": [
+            { "name": "OpenBadgeCredentials", "description": "Exchange of AchievementCredentials and issuer profiles" },
+            { "name": "Discovery", "description": "Service description" },
+        ],
+        "paths": {
+            "/credentials": {
+                "get": operation("getCredentials", "OpenBadgeCredentials", Some(scopes::CREDENTIAL_READONLY)),
+                "post": operation("upsertCredential", "OpenBadgeCredentials", Some(scopes::CREDENTIAL_UPSERT)),
+            },
+            "/profile": {
+                "get": operation("getProfile", "OpenBadgeCredentials", Some(scopes::PROFILE_READONLY)),
+            },
+            "/discovery": {
+                "get": operation("getServiceDescription", "Discovery", None),
+            },
+        },
+        "components": {
+            "securitySchemes": {
+                "OAuth2ACG": {
+                    "type": "oauth2",
+                    "description": "OAuth 2.0 Authorization Code Grant authorization",
+                    "x-imssf-name": config.title,
+                    "x-imssf-registrationUrl": config.registration_url,
+                    "flows": {
+                        "authorizationCode": {
+                            "authorizationUrl": config.authorization_url,
+                            "tokenUrl": config.token_url,
+                            "refreshUrl": config.token_url,
+                            "scopes": scopes,
+                        }
+                    }
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_document_advertises_every_scope() {
+        let config = ServiceConfig {
+            base_url: "https://badges.example/".to_string(),
+            title: "Example Badges".to_string(),
+            terms_of_service_url: "https://badges.example/terms".to_string(),
+            privacy_policy_url: "https://badges.example/privacy".to_string(),
+            image_url: None,
+            registration_url: "https://auth.example/register".to_string(),
+            authorization_url: "https://auth.example/authorize".to_string(),
+            token_url: "https://auth.example/token".to_string(),
+        };
+        let document = document(&config);
+        assert_eq!(document["servers"][0]["url"], "https://badges.example/ims/ob/v3p0");
+        let flow = &document["components"]["securitySchemes"]["OAuth2ACG"]["flows"]["authorizationCode"];
+        for (scope, _) in scopes::ALL {
+            assert!(flow["scopes"].get(scope).is_some());
+        }
+        assert_eq!(document["paths"]["/credentials"]["post"]["security"][0]["OAuth2ACG"][0], scopes::CREDENTIAL_UPSERT);
+        assert!(document["paths"]["/discovery"]["get"].get("security").is_none());
+        assert!(document["info"].get("x-imssf-image").is_none());
+    }
+}
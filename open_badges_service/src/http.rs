@@ -0,0 +1,328 @@
+//! HTTP Transport
+//!
+//! A blocking HTTP/1.1 server that parses each request, checks its bearer
+//! token against the endpoint's scope and dispatches to the [`Service`].
+//! Every connection carries one request and is closed after the response.
+//! Failures are answered with the API's `Imsx_StatusInfo` body.
+
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::TcpListener;
+
+use anchor_lang::prelude::Pubkey;
+
+use crate::discovery::BASE_PATH;
+use crate::rpc::AccountSource;
+use crate::service::Service;
+use crate::{scopes, Result, ServiceError};
+
+/// Largest request body accepted, ample for one credential document
+pub const MAX_BODY_LEN: usize = 256 * 1024;
+
+/// What an access token grants
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grant {
+    /// Authenticated entity: a recipient wallet or an issuer authority
+    pub subject: Pubkey,
+    /// Scopes the token carries
+    pub scopes: Vec<String>,
+}
+
+/// Checks access tokens issued by the deployment's authorization server
+pub trait TokenValidator {
+    /// Grant of a valid `token`, or `None` when it is unknown or expired
+    fn validate(&self, token: &str) -> Option<Grant>;
+}
+
+/// Fixed tokens, for development and conformance runs
+#[derive(Clone, Debug, Default)]
+pub struct StaticTokens(pub HashMap<String, Grant>);
+
+impl TokenValidator for StaticTokens {
+    fn validate(&self, token: &str) -> Option<Grant> {
+        self.0.get(token).cloned()
+    }
+}
+
+/// A parsed HTTP request
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Request {
+    /// Request method, e.g. `GET`
+    pub method: String,
+    /// Path without the query string
+    pub path: String,
+    /// Query parameters in order
+    pub query: Vec<(String, String)>,
+    /// Headers with lowercased names
+    pub headers: Vec<(String, String)>,
+    /// Request body
+    pub body: Vec<u8>,
+}
+
+impl Request {
+    /// Value of the header named `name` (lowercase)
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.iter().find(|(header, _)| header == name).map(|(_, value)| value.as_str())
+    }
+
+    /// Value of the query parameter `name`
+    pub fn query_param(&self, name: &str) -> Option<&str> {
+        self.query.iter().find(|(param, _)| param == name).map(|(_, value)| value.as_str())
+    }
+
+    /// Token of an `Authorization: Bearer` header
+    pub fn bearer_token(&self) -> Option<&str> {
+        let value = self.header("authorization")?;
+        let (scheme, token) = value.split_once(' ')?;
+        scheme.eq_ignore_ascii_case("bearer").then(|| token.trim())
+    }
+}
+
+/// An HTTP response
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Response {
+    /// Status code
+    pub status: u16,
+    /// Headers besides Content-Length and Connection
+    pub headers: Vec<(String, String)>,
+    /// Response body
+    pub body: Vec<u8>,
+}
+
+impl Response {
+    /// 200 with a JSON body
+    pub fn json(body: &serde_json::Value) -> Self {
+        Self {
+            status: 200,
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: body.to_string().into_bytes(),
+        }
+    }
+
+    /// Error response with an `Imsx_StatusInfo` body
+    pub fn error(error: &ServiceError) -> Self {
+        Self { status: error.status, ..Self::json(&error.status_info()) }
+    }
+
+    /// Response without a body
+    pub fn empty(status: u16) -> Self {
+        Self { status, headers: vec![], body: vec![] }
+    }
+
+    /// Write the response as HTTP/1.1
+    pub fn write_to(&self, writer: &mut impl Write) -> io::Result<()> {
+        write!(writer, "HTTP/1.1 {} {}\r\n", self.status, reason(self.status))?;
+        for (name, value) in &self.headers {
+            write!(writer, "{}: {}\r\n", name, value)?;
+        }
+        write!(writer, "Content-Length: {}\r\nConnection: close\r\n\r\n", self.body.len())?;
+        writer.write_all(&self.body)?;
+        writer.flush()
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        304 => "Not Modified",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        _ => "Internal Server Error",
+    }
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        let escaped = match bytes[index] {
+            b'%' if index + 2 < bytes.len() => std::str::from_utf8(&bytes[index + 1..index + 3])
+                .ok()
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok()),
+            _ => None,
+        };
+        match (escaped, bytes[index]) {
+            (Some(byte), _) => {
+                decoded.push(byte);
+                index += 3;
+                continue;
+            }
+            (None, b'+') => decoded.push(b' '),
+            (None, byte) => decoded.push(byte),
+        }
+        index += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Read one request: request line, headers and a Content-Length body
+pub fn parse_request(reader: &mut impl BufRead) -> io::Result<Request> {
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let mut parts = line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return Err(invalid("malformed request line"));
+    };
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let query = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (percent_decode(name), percent_decode(value))
+        })
+        .collect();
+
+    let mut headers = vec![];
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 || line.trim_end().is_empty() {
+            break;
+        }
+        let (name, value) = line.split_once(':').ok_or_else(|| invalid("malformed header"))?;
+        headers.push((name.trim().to_ascii_lowercase(), value.trim().to_string()));
+    }
+
+    let mut request = Request { method: method.to_string(), path: path.to_string(), query, headers, body: vec![] };
+    let length: usize = match request.header("content-length") {
+        Some(length) => length.parse().map_err(|_| invalid("malformed Content-Length"))?,
+        None => 0,
+    };
+    if length > MAX_BODY_LEN {
+        return Err(io::Error::new(io::ErrorKind::OutOfMemory, "request body too large"));
+    }
+    request.body = vec![0; length];
+    reader.read_exact(&mut request.body)?;
+    Ok(request)
+}
+
+/// Subject of the request's token, provided it grants `scope`
+fn authorize(request: &Request, tokens: &impl TokenValidator, scope: &str) -> Result<Pubkey> {
+    let grant = request.bearer_token().and_then(|token| tokens.validate(token)).ok_or_else(ServiceError::unauthorized)?;
+    if !grant.scopes.iter().any(|granted| granted == scope) {
+        return Err(ServiceError::forbidden(scope));
+    }
+    Ok(grant.subject)
+}
+
+fn usize_param(request: &Request, name: &str) -> Result<Option<usize>> {
+    request
+        .query_param(name)
+        .map(|value| value.parse().map_err(|_| ServiceError::invalid_query_parameter(format!("{} must be a non-negative integer", name))))
+        .transpose()
+}
+
+fn dispatch<S: AccountSource>(service: &Service<S>, tokens: &impl TokenValidator, request: &Request) -> Result<Response> {
+    let endpoint = request
+        .path
+        .strip_prefix(BASE_PATH)
+        .ok_or_else(|| ServiceError::not_found(format!("No endpoint at {}", request.path)))?;
+    match (endpoint, request.method.as_str()) {
+        ("/discovery", "GET") => Ok(Response::json(&service.discovery())),
+        ("/credentials", "GET") => {
+            let recipient = authorize(request, tokens, scopes::CREDENTIAL_READONLY)?;
+            let limit = usize_param(request, "limit")?;
+            let offset = usize_param(request, "offset")?.unwrap_or(0);
+            let page = service.get_credentials(&recipient, limit, offset)?;
+            let mut response = Response::json(&page.to_json());
+            response.headers.push(("X-Total-Count".to_string(), page.total.to_string()));
+            Ok(response)
+        }
+        ("/credentials", "POST") => {
+            let recipient = authorize(request, tokens, scopes::CREDENTIAL_UPSERT)?;
+            if !request.header("content-type").is_some_and(|value| value.starts_with("application/json")) {
+                return Err(ServiceError::invalid_data("Only application/json credentials can be upserted"));
+            }
+            let document: serde_json::Value = serde_json::from_slice(&request.body)
+                .map_err(|_| ServiceError::invalid_data("The body is not a JSON credential"))?;
+            service.upsert_credential(&recipient, &document)?;
+            Ok(Response::empty(304))
+        }
+        ("/profile", "GET") => {
+            let authority = authorize(request, tokens, scopes::PROFILE_READONLY)?;
+            Ok(Response::json(&service.get_profile(&authority)?))
+        }
+        ("/discovery" | "/credentials" | "/profile", _) => Err(ServiceError::not_allowed()),
+        _ => Err(ServiceError::not_found(format!("No endpoint at {}", request.path))),
+    }
+}
+
+/// Answer one request
+pub fn route<S: AccountSource>(service: &Service<S>, tokens: &impl TokenValidator, request: &Request) -> Response {
+    dispatch(service, tokens, request).unwrap_or_else(|error| Response::error(&error))
+}
+
+/// Serve requests on `listener` until it fails, one connection at a time
+pub fn serve<S: AccountSource>(listener: TcpListener, service: &Service<S>, tokens: &impl TokenValidator) -> io::Result<()> {
+    for stream in listener.incoming() {
+        let mut stream = stream?;
+        let response = match parse_request(&mut BufReader::new(&mut stream)) {
+            Ok(request) => route(service, tokens, &request),
+            Err(error) if error.kind() == io::ErrorKind::OutOfMemory => Response::empty(413),
+            Err(_) => Response::error(&ServiceError::invalid_data("Malformed HTTP request")),
+        };
+        // A client that hung up does not stop the server
+        let _ = response.write_to(&mut stream);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::service::tests::service;
+
+    fn request(raw: &str) -> Request {
+        parse_request(&mut raw.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn test_parses_request_line_headers_and_body() {
+        let parsed = request("POST /ims/ob/v3p0/credentials?limit=2&name=a%20b HTTP/1.1\r\nAuthorization: Bearer abc\r\nContent-Length: 2\r\n\r\n{}");
+        assert_eq!(parsed.method, "POST");
+        assert_eq!(parsed.path, "/ims/ob/v3p0/credentials");
+        assert_eq!(parsed.query_param("name"), Some("a b"));
+        assert_eq!(parsed.bearer_token(), Some("abc"));
+        assert_eq!(parsed.body, b"{}");
+        assert_eq!(percent_decode("100%"), "100%");
+    }
+
+    #[test]
+    fn test_endpoints_enforce_their_scopes() {
+        let recipient = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let service = service(&recipient, &authority, 2);
+        let grant = |subject, scope: &str| Grant { subject, scopes: vec![scope.to_string()] };
+        let tokens = StaticTokens(HashMap::from([
+            ("reader".to_string(), grant(recipient, scopes::CREDENTIAL_READONLY)),
+            ("issuer".to_string(), grant(authority, scopes::PROFILE_READONLY)),
+        ]));
+        let get = |path: &str, token: &str| {
+            route(&service, &tokens, &request(&format!("GET {} HTTP/1.1\r\nAuthorization: Bearer {}\r\n\r\n", path, token)))
+        };
+
+        let credentials = get("/ims/ob/v3p0/credentials?limit=1", "reader");
+        assert_eq!(credentials.status, 200);
+        assert!(credentials.headers.contains(&("X-Total-Count".to_string(), "2".to_string())));
+        assert_eq!(get("/ims/ob/v3p0/credentials", "nobody").status, 401);
+        assert_eq!(get("/ims/ob/v3p0/credentials", "issuer").status, 403);
+        assert_eq!(get("/ims/ob/v3p0/credentials?limit=x", "reader").status, 400);
+        assert_eq!(get("/ims/ob/v3p0/profile", "issuer").status, 200);
+        assert_eq!(get("/ims/ob/v3p0/discovery", "").status, 200);
+        assert_eq!(get("/ims/ob/v3p0/unknown", "reader").status, 404);
+
+        let denied: serde_json::Value = serde_json::from_slice(&get("/ims/ob/v3p0/profile", "reader").body).unwrap();
+        assert_eq!(denied["imsx_codeMinor"]["imsx_codeMinorField"][0]["imsx_codeMinorFieldValue"], "forbidden");
+        let delete = request("DELETE /ims/ob/v3p0/profile HTTP/1.1\r\n\r\n");
+        assert_eq!(route(&service, &tokens, &delete).status, 405);
+    }
+}
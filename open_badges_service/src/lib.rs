@@ -0,0 +1,107 @@
+//! 1EdTech Open Badges API 3.0 service backed by on-chain accounts
+//!
+//! The Open Badges API lets wallets and learner record platforms exchange
+//! credentials over REST: `getCredentials`, `upsertCredential`, `getProfile`
+//! and a public service description document advertising the OAuth 2.0
+//! scopes each endpoint needs. This crate serves those endpoints from the
+//! program's accounts, read over Solana JSON-RPC, so a deployment can run
+//! the 1EdTech API conformance suite as well as the document checks.
+//!
+//! The program is the system of record. Credentials are issued by the
+//! issuer's own transactions; `upsertCredential` recognizes a credential
+//! already stored on-chain and refuses anything else. Access tokens come
+//! from the deployer's OAuth 2.0 authorization server and are checked
+//! through a [`TokenValidator`](http::TokenValidator); the service only
+//! enforces the scopes a token grants.
+//!
+//! The HTTP transport is a small blocking HTTP/1.1 server on `std::net`,
+//! meant to sit behind a TLS-terminating reverse proxy.
+
+use std::fmt;
+
+pub mod discovery;
+pub mod http;
+pub mod rpc;
+pub mod scopes;
+pub mod service;
+
+pub use discovery::ServiceConfig;
+pub use rpc::{AccountSource, RpcClient};
+pub use service::Service;
+
+/// Why a request failed, as the API reports it in an `Imsx_StatusInfo`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ServiceError {
+    /// HTTP status code
+    pub status: u16,
+    /// `imsx_codeMinorFieldValue` naming the failure
+    pub code_minor: &'static str,
+    /// Human-readable `imsx_description`
+    pub description: String,
+}
+
+impl ServiceError {
+    fn new(status: u16, code_minor: &'static str, description: impl Into<String>) -> Self {
+        Self { status, code_minor, description: description.into() }
+    }
+
+    /// 400: the request body or a path segment is malformed
+    pub fn invalid_data(description: impl Into<String>) -> Self {
+        Self::new(400, "invalid_data", description)
+    }
+
+    /// 400: a query parameter is malformed
+    pub fn invalid_query_parameter(description: impl Into<String>) -> Self {
+        Self::new(400, "invalid_query_parameter", description)
+    }
+
+    /// 401: no valid access token
+    pub fn unauthorized() -> Self {
+        Self::new(401, "unauthorizedrequest", "A valid bearer access token is required")
+    }
+
+    /// 403: the token lacks the endpoint's scope
+    pub fn forbidden(scope: &str) -> Self {
+        Self::new(403, "forbidden", format!("The access token does not grant {}", scope))
+    }
+
+    /// 404: no such resource
+    pub fn not_found(description: impl Into<String>) -> Self {
+        Self::new(404, "not_found", description)
+    }
+
+    /// 405: the path exists but not for this method
+    pub fn not_allowed() -> Self {
+        Self::new(405, "not_allowed", "Method not allowed on this endpoint")
+    }
+
+    /// 500: the RPC node could not be reached or returned bad data
+    pub fn internal(description: impl Into<String>) -> Self {
+        Self::new(500, "internal_server_error", description)
+    }
+
+    /// `Imsx_StatusInfo` body of the error response
+    pub fn status_info(&self) -> serde_json::Value {
+        serde_json::json!({
+            "imsx_codeMajor": "failure",
+            "imsx_severity": "error",
+            "imsx_description": self.description,
+            "imsx_codeMinor": {
+                "imsx_codeMinorField": [{
+                    "imsx_codeMinorFieldName": "TargetEndSystem",
+                    "imsx_codeMinorFieldValue": self.code_minor,
+                }]
+            }
+        })
+    }
+}
+
+impl fmt::Display for ServiceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}: {}", self.status, self.code_minor, self.description)
+    }
+}
+
+impl std::error::Error for ServiceError {}
+
+pub type Result<T, E = ServiceError> = std::result::Result<T, E>;
@@ -0,0 +1,71 @@
+//! Open Badges API 3.0 server
+//!
+//! Configured from the environment:
+//!
+//! - `OB_API_BIND`: listen address, default `127.0.0.1:8080`
+//! - `OB_API_RPC_URL`: Solana RPC endpoint, default `http://127.0.0.1:8899`
+//! - `OB_API_BASE_URL`: public URL of the service, default `http://<OB_API_BIND>`
+//! - `OB_API_AUTH_URL`: OAuth 2.0 authorization server base URL, serving
+//!   `/register`, `/authorize` and `/token`
+//! - `OB_API_TOKENS`: development tokens, one `token subject scope...` per line
+
+use std::collections::HashMap;
+use std::env;
+use std::net::TcpListener;
+use std::process::ExitCode;
+use std::str::FromStr;
+
+use anchor_lang::prelude::Pubkey;
+use open_badges_service::http::{self, Grant, StaticTokens};
+use open_badges_service::{RpcClient, Service, ServiceConfig};
+
+fn var(name: &str, default: &str) -> String {
+    env::var(name).unwrap_or_else(|_| default.to_string())
+}
+
+fn static_tokens(spec: &str) -> Result<StaticTokens, String> {
+    let mut tokens = HashMap::new();
+    for line in spec.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        let mut fields = line.split_whitespace();
+        let (Some(token), Some(subject)) = (fields.next(), fields.next()) else {
+            return Err(format!("OB_API_TOKENS line {:?} needs a token and a subject", line));
+        };
+        let subject = Pubkey::from_str(subject).map_err(|_| format!("{} is not a public key", subject))?;
+        tokens.insert(token.to_string(), Grant { subject, scopes: fields.map(str::to_string).collect() });
+    }
+    Ok(StaticTokens(tokens))
+}
+
+fn run() -> Result<(), String> {
+    let bind = var("OB_API_BIND", "127.0.0.1:8080");
+    let base_url = var("OB_API_BASE_URL", &format!("http://{}", bind));
+    let auth_url = var("OB_API_AUTH_URL", &base_url);
+    let config = ServiceConfig {
+        title: "Open Badges on Solana".to_string(),
+        terms_of_service_url: format!("{}/terms", base_url),
+        privacy_policy_url: format!("{}/privacy", base_url),
+        image_url: None,
+        registration_url: format!("{}/register", auth_url),
+        authorization_url: format!("{}/authorize", auth_url),
+        token_url: format!("{}/token", auth_url),
+        base_url,
+    };
+
+    let rpc = RpcClient::new(&var("OB_API_RPC_URL", "http://127.0.0.1:8899")).map_err(|error| error.to_string())?;
+    let tokens = static_tokens(&var("OB_API_TOKENS", ""))?;
+    let listener = TcpListener::bind(&bind).map_err(|error| error.to_string())?;
+
+    println!("Open Badges API 3.0 listening on {}", bind);
+    let service = Service::new(rpc, open_badges::ID, config);
+    http::serve(listener, &service, &tokens).map_err(|error| error.to_string())
+}
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("open-badges-service: {}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,125 @@
+//! Account Reads over Solana JSON-RPC
+//!
+//! The service only reads accounts at known addresses, so `getAccountInfo`
+//! is all it needs. [`AccountSource`] abstracts that read so the endpoints
+//! can be served from an indexer or a cache as well as a node, and tested
+//! without one.
+//!
+//! [`RpcClient`] speaks plain HTTP/1.0 to the node, one connection per
+//! call. Point it at a local validator or an RPC proxy; it does no TLS.
+
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::time::Duration;
+
+use anchor_lang::prelude::Pubkey;
+use base64::Engine;
+
+use crate::{Result, ServiceError};
+
+/// Source of raw account data
+pub trait AccountSource {
+    /// Data of the account at `address`, or `None` when it does not exist
+    fn get_account(&self, address: &Pubkey) -> Result<Option<Vec<u8>>>;
+}
+
+/// JSON-RPC client for a Solana node reached over plain HTTP
+#[derive(Clone, Debug)]
+pub struct RpcClient {
+    host: String,
+    port: u16,
+    path: String,
+    timeout: Duration,
+}
+
+impl RpcClient {
+    /// Client for an `http://host[:port][/path]` RPC URL
+    pub fn new(url: &str) -> Result<Self> {
+        let rest = url
+            .strip_prefix("http://")
+            .ok_or_else(|| ServiceError::internal(format!("RPC URL {} must use http://", url)))?;
+        let (authority, path) = match rest.find('/') {
+            Some(index) => (&rest[..index], &rest[index..]),
+            None => (rest, "/"),
+        };
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) => {
+                let port = port.parse().map_err(|_| ServiceError::internal(format!("Bad RPC port in {}", url)))?;
+                (host, port)
+            }
+            None => (authority, 80),
+        };
+        Ok(Self { host: host.to_string(), port, path: path.to_string(), timeout: Duration::from_secs(10) })
+    }
+
+    fn call(&self, method: &str, params: serde_json::Value) -> Result<serde_json::Value> {
+        let unreachable = |error: std::io::Error| ServiceError::internal(format!("RPC node unreachable: {}", error));
+        let body = serde_json::json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params }).to_string();
+
+        let mut stream = TcpStream::connect((self.host.as_str(), self.port)).map_err(unreachable)?;
+        stream.set_read_timeout(Some(self.timeout)).map_err(unreachable)?;
+        write!(
+            stream,
+            "POST {} HTTP/1.0\r\nHost: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+            self.path,
+            self.host,
+            body.len(),
+            body
+        )
+        .map_err(unreachable)?;
+        let mut response = vec![];
+        stream.read_to_end(&mut response).map_err(unreachable)?;
+
+        let body_start = response
+            .windows(4)
+            .position(|window| window == b"\r\n\r\n")
+            .ok_or_else(|| ServiceError::internal("Malformed RPC response"))?;
+        let reply: serde_json::Value = serde_json::from_slice(&response[body_start + 4..])
+            .map_err(|_| ServiceError::internal("RPC response is not JSON"))?;
+        if let Some(error) = reply.get("error") {
+            return Err(ServiceError::internal(format!("RPC error: {}", error)));
+        }
+        Ok(reply["result"].clone())
+    }
+}
+
+impl AccountSource for RpcClient {
+    fn get_account(&self, address: &Pubkey) -> Result<Option<Vec<u8>>> {
+        let result = self.call("getAccountInfo", serde_json::json!([address.to_string(), { "encoding": "base64" }]))?;
+        decode_account_info(&result)
+    }
+}
+
+/// Account data in a `getAccountInfo` result
+fn decode_account_info(result: &serde_json::Value) -> Result<Option<Vec<u8>>> {
+    let value = &result["value"];
+    if value.is_null() {
+        return Ok(None);
+    }
+    let encoded = value["data"][0]
+        .as_str()
+        .ok_or_else(|| ServiceError::internal("getAccountInfo returned no base64 data"))?;
+    base64::engine::general_purpose::STANDARD
+        .decode(encoded)
+        .map(Some)
+        .map_err(|_| ServiceError::internal("getAccountInfo returned invalid base64"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_urls_and_account_info() {
+        let client = RpcClient::new("http://127.0.0.1:8899").unwrap();
+        assert_eq!((client.host.as_str(), client.port, client.path.as_str()), ("127.0.0.1", 8899, "/"));
+        let client = RpcClient::new("http://rpc.internal/solana").unwrap();
+        assert_eq!((client.port, client.path.as_str()), (80, "/solana"));
+        assert!(RpcClient::new("https://api.mainnet-beta.solana.com").is_err());
+
+        let found = serde_json::json!({ "context": { "slot": 1 }, "value": { "data": ["AQID", "base64"] } });
+        assert_eq!(decode_account_info(&found).unwrap(), Some(vec![1, 2, 3]));
+        let missing = serde_json::json!({ "context": { "slot": 1 }, "value": null });
+        assert_eq!(decode_account_info(&missing).unwrap(), None);
+    }
+}
@@ -0,0 +1,21 @@
+//! OAuth 2.0 scopes of the Open Badges API 3.0
+
+/// Read the authenticated recipient's credentials (`getCredentials`)
+pub const CREDENTIAL_READONLY: &str = "https://purl.imsglobal.org/spec/ob/v3p0/scope/credential.readonly";
+
+/// Store credentials for the authenticated recipient (`upsertCredential`)
+pub const CREDENTIAL_UPSERT: &str = "https://purl.imsglobal.org/spec/ob/v3p0/scope/credential.upsert";
+
+/// Read the authenticated issuer's profile (`getProfile`)
+pub const PROFILE_READONLY: &str = "https://purl.imsglobal.org/spec/ob/v3p0/scope/profile.readonly";
+
+/// Update the authenticated issuer's profile (`putProfile`)
+pub const PROFILE_UPDATE: &str = "https://purl.imsglobal.org/spec/ob/v3p0/scope/profile.update";
+
+/// Every scope with its description, as listed in the discovery document
+pub const ALL: [(&str, &str); 4] = [
+    (CREDENTIAL_READONLY, "Permission to read AchievementCredentials for the authenticated entity."),
+    (CREDENTIAL_UPSERT, "Permission to create or update AchievementCredentials for the authenticated entity."),
+    (PROFILE_READONLY, "Permission to read the profile for the authenticated entity."),
+    (PROFILE_UPDATE, "Permission to update the profile for the authenticated entity."),
+];
@@ -0,0 +1,224 @@
+//! Open Badges API Endpoints
+//!
+//! Each endpoint resolves the authenticated entity's accounts from their
+//! PDAs: a recipient's credentials through their `CredentialIndex` pages, an
+//! issuer's profile through its issuer PDA. Documents are rendered with the
+//! program's own functions, so they match the program's JSON views.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::AccountDeserialize;
+use open_badges::credential_index::{self, CredentialIndex, CredentialIndexPage, CREDENTIAL_INDEX_SEED};
+use open_badges_verify::{credential_document, decode_credential, decode_profile};
+
+use crate::discovery::{self, ServiceConfig};
+use crate::rpc::AccountSource;
+use crate::{Result, ServiceError};
+
+/// One page of `getCredentials`
+#[derive(Clone, Debug, PartialEq)]
+pub struct CredentialsPage {
+    /// Credential documents on this page, oldest first
+    pub credentials: Vec<serde_json::Value>,
+    /// Credentials issued to the recipient in total, for `X-Total-Count`
+    pub total: usize,
+}
+
+impl CredentialsPage {
+    /// `GetOpenBadgeCredentialsResponse` body
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({ "credential": self.credentials, "compactJwsString": [] })
+    }
+}
+
+/// Open Badges API over the program's accounts
+pub struct Service<S> {
+    source: S,
+    program_id: Pubkey,
+    config: ServiceConfig,
+}
+
+impl<S: AccountSource> Service<S> {
+    /// Service reading accounts of the program at `program_id` from `source`
+    pub fn new(source: S, program_id: Pubkey, config: ServiceConfig) -> Self {
+        Self { source, program_id, config }
+    }
+
+    fn credential_keys(&self, recipient: &Pubkey) -> Result<Vec<Pubkey>> {
+        let (address, _) = Pubkey::find_program_address(&[CREDENTIAL_INDEX_SEED, recipient.as_ref()], &self.program_id);
+        let Some(data) = self.source.get_account(&address)? else {
+            return Ok(vec![]);
+        };
+        let index = CredentialIndex::try_deserialize(&mut &data[..])
+            .map_err(|_| ServiceError::internal(format!("{} is not a credential index", address)))?;
+        
+        let mut keys = vec![];
+        for page in 0..index.page_count() {
+            let (address, _) = credential_index::find_index_page_address(recipient, page, &self.program_id);
+            let data = self
+                .source
+                .get_account(&address)?
+                .ok_or_else(|| ServiceError::internal(format!("Credential index page {} is missing", address)))?;
+            let page = CredentialIndexPage::try_deserialize(&mut &data[..])
+                .map_err(|_| ServiceError::internal(format!("{} is not a credential index page", address)))?;
+            keys.extend(page.credentials);
+        }
+        Ok(keys)
+    }
+
+    fn credential_json(&self, address: &Pubkey) -> Result<serde_json::Value> {
+        let data = self
+            .source
+            .get_account(address)?
+            .ok_or_else(|| ServiceError::internal(format!("Indexed credential {} is missing", address)))?;
+        let credential = decode_credential(&data).map_err(|error| ServiceError::internal(error.to_string()))?;
+        Ok(credential_document(&credential))
+    }
+
+    /// `getCredentials`: the recipient's credentials, `limit` at most from `offset`
+    pub fn get_credentials(&self, recipient: &Pubkey, limit: Option<usize>, offset: usize) -> Result<CredentialsPage> {
+        if limit == Some(0) {
+            return Err(ServiceError::invalid_query_parameter("limit must be at least 1"));
+        }
+        let keys = self.credential_keys(recipient)?;
+        let credentials = keys
+            .iter()
+            .skip(offset)
+            .take(limit.unwrap_or(usize::MAX))
+            .map(|address| self.credential_json(address))
+            .collect::<Result<_>>()?;
+        Ok(CredentialsPage { credentials, total: keys.len() })
+    }
+
+    /// `upsertCredential`: succeeds when `document` is already stored on-chain for the recipient
+    ///
+    /// The program is the only store, and only issuers write to it, so a
+    /// success is always Not Modified. A different document under a stored
+    /// id, or an id the recipient does not hold, is refused.
+    pub fn upsert_credential(&self, recipient: &Pubkey, document: &serde_json::Value) -> Result<()> {
+        let id = document["id"]
+            .as_str()
+            .ok_or_else(|| ServiceError::invalid_data("The credential has no id"))?;
+        for address in self.credential_keys(recipient)? {
+            let stored = self.credential_json(&address)?;
+            if stored["id"] != id {
+                continue;
+            }
+            if stored != *document {
+                return Err(ServiceError::invalid_data(format!(
+                    "Credential {} is stored on-chain and cannot be replaced",
+                    id
+                )));
+            }
+            return Ok(());
+        }
+        Err(ServiceError::not_found(format!(
+            "Credential {} is not issued to this recipient on-chain; issuers issue credentials with the program",
+            id
+        )))
+    }
+
+    /// `getProfile`: Profile document of the issuer whose authority is `authority`
+    pub fn get_profile(&self, authority: &Pubkey) -> Result<serde_json::Value> {
+        let (address, _) = open_badges::pda::find_issuer_pda(authority, &self.program_id);
+        let data = self
+            .source
+            .get_account(&address)?
+            .ok_or_else(|| ServiceError::not_found("No issuer profile for the authenticated entity"))?;
+        let profile = decode_profile(&data).map_err(|error| ServiceError::internal(error.to_string()))?;
+        Ok(open_badges::credential::profile_json(&profile))
+    }
+
+    /// `getServiceDescription`: the discovery document
+    pub fn discovery(&self) -> serde_json::Value {
+        discovery::document(&self.config)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use open_badges::test_utils::{account_data, fixture_credential, fixture_profile};
+    use std::collections::HashMap;
+
+    /// Accounts held in memory
+    #[derive(Default)]
+    pub struct MemorySource(pub HashMap<Pubkey, Vec<u8>>);
+
+    impl AccountSource for MemorySource {
+        fn get_account(&self, address: &Pubkey) -> Result<Option<Vec<u8>>> {
+            Ok(self.0.get(address).cloned())
+        }
+    }
+
+    pub fn config() -> ServiceConfig {
+        ServiceConfig {
+            base_url: "https://badges.example".to_string(),
+            title: "Example Badges".to_string(),
+            terms_of_service_url: "https://badges.example/terms".to_string(),
+            privacy_policy_url: "https://badges.example/privacy".to_string(),
+            image_url: None,
+            registration_url: "https://auth.example/register".to_string(),
+            authorization_url: "https://auth.example/authorize".to_string(),
+            token_url: "https://auth.example/token".to_string(),
+        }
+    }
+
+    /// Service holding `count` credentials for `recipient` and a profile for `authority`
+    pub fn service(recipient: &Pubkey, authority: &Pubkey, count: usize) -> Service<MemorySource> {
+        let program_id = open_badges::ID;
+        let mut source = MemorySource::default();
+        let (issuer, _) = open_badges::pda::find_issuer_pda(authority, &program_id);
+        source.0.insert(issuer, account_data(&fixture_profile(&issuer, authority, "Issuer")));
+
+        let mut credentials = vec![];
+        for _ in 0..count {
+            let address = Pubkey::new_unique();
+            let credential = fixture_credential(&address, &issuer, &Pubkey::new_unique(), recipient);
+            source.0.insert(address, account_data(&credential));
+            credentials.push(address);
+        }
+        let (index, bump) = Pubkey::find_program_address(&[CREDENTIAL_INDEX_SEED, recipient.as_ref()], &program_id);
+        source.0.insert(index, account_data(&CredentialIndex { recipient: *recipient, total: count as u32, bump }));
+        for (page, credentials) in (0u32..).zip(credentials.chunks(credential_index::MAX_PAGE_SIZE as usize)) {
+            let (address, bump) = credential_index::find_index_page_address(recipient, page, &program_id);
+            let page = CredentialIndexPage { recipient: *recipient, page, credentials: credentials.to_vec(), bump };
+            source.0.insert(address, account_data(&page));
+        }
+        Service::new(source, program_id, config())
+    }
+
+    #[test]
+    fn test_credentials_are_paged_from_the_index() {
+        let recipient = Pubkey::new_unique();
+        let beyond_one_page = credential_index::MAX_PAGE_SIZE as usize + 1;
+        let paged = service(&recipient, &Pubkey::new_unique(), beyond_one_page);
+        assert_eq!(paged.get_credentials(&recipient, None, 0).unwrap().credentials.len(), beyond_one_page);
+        let service = service(&recipient, &Pubkey::new_unique(), 3);
+
+        let page = service.get_credentials(&recipient, Some(2), 1).unwrap();
+        assert_eq!((page.credentials.len(), page.total), (2, 3));
+        assert_eq!(page.to_json()["credential"][0]["type"][1], "OpenBadgeCredential");
+        assert!(service.get_credentials(&recipient, Some(0), 0).is_err());
+        assert_eq!(service.get_credentials(&Pubkey::new_unique(), None, 0).unwrap().total, 0);
+    }
+
+    #[test]
+    fn test_upsert_only_recognizes_stored_credentials() {
+        let recipient = Pubkey::new_unique();
+        let service = service(&recipient, &Pubkey::new_unique(), 1);
+        let mut document = service.get_credentials(&recipient, None, 0).unwrap().credentials.remove(0);
+        assert_eq!(service.upsert_credential(&recipient, &document), Ok(()));
+
+        assert_eq!(service.upsert_credential(&Pubkey::new_unique(), &document).unwrap_err().status, 404);
+        document["name"] = serde_json::json!("Altered");
+        assert_eq!(service.upsert_credential(&recipient, &document).unwrap_err().status, 400);
+    }
+
+    #[test]
+    fn test_profile_of_the_authenticated_issuer() {
+        let authority = Pubkey::new_unique();
+        let service = service(&Pubkey::new_unique(), &authority, 0);
+        assert_eq!(service.get_profile(&authority).unwrap()["name"], "Issuer");
+        assert_eq!(service.get_profile(&Pubkey::new_unique()).unwrap_err().status, 404);
+    }
+}
@@ -0,0 +1,473 @@
+//! Off-chain verification for Open Badges credentials
+//!
+//! Backends that already hold account data (from `getAccountInfo`, a
+//! Geyser stream or an indexer) can verify credentials here without
+//! simulating the program's view instructions. Accounts are deserialized with
+//! the program crate's own types, the signed credential JSON is rebuilt with
+//! the program's own functions, and proofs and JWTs are checked with
+//! ed25519-dalek, so the result matches what the program would accept.
+//!
+//! A proof naming the issuer DID without a `#key-N` fragment was signed by
+//! the issuer profile's authority; one naming `#key-N` was signed by the
+//! issuer's `IssuerKey` with that index, which must have been active when
+//! the proof was created.
+//!
+//! Holders of a credential with an encrypted payload derive its content key
+//! with `payload_key` and open the ciphertext with any XChaCha20-Poly1305
+//! implementation, then reveal the body or part of it with
+//! `select_disclosures`.
+
+use std::fmt;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::AccountDeserialize;
+use curve25519_dalek::montgomery::MontgomeryPoint;
+use ed25519_dalek::{Signature, VerifyingKey};
+use hkdf::Hkdf;
+use sha2::Sha256;
+
+pub use open_badges::encrypted_payload::{Disclosure, EncryptedPayload};
+pub use open_badges::issuer_keys::IssuerKey;
+pub use open_badges::proof_set::ProofPolicy;
+pub use open_badges::{Achievement, AchievementCredential, Profile, Proof};
+
+use open_badges::{credential, encrypted_payload, issuer_keys, proof_set};
+
+/// Why account data or a credential could not be checked
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VerifyError {
+    /// Account data is not an account of the named type
+    InvalidAccount(&'static str),
+    /// The issuer keys supplied are not those of the credential's issuer
+    IssuerMismatch,
+    /// A proof names a `#key-N` verification method that was not supplied
+    MissingKey(String),
+    /// A date on the credential or a proof is not ISO 8601
+    InvalidTimestamp(String),
+    /// Not a compact JWS with a 64-byte signature
+    InvalidJwt,
+    /// The X25519 secret is not the one the payload was encrypted to
+    RecipientKeyMismatch,
+    /// A decrypted body is not a JSON array of disclosures
+    InvalidPayload,
+}
+
+impl fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidAccount(kind) => write!(f, "account data is not a valid {} account", kind),
+            Self::IssuerMismatch => write!(f, "issuer keys do not belong to the credential's issuer"),
+            Self::MissingKey(method) => write!(f, "no key supplied for verification method {}", method),
+            Self::InvalidTimestamp(value) => write!(f, "{} is not an ISO 8601 date", value),
+            Self::InvalidJwt => write!(f, "malformed JWT"),
+            Self::RecipientKeyMismatch => write!(f, "payload is not encrypted to this key"),
+            Self::InvalidPayload => write!(f, "payload body is not a list of disclosures"),
+        }
+    }
+}
+
+impl std::error::Error for VerifyError {}
+
+pub type Result<T, E = VerifyError> = std::result::Result<T, E>;
+
+fn decode<T: AccountDeserialize>(data: &[u8], kind: &'static str) -> Result<T> {
+    T::try_deserialize(&mut &data[..]).map_err(|_| VerifyError::InvalidAccount(kind))
+}
+
+/// Deserialize an `AchievementCredential` account, discriminator included
+pub fn decode_credential(data: &[u8]) -> Result<AchievementCredential> {
+    decode(data, "AchievementCredential")
+}
+
+/// Deserialize an `Achievement` account, discriminator included
+pub fn decode_achievement(data: &[u8]) -> Result<Achievement> {
+    decode(data, "Achievement")
+}
+
+/// Deserialize a `Profile` account, discriminator included
+pub fn decode_profile(data: &[u8]) -> Result<Profile> {
+    decode(data, "Profile")
+}
+
+/// Deserialize an `IssuerKey` account, discriminator included
+pub fn decode_issuer_key(data: &[u8]) -> Result<IssuerKey> {
+    decode(data, "IssuerKey")
+}
+
+/// Credential document as the program presents it, proofs included
+pub fn credential_document(credential: &AchievementCredential) -> serde_json::Value {
+    credential::credential_account_to_json(credential)
+}
+
+/// Credential JSON the issuer's proof signs
+pub fn signing_json(credential: &AchievementCredential) -> String {
+    open_badges::credential_signing_json(credential)
+}
+
+/// Keys an issuer can sign credential proofs with
+#[derive(Clone, Copy)]
+pub struct IssuerKeys<'a> {
+    /// Issuer profile address
+    pub issuer: Pubkey,
+    /// Issuer profile, whose authority signs proofs without a key fragment
+    pub profile: &'a Profile,
+    /// The issuer's registered verification methods
+    pub keys: &'a [IssuerKey],
+}
+
+impl IssuerKeys<'_> {
+    /// Key that signed `proof`, or None when the proof cannot be attributed to the issuer
+    fn signer_of(&self, proof: &Proof) -> Result<Option<Pubkey>> {
+        let issuer_did = format!("did:sol:{}", self.issuer);
+        let fragment = issuer_keys::parse_key_fragment(&proof.verification_method)
+            .map_err(|_| VerifyError::MissingKey(proof.verification_method.clone()))?;
+        let Some((did, index)) = fragment else {
+            return Ok((proof.verification_method == issuer_did).then_some(self.profile.authority));
+        };
+        if did != issuer_did {
+            return Ok(None);
+        }
+        let key = self
+            .keys
+            .iter()
+            .find(|key| key.issuer == self.issuer && key.key_index == index)
+            .ok_or_else(|| VerifyError::MissingKey(proof.verification_method.clone()))?;
+        let active = key
+            .is_valid_for_proof_at(&proof.created)
+            .map_err(|_| VerifyError::InvalidTimestamp(proof.created.clone()))?;
+        Ok(active.then_some(key.public_key))
+    }
+}
+
+/// Check an Ed25519 signature
+pub fn verify_ed25519(public_key: &Pubkey, message: &[u8], signature: &[u8; 64]) -> bool {
+    VerifyingKey::from_bytes(&public_key.to_bytes())
+        .map(|key| key.verify_strict(message, &Signature::from_bytes(signature)).is_ok())
+        .unwrap_or(false)
+}
+
+fn decode_proof_value(proof_value: &str) -> Option<[u8; 64]> {
+    let encoded = proof_value.strip_prefix('z')?;
+    bs58::decode(encoded).into_vec().ok()?.try_into().ok()
+}
+
+/// Verify the signature of the credential's proof at `index`
+///
+/// A chained proof also covers the proof its `previousProof` names, which
+/// must come earlier in the set.
+pub fn verify_proof(credential: &AchievementCredential, index: usize, keys: &IssuerKeys) -> Result<bool> {
+    let Some(proof) = credential.proof.get(index) else {
+        return Ok(false);
+    };
+    if proof.proof_type != "DataIntegrityProof" {
+        return Ok(false);
+    }
+    let Some(signer) = keys.signer_of(proof)? else {
+        return Ok(false);
+    };
+    let previous = match &proof.previous_proof {
+        None => None,
+        Some(previous_id) => {
+            let position = (0..index).find(|&i| proof_set::proof_id(&credential.id, i) == *previous_id);
+            match position {
+                Some(position) => Some(&credential.proof[position]),
+                None => return Ok(false),
+            }
+        }
+    };
+    let Some(signature) = decode_proof_value(&proof.proof_value) else {
+        return Ok(false);
+    };
+    let message = proof_set::signing_message(&signing_json(credential), previous);
+    Ok(verify_ed25519(&signer, &message, &signature))
+}
+
+/// Outcome of verifying a credential
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Verification {
+    /// Whether the proofs satisfy the policy
+    pub proofs_valid: bool,
+    /// Revoked by its issuer
+    pub revoked: bool,
+    /// Suspended by its issuer
+    pub suspended: bool,
+    /// Before its `validFrom` date
+    pub not_yet_valid: bool,
+    /// Past its `validUntil` date
+    pub expired: bool,
+    /// Newer version its issuer superseded it with
+    pub superseded_by: Option<Pubkey>,
+}
+
+impl Verification {
+    /// Whether the credential is valid overall
+    pub fn is_valid(&self) -> bool {
+        self.proofs_valid
+            && !self.revoked
+            && !self.suspended
+            && !self.not_yet_valid
+            && !self.expired
+            && self.superseded_by.is_none()
+    }
+}
+
+fn unix_time(value: &str) -> Result<i64> {
+    open_badges::parse_iso8601_to_unix(value).map_err(|_| VerifyError::InvalidTimestamp(value.to_string()))
+}
+
+/// Verify a credential's proofs under `policy` and its status at `now` (Unix seconds)
+pub fn verify_credential(
+    credential: &AchievementCredential,
+    keys: &IssuerKeys,
+    policy: ProofPolicy,
+    now: i64,
+) -> Result<Verification> {
+    if keys.issuer != credential.issuer {
+        return Err(VerifyError::IssuerMismatch);
+    }
+    let proofs_valid = match policy {
+        _ if credential.proof.is_empty() => false,
+        ProofPolicy::All => {
+            let mut all = true;
+            for index in 0..credential.proof.len() {
+                all &= verify_proof(credential, index, keys)?;
+            }
+            all
+        }
+        ProofPolicy::Any => (0..credential.proof.len()).any(|index| verify_proof(credential, index, keys).unwrap_or(false)),
+    };
+    let expired = match &credential.valid_until {
+        Some(valid_until) => now > unix_time(valid_until)?,
+        None => false,
+    };
+    Ok(Verification {
+        proofs_valid,
+        revoked: credential.is_revoked,
+        suspended: credential.status == open_badges::lifecycle::CredentialState::Suspended,
+        not_yet_valid: unix_time(&credential.valid_from)? > now,
+        expired,
+        superseded_by: credential.superseded_by,
+    })
+}
+
+/// Verify an EdDSA JWT credential at `now` (Unix seconds)
+///
+/// The `kid` must resolve to an Ed25519 key (did:sol or did:key) and be
+/// trusted for `iss`: its own DID, or the authority of the issuer profile
+/// given in `issuer`. ES256K JWTs need the secp256k1 program and are rejected.
+pub fn verify_jwt(credential_jwt: &str, issuer: Option<(Pubkey, &Profile)>, now: i64) -> Result<bool> {
+    let jwt = credential::decode_jwt(credential_jwt).map_err(|_| VerifyError::InvalidJwt)?;
+    if jwt.header.get("alg").and_then(|alg| alg.as_str()) != Some("EdDSA") {
+        return Ok(false);
+    }
+    if !credential::jwt_time_valid(&jwt.payload, now) {
+        return Ok(false);
+    }
+    let Some(iss) = jwt.payload.get("iss").and_then(|iss| iss.as_str()) else {
+        return Ok(false);
+    };
+    let kid = jwt.header.get("kid").and_then(|kid| kid.as_str()).unwrap_or(iss);
+    let Ok(signer) = credential::resolve_jwt_signer(kid) else {
+        return Ok(false);
+    };
+    if !credential::jwt_issuer_trusted(iss, kid, &signer, issuer, None) {
+        return Ok(false);
+    }
+    Ok(verify_ed25519(&signer, jwt.signing_input.as_bytes(), &jwt.signature))
+}
+
+/// X25519 public key of `secret`, for the issuer to encrypt payloads to
+pub fn payload_recipient_key(secret: &[u8; 32]) -> [u8; 32] {
+    MontgomeryPoint::mul_base_clamped(*secret).to_bytes()
+}
+
+fn content_key(ephemeral_key: &[u8; 32], shared: MontgomeryPoint) -> Result<[u8; 32]> {
+    // An all-zero shared secret means a low-order point was supplied
+    if shared.to_bytes() == [0; 32] {
+        return Err(VerifyError::RecipientKeyMismatch);
+    }
+    let mut key = [0; 32];
+    Hkdf::<Sha256>::new(Some(ephemeral_key), shared.as_bytes())
+        .expand(encrypted_payload::KDF_INFO, &mut key)
+        .map_err(|_| VerifyError::RecipientKeyMismatch)?;
+    Ok(key)
+}
+
+/// Issuer side of the key agreement: the ephemeral public key and the content key to seal a body to `recipient_key`
+/// `ephemeral_secret` must be fresh random bytes for every payload
+pub fn payload_sender_key(recipient_key: &[u8; 32], ephemeral_secret: &[u8; 32]) -> Result<([u8; 32], [u8; 32])> {
+    let ephemeral_key = payload_recipient_key(ephemeral_secret);
+    let shared = MontgomeryPoint(*recipient_key).mul_clamped(*ephemeral_secret);
+    Ok((ephemeral_key, content_key(&ephemeral_key, shared)?))
+}
+
+/// XChaCha20-Poly1305 key of `payload` for the recipient holding `secret`
+///
+/// Open `payload.ciphertext` with this key, `payload.nonce` and
+/// `payload_associated_data` as associated data.
+pub fn payload_key(payload: &EncryptedPayload, secret: &[u8; 32]) -> Result<[u8; 32]> {
+    if payload_recipient_key(secret) != payload.recipient_key {
+        return Err(VerifyError::RecipientKeyMismatch);
+    }
+    let shared = MontgomeryPoint(payload.ephemeral_key).mul_clamped(*secret);
+    content_key(&payload.ephemeral_key, shared)
+}
+
+/// Associated data a credential's payload is sealed with: the credential id
+pub fn payload_associated_data(credential: &AchievementCredential) -> &[u8] {
+    credential.id.as_bytes()
+}
+
+/// The disclosures of a decrypted body at `paths`, for a selective reveal to `verify_revealed_payload`
+/// Revealing the whole body instead shows the verifier it is complete
+pub fn select_disclosures(body: &str, paths: &[&str]) -> Result<String> {
+    let disclosures: Vec<Disclosure> = serde_json::from_str(body).map_err(|_| VerifyError::InvalidPayload)?;
+    let selected: Vec<&Disclosure> = disclosures.iter().filter(|disclosure| paths.contains(&disclosure.path.as_str())).collect();
+    serde_json::to_string(&selected).map_err(|_| VerifyError::InvalidPayload)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use open_badges::test_utils::{
+        account_data, fixture_achievement, fixture_credential, fixture_profile, sign_credential, TestKeypair,
+    };
+
+    struct Fixture {
+        issuer: Pubkey,
+        authority: TestKeypair,
+        profile: Profile,
+        credential: AchievementCredential,
+    }
+
+    fn fixture() -> Fixture {
+        let issuer = Pubkey::new_unique();
+        let authority = TestKeypair::from_seed(21);
+        let profile = fixture_profile(&issuer, &authority.pubkey(), "Issuer");
+        let mut credential =
+            fixture_credential(&Pubkey::new_unique(), &issuer, &Pubkey::new_unique(), &Pubkey::new_unique());
+        sign_credential(&mut credential, &authority);
+        Fixture { issuer, authority, profile, credential }
+    }
+
+    #[test]
+    fn test_decodes_accounts_and_verifies_issuer_proof() {
+        let fixture = fixture();
+        let credential = decode_credential(&account_data(&fixture.credential)).unwrap();
+        let profile = decode_profile(&account_data(&fixture.profile)).unwrap();
+        assert!(decode_achievement(&account_data(&fixture.profile)).is_err());
+
+        let keys = IssuerKeys { issuer: fixture.issuer, profile: &profile, keys: &[] };
+        let verification = verify_credential(&credential, &keys, ProofPolicy::All, 1_800_000_000).unwrap();
+        assert!(verification.is_valid());
+        assert_eq!(credential_document(&credential)["id"], credential.id);
+
+        let early = verify_credential(&credential, &keys, ProofPolicy::All, 0).unwrap();
+        assert!(early.not_yet_valid && early.proofs_valid);
+
+        let mut superseded = credential.clone();
+        superseded.superseded_by = Some(Pubkey::new_unique());
+        let verification = verify_credential(&superseded, &keys, ProofPolicy::All, 1_800_000_000).unwrap();
+        assert!(verification.proofs_valid && !verification.is_valid());
+
+        let other = IssuerKeys { issuer: Pubkey::new_unique(), ..keys };
+        assert_eq!(verify_credential(&credential, &other, ProofPolicy::All, 0), Err(VerifyError::IssuerMismatch));
+    }
+
+    #[test]
+    fn test_tampered_credential_fails() {
+        let mut fixture = fixture();
+        fixture.credential.valid_from = "2023-01-01T00:00:00Z".to_string();
+        let keys = IssuerKeys { issuer: fixture.issuer, profile: &fixture.profile, keys: &[] };
+        assert!(!verify_proof(&fixture.credential, 0, &keys).unwrap());
+    }
+
+    #[test]
+    fn test_chained_proof_by_issuer_key() {
+        let mut fixture = fixture();
+        let cosigner = TestKeypair::from_seed(22);
+        let key = IssuerKey {
+            issuer: fixture.issuer,
+            key_index: 1,
+            public_key: cosigner.pubkey(),
+            created_at: "2023-01-01T00:00:00Z".to_string(),
+            retired_at: None,
+            bump: 255,
+        };
+        let message = proof_set::signing_message(&signing_json(&fixture.credential), Some(&fixture.credential.proof[0]));
+        let chained = Proof {
+            verification_method: key.verification_method_id(),
+            proof_value: format!("z{}", bs58::encode(cosigner.sign(&message)).into_string()),
+            previous_proof: Some(proof_set::proof_id(&fixture.credential.id, 0)),
+            ..fixture.credential.proof[0].clone()
+        };
+        fixture.credential.proof.push(chained);
+
+        let keys = [key];
+        let with_key = IssuerKeys { issuer: fixture.issuer, profile: &fixture.profile, keys: &keys };
+        assert!(verify_proof(&fixture.credential, 1, &with_key).unwrap());
+        assert!(verify_credential(&fixture.credential, &with_key, ProofPolicy::All, 1_800_000_000).unwrap().is_valid());
+
+        let without_key = IssuerKeys { keys: &[], ..with_key };
+        assert!(matches!(verify_proof(&fixture.credential, 1, &without_key), Err(VerifyError::MissingKey(_))));
+        assert!(verify_credential(&fixture.credential, &without_key, ProofPolicy::Any, 1_800_000_000).unwrap().is_valid());
+    }
+
+    #[test]
+    fn test_jwt_signed_by_profile_authority() {
+        let fixture = fixture();
+        let achievement = fixture_achievement(&Pubkey::new_unique(), &fixture.issuer, "Rust");
+        let signing_input = credential::jwt_signing_input(
+            &fixture.issuer,
+            &fixture.authority.pubkey(),
+            None,
+            None,
+            &[],
+            &Pubkey::new_unique(),
+            "urn:example:rust",
+            &achievement,
+            "urn:uuid:1",
+            None,
+            1_700_000_000,
+            false,
+        )
+        .unwrap();
+        let jwt = credential::compact_jwt(&signing_input, &fixture.authority.sign(signing_input.as_bytes()));
+
+        assert!(verify_jwt(&jwt, Some((fixture.issuer, &fixture.profile)), 1_700_000_000).unwrap());
+        assert!(!verify_jwt(&jwt, None, 1_700_000_000).unwrap());
+        let forged = credential::compact_jwt(&signing_input, &TestKeypair::from_seed(23).sign(signing_input.as_bytes()));
+        assert!(!verify_jwt(&forged, Some((fixture.issuer, &fixture.profile)), 1_700_000_000).unwrap());
+        assert_eq!(verify_jwt("only.two", None, 0), Err(VerifyError::InvalidJwt));
+    }
+
+    #[test]
+    fn test_payload_key_agreement_and_selective_reveal() {
+        let recipient_secret = [5u8; 32];
+        let recipient_key = payload_recipient_key(&recipient_secret);
+        let (ephemeral_key, sender_key) = payload_sender_key(&recipient_key, &[9u8; 32]).unwrap();
+
+        let salt = open_badges::redaction::field_salt(&[1u8; 32], "/credentialSubject/narrative");
+        let value = serde_json::json!("Graded A");
+        let body = serde_json::to_string(&[Disclosure { path: "/credentialSubject/narrative".to_string(), salt: salt.clone(), value: value.clone() }]).unwrap();
+        let payload = EncryptedPayload {
+            recipient_key,
+            ephemeral_key,
+            nonce: [0; 24],
+            ciphertext: vec![0; body.len() + encrypted_payload::TAG_LEN],
+            payload_sha256: [0; 32],
+            fields: vec![encrypted_payload::FieldCommitment {
+                path: "/credentialSubject/narrative".to_string(),
+                digest: open_badges::redaction::field_digest(&salt, "/credentialSubject/narrative", &value).unwrap(),
+            }],
+        };
+        assert_eq!(payload_key(&payload, &recipient_secret).unwrap(), sender_key);
+        assert_eq!(payload_key(&payload, &[6u8; 32]), Err(VerifyError::RecipientKeyMismatch));
+
+        let revealed = select_disclosures(&body, &["/credentialSubject/narrative"]).unwrap();
+        let reveal = encrypted_payload::verify_reveal(&payload, &revealed).unwrap().unwrap();
+        assert_eq!(reveal.verified, vec!["/credentialSubject/narrative".to_string()]);
+        assert_eq!(select_disclosures(&body, &[]).unwrap(), "[]");
+        assert_eq!(select_disclosures("{}", &[]), Err(VerifyError::InvalidPayload));
+    }
+}
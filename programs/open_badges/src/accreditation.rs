@@ -0,0 +1,178 @@
+//! Accreditation of Issuers
+//!
+//! Accreditation bodies are themselves issuer Profiles. One endorses another
+//! issuer with an `AccreditationEndorsement` PDA stating the scope of the
+//! accreditation and its validity period, keyed by the accrediting profile
+//! and the accredited one. Which bodies count as recognized is the
+//! verifier's decision: a `VerificationPolicy` names a required accreditor,
+//! and `verify_credential_with_policy` checks that an in-force endorsement
+//! from it for the credential's issuer was supplied.
+//!
+//! Endorsements appear in the issuer's Profile document as OB 3.0
+//! EndorsementCredentials under `endorsement`. Their evidence is the PDA
+//! itself, so the documents carry no proof.
+
+use anchor_lang::prelude::*;
+use crate::common::errors::ValidationError;
+use crate::canonical;
+
+/// Seed prefix for accreditation PDAs: [ACCREDITATION_SEED, accreditor, issuer]
+pub const ACCREDITATION_SEED: &[u8] = b"accreditation";
+
+/// Maximum length of an accreditation scope
+pub const MAX_SCOPE_LEN: usize = 128;
+
+/// OB 3.0 types of an endorsement document
+pub const ENDORSEMENT_TYPE: [&str; 2] = ["VerifiableCredential", "EndorsementCredential"];
+
+/// An accreditation body's endorsement of an issuer
+#[account]
+pub struct AccreditationEndorsement {
+    /// Accrediting profile
+    pub accreditor: Pubkey,
+    /// Accredited issuer profile
+    pub issuer: Pubkey,
+    /// What the issuer is accredited for, e.g. "Undergraduate engineering degrees"
+    pub scope: String,
+    /// Unix time the accreditation takes effect
+    pub valid_from: i64,
+    /// Unix time the accreditation lapses, `None` for open-ended
+    pub valid_until: Option<i64>,
+    /// Whether the accreditor withdrew it
+    pub is_revoked: bool,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl AccreditationEndorsement {
+    /// Account size for `scope`
+    pub fn space(scope: &str) -> usize {
+        8 + 32 + 32 + 4 + scope.len() + 8 + 1 + 8 + 1 + 1
+    }
+
+    /// Whether the accreditation is in force at `now`
+    pub fn is_in_force(&self, now: i64) -> bool {
+        let lapsed = match self.valid_until {
+            Some(valid_until) => now > valid_until,
+            None => false,
+        };
+        !self.is_revoked && self.valid_from <= now && !lapsed
+    }
+
+    /// OB 3.0 EndorsementCredential document, with the issuer as its subject
+    pub fn to_json(&self, address: &Pubkey) -> Result<serde_json::Value> {
+        let mut document = serde_json::json!({
+            "@context": canonical::CREDENTIAL_CONTEXT,
+            "id": format!("did:sol:{}", address),
+            "type": ENDORSEMENT_TYPE,
+            "issuer": { "id": format!("did:sol:{}", self.accreditor), "type": ["Profile"] },
+            "validFrom": crate::datetime::format_iso8601(self.valid_from)?,
+            "credentialSubject": {
+                "id": format!("did:sol:{}", self.issuer),
+                "type": ["EndorsementSubject"],
+                "endorsementComment": self.scope,
+            },
+        });
+        if let Some(valid_until) = self.valid_until {
+            document["validUntil"] = serde_json::json!(crate::datetime::format_iso8601(valid_until)?);
+        }
+        Ok(document)
+    }
+}
+
+/// Check an accreditation: a bounded, non-empty scope and a non-empty validity period
+pub fn validate(scope: &str, valid_from: i64, valid_until: Option<i64>) -> Result<()> {
+    if scope.trim().is_empty() || scope.len() > MAX_SCOPE_LEN {
+        return Err(error!(ValidationError::InvalidAccreditation));
+    }
+    if let Some(valid_until) = valid_until {
+        if valid_until <= valid_from {
+            msg!("❌ Accreditation lapses at {} before it starts at {}", valid_until, valid_from);
+            return Err(error!(ValidationError::InvalidAccreditation));
+        }
+    }
+    Ok(())
+}
+
+/// Whether `info` holds an accreditation endorsement, told apart by its discriminator
+pub fn is_accreditation(info: &AccountInfo) -> bool {
+    info.try_borrow_data()
+        .is_ok_and(|data| data.starts_with(AccreditationEndorsement::DISCRIMINATOR))
+}
+
+/// Accreditors whose endorsement of `issuer` is in force at `now`
+pub fn accreditors_of(issuer: &Pubkey, endorsements: &[&AccreditationEndorsement], now: i64) -> Vec<Pubkey> {
+    endorsements
+        .iter()
+        .filter(|endorsement| endorsement.issuer == *issuer && endorsement.is_in_force(now))
+        .map(|endorsement| endorsement.accreditor)
+        .collect()
+}
+
+/// Set `endorsement` on a profile document when there are any
+pub fn apply_to_json(document: &mut serde_json::Value, endorsements: &[(Pubkey, &AccreditationEndorsement)]) -> Result<()> {
+    if !endorsements.is_empty() {
+        let endorsements: Vec<serde_json::Value> = endorsements
+            .iter()
+            .map(|(address, endorsement)| endorsement.to_json(address))
+            .collect::<Result<_>>()?;
+        document["endorsement"] = serde_json::json!(endorsements);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_735_689_600; // 2025-01-01T00:00:00Z
+
+    fn endorsement(issuer: Pubkey, valid_until: Option<i64>) -> AccreditationEndorsement {
+        AccreditationEndorsement {
+            accreditor: Pubkey::new_unique(),
+            issuer,
+            scope: "Undergraduate engineering degrees".to_string(),
+            valid_from: NOW - 100,
+            valid_until,
+            is_revoked: false,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn test_only_in_force_endorsements_count() {
+        let issuer = Pubkey::new_unique();
+        let current = endorsement(issuer, None);
+        let lapsed = endorsement(issuer, Some(NOW - 1));
+        let mut revoked = endorsement(issuer, None);
+        revoked.is_revoked = true;
+        let other_issuer = endorsement(Pubkey::new_unique(), None);
+
+        let accreditors = accreditors_of(&issuer, &[&current, &lapsed, &revoked, &other_issuer], NOW);
+        assert_eq!(accreditors, vec![current.accreditor]);
+        assert!(!current.is_in_force(NOW - 101));
+
+        assert!(validate("Engineering", NOW, Some(NOW + 1)).is_ok());
+        assert!(validate("Engineering", NOW, Some(NOW)).is_err());
+        assert!(validate(" ", NOW, None).is_err());
+        assert!(validate(&"x".repeat(MAX_SCOPE_LEN + 1), NOW, None).is_err());
+    }
+
+    #[test]
+    fn test_profile_embeds_endorsement_credentials() {
+        let issuer = Pubkey::new_unique();
+        let endorsement = endorsement(issuer, Some(NOW + 86_400));
+        assert_eq!(endorsement.try_to_vec().unwrap().len() + 8, AccreditationEndorsement::space(&endorsement.scope));
+
+        let mut document = serde_json::json!({ "id": format!("did:sol:{}", issuer), "type": ["Profile"] });
+        apply_to_json(&mut document, &[(Pubkey::new_unique(), &endorsement)]).unwrap();
+        let embedded = &document["endorsement"][0];
+        assert_eq!(embedded["type"][1], "EndorsementCredential");
+        assert_eq!(embedded["credentialSubject"]["id"], document["id"]);
+        assert_eq!(embedded["validUntil"], "2025-01-02T00:00:00Z");
+
+        let mut empty = serde_json::json!({});
+        apply_to_json(&mut empty, &[]).unwrap();
+        assert!(empty.get("endorsement").is_none());
+    }
+}
@@ -0,0 +1,168 @@
+//! Issuer Achievement Directory
+//!
+//! Each issuer has a list of its achievement accounts in creation order, so
+//! clients can enumerate an issuer's achievements from known addresses
+//! instead of scanning program accounts with `memcmp` filters on variable
+//! offsets. A header PDA per issuer counts the achievements, and fixed-size
+//! page PDAs `[ACHIEVEMENT_DIRECTORY_SEED, issuer, page]` hold
+//! `MAX_PAGE_SIZE` keys each, so no account grows with the directory.
+//! `create_achievement` creates the header with the issuer's first
+//! achievement and appends to the last page afterwards;
+//! `get_achievements_page` serves one page account per cursor.
+
+use anchor_lang::prelude::*;
+use crate::common::errors::ValidationError;
+
+/// Seed for the achievement directory PDAs: [ACHIEVEMENT_DIRECTORY_SEED, issuer] for the header,
+/// [ACHIEVEMENT_DIRECTORY_SEED, issuer, page (u32 LE)] for each page
+pub const ACHIEVEMENT_DIRECTORY_SEED: &[u8] = b"achievement_directory";
+
+/// Achievement keys per directory page, bounded by return data size
+pub const MAX_PAGE_SIZE: u8 = 24;
+
+/// Header of one issuer's achievement directory
+#[account]
+pub struct AchievementDirectory {
+    /// Issuer profile the directory belongs to
+    pub issuer: Pubkey,
+    /// Achievements recorded across all pages
+    pub total: u32,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl AchievementDirectory {
+    /// Discriminator + issuer + total + bump
+    pub const SPACE: usize = 8 + 32 + 4 + 1;
+
+    /// Page the next achievement is appended to
+    pub fn next_page(&self) -> u32 {
+        self.total / MAX_PAGE_SIZE as u32
+    }
+}
+
+/// Up to `MAX_PAGE_SIZE` achievements created by one issuer
+#[account]
+pub struct AchievementDirectoryPage {
+    /// Issuer profile the page belongs to
+    pub issuer: Pubkey,
+    /// Position of the page in the directory
+    pub page: u32,
+    /// Achievement accounts, oldest first
+    pub achievements: Vec<Pubkey>,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl AchievementDirectoryPage {
+    /// Discriminator + issuer + page + keys at capacity + bump
+    pub const SPACE: usize = 8 + 32 + 4 + 4 + 32 * MAX_PAGE_SIZE as usize + 1;
+}
+
+/// Achievements from a cursor onwards
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct AchievementsPage {
+    /// Achievement accounts on this page
+    pub achievements: Vec<Pubkey>,
+    /// Achievements in the whole directory
+    pub total: u32,
+    /// Cursor of the next page, `None` on the last page
+    pub next_cursor: Option<u32>,
+}
+
+/// Derive the achievement directory page PDA for an issuer
+pub fn find_directory_page_address(issuer: &Pubkey, page: u32, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ACHIEVEMENT_DIRECTORY_SEED, issuer.as_ref(), &page.to_le_bytes()], program_id)
+}
+
+/// Append `achievement` to the directory's last page
+/// `directory_page` must be the page PDA at `directory.next_page()`
+pub fn record(
+    directory: &mut AchievementDirectory,
+    directory_page: &mut AchievementDirectoryPage,
+    issuer: Pubkey,
+    bumps: (u8, u8),
+    achievement: Pubkey,
+) -> Result<()> {
+    directory.issuer = issuer;
+    directory.bump = bumps.0;
+
+    directory_page.issuer = issuer;
+    directory_page.page = directory.next_page();
+    directory_page.bump = bumps.1;
+    if directory_page.achievements.len() >= MAX_PAGE_SIZE as usize {
+        return Err(error!(ValidationError::TooManyItems));
+    }
+    directory_page.achievements.push(achievement);
+    directory.total = directory.total.checked_add(1).ok_or_else(|| error!(ValidationError::TooManyItems))?;
+    Ok(())
+}
+
+/// View of one page account of `directory`; its cursor is the page number
+/// Entries are only appended, so a cursor stays valid while the directory grows
+pub fn page(directory: &AchievementDirectory, directory_page: &AchievementDirectoryPage) -> AchievementsPage {
+    let end = (directory_page.page as u64 + 1) * MAX_PAGE_SIZE as u64;
+    AchievementsPage {
+        achievements: directory_page.achievements.clone(),
+        total: directory.total,
+        next_cursor: (end < directory.total as u64).then_some(directory_page.page + 1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn empty_page() -> AchievementDirectoryPage {
+        AchievementDirectoryPage { issuer: Pubkey::default(), page: 0, achievements: vec![], bump: 0 }
+    }
+
+    #[test]
+    fn test_cursor_walks_directory_in_order() {
+        let issuer = Pubkey::new_unique();
+        let mut directory = AchievementDirectory { issuer, total: 0, bump: 255 };
+        let mut pages = [empty_page(), empty_page(), empty_page()];
+        let keys: Vec<Pubkey> = (0..2 * MAX_PAGE_SIZE as usize + 1).map(|_| Pubkey::new_unique()).collect();
+        for key in &keys {
+            let page = directory.next_page() as usize;
+            record(&mut directory, &mut pages[page], issuer, (255, 254), *key).unwrap();
+        }
+
+        let mut cursor = Some(0);
+        let mut seen = vec![];
+        while let Some(position) = cursor {
+            let page = page(&directory, &pages[position as usize]);
+            assert_eq!(page.total, keys.len() as u32);
+            assert!(page.try_to_vec().unwrap().len() <= 1024);
+            seen.extend(page.achievements);
+            cursor = page.next_cursor;
+        }
+        assert_eq!(seen, keys);
+    }
+
+    #[test]
+    fn test_full_page_is_rejected() {
+        let issuer = Pubkey::new_unique();
+        let mut directory = AchievementDirectory { issuer, total: MAX_PAGE_SIZE as u32, bump: 255 };
+        let mut full = AchievementDirectoryPage {
+            issuer,
+            page: 0,
+            achievements: vec![Pubkey::new_unique(); MAX_PAGE_SIZE as usize],
+            bump: 254,
+        };
+        assert!(record(&mut directory, &mut full, issuer, (255, 254), Pubkey::new_unique()).is_err());
+    }
+
+    #[test]
+    fn test_space_matches_serialized_directory() {
+        let directory = AchievementDirectory { issuer: Pubkey::new_unique(), total: 3, bump: 255 };
+        assert_eq!(crate::test_utils::account_data(&directory).len(), AchievementDirectory::SPACE);
+        let full = AchievementDirectoryPage {
+            issuer: Pubkey::new_unique(),
+            page: 0,
+            achievements: vec![Pubkey::new_unique(); MAX_PAGE_SIZE as usize],
+            bump: 255,
+        };
+        assert_eq!(crate::test_utils::account_data(&full).len(), AchievementDirectoryPage::SPACE);
+    }
+}
@@ -0,0 +1,264 @@
+//! Hash Anchoring for Externally Issued Credentials
+//!
+//! Institutions that sign OB 3.0 credentials with their own tooling can still
+//! use Solana as a tamper-evident registry. `anchor_external_credential`
+//! stores only the SHA-256 of the credential JSON, the issuer DID and,
+//! optionally, a pointer into one of the issuer's revocation lists, so the
+//! credential itself never touches the chain. A verifier holding the JSON
+//! recomputes the hash and checks it against the record and its status bit.
+//!
+//! Issuers notarizing thousands of credentials at once commit a single
+//! merkle root with `anchor_credential_batch` instead. Leaves are the
+//! credential hashes; each credential is then verified with its sibling path.
+//! Leaves and inner nodes are hashed under distinct prefixes and sibling
+//! pairs are sorted before hashing, so a proof needs no position bits.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::{hash, hashv};
+use crate::common::errors::ValidationError;
+use crate::credential_status::RevocationList;
+
+/// Seed for the anchor record PDA: [ANCHOR_RECORD_SEED, issuer, credential_hash]
+pub const ANCHOR_RECORD_SEED: &[u8] = b"anchor_record";
+
+/// Seed for the batch anchor PDA: [BATCH_ANCHOR_SEED, issuer, merkle_root]
+pub const BATCH_ANCHOR_SEED: &[u8] = b"batch_anchor";
+
+/// Longest accepted merkle proof, enough for 2^24 credentials per batch
+pub const MAX_MERKLE_PROOF_LEN: usize = 24;
+
+const MERKLE_LEAF_PREFIX: &[u8] = &[0];
+const MERKLE_NODE_PREFIX: &[u8] = &[1];
+
+/// Maximum length of the anchored issuer DID
+pub const MAX_ISSUER_DID_LEN: usize = 100;
+
+/// Position of an anchored credential in a revocation list
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct StatusPointer {
+    /// Revocation list holding the credential's status bit
+    pub revocation_list: Pubkey,
+    /// Index of the status bit
+    pub index: u32,
+}
+
+/// Anchor of one externally issued credential
+#[account]
+pub struct AnchorRecord {
+    /// Issuer profile that anchored the credential
+    pub issuer: Pubkey,
+    /// SHA-256 of the credential JSON exactly as issued
+    pub credential_hash: [u8; 32],
+    /// Issuer id the credential JSON must carry
+    pub issuer_did: String,
+    /// Revocation status of the credential, if tracked on-chain
+    pub status: Option<StatusPointer>,
+    /// When the credential was anchored (ISO 8601 string)
+    pub anchored_at: String,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl AnchorRecord {
+    /// Discriminator + issuer + hash + DID + status pointer + ISO 8601 timestamp + bump
+    pub fn space(issuer_did: &str) -> usize {
+        8 + 32 + 32 + 4 + issuer_did.len() + 1 + 32 + 4 + 4 + 40 + 1
+    }
+
+    /// Whether `credential_json` is the anchored credential
+    pub fn matches(&self, credential_json: &str) -> bool {
+        credential_hash(credential_json) == self.credential_hash
+            && credential_issuer_id(credential_json).as_deref() == Some(self.issuer_did.as_str())
+    }
+}
+
+/// Merkle root over the hashes of a batch of externally issued credentials
+#[account]
+pub struct BatchAnchor {
+    /// Issuer profile that anchored the batch
+    pub issuer: Pubkey,
+    /// Root of the merkle tree over the batch's credential hashes
+    pub merkle_root: [u8; 32],
+    /// Number of credentials in the batch
+    pub leaf_count: u32,
+    /// Issuer id every credential JSON in the batch must carry
+    pub issuer_did: String,
+    /// When the batch was anchored (ISO 8601 string)
+    pub anchored_at: String,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl BatchAnchor {
+    /// Discriminator + issuer + root + leaf count + DID + ISO 8601 timestamp + bump
+    pub fn space(issuer_did: &str) -> usize {
+        8 + 32 + 32 + 4 + 4 + issuer_did.len() + 4 + 40 + 1
+    }
+
+    /// Whether `credential_json` is in the batch, given its sibling path
+    pub fn contains(&self, credential_json: &str, proof: &[[u8; 32]]) -> bool {
+        proof.len() <= MAX_MERKLE_PROOF_LEN
+            && credential_issuer_id(credential_json).as_deref() == Some(self.issuer_did.as_str())
+            && merkle_root_from_proof(credential_hash(credential_json), proof) == self.merkle_root
+    }
+}
+
+/// SHA-256 of the credential JSON bytes
+pub fn credential_hash(credential_json: &str) -> [u8; 32] {
+    hash(credential_json.as_bytes()).to_bytes()
+}
+
+/// Issuer id of a credential, whether `issuer` is a string or a profile object
+pub fn credential_issuer_id(credential_json: &str) -> Option<String> {
+    let value: serde_json::Value = serde_json::from_str(credential_json).ok()?;
+    match value.get("issuer")? {
+        serde_json::Value::String(id) => Some(id.clone()),
+        issuer => issuer.get("id")?.as_str().map(str::to_string),
+    }
+}
+
+fn merkle_leaf(credential_hash: &[u8; 32]) -> [u8; 32] {
+    hashv(&[MERKLE_LEAF_PREFIX, credential_hash]).to_bytes()
+}
+
+fn merkle_node(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    hashv(&[MERKLE_NODE_PREFIX, left, right]).to_bytes()
+}
+
+/// Fold a credential hash up its sibling path
+pub fn merkle_root_from_proof(credential_hash: [u8; 32], proof: &[[u8; 32]]) -> [u8; 32] {
+    proof.iter().fold(merkle_leaf(&credential_hash), |node, sibling| merkle_node(&node, sibling))
+}
+
+/// Merkle root over credential hashes; an odd node is carried up unchanged
+pub fn merkle_root(credential_hashes: &[[u8; 32]]) -> Option<[u8; 32]> {
+    let mut level: Vec<[u8; 32]> = credential_hashes.iter().map(merkle_leaf).collect();
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| if let [a, b] = pair { merkle_node(a, b) } else { pair[0] })
+            .collect();
+    }
+    level.first().copied()
+}
+
+/// Sibling path of the credential hash at `index`, for off-chain tooling
+pub fn merkle_proof(credential_hashes: &[[u8; 32]], mut index: usize) -> Option<Vec<[u8; 32]>> {
+    if index >= credential_hashes.len() {
+        return None;
+    }
+    let mut level: Vec<[u8; 32]> = credential_hashes.iter().map(merkle_leaf).collect();
+    let mut proof = Vec::new();
+    while level.len() > 1 {
+        if let Some(sibling) = level.get(index ^ 1) {
+            proof.push(*sibling);
+        }
+        level = level
+            .chunks(2)
+            .map(|pair| if let [a, b] = pair { merkle_node(a, b) } else { pair[0] })
+            .collect();
+        index /= 2;
+    }
+    Some(proof)
+}
+
+/// Check an issuer DID before anchoring
+pub fn validate_issuer_did(issuer_did: &str) -> Result<()> {
+    if issuer_did.len() > MAX_ISSUER_DID_LEN {
+        return Err(error!(ValidationError::IdTooLong));
+    }
+    if !issuer_did.starts_with("did:") {
+        return Err(error!(ValidationError::InvalidDid));
+    }
+    Ok(())
+}
+
+/// Check that `list` is the revocation list `status` points into
+pub fn check_status_list<'a>(
+    status: &StatusPointer,
+    list_key: Option<Pubkey>,
+    list: Option<&'a RevocationList>,
+) -> Result<&'a RevocationList> {
+    match (list_key, list) {
+        (Some(key), Some(list)) if key == status.revocation_list => {
+            if status.index >= list.capacity {
+                return Err(error!(ValidationError::IndexOutOfBounds));
+            }
+            Ok(list)
+        }
+        _ => Err(error!(ValidationError::StatusListMismatch)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CREDENTIAL: &str = r#"{"id":"urn:uuid:1","issuer":{"id":"did:web:example.edu","type":"Profile"}}"#;
+
+    fn record(json: &str, issuer_did: &str) -> AnchorRecord {
+        AnchorRecord {
+            issuer: Pubkey::new_unique(),
+            credential_hash: credential_hash(json),
+            issuer_did: issuer_did.to_string(),
+            status: None,
+            anchored_at: "2025-01-01T00:00:00Z".to_string(),
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn test_anchor_matches_exact_json_and_issuer() {
+        let anchor = record(CREDENTIAL, "did:web:example.edu");
+        assert!(anchor.matches(CREDENTIAL));
+        assert!(!anchor.matches(&CREDENTIAL.replace("urn:uuid:1", "urn:uuid:2")));
+        assert!(!record(CREDENTIAL, "did:web:other.edu").matches(CREDENTIAL));
+    }
+
+    #[test]
+    fn test_issuer_id_accepts_string_or_profile() {
+        assert_eq!(credential_issuer_id(r#"{"issuer":"did:key:z6Mk"}"#).as_deref(), Some("did:key:z6Mk"));
+        assert_eq!(credential_issuer_id(CREDENTIAL).as_deref(), Some("did:web:example.edu"));
+        assert_eq!(credential_issuer_id(r#"{"issuer":{}}"#), None);
+        assert_eq!(credential_issuer_id("not json"), None);
+    }
+
+    #[test]
+    fn test_merkle_proofs_verify_every_leaf() {
+        let credentials: Vec<String> = (0..7)
+            .map(|i| CREDENTIAL.replace("urn:uuid:1", &format!("urn:uuid:batch-{}", i)))
+            .collect();
+        let hashes: Vec<[u8; 32]> = credentials.iter().map(|json| credential_hash(json)).collect();
+        let batch = BatchAnchor {
+            issuer: Pubkey::new_unique(),
+            merkle_root: merkle_root(&hashes).unwrap(),
+            leaf_count: hashes.len() as u32,
+            issuer_did: "did:web:example.edu".to_string(),
+            anchored_at: "2025-01-01T00:00:00Z".to_string(),
+            bump: 255,
+        };
+
+        for (index, json) in credentials.iter().enumerate() {
+            let proof = merkle_proof(&hashes, index).unwrap();
+            assert!(batch.contains(json, &proof));
+            assert!(!batch.contains(CREDENTIAL, &proof));
+        }
+        assert!(merkle_proof(&hashes, hashes.len()).is_none());
+    }
+
+    #[test]
+    fn test_leaf_cannot_pose_as_inner_node() {
+        let hashes = [credential_hash("a"), credential_hash("b")];
+        let root = merkle_root(&hashes).unwrap();
+        assert_ne!(root, merkle_root(&[root]).unwrap());
+        assert_ne!(merkle_root_from_proof(root, &[]), root);
+    }
+
+    #[test]
+    fn test_space_fits_record_with_status() {
+        let mut anchor = record(CREDENTIAL, "did:web:example.edu");
+        anchor.status = Some(StatusPointer { revocation_list: Pubkey::new_unique(), index: 7 });
+        assert!(crate::test_utils::account_data(&anchor).len() <= AnchorRecord::space(&anchor.issuer_did));
+    }
+}
@@ -0,0 +1,308 @@
+//! Versioned Instruction Arguments
+//!
+//! Issuance and achievement creation take a single arguments struct rather
+//! than a growing list of positional parameters. Each struct leads with a
+//! `version` byte, and fields are only ever appended, each with a version
+//! bump. Decoding reads the version first and then only the fields that
+//! version has: a client built against an older, shorter layout gets the
+//! defaults for the fields it does not know. A version newer than the
+//! program's is decoded as far as the program's layout goes and rejected
+//! with `UnsupportedArgsVersion`.
+
+use anchor_lang::prelude::*;
+use std::io::Read;
+use crate::common::errors::ValidationError;
+use crate::{criteria, extensions, i18n, results, terms_of_use};
+
+/// Layout version of `IssueCredentialArgs`
+pub const ISSUE_CREDENTIAL_ARGS_VERSION: u8 = 3;
+
+/// Layout version of `CreateAchievementArgs`
+pub const CREATE_ACHIEVEMENT_ARGS_VERSION: u8 = 4;
+
+/// Arguments of the `issue_achievement_credential*` instructions
+#[derive(AnchorSerialize, Clone, Debug, PartialEq)]
+pub struct IssueCredentialArgs {
+    /// Layout version, from 1 to `ISSUE_CREDENTIAL_ARGS_VERSION`
+    pub version: u8,
+    /// Recipient wallet
+    pub recipient_pubkey: Pubkey,
+    /// Ed25519 signature (64 bytes) over `message_data`
+    pub signature_data: Vec<u8>,
+    /// The canonical signing JSON the authority signed
+    pub message_data: Vec<u8>,
+    /// ISO 8601 `validFrom` from the client (for coordination)
+    pub timestamp: String,
+    /// Results obtained by the recipient
+    pub results: Vec<results::ResultInput>,
+    /// Subject narrative
+    pub narrative: Option<String>,
+    /// Issuer notes
+    pub notes: Vec<String>,
+    /// Registered credential schema ids
+    pub credential_schemas: Vec<String>,
+    /// Bind the credential to the recipient's key
+    pub bind_holder: bool,
+    /// `termsOfUse` replacing the issuer's defaults (since version 2)
+    pub terms_of_use: Option<Vec<terms_of_use::TermsOfUse>>,
+    /// Pairwise subject id the holder derived for this issuer, required by
+    /// the pairwise issuance and ignored elsewhere (since version 3)
+    pub pairwise_subject_id: Option<String>,
+}
+
+impl IssueCredentialArgs {
+    /// Current-version arguments with no optional fields set
+    pub fn new(recipient_pubkey: Pubkey, signature_data: Vec<u8>, message_data: Vec<u8>, timestamp: String) -> Self {
+        Self {
+            version: ISSUE_CREDENTIAL_ARGS_VERSION,
+            recipient_pubkey,
+            signature_data,
+            message_data,
+            timestamp,
+            results: vec![],
+            narrative: None,
+            notes: vec![],
+            credential_schemas: vec![],
+            bind_holder: false,
+            terms_of_use: None,
+            pairwise_subject_id: None,
+        }
+    }
+
+    /// Fail unless the arguments use a layout this program knows
+    pub fn ensure_supported(&self) -> Result<()> {
+        ensure_version(self.version, ISSUE_CREDENTIAL_ARGS_VERSION)
+    }
+}
+
+impl AnchorDeserialize for IssueCredentialArgs {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> std::io::Result<Self> {
+        let version = u8::deserialize_reader(reader)?;
+        Ok(Self {
+            version,
+            recipient_pubkey: AnchorDeserialize::deserialize_reader(reader)?,
+            signature_data: AnchorDeserialize::deserialize_reader(reader)?,
+            message_data: AnchorDeserialize::deserialize_reader(reader)?,
+            timestamp: AnchorDeserialize::deserialize_reader(reader)?,
+            results: AnchorDeserialize::deserialize_reader(reader)?,
+            narrative: AnchorDeserialize::deserialize_reader(reader)?,
+            notes: AnchorDeserialize::deserialize_reader(reader)?,
+            credential_schemas: AnchorDeserialize::deserialize_reader(reader)?,
+            bind_holder: AnchorDeserialize::deserialize_reader(reader)?,
+            terms_of_use: since(version, 2, reader)?,
+            pairwise_subject_id: since(version, 3, reader)?,
+        })
+    }
+}
+
+/// Arguments of `create_achievement`
+#[derive(AnchorSerialize, Clone, Debug, PartialEq)]
+pub struct CreateAchievementArgs {
+    /// Layout version, from 1 to `CREATE_ACHIEVEMENT_ARGS_VERSION`
+    pub version: u8,
+    /// Achievement id
+    pub achievement_id: String,
+    /// Achievement name; part of the achievement PDA seeds
+    pub name: String,
+    /// Achievement description
+    pub description: String,
+    /// Criteria narrative
+    pub criteria_narrative: Option<String>,
+    /// Criteria id
+    pub criteria_id: Option<String>,
+    /// Image URI
+    pub image: Option<String>,
+    /// Results the achievement can award
+    pub result_descriptions: Vec<results::ResultDescription>,
+    /// Issuer-defined extension properties
+    pub extensions: Vec<extensions::Extension>,
+    /// OB 3.0 `achievementType` term
+    pub achievement_type: Option<String>,
+    /// Aligned competencies and result thresholds (since version 2)
+    pub requirements: criteria::CriteriaRequirements,
+    /// Translations of `name` (since version 3)
+    pub name_i18n: Vec<i18n::LocalizedText>,
+    /// Translations of `description` (since version 3)
+    pub description_i18n: Vec<i18n::LocalizedText>,
+    /// Issue only against an approved assessment result (since version 4)
+    pub requires_approved_result: bool,
+}
+
+impl CreateAchievementArgs {
+    /// Current-version arguments with no optional fields set
+    pub fn new(achievement_id: String, name: String, description: String) -> Self {
+        Self {
+            version: CREATE_ACHIEVEMENT_ARGS_VERSION,
+            achievement_id,
+            name,
+            description,
+            criteria_narrative: None,
+            criteria_id: None,
+            image: None,
+            result_descriptions: vec![],
+            extensions: vec![],
+            achievement_type: None,
+            requirements: criteria::CriteriaRequirements::default(),
+            name_i18n: vec![],
+            description_i18n: vec![],
+            requires_approved_result: false,
+        }
+    }
+
+    /// Fail unless the arguments use a layout this program knows
+    pub fn ensure_supported(&self) -> Result<()> {
+        ensure_version(self.version, CREATE_ACHIEVEMENT_ARGS_VERSION)
+    }
+}
+
+impl AnchorDeserialize for CreateAchievementArgs {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> std::io::Result<Self> {
+        let version = u8::deserialize_reader(reader)?;
+        Ok(Self {
+            version,
+            achievement_id: AnchorDeserialize::deserialize_reader(reader)?,
+            name: AnchorDeserialize::deserialize_reader(reader)?,
+            description: AnchorDeserialize::deserialize_reader(reader)?,
+            criteria_narrative: AnchorDeserialize::deserialize_reader(reader)?,
+            criteria_id: AnchorDeserialize::deserialize_reader(reader)?,
+            image: AnchorDeserialize::deserialize_reader(reader)?,
+            result_descriptions: AnchorDeserialize::deserialize_reader(reader)?,
+            extensions: AnchorDeserialize::deserialize_reader(reader)?,
+            achievement_type: AnchorDeserialize::deserialize_reader(reader)?,
+            requirements: since(version, 2, reader)?,
+            name_i18n: since(version, 3, reader)?,
+            description_i18n: since(version, 3, reader)?,
+            requires_approved_result: since(version, 4, reader)?,
+        })
+    }
+}
+
+/// Decode a field added in version `added`, or its default when `version` predates it
+fn since<T: AnchorDeserialize + Default, R: Read>(version: u8, added: u8, reader: &mut R) -> std::io::Result<T> {
+    if version < added {
+        return Ok(T::default());
+    }
+    T::deserialize_reader(reader)
+}
+
+fn ensure_version(version: u8, supported: u8) -> Result<()> {
+    if version == 0 || version > supported {
+        msg!("❌ Arguments version {} is not supported (expected 1 to {})", version, supported);
+        return Err(error!(ValidationError::UnsupportedArgsVersion));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `IssueCredentialArgs` as version 1 clients encode it
+    #[derive(AnchorSerialize)]
+    struct IssueCredentialArgsV1 {
+        version: u8,
+        recipient_pubkey: Pubkey,
+        signature_data: Vec<u8>,
+        message_data: Vec<u8>,
+        timestamp: String,
+        results: Vec<results::ResultInput>,
+        narrative: Option<String>,
+        notes: Vec<String>,
+        credential_schemas: Vec<String>,
+        bind_holder: bool,
+    }
+
+    /// `CreateAchievementArgs` as version 1 clients encode it
+    #[derive(AnchorSerialize)]
+    struct CreateAchievementArgsV1 {
+        version: u8,
+        achievement_id: String,
+        name: String,
+        description: String,
+        criteria_narrative: Option<String>,
+        criteria_id: Option<String>,
+        image: Option<String>,
+        result_descriptions: Vec<results::ResultDescription>,
+        extensions: Vec<extensions::Extension>,
+        achievement_type: Option<String>,
+    }
+
+    #[test]
+    fn test_known_versions_are_accepted() {
+        let mut args = IssueCredentialArgs::new(Pubkey::new_unique(), vec![0; 64], vec![], "2024-01-01T00:00:00Z".to_string());
+        assert!(args.ensure_supported().is_ok());
+        args.version = 1;
+        assert!(args.ensure_supported().is_ok());
+        args.version = ISSUE_CREDENTIAL_ARGS_VERSION + 1;
+        assert!(args.ensure_supported().is_err());
+
+        let mut args = CreateAchievementArgs::new("badge".to_string(), "Badge".to_string(), "A badge".to_string());
+        assert!(args.ensure_supported().is_ok());
+        args.version = 0;
+        assert!(args.ensure_supported().is_err());
+    }
+
+    #[test]
+    fn test_version_leads_the_encoding() {
+        let args = IssueCredentialArgs::new(Pubkey::new_unique(), vec![], vec![], String::new());
+        let bytes = args.try_to_vec().unwrap();
+        assert_eq!(bytes[0], ISSUE_CREDENTIAL_ARGS_VERSION);
+        assert_eq!(IssueCredentialArgs::try_from_slice(&bytes).unwrap(), args);
+
+        let args = CreateAchievementArgs::new("badge".to_string(), "Badge".to_string(), "A badge".to_string());
+        assert_eq!(CreateAchievementArgs::try_from_slice(&args.try_to_vec().unwrap()).unwrap(), args);
+    }
+
+    #[test]
+    fn test_version_1_layouts_decode_with_defaults() {
+        let recipient = Pubkey::new_unique();
+        let v1 = IssueCredentialArgsV1 {
+            version: 1,
+            recipient_pubkey: recipient,
+            signature_data: vec![7; 64],
+            message_data: b"{}".to_vec(),
+            timestamp: "2024-01-01T00:00:00Z".to_string(),
+            results: vec![],
+            narrative: Some("Completed the course".to_string()),
+            notes: vec!["Reviewed".to_string()],
+            credential_schemas: vec![],
+            bind_holder: true,
+        };
+        let args = IssueCredentialArgs::try_from_slice(&v1.try_to_vec().unwrap()).unwrap();
+        assert!(args.ensure_supported().is_ok());
+        assert_eq!(args.version, 1);
+        assert_eq!(args.recipient_pubkey, recipient);
+        assert_eq!(args.narrative.as_deref(), Some("Completed the course"));
+        assert!(args.bind_holder);
+        assert_eq!(args.terms_of_use, None);
+        assert_eq!(args.pairwise_subject_id, None);
+
+        let v1 = CreateAchievementArgsV1 {
+            version: 1,
+            achievement_id: "badge".to_string(),
+            name: "Badge".to_string(),
+            description: "A badge".to_string(),
+            criteria_narrative: None,
+            criteria_id: None,
+            image: Some("https://example.com/badge.png".to_string()),
+            result_descriptions: vec![],
+            extensions: vec![],
+            achievement_type: Some("Certificate".to_string()),
+        };
+        let args = CreateAchievementArgs::try_from_slice(&v1.try_to_vec().unwrap()).unwrap();
+        assert!(args.ensure_supported().is_ok());
+        assert_eq!(args.image.as_deref(), Some("https://example.com/badge.png"));
+        assert_eq!(args.requirements, criteria::CriteriaRequirements::default());
+        assert!(args.name_i18n.is_empty() && !args.requires_approved_result);
+    }
+
+    #[test]
+    fn test_newer_versions_reach_the_version_check() {
+        let mut args = IssueCredentialArgs::new(Pubkey::new_unique(), vec![], vec![], String::new());
+        args.version = ISSUE_CREDENTIAL_ARGS_VERSION + 1;
+        let mut bytes = args.try_to_vec().unwrap();
+        bytes.extend_from_slice(&[1, 2, 3]);
+        let decoded = IssueCredentialArgs::deserialize(&mut bytes.as_slice()).unwrap();
+        assert!(decoded.ensure_supported().is_err());
+    }
+}
@@ -0,0 +1,174 @@
+//! Assessment Results and Moderation
+//!
+//! Issuers grant assessor and moderator roles to other wallets. Assessors
+//! submit a `ResultEntry` for a learner, moderators approve or reject it, and
+//! issuance can require an approved entry so credentials are backed by
+//! moderated assessment data recorded on-chain.
+
+use anchor_lang::prelude::*;
+use crate::common::errors::ValidationError;
+use crate::Achievement;
+
+/// Seed prefix for role grant PDAs
+pub const ROLE_SEED: &[u8] = b"role";
+
+/// Seed prefix for result entry PDAs
+pub const RESULT_SEED: &[u8] = b"result";
+
+/// Maximum length of a submitted result value
+pub const MAX_RESULT_VALUE_LEN: usize = 64;
+
+/// Maximum length of an assessor or moderator comment
+pub const MAX_RESULT_COMMENT_LEN: usize = 200;
+
+/// Roles an issuer can delegate for assessment
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AssessmentRole {
+    /// May submit results for learners
+    Assessor,
+    /// May approve or reject submitted results
+    Moderator,
+}
+
+impl AssessmentRole {
+    /// Byte used in the role grant PDA seeds
+    pub fn seed(&self) -> u8 {
+        match self {
+            AssessmentRole::Assessor => 0,
+            AssessmentRole::Moderator => 1,
+        }
+    }
+}
+
+/// Moderation state of a result entry
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ResultStatus {
+    /// Submitted and waiting for a moderator
+    Pending,
+    /// Approved by a moderator; issuance may proceed
+    Approved,
+    /// Rejected by a moderator
+    Rejected,
+}
+
+/// Role delegated by an issuer to a wallet
+#[account]
+pub struct RoleGrant {
+    /// Issuer profile granting the role
+    pub issuer: Pubkey,
+    /// Wallet holding the role
+    pub member: Pubkey,
+    /// Role granted
+    pub role: AssessmentRole,
+    /// When the role was granted (ISO 8601 string)
+    pub granted_at: String,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl RoleGrant {
+    /// Discriminator + issuer + member + role + timestamp + bump
+    pub const SPACE: usize = 8 + 32 + 32 + 1 + 4 + 40 + 1;
+}
+
+/// Assessment result awaiting or past moderation
+#[account]
+pub struct ResultEntry {
+    /// Achievement being assessed
+    pub achievement: Pubkey,
+    /// Issuer profile of the achievement
+    pub issuer: Pubkey,
+    /// Learner the result is for
+    pub recipient: Pubkey,
+    /// Credential PDA the result backs once issued
+    pub credential: Pubkey,
+    /// Assessor who submitted the result
+    pub assessor: Pubkey,
+    /// Result value (e.g. grade, score or level id)
+    pub value: String,
+    /// Assessor's comment
+    pub comment: Option<String>,
+    /// Moderation state
+    pub status: ResultStatus,
+    /// Moderator who reviewed the result
+    pub moderator: Option<Pubkey>,
+    /// Moderator's comment
+    pub moderator_comment: Option<String>,
+    /// Submission timestamp (ISO 8601 string)
+    pub submitted_at: String,
+    /// Review timestamp (ISO 8601 string, optional)
+    pub reviewed_at: Option<String>,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl ResultEntry {
+    /// Space for a result entry at maximum size
+    pub const SPACE: usize = 8
+        + 32 * 5
+        + 4 + MAX_RESULT_VALUE_LEN
+        + 1 + 4 + MAX_RESULT_COMMENT_LEN
+        + 1
+        + 1 + 32
+        + 1 + 4 + MAX_RESULT_COMMENT_LEN
+        + 4 + 40
+        + 1 + 4 + 40
+        + 1;
+}
+
+/// Check the lengths of a result submission
+pub fn validate_submission(value: &str, comment: &Option<String>) -> Result<()> {
+    if value.is_empty() {
+        return Err(error!(ValidationError::MissingRequiredField));
+    }
+    if value.len() > MAX_RESULT_VALUE_LEN {
+        return Err(error!(ValidationError::FieldTooLong));
+    }
+    if comment.as_ref().is_some_and(|c| c.len() > MAX_RESULT_COMMENT_LEN) {
+        return Err(error!(ValidationError::FieldTooLong));
+    }
+    Ok(())
+}
+
+/// Require an approved result for this achievement and recipient when one is
+/// supplied or the achievement sets `requires_approved_result`
+pub fn ensure_result_approved(
+    entry: Option<&ResultEntry>,
+    achievement: &Account<Achievement>,
+    recipient: &Pubkey,
+) -> Result<()> {
+    let Some(entry) = entry else {
+        return ensure_result_not_required(achievement);
+    };
+    if entry.achievement != achievement.key() || entry.recipient != *recipient {
+        return Err(error!(ValidationError::ValidationFailed));
+    }
+    if entry.status != ResultStatus::Approved {
+        msg!("❌ Assessment result is not approved: {:?}", entry.status);
+        return Err(error!(ValidationError::ResultNotApproved));
+    }
+    Ok(())
+}
+
+/// Fail when the achievement requires an approved result, for issuance that supplies none
+pub fn ensure_result_not_required(achievement: &Achievement) -> Result<()> {
+    if achievement.requires_approved_result {
+        msg!("❌ Achievement {} requires an approved assessment result", achievement.id);
+        return Err(error!(ValidationError::ResultNotApproved));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::fixture_achievement;
+
+    #[test]
+    fn test_moderated_achievements_need_a_result() {
+        let mut achievement = fixture_achievement(&Pubkey::new_unique(), &Pubkey::new_unique(), "Badge");
+        assert!(ensure_result_not_required(&achievement).is_ok());
+        achievement.requires_approved_result = true;
+        assert!(ensure_result_not_required(&achievement).is_err());
+    }
+}
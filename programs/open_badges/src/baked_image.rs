@@ -0,0 +1,99 @@
+//! Baked Image Registration
+//!
+//! Baking happens off-chain, so the image a holder shares is not otherwise
+//! tied to the issuance. The issuer registers the SHA-256 of the baked PNG
+//! or SVG on the credential account; anyone handed the image can hash it
+//! and ask `verify_baked_badge` whether it is byte-for-byte the registered
+//! one. Registering again replaces the earlier hash, e.g. after re-baking
+//! with new artwork.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use crate::formats::baked::BakedFormat;
+use crate::AchievementCredential;
+
+/// Image format of a registered baked badge
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BakedImageFormat {
+    Png,
+    Svg,
+}
+
+impl From<&BakedFormat> for BakedImageFormat {
+    fn from(format: &BakedFormat) -> Self {
+        match format {
+            BakedFormat::Png => Self::Png,
+            BakedFormat::Svg => Self::Svg,
+        }
+    }
+}
+
+/// Hash of the baked image registered for a credential
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct BakedImage {
+    /// SHA-256 of the complete baked image file
+    pub sha256: [u8; 32],
+    /// Format of the image
+    pub format: BakedImageFormat,
+    /// When the hash was registered (ISO 8601 string)
+    pub registered_at: String,
+}
+
+/// Serialized size of a registered image, option tag included
+pub const BAKED_IMAGE_SPACE: usize = 1 + 32 + 1 + 4 + 40;
+
+/// SHA-256 of a baked image, as `register_baked_badge` expects it
+pub fn image_sha256(image: &[u8]) -> [u8; 32] {
+    hash(image).to_bytes()
+}
+
+/// Account size that fits `credential` with a registered image
+/// Never shrinks: accounts are allocated with slack at issuance
+pub fn credential_space(current_len: usize, credential: &AchievementCredential) -> usize {
+    let serialized = 8 + credential.try_to_vec().map_or(current_len, |data| data.len());
+    let registered = credential.baked_image.as_ref().map_or(1, |image| 1 + 32 + 1 + 4 + image.registered_at.len());
+    current_len.max(serialized - registered + BAKED_IMAGE_SPACE)
+}
+
+/// Whether `image_hash` is the hash registered for `credential`
+pub fn matches_registered(credential: &AchievementCredential, image_hash: &[u8; 32]) -> bool {
+    credential.baked_image.as_ref().is_some_and(|image| &image.sha256 == image_hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::fixture_credential;
+
+    fn credential() -> AchievementCredential {
+        fixture_credential(&Pubkey::new_unique(), &Pubkey::new_unique(), &Pubkey::new_unique(), &Pubkey::new_unique())
+    }
+
+    #[test]
+    fn test_only_the_registered_image_matches() {
+        let mut credential = credential();
+        let baked = b"\x89PNG baked image";
+        assert!(!matches_registered(&credential, &image_sha256(baked)));
+
+        credential.baked_image = Some(BakedImage {
+            sha256: image_sha256(baked),
+            format: BakedImageFormat::Png,
+            registered_at: "2025-01-01T00:00:00Z".to_string(),
+        });
+        assert!(matches_registered(&credential, &image_sha256(baked)));
+        assert!(!matches_registered(&credential, &image_sha256(b"\x89PNG baked imagf")));
+    }
+
+    #[test]
+    fn test_space_fits_registered_image() {
+        let mut credential = credential();
+        let needed = credential_space(0, &credential);
+        credential.baked_image = Some(BakedImage {
+            sha256: [7; 32],
+            format: BakedImageFormat::from(&BakedFormat::Svg),
+            registered_at: "2025-01-01T00:00:00.000000000Z".to_string(),
+        });
+        assert!(8 + credential.try_to_vec().unwrap().len() <= needed);
+        assert_eq!(credential_space(needed, &credential), needed);
+    }
+}
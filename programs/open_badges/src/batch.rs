@@ -0,0 +1,185 @@
+//! Batch Issuance Limits
+//!
+//! How many credentials fit in one batch depends on the transaction's
+//! compute budget rather than a fixed count, so batch issuance charges each
+//! request an estimated compute cost that grows with its payload, and
+//! rejects a batch whose total exceeds the budget, logging how many of the
+//! submitted requests would have fit. The limits live in a program-wide
+//! `BatchConfig` PDA created by the program's upgrade authority and managed
+//! by the admin it names; without one the defaults apply.
+//!
+//! Batch issuance does not create credential accounts yet, so requests that
+//! carry notes are rejected rather than accepted and dropped.
+
+use anchor_lang::prelude::*;
+use crate::common::errors::ValidationError;
+use crate::BatchIssuanceRequest;
+
+/// Seed for the batch config PDA: [BATCH_CONFIG_SEED]
+pub const BATCH_CONFIG_SEED: &[u8] = b"batch_config";
+
+/// Batch size limit when no config account is passed
+pub const DEFAULT_MAX_BATCH_SIZE: u8 = 10;
+
+/// Compute units a batch may spend when no config account is passed
+pub const DEFAULT_COMPUTE_BUDGET: u32 = 1_000_000;
+
+/// Largest compute budget a transaction can request
+pub const MAX_COMPUTE_BUDGET: u32 = 1_400_000;
+
+/// Estimated cost of a batch before its requests: signature check and logging
+pub const BATCH_BASE_COMPUTE: u32 = 60_000;
+
+/// Estimated cost of one request with an empty payload: PDA derivation and JSON
+pub const REQUEST_BASE_COMPUTE: u32 = 40_000;
+
+/// Estimated cost of each payload byte of a request
+pub const REQUEST_BYTE_COMPUTE: u32 = 100;
+
+/// Program-wide batch limits
+#[account]
+pub struct BatchConfig {
+    /// Signer allowed to change the limits
+    pub admin: Pubkey,
+    /// Most requests accepted in one batch
+    pub max_batch_size: u8,
+    /// Compute units a batch may be estimated to use
+    pub compute_budget: u32,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl BatchConfig {
+    pub const SPACE: usize = 8 + 32 + 1 + 4 + 1;
+}
+
+/// Limits a batch is checked against
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BatchLimits {
+    pub max_batch_size: u8,
+    pub compute_budget: u32,
+}
+
+impl BatchLimits {
+    /// Limits of `config`, or the defaults without one
+    pub fn from_config(config: Option<&BatchConfig>) -> Self {
+        config.map_or(
+            Self { max_batch_size: DEFAULT_MAX_BATCH_SIZE, compute_budget: DEFAULT_COMPUTE_BUDGET },
+            |config| Self { max_batch_size: config.max_batch_size, compute_budget: config.compute_budget },
+        )
+    }
+
+    /// Check limits an admin sets
+    pub fn validate(&self) -> Result<()> {
+        if self.max_batch_size == 0 || self.compute_budget < BATCH_BASE_COMPUTE + REQUEST_BASE_COMPUTE {
+            return Err(error!(ValidationError::InvalidBatchConfig));
+        }
+        if self.compute_budget > MAX_COMPUTE_BUDGET {
+            return Err(error!(ValidationError::InvalidBatchConfig));
+        }
+        Ok(())
+    }
+
+    /// How many of `requests`, taken in order, fit these limits
+    pub fn max_requests(&self, requests: &[BatchIssuanceRequest]) -> usize {
+        let mut total = BATCH_BASE_COMPUTE as u64;
+        requests
+            .iter()
+            .take(self.max_batch_size as usize)
+            .take_while(|request| {
+                total += estimate_request_compute(request) as u64;
+                total <= self.compute_budget as u64
+            })
+            .count()
+    }
+
+    /// Fail early when `requests` exceed these limits, logging how many would fit
+    pub fn check(&self, requests: &[BatchIssuanceRequest]) -> Result<()> {
+        require!(!requests.is_empty(), ValidationError::EmptyBatch);
+        let allowed = self.max_requests(requests);
+        if requests.len() > self.max_batch_size as usize {
+            msg!("❌ Batch of {} exceeds the limit of {} requests", requests.len(), self.max_batch_size);
+            return Err(error!(ValidationError::BatchSizeTooLarge));
+        }
+        if allowed < requests.len() {
+            msg!(
+                "❌ Batch of {} is estimated at {} CU over a budget of {}; at most {} of these requests fit",
+                requests.len(),
+                estimate_batch_compute(requests),
+                self.compute_budget,
+                allowed
+            );
+            return Err(error!(ValidationError::BatchComputeBudgetExceeded));
+        }
+        Ok(())
+    }
+}
+
+/// Fail with `NotImplemented` when a request carries notes, which batch issuance cannot store
+pub fn ensure_no_notes(requests: &[BatchIssuanceRequest]) -> Result<()> {
+    if requests.iter().any(|request| request.notes.as_ref().is_some_and(|notes| !notes.is_empty())) {
+        msg!("❌ Batch issuance does not store notes; issue annotated credentials one at a time");
+        return Err(error!(ValidationError::NotImplemented));
+    }
+    Ok(())
+}
+
+/// Estimated compute units to issue one request
+pub fn estimate_request_compute(request: &BatchIssuanceRequest) -> u32 {
+    let notes_len: usize = request.notes.iter().flatten().map(String::len).sum();
+    let payload_len = (request.achievement_id.len() + notes_len) as u32;
+    REQUEST_BASE_COMPUTE.saturating_add(payload_len.saturating_mul(REQUEST_BYTE_COMPUTE))
+}
+
+/// Estimated compute units of a whole batch
+pub fn estimate_batch_compute(requests: &[BatchIssuanceRequest]) -> u64 {
+    BATCH_BASE_COMPUTE as u64 + requests.iter().map(|request| estimate_request_compute(request) as u64).sum::<u64>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(notes_len: usize) -> BatchIssuanceRequest {
+        BatchIssuanceRequest {
+            recipient_pubkey: Pubkey::new_unique(),
+            achievement_id: Pubkey::new_unique().to_string(),
+            notes: Some(vec!["n".repeat(notes_len)]),
+        }
+    }
+
+    #[test]
+    fn test_default_limits_accept_small_batches() {
+        let limits = BatchLimits::from_config(None);
+        assert!(limits.check(&vec![request(8); 3]).is_ok());
+        assert!(limits.check(&[]).is_err());
+        assert!(limits.check(&vec![request(0); DEFAULT_MAX_BATCH_SIZE as usize + 1]).is_err());
+    }
+
+    #[test]
+    fn test_large_payloads_reduce_the_allowed_count() {
+        let limits = BatchLimits { max_batch_size: 10, compute_budget: 400_000 };
+        let small = vec![request(0); 5];
+        let large = vec![request(1_000); 5];
+        assert_eq!(limits.max_requests(&small), 5);
+        assert!(limits.max_requests(&large) < 5);
+        assert!(limits.check(&large).is_err());
+        assert!(estimate_request_compute(&large[0]) > estimate_request_compute(&small[0]));
+    }
+
+    #[test]
+    fn test_notes_are_rejected() {
+        assert!(ensure_no_notes(&[request(8)]).is_err());
+        let unannotated = BatchIssuanceRequest { notes: None, ..request(0) };
+        let empty = BatchIssuanceRequest { notes: Some(vec![]), ..request(0) };
+        assert!(ensure_no_notes(&[unannotated, empty]).is_ok());
+    }
+
+    #[test]
+    fn test_admin_limits_are_bounded() {
+        assert!(BatchLimits { max_batch_size: 0, compute_budget: 200_000 }.validate().is_err());
+        assert!(BatchLimits { max_batch_size: 5, compute_budget: MAX_COMPUTE_BUDGET + 1 }.validate().is_err());
+        assert!(BatchLimits { max_batch_size: 5, compute_budget: 50_000 }.validate().is_err());
+        assert!(BatchLimits { max_batch_size: 5, compute_budget: 500_000 }.validate().is_ok());
+    }
+}
@@ -0,0 +1,109 @@
+//! Issuer Branding
+//!
+//! Optional brand metadata stored on the issuer Profile so verification
+//! pages can render institution-branded badges from chain data alone. The
+//! logo hash pins the exact logo bytes served at the logo URI.
+
+use anchor_lang::prelude::*;
+use crate::common::errors::ValidationError;
+use crate::validation::validate_image_uri;
+
+/// Length of a `#RRGGBB` color
+pub const BRAND_COLOR_LEN: usize = 7;
+
+/// Length of a hex-encoded SHA-256 logo hash
+pub const LOGO_HASH_LEN: usize = 64;
+
+/// Brand metadata of an issuer
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct IssuerBranding {
+    /// Primary brand color as `#RRGGBB`
+    pub primary_color: Option<String>,
+    /// Logo image URI - data: URIs allowed
+    pub logo_uri: Option<String>,
+    /// Hex SHA-256 of the logo image bytes
+    pub logo_hash: Option<String>,
+    /// Background image URI - data: URIs allowed
+    pub background_image: Option<String>,
+}
+
+impl IssuerBranding {
+    /// Serialized size of this branding
+    pub fn space(&self) -> usize {
+        [&self.primary_color, &self.logo_uri, &self.logo_hash, &self.background_image]
+            .iter()
+            .map(|field| 1 + field.as_ref().map_or(0, |value| 4 + value.len()))
+            .sum()
+    }
+
+    /// Check colors, URIs and the logo hash
+    pub fn validate(&self) -> Result<()> {
+        if let Some(color) = &self.primary_color {
+            let is_hex_color = color.len() == BRAND_COLOR_LEN
+                && color.starts_with('#')
+                && color[1..].chars().all(|c| c.is_ascii_hexdigit());
+            if !is_hex_color {
+                msg!("❌ Brand color must be #RRGGBB: {}", color);
+                return Err(error!(ValidationError::ValidationFailed));
+            }
+        }
+        if let Some(logo_uri) = &self.logo_uri {
+            validate_image_uri(logo_uri)?;
+        }
+        if let Some(logo_hash) = &self.logo_hash {
+            if self.logo_uri.is_none() {
+                return Err(error!(ValidationError::MissingRequiredField));
+            }
+            if logo_hash.len() != LOGO_HASH_LEN || !logo_hash.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(error!(ValidationError::ValidationFailed));
+            }
+        }
+        if let Some(background_image) = &self.background_image {
+            validate_image_uri(background_image)?;
+        }
+        Ok(())
+    }
+}
+
+/// Serialized size of an optional branding field
+pub fn space_of(branding: &Option<IssuerBranding>) -> usize {
+    1 + branding.as_ref().map_or(0, IssuerBranding::space)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn branding() -> IssuerBranding {
+        IssuerBranding {
+            primary_color: Some("#1A2b3C".to_string()),
+            logo_uri: Some("https://example.edu/logo.png".to_string()),
+            logo_hash: Some("ab".repeat(32)),
+            background_image: None,
+        }
+    }
+
+    #[test]
+    fn test_space_matches_serialized_branding() {
+        let branding = Some(branding());
+        assert_eq!(space_of(&branding), branding.try_to_vec().unwrap().len());
+        assert_eq!(space_of(&None), 1);
+    }
+
+    #[test]
+    fn test_validation() {
+        assert!(branding().validate().is_ok());
+
+        let mut bad_color = branding();
+        bad_color.primary_color = Some("blue".to_string());
+        assert!(bad_color.validate().is_err());
+
+        let mut orphan_hash = branding();
+        orphan_hash.logo_uri = None;
+        assert!(orphan_hash.validate().is_err());
+
+        let mut short_hash = branding();
+        short_hash.logo_hash = Some("abcd".to_string());
+        assert!(short_hash.validate().is_err());
+    }
+}
@@ -0,0 +1,295 @@
+//! Canonical Credential Signing JSON
+//!
+//! Issuers sign a compact JSON projection of the credential: context, id,
+//! type, issuer, validFrom and the subject's id, type and achievement, then
+//! the bound holder when there is one, in that order and without whitespace. Every issuance path and every
+//! `generate_credential_json*` view builds it here, and clients can call
+//! `build_credential_signing_json` to get the exact bytes to sign instead
+//! of reproducing the layout themselves. The account's other members, such
+//! as results, narrative and terms of use, are not signed, which is why
+//! `document_integrity` checks copies against the full account document.
+
+use anchor_lang::prelude::*;
+use crate::AchievementCredential;
+
+/// `@context` of issued credentials
+pub const CREDENTIAL_CONTEXT: [&str; 2] = [
+    "https://www.w3.org/ns/credentials/v2",
+    "https://purl.imsglobal.org/spec/ob/v3p0/context-3.0.3.json",
+];
+
+/// `type` of issued credentials
+pub const CREDENTIAL_TYPE: [&str; 2] = ["VerifiableCredential", "OpenBadgeCredential"];
+
+/// `type` of the credential subject
+pub const SUBJECT_TYPE: [&str; 1] = ["AchievementSubject"];
+
+/// Fields covered by the issuer's signature
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SigningJsonParams {
+    /// `@context` URIs
+    pub context: Vec<String>,
+    /// Credential id
+    pub id: String,
+    /// Credential types
+    pub r#type: Vec<String>,
+    /// Issuer id
+    pub issuer: String,
+    /// `validFrom` (ISO 8601 string)
+    pub valid_from: String,
+    /// Credential subject id
+    pub subject_id: String,
+    /// Credential subject types
+    pub subject_type: Vec<String>,
+    /// Achievement id
+    pub achievement: String,
+    /// Key the credential is bound to, signed as its `holder` Multikey
+    pub holder: Option<Pubkey>,
+    /// `encrypted_payload::commitment` of the credential's encrypted body, if any
+    pub encrypted_payload: Option<[u8; 32]>,
+}
+
+impl SigningJsonParams {
+    /// Parameters with the default context and types
+    pub fn new(id: &str, issuer: &str, valid_from: &str, subject_id: &str, achievement: &str) -> Self {
+        let strings = |values: &[&str]| values.iter().map(|value| value.to_string()).collect();
+        Self {
+            context: strings(&CREDENTIAL_CONTEXT),
+            id: id.to_string(),
+            r#type: strings(&CREDENTIAL_TYPE),
+            issuer: issuer.to_string(),
+            valid_from: valid_from.to_string(),
+            subject_id: subject_id.to_string(),
+            subject_type: strings(&SUBJECT_TYPE),
+            achievement: achievement.to_string(),
+            holder: None,
+            encrypted_payload: None,
+        }
+    }
+
+    /// The same parameters for a credential bound to `holder`
+    pub fn with_holder(self, holder: Option<Pubkey>) -> Self {
+        Self { holder, ..self }
+    }
+
+    /// Parameters of an issued credential account
+    /// A re-bound credential keeps the subject id its original proof was signed for
+    pub fn for_credential(credential: &AchievementCredential) -> Self {
+        Self {
+            context: credential.context.clone(),
+            id: credential.id.clone(),
+            r#type: credential.r#type.clone(),
+            issuer: format!("did:sol:{}", credential.issuer),
+            valid_from: credential.valid_from.clone(),
+            subject_id: crate::rebind::original_subject_id(credential).unwrap_or_default(),
+            subject_type: credential.credential_subject.subject_type.clone(),
+            achievement: format!("did:sol:{}", credential.credential_subject.achievement),
+            holder: crate::rebind::original_holder_binding(credential),
+            encrypted_payload: credential.encrypted_payload.as_ref().map(crate::encrypted_payload::commitment),
+        }
+    }
+}
+
+/// Build the signing JSON; string values are JSON-escaped
+pub fn build_signing_json(params: &SigningJsonParams) -> String {
+    let mut out = Vec::with_capacity(capacity(params));
+    write_unclosed(&mut out, params);
+    out.push(b'}');
+    into_string(out)
+}
+
+/// Signed credential document: the signing JSON with the issuer's proof embedded
+/// The signed bytes are kept as-is, so removing `proof` yields exactly the
+/// message the proof verifies. `None` for a credential without a proof.
+pub fn signed_document(credential: &AchievementCredential) -> Option<String> {
+    let proof = credential.proof.first()?;
+    let params = SigningJsonParams::for_credential(credential);
+    let proof = serde_json::json!({
+        "type": proof.proof_type,
+        "cryptosuite": proof.cryptosuite,
+        "created": proof.created,
+        "verificationMethod": proof.verification_method,
+        "proofPurpose": proof.proof_purpose,
+        "proofValue": proof.proof_value,
+    });
+    let mut out = Vec::with_capacity(capacity(&params) + 512);
+    write_unclosed(&mut out, &params);
+    out.extend_from_slice(br#","proof":"#);
+    write_json(&mut out, &proof);
+    out.push(b'}');
+    Some(into_string(out))
+}
+
+/// Signed bytes of a signed document: the document with its trailing `proof` member removed
+/// `None` when the document does not end with a `proof` object, as
+/// `signed_document` writes it.
+pub fn unsigned_document(document: &str) -> Option<String> {
+    let body = document.trim().strip_suffix('}')?;
+    let start = body.rfind(r#","proof":"#)?;
+    let proof: serde_json::Value = serde_json::from_str(&body[start + r#","proof":"#.len()..]).ok()?;
+    proof.is_object().then(|| format!("{}}}", &body[..start]))
+}
+
+/// Write the signing JSON without its closing brace, so a proof can follow
+/// Every value is serialized straight into `out` rather than into its own string
+fn write_unclosed(out: &mut Vec<u8>, params: &SigningJsonParams) {
+    out.extend_from_slice(br#"{"@context":"#);
+    write_json(out, &params.context);
+    out.extend_from_slice(br#","id":"#);
+    write_json(out, &params.id);
+    out.extend_from_slice(br#","type":"#);
+    write_json(out, &params.r#type);
+    out.extend_from_slice(br#","issuer":"#);
+    write_json(out, &params.issuer);
+    out.extend_from_slice(br#","validFrom":"#);
+    write_json(out, &params.valid_from);
+    out.extend_from_slice(br#","credentialSubject":{"id":"#);
+    write_json(out, &params.subject_id);
+    out.extend_from_slice(br#","type":"#);
+    write_json(out, &params.subject_type);
+    out.extend_from_slice(br#","achievement":"#);
+    write_json(out, &params.achievement);
+    out.push(b'}');
+    if let Some(holder) = &params.holder {
+        out.extend_from_slice(br#","holder":"#);
+        write_json(out, &crate::holder_binding::holder_json(holder));
+    }
+    if let Some(commitment) = &params.encrypted_payload {
+        out.extend_from_slice(br#","encryptedPayload":"#);
+        write_json(out, &crate::encrypted_payload::commitment_json(commitment));
+    }
+}
+
+/// Compact JSON encoding of a value, appended to `out`
+fn write_json<T: serde::Serialize + ?Sized>(out: &mut Vec<u8>, value: &T) {
+    // Writing strings and string lists into a Vec cannot fail
+    let _ = serde_json::to_writer(&mut *out, value);
+}
+
+/// Bytes the signing JSON takes before any escaping, to size the buffer once
+fn capacity(params: &SigningJsonParams) -> usize {
+    let list = |values: &[String]| values.iter().map(|value| value.len() + 3).sum::<usize>() + 2;
+    160 + list(&params.context)
+        + list(&params.r#type)
+        + list(&params.subject_type)
+        + params.id.len()
+        + params.issuer.len()
+        + params.valid_from.len()
+        + params.subject_id.len()
+        + params.achievement.len()
+        + params.holder.map_or(0, |_| 320)
+        + params.encrypted_payload.map_or(0, |_| 160)
+}
+
+/// serde_json only writes UTF-8, so the conversion cannot fail
+fn into_string(out: Vec<u8>) -> String {
+    String::from_utf8(out).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{fixture_credential, sign_credential, TestKeypair};
+
+    const EXPECTED: &str = r#"{"@context":["https://www.w3.org/ns/credentials/v2","https://purl.imsglobal.org/spec/ob/v3p0/context-3.0.3.json"],"id":"did:sol:cred","type":["VerifiableCredential","OpenBadgeCredential"],"issuer":"did:sol:issuer","validFrom":"2024-01-01T00:00:00Z","credentialSubject":{"id":"did:sol:alice","type":["AchievementSubject"],"achievement":"did:sol:badge"}}"#;
+
+    fn params() -> SigningJsonParams {
+        SigningJsonParams::new("did:sol:cred", "did:sol:issuer", "2024-01-01T00:00:00Z", "did:sol:alice", "did:sol:badge")
+    }
+
+    #[test]
+    fn test_default_layout_is_stable() {
+        assert_eq!(build_signing_json(&params()), EXPECTED);
+        let parsed: serde_json::Value = serde_json::from_str(EXPECTED).unwrap();
+        assert_eq!(parsed["credentialSubject"]["achievement"], "did:sol:badge");
+    }
+
+    #[test]
+    fn test_buffer_is_sized_once() {
+        let params = params();
+        assert!(build_signing_json(&params).len() <= capacity(&params));
+    }
+
+    #[test]
+    fn test_every_field_is_covered() {
+        let base = build_signing_json(&params());
+        let variants: [fn(&mut SigningJsonParams); 10] = [
+            |p| p.context.push("https://example.com/ctx".to_string()),
+            |p| p.id.push('x'),
+            |p| p.r#type.push("Extra".to_string()),
+            |p| p.issuer.push('x'),
+            |p| p.valid_from = "2024-01-02T00:00:00Z".to_string(),
+            |p| p.subject_id = "sol:alice".to_string(),
+            |p| p.subject_type.clear(),
+            |p| p.achievement.push('x'),
+            |p| p.holder = Some(Pubkey::new_from_array([7; 32])),
+            |p| p.encrypted_payload = Some([9; 32]),
+        ];
+        for change in variants {
+            let mut changed = params();
+            change(&mut changed);
+            assert_ne!(build_signing_json(&changed), base);
+        }
+    }
+
+    #[test]
+    fn test_holder_binding_is_signed() {
+        let holder = Pubkey::new_from_array([7; 32]);
+        let bound = params().with_holder(Some(holder));
+        let json: serde_json::Value = serde_json::from_str(&build_signing_json(&bound)).unwrap();
+        assert_eq!(json["holder"], crate::holder_binding::holder_json(&holder));
+        assert!(build_signing_json(&bound).len() <= capacity(&bound));
+
+        let mut credential = fixture_credential(&Pubkey::new_unique(), &Pubkey::new_unique(), &Pubkey::new_unique(), &holder);
+        credential.holder_binding = Some(holder);
+        assert_eq!(SigningJsonParams::for_credential(&credential).holder, Some(holder));
+        // A re-bound credential keeps the binding its original proof covers
+        crate::rebind::rebind(&mut credential, "did:sol:new".to_string(), "2024-02-01T00:00:00Z".to_string(), Pubkey::new_unique()).unwrap();
+        credential.holder_binding = Some(Pubkey::new_unique());
+        assert_eq!(SigningJsonParams::for_credential(&credential).holder, Some(holder));
+    }
+
+    #[test]
+    fn test_values_are_escaped() {
+        let mut injected = params();
+        injected.subject_id = r#"x","id":"did:sol:mallory"#.to_string();
+        let json: serde_json::Value = serde_json::from_str(&build_signing_json(&injected)).unwrap();
+        assert_eq!(json["credentialSubject"]["id"], injected.subject_id);
+        assert_eq!(json["credentialSubject"].as_object().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_credential_account_parameters() {
+        let credential_key = Pubkey::new_unique();
+        let issuer = Pubkey::new_unique();
+        let achievement = Pubkey::new_unique();
+        let recipient = Pubkey::new_unique();
+        let credential = fixture_credential(&credential_key, &issuer, &achievement, &recipient);
+        let expected = SigningJsonParams::new(
+            &format!("did:sol:{}", credential_key),
+            &format!("did:sol:{}", issuer),
+            &credential.valid_from,
+            &format!("did:sol:{}", recipient),
+            &format!("did:sol:{}", achievement),
+        );
+        assert_eq!(SigningJsonParams::for_credential(&credential), expected);
+    }
+
+    #[test]
+    fn test_signed_document_embeds_the_proof() {
+        let mut credential = fixture_credential(&Pubkey::new_unique(), &Pubkey::new_unique(), &Pubkey::new_unique(), &Pubkey::new_unique());
+        assert!(signed_document(&credential).is_none());
+
+        let authority = TestKeypair::from_seed(7);
+        let signature = sign_credential(&mut credential, &authority);
+        let mut document: serde_json::Value = serde_json::from_str(&signed_document(&credential).unwrap()).unwrap();
+        assert_eq!(document["proof"]["proofValue"], credential.proof[0].proof_value);
+
+        document.as_object_mut().unwrap().remove("proof");
+        let unsigned = build_signing_json(&SigningJsonParams::for_credential(&credential));
+        assert_eq!(document, serde_json::from_str::<serde_json::Value>(&unsigned).unwrap());
+        assert!(authority.verify(unsigned.as_bytes(), &signature));
+        assert_eq!(unsigned_document(&signed_document(&credential).unwrap()), Some(unsigned.clone()));
+        assert_eq!(unsigned_document(&unsigned), None);
+    }
+}
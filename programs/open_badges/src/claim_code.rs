@@ -0,0 +1,150 @@
+//! Claim Codes for Recipients Without a Wallet
+//!
+//! Many learners have no Solana wallet when they earn a badge. The issuer
+//! can instead create a `ClaimOffer`, an escrow of the credential's terms
+//! keyed by a claim key: the Ed25519 public key whose 32-byte seed is the
+//! SHA-256 hash of a secret code handed to the learner out of band. When the
+//! learner later creates a wallet they derive the claim key from the code
+//! and sign their wallet's address with it; `claim_with_code` verifies that
+//! signature through an Ed25519 program instruction, issues the credential
+//! bound to that wallet and closes the offer. Offers expire; an expired or
+//! unwanted offer can be cancelled by the issuer to reclaim its rent.
+//!
+//! The code itself never appears on-chain. A claim transaction seen in
+//! flight carries only a signature for the learner's wallet, so it cannot be
+//! redirected to another one. Codes should carry enough entropy that they
+//! cannot be guessed from the published claim key.
+
+use anchor_lang::prelude::*;
+use crate::common::errors::ValidationError;
+use crate::{proof, results};
+
+/// Seed prefix for claim offer PDAs: [CLAIM_OFFER_SEED, issuer, claim_key]
+pub const CLAIM_OFFER_SEED: &[u8] = b"claim_offer";
+
+/// Domain separator of the message a claim key signs
+pub const CLAIM_MESSAGE_PREFIX: &[u8] = b"open-badges-claim:";
+
+/// Unclaimed credential held for whoever presents the code
+#[account]
+pub struct ClaimOffer {
+    /// Issuer profile that made the offer
+    pub issuer: Pubkey,
+    /// Achievement the credential awards
+    pub achievement: Pubkey,
+    /// Ed25519 public key derived from the claim code
+    pub claim_key: Pubkey,
+    /// Results the credential will carry
+    pub results: Vec<results::ResultInput>,
+    /// When the offer was made (ISO 8601 string)
+    pub offered_at: String,
+    /// Unix time after which the offer can no longer be claimed
+    pub expires_at: i64,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl ClaimOffer {
+    /// Account size of an offer carrying `results`
+    pub fn space(results: &[results::ResultInput]) -> usize {
+        8 + 32 + 32 + 32 + results::space(results) + 4 + crate::refresh::MAX_TIMESTAMP_LEN + 8 + 1
+    }
+
+    /// Fail unless the offer's claim key signed `claim_message(offer, recipient)`
+    /// in an earlier Ed25519 program instruction and the offer has not expired at `now`
+    pub fn ensure_claimable(
+        &self,
+        offer: &Pubkey,
+        recipient: &Pubkey,
+        instructions: &AccountInfo,
+        signature: &[u8; 64],
+        now: i64,
+    ) -> Result<()> {
+        proof::ensure_ed25519_verified(instructions, &self.claim_key, &claim_message(offer, recipient), signature)
+            .map_err(|_| error!(ValidationError::InvalidClaimCode))?;
+        if now > self.expires_at {
+            msg!("❌ Claim offer expired at {}", self.expires_at);
+            return Err(error!(ValidationError::ClaimOfferExpired));
+        }
+        Ok(())
+    }
+}
+
+/// Ed25519 seed of the claim key for a claim code
+pub fn claim_key_seed(secret: &[u8]) -> [u8; 32] {
+    anchor_lang::solana_program::hash::hash(secret).to_bytes()
+}
+
+/// Message the claim key signs to bind `offer` to `recipient`
+pub fn claim_message(offer: &Pubkey, recipient: &Pubkey) -> Vec<u8> {
+    [CLAIM_MESSAGE_PREFIX, offer.as_ref(), recipient.as_ref()].concat()
+}
+
+/// Fail unless an offer made at `now` would expire in the future
+pub fn validate_expiry(expires_at: i64, now: i64) -> Result<()> {
+    if expires_at <= now {
+        return Err(error!(ValidationError::ClaimOfferExpired));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{ed25519_verify_instruction, with_instructions_sysvar, TestKeypair};
+
+    const SECRET: &[u8] = b"correct horse battery staple";
+
+    fn offer(claim_key: Pubkey, expires_at: i64) -> ClaimOffer {
+        ClaimOffer {
+            issuer: Pubkey::new_unique(),
+            achievement: Pubkey::new_unique(),
+            claim_key,
+            results: vec![],
+            offered_at: "2025-01-01T00:00:00Z".to_string(),
+            expires_at,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn test_only_the_code_claims_before_expiry() {
+        let code_key = TestKeypair::from_secret_key(claim_key_seed(SECRET));
+        let offer_key = Pubkey::new_unique();
+        let learner = Pubkey::new_unique();
+        let message = claim_message(&offer_key, &learner);
+        let signature = code_key.sign(&message);
+        let offer = offer(code_key.pubkey(), 1_000);
+
+        with_instructions_sysvar(&[ed25519_verify_instruction(&code_key, &message)], |instructions| {
+            assert!(offer.ensure_claimable(&offer_key, &learner, instructions, &signature, 1_000).is_ok());
+            assert!(offer.ensure_claimable(&offer_key, &learner, instructions, &signature, 1_001).is_err());
+            // A front-runner cannot reuse the signature for their own wallet
+            assert!(offer.ensure_claimable(&offer_key, &Pubkey::new_unique(), instructions, &signature, 500).is_err());
+        });
+
+        let wrong_code = TestKeypair::from_secret_key(claim_key_seed(b"wrong code"));
+        let forged = wrong_code.sign(&message);
+        with_instructions_sysvar(&[ed25519_verify_instruction(&wrong_code, &message)], |instructions| {
+            assert!(offer.ensure_claimable(&offer_key, &learner, instructions, &forged, 500).is_err());
+        });
+    }
+
+    #[test]
+    fn test_offers_must_expire_in_the_future() {
+        assert!(validate_expiry(101, 100).is_ok());
+        assert!(validate_expiry(100, 100).is_err());
+    }
+
+    #[test]
+    fn test_space_matches_serialized_offer() {
+        let mut offer = offer(Pubkey::new_unique(), 0);
+        offer.offered_at = "x".repeat(crate::refresh::MAX_TIMESTAMP_LEN);
+        offer.results = vec![results::ResultInput {
+            result_description: "urn:result:score".to_string(),
+            value: Some("95".to_string()),
+            achieved_level: None,
+        }];
+        assert_eq!(offer.try_to_vec().unwrap().len() + 8, ClaimOffer::space(&offer.results));
+    }
+}
@@ -0,0 +1,163 @@
+//! Comprehensive Learner Record (CLR 2.0)
+//!
+//! An issuer bundles credentials held by one recipient into a `ClrCredential`
+//! for transcript-style exports. The CLR embeds each credential's JSON-LD
+//! document, proof included, in its `ClrSubject` and carries its own Data
+//! Integrity proof by the issuer authority. As with presentations, the
+//! authority signs the CLR JSON with `proofValue` left out.
+
+use anchor_lang::prelude::*;
+use crate::common::errors::ValidationError;
+
+/// JSON-LD context of CLR 2.0
+pub const CLR_CONTEXT: &str = "https://purl.imsglobal.org/spec/clr/v2p0/context-2.0.1.json";
+
+/// Maximum number of credentials in one CLR
+pub const MAX_CLR_CREDENTIALS: usize = 4;
+
+/// Maximum length of a CLR name
+pub const MAX_CLR_NAME_LEN: usize = 100;
+
+/// Builder for a recipient's ClrCredential
+#[derive(Clone, Debug)]
+pub struct ClrCredential {
+    id: String,
+    name: String,
+    issuer: Pubkey,
+    issuer_name: String,
+    recipient: Pubkey,
+    valid_from: String,
+    credentials: Vec<serde_json::Value>,
+}
+
+impl ClrCredential {
+    /// Empty CLR issued by the `issuer` profile to `recipient`
+    pub fn new(id: &str, name: &str, issuer: Pubkey, issuer_name: &str, recipient: Pubkey, valid_from: &str) -> Result<Self> {
+        if id.is_empty() || name.is_empty() {
+            return Err(error!(ValidationError::MissingRequiredField));
+        }
+        if name.len() > MAX_CLR_NAME_LEN {
+            return Err(error!(ValidationError::NameTooLong));
+        }
+        Ok(Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            issuer,
+            issuer_name: issuer_name.to_string(),
+            recipient,
+            valid_from: valid_from.to_string(),
+            credentials: Vec::new(),
+        })
+    }
+
+    /// Add a credential document
+    pub fn add_credential(&mut self, document: serde_json::Value) -> Result<()> {
+        if self.credentials.len() >= MAX_CLR_CREDENTIALS {
+            return Err(error!(ValidationError::TooManyItems));
+        }
+        self.credentials.push(document);
+        Ok(())
+    }
+
+    /// The CLR with its proof options but without `proofValue`
+    pub fn unsigned(&self) -> Result<serde_json::Value> {
+        if self.credentials.is_empty() {
+            return Err(error!(ValidationError::EmptyBatch));
+        }
+        let issuer_did = format!("did:sol:{}", self.issuer);
+        Ok(serde_json::json!({
+            "@context": [
+                "https://www.w3.org/ns/credentials/v2",
+                CLR_CONTEXT,
+                "https://purl.imsglobal.org/spec/ob/v3p0/context-3.0.3.json"
+            ],
+            "id": self.id,
+            "type": ["VerifiableCredential", "ClrCredential"],
+            "issuer": {
+                "id": issuer_did,
+                "type": ["Profile"],
+                "name": self.issuer_name
+            },
+            "name": self.name,
+            "validFrom": self.valid_from,
+            "credentialSubject": {
+                "id": format!("did:sol:{}", self.recipient),
+                "type": ["ClrSubject"],
+                "verifiableCredential": self.credentials
+            },
+            "proof": {
+                "type": "DataIntegrityProof",
+                "cryptosuite": "eddsa-rdfc-2022",
+                "created": self.valid_from,
+                "proofPurpose": "assertionMethod",
+                "verificationMethod": issuer_did
+            }
+        }))
+    }
+
+    /// Bytes the issuer authority signs
+    pub fn signing_bytes(&self) -> Result<Vec<u8>> {
+        serde_json::to_vec(&self.unsigned()?).map_err(|_| error!(ValidationError::SerializationError))
+    }
+
+    /// The CLR with the authority's signature as a base58btc multibase `proofValue`
+    pub fn signed(&self, signature: &[u8]) -> Result<serde_json::Value> {
+        let mut clr = self.unsigned()?;
+        clr["proof"]["proofValue"] = serde_json::json!(crate::multibase::encode(signature));
+        Ok(clr)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::credential::credential_account_to_json;
+    use crate::test_utils::{fixture_credential, TestKeypair};
+
+    fn clr(recipient: Pubkey) -> ClrCredential {
+        ClrCredential::new("urn:uuid:clr-1", "Transcript", Pubkey::new_unique(), "Academy", recipient, "2025-01-01T00:00:00Z")
+            .unwrap()
+    }
+
+    #[test]
+    fn test_clr_embeds_recipient_credentials() {
+        let recipient = Pubkey::new_unique();
+        let mut builder = clr(recipient);
+        for _ in 0..2 {
+            let credential = fixture_credential(&Pubkey::new_unique(), &Pubkey::new_unique(), &Pubkey::new_unique(), &recipient);
+            builder.add_credential(credential_account_to_json(&credential)).unwrap();
+        }
+
+        let unsigned = builder.unsigned().unwrap();
+        assert_eq!(unsigned["type"][1], "ClrCredential");
+        assert_eq!(unsigned["@context"][1], CLR_CONTEXT);
+        assert_eq!(unsigned["credentialSubject"]["type"][0], "ClrSubject");
+        assert_eq!(unsigned["credentialSubject"]["id"], format!("did:sol:{}", recipient));
+        assert_eq!(unsigned["credentialSubject"]["verifiableCredential"].as_array().unwrap().len(), 2);
+        assert!(unsigned["proof"].get("proofValue").is_none());
+    }
+
+    #[test]
+    fn test_signature_covers_unsigned_clr() {
+        let authority = TestKeypair::from_seed(9);
+        let mut builder = clr(Pubkey::new_unique());
+        builder.add_credential(serde_json::json!({ "id": "urn:uuid:1" })).unwrap();
+
+        let signature = authority.sign(&builder.signing_bytes().unwrap());
+        let mut signed = builder.signed(&signature).unwrap();
+        let proof_value = signed["proof"].as_object_mut().unwrap().remove("proofValue").unwrap();
+        assert!(proof_value.as_str().unwrap().starts_with('z'));
+        assert!(authority.verify(&serde_json::to_vec(&signed).unwrap(), &signature));
+    }
+
+    #[test]
+    fn test_clr_bounds() {
+        let mut builder = clr(Pubkey::new_unique());
+        assert!(builder.unsigned().is_err());
+        for _ in 0..MAX_CLR_CREDENTIALS {
+            builder.add_credential(serde_json::json!({})).unwrap();
+        }
+        assert!(builder.add_credential(serde_json::json!({})).is_err());
+        assert!(ClrCredential::new("urn:uuid:clr", &"n".repeat(MAX_CLR_NAME_LEN + 1), Pubkey::new_unique(), "", Pubkey::new_unique(), "").is_err());
+    }
+}
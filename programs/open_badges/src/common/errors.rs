@@ -1,3 +1,18 @@
+//! Program Errors
+//!
+//! Every instruction fails with a `ValidationError`; the program has no other
+//! error enum. Anchor numbers the variants from 6000 in declaration order, so
+//! the numeric code of a variant is stable as long as new variants are only
+//! ever appended. Each variant belongs to an `ErrorCategory`, and categories
+//! that correspond to a step of the OB 3.0 verification algorithm (§ 9.1)
+//! name that step, so client SDKs can tell a holder which check failed.
+//!
+//! A string over its `MAX_*_LEN` fails with the error for its kind: names
+//! and titles with `NameTooLong`, descriptions with `DescriptionTooLong`,
+//! narratives with `NarrativeTooLong`, URLs with `UrlTooLong` and ids, DIDs
+//! and identifier URIs with `IdTooLong`. Any other string, such as a
+//! comment, a timestamp or an extension value, fails with `FieldTooLong`.
+
 use anchor_lang::prelude::*;
 
 #[error_code]
@@ -68,4 +83,256 @@ pub enum ValidationError {
     InvalidSignatureLength,
     #[msg("Invalid achievement ID format")]
     InvalidAchievementId,
+    #[msg("Recipient has opted out of receiving badges")]
+    RecipientOptedOut,
+    #[msg("Opt-out account does not match recipient")]
+    InvalidOptOutAccount,
+    #[msg("Revocation reason too long")]
+    RevocationReasonTooLong,
+    #[msg("Field exceeds maximum length")]
+    FieldTooLong,
+    #[msg("Too many items")]
+    TooManyItems,
+    #[msg("Assessment result has not been approved")]
+    ResultNotApproved,
+    #[msg("Assessment result has already been reviewed")]
+    ResultAlreadyReviewed,
+    #[msg("Peer threshold must be between 1 and the maximum allowed")]
+    InvalidPeerThreshold,
+    #[msg("Recipients cannot endorse themselves")]
+    SelfEndorsement,
+    #[msg("Endorser does not hold an active credential for this achievement")]
+    EndorserNotHolder,
+    #[msg("Not enough peer endorsements to issue this credential")]
+    PeerThresholdNotMet,
+    #[msg("Invalid URL")]
+    InvalidUrl,
+    #[msg("Nomination has already been reviewed")]
+    NominationNotPending,
+    #[msg("Result refers to a result description the achievement does not declare")]
+    UnknownResultDescription,
+    #[msg("Result value is not allowed by its result description")]
+    ResultValueNotAllowed,
+    #[msg("Achieved level is not part of the achievement rubric")]
+    UnknownAchievedLevel,
+    #[msg("Credential was not issued to the presentation holder")]
+    HolderMismatch,
+    #[msg("Cross-program invocation of this instruction is not allowed for the issuer")]
+    CpiNotAllowed,
+    #[msg("Revocation list does not match the anchored status pointer")]
+    StatusListMismatch,
+    #[msg("Image data is not a well-formed badge image")]
+    InvalidBadgeImage,
+    #[msg("Badge image already carries a baked credential")]
+    AlreadyBaked,
+    #[msg("Signed payload was already used")]
+    PayloadReplayed,
+    #[msg("Batch limits are out of range")]
+    InvalidBatchConfig,
+    #[msg("Batch exceeds the compute budget for its payload sizes")]
+    BatchComputeBudgetExceeded,
+    #[msg("Program config account is not the program config PDA")]
+    InvalidProgramConfig,
+    #[msg("Program is paused by its admin")]
+    ProgramPaused,
+    #[msg("Fee account is missing or does not match the configured fee")]
+    InvalidFeeAccount,
+    #[msg("Treasury does not hold enough to withdraw")]
+    InsufficientTreasuryBalance,
+    #[msg("Credential has not reached its validUntil date")]
+    CredentialNotExpired,
+    #[msg("Credential is already marked expired")]
+    AlreadyExpired,
+    #[msg("achievementType is not an OB 3.0 term or an ext: extension term")]
+    InvalidAchievementType,
+    #[msg("Credential schema is not registered or its digest does not match the registry")]
+    InvalidCredentialSchema,
+    #[msg("Credential is bound to a different holder key")]
+    HolderBindingMismatch,
+    #[msg("Holder binding would reveal the wallet behind a pairwise subject")]
+    HolderBindingUnavailable,
+    #[msg("Instruction arguments use an unsupported layout version")]
+    UnsupportedArgsVersion,
+    #[msg("Credential cannot move to the requested lifecycle state")]
+    InvalidStatusTransition,
+    #[msg("Results do not meet the achievement's criteria thresholds")]
+    CriteriaNotMet,
+    #[msg("Issuer DID account is closed or no longer lists the proof's verification method")]
+    IssuerDidRevoked,
+    #[msg("Profile name must not be empty")]
+    InvalidProfileName,
+    #[msg("Profile URL must be an http(s) URL with a host")]
+    InvalidProfileUrl,
+    #[msg("Profile email is not a valid address")]
+    InvalidProfileEmail,
+    #[msg("Profile id must be a did:sol DID")]
+    InvalidProfileDid,
+    /// No longer returned: profile fields use the error for their kind
+    #[msg("Profile field exceeds its maximum length")]
+    ProfileFieldTooLong,
+    #[msg("Rate limit needs a non-zero issuance count and window")]
+    InvalidRateLimit,
+    #[msg("Issuer is frozen and cannot issue credentials")]
+    IssuerFrozen,
+    #[msg("Issuer has reached its issuance rate limit for the current window")]
+    IssuanceRateLimited,
+    #[msg("Claim code does not match the offer")]
+    InvalidClaimCode,
+    #[msg("Claim offer has expired")]
+    ClaimOfferExpired,
+    #[msg("termsOfUse entries need a type and an absolute policy URI")]
+    InvalidTermsOfUse,
+    #[msg("otherIdentifier entries need an identifier and an IdentifierTypeEnum or ext: identifierType")]
+    InvalidIdentifier,
+    #[msg("Unauthorized issuer")]
+    UnauthorizedIssuer,
+    #[msg("Credential already revoked")]
+    AlreadyRevoked,
+    #[msg("Credential is suspended")]
+    CredentialSuspended,
+    #[msg("Credential is not valid before its validFrom date")]
+    CredentialNotYetValid,
+    #[msg("Credential is past its validUntil date")]
+    CredentialExpired,
+    #[msg("Team credentials need a team id of at most 32 bytes and 1 to 10 distinct members")]
+    InvalidTeam,
+    #[msg("Accreditations need a scope of at most 128 bytes and a validity period that ends after it starts")]
+    InvalidAccreditation,
+    #[msg("Scoped authorizations need at least one distinct scope and an expiry in the future")]
+    InvalidScopedAuthorization,
+    #[msg("The service's authorization does not grant this scope for this achievement")]
+    ScopeNotGranted,
+    #[msg("The service's authorization has expired")]
+    AuthorizationExpired,
+    #[msg("Translations need a well-formed BCP 47 language tag, a non-empty text and one entry per language")]
+    InvalidTranslation,
+    #[msg("Name exceeds its maximum length")]
+    NameTooLong,
+    #[msg("Description exceeds its maximum length")]
+    DescriptionTooLong,
+    #[msg("Narrative exceeds its maximum length")]
+    NarrativeTooLong,
+    #[msg("URL exceeds its maximum length")]
+    UrlTooLong,
+    #[msg("Identifier exceeds its maximum length")]
+    IdTooLong,
+    #[msg("Credential has been superseded by a newer version")]
+    CredentialSuperseded,
+    #[msg("Invalid encrypted payload")]
+    InvalidEncryptedPayload,
+    #[msg("Pairwise subject ids are urn:ob-pairwise: followed by 32 base58-encoded bytes")]
+    InvalidPairwiseSubjectId,
+}
+
+/// Numeric code of the first `ValidationError` variant
+pub const ERROR_CODE_BASE: u32 = anchor_lang::error::ERROR_CODE_OFFSET;
+
+/// Group of related errors
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ErrorCategory {
+    /// The credential does not conform to the specification (§ 9.1 step 1)
+    Conformance,
+    /// The proof method is not satisfied (§ 9.1 step 2)
+    Proof,
+    /// The credential could not be refreshed (§ 9.1 step 3)
+    Refresh,
+    /// The credential is revoked, suspended, expired or not yet valid (§ 9.1 step 4)
+    Status,
+    /// The recipient could not be verified (§ 9.1 step 5, § 9.3)
+    Recipient,
+    /// An issuer DID or key could not be resolved
+    Did,
+    /// The signer may not perform the operation
+    Authorization,
+    /// Issuance was refused by issuer, recipient or program policy
+    Issuance,
+    /// An input exceeds a size or count limit
+    Limits,
+    /// Program-internal failures
+    Internal,
+}
+
+impl ErrorCategory {
+    /// OB 3.0 verification assertion the category reports on, if any
+    pub fn conformance_assertion(&self) -> Option<&'static str> {
+        match self {
+            Self::Conformance => Some("ob3.verification.9.1.1"),
+            Self::Proof => Some("ob3.verification.9.1.2"),
+            Self::Refresh => Some("ob3.verification.9.1.3"),
+            Self::Status => Some("ob3.verification.9.1.4"),
+            Self::Recipient => Some("ob3.verification.9.1.5"),
+            _ => None,
+        }
+    }
+}
+
+impl ValidationError {
+    /// Stable numeric code, as returned in the transaction error
+    pub fn code(self) -> u32 {
+        ERROR_CODE_BASE + self as u32
+    }
+
+    /// Category the error belongs to
+    pub fn category(self) -> ErrorCategory {
+        use ValidationError::*;
+        match self {
+            InvalidJson | MissingRequiredField | InvalidCredentialType | UnsupportedFormat | InvalidTimestampFormat
+            | InvalidAchievementId | InvalidUrl | UnknownResultDescription | ResultValueNotAllowed
+            | UnknownAchievedLevel | InvalidBadgeImage | InvalidAchievementType | InvalidCredentialSchema
+            | InvalidProfileName | InvalidProfileUrl | InvalidProfileEmail | InvalidProfileDid
+            | InvalidTermsOfUse | InvalidIdentifier | UnsupportedArgsVersion | InvalidTeam
+            | InvalidTranslation | InvalidEncryptedPayload | InvalidPairwiseSubjectId => ErrorCategory::Conformance,
+            InvalidProof | InvalidProofValue | InvalidJwtFormat | InvalidBase64Encoding | InvalidSignature
+            | InvalidSignatureLength | PayloadReplayed => ErrorCategory::Proof,
+            CredentialNotExpired => ErrorCategory::Refresh,
+            IndexOutOfBounds | InvalidEncodedList | StatusListMismatch | AlreadyExpired | InvalidStatusTransition
+            | AlreadyRevoked | CredentialSuspended | CredentialNotYetValid | CredentialExpired
+            | CredentialSuperseded => ErrorCategory::Status,
+            HolderMismatch | HolderBindingMismatch | HolderBindingUnavailable => ErrorCategory::Recipient,
+            InvalidKey | MissingKeyFragment | VerificationMethodNotFound | NoPublicKeyFound | UnsupportedKeyEncoding
+            | UnsupportedKeyType | InvalidSolanaPublicKey | InvalidKeyEncoding | InvalidKeyLength | InvalidDid
+            | UnsupportedDidMethod | IssuerDidRevoked => ErrorCategory::Did,
+            UnauthorizedAccess | InvalidOptOutAccount | CpiNotAllowed | InvalidProgramConfig | InvalidFeeAccount
+            | UnauthorizedIssuer | InvalidClaimCode | InvalidAccreditation | InvalidScopedAuthorization | ScopeNotGranted
+            | AuthorizationExpired => ErrorCategory::Authorization,
+            RecipientOptedOut | ResultNotApproved | ResultAlreadyReviewed | InvalidPeerThreshold | SelfEndorsement
+            | EndorserNotHolder | PeerThresholdNotMet | NominationNotPending | AlreadyBaked | ProgramPaused
+            | InsufficientTreasuryBalance | CriteriaNotMet | InvalidRateLimit | IssuerFrozen | IssuanceRateLimited
+            | ClaimOfferExpired => ErrorCategory::Issuance,
+            InvalidCapacity | BatchSizeTooLarge | EmptyBatch | RevocationReasonTooLong | FieldTooLong | TooManyItems
+            | InvalidBatchConfig | BatchComputeBudgetExceeded | ProfileFieldTooLong | NameTooLong | DescriptionTooLong
+            | NarrativeTooLong | UrlTooLong | IdTooLong => ErrorCategory::Limits,
+            ValidationFailed | NotImplemented | SerializationError | SerializationFailed => ErrorCategory::Internal,
+        }
+    }
+
+    /// OB 3.0 verification assertion the error reports on, if any
+    pub fn conformance_assertion(self) -> Option<&'static str> {
+        self.category().conformance_assertion()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_codes_are_stable() {
+        // Published codes; new variants must be appended after the last one
+        assert_eq!(ValidationError::InvalidJson.code(), 6000);
+        assert_eq!(ValidationError::InvalidCapacity.code(), 6020);
+        assert_eq!(ValidationError::RecipientOptedOut.code(), 6033);
+        assert_eq!(ValidationError::IssuerFrozen.code(), 6077);
+        assert_eq!(ValidationError::InvalidIdentifier.code(), 6082);
+        assert_eq!(ValidationError::AlreadyRevoked.code(), 6084);
+        assert_eq!(u32::from(ValidationError::UnauthorizedIssuer), ValidationError::UnauthorizedIssuer.code());
+    }
+
+    #[test]
+    fn test_categories_name_verification_steps() {
+        assert_eq!(ValidationError::InvalidSignature.conformance_assertion(), Some("ob3.verification.9.1.2"));
+        assert_eq!(ValidationError::AlreadyRevoked.conformance_assertion(), Some("ob3.verification.9.1.4"));
+        assert_eq!(ValidationError::HolderMismatch.category(), ErrorCategory::Recipient);
+        assert_eq!(ValidationError::UnauthorizedIssuer.conformance_assertion(), None);
+    }
 }
@@ -6,6 +6,7 @@
 use anchor_lang::prelude::*;
 use serde_json::Value;
 use crate::common::errors::ValidationError;
+use crate::validation::{self, AchievementType};
 
 /// Comprehensive validation suite for Open Badges v3.0
 pub struct ComplianceValidator {
@@ -20,6 +21,12 @@ pub struct ComplianceValidator {
     
     /// Enable credential status checking
     pub check_status: bool,
+
+    /// Accepted `achievementType` terms; empty accepts any valid term
+    pub allowed_achievement_types: Vec<AchievementType>,
+
+    /// Unix time validity periods are checked against; see `at`
+    pub now: Option<i64>,
 }
 
 impl ComplianceValidator {
@@ -30,6 +37,8 @@ impl ComplianceValidator {
             vccs_conformance: true,
             verify_proofs: true,
             check_status: true,
+            allowed_achievement_types: Vec::new(),
+            now: None,
         }
     }
     
@@ -40,6 +49,8 @@ impl ComplianceValidator {
             vccs_conformance: true,
             verify_proofs: true,
             check_status: true,
+            allowed_achievement_types: Vec::new(),
+            now: None,
         }
     }
     
@@ -50,8 +61,32 @@ impl ComplianceValidator {
             vccs_conformance: true,
             verify_proofs: false,
             check_status: false,
+            allowed_achievement_types: Vec::new(),
+            now: None,
         }
     }
+
+    /// Only accept achievements of the given `achievementType` terms
+    pub fn with_achievement_types(mut self, achievement_types: Vec<AchievementType>) -> Self {
+        self.allowed_achievement_types = achievement_types;
+        self
+    }
+    
+    /// Check validity periods against `now`
+    /// On-chain callers pass the Clock sysvar time; off-chain builds fall back
+    /// to the system clock when no time is given
+    pub fn at(mut self, now: i64) -> Self {
+        self.now = Some(now);
+        self
+    }
+
+    /// Time to check validity periods against, if any is known
+    fn current_time(&self) -> Option<i64> {
+        #[cfg(feature = "offchain")]
+        return self.now.or_else(|| Some(crate::system_unix_timestamp()));
+        #[cfg(not(feature = "offchain"))]
+        return self.now;
+    }
     
     /// Validate a complete Open Badge credential
     pub fn validate_credential(&self, credential_json: &str) -> Result<ValidationReport> {
@@ -100,6 +135,9 @@ impl ComplianceValidator {
         // Step 12: Evidence validation (if present)
         self.validate_evidence(&credential, &mut report)?;
         
+        // Step 13: Terms of use validation (if present)
+        self.validate_terms_of_use(&credential, &mut report)?;
+        
         // Final compliance score
         report.calculate_compliance_score();
         
@@ -132,21 +170,12 @@ impl ComplianceValidator {
             .and_then(|c| c.as_array())
             .ok_or_else(|| error!(ValidationError::MissingRequiredField))?;
         
-        // Required contexts for Open Badges v3.0
-        let required_contexts = [
-            "https://www.w3.org/ns/credentials/v2",
-            "https://purl.imsglobal.org/spec/ob/v3p0/context-3.0.3.json",
-        ];
-        
-        for required in required_contexts {
-            let found = contexts.iter().any(|ctx| {
-                ctx.as_str().map_or(false, |s| s == required)
-            });
-            
-            if found {
-                report.add_success(format!("Context '{}' present", required));
+        let values: Vec<&str> = contexts.iter().filter_map(Value::as_str).collect();
+        for rule in crate::validation::CREDENTIAL_CONTEXT_RULES {
+            if rule.is_met(values.iter().copied()) {
+                report.add_success(format!("{} present", rule.description));
             } else {
-                report.add_error(format!("Missing required context '{}'", required));
+                report.add_error(format!("Missing required context: {}", rule.description));
                 if self.strict_mode {
                     return Err(error!(ValidationError::MissingRequiredField));
                 }
@@ -187,18 +216,12 @@ impl ComplianceValidator {
             .and_then(|t| t.as_array())
             .ok_or_else(|| error!(ValidationError::InvalidCredentialType))?;
         
-        // Required types for Open Badges v3.0
-        let required_types = ["VerifiableCredential", "OpenBadgeCredential"];
-        
-        for required in required_types {
-            let found = types.iter().any(|t| {
-                t.as_str().map_or(false, |s| s == required)
-            });
-            
-            if found {
-                report.add_success(format!("Required type '{}' present", required));
+        let values: Vec<&str> = types.iter().filter_map(Value::as_str).collect();
+        for rule in crate::validation::CREDENTIAL_TYPE_RULES {
+            if rule.is_met(values.iter().copied()) {
+                report.add_success(format!("{} present", rule.description));
             } else {
-                report.add_error(format!("Missing required type '{}'", required));
+                report.add_error(format!("Missing required type: {}", rule.description));
                 if self.strict_mode {
                     return Err(error!(ValidationError::InvalidCredentialType));
                 }
@@ -314,6 +337,36 @@ impl ComplianceValidator {
                 report.add_error("Missing Achievement type".to_string());
             }
         }
+
+        // Validate achievementType against the OB 3.0 vocabulary and the filter
+        match achievement.get("achievementType").map(|t| t.as_str()) {
+            Some(Some(achievement_type)) if validation::validate_achievement_type_term(achievement_type).is_ok() => {
+                let allowed = self.allowed_achievement_types.is_empty()
+                    || AchievementType::parse(achievement_type)
+                        .is_some_and(|t| self.allowed_achievement_types.contains(&t));
+                if allowed {
+                    report.add_success(format!("Valid achievementType '{}'", achievement_type));
+                } else {
+                    report.add_error(format!("achievementType '{}' is not accepted", achievement_type));
+                    if self.strict_mode {
+                        return Err(error!(ValidationError::InvalidAchievementType));
+                    }
+                }
+            }
+            Some(_) => {
+                report.add_error("Invalid achievementType".to_string());
+                if self.strict_mode {
+                    return Err(error!(ValidationError::InvalidAchievementType));
+                }
+            }
+            None if !self.allowed_achievement_types.is_empty() => {
+                report.add_error("Missing achievementType".to_string());
+                if self.strict_mode {
+                    return Err(error!(ValidationError::InvalidAchievementType));
+                }
+            }
+            None => {}
+        }
         
         // Validate criteria structure
         if let Some(criteria) = achievement.get("criteria") {
@@ -329,12 +382,15 @@ impl ComplianceValidator {
     
     /// Validate temporal constraints
     fn validate_temporal_constraints(&self, credential: &Value, report: &mut ValidationReport) -> Result<()> {
-        let now = chrono::Utc::now();
+        let Some(now) = self.current_time() else {
+            report.add_warning("Validity period not checked: no current time supplied".to_string());
+            return Ok(());
+        };
         
         // Check validFrom
         if let Some(valid_from) = credential.get("validFrom")
             .and_then(|v| v.as_str()) {
-            match chrono::DateTime::parse_from_rfc3339(valid_from) {
+            match crate::parse_iso8601_to_unix(valid_from) {
                 Ok(from_time) => {
                     if from_time <= now {
                         report.add_success("Credential is valid (not before constraint met)".to_string());
@@ -351,7 +407,7 @@ impl ComplianceValidator {
         // Check validUntil (if present)
         if let Some(valid_until) = credential.get("validUntil")
             .and_then(|v| v.as_str()) {
-            match chrono::DateTime::parse_from_rfc3339(valid_until) {
+            match crate::parse_iso8601_to_unix(valid_until) {
                 Ok(until_time) => {
                     if until_time >= now {
                         report.add_success("Credential not expired (validUntil constraint met)".to_string());
@@ -370,33 +426,40 @@ impl ComplianceValidator {
     
     /// Validate cryptographic proofs
     fn validate_proofs(&self, credential: &Value, report: &mut ValidationReport) -> Result<()> {
-        if let Some(proof) = credential.get("proof") {
-            // Validate proof structure
-            if let Some(proof_type) = proof.get("type") {
-                if proof_type.as_str() == Some("DataIntegrityProof") {
-                    report.add_success("Valid proof type".to_string());
-                } else {
-                    report.add_warning("Non-standard proof type".to_string());
+        if let Some(proof_entry) = credential.get("proof") {
+            // A proof set or chain is an array of proofs
+            let proofs: Vec<&Value> = match proof_entry {
+                Value::Array(proofs) => proofs.iter().collect(),
+                proof => vec![proof],
+            };
+            for proof in proofs {
+                // Validate proof structure
+                if let Some(proof_type) = proof.get("type") {
+                    if proof_type.as_str() == Some("DataIntegrityProof") {
+                        report.add_success("Valid proof type".to_string());
+                    } else {
+                        report.add_warning("Non-standard proof type".to_string());
+                    }
                 }
-            }
             
-            // Check for required proof properties
-            let required_proof_props = ["type", "cryptosuite", "created", "verificationMethod", "proofPurpose", "proofValue"];
+                // Check for required proof properties
+                let required_proof_props = ["type", "cryptosuite", "created", "verificationMethod", "proofPurpose", "proofValue"];
             
-            for prop in required_proof_props {
-                if proof.get(prop).is_some() {
-                    report.add_success(format!("Proof property '{}' present", prop));
-                } else {
-                    report.add_error(format!("Missing proof property '{}'", prop));
+                for prop in required_proof_props {
+                    if proof.get(prop).is_some() {
+                        report.add_success(format!("Proof property '{}' present", prop));
+                    } else {
+                        report.add_error(format!("Missing proof property '{}'", prop));
+                    }
                 }
-            }
             
-            // Validate cryptosuite
-            if let Some(cryptosuite) = proof.get("cryptosuite").and_then(|c| c.as_str()) {
-                if cryptosuite == "eddsa-rdfc-2022" {
-                    report.add_success("Standard cryptosuite used".to_string());
-                } else {
-                    report.add_warning("Non-standard cryptosuite".to_string());
+                // Validate cryptosuite
+                if let Some(cryptosuite) = proof.get("cryptosuite").and_then(|c| c.as_str()) {
+                    if cryptosuite == "eddsa-rdfc-2022" {
+                        report.add_success("Standard cryptosuite used".to_string());
+                    } else {
+                        report.add_warning("Non-standard cryptosuite".to_string());
+                    }
                 }
             }
         } else {
@@ -455,9 +518,34 @@ impl ComplianceValidator {
         Ok(())
     }
     
+    /// Validate terms of use (if present): each entry needs a type, and an id must be an absolute URI
+    fn validate_terms_of_use(&self, credential: &Value, report: &mut ValidationReport) -> Result<()> {
+        let Some(terms) = credential.get("termsOfUse") else {
+            report.add_info("No terms of use present".to_string());
+            return Ok(());
+        };
+        let Some(terms_array) = terms.as_array() else {
+            report.add_error("termsOfUse must be an array".to_string());
+            return Ok(());
+        };
+        for (idx, entry) in terms_array.iter().enumerate() {
+            let has_type = entry.get("type").and_then(|t| t.as_str()).is_some_and(|t| !t.is_empty());
+            let id_valid = match entry.get("id") {
+                None => true,
+                Some(id) => id.as_str().is_some_and(crate::terms_of_use::is_valid_id),
+            };
+            if has_type && id_valid {
+                report.add_success(format!("Terms of use entry {} valid", idx));
+            } else {
+                report.add_error(format!("Terms of use entry {} is malformed", idx));
+            }
+        }
+        Ok(())
+    }
+    
     /// Helper: Check if string is valid ISO 8601 timestamp
     fn is_valid_iso8601(&self, timestamp: &str) -> bool {
-        chrono::DateTime::parse_from_rfc3339(timestamp).is_ok()
+        crate::parse_iso8601_to_unix(timestamp).is_ok()
     }
     
     /// Helper: Check if string is valid DID or URL
@@ -537,3 +625,25 @@ impl ValidationReport {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validity_period_uses_the_supplied_time() {
+        let credential = serde_json::json!({
+            "validFrom": "2025-01-01T00:00:00Z",
+            "validUntil": "2026-01-01T00:00:00Z",
+        });
+        let check = |validator: ComplianceValidator| {
+            let mut report = ValidationReport::new();
+            validator.validate_temporal_constraints(&credential, &mut report).unwrap();
+            report
+        };
+
+        assert!(check(ComplianceValidator::new().at(1_750_000_000)).errors.is_empty());
+        assert_eq!(check(ComplianceValidator::new().at(1_800_000_000)).errors, vec!["Credential has expired".to_string()]);
+        assert_eq!(check(ComplianceValidator::new().at(1_700_000_000)).errors.len(), 1);
+    }
+}
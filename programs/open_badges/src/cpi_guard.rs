@@ -0,0 +1,142 @@
+//! Cross-Program Invocation Guard
+//!
+//! Issuance, proof upgrades and direct revocation act with the issuer's
+//! authority. Called from a transaction they always run; called through CPI
+//! they run only when the issuer's `CpiPolicy` enables the action and lists
+//! the program at the top of the transaction. That program is read from the
+//! instructions sysvar, which CPI callers must pass. Re-entry through this
+//! program itself is always rejected.
+//!
+//! CPI-callable, subject to the policy:
+//! - issuance (`issue_achievement_credential*`, including the `_cpi` entry
+//!   point) and `upgrade_credential_proof` under `allow_issuance`
+//! - `revoke_credential_direct` under `allow_revocation`
+//!
+//! Every other instruction either holds no issuer authority or is a view.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{get_stack_height, TRANSACTION_LEVEL_STACK_HEIGHT};
+use anchor_lang::solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
+use crate::common::errors::ValidationError;
+
+/// Seed for the CPI policy PDA: [CPI_POLICY_SEED, issuer]
+pub const CPI_POLICY_SEED: &[u8] = b"cpi_policy";
+
+/// Maximum number of programs an issuer can allow
+pub const MAX_ALLOWED_PROGRAMS: usize = 4;
+
+/// Which of an issuer's instructions other programs may invoke
+#[account]
+pub struct CpiPolicy {
+    /// Issuer profile the policy belongs to
+    pub issuer: Pubkey,
+    /// Issuance and proof upgrades are CPI-callable
+    pub allow_issuance: bool,
+    /// Direct revocation is CPI-callable
+    pub allow_revocation: bool,
+    /// Top-level programs allowed to invoke the enabled instructions
+    pub allowed_programs: Vec<Pubkey>,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl CpiPolicy {
+    pub const SPACE: usize = 8 + 32 + 1 + 1 + 4 + 32 * MAX_ALLOWED_PROGRAMS + 1;
+}
+
+/// Instruction class a guard protects
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GuardedAction {
+    Issuance,
+    Revocation,
+}
+
+/// Whether the current instruction runs inside a CPI
+pub fn is_cpi() -> bool {
+    get_stack_height() > TRANSACTION_LEVEL_STACK_HEIGHT
+}
+
+/// Check that `caller` may invoke `action` under `policy`
+pub fn check_caller(action: GuardedAction, policy: Option<&CpiPolicy>, caller: &Pubkey) -> Result<()> {
+    if *caller == crate::ID {
+        msg!("❌ Re-entrant invocation rejected");
+        return Err(error!(ValidationError::CpiNotAllowed));
+    }
+    let Some(policy) = policy else {
+        msg!("❌ Issuer has no CPI policy; {} may not invoke", caller);
+        return Err(error!(ValidationError::CpiNotAllowed));
+    };
+    let enabled = match action {
+        GuardedAction::Issuance => policy.allow_issuance,
+        GuardedAction::Revocation => policy.allow_revocation,
+    };
+    if !enabled || !policy.allowed_programs.contains(caller) {
+        msg!("❌ CPI policy does not allow {} for {:?}", caller, action);
+        return Err(error!(ValidationError::CpiNotAllowed));
+    }
+    Ok(())
+}
+
+/// Allow top-level calls; require the issuer's policy to allow CPI calls
+pub fn ensure_invocation_allowed(
+    action: GuardedAction,
+    policy: Option<&CpiPolicy>,
+    instructions: Option<&AccountInfo>,
+) -> Result<()> {
+    if !is_cpi() {
+        return Ok(());
+    }
+    let instructions = instructions.ok_or(error!(ValidationError::CpiNotAllowed))?;
+    let current = load_current_index_checked(instructions)?;
+    let top_level = load_instruction_at_checked(current as usize, instructions)?;
+    check_caller(action, policy, &top_level.program_id)
+}
+
+/// Check policy settings before storing them
+pub fn validate_allowed_programs(allowed_programs: &[Pubkey]) -> Result<()> {
+    if allowed_programs.len() > MAX_ALLOWED_PROGRAMS {
+        return Err(error!(ValidationError::TooManyItems));
+    }
+    if allowed_programs.contains(&crate::ID) {
+        return Err(error!(ValidationError::CpiNotAllowed));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(caller: Pubkey) -> CpiPolicy {
+        CpiPolicy {
+            issuer: Pubkey::new_unique(),
+            allow_issuance: true,
+            allow_revocation: false,
+            allowed_programs: vec![caller],
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn test_listed_program_may_issue_only() {
+        let caller = Pubkey::new_unique();
+        let policy = policy(caller);
+        assert!(check_caller(GuardedAction::Issuance, Some(&policy), &caller).is_ok());
+        assert!(check_caller(GuardedAction::Revocation, Some(&policy), &caller).is_err());
+        assert!(check_caller(GuardedAction::Issuance, Some(&policy), &Pubkey::new_unique()).is_err());
+    }
+
+    #[test]
+    fn test_missing_policy_and_reentry_are_rejected() {
+        assert!(check_caller(GuardedAction::Issuance, None, &Pubkey::new_unique()).is_err());
+        let policy = policy(crate::ID);
+        assert!(check_caller(GuardedAction::Issuance, Some(&policy), &crate::ID).is_err());
+        assert!(validate_allowed_programs(&[crate::ID]).is_err());
+    }
+
+    #[test]
+    fn test_top_level_calls_need_no_policy() {
+        assert!(!is_cpi());
+        assert!(ensure_invocation_allowed(GuardedAction::Revocation, None, None).is_ok());
+    }
+}
@@ -6,23 +6,109 @@
 use anchor_lang::prelude::*;
 use std::str::FromStr;
 use crate::common::errors::ValidationError;
-use crate::did::{DidDocument, DidMethod};
+use crate::did::{DidMethod, DidResolverRegistry, DidUrl, SolanaDidResolver};
+use crate::rubric::Rubric;
+use crate::{Achievement, Proof};
+use base64::{Engine, engine::general_purpose};
 use serde_json;
 
+/// Build the achievement criteria, attaching the rubric as an extension when present
+fn criteria_json(rubric: Option<&Rubric>) -> serde_json::Value {
+    let mut criteria = serde_json::json!({
+        "narrative": "Demonstrated competency in the specified area"
+    });
+    if let Some(rubric) = rubric {
+        criteria["rubric"] = rubric.to_extension_json();
+    }
+    criteria
+}
+
+/// Build an Open Badges Image object for an image URI
+fn image_json(uri: &str) -> serde_json::Value {
+    serde_json::json!({
+        "id": uri,
+        "type": "Image"
+    })
+}
+
+/// Open Badges Profile document of an issuer profile account
+pub fn profile_json(profile: &crate::Profile) -> serde_json::Value {
+    let mut document = serde_json::json!({
+        "@context": crate::canonical::CREDENTIAL_CONTEXT,
+        "id": profile.id,
+        "type": profile.r#type,
+        "name": profile.name,
+    });
+    if let Some(url) = &profile.url {
+        document["url"] = serde_json::json!(url);
+    }
+    if let Some(email) = &profile.email {
+        document["email"] = serde_json::json!(email);
+    }
+    if let Some(image) = &profile.image {
+        document["image"] = image_json(image);
+    }
+    crate::other_identifier::apply_to_json(&mut document, &profile.other_identifier);
+    crate::i18n::apply_to_json(&mut document, "name", &profile.name_i18n);
+    document
+}
+
+/// Build the achievement object, including its image and creator when set
+fn achievement_json(achievement_id: &str, achievement: &Achievement, rubric: Option<&Rubric>) -> serde_json::Value {
+    let mut achievement_json = serde_json::json!({
+        "id": achievement_id,
+        "type": ["Achievement"],
+        "name": achievement.name,
+        "description": achievement.description,
+        "criteria": criteria_json(rubric)
+    });
+    if let Some(image) = &achievement.image {
+        achievement_json["image"] = image_json(image);
+    }
+    if let Some(achievement_type) = &achievement.achievement_type {
+        achievement_json["achievementType"] = serde_json::json!(achievement_type);
+    }
+    if let Some(creator) = &achievement.creator {
+        achievement_json["creator"] = serde_json::json!({
+            "id": format!("did:sol:{}", creator),
+            "type": ["Profile"]
+        });
+    }
+    if !achievement.result_descriptions.is_empty() {
+        let descriptions: Vec<serde_json::Value> =
+            achievement.result_descriptions.iter().map(|d| d.to_json()).collect();
+        achievement_json["resultDescription"] = serde_json::json!(descriptions);
+    }
+    if !achievement.requirements.required_competencies.is_empty() {
+        let alignments: Vec<serde_json::Value> =
+            achievement.requirements.required_competencies.iter().map(|a| a.to_json()).collect();
+        achievement_json["alignment"] = serde_json::json!(alignments);
+    }
+    crate::extensions::apply_to_json(&mut achievement_json, &achievement.extensions);
+    crate::i18n::apply_to_json(&mut achievement_json, "name", &achievement.name_i18n);
+    crate::i18n::apply_to_json(&mut achievement_json, "description", &achievement.description_i18n);
+    achievement_json
+}
+
 /// Generate a credential in JSON-LD format for Open Badges 3.0
+#[allow(clippy::too_many_arguments)]
 pub fn generate_jsonld_credential(
     issuer_pubkey: &Pubkey,
+    issuer_image: Option<&str>,
+    refresh_service_url: Option<&str>,
+    terms_of_use: &[crate::terms_of_use::TermsOfUse],
     recipient_pubkey: &Pubkey,
     achievement_id: &str,
-    achievement_name: &str,
-    achievement_description: &str,
+    achievement: &Achievement,
     credential_id: &str,
+    rubric: Option<&Rubric>,
+    valid_from: &str,
 ) -> Result<String> {
     let issuer_did = format!("did:sol:{}", issuer_pubkey);
     let recipient_did = format!("did:sol:{}", recipient_pubkey);
     
     // Create JSON-LD credential structure compliant with Open Badges 3.0
-    let credential = serde_json::json!({
+    let mut credential = serde_json::json!({
         "@context": [
             "https://www.w3.org/ns/credentials/v2",
             "https://purl.imsglobal.org/spec/ob/v3p0/context-3.0.3.json"
@@ -33,46 +119,65 @@ pub fn generate_jsonld_credential(
             "id": issuer_did,
             "type": ["Profile"]
         },
-        "validFrom": chrono::Utc::now().to_rfc3339(),
+        "validFrom": valid_from,
         "credentialSubject": {
             "type": ["AchievementSubject"],
             "id": recipient_did,
-            "achievement": {
-                "id": achievement_id,
-                "type": ["Achievement"],
-                "name": achievement_name,
-                "description": achievement_description,
-                "criteria": {
-                    "narrative": "Demonstrated competency in the specified area"
-                }
-            }
+            "achievement": achievement_json(achievement_id, achievement, rubric)
         }
     });
+    if let Some(image) = issuer_image {
+        credential["issuer"]["image"] = image_json(image);
+    }
+    crate::refresh::apply_to_json(&mut credential, refresh_service_url);
+    crate::terms_of_use::apply_to_json(&mut credential, terms_of_use);
+    crate::extensions::extend_context_json(&mut credential, &achievement.extensions);
     
     let credential_json = serde_json::to_string_pretty(&credential)
         .map_err(|_| error!(ValidationError::ValidationFailed))?;
     
-    msg!("✅ Generated JSON-LD credential for achievement: {}", achievement_name);
+    msg!("✅ Generated JSON-LD credential for achievement: {}", achievement.name);
     Ok(credential_json)
 }
 
-/// Generate a credential in JWT format for Open Badges 3.0  
-pub fn generate_jwt_credential(
+/// Build the JWS signing input (`header.payload`) of a JWT credential
+///
+/// The payload is fully determined by its arguments, so the issuer authority
+/// can sign the string returned here and `compact_jwt` can rebuild the same
+/// input on-chain when the signature is submitted. The `kid` names the
+/// signer's did:sol key; `verify_jwt_credential` links it to `iss` through
+/// the issuer profile's authority. A holder-bound JWT carries the
+/// recipient's key as its `cnf` claim.
+#[allow(clippy::too_many_arguments)]
+pub fn jwt_signing_input(
     issuer_pubkey: &Pubkey,
+    signer: &Pubkey,
+    issuer_image: Option<&str>,
+    refresh_service_url: Option<&str>,
+    terms_of_use: &[crate::terms_of_use::TermsOfUse],
     recipient_pubkey: &Pubkey,
     achievement_id: &str,
-    achievement_name: &str,
-    achievement_description: &str,
+    achievement: &Achievement,
     credential_id: &str,
+    rubric: Option<&Rubric>,
+    issued_at: i64,
+    bind_holder: bool,
 ) -> Result<String> {
     let issuer_did = format!("did:sol:{}", issuer_pubkey);
     let recipient_did = format!("did:sol:{}", recipient_pubkey);
     
+    let header = serde_json::json!({
+        "alg": "EdDSA",
+        "typ": "JWT",
+        "kid": format!("did:sol:{}", signer)
+    });
+    
     // Create JWT payload structure compliant with Open Badges 3.0
-    let payload = serde_json::json!({
+    let mut payload = serde_json::json!({
         "iss": issuer_did,
         "sub": recipient_did,
-        "iat": chrono::Utc::now().timestamp(),
+        "iat": issued_at,
+        "jti": credential_id,
         "vc": {
             "@context": [
                 "https://www.w3.org/ns/credentials/v2",
@@ -80,39 +185,183 @@ pub fn generate_jwt_credential(
             ],
             "id": credential_id,
             "type": ["VerifiableCredential", "OpenBadgeCredential"],
+            "issuer": {
+                "id": issuer_did,
+                "type": ["Profile"]
+            },
             "credentialSubject": {
+                "id": recipient_did,
                 "type": ["AchievementSubject"],
-                "achievement": {
-                    "id": achievement_id,
-                    "type": ["Achievement"],
-                    "name": achievement_name,
-                    "description": achievement_description,
-                    "criteria": {
-                        "narrative": "Demonstrated competency in the specified area"
-                    }
-                }
+                "achievement": achievement_json(achievement_id, achievement, rubric)
             }
         }
     });
+    if let Some(image) = issuer_image {
+        payload["vc"]["issuer"]["image"] = image_json(image);
+    }
+    crate::refresh::apply_to_json(&mut payload["vc"], refresh_service_url);
+    crate::terms_of_use::apply_to_json(&mut payload["vc"], terms_of_use);
+    crate::extensions::extend_context_json(&mut payload["vc"], &achievement.extensions);
+    if bind_holder {
+        payload["cnf"] = crate::holder_binding::cnf_claim(recipient_pubkey);
+        payload["vc"]["holder"] = crate::holder_binding::holder_json(recipient_pubkey);
+    }
     
-    // For educational purposes, return the payload as JSON
-    // In production, this would be signed and encoded as a JWT
-    let jwt_payload = serde_json::to_string_pretty(&payload)
-        .map_err(|_| error!(ValidationError::ValidationFailed))?;
+    let header_json = serde_json::to_string(&header)
+        .map_err(|_| error!(ValidationError::SerializationError))?;
+    let payload_json = serde_json::to_string(&payload)
+        .map_err(|_| error!(ValidationError::SerializationError))?;
     
-    msg!("✅ Generated JWT credential payload for achievement: {}", achievement_name);
-    Ok(jwt_payload)
+    Ok(format!(
+        "{}.{}",
+        general_purpose::URL_SAFE_NO_PAD.encode(header_json),
+        general_purpose::URL_SAFE_NO_PAD.encode(payload_json)
+    ))
+}
+
+/// Append a detached Ed25519 signature to a JWS signing input
+pub fn compact_jwt(signing_input: &str, signature: &[u8; 64]) -> String {
+    format!("{}.{}", signing_input, general_purpose::URL_SAFE_NO_PAD.encode(signature))
+}
+
+/// Build the JSON-LD document for an on-chain AchievementCredential account
+pub fn credential_account_to_json(credential: &crate::AchievementCredential) -> serde_json::Value {
+    let subject = &credential.credential_subject;
+    let identifier: Vec<serde_json::Value> = subject.identifier.iter().map(|identity| {
+        serde_json::json!({
+            "type": identity.identity_type,
+            "hashed": identity.hashed,
+            "identityHash": identity.identity_hash,
+            "identityType": identity.identity_type_name,
+        })
+    }).collect();
+    
+    let mut credential_subject = serde_json::json!({
+        "type": subject.subject_type,
+        "achievement": format!("did:sol:{}", subject.achievement),
+    });
+    if let Some(id) = &subject.id {
+        credential_subject["id"] = serde_json::json!(id);
+    }
+    if !identifier.is_empty() {
+        credential_subject["identifier"] = serde_json::json!(identifier);
+    }
+    if !subject.result.is_empty() {
+        let results: Vec<serde_json::Value> = subject.result.iter().map(|r| r.to_json()).collect();
+        credential_subject["result"] = serde_json::json!(results);
+    }
+    if let Some(narrative) = &subject.narrative {
+        credential_subject["narrative"] = serde_json::json!(narrative);
+    }
+    
+    let mut document = serde_json::json!({
+        "@context": credential.context,
+        "id": credential.id,
+        "type": credential.r#type,
+        "issuer": format!("did:sol:{}", credential.issuer),
+        "validFrom": credential.valid_from,
+        "credentialSubject": credential_subject,
+    });
+    if let Some(valid_until) = &credential.valid_until {
+        document["validUntil"] = serde_json::json!(valid_until);
+    }
+    if !credential.related.is_empty() {
+        let related: Vec<serde_json::Value> = credential
+            .related
+            .iter()
+            .map(|id| serde_json::json!({ "id": id, "type": ["Related"] }))
+            .collect();
+        document["related"] = serde_json::json!(related);
+    }
+    if !credential.credential_schema.is_empty() {
+        let schemas: Vec<serde_json::Value> = credential.credential_schema.iter().map(|s| s.to_json()).collect();
+        document["credentialSchema"] = serde_json::json!(schemas);
+    }
+    if let Some(holder) = &credential.holder_binding {
+        document["holder"] = crate::holder_binding::holder_json(holder);
+    }
+    crate::terms_of_use::apply_to_json(&mut document, &credential.terms_of_use);
+    if let Some(payload) = &credential.encrypted_payload {
+        document["encryptedPayload"] = crate::encrypted_payload::commitment_json(&crate::encrypted_payload::commitment(payload));
+    }
+    // A single proof stays an object; a proof set or chain is an array with proof ids
+    let proofs: Vec<serde_json::Value> = credential
+        .proof
+        .iter()
+        .enumerate()
+        .map(|(index, proof)| {
+            let mut proof_json = serde_json::json!({
+                "type": proof.proof_type,
+                "cryptosuite": proof.cryptosuite,
+                "created": proof.created,
+                "proofPurpose": proof.proof_purpose,
+                "verificationMethod": proof.verification_method,
+                "proofValue": proof.proof_value,
+            });
+            if credential.proof.len() > 1 {
+                proof_json["id"] = serde_json::json!(crate::proof_set::proof_id(&credential.id, index));
+            }
+            if let Some(previous_proof) = &proof.previous_proof {
+                proof_json["previousProof"] = serde_json::json!(previous_proof);
+            }
+            proof_json
+        })
+        .collect();
+    match proofs.len() {
+        0 => {}
+        1 => document["proof"] = proofs[0].clone(),
+        _ => document["proof"] = serde_json::json!(proofs),
+    }
+    crate::extensions::apply_to_json(&mut document, &credential.extensions);
+    
+    document
+}
+
+/// Unsigned credential document for re-securing an issued credential in another format
+///
+/// The account's JSON-LD document without its proof, with the issuer as a
+/// Profile object and the achievement embedded.
+pub fn issued_credential_document(
+    credential: &crate::AchievementCredential,
+    achievement: &Achievement,
+    rubric: Option<&Rubric>,
+) -> serde_json::Value {
+    let mut document = credential_account_to_json(credential);
+    if let Some(object) = document.as_object_mut() {
+        object.remove("proof");
+    }
+    document["issuer"] = serde_json::json!({
+        "id": format!("did:sol:{}", credential.issuer),
+        "type": ["Profile"]
+    });
+    document["credentialSubject"]["achievement"] = achievement_json(&achievement.id, achievement, rubric);
+    document
+}
+
+/// What a JWT credential is checked against beyond its own contents
+pub struct JwtVerificationContext<'a, 'info> {
+    /// Solana clock time, for `exp` and `nbf`
+    pub now: i64,
+    /// Issuer profile account whose authority may sign for its did:sol identifier
+    pub issuer: Option<(Pubkey, &'a crate::Profile)>,
+    /// Instructions sysvar holding the Ed25519 or secp256k1 program instruction for the signature
+    pub instructions: Option<&'a AccountInfo<'info>>,
+    /// Issuer's staff key record for the signing key, and the key's sol_did account
+    pub staff: Option<(&'a crate::staff_key::StaffKey, &'a sol_did_cpi::state::DidAccount)>,
 }
 
 /// Verify a credential in any supported format
-pub fn verify_credential_format(credential_data: &str) -> Result<bool> {
+pub fn verify_credential_format(credential_data: &str, context: &JwtVerificationContext) -> Result<bool> {
     // Detect format based on structure
     if credential_data.trim().starts_with('{') {
         // JSON-LD format
         verify_jsonld_credential(credential_data)
+    } else if let Some(cose) = crate::formats::cose::detect(credential_data) {
+        // COSE_Sign1 as base64url text
+        crate::formats::cose::verify_cose_credential(&cose, context)
     } else {
         // Assume JWT format or other
-        verify_jwt_credential(credential_data)
+        verify_jwt_credential(credential_data, context)
     }
 }
 
@@ -155,65 +404,260 @@ pub fn verify_jsonld_credential(credential_json: &str) -> Result<bool> {
     Ok(true)
 }
 
-/// Verify a JWT credential
-pub fn verify_jwt_credential(_credential_jwt: &str) -> Result<bool> {
-    // For educational purposes, assume JWT is valid
-    // In production, this would verify the JWT signature and claims
-    msg!("✅ JWT credential verification successful (educational mode)");
+/// A compact JWS split into its decoded parts
+pub struct DecodedJwt {
+    pub header: serde_json::Value,
+    pub payload: serde_json::Value,
+    /// `header.payload` exactly as signed
+    pub signing_input: String,
+    pub signature: [u8; 64],
+}
+
+/// Split and decode a compact JWS with a 64-byte (Ed25519 or ES256K) signature
+pub fn decode_jwt(credential_jwt: &str) -> Result<DecodedJwt> {
+    let parts: Vec<&str> = credential_jwt.trim().split('.').collect();
+    let [header_b64, payload_b64, signature_b64] = parts.as_slice() else {
+        return Err(error!(ValidationError::InvalidJwtFormat));
+    };
+    let decode_json = |part: &str| -> Result<serde_json::Value> {
+        let bytes = general_purpose::URL_SAFE_NO_PAD.decode(part)
+            .map_err(|_| error!(ValidationError::InvalidJwtFormat))?;
+        serde_json::from_slice(&bytes).map_err(|_| error!(ValidationError::InvalidJwtFormat))
+    };
+    let signature: [u8; 64] = general_purpose::URL_SAFE_NO_PAD.decode(signature_b64)
+        .map_err(|_| error!(ValidationError::InvalidJwtFormat))?
+        .try_into()
+        .map_err(|_| error!(ValidationError::InvalidSignatureLength))?;
+
+    Ok(DecodedJwt {
+        header: decode_json(header_b64)?,
+        payload: decode_json(payload_b64)?,
+        signing_input: format!("{}.{}", header_b64, payload_b64),
+        signature,
+    })
+}
+
+/// Whether `now` lies within the payload's `nbf` and `exp` claims
+pub fn jwt_time_valid(payload: &serde_json::Value, now: i64) -> bool {
+    let claim = |name: &str| payload.get(name).and_then(serde_json::Value::as_i64);
+    !matches!(claim("nbf"), Some(nbf) if nbf > now) && !matches!(claim("exp"), Some(exp) if exp <= now)
+}
+
+/// Resolve the Ed25519 key a JWT `kid` names
+/// Only DID methods resolvable on-chain are accepted
+pub fn resolve_jwt_signer(kid: &str) -> Result<Pubkey> {
+    let did_url = DidUrl::parse(kid)?;
+    if !matches!(did_url.method, DidMethod::Sol | DidMethod::Key) {
+        msg!("❌ JWT key {} cannot be resolved on-chain", kid);
+        return Err(error!(ValidationError::UnsupportedDidMethod));
+    }
+    let key = crate::did::DidResolver::new().resolve_assertion_key(kid)?;
+    let key: [u8; 32] = key.try_into().map_err(|_| error!(ValidationError::InvalidKeyLength))?;
+    Ok(Pubkey::new_from_array(key))
+}
+
+/// Whether the key behind `kid` may sign for the `iss` DID
+///
+/// The key's own DID may always sign. An issuer profile's did:sol
+/// identifier is signed for by the profile's authority, which requires the
+/// profile account to be supplied. So is it by a staff key, given in
+/// `staff` with its did:sol account, that the issuer recorded with
+/// `set_staff_did_controller` and whose DID lists the issuer as controller.
+pub fn jwt_issuer_trusted(
+    iss: &str,
+    kid: &str,
+    signer: &Pubkey,
+    issuer: Option<(Pubkey, &crate::Profile)>,
+    staff: Option<(&crate::staff_key::StaffKey, &sol_did_cpi::state::DidAccount)>,
+) -> bool {
+    let kid_did = kid.split('#').next().unwrap_or_default();
+    if kid_did == iss {
+        return true;
+    }
+    issuer.is_some_and(|(issuer_key, profile)| {
+        if iss != format!("did:sol:{}", issuer_key) {
+            return false;
+        }
+        profile.authority == *signer
+            || staff.is_some_and(|(staff_key, did_account)| {
+                kid_did == format!("did:sol:{}", signer)
+                    && crate::staff_key::authorizes(staff_key, did_account, &issuer_key, profile, signer)
+            })
+    })
+}
+
+/// Whether a sol_did account lists the issuer as controller
+/// Either the profile's authority as a native controller, or the profile's
+/// or its authority's did:sol identifier among the other controllers
+pub fn did_controlled_by_issuer(did_account: &sol_did_cpi::state::DidAccount, issuer_key: &Pubkey, profile: &crate::Profile) -> bool {
+    let issuer_dids = [format!("did:sol:{}", issuer_key), format!("did:sol:{}", profile.authority)];
+    did_account.native_controllers.contains(&profile.authority)
+        || did_account.other_controllers.iter().any(|controller| issuer_dids.contains(controller))
+}
+
+/// Verify a JWT credential: structure, validity window, issuer key and signature
+pub fn verify_jwt_credential(credential_jwt: &str, context: &JwtVerificationContext) -> Result<bool> {
+    let jwt = decode_jwt(credential_jwt)?;
+    
+    let alg = jwt.header.get("alg").and_then(|alg| alg.as_str());
+    if !matches!(alg, Some("EdDSA") | Some(crate::secp256k1::JWT_ALGORITHM)) {
+        msg!("❌ Unsupported JWT algorithm");
+        return Ok(false);
+    }
+    
+    let vc = jwt.payload.get("vc").map(|vc| vc.to_string()).unwrap_or_default();
+    if !verify_jsonld_credential(&vc)? {
+        return Ok(false);
+    }
+    
+    if !jwt_time_valid(&jwt.payload, context.now) {
+        msg!("❌ JWT is expired or not yet valid");
+        return Ok(false);
+    }
+    
+    let Some(iss) = jwt.payload.get("iss").and_then(|iss| iss.as_str()) else {
+        return Ok(false);
+    };
+    let kid = jwt.header.get("kid").and_then(|kid| kid.as_str()).unwrap_or(iss);
+    if alg == Some(crate::secp256k1::JWT_ALGORITHM) {
+        return verify_es256k_jwt(&jwt, iss, kid, context);
+    }
+    let signer = resolve_jwt_signer(kid)?;
+    if !jwt_issuer_trusted(iss, kid, &signer, context.issuer, context.staff) {
+        msg!("❌ JWT key {} is not authorized for issuer {}", kid, iss);
+        return Ok(false);
+    }
+    
+    let Some(instructions) = context.instructions else {
+        msg!("❌ JWT signature check needs the instructions sysvar and an Ed25519 instruction");
+        return Ok(false);
+    };
+    if crate::proof::ensure_ed25519_verified(instructions, &signer, jwt.signing_input.as_bytes(), &jwt.signature).is_err() {
+        return Ok(false);
+    }
+    
+    msg!("✅ JWT credential signature verified for {}", iss);
     Ok(true)
 }
 
-/// Resolve a DID to its document using the appropriate method
-pub fn resolve_did_document(did: &str) -> Result<String> {
-    // Parse DID to determine method
-    let did_method = if did.starts_with("did:sol:") {
-        DidMethod::Sol
-    } else if did.starts_with("did:key:") {
-        DidMethod::Key
-    } else if did.starts_with("did:web:") {
-        DidMethod::Web
-    } else {
-        return Err(error!(ValidationError::ValidationFailed));
+/// Verify the signature of an ES256K JWT issued by an EVM account
+/// The `kid` must name the did:ethr DID in `iss`
+fn verify_es256k_jwt(jwt: &DecodedJwt, iss: &str, kid: &str, context: &JwtVerificationContext) -> Result<bool> {
+    if kid.split('#').next() != Some(iss) || !iss.starts_with(crate::secp256k1::ETHR_DID_PREFIX) {
+        msg!("❌ ES256K JWT key {} is not authorized for issuer {}", kid, iss);
+        return Ok(false);
+    }
+    let eth_address = crate::secp256k1::parse_eth_address(kid)?;
+    
+    let Some(instructions) = context.instructions else {
+        msg!("❌ ES256K signature check needs the instructions sysvar and a secp256k1 instruction");
+        return Ok(false);
     };
+    if crate::secp256k1::ensure_secp256k1_verified(instructions, &eth_address, jwt.signing_input.as_bytes(), &jwt.signature).is_err() {
+        return Ok(false);
+    }
     
-    match did_method {
-        DidMethod::Sol => {
-            // Extract Solana public key from DID
-            let pubkey_str = did.strip_prefix("did:sol:").unwrap_or("");
-            let _pubkey = Pubkey::from_str(pubkey_str)
-                .map_err(|_| error!(ValidationError::InvalidKey))?;
-            
-            // Create DID document for Solana DID
-            let did_document = DidDocument {
-                id: did.to_string(),
-                context: vec![
-                    "https://www.w3.org/ns/did/v1".to_string(),
-                    "https://w3id.org/security/suites/ed25519-2020/v1".to_string(),
-                ],
-                verification_method: vec![],
-                authentication: vec![],
-                assertion_method: vec![],
-                key_agreement: vec![],
-                service: vec![],
-            };
-            
-            let doc_json = serde_json::to_string_pretty(&did_document)
-                .map_err(|_| error!(ValidationError::ValidationFailed))?;
-            
-            msg!("✅ Resolved DID document for: {}", did);
-            Ok(doc_json)
-        },
-        _ => {
-            msg!("DID method not yet implemented: {:?}", did_method);
-            Err(error!(ValidationError::NotImplemented))
+    msg!("✅ ES256K JWT credential signature verified for {}", iss);
+    Ok(true)
+}
+
+/// Resolve a DID through the method registry
+/// Returns the DID Core resolution result; failures are reported in its metadata
+pub fn resolve_did_document(did: &str) -> Result<String> {
+    let result = DidResolverRegistry::with_default_methods().resolve(did);
+    if let Some(error) = &result.did_resolution_metadata.error {
+        msg!("❌ DID resolution failed for {}: {}", did, error);
+    }
+    serde_json::to_string_pretty(&result).map_err(|_| error!(ValidationError::ValidationFailed))
+}
+
+/// Resolve a did:sol DID from the sol_did program's DID account
+/// Falls back to the generative document when the account was never initialized
+pub fn resolve_sol_did_document_from_account(did: &str, did_data: &AccountInfo) -> Result<String> {
+    let did_url = DidUrl::parse(did)?;
+    if did_url.method != DidMethod::Sol {
+        return Err(error!(ValidationError::UnsupportedDidMethod));
+    }
+
+    // did:sol:<identifier> or did:sol:<network>:<identifier>
+    let identifier = did_url.method_specific_id.rsplit(':').next().unwrap_or_default();
+    let authority = Pubkey::from_str(identifier)
+        .map_err(|_| error!(ValidationError::InvalidSolanaPublicKey))?;
+
+    let did_account = read_sol_did_account(&authority, did_data)?;
+    let did_document = SolanaDidResolver::new().resolve_from_account(&did_url, &did_account)?;
+    let doc_json = serde_json::to_string_pretty(&did_document)
+        .map_err(|_| error!(ValidationError::ValidationFailed))?;
+
+    msg!("✅ Resolved DID document from account for: {}", did);
+    Ok(doc_json)
+}
+
+/// Read `authority`'s sol_did DID account
+/// Falls back to the generative account when it was never initialized
+pub fn read_sol_did_account(authority: &Pubkey, did_data: &AccountInfo) -> Result<sol_did_cpi::state::DidAccount> {
+    // The account must be the DID account PDA derived by sol_did for this authority
+    let (expected_did_data, _) = SolanaDidResolver::derive_did_account(authority);
+    if did_data.key() != expected_did_data {
+        return Err(error!(ValidationError::InvalidDid));
+    }
+
+    if did_data.owner == &sol_did_cpi::ID && !did_data.data_is_empty() {
+        let data = did_data.try_borrow_data()?;
+        sol_did_cpi::state::DidAccount::try_deserialize(&mut &data[..])
+            .map_err(|_| error!(ValidationError::InvalidDid))
+    } else {
+        msg!("ℹ️ No on-chain DID account, using generative document");
+        Ok(SolanaDidResolver::generative_did_account(authority))
+    }
+}
+
+/// Read a signing key's sol_did DID account, which must be initialized
+/// The key is taken from the account, whose address must be its DID PDA
+pub fn read_signer_did_account(did_data: &AccountInfo) -> Result<sol_did_cpi::state::DidAccount> {
+    if did_data.owner != &sol_did_cpi::ID || did_data.data_is_empty() {
+        return Err(error!(ValidationError::InvalidDid));
+    }
+    let data = did_data.try_borrow_data()?;
+    let did_account = sol_did_cpi::state::DidAccount::try_deserialize(&mut &data[..])
+        .map_err(|_| error!(ValidationError::InvalidDid))?;
+    let signer = Pubkey::new_from_array(did_account.initial_verification_method.key_data.as_slice().try_into()
+        .map_err(|_| error!(ValidationError::InvalidKeyLength))?);
+    if did_data.key() != SolanaDidResolver::derive_did_account(&signer).0 {
+        return Err(error!(ValidationError::InvalidDid));
+    }
+    Ok(did_account)
+}
+
+/// Check that an issuer's DID account is still alive and lists every proof's method
+/// A closed or unowned account, or one whose keys were removed, fails with `IssuerDidRevoked`
+pub fn ensure_issuer_did_active(authority: &Pubkey, did_data: &AccountInfo, proofs: &[Proof]) -> Result<()> {
+    let (expected_did_data, _) = SolanaDidResolver::derive_did_account(authority);
+    if did_data.key() != expected_did_data {
+        return Err(error!(ValidationError::InvalidDid));
+    }
+
+    if did_data.owner != &sol_did_cpi::ID || did_data.data_is_empty() {
+        msg!("❌ Issuer DID account {} is closed", did_data.key());
+        return Err(error!(ValidationError::IssuerDidRevoked));
+    }
+    let data = did_data.try_borrow_data()?;
+    let did_account = sol_did_cpi::state::DidAccount::try_deserialize(&mut &data[..])
+        .map_err(|_| error!(ValidationError::InvalidDid))?;
+
+    for proof in proofs {
+        if !SolanaDidResolver::has_verification_method(&did_account, &proof.verification_method) {
+            msg!("❌ Verification method no longer in issuer DID: {}", proof.verification_method);
+            return Err(error!(ValidationError::IssuerDidRevoked));
         }
     }
+    Ok(())
 }
 
 /// Validate Open Badges 3.0 compliance for any credential format
-pub fn validate_ob3_compliance(credential_data: &str) -> Result<bool> {
+pub fn validate_ob3_compliance(credential_data: &str, context: &JwtVerificationContext) -> Result<bool> {
     // First verify the credential format
-    let is_valid_format = verify_credential_format(credential_data)?;
+    let is_valid_format = verify_credential_format(credential_data, context)?;
     
     if !is_valid_format {
         return Ok(false);
@@ -225,4 +669,221 @@ pub fn validate_ob3_compliance(credential_data: &str) -> Result<bool> {
     
     msg!("✅ Open Badges 3.0 compliance validation successful");
     Ok(true)
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{fixture_achievement, fixture_credential, fixture_profile, TestKeypair};
+
+    fn signed_jwt(authority: &TestKeypair, issuer_key: &Pubkey) -> (String, [u8; 64]) {
+        let achievement = fixture_achievement(&Pubkey::new_unique(), issuer_key, "Rust");
+        let signing_input = jwt_signing_input(
+            issuer_key,
+            &authority.pubkey(),
+            None,
+            None,
+            &[],
+            &Pubkey::new_unique(),
+            "urn:example:rust",
+            &achievement,
+            "urn:uuid:1",
+            None,
+            1_700_000_000,
+            false,
+        )
+        .unwrap();
+        let signature = authority.sign(signing_input.as_bytes());
+        (compact_jwt(&signing_input, &signature), signature)
+    }
+
+    #[test]
+    fn test_signed_jwt_decodes_to_signing_input() {
+        let authority = TestKeypair::from_seed(9);
+        let issuer_key = Pubkey::new_unique();
+        let (jwt, signature) = signed_jwt(&authority, &issuer_key);
+
+        let decoded = decode_jwt(&jwt).unwrap();
+        assert_eq!(decoded.signature, signature);
+        assert!(authority.verify(decoded.signing_input.as_bytes(), &decoded.signature));
+        assert_eq!(decoded.payload["iss"], format!("did:sol:{}", issuer_key));
+        assert_eq!(decoded.header["kid"], format!("did:sol:{}", authority.pubkey()));
+        assert!(verify_jsonld_credential(&decoded.payload["vc"].to_string()).unwrap());
+        assert!(decode_jwt("only.two").is_err());
+    }
+
+    #[test]
+    fn test_validity_window_uses_given_clock() {
+        let payload = serde_json::json!({ "nbf": 100, "exp": 200 });
+        assert!(!jwt_time_valid(&payload, 99));
+        assert!(jwt_time_valid(&payload, 100));
+        assert!(!jwt_time_valid(&payload, 200));
+        assert!(jwt_time_valid(&serde_json::json!({}), 0));
+    }
+
+    #[test]
+    fn test_kid_resolves_to_ed25519_key() {
+        let key = TestKeypair::from_seed(3).pubkey();
+        assert_eq!(resolve_jwt_signer(&format!("did:sol:{}", key)).unwrap(), key);
+        assert_eq!(resolve_jwt_signer(&format!("did:sol:{}#key1", key)).unwrap(), key);
+
+        #[cfg(feature = "did-key")]
+        {
+            let did_key = format!("did:key:{}", crate::multibase::encode_ed25519_multikey(&key));
+            assert_eq!(resolve_jwt_signer(&did_key).unwrap(), key);
+        }
+
+        assert!(resolve_jwt_signer("did:web:example.com").is_err());
+    }
+
+    #[test]
+    fn test_profile_authority_signs_for_issuer() {
+        let authority = TestKeypair::from_seed(4).pubkey();
+        let issuer_key = Pubkey::new_unique();
+        let profile = fixture_profile(&issuer_key, &authority, "Issuer");
+        let iss = format!("did:sol:{}", issuer_key);
+        let kid = format!("did:sol:{}", authority);
+
+        assert!(jwt_issuer_trusted(&kid, &kid, &authority, None, None));
+        assert!(!jwt_issuer_trusted(&iss, &kid, &authority, None, None));
+        assert!(jwt_issuer_trusted(&iss, &kid, &authority, Some((issuer_key, &profile)), None));
+        assert!(!jwt_issuer_trusted(&iss, &kid, &Pubkey::new_unique(), Some((issuer_key, &profile)), None));
+        assert!(!jwt_issuer_trusted(&iss, &kid, &authority, Some((Pubkey::new_unique(), &profile)), None));
+    }
+
+    #[test]
+    fn test_issuer_controlled_staff_key_signs_for_issuer() {
+        let authority = TestKeypair::from_seed(4).pubkey();
+        let staff = TestKeypair::from_seed(6).pubkey();
+        let issuer_key = Pubkey::new_unique();
+        let profile = fixture_profile(&issuer_key, &authority, "Issuer");
+        let iss = format!("did:sol:{}", issuer_key);
+        let kid = format!("did:sol:{}#default", staff);
+        let staff_key = crate::staff_key::StaffKey {
+            issuer: issuer_key,
+            staff,
+            authorized_at: "2024-01-01T00:00:00Z".to_string(),
+            bump: 255,
+        };
+        let issuer = Some((issuer_key, &profile));
+
+        let mut did_account = SolanaDidResolver::generative_did_account(&staff);
+        assert!(!jwt_issuer_trusted(&iss, &kid, &staff, issuer, Some((&staff_key, &did_account))));
+
+        did_account.native_controllers.push(authority);
+        assert!(did_controlled_by_issuer(&did_account, &issuer_key, &profile));
+        assert!(jwt_issuer_trusted(&iss, &kid, &staff, issuer, Some((&staff_key, &did_account))));
+        assert!(!jwt_issuer_trusted(&iss, &kid, &staff, issuer, None));
+        // A DID naming the issuer as controller signs for nobody without the issuer's record
+        let foreign = crate::staff_key::StaffKey { issuer: Pubkey::new_unique(), ..staff_key.clone() };
+        assert!(!jwt_issuer_trusted(&iss, &kid, &staff, issuer, Some((&foreign, &did_account))));
+        let other = Pubkey::new_unique();
+        let attacker = crate::staff_key::StaffKey { staff: other, ..staff_key.clone() };
+        let mut attacker_did = SolanaDidResolver::generative_did_account(&other);
+        attacker_did.native_controllers.push(authority);
+        assert!(!jwt_issuer_trusted(&iss, &format!("did:sol:{}", other), &other, issuer, Some((&staff_key, &attacker_did))));
+        assert!(!jwt_issuer_trusted(&iss, &format!("did:sol:{}", other), &other, issuer, Some((&attacker, &did_account))));
+        assert!(!jwt_issuer_trusted(&iss, &kid, &staff, Some((Pubkey::new_unique(), &profile)), Some((&staff_key, &did_account))));
+
+        did_account.native_controllers.clear();
+        did_account.other_controllers.push(iss.clone());
+        assert!(jwt_issuer_trusted(&iss, &kid, &staff, issuer, Some((&staff_key, &did_account))));
+    }
+
+    #[test]
+    fn test_narrative_is_presented_and_notes_are_not() {
+        let mut credential = fixture_credential(
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+        );
+        assert!(credential_account_to_json(&credential)["credentialSubject"].get("narrative").is_none());
+
+        credential.credential_subject.narrative = Some("Led the capstone project".to_string());
+        credential.notes = vec!["Reviewed by the committee".to_string()];
+        let document = credential_account_to_json(&credential);
+        assert_eq!(document["credentialSubject"]["narrative"], "Led the capstone project");
+        assert!(!document.to_string().contains("Reviewed by the committee"));
+    }
+
+    #[test]
+    fn test_proof_chain_is_presented_as_array() {
+        let mut credential = fixture_credential(
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+        );
+        crate::test_utils::sign_credential(&mut credential, &crate::test_utils::TestKeypair::from_seed(2));
+        assert!(credential_account_to_json(&credential)["proof"].is_object());
+
+        let mut counter_signature = credential.proof[0].clone();
+        counter_signature.verification_method = format!("{}#key-1", credential.issuer);
+        counter_signature.previous_proof = Some(crate::proof_set::proof_id(&credential.id, 0));
+        credential.proof.push(counter_signature);
+        let proofs = credential_account_to_json(&credential)["proof"].clone();
+        assert_eq!(proofs.as_array().map(Vec::len), Some(2));
+        assert_eq!(proofs[1]["previousProof"], proofs[0]["id"]);
+    }
+
+    #[test]
+    fn test_credential_schema_is_presented() {
+        let mut credential = fixture_credential(
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+        );
+        assert!(credential_account_to_json(&credential).get("credentialSchema").is_none());
+
+        let mut registry = crate::schema::SchemaRegistry { issuer: credential.issuer, schemas: vec![], bump: 255 };
+        registry.register(credential.issuer, 255, "https://example.com/schemas/course.json".to_string(), [3u8; 32]).unwrap();
+        credential.credential_schema = registry.schemas.clone();
+        let document = credential_account_to_json(&credential);
+        assert_eq!(document["credentialSchema"][0]["type"], crate::schema::JSON_SCHEMA_TYPE);
+        assert!(crate::schema::verify_declared(&document, Some(&registry)).is_ok());
+    }
+
+    #[test]
+    fn test_holder_binding_is_presented() {
+        let holder = TestKeypair::from_seed(12).pubkey();
+        let issuer_key = Pubkey::new_unique();
+        let mut credential = fixture_credential(&Pubkey::new_unique(), &issuer_key, &Pubkey::new_unique(), &holder);
+        assert!(credential_account_to_json(&credential).get("holder").is_none());
+
+        credential.holder_binding = Some(holder);
+        let document = credential_account_to_json(&credential);
+        assert_eq!(document["holder"]["publicKeyMultibase"], crate::holder_binding::public_key_multibase(&holder));
+
+        let achievement = fixture_achievement(&Pubkey::new_unique(), &issuer_key, "Rust");
+        let signing_input = jwt_signing_input(
+            &issuer_key, &Pubkey::new_unique(), None, None, &[], &holder, "urn:example:rust", &achievement, "urn:uuid:2", None, 0, true,
+        )
+        .unwrap();
+        let jwt = decode_jwt(&compact_jwt(&signing_input, &[0u8; 64])).unwrap();
+        assert_eq!(jwt.payload["cnf"]["kid"], crate::holder_binding::verification_method(&holder));
+        assert_eq!(jwt.payload["vc"]["holder"], document["holder"]);
+    }
+
+    #[test]
+    fn test_terms_of_use_are_presented() {
+        let terms = vec![crate::terms_of_use::TermsOfUse {
+            id: Some("https://issuer.example/policy".to_string()),
+            terms_type: "IssuerPolicy".to_string(),
+        }];
+        let issuer_key = Pubkey::new_unique();
+        let mut credential = fixture_credential(&Pubkey::new_unique(), &issuer_key, &Pubkey::new_unique(), &Pubkey::new_unique());
+        assert!(credential_account_to_json(&credential).get("termsOfUse").is_none());
+        credential.terms_of_use = terms.clone();
+        let document = credential_account_to_json(&credential);
+        assert_eq!(document["termsOfUse"][0]["type"], "IssuerPolicy");
+
+        let achievement = fixture_achievement(&Pubkey::new_unique(), &issuer_key, "Rust");
+        let signing_input = jwt_signing_input(
+            &issuer_key, &Pubkey::new_unique(), None, None, &terms, &Pubkey::new_unique(), "urn:example:rust", &achievement, "urn:uuid:3", None, 0, false,
+        )
+        .unwrap();
+        let jwt = decode_jwt(&compact_jwt(&signing_input, &[0u8; 64])).unwrap();
+        assert_eq!(jwt.payload["vc"]["termsOfUse"], document["termsOfUse"]);
+    }
+}
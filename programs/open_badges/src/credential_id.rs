@@ -0,0 +1,92 @@
+//! Credential Identifiers
+//!
+//! A credential's `id` defaults to `did:sol:<credential PDA>`. A PDA is not
+//! an identifier registered under did:sol, so issuers can switch to
+//! `urn:uuid:` ids instead. The UUID is name-based in the manner of UUIDv5,
+//! hashing a fixed namespace and the PDA bytes; it uses SHA-256, which
+//! RFC 9562 assigns to version 8. The same PDA always yields the same UUID,
+//! so clients can compute the id before issuance and sign it.
+
+use anchor_lang::prelude::*;
+use sha2::{Digest, Sha256};
+use std::str::FromStr;
+use crate::common::errors::ValidationError;
+
+/// Namespace of credential UUIDs (3f1c1e0a-6b0e-4c8f-9f2b-0b5d6f1e2a47)
+pub const CREDENTIAL_UUID_NAMESPACE: [u8; 16] = [
+    0x3f, 0x1c, 0x1e, 0x0a, 0x6b, 0x0e, 0x4c, 0x8f, 0x9f, 0x2b, 0x0b, 0x5d, 0x6f, 0x1e, 0x2a, 0x47,
+];
+
+/// How an issuer's credentials are identified
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CredentialIdFormat {
+    /// `did:sol:<credential PDA>`
+    #[default]
+    DidSol,
+    /// `urn:uuid:<UUID derived from the credential PDA>`
+    UrnUuid,
+}
+
+/// Name-based UUID of a credential PDA, as a `urn:uuid:` URI
+pub fn urn_uuid(credential: &Pubkey) -> String {
+    let digest = Sha256::new()
+        .chain_update(CREDENTIAL_UUID_NAMESPACE)
+        .chain_update(credential.as_ref())
+        .finalize();
+    let mut bytes = [0u8; 16];
+    bytes.copy_from_slice(&digest[..16]);
+    bytes[6] = (bytes[6] & 0x0f) | 0x80;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+
+    let hex = hex::encode(bytes);
+    format!("urn:uuid:{}-{}-{}-{}-{}", &hex[..8], &hex[8..12], &hex[12..16], &hex[16..20], &hex[20..])
+}
+
+/// `id` of the credential stored at `credential`
+pub fn credential_id(format: CredentialIdFormat, credential: &Pubkey) -> String {
+    match format {
+        CredentialIdFormat::DidSol => format!("did:sol:{}", credential),
+        CredentialIdFormat::UrnUuid => urn_uuid(credential),
+    }
+}
+
+/// `id` of the credential at a base58 address, as given to the JSON views
+pub fn credential_id_for_address(format: CredentialIdFormat, address: &str) -> Result<String> {
+    match format {
+        CredentialIdFormat::DidSol => Ok(format!("did:sol:{}", address)),
+        CredentialIdFormat::UrnUuid => {
+            let credential = Pubkey::from_str(address).map_err(|_| error!(ValidationError::InvalidSolanaPublicKey))?;
+            Ok(urn_uuid(&credential))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_uuid_is_deterministic_and_well_formed() {
+        let credential = Pubkey::new_unique();
+        let id = urn_uuid(&credential);
+        assert_eq!(id, urn_uuid(&credential));
+        assert_ne!(id, urn_uuid(&Pubkey::new_unique()));
+
+        let uuid = id.strip_prefix("urn:uuid:").unwrap();
+        let groups: Vec<usize> = uuid.split('-').map(str::len).collect();
+        assert_eq!(groups, vec![8, 4, 4, 4, 12]);
+        assert_eq!(&uuid[14..15], "8");
+        assert!(matches!(&uuid[19..20], "8" | "9" | "a" | "b"));
+    }
+
+    #[test]
+    fn test_format_selects_the_id() {
+        let credential = Pubkey::new_unique();
+        assert_eq!(credential_id(CredentialIdFormat::DidSol, &credential), format!("did:sol:{}", credential));
+        assert_eq!(
+            credential_id_for_address(CredentialIdFormat::UrnUuid, &credential.to_string()).unwrap(),
+            credential_id(CredentialIdFormat::UrnUuid, &credential)
+        );
+        assert!(credential_id_for_address(CredentialIdFormat::UrnUuid, "not-a-key").is_err());
+    }
+}
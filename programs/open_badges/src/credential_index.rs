@@ -0,0 +1,197 @@
+//! Recipient Credential Index
+//!
+//! Each recipient has a list of the credential accounts issued to them, in
+//! issuance order, so wallets can enumerate a holder's badges from known
+//! addresses instead of scanning program accounts. A header PDA per
+//! recipient counts the credentials, and fixed-size page PDAs
+//! `[CREDENTIAL_INDEX_SEED, recipient, page]` hold `MAX_PAGE_SIZE` keys
+//! each, so no account grows with the index. Issuance creates the header on
+//! a recipient's first credential and appends each credential to the last
+//! page; `get_credentials_page` serves one page account per call.
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use crate::common::errors::ValidationError;
+
+/// Seed for the credential index PDAs: [CREDENTIAL_INDEX_SEED, recipient] for the header,
+/// [CREDENTIAL_INDEX_SEED, recipient, page (u32 LE)] for each page
+pub const CREDENTIAL_INDEX_SEED: &[u8] = b"credential_index";
+
+/// Credential keys per index page, bounded by return data size
+pub const MAX_PAGE_SIZE: u8 = 24;
+
+/// Header of one recipient's credential index
+#[account]
+pub struct CredentialIndex {
+    /// Recipient the index belongs to
+    pub recipient: Pubkey,
+    /// Credentials recorded across all pages
+    pub total: u32,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl CredentialIndex {
+    /// Discriminator + recipient + total + bump
+    pub const SPACE: usize = 8 + 32 + 4 + 1;
+
+    /// Page the next credential is appended to
+    pub fn next_page(&self) -> u32 {
+        self.total / MAX_PAGE_SIZE as u32
+    }
+
+    /// Pages holding at least one credential
+    pub fn page_count(&self) -> u32 {
+        self.total.div_ceil(MAX_PAGE_SIZE as u32)
+    }
+}
+
+/// Up to `MAX_PAGE_SIZE` credentials issued to one recipient
+#[account]
+pub struct CredentialIndexPage {
+    /// Recipient the page belongs to
+    pub recipient: Pubkey,
+    /// Position of the page in the index
+    pub page: u32,
+    /// Credential accounts, oldest first
+    pub credentials: Vec<Pubkey>,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl CredentialIndexPage {
+    /// Discriminator + recipient + page + keys at capacity + bump
+    pub const SPACE: usize = 8 + 32 + 4 + 4 + 32 * MAX_PAGE_SIZE as usize + 1;
+}
+
+/// One page of a recipient's credentials
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct CredentialsPage {
+    /// Credential accounts on this page
+    pub credentials: Vec<Pubkey>,
+    /// Credentials in the whole index
+    pub total: u32,
+    /// Whether a later page exists
+    pub has_more: bool,
+}
+
+/// Derive the credential index page PDA for a recipient
+pub fn find_index_page_address(recipient: &Pubkey, page: u32, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CREDENTIAL_INDEX_SEED, recipient.as_ref(), &page.to_le_bytes()], program_id)
+}
+
+/// Append `credential` to the index's last page
+/// `index_page` must be the page PDA at `index.next_page()`
+pub fn record(
+    index: &mut CredentialIndex,
+    index_page: &mut CredentialIndexPage,
+    recipient: Pubkey,
+    bumps: (u8, u8),
+    credential: Pubkey,
+) -> Result<()> {
+    index.recipient = recipient;
+    index.bump = bumps.0;
+
+    index_page.recipient = recipient;
+    index_page.page = index.next_page();
+    index_page.bump = bumps.1;
+    if index_page.credentials.len() >= MAX_PAGE_SIZE as usize {
+        return Err(error!(ValidationError::TooManyItems));
+    }
+    index_page.credentials.push(credential);
+    index.total = index.total.checked_add(1).ok_or_else(|| error!(ValidationError::TooManyItems))?;
+    Ok(())
+}
+
+/// Grow an account to `needed` bytes, topping up its rent from `payer`
+pub(crate) fn grow_to<'info>(
+    info: &AccountInfo<'info>,
+    needed: usize,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    if info.data_len() >= needed {
+        return Ok(());
+    }
+    let shortfall = Rent::get()?.minimum_balance(needed).saturating_sub(info.lamports());
+    if shortfall > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                system_program::Transfer { from: payer.clone(), to: info.clone() },
+            ),
+            shortfall,
+        )?;
+    }
+    info.realloc(needed, false)?;
+    Ok(())
+}
+
+/// View of one page account of `index`
+pub fn page(index: &CredentialIndex, index_page: &CredentialIndexPage) -> CredentialsPage {
+    let end = (index_page.page as u64 + 1) * MAX_PAGE_SIZE as u64;
+    CredentialsPage {
+        credentials: index_page.credentials.clone(),
+        total: index.total,
+        has_more: end < index.total as u64,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn empty_page() -> CredentialIndexPage {
+        CredentialIndexPage { recipient: Pubkey::default(), page: 0, credentials: vec![], bump: 0 }
+    }
+
+    #[test]
+    fn test_pages_cover_index_in_order() {
+        let recipient = Pubkey::new_unique();
+        let mut index = CredentialIndex { recipient, total: 0, bump: 255 };
+        let mut pages = [empty_page(), empty_page()];
+        let keys: Vec<Pubkey> = (0..MAX_PAGE_SIZE as usize + 2).map(|_| Pubkey::new_unique()).collect();
+        for key in &keys {
+            let page = index.next_page() as usize;
+            record(&mut index, &mut pages[page], recipient, (255, 254), *key).unwrap();
+        }
+
+        let first = page(&index, &pages[0]);
+        assert_eq!(first.credentials, keys[..MAX_PAGE_SIZE as usize].to_vec());
+        assert_eq!(first.total, MAX_PAGE_SIZE as u32 + 2);
+        assert!(first.has_more);
+        assert!(first.try_to_vec().unwrap().len() <= 1024);
+
+        let last = page(&index, &pages[1]);
+        assert_eq!(pages[1].page, 1);
+        assert_eq!(last.credentials, keys[MAX_PAGE_SIZE as usize..].to_vec());
+        assert!(!last.has_more);
+    }
+
+    #[test]
+    fn test_full_page_is_rejected() {
+        let recipient = Pubkey::new_unique();
+        let mut index = CredentialIndex { recipient, total: MAX_PAGE_SIZE as u32, bump: 255 };
+        let mut full = CredentialIndexPage {
+            recipient,
+            page: 0,
+            credentials: vec![Pubkey::new_unique(); MAX_PAGE_SIZE as usize],
+            bump: 254,
+        };
+        assert!(record(&mut index, &mut full, recipient, (255, 254), Pubkey::new_unique()).is_err());
+        assert_eq!(index.total, MAX_PAGE_SIZE as u32);
+    }
+
+    #[test]
+    fn test_space_matches_serialized_index() {
+        let index = CredentialIndex { recipient: Pubkey::new_unique(), total: 3, bump: 255 };
+        assert_eq!(crate::test_utils::account_data(&index).len(), CredentialIndex::SPACE);
+        let full = CredentialIndexPage {
+            recipient: Pubkey::new_unique(),
+            page: 0,
+            credentials: vec![Pubkey::new_unique(); MAX_PAGE_SIZE as usize],
+            bump: 255,
+        };
+        assert_eq!(crate::test_utils::account_data(&full).len(), CredentialIndexPage::SPACE);
+    }
+}
@@ -7,7 +7,10 @@
 //! Reference: https://www.imsglobal.org/spec/ob/v3p0/
 
 use anchor_lang::prelude::*;
+use base64::{Engine, engine::general_purpose};
+use flate2::{Compression, read::GzDecoder, write::GzEncoder};
 use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
 use crate::common::errors::ValidationError;
 
 /// Credential Status as per W3C VC Data Model v2.0 Section 4.9
@@ -69,40 +72,112 @@ pub enum RevocationStatus {
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct StatusListCredential {
     /// JSON-LD context
+    #[serde(rename = "@context")]
     pub context: Vec<String>,
     
     /// Unique identifier for the status list
     pub id: String,
     
     /// Type - must include "VerifiableCredential" and "StatusList2021Credential"
+    #[serde(rename = "type")]
     pub credential_type: Vec<String>,
     
     /// Issuer of the status list
     pub issuer: String,
     
     /// When the status list was issued
+    #[serde(rename = "validFrom")]
     pub issued: String,
     
     /// Subject containing the status list
+    #[serde(rename = "credentialSubject")]
     pub credential_subject: StatusListSubject,
     
     /// Proof for the status list
+    #[serde(skip)]
     pub proof: Option<crate::proof::DataIntegrityProof>,
 }
 
+impl StatusListCredential {
+    /// JSON the issuer signs, i.e. the credential without its proof
+    pub fn to_signing_json(&self) -> Result<String> {
+        serde_json::to_string(self)
+            .map_err(|_| error!(ValidationError::SerializationError))
+    }
+    
+    /// Credential JSON with the proof attached
+    pub fn to_json(&self) -> Result<String> {
+        let unsigned = self.to_signing_json()?;
+        match &self.proof {
+            Some(proof) => crate::proof::CredentialProofManager::add_proof_to_credential(&unsigned, proof),
+            None => Ok(unsigned),
+        }
+    }
+}
+
 /// Status List Subject containing the actual status data
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct StatusListSubject {
+    /// Identifier of the status list, `<credential id>#list`
+    pub id: String,
+    
     /// Type - must be "StatusList2021"
+    #[serde(rename = "type")]
     pub subject_type: String,
     
     /// Purpose of this status list
+    #[serde(rename = "statusPurpose")]
     pub status_purpose: String,
     
     /// Encoded status list (compressed bitstring)
+    #[serde(rename = "encodedList")]
     pub encoded_list: String,
 }
 
+/// Seed prefix for published status list credential PDAs
+pub const STATUS_LIST_CREDENTIAL_SEED: &[u8] = b"status_list_credential";
+
+/// Maximum length of a stored, signed status list credential
+pub const MAX_STATUS_LIST_CREDENTIAL_LEN: usize = 3072;
+
+/// Signed StatusList2021 credential published for a revocation list
+#[account]
+pub struct PublishedStatusList {
+    /// Revocation list the credential was generated from
+    pub revocation_list: Pubkey,
+    
+    /// Authority who signed the credential
+    pub authority: Pubkey,
+    
+    /// Signed status list credential JSON
+    pub credential_json: String,
+    
+    /// `updated_at` of the revocation list when it was signed
+    pub list_updated_at: String,
+    
+    /// When the credential was published (ISO 8601 string)
+    pub published_at: String,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl PublishedStatusList {
+    /// Space for a published status list at maximum size
+    pub const SPACE: usize = 8
+        + 32
+        + 32
+        + 4 + MAX_STATUS_LIST_CREDENTIAL_LEN
+        + 4 + 40
+        + 4 + 40
+        + 1;
+    
+    /// Whether the revocation list changed after this credential was signed
+    pub fn is_stale(&self, revocation_list: &RevocationList) -> bool {
+        self.list_updated_at != revocation_list.updated_at
+    }
+}
+
 /// Account structure for storing revocation lists on-chain
 #[account]
 pub struct RevocationList {
@@ -148,7 +223,91 @@ pub struct RevocationListMetadata {
     pub version: String,
 }
 
+/// Maximum length of a revocation list id; the id is a PDA seed
+pub const MAX_LIST_ID_LEN: usize = 32;
+
+/// Maximum length of a revocation list name
+pub const MAX_LIST_NAME_LEN: usize = 100;
+
+/// Maximum length of a revocation list description
+pub const MAX_LIST_DESCRIPTION_LEN: usize = 200;
+
+/// Maximum length of a revocation list's status list URL
+pub const MAX_STATUS_LIST_URL_LEN: usize = 200;
+
+/// Largest revocation list account: the system program creates at most
+/// 10 KiB of account data when invoked through CPI, as `init` does
+pub const MAX_REVOCATION_LIST_SPACE: usize = anchor_lang::solana_program::entrypoint::MAX_PERMITTED_DATA_INCREASE;
+
+/// Space of a list with the longest id and metadata, bitfield excluded
+const MAX_LIST_METADATA_SPACE: usize = 8 + 32
+    + 4 + MAX_LIST_ID_LEN
+    + 4 + 4 + 4
+    + 4 + MAX_LIST_NAME_LEN
+    + 4 + MAX_LIST_DESCRIPTION_LEN
+    + 4 + MAX_STATUS_LIST_URL_LEN
+    + 4 + 8 + 4 + 40 + 4 + 40;
+
+/// Largest capacity a revocation list can be created with or resized to
+/// (76,320 entries): its account fits `MAX_REVOCATION_LIST_SPACE` with any
+/// id and metadata, so `initialize_revocation_list` can create it in one
+/// call, and the deserialized list plus the bit-reversed copy
+/// `get_encoded_list` compresses stay under 20 KiB of the 32 KiB heap
+pub const MAX_REVOCATION_LIST_CAPACITY: u32 = ((MAX_REVOCATION_LIST_SPACE - MAX_LIST_METADATA_SPACE) * 8) as u32;
+
 impl RevocationList {
+    /// Account space for a list with the given identifiers and capacity
+    pub fn space_for(
+        list_id: &str,
+        capacity: u32,
+        name: &str,
+        description: &str,
+        status_list_url: &str,
+    ) -> usize {
+        8 // discriminator
+            + 32 // authority
+            + 4 + list_id.len()
+            + 4 // capacity
+            + 4 // current_size
+            + 4 + Self::bitfield_len(capacity)
+            + 4 + name.len()
+            + 4 + description.len()
+            + 4 + status_list_url.len()
+            + 4 + 8 // version
+            + 4 + 40 // created_at
+            + 4 + 40 // updated_at
+    }
+    
+    /// Bytes needed to hold one bit per credential
+    pub fn bitfield_len(capacity: u32) -> usize {
+        capacity.div_ceil(8) as usize
+    }
+    
+    /// Account space for this list once grown to `new_capacity`
+    pub fn space_with_capacity(&self, new_capacity: u32) -> usize {
+        Self::space_for(
+            &self.list_id,
+            new_capacity,
+            &self.metadata.name,
+            &self.metadata.description,
+            &self.metadata.status_list_url,
+        )
+    }
+    
+    /// Grow the bitfield to `new_capacity`; new entries start active
+    pub fn grow(&mut self, new_capacity: u32, current_timestamp: String) -> Result<()> {
+        if new_capacity <= self.capacity || new_capacity > MAX_REVOCATION_LIST_CAPACITY {
+            return Err(error!(ValidationError::InvalidCapacity));
+        }
+        
+        self.status_bits.resize(Self::bitfield_len(new_capacity), 0);
+        self.capacity = new_capacity;
+        self.updated_at = current_timestamp;
+        
+        msg!("Resized revocation list {} to capacity {}", self.list_id, new_capacity);
+        Ok(())
+    }
+    
     /// Initialize a new revocation list
     pub fn new(
         authority: Pubkey,
@@ -160,14 +319,14 @@ impl RevocationList {
         current_timestamp: String,
     ) -> Result<Self> {
         // Calculate required bytes for bitfield (1 bit per credential)
-        let required_bytes = (capacity + 7) / 8; // Round up to nearest byte
+        let required_bytes = Self::bitfield_len(capacity);
         
         Ok(Self {
             authority,
             list_id,
             capacity,
             current_size: 0,
-            status_bits: vec![0u8; required_bytes as usize],
+            status_bits: vec![0u8; required_bytes],
             metadata: RevocationListMetadata {
                 name,
                 description,
@@ -253,10 +412,20 @@ impl RevocationList {
     }
     
     /// Get the encoded status list for the StatusList2021 credential
-    pub fn get_encoded_list(&self) -> String {
-        // In a full implementation, this would use GZIP compression
-        // For simplicity, we'll use hex encoding
-        hex::encode(&self.status_bits)
+    ///
+    /// The bitstring is GZIP-compressed and base64url-encoded. Index 0 is the
+    /// left-most bit as the spec requires, while `status_bits` stores it in the
+    /// least significant bit, so each byte is bit-reversed before compressing.
+    pub fn get_encoded_list(&self) -> Result<String> {
+        let bitstring: Vec<u8> = self.status_bits.iter().map(|byte| byte.reverse_bits()).collect();
+        
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+        encoder.write_all(&bitstring)
+            .map_err(|_| error!(ValidationError::SerializationError))?;
+        let compressed = encoder.finish()
+            .map_err(|_| error!(ValidationError::SerializationError))?;
+        
+        Ok(general_purpose::URL_SAFE_NO_PAD.encode(compressed))
     }
     
     /// Generate a complete StatusList2021 credential
@@ -265,12 +434,13 @@ impl RevocationList {
         issuer_did: &str,
         current_timestamp: &str,
     ) -> Result<StatusListCredential> {
+        let id = format!("{}/status-lists/{}", issuer_did, self.list_id);
         Ok(StatusListCredential {
             context: vec![
                 "https://www.w3.org/ns/credentials/v2".to_string(),
                 "https://w3id.org/vc/status-list/2021/v1".to_string(),
             ],
-            id: format!("{}/status-lists/{}", issuer_did, self.list_id),
+            id: id.clone(),
             credential_type: vec![
                 "VerifiableCredential".to_string(),
                 "StatusList2021Credential".to_string(),
@@ -278,9 +448,10 @@ impl RevocationList {
             issuer: issuer_did.to_string(),
             issued: current_timestamp.to_string(),
             credential_subject: StatusListSubject {
+                id: format!("{}#list", id),
                 subject_type: "StatusList2021".to_string(),
                 status_purpose: "revocation".to_string(),
-                encoded_list: self.get_encoded_list(),
+                encoded_list: self.get_encoded_list()?,
             },
             proof: None, // Would be added during signing
         })
@@ -362,28 +533,133 @@ pub mod status_utils {
     }
     
     /// Parse encoded status list from external sources
+    ///
+    /// Accepts GZIP-compressed base64url, with or without padding and with an
+    /// optional multibase `u` prefix (Bitstring Status List). Returns the bits
+    /// in the same layout as `RevocationList::status_bits`.
     pub fn parse_encoded_list(encoded: &str) -> Result<Vec<u8>> {
-        // In a full implementation, this would handle GZIP decompression
-        hex::decode(encoded)
-            .map_err(|_| error!(ValidationError::InvalidEncodedList))
+        let encoded = encoded.strip_prefix('u').unwrap_or(encoded).trim_end_matches('=');
+        let compressed = general_purpose::URL_SAFE_NO_PAD.decode(encoded)
+            .map_err(|_| error!(ValidationError::InvalidEncodedList))?;
+        
+        let mut bitstring = Vec::new();
+        GzDecoder::new(compressed.as_slice())
+            .read_to_end(&mut bitstring)
+            .map_err(|_| error!(ValidationError::InvalidEncodedList))?;
+        
+        Ok(bitstring.into_iter().map(|byte| byte.reverse_bits()).collect())
+    }
+    
+    /// Check a single index of a bitstring returned by `parse_encoded_list`
+    pub fn is_index_set(status_bits: &[u8], index: u32) -> Result<bool> {
+        let byte = status_bits
+            .get((index / 8) as usize)
+            .ok_or_else(|| error!(ValidationError::IndexOutOfBounds))?;
+        Ok(byte & (1 << (index % 8)) != 0)
     }
 }
 
-/// Error types specific to credential status
-#[error_code]
-pub enum StatusError {
-    #[msg("Credential index is out of bounds")]
-    IndexOutOfBounds,
-    
-    #[msg("Invalid encoded status list format")]
-    InvalidEncodedList,
-    
-    #[msg("Unauthorized revocation attempt")]
-    UnauthorizedRevocation,
-    
-    #[msg("Status list is at capacity")]
-    StatusListFull,
-    
-    #[msg("Invalid status list credential")]
-    InvalidStatusListCredential,
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list_with(revoked: &[u32]) -> RevocationList {
+        let mut list = RevocationList::new(
+            Pubkey::new_unique(),
+            "list-1".to_string(),
+            1024,
+            "Test list".to_string(),
+            "Test revocation list".to_string(),
+            "https://example.edu/status/1".to_string(),
+            "2025-01-01T00:00:00Z".to_string(),
+        ).unwrap();
+        for index in revoked {
+            list.revoke_credential(*index, "2025-01-02T00:00:00Z".to_string()).unwrap();
+        }
+        list
+    }
+
+    #[test]
+    fn test_encoded_list_round_trip() {
+        let list = list_with(&[0, 9, 1023]);
+        let encoded = list.get_encoded_list().unwrap();
+        assert!(!encoded.contains('+') && !encoded.contains('/'));
+
+        let decoded = status_utils::parse_encoded_list(&encoded).unwrap();
+        assert_eq!(decoded, list.status_bits);
+        assert!(status_utils::is_index_set(&decoded, 9).unwrap());
+        assert!(!status_utils::is_index_set(&decoded, 10).unwrap());
+    }
+
+    #[test]
+    fn test_encoded_list_uses_left_most_bit_for_index_zero() {
+        let encoded = list_with(&[0]).get_encoded_list().unwrap();
+        let compressed = general_purpose::URL_SAFE_NO_PAD.decode(&encoded).unwrap();
+        let mut bitstring = Vec::new();
+        GzDecoder::new(compressed.as_slice()).read_to_end(&mut bitstring).unwrap();
+        assert_eq!(bitstring[0], 0b1000_0000);
+    }
+
+    #[test]
+    fn test_status_list_credential_json_shape() {
+        let list = list_with(&[3]);
+        let credential = list
+            .generate_status_list_credential("did:sol:issuer", "2025-01-02T00:00:00Z")
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_str(&credential.to_signing_json().unwrap()).unwrap();
+
+        assert_eq!(json["type"][1], "StatusList2021Credential");
+        assert_eq!(json["credentialSubject"]["id"], "did:sol:issuer/status-lists/list-1#list");
+        assert_eq!(json["credentialSubject"]["encodedList"], list.get_encoded_list().unwrap());
+        assert!(json.get("proof").is_none());
+    }
+
+    #[test]
+    fn test_grow_keeps_existing_bits() {
+        let mut list = list_with(&[5, 1000]);
+        list.grow(50_000, "2025-01-03T00:00:00Z".to_string()).unwrap();
+
+        assert_eq!(list.status_bits.len(), 6_250);
+        assert!(list.is_revoked(5).unwrap());
+        assert!(list.is_revoked(1000).unwrap());
+        assert!(!list.is_revoked(49_999).unwrap());
+        assert!(list.grow(50_000, "2025-01-03T00:00:00Z".to_string()).is_err());
+        assert!(list.grow(MAX_REVOCATION_LIST_CAPACITY + 1, "2025-01-03T00:00:00Z".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_largest_list_fits_one_account_creation() {
+        let mut list = RevocationList::new(
+            Pubkey::new_unique(),
+            "i".repeat(MAX_LIST_ID_LEN),
+            MAX_REVOCATION_LIST_CAPACITY,
+            "n".repeat(MAX_LIST_NAME_LEN),
+            "d".repeat(MAX_LIST_DESCRIPTION_LEN),
+            "u".repeat(MAX_STATUS_LIST_URL_LEN),
+            "2025-01-01T00:00:00Z".to_string(),
+        ).unwrap();
+        assert_eq!(list.space_with_capacity(MAX_REVOCATION_LIST_CAPACITY), MAX_REVOCATION_LIST_SPACE);
+        assert!(list.space_with_capacity(MAX_REVOCATION_LIST_CAPACITY + 8) > MAX_REVOCATION_LIST_SPACE);
+
+        list.revoke_credential(MAX_REVOCATION_LIST_CAPACITY - 1, "2025-01-02T00:00:00Z".to_string()).unwrap();
+        let decoded = status_utils::parse_encoded_list(&list.get_encoded_list().unwrap()).unwrap();
+        assert_eq!(decoded, list.status_bits);
+        assert!(status_utils::is_index_set(&decoded, MAX_REVOCATION_LIST_CAPACITY - 1).unwrap());
+    }
+
+    #[test]
+    fn test_space_for_matches_serialized_size() {
+        let list = list_with(&[]);
+        let mut data = Vec::new();
+        list.serialize(&mut data).unwrap();
+
+        // Timestamps are reserved at 40 bytes but serialized at their actual length
+        let timestamp_slack = 2 * (40 - list.created_at.len()) + (8 - list.metadata.version.len());
+        assert_eq!(list.space_with_capacity(list.capacity), 8 + data.len() + timestamp_slack);
+    }
+
+    #[test]
+    fn test_parse_rejects_hex_list() {
+        assert!(status_utils::parse_encoded_list("00ff").is_err());
+    }
 }
@@ -0,0 +1,215 @@
+//! Machine-Readable Criteria
+//!
+//! Besides the narrative `Criteria`, an achievement can declare the
+//! competencies it certifies, published as OB 3.0 `alignment` entries, and
+//! minimum values for its numeric results. When results are supplied at
+//! issuance every threshold must be met, so automated issuers can enforce
+//! rules such as "score ≥ 80" on-chain.
+
+use anchor_lang::prelude::*;
+use crate::common::errors::ValidationError;
+use crate::results::{ResultDescription, ResultInput};
+
+/// Maximum number of aligned competencies on an achievement
+pub const MAX_ALIGNMENTS: usize = 4;
+
+/// Maximum number of result thresholds on an achievement
+pub const MAX_THRESHOLDS: usize = 4;
+
+/// Maximum length of alignment URLs, names and codes
+pub const MAX_ALIGNMENT_FIELD_LEN: usize = 128;
+
+/// Competency or framework node the achievement aligns to (OB 3.0 Alignment)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct Alignment {
+    /// URL of the competency definition
+    pub target_url: String,
+    /// Name of the competency
+    pub target_name: String,
+    /// Framework the competency belongs to
+    pub target_framework: Option<String>,
+    /// Code of the competency within its framework
+    pub target_code: Option<String>,
+}
+
+impl Alignment {
+    /// Serialized size of this alignment
+    pub fn space(&self) -> usize {
+        let optional = |value: &Option<String>| 1 + value.as_ref().map_or(0, |v| 4 + v.len());
+        4 + self.target_url.len() + 4 + self.target_name.len() + optional(&self.target_framework) + optional(&self.target_code)
+    }
+
+    /// Serialize as an OB 3.0 Alignment
+    pub fn to_json(&self) -> serde_json::Value {
+        let mut alignment = serde_json::json!({
+            "type": ["Alignment"],
+            "targetName": self.target_name,
+            "targetUrl": self.target_url,
+            "targetType": "ceasn:Competency",
+        });
+        if let Some(framework) = &self.target_framework {
+            alignment["targetFramework"] = serde_json::json!(framework);
+        }
+        if let Some(code) = &self.target_code {
+            alignment["targetCode"] = serde_json::json!(code);
+        }
+        alignment
+    }
+}
+
+/// Lowest value a result must reach for the achievement to be awarded
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct ResultThreshold {
+    /// Id of the achievement's result description
+    pub result_description: String,
+    /// Minimum numeric value, inclusive
+    pub min_value: String,
+}
+
+/// Structured requirements stored on an achievement
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct CriteriaRequirements {
+    /// Competencies the achievement certifies
+    pub required_competencies: Vec<Alignment>,
+    /// Minimum result values required at issuance
+    pub min_results: Vec<ResultThreshold>,
+}
+
+impl CriteriaRequirements {
+    /// Serialized size of these requirements
+    pub fn space(&self) -> usize {
+        4 + self.required_competencies.iter().map(Alignment::space).sum::<usize>()
+            + 4 + self.min_results.iter().map(|t| 4 + t.result_description.len() + 4 + t.min_value.len()).sum::<usize>()
+    }
+}
+
+fn parse_number(value: &str) -> Result<f64> {
+    value.parse::<f64>().map_err(|_| error!(ValidationError::ResultValueNotAllowed))
+}
+
+/// Check the requirements declared by a new achievement against its result descriptions
+pub fn validate_requirements(requirements: &CriteriaRequirements, descriptions: &[ResultDescription]) -> Result<()> {
+    if requirements.required_competencies.len() > MAX_ALIGNMENTS || requirements.min_results.len() > MAX_THRESHOLDS {
+        return Err(error!(ValidationError::TooManyItems));
+    }
+
+    for alignment in &requirements.required_competencies {
+        if alignment.target_url.is_empty() || alignment.target_name.is_empty() {
+            return Err(error!(ValidationError::MissingRequiredField));
+        }
+        let too_long = [&alignment.target_url, &alignment.target_name].iter().any(|v| v.len() > MAX_ALIGNMENT_FIELD_LEN)
+            || [&alignment.target_framework, &alignment.target_code]
+                .iter()
+                .any(|v| v.as_ref().is_some_and(|v| v.len() > MAX_ALIGNMENT_FIELD_LEN));
+        if too_long {
+            return Err(error!(ValidationError::FieldTooLong));
+        }
+        if !alignment.target_url.starts_with("https://") && !alignment.target_url.starts_with("http://") {
+            return Err(error!(ValidationError::InvalidUrl));
+        }
+    }
+
+    for (index, threshold) in requirements.min_results.iter().enumerate() {
+        if !descriptions.iter().any(|d| d.id == threshold.result_description) {
+            msg!("❌ Threshold on unknown result description: {}", threshold.result_description);
+            return Err(error!(ValidationError::UnknownResultDescription));
+        }
+        if requirements.min_results[..index].iter().any(|other| other.result_description == threshold.result_description) {
+            return Err(error!(ValidationError::ValidationFailed));
+        }
+        parse_number(&threshold.min_value)?;
+    }
+
+    Ok(())
+}
+
+/// Check that the results supplied at issuance meet every threshold
+/// Issuance without results is not subject to thresholds
+pub fn ensure_thresholds_met(requirements: &CriteriaRequirements, results: &[ResultInput]) -> Result<()> {
+    if results.is_empty() {
+        return Ok(());
+    }
+
+    for threshold in &requirements.min_results {
+        let value = results
+            .iter()
+            .find(|result| result.result_description == threshold.result_description)
+            .and_then(|result| result.value.as_deref());
+        let Some(value) = value else {
+            msg!("❌ Missing result for required {}", threshold.result_description);
+            return Err(error!(ValidationError::CriteriaNotMet));
+        };
+        if parse_number(value)? < parse_number(&threshold.min_value)? {
+            msg!("❌ {} of {} is below the required {}", value, threshold.result_description, threshold.min_value);
+            return Err(error!(ValidationError::CriteriaNotMet));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::results::ResultType;
+
+    fn score() -> ResultDescription {
+        ResultDescription {
+            id: "score".to_string(),
+            name: "Final score".to_string(),
+            result_type: ResultType::Percent,
+            allowed_values: vec![],
+            required_value: None,
+            value_min: Some("0".to_string()),
+            value_max: Some("100".to_string()),
+        }
+    }
+
+    fn requirements() -> CriteriaRequirements {
+        CriteriaRequirements {
+            required_competencies: vec![Alignment {
+                target_url: "https://example.edu/competencies/rust".to_string(),
+                target_name: "Rust programming".to_string(),
+                target_framework: Some("Example Framework".to_string()),
+                target_code: None,
+            }],
+            min_results: vec![ResultThreshold { result_description: "score".to_string(), min_value: "80".to_string() }],
+        }
+    }
+
+    fn score_result(value: &str) -> ResultInput {
+        ResultInput { result_description: "score".to_string(), value: Some(value.to_string()), achieved_level: None }
+    }
+
+    #[test]
+    fn test_thresholds_enforced_when_results_supplied() {
+        let requirements = requirements();
+        assert!(ensure_thresholds_met(&requirements, &[score_result("80")]).is_ok());
+        assert!(ensure_thresholds_met(&requirements, &[score_result("92.5")]).is_ok());
+        assert!(ensure_thresholds_met(&requirements, &[score_result("79.9")]).is_err());
+        let other = ResultInput { result_description: "other".to_string(), value: Some("100".to_string()), achieved_level: None };
+        assert!(ensure_thresholds_met(&requirements, &[other]).is_err());
+        assert!(ensure_thresholds_met(&requirements, &[]).is_ok());
+    }
+
+    #[test]
+    fn test_requirements_must_reference_declared_results() {
+        assert!(validate_requirements(&requirements(), &[score()]).is_ok());
+        assert!(validate_requirements(&requirements(), &[]).is_err());
+
+        let mut duplicated = requirements();
+        duplicated.min_results.push(duplicated.min_results[0].clone());
+        assert!(validate_requirements(&duplicated, &[score()]).is_err());
+
+        let mut unnamed = requirements();
+        unnamed.required_competencies[0].target_name.clear();
+        assert!(validate_requirements(&unnamed, &[score()]).is_err());
+    }
+
+    #[test]
+    fn test_space_matches_serialized_requirements() {
+        let requirements = requirements();
+        assert_eq!(requirements.space(), requirements.try_to_vec().unwrap().len());
+        assert_eq!(requirements.required_competencies[0].to_json()["targetFramework"], "Example Framework");
+    }
+}
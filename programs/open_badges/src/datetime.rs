@@ -0,0 +1,98 @@
+//! Civil Dates for On-Chain Timestamps
+//!
+//! The Clock sysvar gives Unix seconds; credentials and proofs need ISO 8601
+//! `DateTimeZ` strings. The conversion uses Howard Hinnant's `civil_from_days`
+//! algorithm, which is exact for every day of the proleptic Gregorian
+//! calendar including leap years and century rules, and needs no tables or
+//! time-zone data.
+
+use anchor_lang::prelude::*;
+use crate::common::errors::ValidationError;
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// Year, month (1-12) and day (1-31) of the day `days` after 1970-01-01
+pub fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    // Shift the epoch to 0000-03-01 so leap days fall at the end of each 400-year era
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Days from 1970-01-01 to the given civil date
+pub fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = year - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let month = i64::from(month);
+    let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// ISO 8601 `YYYY-MM-DDTHH:MM:SSZ` form of a Unix timestamp
+/// Fails outside years 0000-9999, which the four-digit form cannot express
+pub fn format_iso8601(timestamp: i64) -> Result<String> {
+    let (year, month, day) = civil_from_days(timestamp.div_euclid(SECONDS_PER_DAY));
+    if !(0..=9999).contains(&year) {
+        return Err(error!(ValidationError::InvalidTimestampFormat));
+    }
+    let seconds = timestamp.rem_euclid(SECONDS_PER_DAY);
+    Ok(format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        seconds / 3_600,
+        seconds / 60 % 60,
+        seconds % 60,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_month_and_leap_year_boundaries() {
+        let cases = [
+            (0, "1970-01-01T00:00:00Z"),
+            (-1, "1969-12-31T23:59:59Z"),
+            (951_782_400, "2000-02-29T00:00:00Z"),
+            (1_709_251_199, "2024-02-29T23:59:59Z"),
+            (1_709_251_200, "2024-03-01T00:00:00Z"),
+            (1_677_628_799, "2023-02-28T23:59:59Z"),
+            (1_677_628_800, "2023-03-01T00:00:00Z"),
+            (1_704_067_199, "2023-12-31T23:59:59Z"),
+            (1_735_689_600, "2025-01-01T00:00:00Z"),
+            (4_107_456_000, "2100-02-28T00:00:00Z"),
+            (4_107_542_400, "2100-03-01T00:00:00Z"),
+            (253_402_300_799, "9999-12-31T23:59:59Z"),
+        ];
+        for (timestamp, expected) in cases {
+            assert_eq!(format_iso8601(timestamp).unwrap(), expected, "timestamp {}", timestamp);
+        }
+        assert!(format_iso8601(253_402_300_800).is_err());
+        assert!(format_iso8601(-62_167_219_201).is_err());
+    }
+
+    #[test]
+    fn test_day_conversions_round_trip() {
+        // Every day from 1900 to 2200 maps back to itself and parses to the same instant
+        for days in days_from_civil(1900, 1, 1)..days_from_civil(2200, 1, 1) {
+            let (year, month, day) = civil_from_days(days);
+            assert!((1..=12).contains(&month) && (1..=31).contains(&day));
+            assert_eq!(days_from_civil(year, month, day), days);
+        }
+        for timestamp in (-2_208_988_800..7_258_118_400).step_by(7_777_777) {
+            assert_eq!(crate::parse_iso8601_to_unix(&format_iso8601(timestamp).unwrap()).unwrap(), timestamp);
+        }
+    }
+}
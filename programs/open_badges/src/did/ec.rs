@@ -0,0 +1,113 @@
+//! Elliptic Curve Point Decompression
+//!
+//! did:key carries secp256k1 and P-256 keys as 33-byte compressed points,
+//! while a JWK needs both affine coordinates. Both curves have p ≡ 3 (mod 4),
+//! so y is recovered as (x³ + ax + b)^((p+1)/4) mod p and the prefix byte
+//! picks the root of matching parity.
+
+use anchor_lang::prelude::*;
+use num_bigint::BigUint;
+use crate::common::errors::ValidationError;
+
+/// Short Weierstrass curves with compressed did:key encodings
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Curve {
+    Secp256k1,
+    P256,
+}
+
+impl Curve {
+    /// Field prime, a and b of y² = x³ + ax + b
+    fn parameters(&self) -> (BigUint, BigUint, BigUint) {
+        let hex = |value: &str| BigUint::parse_bytes(value.as_bytes(), 16).expect("curve constants are valid hex");
+        match self {
+            Curve::Secp256k1 => (
+                hex("fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f"),
+                BigUint::from(0u8),
+                BigUint::from(7u8),
+            ),
+            Curve::P256 => (
+                hex("ffffffff00000001000000000000000000000000ffffffffffffffffffffffff"),
+                hex("ffffffff00000001000000000000000000000000fffffffffffffffffffffffc"),
+                hex("5ac635d8aa3a93e7b3ebbd55769886bc651d06b0cc53b0f63bce3c3e27d2604b"),
+            ),
+        }
+    }
+}
+
+/// Affine (x, y) coordinates of a compressed point, 32 big-endian bytes each
+pub fn decompress(curve: Curve, compressed: &[u8]) -> Result<([u8; 32], [u8; 32])> {
+    if compressed.len() != 33 {
+        return Err(error!(ValidationError::InvalidKeyLength));
+    }
+    let odd = match compressed[0] {
+        0x02 => false,
+        0x03 => true,
+        _ => return Err(error!(ValidationError::InvalidKeyEncoding)),
+    };
+
+    let (p, a, b) = curve.parameters();
+    let x = BigUint::from_bytes_be(&compressed[1..]);
+    if x >= p {
+        return Err(error!(ValidationError::InvalidKeyEncoding));
+    }
+    let y_squared = (x.modpow(&BigUint::from(3u8), &p) + &a * &x + &b) % &p;
+    let mut y = y_squared.modpow(&((&p + 1u8) >> 2), &p);
+    if y.modpow(&BigUint::from(2u8), &p) != y_squared {
+        // x is not the abscissa of a point on the curve
+        return Err(error!(ValidationError::InvalidKeyEncoding));
+    }
+    if y.bit(0) != odd {
+        y = &p - y;
+    }
+
+    let mut x_bytes = [0u8; 32];
+    x_bytes.copy_from_slice(&compressed[1..]);
+    Ok((x_bytes, to_32_bytes(&y)))
+}
+
+/// Left-pad a field element to 32 big-endian bytes
+fn to_32_bytes(value: &BigUint) -> [u8; 32] {
+    let bytes = value.to_bytes_be();
+    let mut out = [0u8; 32];
+    out[32 - bytes.len()..].copy_from_slice(&bytes);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compressed(prefix: u8, x: &str) -> Vec<u8> {
+        let mut bytes = vec![prefix];
+        bytes.extend(hex::decode(x).unwrap());
+        bytes
+    }
+
+    #[test]
+    fn test_generators_decompress() {
+        let (_, y) = decompress(
+            Curve::Secp256k1,
+            &compressed(0x02, "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"),
+        )
+        .unwrap();
+        assert_eq!(hex::encode(y), "483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8");
+
+        let (_, y) = decompress(
+            Curve::P256,
+            &compressed(0x03, "6b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296"),
+        )
+        .unwrap();
+        assert_eq!(hex::encode(y), "4fe342e2fe1a7f9b8ee7eb4a7c0f9e162bce33576b315ececbb6406837bf51f5");
+    }
+
+    #[test]
+    fn test_invalid_points_are_rejected() {
+        let x = "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+        assert!(decompress(Curve::Secp256k1, &compressed(0x04, x)).is_err());
+        assert!(decompress(Curve::Secp256k1, &compressed(0x02, x)[..32]).is_err());
+        // x = 5 is not on secp256k1: 5³ + 7 = 132 is a non-residue
+        let not_on_curve = format!("{:0>64}", "05");
+        assert!(decompress(Curve::Secp256k1, &compressed(0x02, &not_on_curve)).is_err());
+    }
+}
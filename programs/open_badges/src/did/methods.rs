@@ -1,10 +1,12 @@
 //! DID method implementations for Solana, Key, and Web methods
 //! 
 //! This module now integrates with the official Identity.com sol-did program
-//! for proper did:sol method resolution.
+//! for proper did:sol method resolution. did:key (with its elliptic-curve
+//! point decompression) and did:web are behind the `did-key` and `did-web`
+//! features.
 
 use anchor_lang::prelude::*;
-use crate::did::{DidDocument, DidUrl, VerificationMethod, JsonWebKey};
+use crate::did::{DidDocument, DidUrl, VerificationMethod, JsonWebKey, ServiceEndpoint};
 use base64::{Engine, engine::general_purpose};
 use std::str::FromStr;
 
@@ -68,10 +70,12 @@ impl SolanaDidResolver {
                 "https://w3id.org/did/v1.0".to_string(), // Official spec context
                 "https://w3id.org/sol/v1".to_string(),   // Official spec context
             ],
+            controller: vec![],
             verification_method: vec![verification_method],
             authentication: vec![vm_id.clone()],
             assertion_method: vec![vm_id.clone()],
             key_agreement: vec![],
+            capability_invocation: vec![],
             service: vec![],
         })
     }
@@ -80,7 +84,7 @@ impl SolanaDidResolver {
     fn encode_solana_key_multibase(&self, pubkey: &Pubkey) -> String {
         let bytes = pubkey.to_bytes();
         // Multibase prefix for base58btc is 'z'
-        format!("z{}", bs58::encode(bytes).into_string())
+        crate::multibase::encode(&bytes)
     }
     
     /// Create JWK for Solana public key
@@ -92,12 +96,180 @@ impl SolanaDidResolver {
             kty: "OKP".to_string(),
             crv: "Ed25519".to_string(),
             x,
+            y: None,
             key_use: Some("sig".to_string()),
             key_ops: vec!["verify".to_string()],
         }
     }
 }
 
+/// sol_did `VerificationMethodFlags` bits used when building a document
+pub mod sol_did_flags {
+    pub const AUTHENTICATION: u16 = 1 << 0;
+    pub const ASSERTION: u16 = 1 << 1;
+    pub const KEY_AGREEMENT: u16 = 1 << 2;
+    pub const CAPABILITY_INVOCATION: u16 = 1 << 3;
+    pub const DID_DOC_HIDDEN: u16 = 1 << 5;
+    pub const OWNERSHIP_PROOF: u16 = 1 << 6;
+    pub const PROTECTED: u16 = 1 << 7;
+}
+
+/// Seed used by the sol_did program for its DID account PDA
+pub const SOL_DID_ACCOUNT_SEED: &[u8] = b"did-account";
+
+/// Fragment of the badge issuance service in an issuer's did:sol document
+pub const BADGE_SERVICE_FRAGMENT: &str = "open-badges";
+
+/// Type of the badge issuance service in an issuer's did:sol document
+pub const BADGE_SERVICE_TYPE: &str = "OpenBadgesIssuer";
+
+/// sol_did method type of Ed25519 keys (`Ed25519VerificationKey2018`)
+const SOL_DID_ED25519_METHOD_TYPE: u8 = 0;
+
+impl SolanaDidResolver {
+    /// Derive the sol_did DID account PDA for an authority key
+    pub fn derive_did_account(authority: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[SOL_DID_ACCOUNT_SEED, authority.as_ref()], &sol_did_cpi::ID)
+    }
+
+    /// sol_did verification method advertising an issuer key for assertions
+    pub fn assertion_method(issuer_key: &crate::issuer_keys::IssuerKey) -> sol_did_cpi::VerificationMethod {
+        sol_did_cpi::VerificationMethod {
+            fragment: issuer_key.fragment(),
+            flags: sol_did_flags::ASSERTION,
+            method_type: SOL_DID_ED25519_METHOD_TYPE,
+            key_data: issuer_key.public_key.to_bytes().to_vec(),
+        }
+    }
+
+    /// sol_did service advertising the issuer's badge endpoint
+    pub fn badge_service(service_endpoint: &str) -> sol_did_cpi::Service {
+        sol_did_cpi::Service {
+            fragment: BADGE_SERVICE_FRAGMENT.to_string(),
+            service_type: BADGE_SERVICE_TYPE.to_string(),
+            service_endpoint: service_endpoint.to_string(),
+        }
+    }
+
+    /// Whether the DID account still lists the method a proof names
+    /// A verification method without a fragment refers to the `default` key
+    pub fn has_verification_method(did_account: &sol_did_cpi::state::DidAccount, verification_method: &str) -> bool {
+        let fragment = verification_method.split_once('#').map_or("default", |(_, fragment)| fragment);
+        std::iter::once(&did_account.initial_verification_method)
+            .chain(did_account.verification_methods.iter())
+            .any(|vm| vm.fragment == fragment)
+    }
+
+    /// The generative DID account sol_did assumes when none exists on-chain
+    pub fn generative_did_account(authority: &Pubkey) -> sol_did_cpi::state::DidAccount {
+        sol_did_cpi::state::DidAccount {
+            version: 0,
+            bump: Self::derive_did_account(authority).1,
+            nonce: 0,
+            initial_verification_method: sol_did_cpi::VerificationMethod {
+                fragment: "default".to_string(),
+                flags: sol_did_flags::CAPABILITY_INVOCATION
+                    | sol_did_flags::OWNERSHIP_PROOF
+                    | sol_did_flags::PROTECTED,
+                method_type: 0,
+                key_data: authority.to_bytes().to_vec(),
+            },
+            verification_methods: vec![],
+            services: vec![],
+            native_controllers: vec![],
+            other_controllers: vec![],
+        }
+    }
+
+    /// Resolve a did:sol DID from the sol_did program's on-chain DidAccount
+    /// Uses the account's real verification methods, services and controllers
+    pub fn resolve_from_account(
+        &self,
+        did_url: &DidUrl,
+        did_account: &sol_did_cpi::state::DidAccount,
+    ) -> Result<DidDocument> {
+        let mut verification_method = vec![];
+        let mut authentication = vec![];
+        let mut assertion_method = vec![];
+        let mut key_agreement = vec![];
+        let mut capability_invocation = vec![];
+
+        let all_methods = std::iter::once(&did_account.initial_verification_method)
+            .chain(did_account.verification_methods.iter());
+
+        for vm in all_methods {
+            if vm.flags & sol_did_flags::DID_DOC_HIDDEN != 0 {
+                continue;
+            }
+
+            let vm_id = format!("{}#{}", did_url.did, vm.fragment);
+            let (key_type, public_key_jwk) = match vm.method_type {
+                0 => {
+                    let pubkey = Pubkey::try_from(vm.key_data.as_slice())
+                        .map_err(|_| error!(crate::common::errors::ValidationError::InvalidKeyLength))?;
+                    ("Ed25519VerificationKey2018", Some(self.create_solana_jwk(&pubkey)))
+                }
+                1 => ("EcdsaSecp256k1RecoveryMethod2020", None),
+                2 => ("EcdsaSecp256k1VerificationKey2019", None),
+                _ => return Err(error!(crate::common::errors::ValidationError::UnsupportedKeyType)),
+            };
+
+            verification_method.push(VerificationMethod {
+                id: vm_id.clone(),
+                key_type: key_type.to_string(),
+                controller: did_url.did.clone(),
+                public_key_multibase: Some(crate::multibase::encode(&vm.key_data)),
+                public_key_jwk,
+            });
+
+            if vm.flags & sol_did_flags::AUTHENTICATION != 0 {
+                authentication.push(vm_id.clone());
+            }
+            if vm.flags & sol_did_flags::ASSERTION != 0 {
+                assertion_method.push(vm_id.clone());
+            }
+            if vm.flags & sol_did_flags::KEY_AGREEMENT != 0 {
+                key_agreement.push(vm_id.clone());
+            }
+            if vm.flags & sol_did_flags::CAPABILITY_INVOCATION != 0 {
+                capability_invocation.push(vm_id);
+            }
+        }
+
+        let service = did_account
+            .services
+            .iter()
+            .map(|svc| ServiceEndpoint {
+                id: format!("{}#{}", did_url.did, svc.fragment),
+                service_type: svc.service_type.clone(),
+                service_endpoint: svc.service_endpoint.clone(),
+            })
+            .collect();
+
+        let controller = did_account
+            .native_controllers
+            .iter()
+            .map(|key| format!("did:sol:{}", key))
+            .chain(did_account.other_controllers.iter().cloned())
+            .collect();
+
+        Ok(DidDocument {
+            id: did_url.did.clone(),
+            context: vec![
+                "https://w3id.org/did/v1.0".to_string(),
+                "https://w3id.org/sol/v2.0".to_string(),
+            ],
+            controller,
+            verification_method,
+            authentication,
+            assertion_method,
+            key_agreement,
+            capability_invocation,
+            service,
+        })
+    }
+}
+
 impl OpenBadgesSolanaDidResolver {
     pub fn new() -> Self {
         Self
@@ -127,10 +299,12 @@ impl OpenBadgesSolanaDidResolver {
                 "https://w3id.org/security/suites/ed25519-2020/v1".to_string(),
                 "https://purl.imsglobal.org/spec/ob/v3p0/context-3.0.3.json".to_string(), // Open Badges context
             ],
+            controller: vec![],
             verification_method: vec![verification_method],
             authentication: vec![vm_id.clone()],
             assertion_method: vec![vm_id],
             key_agreement: vec![],
+            capability_invocation: vec![],
             service: vec![],
         })
     }
@@ -139,7 +313,7 @@ impl OpenBadgesSolanaDidResolver {
     fn encode_solana_key_multibase(&self, pubkey: &Pubkey) -> String {
         let bytes = pubkey.to_bytes();
         // Multibase prefix for base58btc is 'z'
-        format!("z{}", bs58::encode(bytes).into_string())
+        crate::multibase::encode(&bytes)
     }
     
     /// Create JWK for Solana public key
@@ -151,21 +325,70 @@ impl OpenBadgesSolanaDidResolver {
             kty: "OKP".to_string(),
             crv: "Ed25519".to_string(),
             x,
+            y: None,
             key_use: Some("sig".to_string()),
             key_ops: vec!["verify".to_string()],
         }
     }
 }
 
+#[cfg(feature = "did-key")]
+/// Key types a did:key identifier can carry, by multicodec prefix
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MulticodecKeyType {
+    /// ed25519-pub (0xed)
+    Ed25519,
+    /// x25519-pub (0xec), used for key agreement only
+    X25519,
+    /// secp256k1-pub (0xe7), compressed point
+    Secp256k1,
+    /// p256-pub (0x1200), compressed point
+    P256,
+}
+
+#[cfg(feature = "did-key")]
+impl MulticodecKeyType {
+    /// Key type, multicodec prefix (unsigned varint) and public key length
+    const CODECS: [(MulticodecKeyType, &'static [u8], usize); 4] = [
+        (MulticodecKeyType::Ed25519, &[0xed, 0x01], 32),
+        (MulticodecKeyType::X25519, &[0xec, 0x01], 32),
+        (MulticodecKeyType::Secp256k1, &[0xe7, 0x01], 33),
+        (MulticodecKeyType::P256, &[0x80, 0x24], 33),
+    ];
+
+    /// Verification method type of keys of this kind
+    pub fn verification_method_type(&self) -> &'static str {
+        match self {
+            MulticodecKeyType::Ed25519 => "Ed25519VerificationKey2020",
+            MulticodecKeyType::X25519 => "X25519KeyAgreementKey2020",
+            MulticodecKeyType::Secp256k1 => "EcdsaSecp256k1VerificationKey2019",
+            MulticodecKeyType::P256 => "JsonWebKey2020",
+        }
+    }
+
+    /// JSON-LD context defining the verification method type
+    pub fn suite_context(&self) -> &'static str {
+        match self {
+            MulticodecKeyType::Ed25519 => "https://w3id.org/security/suites/ed25519-2020/v1",
+            MulticodecKeyType::X25519 => "https://w3id.org/security/suites/x25519-2020/v1",
+            MulticodecKeyType::Secp256k1 => "https://w3id.org/security/suites/secp256k1-2019/v1",
+            MulticodecKeyType::P256 => "https://w3id.org/security/suites/jws-2020/v1",
+        }
+    }
+}
+
+#[cfg(feature = "did-key")]
 /// Key DID method resolver (did:key:)
 pub struct KeyDidResolver;
 
+#[cfg(feature = "did-key")]
 impl KeyDidResolver {
     pub fn new() -> Self {
         Self
     }
     
     /// Resolve a did:key DID to a DID document
+    /// X25519 keys are exposed for key agreement; the other types sign
     pub fn resolve(&self, did_url: &DidUrl) -> Result<DidDocument> {
         // Parse multicodec key from method-specific ID
         let (key_type, public_key_bytes) = self.parse_multicodec_key(&did_url.method_specific_id)?;
@@ -174,51 +397,83 @@ impl KeyDidResolver {
         let vm_id = format!("{}#{}", did_url.did, did_url.method_specific_id);
         let verification_method = VerificationMethod {
             id: vm_id.clone(),
-            key_type: key_type.clone(),
+            key_type: key_type.verification_method_type().to_string(),
             controller: did_url.did.clone(),
-            public_key_multibase: Some(format!("z{}", did_url.method_specific_id)),
-            public_key_jwk: if key_type == "Ed25519VerificationKey2020" {
-                Some(self.create_ed25519_jwk(&public_key_bytes))
-            } else {
-                None
-            },
+            public_key_multibase: Some(did_url.method_specific_id.clone()),
+            public_key_jwk: Some(self.create_jwk(key_type, &public_key_bytes)?),
         };
         
+        let (signing, key_agreement) = match key_type {
+            MulticodecKeyType::X25519 => (vec![], vec![vm_id]),
+            _ => (vec![vm_id], vec![]),
+        };
         Ok(DidDocument {
             id: did_url.did.clone(),
             context: vec![
                 "https://www.w3.org/ns/did/v1".to_string(),
-                "https://w3id.org/security/suites/ed25519-2020/v1".to_string(),
+                key_type.suite_context().to_string(),
             ],
+            controller: vec![],
             verification_method: vec![verification_method],
-            authentication: vec![vm_id.clone()],
-            assertion_method: vec![vm_id],
-            key_agreement: vec![],
+            authentication: signing.clone(),
+            assertion_method: signing,
+            key_agreement,
+            capability_invocation: vec![],
             service: vec![],
         })
     }
     
     /// Parse multicodec key from method-specific ID
-    fn parse_multicodec_key(&self, method_id: &str) -> Result<(String, Vec<u8>)> {
-        // Decode base58
-        let decoded = bs58::decode(method_id)
-            .into_vec()
-            .map_err(|_| error!(crate::common::errors::ValidationError::InvalidKeyEncoding))?;
+    fn parse_multicodec_key(&self, method_id: &str) -> Result<(MulticodecKeyType, Vec<u8>)> {
+        // The identifier is a base58btc multibase string
+        if !method_id.starts_with(crate::multibase::BASE58BTC_PREFIX) {
+            return Err(error!(crate::common::errors::ValidationError::UnsupportedKeyEncoding));
+        }
+        let decoded = crate::multibase::decode(method_id)
+            .ok_or_else(|| error!(crate::common::errors::ValidationError::InvalidKeyEncoding))?;
         
         if decoded.len() < 2 {
             return Err(error!(crate::common::errors::ValidationError::InvalidKeyEncoding));
         }
         
         // Check multicodec prefix
-        match (decoded[0], decoded[1]) {
-            (0xed, 0x01) => {
-                // Ed25519 public key
-                if decoded.len() != 34 {
-                    return Err(error!(crate::common::errors::ValidationError::InvalidKeyLength));
-                }
-                Ok(("Ed25519VerificationKey2020".to_string(), decoded[2..].to_vec()))
-            }
-            _ => Err(error!(crate::common::errors::ValidationError::UnsupportedKeyType)),
+        let (key_type, prefix, key_len) = MulticodecKeyType::CODECS
+            .iter()
+            .find(|(_, prefix, _)| decoded.starts_with(prefix))
+            .ok_or_else(|| error!(crate::common::errors::ValidationError::UnsupportedKeyType))?;
+        if decoded.len() != prefix.len() + key_len {
+            return Err(error!(crate::common::errors::ValidationError::InvalidKeyLength));
+        }
+        Ok((*key_type, decoded[prefix.len()..].to_vec()))
+    }
+    
+    /// Create the JWK of a did:key public key
+    fn create_jwk(&self, key_type: MulticodecKeyType, public_key_bytes: &[u8]) -> Result<JsonWebKey> {
+        let encode = |bytes: &[u8]| general_purpose::URL_SAFE_NO_PAD.encode(bytes);
+        let ec_jwk = |curve: crate::did::ec::Curve, crv: &str| -> Result<JsonWebKey> {
+            let (x, y) = crate::did::ec::decompress(curve, public_key_bytes)?;
+            Ok(JsonWebKey {
+                kty: "EC".to_string(),
+                crv: crv.to_string(),
+                x: encode(&x),
+                y: Some(encode(&y)),
+                key_use: Some("sig".to_string()),
+                key_ops: vec!["verify".to_string()],
+            })
+        };
+        
+        match key_type {
+            MulticodecKeyType::Ed25519 => Ok(self.create_ed25519_jwk(public_key_bytes)),
+            MulticodecKeyType::X25519 => Ok(JsonWebKey {
+                kty: "OKP".to_string(),
+                crv: "X25519".to_string(),
+                x: encode(public_key_bytes),
+                y: None,
+                key_use: Some("enc".to_string()),
+                key_ops: vec!["deriveKey".to_string()],
+            }),
+            MulticodecKeyType::Secp256k1 => ec_jwk(crate::did::ec::Curve::Secp256k1, "secp256k1"),
+            MulticodecKeyType::P256 => ec_jwk(crate::did::ec::Curve::P256, "P-256"),
         }
     }
     
@@ -230,15 +485,18 @@ impl KeyDidResolver {
             kty: "OKP".to_string(),
             crv: "Ed25519".to_string(),
             x,
+            y: None,
             key_use: Some("sig".to_string()),
             key_ops: vec!["verify".to_string()],
         }
     }
 }
 
+#[cfg(feature = "did-web")]
 /// Web DID method resolver (did:web:)
 pub struct WebDidResolver;
 
+#[cfg(feature = "did-web")]
 impl WebDidResolver {
     pub fn new() -> Self {
         Self
@@ -272,10 +530,12 @@ impl WebDidResolver {
                 "https://www.w3.org/ns/did/v1".to_string(),
                 "https://w3id.org/security/suites/ed25519-2020/v1".to_string(),
             ],
+            controller: vec![],
             verification_method: vec![verification_method],
             authentication: vec![vm_id.clone()],
             assertion_method: vec![vm_id],
             key_agreement: vec![],
+            capability_invocation: vec![],
             service: vec![],
         })
     }
@@ -287,6 +547,7 @@ impl Default for SolanaDidResolver {
     }
 }
 
+#[cfg(feature = "did-key")]
 impl Default for KeyDidResolver {
     fn default() -> Self {
         Self::new()
@@ -298,3 +559,80 @@ impl Default for OpenBadgesSolanaDidResolver {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "did-key")]
+    fn did_key(prefix: &[u8], key: &[u8]) -> DidUrl {
+        let multibase = crate::multibase::encode(&[prefix, key].concat());
+        DidUrl::parse(&format!("did:key:{}", multibase)).unwrap()
+    }
+
+    #[cfg(feature = "did-key")]
+    #[test]
+    fn test_resolves_every_multicodec_key_type() {
+        let p256_generator =
+            hex::decode("036b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296").unwrap();
+        let document = KeyDidResolver::new().resolve(&did_key(&[0x80, 0x24], &p256_generator)).unwrap();
+        let jwk = document.verification_method[0].public_key_jwk.clone().unwrap();
+        assert_eq!(document.verification_method[0].key_type, "JsonWebKey2020");
+        assert_eq!((jwk.kty.as_str(), jwk.crv.as_str()), ("EC", "P-256"));
+        assert!(jwk.y.is_some());
+
+        let x25519 = KeyDidResolver::new().resolve(&did_key(&[0xec, 0x01], &[9u8; 32])).unwrap();
+        assert_eq!(x25519.verification_method[0].key_type, "X25519KeyAgreementKey2020");
+        assert!(x25519.assertion_method.is_empty());
+        assert_eq!(x25519.key_agreement.len(), 1);
+
+        let ed25519 = KeyDidResolver::new().resolve(&did_key(&[0xed, 0x01], &[1u8; 32])).unwrap();
+        assert_eq!(ed25519.verification_method[0].public_key_jwk.clone().unwrap().y, None);
+        assert!(KeyDidResolver::new().resolve(&did_key(&[0xe7, 0x01], &[2u8; 32])).is_err());
+        assert!(KeyDidResolver::new().resolve(&did_key(&[0x12, 0x05], &[2u8; 32])).is_err());
+    }
+
+    #[test]
+    fn test_removed_verification_method_is_not_found() {
+        let authority = Pubkey::new_unique();
+        let issuer_key = crate::issuer_keys::IssuerKey {
+            issuer: Pubkey::new_unique(),
+            key_index: 1,
+            public_key: Pubkey::new_unique(),
+            created_at: "2024-01-01T00:00:00Z".to_string(),
+            retired_at: None,
+            bump: 255,
+        };
+        let mut account = SolanaDidResolver::generative_did_account(&authority);
+        let method = issuer_key.verification_method_id();
+        assert!(SolanaDidResolver::has_verification_method(&account, &format!("did:sol:{}", issuer_key.issuer)));
+        assert!(!SolanaDidResolver::has_verification_method(&account, &method));
+
+        account.verification_methods.push(SolanaDidResolver::assertion_method(&issuer_key));
+        assert!(SolanaDidResolver::has_verification_method(&account, &method));
+        account.verification_methods.clear();
+        assert!(!SolanaDidResolver::has_verification_method(&account, &method));
+    }
+
+    #[test]
+    fn test_published_issuer_key_and_service_resolve() {
+        let authority = Pubkey::new_unique();
+        let issuer_key = crate::issuer_keys::IssuerKey {
+            issuer: Pubkey::new_unique(),
+            key_index: 2,
+            public_key: Pubkey::new_unique(),
+            created_at: "2024-01-01T00:00:00Z".to_string(),
+            retired_at: None,
+            bump: 255,
+        };
+        let mut account = SolanaDidResolver::generative_did_account(&authority);
+        account.verification_methods.push(SolanaDidResolver::assertion_method(&issuer_key));
+        account.services.push(SolanaDidResolver::badge_service("https://badges.example.edu"));
+
+        let did_url = DidUrl::parse(&format!("did:sol:{}", authority)).unwrap();
+        let document = SolanaDidResolver::new().resolve_from_account(&did_url, &account).unwrap();
+        assert_eq!(document.assertion_method, vec![format!("did:sol:{}#key-2", authority)]);
+        assert_eq!(document.service[0].service_type, BADGE_SERVICE_TYPE);
+        assert_eq!(document.service[0].service_endpoint, "https://badges.example.edu");
+    }
+}
@@ -5,6 +5,8 @@
 
 pub mod resolver;
 pub mod methods;
+#[cfg(feature = "did-key")]
+pub mod ec;
 
 pub use resolver::*;
 pub use methods::*;
@@ -33,6 +35,9 @@ pub struct DidDocument {
     /// Context for JSON-LD processing
     #[serde(rename = "@context")]
     pub context: Vec<String>,
+    /// Controllers of the DID (did:sol native and other controllers)
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub controller: Vec<String>,
     /// Verification methods (public keys)
     #[serde(rename = "verificationMethod")]
     pub verification_method: Vec<VerificationMethod>,
@@ -45,6 +50,9 @@ pub struct DidDocument {
     /// Key agreement methods
     #[serde(rename = "keyAgreement", skip_serializing_if = "Vec::is_empty")]
     pub key_agreement: Vec<String>,
+    /// Capability invocation methods (keys allowed to update the DID)
+    #[serde(rename = "capabilityInvocation", skip_serializing_if = "Vec::is_empty", default)]
+    pub capability_invocation: Vec<String>,
     /// Service endpoints
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub service: Vec<ServiceEndpoint>,
@@ -71,12 +79,15 @@ pub struct VerificationMethod {
 /// JSON Web Key representation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JsonWebKey {
-    /// Key type (e.g., "OKP" for Ed25519)
+    /// Key type (e.g., "OKP" for Ed25519, "EC" for secp256k1 and P-256)
     pub kty: String,
     /// Curve (e.g., "Ed25519")
     pub crv: String,
     /// X coordinate (base64url encoded)
     pub x: String,
+    /// Y coordinate of EC keys (base64url encoded)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub y: Option<String>,
     /// Key use (optional)
     #[serde(rename = "use", skip_serializing_if = "Option::is_none")]
     pub key_use: Option<String>,
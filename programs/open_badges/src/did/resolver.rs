@@ -1,39 +1,234 @@
 //! Universal DID resolver supporting multiple DID methods
+//!
+//! Each DID method implements `DidMethodResolver` and is registered in a
+//! `DidResolverRegistry`. Resolution follows DID Core's contract: it never
+//! fails outright, but returns the document together with resolution
+//! metadata naming the method and, when no document could be produced, a
+//! DID Core error code.
 
 use anchor_lang::prelude::*;
+use base64::{Engine, engine::general_purpose};
+use serde::{Deserialize, Serialize};
+use crate::common::errors::ValidationError;
 use crate::did::{DidDocument, DidUrl, DidMethod};
-use crate::did::methods::{SolanaDidResolver, KeyDidResolver, WebDidResolver};
+use crate::did::methods::{OpenBadgesSolanaDidResolver, SolanaDidResolver};
+#[cfg(feature = "did-key")]
+use crate::did::methods::KeyDidResolver;
+#[cfg(feature = "did-web")]
+use crate::did::methods::WebDidResolver;
 
-/// Universal DID resolver
+/// Media type of resolved DID documents
+pub const DID_DOCUMENT_CONTENT_TYPE: &str = "application/did+json";
+
+/// DID Core resolution error: the DID is not conformant or its identifier is malformed
+pub const ERROR_INVALID_DID: &str = "invalidDid";
+
+/// DID Core resolution error: no resolver is registered for the DID method
+pub const ERROR_METHOD_NOT_SUPPORTED: &str = "methodNotSupported";
+
+/// Resolver of one DID method
+pub trait DidMethodResolver {
+    /// The DID method this resolver handles
+    fn method(&self) -> DidMethod;
+
+    /// Method name as it appears in the DID (`did:<name>:...`)
+    fn method_name(&self) -> &'static str;
+
+    /// Resolve a parsed DID of this method to its document
+    fn resolve(&self, did_url: &DidUrl) -> Result<DidDocument>;
+}
+
+impl DidMethodResolver for SolanaDidResolver {
+    fn method(&self) -> DidMethod {
+        DidMethod::Sol
+    }
+
+    fn method_name(&self) -> &'static str {
+        "sol"
+    }
+
+    fn resolve(&self, did_url: &DidUrl) -> Result<DidDocument> {
+        SolanaDidResolver::resolve(self, did_url)
+    }
+}
+
+impl DidMethodResolver for OpenBadgesSolanaDidResolver {
+    fn method(&self) -> DidMethod {
+        DidMethod::ObSol
+    }
+
+    fn method_name(&self) -> &'static str {
+        "ob-sol"
+    }
+
+    fn resolve(&self, did_url: &DidUrl) -> Result<DidDocument> {
+        OpenBadgesSolanaDidResolver::resolve(self, did_url)
+    }
+}
+
+#[cfg(feature = "did-key")]
+impl DidMethodResolver for KeyDidResolver {
+    fn method(&self) -> DidMethod {
+        DidMethod::Key
+    }
+
+    fn method_name(&self) -> &'static str {
+        "key"
+    }
+
+    fn resolve(&self, did_url: &DidUrl) -> Result<DidDocument> {
+        KeyDidResolver::resolve(self, did_url)
+    }
+}
+
+#[cfg(feature = "did-web")]
+impl DidMethodResolver for WebDidResolver {
+    fn method(&self) -> DidMethod {
+        DidMethod::Web
+    }
+
+    fn method_name(&self) -> &'static str {
+        "web"
+    }
+
+    fn resolve(&self, did_url: &DidUrl) -> Result<DidDocument> {
+        WebDidResolver::resolve(self, did_url)
+    }
+}
+
+/// DID Core resolution metadata
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DidResolutionMetadata {
+    /// Media type of the returned document
+    #[serde(rename = "contentType", skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    /// DID method that handled the request
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub method: Option<String>,
+    /// DID Core error code when no document was produced
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Result of resolving a DID: metadata and, on success, the document
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DidResolutionResult {
+    /// How resolution went
+    #[serde(rename = "didResolutionMetadata")]
+    pub did_resolution_metadata: DidResolutionMetadata,
+    /// The resolved document
+    #[serde(rename = "didDocument")]
+    pub did_document: Option<DidDocument>,
+}
+
+impl DidResolutionResult {
+    fn failed(method: Option<&str>, error: &str) -> Self {
+        Self {
+            did_resolution_metadata: DidResolutionMetadata {
+                content_type: None,
+                method: method.map(str::to_string),
+                error: Some(error.to_string()),
+            },
+            did_document: None,
+        }
+    }
+}
+
+/// Registry of DID method resolvers
+pub struct DidResolverRegistry {
+    resolvers: Vec<Box<dyn DidMethodResolver>>,
+}
+
+impl DidResolverRegistry {
+    /// An empty registry
+    pub fn new() -> Self {
+        Self { resolvers: vec![] }
+    }
+
+    /// A registry with every method compiled into the program
+    pub fn with_default_methods() -> Self {
+        let mut registry = Self::new();
+        registry.register(Box::new(SolanaDidResolver::new()));
+        registry.register(Box::new(OpenBadgesSolanaDidResolver::new()));
+        #[cfg(feature = "did-key")]
+        registry.register(Box::new(KeyDidResolver::new()));
+        #[cfg(feature = "did-web")]
+        registry.register(Box::new(WebDidResolver::new()));
+        registry
+    }
+
+    /// Register a resolver, replacing any earlier one for the same method
+    pub fn register(&mut self, resolver: Box<dyn DidMethodResolver>) {
+        self.resolvers.retain(|existing| existing.method() != resolver.method());
+        self.resolvers.push(resolver);
+    }
+
+    /// The resolver registered for a method
+    pub fn resolver_for(&self, method: &DidMethod) -> Option<&dyn DidMethodResolver> {
+        self.resolvers.iter().find(|resolver| resolver.method() == *method).map(|resolver| &**resolver)
+    }
+
+    /// Resolve a DID, reporting failures in the resolution metadata
+    pub fn resolve(&self, did: &str) -> DidResolutionResult {
+        let did_url = match DidUrl::parse(did) {
+            Ok(did_url) => did_url,
+            Err(_) if did.starts_with("did:") && did.split(':').count() >= 3 => {
+                return DidResolutionResult::failed(did.split(':').nth(1), ERROR_METHOD_NOT_SUPPORTED);
+            }
+            Err(_) => return DidResolutionResult::failed(None, ERROR_INVALID_DID),
+        };
+        let Some(resolver) = self.resolver_for(&did_url.method) else {
+            return DidResolutionResult::failed(did.split(':').nth(1), ERROR_METHOD_NOT_SUPPORTED);
+        };
+
+        // The registered methods derive documents from the identifier alone,
+        // so a failing resolver means the identifier itself is malformed
+        match resolver.resolve(&did_url) {
+            Ok(document) => DidResolutionResult {
+                did_resolution_metadata: DidResolutionMetadata {
+                    content_type: Some(DID_DOCUMENT_CONTENT_TYPE.to_string()),
+                    method: Some(resolver.method_name().to_string()),
+                    error: None,
+                },
+                did_document: Some(document),
+            },
+            Err(_) => DidResolutionResult::failed(Some(resolver.method_name()), ERROR_INVALID_DID),
+        }
+    }
+
+    /// Resolve a DID to its document, mapping resolution errors to program errors
+    pub fn resolve_document(&self, did: &str) -> Result<DidDocument> {
+        let result = self.resolve(did);
+        match (result.did_document, result.did_resolution_metadata.error.as_deref()) {
+            (Some(document), _) => Ok(document),
+            (None, Some(ERROR_METHOD_NOT_SUPPORTED)) => Err(error!(ValidationError::UnsupportedDidMethod)),
+            (None, _) => Err(error!(ValidationError::InvalidDid)),
+        }
+    }
+}
+
+impl Default for DidResolverRegistry {
+    fn default() -> Self {
+        Self::with_default_methods()
+    }
+}
+
+/// Universal DID resolver over the default method registry
 pub struct DidResolver {
-    /// Solana DID resolver
-    sol_resolver: SolanaDidResolver,
-    /// Key DID resolver
-    key_resolver: KeyDidResolver,
-    /// Web DID resolver
-    web_resolver: WebDidResolver,
+    registry: DidResolverRegistry,
 }
 
 impl DidResolver {
     /// Create a new universal DID resolver
     pub fn new() -> Self {
         Self {
-            sol_resolver: SolanaDidResolver::new(),
-            key_resolver: KeyDidResolver::new(),
-            web_resolver: WebDidResolver::new(),
+            registry: DidResolverRegistry::with_default_methods(),
         }
     }
     
     /// Resolve a DID to a DID document
     pub fn resolve(&self, did: &str) -> Result<DidDocument> {
-        let did_url = DidUrl::parse(did)?;
-        
-        match did_url.method {
-            DidMethod::Sol => self.sol_resolver.resolve(&did_url),
-            DidMethod::Key => self.key_resolver.resolve(&did_url),
-            DidMethod::Web => self.web_resolver.resolve(&did_url),
-            DidMethod::ObSol => self.sol_resolver.resolve(&did_url), // Use sol resolver for ob-sol method
-        }
+        self.registry.resolve_document(did)
     }
     
     /// Resolve a verification method to get public key
@@ -58,6 +253,24 @@ impl DidResolver {
         Err(error!(crate::common::errors::ValidationError::VerificationMethodNotFound))
     }
     
+    /// Resolve a DID URL to an assertion key, using the document's first
+    /// assertion method when the URL has no fragment
+    pub fn resolve_assertion_key(&self, did_url: &str) -> Result<Vec<u8>> {
+        let parsed = DidUrl::parse(did_url)?;
+        if parsed.fragment.is_some() {
+            return self.resolve_verification_method(did_url);
+        }
+        
+        let did_doc = self.resolve(&parsed.did)?;
+        let vm_id = did_doc.assertion_method.first()
+            .ok_or_else(|| error!(crate::common::errors::ValidationError::VerificationMethodNotFound))?;
+        
+        did_doc.verification_method.iter()
+            .find(|vm| &vm.id == vm_id)
+            .ok_or_else(|| error!(crate::common::errors::ValidationError::VerificationMethodNotFound))
+            .and_then(|vm| self.extract_public_key(vm))
+    }
+    
     /// Extract public key bytes from verification method
     fn extract_public_key(&self, vm: &crate::did::VerificationMethod) -> Result<Vec<u8>> {
         if let Some(public_key_multibase) = &vm.public_key_multibase {
@@ -71,27 +284,32 @@ impl DidResolver {
         Err(error!(crate::common::errors::ValidationError::NoPublicKeyFound))
     }
     
-    /// Decode multibase-encoded public key
+    /// Decode a base58btc multibase key, with or without the Ed25519 multicodec prefix
     fn decode_multibase_key(&self, multibase_key: &str) -> Result<Vec<u8>> {
-        // Placeholder multibase decoding
-        // In a real implementation, this would decode the multibase string
-        if multibase_key.starts_with('z') {
-            // Assume base58btc encoding
-            Ok(vec![0u8; 32]) // Placeholder Ed25519 public key
-        } else {
-            Err(error!(crate::common::errors::ValidationError::UnsupportedKeyEncoding))
+        if !multibase_key.starts_with(crate::multibase::BASE58BTC_PREFIX) {
+            return Err(error!(crate::common::errors::ValidationError::UnsupportedKeyEncoding));
+        }
+        let decoded = crate::multibase::decode(multibase_key)
+            .ok_or_else(|| error!(crate::common::errors::ValidationError::InvalidKeyEncoding))?;
+        
+        match decoded.as_slice() {
+            [0xed, 0x01, key @ ..] if key.len() == 32 => Ok(key.to_vec()),
+            key if key.len() == 32 => Ok(key.to_vec()),
+            _ => Err(error!(crate::common::errors::ValidationError::InvalidKeyLength)),
         }
     }
     
-    /// Decode JWK public key
+    /// Decode the `x` coordinate of an Ed25519 JWK
     fn decode_jwk_key(&self, jwk: &crate::did::JsonWebKey) -> Result<Vec<u8>> {
-        if jwk.kty == "OKP" && jwk.crv == "Ed25519" {
-            // Decode base64url x coordinate
-            // Placeholder decoding
-            Ok(vec![0u8; 32]) // Placeholder Ed25519 public key
-        } else {
-            Err(error!(crate::common::errors::ValidationError::UnsupportedKeyType))
+        if jwk.kty != "OKP" || jwk.crv != "Ed25519" {
+            return Err(error!(crate::common::errors::ValidationError::UnsupportedKeyType));
+        }
+        let key = general_purpose::URL_SAFE_NO_PAD.decode(&jwk.x)
+            .map_err(|_| error!(crate::common::errors::ValidationError::InvalidKeyEncoding))?;
+        if key.len() != 32 {
+            return Err(error!(crate::common::errors::ValidationError::InvalidKeyLength));
         }
+        Ok(key)
     }
 }
 
@@ -112,3 +330,60 @@ pub fn resolve_verification_method(verification_method: &str) -> Result<Vec<u8>>
     let resolver = DidResolver::new();
     resolver.resolve_verification_method(verification_method)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FixedResolver;
+
+    impl DidMethodResolver for FixedResolver {
+        fn method(&self) -> DidMethod {
+            DidMethod::Web
+        }
+
+        fn method_name(&self) -> &'static str {
+            "web"
+        }
+
+        fn resolve(&self, did_url: &DidUrl) -> Result<DidDocument> {
+            let mut document = OpenBadgesSolanaDidResolver::new()
+                .resolve(&DidUrl::parse(&format!("did:ob-sol:{}", Pubkey::default()))?)?;
+            document.id = did_url.did.clone();
+            Ok(document)
+        }
+    }
+
+    #[test]
+    fn test_resolution_reports_method_and_errors() {
+        let registry = DidResolverRegistry::with_default_methods();
+        let resolved = registry.resolve(&format!("did:sol:{}", Pubkey::new_unique()));
+        assert!(resolved.did_document.is_some());
+        assert_eq!(resolved.did_resolution_metadata.method.as_deref(), Some("sol"));
+        assert_eq!(resolved.did_resolution_metadata.error, None);
+
+        let unsupported = registry.resolve("did:ion:EiClkZMDxPKqC9c");
+        assert_eq!(unsupported.did_resolution_metadata.error.as_deref(), Some(ERROR_METHOD_NOT_SUPPORTED));
+        assert_eq!(unsupported.did_resolution_metadata.method.as_deref(), Some("ion"));
+
+        let malformed = registry.resolve("did:sol:not-a-key");
+        assert!(malformed.did_document.is_none());
+        assert_eq!(malformed.did_resolution_metadata.error.as_deref(), Some(ERROR_INVALID_DID));
+        assert_eq!(registry.resolve("sol:abc").did_resolution_metadata.error.as_deref(), Some(ERROR_INVALID_DID));
+    }
+
+    #[test]
+    fn test_registered_resolver_replaces_method() {
+        let mut registry = DidResolverRegistry::new();
+        assert_eq!(
+            registry.resolve("did:web:example.edu").did_resolution_metadata.error.as_deref(),
+            Some(ERROR_METHOD_NOT_SUPPORTED)
+        );
+        assert!(registry.resolve_document("did:web:example.edu").is_err());
+
+        registry.register(Box::new(FixedResolver));
+        registry.register(Box::new(FixedResolver));
+        assert_eq!(registry.resolvers.len(), 1);
+        assert_eq!(registry.resolve_document("did:web:example.edu").unwrap().id, "did:web:example.edu");
+    }
+}
@@ -0,0 +1,115 @@
+//! Credential Document Integrity
+//!
+//! A credential travels off-chain as JSON: in a wallet, an email, a baked
+//! image. `verify_credential_document` regenerates the account's full
+//! JSON-LD document (`credential::credential_account_to_json`) and compares
+//! a presented copy with it, so a copy altered after issuance is told apart
+//! from the chain record. A copy matches when it is the same JSON value as
+//! that document regardless of whitespace and member order; otherwise the
+//! members that differ are reported.
+//!
+//! The signed document issuance emitted (`canonical::signed_document`) is
+//! recognized byte-for-byte, since its proof then verifies as-is, but it
+//! does not match on its own: the issuer's signature covers only the
+//! canonical projection, not the account's validity period, results,
+//! narrative, schemas, terms of use, extensions or related credentials, so a
+//! copy of it cannot vouch for them.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use crate::common::errors::ValidationError;
+use crate::AchievementCredential;
+
+/// Outcome of comparing a presented document with a credential account
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DocumentComparison {
+    /// Whether the document is byte-for-byte the signed document, whose proof verifies as-is
+    pub exact: bool,
+    /// JSON paths, e.g. `credentialSubject.id`, whose values differ from the account; empty when it matches
+    pub mismatched: Vec<String>,
+}
+
+impl DocumentComparison {
+    /// Whether the document is the account's full document
+    pub fn matches(&self) -> bool {
+        self.mismatched.is_empty()
+    }
+}
+
+/// Compare `document` with the full document regenerated from `credential`
+pub fn compare(credential: &AchievementCredential, document: &str) -> Result<DocumentComparison> {
+    let presented: serde_json::Value = serde_json::from_str(document)
+        .map_err(|_| error!(ValidationError::InvalidJson))?;
+
+    let exact = crate::canonical::signed_document(credential)
+        .is_some_and(|signed| hash(signed.as_bytes()) == hash(document.trim().as_bytes()));
+
+    let mut mismatched = Vec::new();
+    diff("", &crate::credential::credential_account_to_json(credential), &presented, &mut mismatched);
+    Ok(DocumentComparison { exact, mismatched })
+}
+
+/// Append the paths under `path` where `presented` differs from `expected`
+/// Objects are compared member by member; any other values, arrays included, as a whole
+fn diff(path: &str, expected: &serde_json::Value, presented: &serde_json::Value, paths: &mut Vec<String>) {
+    let (Some(expected), Some(presented)) = (expected.as_object(), presented.as_object()) else {
+        if expected != presented {
+            paths.push(if path.is_empty() { "$".to_string() } else { path.to_string() });
+        }
+        return;
+    };
+    let mut keys: Vec<&String> = expected.keys().chain(presented.keys().filter(|key| !expected.contains_key(*key))).collect();
+    keys.sort();
+    for key in keys {
+        let member = if path.is_empty() { key.clone() } else { format!("{}.{}", path, key) };
+        match (expected.get(key), presented.get(key)) {
+            (Some(expected), Some(presented)) => diff(&member, expected, presented, paths),
+            _ => paths.push(member),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{fixture_credential, sign_credential, TestKeypair};
+
+    fn credential() -> AchievementCredential {
+        let mut credential = fixture_credential(&Pubkey::new_unique(), &Pubkey::new_unique(), &Pubkey::new_unique(), &Pubkey::new_unique());
+        sign_credential(&mut credential, &TestKeypair::from_seed(5));
+        credential
+    }
+
+    #[test]
+    fn test_full_documents_match() {
+        let credential = credential();
+        let full = crate::credential::credential_account_to_json(&credential);
+        assert_eq!(compare(&credential, &full.to_string()).unwrap(), DocumentComparison { exact: false, mismatched: vec![] });
+        assert!(compare(&credential, &serde_json::to_string_pretty(&full).unwrap()).unwrap().matches());
+        assert!(compare(&credential, "{not json").is_err());
+    }
+
+    #[test]
+    fn test_signed_documents_do_not_vouch_for_unsigned_members() {
+        let mut credential = credential();
+        credential.credential_subject.narrative = Some("Completed every module".to_string());
+        let signed = crate::canonical::signed_document(&credential).unwrap();
+
+        let comparison = compare(&credential, &signed).unwrap();
+        assert!(comparison.exact);
+        assert!(!comparison.matches());
+        assert!(comparison.mismatched.contains(&"credentialSubject.narrative".to_string()));
+    }
+
+    #[test]
+    fn test_tampered_members_are_reported() {
+        let credential = credential();
+        let mut document = crate::credential::credential_account_to_json(&credential);
+        document["credentialSubject"]["id"] = serde_json::json!("did:sol:mallory");
+        document["validUntil"] = serde_json::json!("2099-01-01T00:00:00Z");
+
+        let comparison = compare(&credential, &document.to_string()).unwrap();
+        assert!(!comparison.matches());
+        assert_eq!(comparison.mismatched, vec!["credentialSubject.id".to_string(), "validUntil".to_string()]);
+    }
+}
@@ -0,0 +1,310 @@
+//! Encrypted Credential Payloads
+//!
+//! Some programs must keep grades and evidence confidential while the
+//! credential itself stays publicly verifiable. For those, the credential
+//! is issued without results or a narrative and the issuer then attaches
+//! the confidential body encrypted to the recipient: only the envelope,
+//! its SHA-256 and one salted digest per field are on-chain, next to the
+//! public issuer, status and proof.
+//!
+//! The envelope is covered by the issuer's signature: the signing JSON of a
+//! credential with a payload carries its `commitment` as `encryptedPayload`,
+//! so attaching one re-signs the credential. The authority signs the new
+//! signing JSON off-chain and the previous proof is deprecated, as in a
+//! proof upgrade. A co-signed credential cannot take a payload, since its
+//! co-signatures cover the signing JSON without one.
+//!
+//! Encryption happens off-chain, in the issuer's tooling:
+//!
+//! 1. The body is a JSON array of disclosures, `{"path", "salt", "value"}`
+//!    objects in the form `redaction::verify_disclosure` checks, with
+//!    `path` a JSON Pointer into the credential document.
+//! 2. An ephemeral X25519 key is agreed with the recipient's X25519 key;
+//!    HKDF-SHA256 over the shared secret, with the ephemeral key as salt
+//!    and `KDF_INFO` as info, gives the content key.
+//! 3. The body is sealed with XChaCha20-Poly1305 under a random 24-byte
+//!    nonce, with the credential id as associated data, so the ciphertext
+//!    cannot be moved to another credential.
+//!
+//! The holder decrypts with their X25519 secret and either reveals the
+//! whole body, which must hash to `payload_sha256`, or a subset of the
+//! disclosures, each of which must match its field digest.
+//! `verify_revealed_payload` checks either form against the account.
+//! open_badges_verify derives the holder's content key (`payload_key`) and
+//! associated data (`payload_associated_data`); it does no encryption
+//! itself, so the ciphertext is opened with any XChaCha20-Poly1305
+//! implementation.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use crate::common::errors::ValidationError;
+use crate::AchievementCredential;
+
+/// Key agreement, key derivation and content encryption of every payload
+pub const ALGORITHM: &str = "X25519-HKDF-SHA256+XChaCha20-Poly1305";
+
+/// HKDF info of the content key
+pub const KDF_INFO: &[u8] = b"open-badges/encrypted-payload";
+
+/// Length of the Poly1305 authentication tag ending every ciphertext
+pub const TAG_LEN: usize = 16;
+
+/// Maximum ciphertext length, tag included
+pub const MAX_CIPHERTEXT_LEN: usize = 2048;
+
+/// Maximum committed fields of one payload
+pub const MAX_FIELDS: usize = 16;
+
+/// Maximum length of a committed field's JSON Pointer
+pub const MAX_FIELD_PATH_LEN: usize = 64;
+
+/// Length of a field digest, base64url SHA-256 without padding
+pub const FIELD_DIGEST_LEN: usize = 43;
+
+/// Serialized size of no payload, reserved at issuance
+pub const EMPTY_SPACE: usize = 1;
+
+/// Digest of one field of the encrypted body
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct FieldCommitment {
+    /// JSON Pointer of the field in the credential document
+    pub path: String,
+    /// `redaction::field_digest` of the field's salt, path and value
+    pub digest: String,
+}
+
+/// Credential body encrypted to its recipient
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct EncryptedPayload {
+    /// Recipient's X25519 public key the body is encrypted to
+    pub recipient_key: [u8; 32],
+    /// Issuer's ephemeral X25519 public key
+    pub ephemeral_key: [u8; 32],
+    /// XChaCha20-Poly1305 nonce
+    pub nonce: [u8; 24],
+    /// Sealed body, authentication tag included
+    pub ciphertext: Vec<u8>,
+    /// SHA-256 of the plaintext body
+    pub payload_sha256: [u8; 32],
+    /// One digest per disclosure in the body, for selective reveal
+    pub fields: Vec<FieldCommitment>,
+}
+
+/// SHA-256 of the envelope's Borsh serialization, signed as the credential's `encryptedPayload`
+pub fn commitment(payload: &EncryptedPayload) -> [u8; 32] {
+    // Serializing into a Vec cannot fail
+    hash(&payload.try_to_vec().unwrap_or_default()).to_bytes()
+}
+
+/// `encryptedPayload` member of the credential document and its signing JSON
+pub fn commitment_json(commitment: &[u8; 32]) -> serde_json::Value {
+    serde_json::json!({
+        "type": "EncryptedPayload",
+        "algorithm": ALGORITHM,
+        "digestMultibase": crate::multibase::encode(commitment),
+    })
+}
+
+impl EncryptedPayload {
+    /// Serialized size of the payload, option tag included
+    pub fn space(&self) -> usize {
+        1 + 32 + 32 + 24
+            + 4 + self.ciphertext.len()
+            + 32
+            + 4 + self.fields.iter().map(|field| 4 + field.path.len() + 4 + field.digest.len()).sum::<usize>()
+    }
+}
+
+/// Check an envelope: a sealed body within bounds and distinct, well-formed field digests
+pub fn validate(payload: &EncryptedPayload) -> Result<()> {
+    if payload.ciphertext.len() <= TAG_LEN || payload.fields.is_empty() {
+        msg!("❌ Encrypted payload needs a sealed body and at least one field digest");
+        return Err(error!(ValidationError::InvalidEncryptedPayload));
+    }
+    if payload.ciphertext.len() > MAX_CIPHERTEXT_LEN || payload.fields.len() > MAX_FIELDS {
+        return Err(error!(ValidationError::TooManyItems));
+    }
+    for (index, field) in payload.fields.iter().enumerate() {
+        let repeated = payload.fields[..index].iter().any(|earlier| earlier.path == field.path);
+        if repeated
+            || !field.path.starts_with('/')
+            || field.path.len() > MAX_FIELD_PATH_LEN
+            || field.digest.len() != FIELD_DIGEST_LEN
+        {
+            msg!("❌ Invalid field digest: {:?}", field);
+            return Err(error!(ValidationError::InvalidEncryptedPayload));
+        }
+    }
+    Ok(())
+}
+
+/// Check that `credential` can take a payload: unrevoked, not co-signed, without one and without a public body
+pub fn ensure_attachable(credential: &AchievementCredential) -> Result<()> {
+    if credential.is_revoked {
+        return Err(error!(ValidationError::AlreadyRevoked));
+    }
+    if credential.proof.len() > 1 {
+        msg!("❌ Credential {} is co-signed without a payload", credential.id);
+        return Err(error!(ValidationError::InvalidEncryptedPayload));
+    }
+    let subject = &credential.credential_subject;
+    if credential.encrypted_payload.is_some() || !subject.result.is_empty() || subject.narrative.is_some() {
+        msg!("❌ Credential {} already has a payload or a public body", credential.id);
+        return Err(error!(ValidationError::InvalidEncryptedPayload));
+    }
+    Ok(())
+}
+
+/// Account size that fits `credential` with `payload` attached and its proof replaced
+/// Never shrinks: accounts are allocated with slack at issuance
+pub fn credential_space(current_len: usize, credential: &AchievementCredential, payload: &EncryptedPayload) -> usize {
+    let re_signed = crate::proof_upgrade::upgraded_space(0, credential);
+    current_len.max(re_signed - EMPTY_SPACE + payload.space())
+}
+
+/// A disclosure of one field of the body
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Disclosure {
+    /// JSON Pointer of the field
+    pub path: String,
+    /// Salt the field digest was computed with
+    pub salt: String,
+    /// Value of the field
+    pub value: serde_json::Value,
+}
+
+/// Outcome of checking revealed disclosures against a payload
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Reveal {
+    /// Whether the revealed text is the whole body, hashing to `payload_sha256`
+    pub complete: bool,
+    /// Paths whose disclosure matched its field digest, in the order revealed
+    pub verified: Vec<String>,
+}
+
+/// Check revealed disclosures: the whole decrypted body, or a JSON array of some of its disclosures
+/// `None` when a disclosure does not match its field digest; fails when the
+/// text is not a disclosure array
+pub fn verify_reveal(payload: &EncryptedPayload, revealed: &str) -> Result<Option<Reveal>> {
+    let disclosures: Vec<Disclosure> = serde_json::from_str(revealed)
+        .map_err(|_| error!(ValidationError::InvalidJson))?;
+    let mut verified = Vec::with_capacity(disclosures.len());
+    for disclosure in &disclosures {
+        let matches = match payload.fields.iter().find(|field| field.path == disclosure.path) {
+            Some(field) => field.digest == crate::redaction::field_digest(&disclosure.salt, &disclosure.path, &disclosure.value)?,
+            None => false,
+        };
+        if !matches {
+            msg!("❌ Disclosure of {} does not match the payload", disclosure.path);
+            return Ok(None);
+        }
+        verified.push(disclosure.path.clone());
+    }
+    let complete = hash(revealed.as_bytes()).to_bytes() == payload.payload_sha256;
+    Ok(Some(Reveal { complete, verified }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::redaction::{field_digest, field_salt};
+    use crate::test_utils::{fixture_credential, sign_credential, TestKeypair};
+
+    fn body() -> (String, Vec<FieldCommitment>) {
+        let seed = [3u8; 32];
+        let disclosures: Vec<Disclosure> = [
+            ("/credentialSubject/result", serde_json::json!([{ "type": ["Result"], "value": "A" }])),
+            ("/credentialSubject/narrative", serde_json::json!("Thesis defended with distinction")),
+        ]
+        .into_iter()
+        .map(|(path, value)| Disclosure { path: path.to_string(), salt: field_salt(&seed, path), value })
+        .collect();
+        let fields = disclosures
+            .iter()
+            .map(|disclosure| FieldCommitment {
+                path: disclosure.path.clone(),
+                digest: field_digest(&disclosure.salt, &disclosure.path, &disclosure.value).unwrap(),
+            })
+            .collect();
+        (serde_json::to_string(&disclosures).unwrap(), fields)
+    }
+
+    fn payload(body: &str, fields: Vec<FieldCommitment>) -> EncryptedPayload {
+        EncryptedPayload {
+            recipient_key: [1; 32],
+            ephemeral_key: [2; 32],
+            nonce: [3; 24],
+            ciphertext: vec![4; body.len() + TAG_LEN],
+            payload_sha256: hash(body.as_bytes()).to_bytes(),
+            fields,
+        }
+    }
+
+    #[test]
+    fn test_payload_is_validated_and_fits() {
+        let (body, fields) = body();
+        let payload = payload(&body, fields);
+        assert!(validate(&payload).is_ok());
+
+        let mut credential = fixture_credential(&Pubkey::new_unique(), &Pubkey::new_unique(), &Pubkey::new_unique(), &Pubkey::new_unique());
+        sign_credential(&mut credential, &TestKeypair::from_seed(6));
+        assert!(ensure_attachable(&credential).is_ok());
+        let current_len = crate::test_utils::account_data(&credential).len();
+        let space = credential_space(current_len, &credential, &payload);
+        credential.encrypted_payload = Some(payload.clone());
+        let mut proof = credential.proof[0].clone();
+        proof.proof_value = format!("z{}", "1".repeat(88));
+        crate::proof_upgrade::replace_proof(&mut credential, proof, "2025-01-01T00:00:00Z".to_string()).unwrap();
+        assert!(crate::test_utils::account_data(&credential).len() <= space);
+        assert!(ensure_attachable(&credential).is_err());
+
+        let unsealed = EncryptedPayload { ciphertext: vec![0; TAG_LEN], ..payload.clone() };
+        assert!(validate(&unsealed).is_err());
+        let mut repeated = payload.clone();
+        repeated.fields.push(repeated.fields[0].clone());
+        assert!(validate(&repeated).is_err());
+    }
+
+    #[test]
+    fn test_envelope_is_covered_by_the_signing_json() {
+        let (body, fields) = body();
+        let payload = payload(&body, fields);
+        let mut credential = fixture_credential(&Pubkey::new_unique(), &Pubkey::new_unique(), &Pubkey::new_unique(), &Pubkey::new_unique());
+        let unsigned = crate::credential_signing_json(&credential);
+        credential.encrypted_payload = Some(payload.clone());
+        let signed: serde_json::Value = serde_json::from_str(&crate::credential_signing_json(&credential)).unwrap();
+        assert_eq!(signed["encryptedPayload"], commitment_json(&commitment(&payload)));
+        assert_ne!(crate::credential_signing_json(&credential), unsigned);
+
+        let mut swapped = payload.clone();
+        swapped.fields[0].digest = "A".repeat(FIELD_DIGEST_LEN);
+        assert_ne!(commitment(&swapped), commitment(&payload));
+    }
+
+    #[test]
+    fn test_co_signed_credentials_cannot_take_a_payload() {
+        let mut credential = fixture_credential(&Pubkey::new_unique(), &Pubkey::new_unique(), &Pubkey::new_unique(), &Pubkey::new_unique());
+        sign_credential(&mut credential, &TestKeypair::from_seed(6));
+        credential.proof.push(credential.proof[0].clone());
+        assert!(ensure_attachable(&credential).is_err());
+    }
+
+    #[test]
+    fn test_whole_and_selective_reveals() {
+        let (body, fields) = body();
+        let payload = payload(&body, fields);
+        let whole = verify_reveal(&payload, &body).unwrap().unwrap();
+        assert!(whole.complete);
+        assert_eq!(whole.verified.len(), 2);
+
+        let mut disclosures: Vec<Disclosure> = serde_json::from_str(&body).unwrap();
+        disclosures.truncate(1);
+        let partial = verify_reveal(&payload, &serde_json::to_string(&disclosures).unwrap()).unwrap().unwrap();
+        assert!(!partial.complete);
+        assert_eq!(partial.verified, vec!["/credentialSubject/result".to_string()]);
+
+        disclosures[0].value = serde_json::json!([{ "type": ["Result"], "value": "A+" }]);
+        assert_eq!(verify_reveal(&payload, &serde_json::to_string(&disclosures).unwrap()).unwrap(), None);
+        assert!(verify_reveal(&payload, "{}").is_err());
+    }
+}
@@ -0,0 +1,521 @@
+//! Program events for verifiers and audit tooling
+//!
+//! Events are emitted through Anchor's `emit!` so indexers can follow
+//! credential lifecycle changes without polling accounts.
+
+use anchor_lang::prelude::*;
+
+/// Emitted when a credential account is revoked
+#[event]
+pub struct CredentialRevoked {
+    /// Revoked credential account
+    pub credential: Pubkey,
+
+    /// Issuer profile of the credential
+    pub issuer: Pubkey,
+
+    /// Signer who performed the revocation
+    pub revoked_by: Pubkey,
+
+    /// Reason given for the revocation, if any
+    pub reason: Option<String>,
+
+    /// Revocation timestamp (ISO 8601 string)
+    pub revoked_at: String,
+
+    /// Issuer sequence number of this revocation
+    pub sequence: u64,
+}
+
+/// Emitted when a credential past its validUntil date is marked expired
+#[event]
+pub struct CredentialExpired {
+    /// Expired credential account
+    pub credential: Pubkey,
+
+    /// Issuer profile of the credential
+    pub issuer: Pubkey,
+
+    /// Achievement the credential awards
+    pub achievement: Pubkey,
+
+    /// The credential's validUntil date (ISO 8601 string)
+    pub valid_until: String,
+
+    /// When the credential was marked expired (ISO 8601 string)
+    pub expired_at: String,
+}
+
+/// Emitted when an issuer suspends a credential
+#[event]
+pub struct CredentialSuspended {
+    /// Suspended credential account
+    pub credential: Pubkey,
+
+    /// Issuer profile of the credential
+    pub issuer: Pubkey,
+
+    /// Signer who suspended the credential
+    pub suspended_by: Pubkey,
+
+    /// Reason given for the suspension, if any
+    pub reason: Option<String>,
+
+    /// Suspension timestamp (ISO 8601 string)
+    pub suspended_at: String,
+
+    /// Issuer sequence number of this suspension
+    pub sequence: u64,
+}
+
+/// Emitted when an issuer reinstates a suspended credential
+#[event]
+pub struct CredentialReinstated {
+    /// Reinstated credential account
+    pub credential: Pubkey,
+
+    /// Issuer profile of the credential
+    pub issuer: Pubkey,
+
+    /// Signer who reinstated the credential
+    pub reinstated_by: Pubkey,
+
+    /// Reason given for the reinstatement, if any
+    pub reason: Option<String>,
+
+    /// Reinstatement timestamp (ISO 8601 string)
+    pub reinstated_at: String,
+
+    /// Issuer sequence number of this reinstatement
+    pub sequence: u64,
+}
+
+/// Emitted when an issuer moves a credential to a new subject DID
+#[event]
+pub struct CredentialRebound {
+    /// Re-bound credential account
+    pub credential: Pubkey,
+
+    /// Issuer profile of the credential
+    pub issuer: Pubkey,
+
+    /// Subject id before the re-binding
+    pub previous_subject: String,
+
+    /// Subject id after the re-binding
+    pub new_subject: String,
+
+    /// Issuer authority that approved the re-binding
+    pub authorized_by: Pubkey,
+
+    /// Re-binding timestamp (ISO 8601 string)
+    pub rebound_at: String,
+
+    /// Issuer sequence number of this re-binding
+    pub sequence: u64,
+}
+
+/// Emitted when an issuer signs and stores a credential
+#[event]
+pub struct CredentialIssued {
+    /// Issued credential account
+    pub credential: Pubkey,
+
+    /// Issuer profile of the credential
+    pub issuer: Pubkey,
+
+    /// Recipient of the credential
+    pub recipient: Pubkey,
+
+    /// Issuer sequence number, also stored on the credential
+    pub sequence: u64,
+}
+
+/// Emitted when an issuer escrows a credential behind a claim code
+#[event]
+pub struct ClaimOfferCreated {
+    /// Claim offer account
+    pub offer: Pubkey,
+
+    /// Issuer profile making the offer
+    pub issuer: Pubkey,
+
+    /// Achievement the credential awards
+    pub achievement: Pubkey,
+
+    /// Unix time after which the offer can no longer be claimed
+    pub expires_at: i64,
+}
+
+/// Emitted when an issuer's issuance rate limit is set or removed
+#[event]
+pub struct IssuanceRateLimitUpdated {
+    /// Issuer profile
+    pub issuer: Pubkey,
+
+    /// New limit, `None` when removed
+    pub limit: Option<crate::rate_limit::RateLimit>,
+}
+
+/// Emitted when an issuer is frozen or unfrozen
+#[event]
+pub struct IssuerFreezeUpdated {
+    /// Issuer profile
+    pub issuer: Pubkey,
+
+    /// Whether issuance is now halted
+    pub frozen: bool,
+
+    /// Authority or admin who made the change
+    pub updated_by: Pubkey,
+}
+
+/// Emitted with a signed credential at issuance, so wallets can archive a verifiable copy
+#[event]
+pub struct CredentialDocument {
+    /// Issued credential account
+    pub credential: Pubkey,
+
+    /// Signing JSON with the issuer's proof embedded (see `canonical::signed_document`)
+    pub document: String,
+}
+
+/// Emitted when an issuer reissues an expired credential
+#[event]
+pub struct CredentialReissued {
+    /// New credential account
+    pub credential: Pubkey,
+
+    /// Expired credential it replaces
+    pub previous: Pubkey,
+
+    /// Issuer profile of both credentials
+    pub issuer: Pubkey,
+
+    /// End of the new validity period, if any
+    pub valid_until: Option<String>,
+
+    /// Issuer sequence number, also stored on the new credential
+    pub sequence: u64,
+}
+
+/// Emitted when an issuer supersedes a credential with a corrected replacement
+#[event]
+pub struct CredentialSuperseded {
+    /// Replacement credential account
+    pub credential: Pubkey,
+
+    /// Credential it supersedes
+    pub previous: Pubkey,
+
+    /// Issuer profile of both credentials
+    pub issuer: Pubkey,
+
+    /// Achievement the replacement awards
+    pub achievement: Pubkey,
+
+    /// Issuer sequence number, also stored on the replacement
+    pub sequence: u64,
+}
+
+/// Emitted when an issuer registers the hash of a credential's baked image
+#[event]
+pub struct BakedBadgeRegistered {
+    /// Credential the image belongs to
+    pub credential: Pubkey,
+
+    /// Issuer profile of the credential
+    pub issuer: Pubkey,
+
+    /// SHA-256 of the baked image
+    pub image_sha256: [u8; 32],
+
+    /// Format of the baked image
+    pub format: crate::baked_image::BakedImageFormat,
+
+    /// Registration timestamp (ISO 8601 string)
+    pub registered_at: String,
+
+    /// Issuer sequence number of this registration
+    pub sequence: u64,
+}
+
+/// Emitted when an issuer attaches an encrypted body to a credential
+#[event]
+pub struct EncryptedPayloadAttached {
+    /// Credential the body belongs to
+    pub credential: Pubkey,
+
+    /// Issuer profile of the credential
+    pub issuer: Pubkey,
+
+    /// SHA-256 of the plaintext body
+    pub payload_sha256: [u8; 32],
+
+    /// JSON Pointers of the committed fields
+    pub fields: Vec<String>,
+
+    /// Attachment timestamp (ISO 8601 string)
+    pub attached_at: String,
+
+    /// Issuer sequence number of this attachment
+    pub sequence: u64,
+}
+
+/// Emitted when an issuer sets a credential's extensions
+#[event]
+pub struct CredentialExtensionsUpdated {
+    /// Credential whose extensions were set
+    pub credential: Pubkey,
+
+    /// Issuer profile of the credential
+    pub issuer: Pubkey,
+
+    /// Extension terms now on the credential
+    pub terms: Vec<String>,
+
+    /// Update timestamp (ISO 8601 string)
+    pub updated_at: String,
+
+    /// Issuer sequence number of this update
+    pub sequence: u64,
+}
+
+/// Emitted when an issuer replaces a credential's proof
+#[event]
+pub struct CredentialProofUpgraded {
+    /// Credential whose proof was replaced
+    pub credential: Pubkey,
+
+    /// Issuer profile of the credential
+    pub issuer: Pubkey,
+
+    /// Number of deprecated proofs now kept on the credential
+    pub deprecated_proofs: u8,
+
+    /// Upgrade timestamp (ISO 8601 string)
+    pub upgraded_at: String,
+
+    /// Issuer sequence number of this upgrade
+    pub sequence: u64,
+}
+
+/// Emitted when a co-signer adds a proof to a credential
+#[event]
+pub struct CredentialProofAdded {
+    /// Credential that was co-signed
+    pub credential: Pubkey,
+
+    /// Issuer profile of the credential
+    pub issuer: Pubkey,
+
+    /// Verification method of the added proof
+    pub verification_method: String,
+
+    /// Whether the proof counter-signs the previous proof
+    pub chained: bool,
+
+    /// Number of proofs now on the credential
+    pub proof_count: u8,
+}
+
+/// Emitted by `verify_credentials_batch` with one result bit per credential
+#[event]
+pub struct CredentialsBatchVerified {
+    /// Number of credentials checked
+    pub count: u8,
+
+    /// Bit i is set when the i-th credential is valid
+    pub valid_mask: u64,
+}
+
+/// Emitted by `validate_credential_full`
+#[event]
+pub struct ComplianceReport {
+    /// SHA-256 of the validated JSON, to match reports to inputs
+    pub credential_hash: [u8; 32],
+
+    /// Validation mode the validator ran in
+    pub mode: crate::strictness::ValidationMode,
+
+    /// Compliance score out of 100
+    pub score: u8,
+
+    /// Checks that failed
+    pub error_count: u16,
+
+    /// Checks that passed with a warning
+    pub warning_count: u16,
+
+    /// Whether no check failed
+    pub is_valid: bool,
+}
+
+/// Emitted by `verify_presentation`
+#[event]
+pub struct PresentationVerified {
+    /// Holder who signed the presentation
+    pub holder: Pubkey,
+
+    /// Number of credentials in the presentation
+    pub credential_count: u8,
+
+    /// Whether the signature, challenge, domain and every credential checked out
+    pub valid: bool,
+}
+
+/// Emitted when a signed status list credential is published
+#[event]
+pub struct StatusListPublished {
+    /// Revocation list the credential encodes
+    pub revocation_list: Pubkey,
+
+    /// Account storing the signed credential
+    pub status_list_credential: Pubkey,
+
+    /// Signed StatusList2021 credential JSON
+    pub credential_json: String,
+
+    /// Publication timestamp (ISO 8601 string)
+    pub published_at: String,
+}
+
+/// Emitted when a credential is issued through peer endorsements
+#[event]
+pub struct PeerCredentialIssued {
+    /// Issued credential account
+    pub credential: Pubkey,
+
+    /// Achievement the credential awards
+    pub achievement: Pubkey,
+
+    /// Recipient of the credential
+    pub recipient: Pubkey,
+
+    /// Distinct endorsements counted at issuance
+    pub endorsement_count: u16,
+
+    /// Issuer sequence number, also stored on the credential
+    pub sequence: u64,
+}
+
+/// Emitted when an issuer anchors the hash of an externally issued credential
+#[event]
+pub struct ExternalCredentialAnchored {
+    /// Anchor record account
+    pub anchor_record: Pubkey,
+
+    /// Issuer profile that anchored the credential
+    pub issuer: Pubkey,
+
+    /// SHA-256 of the credential JSON
+    pub credential_hash: [u8; 32],
+
+    /// Issuer sequence number of this anchoring
+    pub sequence: u64,
+}
+
+/// Emitted when an issuer anchors a merkle root over external credentials
+#[event]
+pub struct CredentialBatchAnchored {
+    /// Batch anchor account
+    pub batch_anchor: Pubkey,
+
+    /// Issuer profile that anchored the batch
+    pub issuer: Pubkey,
+
+    /// Root of the merkle tree over the batch's credential hashes
+    pub merkle_root: [u8; 32],
+
+    /// Number of credentials in the batch
+    pub leaf_count: u32,
+
+    /// Issuer sequence number of this anchoring
+    pub sequence: u64,
+}
+
+/// Emitted when the program config is created or changed
+#[event]
+pub struct ProgramConfigUpdated {
+    /// Signer allowed to change the settings
+    pub admin: Pubkey,
+
+    /// Whether issuance and revocation are halted
+    pub paused: bool,
+
+    /// Lamports charged per issued credential
+    pub issuance_fee_lamports: u64,
+
+    /// SPL token fee charged per issued credential
+    pub token_fee: Option<crate::program_config::TokenFee>,
+}
+
+/// Emitted when the admin withdraws collected fees
+#[event]
+pub struct TreasuryWithdrawn {
+    /// Account the fees were sent to
+    pub destination: Pubkey,
+
+    /// Mint of the withdrawn tokens, `None` for lamports
+    pub mint: Option<Pubkey>,
+
+    /// Amount withdrawn in lamports or token base units
+    pub amount: u64,
+}
+
+/// Emitted when a credential is issued to a team
+#[event]
+pub struct TeamCredentialIssued {
+    /// Team credential account
+    pub credential: Pubkey,
+
+    /// Issuer profile
+    pub issuer: Pubkey,
+
+    /// Achievement awarded
+    pub achievement: Pubkey,
+
+    /// Issuer-chosen team id
+    pub team_id: String,
+
+    /// Member wallets
+    pub members: Vec<Pubkey>,
+}
+
+/// Emitted when an accreditation body endorses an issuer
+#[event]
+pub struct IssuerAccredited {
+    /// Accreditation endorsement account
+    pub accreditation: Pubkey,
+
+    /// Accrediting profile
+    pub accreditor: Pubkey,
+
+    /// Accredited issuer profile
+    pub issuer: Pubkey,
+
+    /// What the issuer is accredited for
+    pub scope: String,
+
+    /// Unix time the accreditation lapses, `None` for open-ended
+    pub valid_until: Option<i64>,
+}
+
+/// Emitted when an issuer grants a service a scoped authorization
+#[event]
+pub struct ScopedAuthorizationGranted {
+    /// Scoped authorization account
+    pub authorization: Pubkey,
+
+    /// Issuer profile that made the grant
+    pub issuer: Pubkey,
+
+    /// Service key the grant is for
+    pub service: Pubkey,
+
+    /// Granted scopes
+    pub scopes: Vec<crate::scoped_authorization::Scope>,
+
+    /// Unix time the grant expires, `None` for no expiry
+    pub expires_at: Option<i64>,
+}
@@ -0,0 +1,74 @@
+//! Credential Expiration Sweep
+//!
+//! A credential past its `validUntil` date stops verifying, but its account
+//! and the achievement counters still describe it as active until someone
+//! looks at the clock. `expire_credential` is a permissionless crank: any
+//! signer can mark such a credential expired, which moves it from the
+//! achievement's active count to its expired count and emits
+//! `CredentialExpired` for indexers.
+
+use anchor_lang::prelude::*;
+use crate::common::errors::ValidationError;
+use crate::AchievementCredential;
+
+/// Whether `credential` is past its `validUntil` date at `now`
+pub fn has_expired(credential: &AchievementCredential, now: i64) -> Result<bool> {
+    match &credential.valid_until {
+        Some(valid_until) => Ok(now > crate::parse_iso8601_to_unix(valid_until)?),
+        None => Ok(false),
+    }
+}
+
+/// Check that `credential` may be marked expired at `now`
+pub fn ensure_expirable(credential: &AchievementCredential, now: i64) -> Result<()> {
+    if credential.is_expired {
+        return Err(error!(ValidationError::AlreadyExpired));
+    }
+    if credential.is_revoked {
+        msg!("❌ Revoked credentials are not expired: {}", credential.id);
+        return Err(error!(ValidationError::ValidationFailed));
+    }
+    if !has_expired(credential, now)? {
+        msg!("❌ Credential has not reached its validUntil date: {}", credential.id);
+        return Err(error!(ValidationError::CredentialNotExpired));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::fixture_credential;
+
+    const NOW: i64 = 1_735_689_600; // 2025-01-01T00:00:00Z
+
+    fn credential(valid_until: Option<&str>) -> AchievementCredential {
+        let mut credential = fixture_credential(
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+        );
+        credential.valid_until = valid_until.map(str::to_string);
+        credential
+    }
+
+    #[test]
+    fn test_only_lapsed_credentials_expire() {
+        assert!(ensure_expirable(&credential(Some("2024-06-01T00:00:00Z")), NOW).is_ok());
+        assert!(ensure_expirable(&credential(Some("2025-06-01T00:00:00Z")), NOW).is_err());
+        assert!(ensure_expirable(&credential(Some("2025-01-01T00:00:00Z")), NOW).is_err());
+        assert!(ensure_expirable(&credential(None), NOW).is_err());
+    }
+
+    #[test]
+    fn test_expired_or_revoked_credentials_are_skipped() {
+        let mut expired = credential(Some("2024-06-01T00:00:00Z"));
+        expired.is_expired = true;
+        assert!(ensure_expirable(&expired, NOW).is_err());
+
+        let mut revoked = credential(Some("2024-06-01T00:00:00Z"));
+        revoked.is_revoked = true;
+        assert!(ensure_expirable(&revoked, NOW).is_err());
+    }
+}
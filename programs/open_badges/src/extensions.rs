@@ -0,0 +1,272 @@
+//! Open Badges Extensions
+//!
+//! Issuers attach domain-specific metadata to achievements and credentials
+//! without forking the program. Each extension names the JSON-LD context
+//! that defines its term and carries either a small inline JSON value or the
+//! SHA-256 of a larger JSON document kept off-chain. Extension contexts are
+//! added to the document's `@context` so JSON-LD processors can expand the
+//! extension terms. `@context` is part of the signed JSON, so setting a
+//! credential's extensions replaces its proof with one over the new context.
+
+use anchor_lang::prelude::*;
+use base64::{Engine, engine::general_purpose};
+use crate::common::errors::ValidationError;
+use crate::AchievementCredential;
+
+/// Maximum extensions on one achievement or credential
+pub const MAX_EXTENSIONS: usize = 4;
+
+/// Maximum length of an extension context IRI
+pub const MAX_EXTENSION_CONTEXT_LEN: usize = 128;
+
+/// Maximum length of an extension term
+pub const MAX_EXTENSION_TERM_LEN: usize = 64;
+
+/// Maximum length of an inline extension value (JSON text)
+pub const MAX_INLINE_VALUE_LEN: usize = 256;
+
+/// Properties an extension may not overwrite
+const RESERVED_TERMS: &[&str] = &[
+    "id", "type", "issuer", "name", "description", "criteria", "image", "creator",
+    "credentialSubject", "validFrom", "validUntil", "proof", "credentialStatus",
+    "resultDescription", "achievement", "result", "identifier", "alignment",
+];
+
+/// One issuer-defined extension property
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct Extension {
+    /// IRI of the JSON-LD context defining `term`
+    pub context: String,
+    /// Property name the extension adds to the node
+    pub term: String,
+    /// Small JSON value stored on-chain
+    pub inline_value: Option<String>,
+    /// SHA-256 of a JSON value kept off-chain
+    pub value_hash: Option<[u8; 32]>,
+}
+
+impl Extension {
+    /// Serialized size of this extension
+    pub fn space(&self) -> usize {
+        4 + self.context.len()
+            + 4 + self.term.len()
+            + 1 + self.inline_value.as_ref().map_or(0, |value| 4 + value.len())
+            + 1 + self.value_hash.map_or(0, |_| 32)
+    }
+
+    /// Check the context IRI, term and value
+    pub fn validate(&self) -> Result<()> {
+        if self.context.len() > MAX_EXTENSION_CONTEXT_LEN || self.term.len() > MAX_EXTENSION_TERM_LEN {
+            return Err(error!(ValidationError::FieldTooLong));
+        }
+        if self.context.strip_prefix("https://").unwrap_or_default().is_empty() {
+            msg!("❌ Extension context must be an https IRI: {}", self.context);
+            return Err(error!(ValidationError::InvalidUrl));
+        }
+        let is_term = self.term.chars().next().is_some_and(|c| c.is_ascii_alphabetic())
+            && self.term.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !is_term || RESERVED_TERMS.contains(&self.term.as_str()) {
+            msg!("❌ Extension term is not allowed: {}", self.term);
+            return Err(error!(ValidationError::ValidationFailed));
+        }
+        match (&self.inline_value, &self.value_hash) {
+            (Some(value), None) => {
+                if value.len() > MAX_INLINE_VALUE_LEN {
+                    return Err(error!(ValidationError::FieldTooLong));
+                }
+                serde_json::from_str::<serde_json::Value>(value)
+                    .map_err(|_| error!(ValidationError::InvalidJson))?;
+                Ok(())
+            }
+            (None, Some(_)) => Ok(()),
+            _ => Err(error!(ValidationError::ValidationFailed)),
+        }
+    }
+
+    /// JSON-LD value of the extension property
+    pub fn to_json(&self) -> serde_json::Value {
+        match (&self.inline_value, &self.value_hash) {
+            (Some(value), _) => serde_json::from_str(value).unwrap_or(serde_json::Value::Null),
+            (None, Some(hash)) => serde_json::json!({
+                "digestSRI": format!("sha256-{}", general_purpose::STANDARD.encode(hash))
+            }),
+            (None, None) => serde_json::Value::Null,
+        }
+    }
+}
+
+/// Check a set of extensions; terms must be unique
+pub fn validate_extensions(extensions: &[Extension]) -> Result<()> {
+    if extensions.len() > MAX_EXTENSIONS {
+        return Err(error!(ValidationError::TooManyItems));
+    }
+    for (i, extension) in extensions.iter().enumerate() {
+        extension.validate()?;
+        if extensions[..i].iter().any(|other| other.term == extension.term) {
+            return Err(error!(ValidationError::ValidationFailed));
+        }
+    }
+    Ok(())
+}
+
+/// Check that every extension's context is declared in `context`
+pub fn ensure_contexts_present(extensions: &[Extension], context: &[String]) -> Result<()> {
+    if let Some(missing) = extensions.iter().find(|extension| !context.contains(&extension.context)) {
+        msg!("❌ Extension context missing from @context: {}", missing.context);
+        return Err(error!(ValidationError::ValidationFailed));
+    }
+    Ok(())
+}
+
+/// Context IRIs of `extensions` not yet in `context`
+pub fn missing_contexts(extensions: &[Extension], context: &[String]) -> Vec<String> {
+    let mut missing: Vec<String> = Vec::new();
+    for extension in extensions {
+        if !context.contains(&extension.context) && !missing.contains(&extension.context) {
+            missing.push(extension.context.clone());
+        }
+    }
+    missing
+}
+
+/// Account space for `extensions` plus the context entries they add
+pub fn space(extensions: &[Extension], context: &[String]) -> usize {
+    4 + extensions.iter().map(Extension::space).sum::<usize>()
+        + missing_contexts(extensions, context).iter().map(|iri| 4 + iri.len()).sum::<usize>()
+}
+
+/// Account size that fits `credential` once `extensions` replace its current ones
+/// and its proof is replaced. Never shrinks: the replaced extensions are not subtracted
+pub fn credential_space(current_len: usize, credential: &AchievementCredential, extensions: &[Extension]) -> usize {
+    let re_signed = crate::proof_upgrade::upgraded_space(0, credential);
+    current_len.max(re_signed + space(extensions, &credential.context))
+}
+
+/// Replace the extensions of `credential`, adding their contexts to `@context`
+/// The signing JSON changes with `@context`, so the caller re-signs the credential
+pub fn set(credential: &mut AchievementCredential, extensions: Vec<Extension>) -> Result<()> {
+    let extension_contexts = missing_contexts(&extensions, &credential.context);
+    credential.context.extend(extension_contexts);
+    credential.extensions = extensions;
+    ensure_contexts_present(&credential.extensions, &credential.context)
+}
+
+/// Add the extension properties to a JSON-LD node
+pub fn apply_to_json(node: &mut serde_json::Value, extensions: &[Extension]) {
+    for extension in extensions {
+        node[extension.term.as_str()] = extension.to_json();
+    }
+}
+
+/// Add extension contexts missing from a document's `@context` array
+pub fn extend_context_json(document: &mut serde_json::Value, extensions: &[Extension]) {
+    if let Some(context) = document.get_mut("@context").and_then(|c| c.as_array_mut()) {
+        for extension in extensions {
+            let iri = serde_json::Value::String(extension.context.clone());
+            if !context.contains(&iri) {
+                context.push(iri);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inline(term: &str, value: &str) -> Extension {
+        Extension {
+            context: "https://example.org/ext/v1/context.json".to_string(),
+            term: term.to_string(),
+            inline_value: Some(value.to_string()),
+            value_hash: None,
+        }
+    }
+
+    #[test]
+    fn test_extension_validation() {
+        assert!(validate_extensions(&[inline("creditHours", "3")]).is_ok());
+        assert!(inline("creditHours", "{not json").validate().is_err());
+        assert!(inline("type", "\"Hacked\"").validate().is_err());
+        assert!(inline("credit-hours", "3").validate().is_err());
+        assert!(validate_extensions(&[inline("creditHours", "3"), inline("creditHours", "4")]).is_err());
+
+        let mut both = inline("creditHours", "3");
+        both.value_hash = Some([1; 32]);
+        assert!(both.validate().is_err());
+
+        let mut insecure = inline("creditHours", "3");
+        insecure.context = "http://example.org/ext".to_string();
+        assert!(insecure.validate().is_err());
+    }
+
+    #[test]
+    fn test_contexts_and_json_output() {
+        let hashed = Extension { inline_value: None, value_hash: Some([0; 32]), ..inline("transcript", "") };
+        let extensions = vec![inline("creditHours", "3"), hashed];
+        let base = vec!["https://www.w3.org/ns/credentials/v2".to_string()];
+
+        assert_eq!(missing_contexts(&extensions, &base), vec![extensions[0].context.clone()]);
+        assert!(ensure_contexts_present(&extensions, &base).is_err());
+
+        let mut document = serde_json::json!({ "@context": base, "id": "urn:uuid:1" });
+        extend_context_json(&mut document, &extensions);
+        apply_to_json(&mut document, &extensions);
+        assert_eq!(document["@context"].as_array().unwrap().len(), 2);
+        assert_eq!(document["creditHours"], 3);
+        assert!(document["transcript"]["digestSRI"].as_str().unwrap().starts_with("sha256-"));
+    }
+
+    #[test]
+    fn test_space_matches_serialized_extension() {
+        let extension = inline("creditHours", "3");
+        assert_eq!(extension.try_to_vec().unwrap().len(), extension.space());
+
+        let mut credential = crate::test_utils::fixture_credential(
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+        );
+        let needed = credential_space(0, &credential, std::slice::from_ref(&extension));
+        credential.context.push(extension.context.clone());
+        credential.extensions = vec![extension];
+        assert!(crate::test_utils::account_data(&credential).len() <= needed);
+    }
+
+    #[test]
+    fn test_re_signed_proof_verifies_after_extensions_are_set() {
+        use crate::test_utils::{
+            account_data, credential_signing_json, ed25519_verify_instruction, fixture_credential, fixture_profile,
+            sign_credential, with_instructions_sysvar, TestKeypair,
+        };
+
+        let authority = TestKeypair::from_seed(6);
+        let issuer_key = Pubkey::new_unique();
+        let issuer = fixture_profile(&issuer_key, &authority.pubkey(), "Issuer");
+        let mut credential = fixture_credential(&Pubkey::new_unique(), &issuer_key, &Pubkey::new_unique(), &Pubkey::new_unique());
+        sign_credential(&mut credential, &authority);
+        let space = credential_space(account_data(&credential).len(), &credential, &[inline("creditHours", "3")]);
+        let issued_json = credential_signing_json(&credential);
+
+        set(&mut credential, vec![inline("creditHours", "3")]).unwrap();
+        let extended_json = credential_signing_json(&credential);
+        assert_ne!(extended_json, issued_json);
+
+        let check = |credential: &AchievementCredential, signed: &str| {
+            with_instructions_sysvar(&[ed25519_verify_instruction(&authority, signed.as_bytes())], |instructions| {
+                let signatures = crate::ProofSignatures { issuer: Some(&issuer), instructions: Some(instructions), staff: None };
+                crate::check_proof(&credential.proof[0], credential, &[], signatures).unwrap()
+            })
+        };
+        // The issuance proof covers the old @context and no longer verifies
+        assert!(!check(&credential, &issued_json));
+
+        let mut proof = credential.proof[0].clone();
+        proof.proof_value = crate::multibase::encode(&authority.sign(extended_json.as_bytes()));
+        crate::proof_upgrade::replace_proof(&mut credential, proof, "2025-01-01T00:00:00Z".to_string()).unwrap();
+        assert!(check(&credential, &extended_json));
+        assert_eq!(credential.deprecated_proofs.len(), 1);
+        assert!(account_data(&credential).len() <= space);
+    }
+}
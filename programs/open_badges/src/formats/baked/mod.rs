@@ -1,10 +1,10 @@
-/// Baked Badge Format Implementation
-/// 
-/// This module implements the Open Badges v3.0 specification for baked badges,
-/// supporting both PNG and SVG formats with embedded credentials.
-/// 
-/// Reference: Open Badges Specification v3.0 - Section 4.1 "Baked Badges"
-/// https://www.imsglobal.org/spec/ob/v3p0/#baked-badges
+//! Baked Badge Format Implementation
+//!
+//! This module implements the Open Badges v3.0 specification for baked badges,
+//! supporting both PNG and SVG formats with embedded credentials.
+//!
+//! Reference: Open Badges Specification v3.0 - Appendix B "Baked Badges"
+//! https://www.imsglobal.org/spec/ob/v3p0/#baked-badges
 
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::msg;
@@ -35,6 +35,8 @@ pub struct BakedBadge {
     pub verification_info: Option<String>,
     /// Badge image data (binary for PNG, text for SVG)
     pub image_data: Vec<u8>,
+    /// URI the image was taken from (the Achievement's `image`), if known
+    pub image_uri: Option<String>,
 }
 
 impl BakedBadge {
@@ -50,9 +52,16 @@ impl BakedBadge {
             credential_data,
             verification_info,
             image_data,
+            image_uri: None,
         }
     }
 
+    /// Record the image URI the badge was baked from
+    pub fn with_image_uri(mut self, image_uri: Option<String>) -> Self {
+        self.image_uri = image_uri;
+        self
+    }
+
     /// Extract credential from baked badge
     pub fn extract_credential(&self) -> Result<String> {
         match self.format {
@@ -107,8 +116,18 @@ pub fn bake_credential(
     }
 }
 
-/// Extract and validate a credential from a baked badge
-pub fn extract_and_validate_credential(image_data: &[u8], format: BakedFormat) -> Result<String> {
+/// Extract the credential from a baked badge and verify it
+///
+/// The extracted credential must parse as JSON-LD or a compact JWT. It is
+/// then checked for compliance and for a valid embedded proof, and, when
+/// `registered` is the credential account, the image is compared with the
+/// baked image registered for it.
+pub fn extract_and_validate_credential(
+    image_data: &[u8],
+    format: BakedFormat,
+    context: &crate::credential::JwtVerificationContext,
+    registered: Option<&crate::AchievementCredential>,
+) -> Result<BakedVerification> {
     let credential = match format {
         BakedFormat::Png => png_baking::extract_credential_from_png(image_data)?,
         BakedFormat::Svg => svg_baking::extract_credential_from_svg(image_data)?,
@@ -119,13 +138,15 @@ pub fn extract_and_validate_credential(image_data: &[u8], format: BakedFormat) -
         return Err(error!(crate::common::errors::ValidationError::InvalidCredentialType));
     }
 
-    // Basic JSON validation
-    if !credential.trim().starts_with('{') {
-        return Err(error!(crate::common::errors::ValidationError::InvalidJson));
-    }
-
-    msg!("✅ Successfully extracted credential from baked badge");
-    Ok(credential)
+    let verification = validation::verify_extracted_credential(image_data, credential, context, registered)?;
+    msg!(
+        "{} Verified credential from baked badge: image {:?}, compliant {}, proof {}",
+        if verification.is_valid() { "✅" } else { "❌" },
+        verification.image_intact,
+        verification.is_compliant(),
+        verification.proof_valid
+    );
+    Ok(verification)
 }
 
 #[cfg(test)]
@@ -0,0 +1,245 @@
+//! PNG baking (Open Badges 3.0 §B.1)
+//!
+//! A baked PNG carries the credential in an `iTXt` chunk with the keyword
+//! `openbadgecredential`, no compression, and empty language and translated
+//! keyword fields. The chunk is inserted before `IEND` with its CRC computed
+//! over the chunk type and data. An image holding a credential already is
+//! rejected rather than baked twice. Extraction also reads the Open Badges
+//! 2.0 `openbadges` keyword from `iTXt` (compressed or not) and `tEXt`.
+
+use anchor_lang::prelude::*;
+use std::io::Read;
+use crate::common::errors::ValidationError;
+
+/// PNG file signature
+pub const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+
+/// iTXt keyword for Open Badges 3.0 credentials
+pub const PNG_CREDENTIAL_KEYWORD: &str = "openbadgecredential";
+
+/// Keyword used by Open Badges 2.0 baked images
+pub const PNG_LEGACY_KEYWORD: &str = "openbadges";
+
+/// Largest chunk length the PNG specification allows
+const MAX_CHUNK_LEN: usize = 0x7fff_ffff;
+
+/// One chunk of a PNG datastream
+#[derive(Clone, Debug, PartialEq)]
+pub struct PngChunk<'a> {
+    /// Four-letter chunk type
+    pub chunk_type: [u8; 4],
+    /// Chunk data
+    pub data: &'a [u8],
+    /// Offset of the chunk's length field in the image
+    pub offset: usize,
+}
+
+impl PngChunk<'_> {
+    /// Keyword of a tEXt, zTXt or iTXt chunk
+    pub fn text_keyword(&self) -> Option<&str> {
+        if !matches!(&self.chunk_type, b"tEXt" | b"zTXt" | b"iTXt") {
+            return None;
+        }
+        let end = self.data.iter().position(|byte| *byte == 0)?;
+        std::str::from_utf8(&self.data[..end]).ok()
+    }
+}
+
+/// CRC-32 of a chunk's type and data
+pub fn chunk_crc(chunk_type: &[u8; 4], data: &[u8]) -> u32 {
+    let mut crc = flate2::Crc::new();
+    crc.update(chunk_type);
+    crc.update(data);
+    crc.sum()
+}
+
+/// Serialize a chunk: length, type, data and CRC
+pub fn encode_chunk(chunk_type: &[u8; 4], data: &[u8]) -> Vec<u8> {
+    let mut chunk = Vec::with_capacity(12 + data.len());
+    chunk.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    chunk.extend_from_slice(chunk_type);
+    chunk.extend_from_slice(data);
+    chunk.extend_from_slice(&chunk_crc(chunk_type, data).to_be_bytes());
+    chunk
+}
+
+/// Split a PNG into its chunks, checking the signature, every CRC and the final IEND
+pub fn parse_chunks(png: &[u8]) -> Result<Vec<PngChunk<'_>>> {
+    let invalid = || error!(ValidationError::InvalidBadgeImage);
+    if !png.starts_with(&PNG_SIGNATURE) {
+        return Err(invalid());
+    }
+
+    let mut chunks = Vec::new();
+    let mut offset = PNG_SIGNATURE.len();
+    while offset < png.len() {
+        let header = png.get(offset..offset + 8).ok_or_else(invalid)?;
+        let length = u32::from_be_bytes([header[0], header[1], header[2], header[3]]) as usize;
+        if length > MAX_CHUNK_LEN {
+            return Err(invalid());
+        }
+        let chunk_type = [header[4], header[5], header[6], header[7]];
+        let data_start = offset + 8;
+        let data = png.get(data_start..data_start + length).ok_or_else(invalid)?;
+        let crc = png.get(data_start + length..data_start + length + 4).ok_or_else(invalid)?;
+        if u32::from_be_bytes([crc[0], crc[1], crc[2], crc[3]]) != chunk_crc(&chunk_type, data) {
+            msg!("❌ PNG chunk {:?} has a bad CRC", String::from_utf8_lossy(&chunk_type));
+            return Err(invalid());
+        }
+        chunks.push(PngChunk { chunk_type, data, offset });
+        offset = data_start + length + 4;
+        if &chunk_type == b"IEND" {
+            break;
+        }
+    }
+
+    match (chunks.first(), chunks.last()) {
+        (Some(first), Some(last)) if &first.chunk_type == b"IHDR" && &last.chunk_type == b"IEND" => Ok(chunks),
+        _ => Err(invalid()),
+    }
+}
+
+/// iTXt data holding `text` uncompressed under `keyword`
+pub fn itxt_data(keyword: &str, text: &str) -> Vec<u8> {
+    let mut data = Vec::with_capacity(keyword.len() + 5 + text.len());
+    data.extend_from_slice(keyword.as_bytes());
+    // Null separator, compression flag, compression method, empty language tag and translated keyword
+    data.extend_from_slice(&[0, 0, 0, 0, 0]);
+    data.extend_from_slice(text.as_bytes());
+    data
+}
+
+/// Text of an iTXt chunk's data, inflating it when compressed
+fn itxt_text(data: &[u8]) -> Result<String> {
+    let invalid = || error!(ValidationError::InvalidBadgeImage);
+    let keyword_end = data.iter().position(|byte| *byte == 0).ok_or_else(invalid)?;
+    let flags = data.get(keyword_end + 1..keyword_end + 3).ok_or_else(invalid)?;
+    let mut rest = &data[keyword_end + 3..];
+    // Skip the language tag and the translated keyword
+    for _ in 0..2 {
+        let end = rest.iter().position(|byte| *byte == 0).ok_or_else(invalid)?;
+        rest = &rest[end + 1..];
+    }
+    let text = match flags {
+        [0, _] => rest.to_vec(),
+        [1, 0] => {
+            let mut inflated = Vec::new();
+            flate2::read::ZlibDecoder::new(rest).read_to_end(&mut inflated).map_err(|_| invalid())?;
+            inflated
+        }
+        _ => return Err(invalid()),
+    };
+    String::from_utf8(text).map_err(|_| invalid())
+}
+
+/// Whether the chunk carries a baked credential, in either keyword
+fn is_badge_chunk(chunk: &PngChunk) -> bool {
+    matches!(chunk.text_keyword(), Some(PNG_CREDENTIAL_KEYWORD) | Some(PNG_LEGACY_KEYWORD))
+}
+
+/// Embed a credential (JSON-LD or compact JWT) in a PNG
+pub fn embed_credential_in_png(credential_data: &str, png: &[u8]) -> Result<Vec<u8>> {
+    if credential_data.is_empty() {
+        return Err(error!(ValidationError::MissingRequiredField));
+    }
+    let chunks = parse_chunks(png)?;
+    if chunks.iter().any(is_badge_chunk) {
+        msg!("❌ PNG is already baked");
+        return Err(error!(ValidationError::AlreadyBaked));
+    }
+
+    let iend = chunks.last().map_or(png.len(), |chunk| chunk.offset);
+    let chunk = encode_chunk(b"iTXt", &itxt_data(PNG_CREDENTIAL_KEYWORD, credential_data));
+    let mut baked = Vec::with_capacity(png.len() + chunk.len());
+    baked.extend_from_slice(&png[..iend]);
+    baked.extend_from_slice(&chunk);
+    baked.extend_from_slice(&png[iend..]);
+
+    msg!("🍪 Baked credential into PNG ({} bytes)", baked.len());
+    Ok(baked)
+}
+
+/// Extract the baked credential from a PNG
+/// Prefers the OB 3.0 `openbadgecredential` iTXt chunk over legacy `openbadges` chunks
+pub fn extract_credential_from_png(png: &[u8]) -> Result<String> {
+    let chunks = parse_chunks(png)?;
+    if let Some(chunk) = chunks
+        .iter()
+        .find(|chunk| &chunk.chunk_type == b"iTXt" && chunk.text_keyword() == Some(PNG_CREDENTIAL_KEYWORD))
+    {
+        return itxt_text(chunk.data);
+    }
+
+    for chunk in chunks.iter().filter(|chunk| chunk.text_keyword() == Some(PNG_LEGACY_KEYWORD)) {
+        match &chunk.chunk_type {
+            b"iTXt" => return itxt_text(chunk.data),
+            b"tEXt" => {
+                // Latin-1 text after the keyword's null separator
+                let text = &chunk.data[PNG_LEGACY_KEYWORD.len() + 1..];
+                return Ok(text.iter().map(|byte| *byte as char).collect());
+            }
+            _ => {}
+        }
+    }
+
+    Err(error!(ValidationError::MissingRequiredField))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Smallest valid PNG layout: signature, IHDR, empty IDAT, IEND
+    fn blank_png() -> Vec<u8> {
+        let mut png = PNG_SIGNATURE.to_vec();
+        png.extend(encode_chunk(b"IHDR", &[0, 0, 0, 1, 0, 0, 0, 1, 8, 0, 0, 0, 0]));
+        png.extend(encode_chunk(b"IDAT", &[]));
+        png.extend(encode_chunk(b"IEND", &[]));
+        png
+    }
+
+    #[test]
+    fn test_crc_matches_png_reference() {
+        // The IEND chunk CRC is fixed by the PNG specification
+        assert_eq!(chunk_crc(b"IEND", &[]), 0xae42_6082);
+    }
+
+    #[test]
+    fn test_bake_and_extract_round_trip() {
+        let credential = r#"{"id":"urn:uuid:1","name":"Rust ✓"}"#;
+        let baked = embed_credential_in_png(credential, &blank_png()).unwrap();
+
+        let chunks = parse_chunks(&baked).unwrap();
+        let types: Vec<&[u8; 4]> = chunks.iter().map(|chunk| &chunk.chunk_type).collect();
+        assert_eq!(types, vec![b"IHDR", b"IDAT", b"iTXt", b"IEND"]);
+        assert_eq!(extract_credential_from_png(&baked).unwrap(), credential);
+    }
+
+    #[test]
+    fn test_double_baking_is_rejected() {
+        let baked = embed_credential_in_png("{}", &blank_png()).unwrap();
+        assert!(embed_credential_in_png("{}", &baked).is_err());
+    }
+
+    #[test]
+    fn test_extracts_legacy_chunks() {
+        let mut png = blank_png();
+        let iend = png.len() - 12;
+        let mut text = PNG_LEGACY_KEYWORD.as_bytes().to_vec();
+        text.push(0);
+        text.extend_from_slice(b"https://example.org/assertion");
+        png.splice(iend..iend, encode_chunk(b"tEXt", &text));
+        assert_eq!(extract_credential_from_png(&png).unwrap(), "https://example.org/assertion");
+        assert!(embed_credential_in_png("{}", &png).is_err());
+    }
+
+    #[test]
+    fn test_corrupt_images_are_rejected() {
+        let mut png = blank_png();
+        let last = png.len() - 1;
+        png[last] ^= 0xff;
+        assert!(parse_chunks(&png).is_err());
+        assert!(parse_chunks(&PNG_SIGNATURE).is_err());
+        assert!(extract_credential_from_png(&blank_png()).is_err());
+    }
+}
@@ -0,0 +1,354 @@
+//! SVG baking (Open Badges 3.0 §B.2)
+//!
+//! A baked SVG declares the `https://purl.imsglobal.org/ob/v3p0` namespace on
+//! its root element and carries one `<openbadges:credential>` element as the
+//! root's first child. A JSON-LD credential is wrapped in CDATA; a compact
+//! JWT or a hosted URL goes in the element's `verify` attribute instead.
+//!
+//! The SVG is read with a small tokenizer rather than an XML parser: it
+//! skips comments, processing instructions and the doctype, keeps byte
+//! ranges so embedding leaves the rest of the image untouched, and resolves
+//! the namespace prefix from the declarations, so extraction also accepts
+//! images baked under another prefix and Open Badges 2.0
+//! `<openbadges:assertion>` elements.
+
+use anchor_lang::prelude::*;
+use std::ops::Range;
+use crate::common::errors::ValidationError;
+
+/// Namespace of Open Badges 3.0 baked credentials
+pub const SVG_CREDENTIAL_NAMESPACE: &str = "https://purl.imsglobal.org/ob/v3p0";
+
+/// Namespace used by Open Badges 2.0 baked images
+pub const SVG_LEGACY_NAMESPACE: &str = "http://openbadges.org";
+
+/// Namespace prefix used when baking
+pub const SVG_PREFIX: &str = "openbadges";
+
+/// One piece of SVG markup
+#[derive(Clone, Debug, PartialEq)]
+enum Node<'a> {
+    /// Start tag, or an empty-element tag when `empty` is set
+    Start {
+        name: &'a str,
+        attributes: Vec<(&'a str, String)>,
+        range: Range<usize>,
+        empty: bool,
+    },
+    /// End tag
+    End { name: &'a str },
+    /// Character data between tags, still entity-escaped
+    Text(&'a str),
+    /// Contents of a CDATA section
+    CData(&'a str),
+}
+
+fn invalid() -> Error {
+    error!(ValidationError::InvalidBadgeImage)
+}
+
+/// Replace the predefined XML entities
+fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Escape text for a double-quoted attribute value
+fn escape_attribute(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Index of the `>` closing the tag at the start of `markup`, skipping quoted values
+fn tag_end(markup: &str) -> Option<usize> {
+    let mut quote = None;
+    for (index, c) in markup.char_indices() {
+        match (quote, c) {
+            (None, '"') | (None, '\'') => quote = Some(c),
+            (Some(open), _) if open == c => quote = None,
+            (None, '>') => return Some(index),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Attributes of a start tag, after its name
+fn parse_attributes(mut markup: &str) -> Result<Vec<(&str, String)>> {
+    let mut attributes = Vec::new();
+    loop {
+        markup = markup.trim_start();
+        if markup.is_empty() {
+            return Ok(attributes);
+        }
+        let name_end = markup.find(|c: char| c == '=' || c.is_whitespace()).ok_or_else(invalid)?;
+        let name = &markup[..name_end];
+        let rest = markup[name_end..].trim_start().strip_prefix('=').ok_or_else(invalid)?.trim_start();
+        let quote = rest.chars().next().filter(|c| *c == '"' || *c == '\'').ok_or_else(invalid)?;
+        let value_end = rest[1..].find(quote).ok_or_else(invalid)?;
+        if name.is_empty() {
+            return Err(invalid());
+        }
+        attributes.push((name, unescape(&rest[1..1 + value_end])));
+        markup = &rest[value_end + 2..];
+    }
+}
+
+/// Split an SVG into tags, text and CDATA sections
+fn tokenize(svg: &str) -> Result<Vec<Node<'_>>> {
+    let mut nodes = Vec::new();
+    let mut pos = 0;
+    while pos < svg.len() {
+        let rest = &svg[pos..];
+        if let Some(body) = rest.strip_prefix("<!--") {
+            pos += 4 + body.find("-->").ok_or_else(invalid)? + 3;
+        } else if let Some(body) = rest.strip_prefix("<![CDATA[") {
+            let end = body.find("]]>").ok_or_else(invalid)?;
+            nodes.push(Node::CData(&body[..end]));
+            pos += 9 + end + 3;
+        } else if rest.starts_with("<?") {
+            pos += rest.find("?>").ok_or_else(invalid)? + 2;
+        } else if rest.starts_with("<!") {
+            // Doctype, including an internal subset in brackets
+            let end = match (rest.find('['), rest.find('>')) {
+                (Some(open), Some(close)) if open < close => rest.find("]>").map(|end| end + 1),
+                (_, close) => close,
+            };
+            pos += end.ok_or_else(invalid)? + 1;
+        } else if let Some(body) = rest.strip_prefix("</") {
+            let end = body.find('>').ok_or_else(invalid)?;
+            nodes.push(Node::End { name: body[..end].trim() });
+            pos += 2 + end + 1;
+        } else if rest.starts_with('<') {
+            let end = tag_end(rest).ok_or_else(invalid)?;
+            let inner = &rest[1..end];
+            let (inner, empty) = match inner.strip_suffix('/') {
+                Some(inner) => (inner, true),
+                None => (inner, false),
+            };
+            let name_end = inner.find(char::is_whitespace).unwrap_or(inner.len());
+            if name_end == 0 {
+                return Err(invalid());
+            }
+            nodes.push(Node::Start {
+                name: &inner[..name_end],
+                attributes: parse_attributes(&inner[name_end..])?,
+                range: pos..pos + end + 1,
+                empty,
+            });
+            pos += end + 1;
+        } else {
+            let end = rest.find('<').unwrap_or(rest.len());
+            nodes.push(Node::Text(&rest[..end]));
+            pos += end;
+        }
+    }
+    Ok(nodes)
+}
+
+/// Prefix an element's attributes bind to `namespace`
+fn namespace_prefix<'a>(attributes: &[(&'a str, String)], namespace: &str) -> Option<&'a str> {
+    attributes
+        .iter()
+        .find(|(name, value)| value == namespace && name.starts_with("xmlns:"))
+        .map(|(name, _)| &name["xmlns:".len()..])
+}
+
+/// Index of the root `svg` element
+fn root_index(nodes: &[Node]) -> Result<usize> {
+    let index = nodes.iter().position(|node| matches!(node, Node::Start { .. })).ok_or_else(invalid)?;
+    match &nodes[index] {
+        Node::Start { name, .. } if name.rsplit(':').next() == Some("svg") => Ok(index),
+        _ => Err(invalid()),
+    }
+}
+
+/// Index of the first `local` element in `namespace`, declared on the root or the element itself
+fn find_element(nodes: &[Node], root: usize, namespace: &str, local: &str) -> Option<usize> {
+    let Node::Start { attributes: root_attributes, .. } = &nodes[root] else {
+        return None;
+    };
+    nodes.iter().enumerate().skip(root + 1).find_map(|(index, node)| match node {
+        Node::Start { name, attributes, .. } => {
+            let prefix = namespace_prefix(attributes, namespace).or_else(|| namespace_prefix(root_attributes, namespace))?;
+            (name.strip_prefix(prefix)?.strip_prefix(':')? == local).then_some(index)
+        }
+        _ => None,
+    })
+}
+
+/// The `verify` attribute of the element at `index`, or its text and CDATA content
+fn element_payload(nodes: &[Node], index: usize) -> Option<String> {
+    let Node::Start { name, attributes, empty, .. } = &nodes[index] else {
+        return None;
+    };
+    if let Some((_, verify)) = attributes.iter().find(|(attribute, _)| *attribute == "verify") {
+        return Some(verify.trim().to_string()).filter(|verify| !verify.is_empty());
+    }
+    if *empty {
+        return None;
+    }
+
+    let mut payload = String::new();
+    for node in &nodes[index + 1..] {
+        match node {
+            Node::End { name: end } if end == name => break,
+            Node::Text(text) => payload.push_str(&unescape(text)),
+            Node::CData(data) => payload.push_str(data),
+            _ => {}
+        }
+    }
+    Some(payload.trim().to_string()).filter(|payload| !payload.is_empty())
+}
+
+/// The credential element for `credential_data` under `prefix`
+fn credential_element(prefix: &str, credential_data: &str) -> String {
+    if credential_data.trim_start().starts_with('{') {
+        // "]]>" cannot appear inside CDATA; split the section around it
+        let data = credential_data.replace("]]>", "]]]]><![CDATA[>");
+        format!("<{prefix}:credential><![CDATA[{data}]]></{prefix}:credential>")
+    } else {
+        format!(r#"<{prefix}:credential verify="{}"></{prefix}:credential>"#, escape_attribute(credential_data))
+    }
+}
+
+/// Embed a credential in an SVG image
+/// JSON-LD credentials are embedded in CDATA, anything else (a compact JWT or URL) as `verify`
+pub fn embed_credential_in_svg(credential_data: &str, svg: &[u8]) -> Result<Vec<u8>> {
+    if credential_data.is_empty() {
+        return Err(error!(ValidationError::MissingRequiredField));
+    }
+    let svg = std::str::from_utf8(svg).map_err(|_| invalid())?;
+    let nodes = tokenize(svg)?;
+    let root = root_index(&nodes)?;
+    if find_element(&nodes, root, SVG_CREDENTIAL_NAMESPACE, "credential").is_some()
+        || find_element(&nodes, root, SVG_LEGACY_NAMESPACE, "assertion").is_some()
+    {
+        msg!("❌ SVG is already baked");
+        return Err(error!(ValidationError::AlreadyBaked));
+    }
+    let Node::Start { name, attributes, range, empty } = &nodes[root] else {
+        return Err(invalid());
+    };
+
+    let (prefix, declaration) = match namespace_prefix(attributes, SVG_CREDENTIAL_NAMESPACE) {
+        Some(prefix) => (prefix, String::new()),
+        None if attributes.iter().any(|(attribute, _)| *attribute == format!("xmlns:{SVG_PREFIX}")) => {
+            msg!("❌ SVG binds the {} prefix to another namespace", SVG_PREFIX);
+            return Err(invalid());
+        }
+        None => (SVG_PREFIX, format!(r#" xmlns:{SVG_PREFIX}="{SVG_CREDENTIAL_NAMESPACE}""#)),
+    };
+
+    let tag = svg[range.clone()].trim_end_matches('>');
+    let tag = if *empty { tag.trim_end_matches('/') } else { tag };
+    let mut baked = String::with_capacity(svg.len() + declaration.len() + credential_data.len() + 64);
+    baked.push_str(&svg[..range.start]);
+    baked.push_str(tag.trim_end());
+    baked.push_str(&declaration);
+    baked.push('>');
+    baked.push_str(&credential_element(prefix, credential_data));
+    if *empty {
+        baked.push_str(&format!("</{name}>"));
+    }
+    baked.push_str(&svg[range.end..]);
+
+    msg!("🍪 Baked credential into SVG ({} bytes)", baked.len());
+    Ok(baked.into_bytes())
+}
+
+/// Extract the baked credential from an SVG image
+/// Prefers the OB 3.0 `credential` element over a legacy `assertion` element
+pub fn extract_credential_from_svg(svg: &[u8]) -> Result<String> {
+    let svg = std::str::from_utf8(svg).map_err(|_| invalid())?;
+    let nodes = tokenize(svg)?;
+    let root = root_index(&nodes)?;
+    [(SVG_CREDENTIAL_NAMESPACE, "credential"), (SVG_LEGACY_NAMESPACE, "assertion")]
+        .iter()
+        .find_map(|(namespace, local)| {
+            find_element(&nodes, root, namespace, local).and_then(|index| element_payload(&nodes, index))
+        })
+        .ok_or_else(|| error!(ValidationError::MissingRequiredField))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SVG: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!-- badge artwork -->
+<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 10 10"><circle r="4"/></svg>"#;
+
+    #[test]
+    fn test_bake_and_extract_json_credential() {
+        let credential = r#"{"id":"urn:uuid:1","name":"A <b> & \"c\" ]]> d"}"#;
+        let baked = embed_credential_in_svg(credential, SVG.as_bytes()).unwrap();
+        let text = String::from_utf8(baked.clone()).unwrap();
+        assert!(text.contains(
+            r#"viewBox="0 0 10 10" xmlns:openbadges="https://purl.imsglobal.org/ob/v3p0"><openbadges:credential><![CDATA["#
+        ));
+        assert!(text.ends_with(r#"<circle r="4"/></svg>"#));
+        assert_eq!(extract_credential_from_svg(&baked).unwrap(), credential);
+    }
+
+    #[test]
+    fn test_jwt_goes_in_verify_attribute() {
+        let jwt = "eyJhbGciOiJFZERTQSJ9.eyJpc3MiOiJ4In0.c2ln";
+        let baked = embed_credential_in_svg(jwt, SVG.as_bytes()).unwrap();
+        let text = String::from_utf8(baked.clone()).unwrap();
+        assert!(text.contains(&format!(r#"<openbadges:credential verify="{jwt}"></openbadges:credential>"#)));
+        assert_eq!(extract_credential_from_svg(&baked).unwrap(), jwt);
+    }
+
+    #[test]
+    fn test_existing_namespace_and_empty_root() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" xmlns:ob="https://purl.imsglobal.org/ob/v3p0" />"#;
+        let baked = String::from_utf8(embed_credential_in_svg("{}", svg.as_bytes()).unwrap()).unwrap();
+        assert_eq!(baked.matches("purl.imsglobal.org").count(), 1);
+        assert!(baked.contains("<ob:credential><![CDATA[{}]]></ob:credential></svg>"));
+        assert_eq!(extract_credential_from_svg(baked.as_bytes()).unwrap(), "{}");
+
+        let clash = r#"<svg xmlns:openbadges="https://example.org/other"></svg>"#;
+        assert!(embed_credential_in_svg("{}", clash.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_double_baking_is_rejected() {
+        let baked = embed_credential_in_svg("{}", SVG.as_bytes()).unwrap();
+        assert!(embed_credential_in_svg("{}", &baked).is_err());
+    }
+
+    #[test]
+    fn test_extraction_tolerates_comments_and_whitespace() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg"
+                xmlns:openbadges="https://purl.imsglobal.org/ob/v3p0">
+            <!-- <openbadges:credential verify="https://decoy.example"/> -->
+            <openbadges:credential>
+                <!-- issued 2024 -->
+                <![CDATA[ {"id":"urn:uuid:2"} ]]>
+            </openbadges:credential>
+        </svg>"#;
+        assert_eq!(extract_credential_from_svg(svg.as_bytes()).unwrap(), r#"{"id":"urn:uuid:2"}"#);
+    }
+
+    #[test]
+    fn test_extracts_legacy_assertion() {
+        let svg = r#"<svg xmlns:openbadges="http://openbadges.org"><openbadges:assertion verify="https://example.org/a/1"></openbadges:assertion></svg>"#;
+        assert_eq!(extract_credential_from_svg(svg.as_bytes()).unwrap(), "https://example.org/a/1");
+        assert!(embed_credential_in_svg("{}", svg.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_malformed_images_are_rejected() {
+        assert!(extract_credential_from_svg(b"<html></html>").is_err());
+        assert!(extract_credential_from_svg(b"<svg><!-- unterminated </svg>").is_err());
+        assert!(extract_credential_from_svg(SVG.as_bytes()).is_err());
+        assert!(embed_credential_in_svg("{}", b"not markup").is_err());
+    }
+}
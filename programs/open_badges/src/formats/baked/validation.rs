@@ -0,0 +1,162 @@
+//! Baked Badge Validation
+//!
+//! A baked badge is valid when the credential extracted from its image is
+//! the credential it was baked with.
+//!
+//! Verifying a badge handed over by a holder goes further: the image is
+//! compared with the hash its issuer registered, the extracted credential
+//! is run through the compliance validator, and its embedded proof is
+//! checked. A JSON-LD credential carries a Data Integrity proof over the
+//! document without its `proof` member; a JWT carries its own signature.
+//! Both signatures are checked against an Ed25519 program instruction, as
+//! in `verify_credential_format`.
+
+use anchor_lang::prelude::*;
+use super::BakedBadge;
+use crate::compliance_validator::ComplianceValidator;
+use crate::credential::{self, JwtVerificationContext};
+use crate::AchievementCredential;
+
+/// Check that the image carries exactly the badge's credential
+pub fn validate_baked_badge(badge: &BakedBadge) -> Result<bool> {
+    let embedded = badge.extract_credential()?;
+    if embedded != badge.credential_data {
+        msg!("❌ Baked credential does not match the badge's credential");
+        return Ok(false);
+    }
+    Ok(true)
+}
+
+/// Outcome of verifying a baked badge
+#[derive(Clone, Debug, PartialEq)]
+pub struct BakedVerification {
+    /// Credential as embedded in the image, JSON-LD or a compact JWT
+    pub credential: String,
+    /// Whether the image is the one registered for the credential; `None` when no credential account was given
+    pub image_intact: Option<bool>,
+    /// Compliance validator errors of the credential; empty when it is compliant
+    pub compliance_errors: Vec<String>,
+    /// Whether the embedded Data Integrity proof or JWT signature verifies
+    pub proof_valid: bool,
+}
+
+impl BakedVerification {
+    /// Whether the credential is compliant
+    pub fn is_compliant(&self) -> bool {
+        self.compliance_errors.is_empty()
+    }
+
+    /// Whether every check made passed
+    pub fn is_valid(&self) -> bool {
+        self.image_intact != Some(false) && self.is_compliant() && self.proof_valid
+    }
+}
+
+/// Verify a credential extracted from `image`
+///
+/// `registered` is the credential account the badge claims to belong to;
+/// its registered baked image is compared with `image` when given.
+pub fn verify_extracted_credential(
+    image: &[u8],
+    extracted: String,
+    context: &JwtVerificationContext,
+    registered: Option<&AchievementCredential>,
+) -> Result<BakedVerification> {
+    let image_intact = registered
+        .map(|credential| crate::baked_image::matches_registered(credential, &crate::baked_image::image_sha256(image)));
+
+    let (document, proof_valid) = if extracted.trim().starts_with('{') {
+        let document: serde_json::Value = serde_json::from_str(&extracted)
+            .map_err(|_| error!(crate::common::errors::ValidationError::InvalidJson))?;
+        let proof_valid = verify_data_integrity_proof(&extracted, &document, context);
+        (document, proof_valid)
+    } else {
+        let jwt = credential::decode_jwt(&extracted)?;
+        let proof_valid = credential::verify_jwt_credential(&extracted, context).unwrap_or(false);
+        (jwt.payload["vc"].clone(), proof_valid)
+    };
+
+    let compliance_errors = match ComplianceValidator::new().at(context.now).validate_credential(&document.to_string()) {
+        Ok(report) => report.errors,
+        Err(error) => vec![error.to_string()],
+    };
+
+    Ok(BakedVerification { credential: extracted, image_intact, compliance_errors, proof_valid })
+}
+
+/// Whether the Data Integrity proof of `raw` verifies
+///
+/// The signed message is `raw` with its trailing `proof` member removed, so
+/// the document must have been baked byte-for-byte as signed.
+fn verify_data_integrity_proof(raw: &str, document: &serde_json::Value, context: &JwtVerificationContext) -> bool {
+    let proof = &document["proof"];
+    let field = |name: &str| proof.get(name).and_then(serde_json::Value::as_str);
+    if field("type") != Some("DataIntegrityProof") {
+        msg!("❌ Baked credential has no Data Integrity proof");
+        return false;
+    }
+    let (Some(method), Some(issuer)) = (field("verificationMethod"), document["issuer"].as_str().or(document["issuer"]["id"].as_str())) else {
+        return false;
+    };
+    let signature = field("proofValue").and_then(crate::multibase::decode_signature);
+    let (Some(signature), Some(message)) = (signature, crate::canonical::unsigned_document(raw)) else {
+        return false;
+    };
+
+    // A did:sol issuer profile is signed for by its authority
+    let method_did = method.split('#').next().unwrap_or_default();
+    let signer = match context.issuer {
+        Some((issuer_key, profile)) if method_did == format!("did:sol:{}", issuer_key) => Some(profile.authority),
+        _ => credential::resolve_jwt_signer(method).ok(),
+    };
+    let Some(signer) = signer.filter(|signer| credential::jwt_issuer_trusted(issuer, method, signer, context.issuer, context.staff)) else {
+        msg!("❌ Proof key {} is not authorized for issuer {}", method, issuer);
+        return false;
+    };
+
+    let Some(instructions) = context.instructions else {
+        msg!("❌ Proof check needs the instructions sysvar and an Ed25519 instruction");
+        return false;
+    };
+    crate::proof::ensure_ed25519_verified(instructions, &signer, message.as_bytes(), &signature).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formats::baked::{bake_credential, BakedFormat};
+    use crate::test_utils::{fixture_credential, fixture_profile, sign_credential, TestKeypair};
+
+    const SVG: &[u8] = br#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10"></svg>"#;
+
+    #[test]
+    fn test_extracted_credential_reports_image_compliance_and_proof() {
+        let issuer = Pubkey::new_unique();
+        let authority = TestKeypair::from_seed(3);
+        let profile = fixture_profile(&issuer, &authority.pubkey(), "Issuer");
+        let mut credential = fixture_credential(&Pubkey::new_unique(), &issuer, &Pubkey::new_unique(), &Pubkey::new_unique());
+        sign_credential(&mut credential, &authority);
+        let signed = crate::canonical::signed_document(&credential).unwrap();
+
+        let badge = bake_credential(&signed, SVG, BakedFormat::Svg).unwrap();
+        credential.baked_image = Some(crate::baked_image::BakedImage {
+            sha256: crate::baked_image::image_sha256(&badge.image_data),
+            format: crate::baked_image::BakedImageFormat::Svg,
+            registered_at: "2024-01-01T00:00:00Z".to_string(),
+        });
+        let context = JwtVerificationContext { now: 1_750_000_000, issuer: Some((issuer, &profile)), instructions: None, staff: None };
+
+        let verification = verify_extracted_credential(&badge.image_data, signed.clone(), &context, Some(&credential)).unwrap();
+        assert_eq!(verification.image_intact, Some(true));
+        // The signing projection omits the achievement's name and criteria
+        assert!(!verification.is_compliant());
+        // No Ed25519 instruction accompanies a unit test
+        assert!(!verification.proof_valid);
+        assert!(!verification.is_valid());
+
+        let unregistered = verify_extracted_credential(SVG, signed, &context, Some(&credential)).unwrap();
+        assert_eq!(unregistered.image_intact, Some(false));
+        assert!(verify_extracted_credential(SVG, "{not json".to_string(), &context, None).is_err());
+        assert!(verify_extracted_credential(SVG, "not.a-jwt".to_string(), &context, None).is_err());
+    }
+}
@@ -0,0 +1,258 @@
+//! Minimal CBOR encoder and decoder (RFC 8949)
+//!
+//! Only the subset needed to build and read mdoc and COSE structures
+//! on-chain: integers, byte and text strings, arrays, maps, tags, booleans
+//! and null. Values are written with the shortest argument encoding, and
+//! maps keep their insertion order so callers control the canonical key
+//! order. The decoder accepts definite lengths only.
+
+/// A CBOR data item
+#[derive(Clone, Debug, PartialEq)]
+pub enum CborValue {
+    /// Major type 0
+    Unsigned(u64),
+    /// Major type 1, holding the (negative) value itself
+    Negative(i64),
+    /// Major type 2
+    Bytes(Vec<u8>),
+    /// Major type 3
+    Text(String),
+    /// Major type 4
+    Array(Vec<CborValue>),
+    /// Major type 5, entries in encoding order
+    Map(Vec<(CborValue, CborValue)>),
+    /// Major type 6
+    Tag(u64, Box<CborValue>),
+    /// Simple values false/true
+    Bool(bool),
+    /// Simple value null
+    Null,
+}
+
+/// Tag for an RFC 3339 date/time string
+pub const TAG_DATE_TIME: u64 = 0;
+
+/// Tag for embedded CBOR data items ("encoded CBOR data item")
+pub const TAG_ENCODED_CBOR: u64 = 24;
+
+impl CborValue {
+    /// Text string from anything string-like
+    pub fn text(value: impl Into<String>) -> Self {
+        CborValue::Text(value.into())
+    }
+
+    /// Integer, picking the major type from the sign
+    pub fn int(value: i64) -> Self {
+        if value >= 0 {
+            CborValue::Unsigned(value as u64)
+        } else {
+            CborValue::Negative(value)
+        }
+    }
+
+    /// Wrap already-encoded CBOR as `#6.24(bstr)`
+    pub fn encoded_cbor(encoded: Vec<u8>) -> Self {
+        CborValue::Tag(TAG_ENCODED_CBOR, Box::new(CborValue::Bytes(encoded)))
+    }
+
+    /// Encode to bytes
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        self.encode_into(&mut out);
+        out
+    }
+
+    /// Append the encoding to `out`
+    pub fn encode_into(&self, out: &mut Vec<u8>) {
+        match self {
+            CborValue::Unsigned(value) => write_head(out, 0, *value),
+            CborValue::Negative(value) => write_head(out, 1, (-1 - *value) as u64),
+            CborValue::Bytes(bytes) => {
+                write_head(out, 2, bytes.len() as u64);
+                out.extend_from_slice(bytes);
+            }
+            CborValue::Text(text) => {
+                write_head(out, 3, text.len() as u64);
+                out.extend_from_slice(text.as_bytes());
+            }
+            CborValue::Array(items) => {
+                write_head(out, 4, items.len() as u64);
+                for item in items {
+                    item.encode_into(out);
+                }
+            }
+            CborValue::Map(entries) => {
+                write_head(out, 5, entries.len() as u64);
+                for (key, value) in entries {
+                    key.encode_into(out);
+                    value.encode_into(out);
+                }
+            }
+            CborValue::Tag(tag, value) => {
+                write_head(out, 6, *tag);
+                value.encode_into(out);
+            }
+            CborValue::Bool(false) => out.push(0xf4),
+            CborValue::Bool(true) => out.push(0xf5),
+            CborValue::Null => out.push(0xf6),
+        }
+    }
+}
+
+/// Deepest nesting the decoder follows
+pub const MAX_DECODE_DEPTH: usize = 16;
+
+impl CborValue {
+    /// Decode a single data item that spans all of `bytes`
+    pub fn decode(bytes: &[u8]) -> Option<CborValue> {
+        let mut position = 0;
+        let value = decode_item(bytes, &mut position, 0)?;
+        (position == bytes.len()).then_some(value)
+    }
+
+    /// Integer value of an Unsigned or Negative item
+    pub fn as_int(&self) -> Option<i64> {
+        match self {
+            CborValue::Unsigned(value) => i64::try_from(*value).ok(),
+            CborValue::Negative(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Value of `key` in a map
+    pub fn get(&self, key: &CborValue) -> Option<&CborValue> {
+        match self {
+            CborValue::Map(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+}
+
+/// Read `len` bytes at `position`
+fn take<'a>(bytes: &'a [u8], position: &mut usize, len: usize) -> Option<&'a [u8]> {
+    let end = position.checked_add(len)?;
+    let slice = bytes.get(*position..end)?;
+    *position = end;
+    Some(slice)
+}
+
+/// Read one data item at `position`
+fn decode_item(bytes: &[u8], position: &mut usize, depth: usize) -> Option<CborValue> {
+    if depth > MAX_DECODE_DEPTH {
+        return None;
+    }
+    let initial = *take(bytes, position, 1)?.first()?;
+    let major = initial >> 5;
+    let info = initial & 0x1f;
+    if major == 7 {
+        return match info {
+            20 => Some(CborValue::Bool(false)),
+            21 => Some(CborValue::Bool(true)),
+            22 => Some(CborValue::Null),
+            _ => None,
+        };
+    }
+    let argument = match info {
+        0..=23 => info as u64,
+        24 => take(bytes, position, 1)?[0] as u64,
+        25 => u16::from_be_bytes(take(bytes, position, 2)?.try_into().ok()?) as u64,
+        26 => u32::from_be_bytes(take(bytes, position, 4)?.try_into().ok()?) as u64,
+        27 => u64::from_be_bytes(take(bytes, position, 8)?.try_into().ok()?),
+        _ => return None,
+    };
+    match major {
+        0 => return Some(CborValue::Unsigned(argument)),
+        1 => return Some(CborValue::Negative(-1 - i64::try_from(argument).ok()?)),
+        6 => return Some(CborValue::Tag(argument, Box::new(decode_item(bytes, position, depth + 1)?))),
+        _ => {}
+    }
+    // Every element takes at least one byte, which bounds the length of what follows
+    let count = usize::try_from(argument).ok().filter(|count| *count <= bytes.len())?;
+    match major {
+        2 => Some(CborValue::Bytes(take(bytes, position, count)?.to_vec())),
+        3 => Some(CborValue::Text(String::from_utf8(take(bytes, position, count)?.to_vec()).ok()?)),
+        4 => (0..count)
+            .map(|_| decode_item(bytes, position, depth + 1))
+            .collect::<Option<Vec<_>>>()
+            .map(CborValue::Array),
+        5 => (0..count)
+            .map(|_| Some((decode_item(bytes, position, depth + 1)?, decode_item(bytes, position, depth + 1)?)))
+            .collect::<Option<Vec<_>>>()
+            .map(CborValue::Map),
+        _ => None,
+    }
+}
+
+/// Write a major type and argument with the shortest encoding
+fn write_head(out: &mut Vec<u8>, major: u8, argument: u64) {
+    let major = major << 5;
+    if argument < 24 {
+        out.push(major | argument as u8);
+    } else if argument <= u8::MAX as u64 {
+        out.push(major | 24);
+        out.push(argument as u8);
+    } else if argument <= u16::MAX as u64 {
+        out.push(major | 25);
+        out.extend_from_slice(&(argument as u16).to_be_bytes());
+    } else if argument <= u32::MAX as u64 {
+        out.push(major | 26);
+        out.extend_from_slice(&(argument as u32).to_be_bytes());
+    } else {
+        out.push(major | 27);
+        out.extend_from_slice(&argument.to_be_bytes());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rfc8949_examples() {
+        assert_eq!(CborValue::int(0).encode(), vec![0x00]);
+        assert_eq!(CborValue::int(24).encode(), vec![0x18, 0x18]);
+        assert_eq!(CborValue::int(1000).encode(), vec![0x19, 0x03, 0xe8]);
+        assert_eq!(CborValue::int(-1).encode(), vec![0x20]);
+        assert_eq!(CborValue::int(-1000).encode(), vec![0x39, 0x03, 0xe7]);
+        assert_eq!(CborValue::text("IETF").encode(), vec![0x64, b'I', b'E', b'T', b'F']);
+        assert_eq!(
+            CborValue::Array(vec![CborValue::int(1), CborValue::int(2)]).encode(),
+            vec![0x82, 0x01, 0x02]
+        );
+        assert_eq!(
+            CborValue::Map(vec![(CborValue::text("a"), CborValue::Bool(true))]).encode(),
+            vec![0xa1, 0x61, b'a', 0xf5]
+        );
+    }
+
+    #[test]
+    fn test_decode_round_trips() {
+        let value = CborValue::Tag(
+            18,
+            Box::new(CborValue::Array(vec![
+                CborValue::Bytes(vec![1, 2, 3]),
+                CborValue::Map(vec![(CborValue::int(-8), CborValue::text("kid"))]),
+                CborValue::Null,
+                CborValue::Unsigned(u32::MAX as u64 + 1),
+                CborValue::Bool(true),
+            ])),
+        );
+        assert_eq!(CborValue::decode(&value.encode()), Some(value));
+    }
+
+    #[test]
+    fn test_decode_rejects_malformed_input() {
+        // Trailing bytes, truncated string, indefinite length, oversized count
+        assert_eq!(CborValue::decode(&[0x01, 0x02]), None);
+        assert_eq!(CborValue::decode(&[0x43, 0x01]), None);
+        assert_eq!(CborValue::decode(&[0x9f, 0xff]), None);
+        assert_eq!(CborValue::decode(&[0x9b, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]), None);
+        assert_eq!(CborValue::decode(&[0x81; 64]), None);
+    }
+
+    #[test]
+    fn test_encoded_cbor_tag() {
+        let inner = CborValue::int(1).encode();
+        assert_eq!(CborValue::encoded_cbor(inner).encode(), vec![0xd8, 0x18, 0x41, 0x01]);
+    }
+}
@@ -0,0 +1,275 @@
+//! COSE-secured credentials (W3C VC JOSE COSE)
+//!
+//! Wraps the credential document in a COSE_Sign1 message signed with EdDSA,
+//! the compact binary form NFC and QR card wallets read. The payload is the
+//! JSON-LD credential (`application/vc`); the protected header names the
+//! algorithm, the content type and, as `kid`, the signer's DID URL.
+//!
+//! Signing follows the JWT flow: `prepare_cose_credential` gives the
+//! `Sig_structure` for the issuer authority to sign off-chain and
+//! `CoseDraft::finish` attaches the signature. Credentials travel as
+//! base64url text, which `detect` recognises in `verify_credential_format`.
+
+use anchor_lang::prelude::*;
+use base64::{Engine, engine::general_purpose};
+use super::cbor::CborValue;
+use crate::common::errors::ValidationError;
+use crate::credential::JwtVerificationContext;
+use crate::rubric::Rubric;
+use crate::{Achievement, AchievementCredential};
+
+/// CBOR tag of a COSE_Sign1 message
+pub const COSE_SIGN1_TAG: u64 = 18;
+
+/// COSE algorithm identifier for EdDSA
+pub const COSE_ALG_EDDSA: i64 = -8;
+
+/// COSE header label for the algorithm
+pub const COSE_HEADER_ALG: i64 = 1;
+
+/// COSE header label for the content type
+pub const COSE_HEADER_CONTENT_TYPE: i64 = 3;
+
+/// COSE header label for the key identifier
+pub const COSE_HEADER_KID: i64 = 4;
+
+/// COSE header label for the message type
+pub const COSE_HEADER_TYP: i64 = 16;
+
+/// Media type of a COSE-secured credential
+pub const VC_COSE_TYPE: &str = "application/vc+cose";
+
+/// Media type of the credential payload
+pub const VC_CONTENT_TYPE: &str = "application/vc";
+
+/// COSE `Sig_structure` for a Sign1 message with no external AAD
+pub fn sig_structure(protected: &[u8], payload: &[u8]) -> Vec<u8> {
+    CborValue::Array(vec![
+        CborValue::text("Signature1"),
+        CborValue::Bytes(protected.to_vec()),
+        CborValue::Bytes(Vec::new()),
+        CborValue::Bytes(payload.to_vec()),
+    ])
+    .encode()
+}
+
+/// Serialized protected header for a credential signed by `kid`
+pub fn protected_header(kid: &str) -> Vec<u8> {
+    CborValue::Map(vec![
+        (CborValue::int(COSE_HEADER_ALG), CborValue::int(COSE_ALG_EDDSA)),
+        (CborValue::int(COSE_HEADER_CONTENT_TYPE), CborValue::text(VC_CONTENT_TYPE)),
+        (CborValue::int(COSE_HEADER_KID), CborValue::Bytes(kid.as_bytes().to_vec())),
+        (CborValue::int(COSE_HEADER_TYP), CborValue::text(VC_COSE_TYPE)),
+    ])
+    .encode()
+}
+
+/// A COSE_Sign1 credential waiting for the issuer's signature
+#[derive(Clone, Debug, PartialEq)]
+pub struct CoseDraft {
+    /// Serialized protected header
+    pub protected: Vec<u8>,
+    /// Credential document as JSON bytes
+    pub payload: Vec<u8>,
+}
+
+impl CoseDraft {
+    /// Bytes the issuer signs
+    pub fn to_be_signed(&self) -> Vec<u8> {
+        sig_structure(&self.protected, &self.payload)
+    }
+
+    /// Tagged COSE_Sign1 message carrying `signature`
+    pub fn finish(&self, signature: &[u8; 64]) -> Vec<u8> {
+        CborValue::Tag(
+            COSE_SIGN1_TAG,
+            Box::new(CborValue::Array(vec![
+                CborValue::Bytes(self.protected.clone()),
+                CborValue::Map(vec![]),
+                CborValue::Bytes(self.payload.clone()),
+                CborValue::Bytes(signature.to_vec()),
+            ])),
+        )
+        .encode()
+    }
+}
+
+/// Prepare a COSE_Sign1 credential for an issued credential account
+pub fn prepare_cose_credential(
+    signer: &Pubkey,
+    credential: &AchievementCredential,
+    achievement: &Achievement,
+    rubric: Option<&Rubric>,
+) -> Result<CoseDraft> {
+    let document = crate::credential::issued_credential_document(credential, achievement, rubric);
+    let payload = serde_json::to_vec(&document)
+        .map_err(|_| error!(ValidationError::SerializationError))?;
+    Ok(CoseDraft {
+        protected: protected_header(&format!("did:sol:{}", signer)),
+        payload,
+    })
+}
+
+/// A COSE_Sign1 message split into its parts
+#[derive(Clone, Debug, PartialEq)]
+pub struct DecodedCoseSign1 {
+    /// Protected header exactly as signed
+    pub protected: Vec<u8>,
+    /// Algorithm from the protected header
+    pub alg: Option<i64>,
+    /// Key identifier from the protected header
+    pub kid: Option<String>,
+    /// Message payload
+    pub payload: Vec<u8>,
+    pub signature: [u8; 64],
+}
+
+impl DecodedCoseSign1 {
+    /// Bytes the signature covers
+    pub fn to_be_signed(&self) -> Vec<u8> {
+        sig_structure(&self.protected, &self.payload)
+    }
+}
+
+/// Decode a tagged or untagged COSE_Sign1 message with an Ed25519 signature
+pub fn decode_cose_sign1(bytes: &[u8]) -> Result<DecodedCoseSign1> {
+    let malformed = || error!(ValidationError::UnsupportedFormat);
+    let message = match CborValue::decode(bytes).ok_or_else(malformed)? {
+        CborValue::Tag(COSE_SIGN1_TAG, inner) => *inner,
+        other => other,
+    };
+    let CborValue::Array(parts) = message else {
+        return Err(malformed());
+    };
+    let [CborValue::Bytes(protected), _, CborValue::Bytes(payload), CborValue::Bytes(signature)] = parts.as_slice() else {
+        return Err(malformed());
+    };
+    let header = if protected.is_empty() {
+        CborValue::Map(vec![])
+    } else {
+        CborValue::decode(protected).ok_or_else(malformed)?
+    };
+    let kid = match header.get(&CborValue::int(COSE_HEADER_KID)) {
+        Some(CborValue::Bytes(kid)) => Some(String::from_utf8(kid.clone()).map_err(|_| malformed())?),
+        _ => None,
+    };
+
+    Ok(DecodedCoseSign1 {
+        alg: header.get(&CborValue::int(COSE_HEADER_ALG)).and_then(CborValue::as_int),
+        kid,
+        protected: protected.clone(),
+        payload: payload.clone(),
+        signature: signature.as_slice().try_into().map_err(|_| error!(ValidationError::InvalidSignatureLength))?,
+    })
+}
+
+/// COSE_Sign1 bytes of base64url text, when the text is one
+pub fn detect(credential_data: &str) -> Option<Vec<u8>> {
+    let bytes = general_purpose::URL_SAFE_NO_PAD.decode(credential_data.trim()).ok()?;
+    // Tag 18 (0xd2) or a bare four-element array (0x84)
+    matches!(bytes.first(), Some(0xd2) | Some(0x84)).then_some(bytes)
+}
+
+/// Whether `now` lies within the document's validFrom and validUntil
+pub fn document_time_valid(document: &serde_json::Value, now: i64) -> bool {
+    let timestamp = |name: &str| {
+        document.get(name)
+            .and_then(serde_json::Value::as_str)
+            .and_then(|value| crate::parse_iso8601_to_unix(value).ok())
+    };
+    !matches!(timestamp("validFrom"), Some(from) if from > now)
+        && !matches!(timestamp("validUntil"), Some(until) if until <= now)
+}
+
+/// Verify a COSE-secured credential: structure, validity window, issuer key and signature
+pub fn verify_cose_credential(bytes: &[u8], context: &JwtVerificationContext) -> Result<bool> {
+    let message = decode_cose_sign1(bytes)?;
+    if message.alg != Some(COSE_ALG_EDDSA) {
+        msg!("❌ Unsupported COSE algorithm");
+        return Ok(false);
+    }
+
+    let document: serde_json::Value = serde_json::from_slice(&message.payload)
+        .map_err(|_| error!(ValidationError::InvalidJson))?;
+    if !crate::credential::verify_jsonld_credential(&document.to_string())? {
+        return Ok(false);
+    }
+    if !document_time_valid(&document, context.now) {
+        msg!("❌ COSE credential is expired or not yet valid");
+        return Ok(false);
+    }
+
+    let issuer = document["issuer"].get("id").unwrap_or(&document["issuer"]);
+    let Some(iss) = issuer.as_str() else {
+        return Ok(false);
+    };
+    let kid = message.kid.as_deref().unwrap_or(iss);
+    let signer = crate::credential::resolve_jwt_signer(kid)?;
+    if !crate::credential::jwt_issuer_trusted(iss, kid, &signer, context.issuer, context.staff) {
+        msg!("❌ COSE key {} is not authorized for issuer {}", kid, iss);
+        return Ok(false);
+    }
+
+    let Some(instructions) = context.instructions else {
+        msg!("❌ COSE signature check needs the instructions sysvar and an Ed25519 instruction");
+        return Ok(false);
+    };
+    if crate::proof::ensure_ed25519_verified(instructions, &signer, &message.to_be_signed(), &message.signature).is_err() {
+        return Ok(false);
+    }
+
+    msg!("✅ COSE credential signature verified for {}", iss);
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{fixture_achievement, fixture_credential, TestKeypair};
+
+    fn draft(signer: &TestKeypair) -> CoseDraft {
+        let issuer = Pubkey::new_unique();
+        let achievement_key = Pubkey::new_unique();
+        let credential = fixture_credential(&Pubkey::new_unique(), &issuer, &achievement_key, &Pubkey::new_unique());
+        let achievement = fixture_achievement(&achievement_key, &issuer, "Rust");
+        prepare_cose_credential(&signer.pubkey(), &credential, &achievement, None).unwrap()
+    }
+
+    #[test]
+    fn test_signed_message_decodes() {
+        let signer = TestKeypair::from_seed(6);
+        let draft = draft(&signer);
+        let signature = signer.sign(&draft.to_be_signed());
+        let bytes = draft.finish(&signature);
+
+        let decoded = decode_cose_sign1(&bytes).unwrap();
+        assert_eq!(decoded.alg, Some(COSE_ALG_EDDSA));
+        assert_eq!(decoded.kid, Some(format!("did:sol:{}", signer.pubkey())));
+        assert_eq!(decoded.payload, draft.payload);
+        assert!(signer.verify(&decoded.to_be_signed(), &decoded.signature));
+
+        let document: serde_json::Value = serde_json::from_slice(&decoded.payload).unwrap();
+        assert!(document.get("proof").is_none());
+        assert_eq!(document["credentialSubject"]["achievement"]["name"], "Rust");
+    }
+
+    #[test]
+    fn test_detects_only_cose_text() {
+        let signer = TestKeypair::from_seed(6);
+        let bytes = draft(&signer).finish(&[0u8; 64]);
+        assert_eq!(detect(&general_purpose::URL_SAFE_NO_PAD.encode(&bytes)), Some(bytes));
+        assert_eq!(detect("eyJhbGciOiJFZERTQSJ9.e30.sig"), None);
+        assert_eq!(detect("{\"id\":1}"), None);
+    }
+
+    #[test]
+    fn test_validity_window() {
+        let document = serde_json::json!({
+            "validFrom": "2024-01-01T00:00:00Z",
+            "validUntil": "2025-01-01T00:00:00Z"
+        });
+        assert!(document_time_valid(&document, 1_710_000_000));
+        assert!(!document_time_valid(&document, 1_700_000_000));
+        assert!(!document_time_valid(&document, 1_740_000_000));
+    }
+}
@@ -83,6 +83,8 @@ impl JsonLdBuilder {
             &key_pair,
             &self.proof_purpose,
             signer_pubkey,
+            None,
+            None,
         )?;
         
         // Convert to JSON-LD format
@@ -175,6 +177,12 @@ impl JsonLdBuilder {
             id: subject.id.clone(),
             subject_type: Some("AchievementSubject".to_string()),
             achievement: self.convert_achievement(&subject.achievement),
+            results: subject.result.iter().map(|result| JsonLdResult {
+                result_type: vec!["Result".to_string()],
+                result_description: result.result_description.clone(),
+                value: result.value.clone(),
+                achieved_level: result.achieved_level.clone(),
+            }).collect(),
         }
     }
     
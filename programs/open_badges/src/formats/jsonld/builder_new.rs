@@ -229,7 +229,7 @@ impl JsonLdBuilder {
         signature[32..].copy_from_slice(&signer_pubkey.to_bytes());
         
         // Encode as multibase (base58btc with 'z' prefix)
-        let proof_value = format!("z{}", bs58::encode(&signature).into_string());
+        let proof_value = crate::multibase::encode(&signature);
         
         msg!("🔐 On-chain Ed25519 signature generated: {} bytes", signature.len());
         Ok(proof_value)
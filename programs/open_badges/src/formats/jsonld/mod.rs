@@ -97,6 +97,21 @@ pub struct JsonLdCredentialSubject {
     #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
     pub subject_type: Option<String>,
     pub achievement: JsonLdAchievement,
+    #[serde(rename = "result", skip_serializing_if = "Vec::is_empty")]
+    pub results: Vec<JsonLdResult>,
+}
+
+/// JSON-LD Result representation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonLdResult {
+    #[serde(rename = "type")]
+    pub result_type: Vec<String>,
+    #[serde(rename = "resultDescription")]
+    pub result_description: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    #[serde(rename = "achievedLevel", skip_serializing_if = "Option::is_none")]
+    pub achieved_level: Option<String>,
 }
 
 /// JSON-LD Achievement representation
@@ -144,7 +144,7 @@ impl JsonLdVerifier {
     
     /// Validate temporal constraints
     fn validate_temporal_constraints(&self, credential: &JsonLdCredential) -> Result<()> {
-        let current_time = self.get_current_timestamp();
+        let current_time = self.get_current_timestamp()?;
         
         // Parse validFrom timestamp
         let valid_from = self.parse_iso8601_timestamp(&credential.valid_from)?;
@@ -186,16 +186,11 @@ impl JsonLdVerifier {
         Ok(data.into_bytes())
     }
     
-    /// Decode multibase proof value (placeholder)
+    /// Decode a base58btc multibase proof value
     fn decode_proof_value(&self, proof_value: &str) -> Result<Vec<u8>> {
-        // Placeholder multibase decoding
-        // In a real implementation, this would decode the multibase string
-        if !proof_value.starts_with('z') {
-            return Err(error!(crate::common::errors::ValidationError::InvalidProofFormat));
-        }
-        
-        // Return placeholder signature
-        Ok(vec![0u8; 64]) // Ed25519 signature is 64 bytes
+        crate::multibase::decode_signature(proof_value)
+            .map(|signature| signature.to_vec())
+            .ok_or_else(|| error!(crate::common::errors::ValidationError::InvalidProofFormat))
     }
     
     /// Resolve verification method to get public key (placeholder)
@@ -219,22 +214,15 @@ impl JsonLdVerifier {
     
     /// Parse ISO 8601 timestamp to Unix timestamp
     fn parse_iso8601_timestamp(&self, timestamp: &str) -> Result<i64> {
-        // Placeholder timestamp parsing - would use chrono in real implementation
         if timestamp.is_empty() {
             return Err(error!(crate::common::errors::ValidationError::InvalidTimestamp));
         }
-        
-        // Return current timestamp as placeholder
-        Ok(self.get_current_timestamp())
+        crate::parse_iso8601_to_unix(timestamp)
     }
     
-    /// Get current Unix timestamp
-    fn get_current_timestamp(&self) -> i64 {
-        use std::time::{SystemTime, UNIX_EPOCH};
-        SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs() as i64
+    /// Get current Unix timestamp from the Clock sysvar
+    fn get_current_timestamp(&self) -> Result<i64> {
+        Ok(Clock::get()?.unix_timestamp)
     }
 }
 
@@ -109,9 +109,23 @@ impl JwtBuilder {
             id: subject.id.clone(),
             subject_type: subject.subject_type.get(0).cloned(),
             achievement: self.convert_achievement(&subject.achievement),
+            results: self.convert_results(&subject.result),
         }
     }
     
+    /// Convert credential results to JwtResult entries
+    fn convert_results(&self, results: &[crate::results::ResultInput]) -> Option<Vec<JwtResult>> {
+        if results.is_empty() {
+            return None;
+        }
+        Some(results.iter().map(|result| JwtResult {
+            result_type: vec!["Result".to_string()],
+            achieved_level: result.achieved_level.clone(),
+            result_description: Some(result.result_description.clone()),
+            value: result.value.clone(),
+        }).collect())
+    }
+    
     /// Convert Achievement to JwtAchievement
     fn convert_achievement(&self, achievement: &Achievement) -> JwtAchievement {
         JwtAchievement {
@@ -148,14 +162,8 @@ impl JwtBuilder {
     }
     
     /// Parse ISO 8601 timestamp to Unix timestamp
-    fn parse_timestamp(&self, _timestamp: &str) -> Result<i64> {
-        // Simplified timestamp parsing - would use chrono in real implementation
-        // For now, return current timestamp as placeholder
-        use std::time::{SystemTime, UNIX_EPOCH};
-        Ok(SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs() as i64)
+    fn parse_timestamp(&self, timestamp: &str) -> Result<i64> {
+        crate::parse_iso8601_to_unix(timestamp)
     }
     
     /// Sign JWT using Ed25519 (placeholder implementation)
@@ -100,7 +100,7 @@ impl JwtVerifier {
         }
         
         // Validate timestamps
-        let current_time = self.get_current_timestamp();
+        let current_time = self.get_current_timestamp()?;
         
         // Check not before
         if let Some(nbf) = payload.nbf {
@@ -176,13 +176,9 @@ impl JwtVerifier {
         Ok(())
     }
     
-    /// Get current Unix timestamp
-    fn get_current_timestamp(&self) -> i64 {
-        use std::time::{SystemTime, UNIX_EPOCH};
-        SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs() as i64
+    /// Get current Unix timestamp from the Clock sysvar
+    fn get_current_timestamp(&self) -> Result<i64> {
+        Ok(Clock::get()?.unix_timestamp)
     }
 
     /// Verify a JWT credential with on-chain validation
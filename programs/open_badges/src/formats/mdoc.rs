@@ -0,0 +1,326 @@
+//! Experimental ISO/IEC 18013-5 style mdoc export
+//!
+//! Serializes a credential into the mobile document structure used by
+//! mobile ID wallets: each data element becomes an `IssuerSignedItem`, the
+//! Mobile Security Object (MSO) commits to their SHA-256 digests, and the
+//! issuer signs the MSO as a COSE_Sign1 (`issuerAuth`).
+//!
+//! Signing happens off-chain: `MdocDraft::to_be_signed` gives the `Sig_structure`
+//! bytes for the issuer wallet to sign, and `MdocDraft::finish` attaches the
+//! Ed25519 signature once an Ed25519 program instruction in the same
+//! transaction has verified it. The issuer is identified by `kid` (its public key)
+//! rather than an X.509 `x5chain`, so wallets need a trust anchor for
+//! Solana keys before these documents verify end to end.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use super::cbor::{CborValue, TAG_DATE_TIME};
+use super::cose::{COSE_HEADER_ALG, COSE_HEADER_KID};
+pub use super::cose::{sig_structure, COSE_ALG_EDDSA};
+use crate::{Achievement, AchievementCredential, Profile};
+
+/// Document type for Open Badges mdocs
+pub const MDOC_DOC_TYPE: &str = "org.1edtech.openbadges.3";
+
+/// Namespace holding the badge data elements
+pub const MDOC_NAMESPACE: &str = "org.1edtech.openbadges.3";
+
+/// MSO version string
+pub const MSO_VERSION: &str = "1.0";
+
+/// Digest algorithm named in the MSO
+pub const MSO_DIGEST_ALGORITHM: &str = "SHA-256";
+
+/// `validUntil` used for credentials without an expiration date
+pub const OPEN_ENDED_VALID_UNTIL: &str = "9999-12-31T23:59:59Z";
+
+/// A named value in the badge namespace
+#[derive(Clone, Debug, PartialEq)]
+pub struct DataElement {
+    /// Element identifier (e.g. "achievement_name")
+    pub identifier: String,
+    /// Element value
+    pub value: CborValue,
+}
+
+impl DataElement {
+    fn text(identifier: &str, value: impl Into<String>) -> Self {
+        Self { identifier: identifier.to_string(), value: CborValue::text(value) }
+    }
+
+    fn date(identifier: &str, value: &str) -> Self {
+        Self {
+            identifier: identifier.to_string(),
+            value: CborValue::Tag(TAG_DATE_TIME, Box::new(CborValue::text(value))),
+        }
+    }
+}
+
+/// Map a credential and its achievement and issuer onto mdoc data elements
+pub fn credential_data_elements(
+    credential: &AchievementCredential,
+    achievement: &Achievement,
+    issuer: &Profile,
+) -> Vec<DataElement> {
+    let mut elements = vec![
+        DataElement::text("credential_id", credential.id.as_str()),
+        DataElement::text("achievement_id", achievement.id.as_str()),
+        DataElement::text("achievement_name", achievement.name.as_str()),
+        DataElement::text("achievement_description", achievement.description.as_str()),
+        DataElement::text("issuer_id", issuer.id.as_str()),
+        DataElement::text("issuer_name", issuer.name.as_str()),
+        DataElement::date("issuance_date", &credential.issued_at),
+        DataElement::date("valid_from", &credential.valid_from),
+    ];
+    if let Some(valid_until) = &credential.valid_until {
+        elements.push(DataElement::date("valid_until", valid_until));
+    }
+    if let Some(narrative) = &achievement.criteria.narrative {
+        elements.push(DataElement::text("criteria", narrative.as_str()));
+    }
+    if let Some(achievement_type) = &achievement.achievement_type {
+        elements.push(DataElement::text("achievement_type", achievement_type.as_str()));
+    }
+    if let Some(recipient_id) = &credential.credential_subject.id {
+        elements.push(DataElement::text("recipient_id", recipient_id.as_str()));
+    }
+    if let Some(image) = &achievement.image {
+        elements.push(DataElement::text("image_uri", image.as_str()));
+    }
+    elements
+}
+
+/// A data element with the salt and digest id the MSO refers to
+#[derive(Clone, Debug, PartialEq)]
+pub struct IssuerSignedItem {
+    /// Key of this item's digest in the MSO
+    pub digest_id: u32,
+    /// Salt that stops digests from revealing undisclosed values
+    pub random: [u8; 16],
+    /// The data element
+    pub element: DataElement,
+}
+
+impl IssuerSignedItem {
+    /// `IssuerSignedItem` map
+    pub fn to_cbor(&self) -> CborValue {
+        CborValue::Map(vec![
+            (CborValue::text("digestID"), CborValue::Unsigned(self.digest_id as u64)),
+            (CborValue::text("random"), CborValue::Bytes(self.random.to_vec())),
+            (CborValue::text("elementIdentifier"), CborValue::text(self.element.identifier.as_str())),
+            (CborValue::text("elementValue"), self.element.value.clone()),
+        ])
+    }
+
+    /// `IssuerSignedItemBytes`: the item wrapped as `#6.24(bstr)`
+    pub fn to_bytes(&self) -> Vec<u8> {
+        CborValue::encoded_cbor(self.to_cbor().encode()).encode()
+    }
+
+    /// SHA-256 over `IssuerSignedItemBytes`, as recorded in the MSO
+    pub fn digest(&self) -> [u8; 32] {
+        hashv(&[&self.to_bytes()]).to_bytes()
+    }
+}
+
+/// Build the issuer-signed items, deriving each salt from `salt_seed`
+///
+/// The seed must be secret and unique per credential, otherwise holders
+/// cannot withhold elements from a verifier.
+pub fn issuer_signed_items(elements: Vec<DataElement>, salt_seed: &[u8; 32]) -> Vec<IssuerSignedItem> {
+    elements
+        .into_iter()
+        .enumerate()
+        .map(|(index, element)| {
+            let digest_id = index as u32;
+            let hash = hashv(&[b"mdoc-random", salt_seed, &digest_id.to_le_bytes()]).to_bytes();
+            let mut random = [0u8; 16];
+            random.copy_from_slice(&hash[..16]);
+            IssuerSignedItem { digest_id, random, element }
+        })
+        .collect()
+}
+
+/// Ed25519 holder key as a COSE_Key (OKP, crv Ed25519)
+pub fn device_key(holder: &Pubkey) -> CborValue {
+    CborValue::Map(vec![
+        (CborValue::int(1), CborValue::int(1)),
+        (CborValue::int(-1), CborValue::int(6)),
+        (CborValue::int(-2), CborValue::Bytes(holder.to_bytes().to_vec())),
+    ])
+}
+
+/// Mobile Security Object committing to the issuer-signed items
+pub fn mobile_security_object(
+    items: &[IssuerSignedItem],
+    holder: &Pubkey,
+    credential: &AchievementCredential,
+) -> CborValue {
+    let digests = items
+        .iter()
+        .map(|item| (CborValue::Unsigned(item.digest_id as u64), CborValue::Bytes(item.digest().to_vec())))
+        .collect();
+    let tdate = |value: &str| CborValue::Tag(TAG_DATE_TIME, Box::new(CborValue::text(value)));
+    let valid_until = credential.valid_until.as_deref().unwrap_or(OPEN_ENDED_VALID_UNTIL);
+
+    CborValue::Map(vec![
+        (CborValue::text("version"), CborValue::text(MSO_VERSION)),
+        (CborValue::text("digestAlgorithm"), CborValue::text(MSO_DIGEST_ALGORITHM)),
+        (
+            CborValue::text("valueDigests"),
+            CborValue::Map(vec![(CborValue::text(MDOC_NAMESPACE), CborValue::Map(digests))]),
+        ),
+        (
+            CborValue::text("deviceKeyInfo"),
+            CborValue::Map(vec![(CborValue::text("deviceKey"), device_key(holder))]),
+        ),
+        (CborValue::text("docType"), CborValue::text(MDOC_DOC_TYPE)),
+        (
+            CborValue::text("validityInfo"),
+            CborValue::Map(vec![
+                (CborValue::text("signed"), tdate(&credential.issued_at)),
+                (CborValue::text("validFrom"), tdate(&credential.valid_from)),
+                (CborValue::text("validUntil"), tdate(valid_until)),
+            ]),
+        ),
+    ])
+}
+
+/// COSE protected header selecting EdDSA, as a serialized bstr payload
+pub fn protected_header() -> Vec<u8> {
+    CborValue::Map(vec![(CborValue::int(COSE_HEADER_ALG), CborValue::int(COSE_ALG_EDDSA))]).encode()
+}
+
+/// An mdoc waiting for the issuer's signature over its MSO
+#[derive(Clone, Debug)]
+pub struct MdocDraft {
+    /// Issuer-signed items in digest id order
+    pub items: Vec<IssuerSignedItem>,
+    /// `MobileSecurityObjectBytes` (MSO wrapped as `#6.24(bstr)`)
+    pub mso_bytes: Vec<u8>,
+    /// Serialized protected header
+    pub protected: Vec<u8>,
+}
+
+impl MdocDraft {
+    /// Bytes the issuer signs
+    pub fn to_be_signed(&self) -> Vec<u8> {
+        sig_structure(&self.protected, &self.mso_bytes)
+    }
+
+    /// Attach the issuer's signature and return the `IssuerSigned` structure
+    /// The signature must be verified by an Ed25519 instruction read from `instructions`
+    pub fn finish(&self, issuer_key: &Pubkey, signature: &[u8; 64], instructions: &AccountInfo) -> Result<Vec<u8>> {
+        crate::proof::ensure_ed25519_verified(instructions, issuer_key, &self.to_be_signed(), signature)?;
+
+        let issuer_auth = CborValue::Array(vec![
+            CborValue::Bytes(self.protected.clone()),
+            CborValue::Map(vec![(
+                CborValue::int(COSE_HEADER_KID),
+                CborValue::Bytes(issuer_key.to_bytes().to_vec()),
+            )]),
+            CborValue::Bytes(self.mso_bytes.clone()),
+            CborValue::Bytes(signature.to_vec()),
+        ]);
+        let name_space_items = self
+            .items
+            .iter()
+            .map(|item| CborValue::encoded_cbor(item.to_cbor().encode()))
+            .collect();
+
+        msg!("📱 MDOC_SIGNED: {} data elements", self.items.len());
+        Ok(CborValue::Map(vec![
+            (
+                CborValue::text("nameSpaces"),
+                CborValue::Map(vec![(CborValue::text(MDOC_NAMESPACE), CborValue::Array(name_space_items))]),
+            ),
+            (CborValue::text("issuerAuth"), issuer_auth),
+        ])
+        .encode())
+    }
+}
+
+/// Prepare an mdoc for a credential, bound to the holder's device key
+pub fn prepare_mdoc(
+    credential: &AchievementCredential,
+    achievement: &Achievement,
+    issuer: &Profile,
+    holder: &Pubkey,
+    salt_seed: &[u8; 32],
+) -> MdocDraft {
+    let items = issuer_signed_items(credential_data_elements(credential, achievement, issuer), salt_seed);
+    let mso = mobile_security_object(&items, holder, credential);
+    MdocDraft {
+        mso_bytes: CborValue::encoded_cbor(mso.encode()).encode(),
+        items,
+        protected: protected_header(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn element(identifier: &str, value: &str) -> DataElement {
+        DataElement::text(identifier, value)
+    }
+
+    #[test]
+    fn test_item_bytes_are_tag24_wrapped() {
+        let items = issuer_signed_items(vec![element("achievement_name", "Rustacean")], &[7u8; 32]);
+        let bytes = items[0].to_bytes();
+        // #6.24 followed by a byte string header
+        assert_eq!(&bytes[..2], &[0xd8, 0x18]);
+        assert_eq!(bytes[2] >> 5, 2);
+        assert_eq!(items[0].digest(), hashv(&[&bytes]).to_bytes());
+    }
+
+    #[test]
+    fn test_salts_differ_per_element_and_seed() {
+        let elements = vec![element("a", "x"), element("b", "x")];
+        let items = issuer_signed_items(elements.clone(), &[1u8; 32]);
+        assert_ne!(items[0].random, items[1].random);
+        let reseeded = issuer_signed_items(elements, &[2u8; 32]);
+        assert_ne!(items[0].random, reseeded[0].random);
+        assert_eq!(items[1].digest_id, 1);
+    }
+
+    #[test]
+    fn test_sig_structure_layout() {
+        let protected = protected_header();
+        // {1: -8}
+        assert_eq!(protected, vec![0xa1, 0x01, 0x27]);
+        let tbs = sig_structure(&protected, &[0xaa]);
+        let mut expected = vec![0x84, 0x6a];
+        expected.extend_from_slice(b"Signature1");
+        expected.extend_from_slice(&[0x43, 0xa1, 0x01, 0x27, 0x40, 0x41, 0xaa]);
+        assert_eq!(tbs, expected);
+    }
+
+    #[test]
+    fn test_device_key_is_okp_ed25519() {
+        let holder = Pubkey::new_from_array([9u8; 32]);
+        let encoded = device_key(&holder).encode();
+        assert_eq!(&encoded[..7], &[0xa3, 0x01, 0x01, 0x20, 0x06, 0x21, 0x58]);
+        assert_eq!(encoded[7], 32);
+        assert_eq!(&encoded[8..], &[9u8; 32]);
+    }
+
+    #[test]
+    fn test_finish_requires_an_ed25519_instruction_over_the_mso() {
+        use crate::test_utils::{ed25519_verify_instruction, with_instructions_sysvar, TestKeypair};
+
+        let issuer = TestKeypair::from_seed(3);
+        let draft = MdocDraft {
+            items: issuer_signed_items(vec![element("achievement_name", "Rustacean")], &[7u8; 32]),
+            mso_bytes: CborValue::encoded_cbor(vec![0xa0]).encode(),
+            protected: protected_header(),
+        };
+        let signature = issuer.sign(&draft.to_be_signed());
+        let verified = [ed25519_verify_instruction(&issuer, &draft.to_be_signed())];
+
+        assert!(with_instructions_sysvar(&verified, |ix| draft.finish(&issuer.pubkey(), &signature, ix)).is_ok());
+        assert!(with_instructions_sysvar(&verified, |ix| draft.finish(&issuer.pubkey(), &[7u8; 64], ix)).is_err());
+        assert!(with_instructions_sysvar(&[], |ix| draft.finish(&issuer.pubkey(), &signature, ix)).is_err());
+    }
+}
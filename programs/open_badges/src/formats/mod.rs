@@ -1,7 +1,19 @@
 //! Proof format implementations for Open Badges 3.0
 //! 
 //! This module provides format-specific implementations for serializing
-//! UnifiedCredential to different proof formats (JWT, JSON-LD).
+//! UnifiedCredential to different proof formats (JWT, JSON-LD, COSE),
+//! SD-JWT with selective disclosure, baked PNG and SVG badge images, and an
+//! experimental mdoc export built on a minimal CBOR encoder.
+
+pub mod baked;
+
+pub mod cbor;
+
+pub mod cose;
+
+pub mod mdoc;
+
+pub mod sd_jwt;
 
 #[cfg(feature = "jwt")]
 pub mod jwt;
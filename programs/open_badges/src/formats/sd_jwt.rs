@@ -0,0 +1,349 @@
+//! SD-JWT VC export with selective disclosure
+//!
+//! Credentials are issued as SD-JWTs (IETF SD-JWT, `vc+sd-jwt`): claims a
+//! learner may want to withhold - the recipient's identity, graded results
+//! and evidence - are replaced in the signed payload by the SHA-256 digests
+//! of salted disclosures, listed in `_sd` arrays. A holder presents the JWT
+//! with only the disclosures they choose, and a verifier checks each one
+//! against the signed digests.
+//!
+//! Signing happens off-chain: `prepare_sd_jwt` gives the JWS signing input
+//! and the disclosures, the issuer authority signs the input, and
+//! `compose_sd_jwt` assembles `<jwt>~<disclosure>~...~`. The salt seed must
+//! stay secret, so the preparing instruction is meant to be simulated rather
+//! than sent.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use base64::{Engine, engine::general_purpose};
+use crate::common::errors::ValidationError;
+use crate::credential::JwtVerificationContext;
+use crate::rubric::Rubric;
+use crate::{Achievement, AchievementCredential};
+
+/// JOSE `typ` of an SD-JWT VC
+pub const SD_JWT_TYPE: &str = "vc+sd-jwt";
+
+/// Digest algorithm named by `_sd_alg`
+pub const SD_ALG: &str = "sha-256";
+
+/// Maximum evidence entries in one SD-JWT
+pub const MAX_EVIDENCE: usize = 4;
+
+/// Maximum length of an evidence URL
+pub const MAX_EVIDENCE_URL_LEN: usize = 200;
+
+/// Credential subject claims that are issued as disclosures
+pub const DISCLOSABLE_SUBJECT_CLAIMS: &[&str] = &["id", "identifier", "result"];
+
+/// One salted claim a holder may reveal
+#[derive(Clone, Debug, PartialEq)]
+pub struct Disclosure {
+    /// Base64url salt that keeps the digest from revealing the value
+    pub salt: String,
+    /// Claim name
+    pub claim: String,
+    /// Claim value
+    pub value: serde_json::Value,
+}
+
+impl Disclosure {
+    /// Base64url of the JSON array `[salt, claim, value]`
+    pub fn encode(&self) -> String {
+        let array = serde_json::json!([self.salt, self.claim, self.value]);
+        general_purpose::URL_SAFE_NO_PAD.encode(array.to_string())
+    }
+
+    /// Parse an encoded disclosure
+    pub fn decode(encoded: &str) -> Result<Self> {
+        let bytes = general_purpose::URL_SAFE_NO_PAD.decode(encoded)
+            .map_err(|_| error!(ValidationError::InvalidJwtFormat))?;
+        let array: Vec<serde_json::Value> = serde_json::from_slice(&bytes)
+            .map_err(|_| error!(ValidationError::InvalidJwtFormat))?;
+        let [salt, claim, value] = <[serde_json::Value; 3]>::try_from(array)
+            .map_err(|_| error!(ValidationError::InvalidJwtFormat))?;
+        match (salt, claim) {
+            (serde_json::Value::String(salt), serde_json::Value::String(claim)) => Ok(Self { salt, claim, value }),
+            _ => Err(error!(ValidationError::InvalidJwtFormat)),
+        }
+    }
+}
+
+/// `_sd` digest of an encoded disclosure: base64url SHA-256 over its ASCII form
+pub fn disclosure_digest(encoded: &str) -> String {
+    general_purpose::URL_SAFE_NO_PAD.encode(hashv(&[encoded.as_bytes()]).to_bytes())
+}
+
+/// Salt of the disclosure at `index`, derived from the issuer's secret seed
+pub fn disclosure_salt(salt_seed: &[u8; 32], index: u32) -> String {
+    let hash = hashv(&[b"sd-jwt-salt", salt_seed, &index.to_le_bytes()]).to_bytes();
+    general_purpose::URL_SAFE_NO_PAD.encode(&hash[..16])
+}
+
+/// Check evidence URLs before they are issued as a disclosure
+pub fn validate_evidence(evidence: &[String]) -> Result<()> {
+    if evidence.len() > MAX_EVIDENCE {
+        return Err(error!(ValidationError::TooManyItems));
+    }
+    for url in evidence {
+        if url.len() > MAX_EVIDENCE_URL_LEN {
+            return Err(error!(ValidationError::UrlTooLong));
+        }
+        if url.strip_prefix("https://").unwrap_or_default().is_empty() {
+            msg!("❌ Evidence must be an https URL: {}", url);
+            return Err(error!(ValidationError::InvalidUrl));
+        }
+    }
+    Ok(())
+}
+
+/// A signing input waiting for the issuer's signature, with its disclosures
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct SdJwtDraft {
+    /// JWS signing input (`header.payload`) the issuer authority signs
+    pub signing_input: String,
+    /// Encoded disclosures, subject claims first
+    pub disclosures: Vec<String>,
+}
+
+/// Move `claims` out of `node` into salted disclosures listed in `node._sd`
+fn conceal(node: &mut serde_json::Value, claims: &[&str], salt_seed: &[u8; 32], disclosures: &mut Vec<String>) {
+    let mut digests = Vec::new();
+    for claim in claims {
+        let Some(value) = node.as_object_mut().and_then(|object| object.remove(*claim)) else {
+            continue;
+        };
+        let disclosure = Disclosure {
+            salt: disclosure_salt(salt_seed, disclosures.len() as u32),
+            claim: claim.to_string(),
+            value,
+        };
+        let encoded = disclosure.encode();
+        digests.push(disclosure_digest(&encoded));
+        disclosures.push(encoded);
+    }
+    if !digests.is_empty() {
+        // Sorted so the digest order does not reveal which claim is which
+        digests.sort();
+        node["_sd"] = serde_json::json!(digests);
+    }
+}
+
+/// Prepare an SD-JWT for a credential account
+#[allow(clippy::too_many_arguments)]
+pub fn prepare_sd_jwt(
+    issuer_key: &Pubkey,
+    signer: &Pubkey,
+    credential: &AchievementCredential,
+    achievement: &Achievement,
+    rubric: Option<&Rubric>,
+    evidence: &[String],
+    salt_seed: &[u8; 32],
+    issued_at: i64,
+) -> Result<SdJwtDraft> {
+    validate_evidence(evidence)?;
+    let issuer_did = format!("did:sol:{}", issuer_key);
+
+    let mut vc = crate::credential::issued_credential_document(credential, achievement, rubric);
+    if !evidence.is_empty() {
+        let items: Vec<serde_json::Value> = evidence
+            .iter()
+            .map(|url| serde_json::json!({ "id": url, "type": ["Evidence"] }))
+            .collect();
+        vc["evidence"] = serde_json::json!(items);
+    }
+
+    let mut disclosures = Vec::new();
+    conceal(&mut vc["credentialSubject"], DISCLOSABLE_SUBJECT_CLAIMS, salt_seed, &mut disclosures);
+    conceal(&mut vc, &["evidence"], salt_seed, &mut disclosures);
+
+    // No `sub`: the recipient is only revealed through its disclosure
+    let header = serde_json::json!({
+        "alg": "EdDSA",
+        "typ": SD_JWT_TYPE,
+        "kid": format!("did:sol:{}", signer)
+    });
+    let mut payload = serde_json::json!({
+        "iss": issuer_did,
+        "iat": issued_at,
+        "jti": credential.id,
+        "_sd_alg": SD_ALG,
+        "vc": vc
+    });
+    if let Some(holder) = &credential.holder_binding {
+        payload["cnf"] = crate::holder_binding::cnf_claim(holder);
+    }
+
+    let header_json = serde_json::to_string(&header)
+        .map_err(|_| error!(ValidationError::SerializationError))?;
+    let payload_json = serde_json::to_string(&payload)
+        .map_err(|_| error!(ValidationError::SerializationError))?;
+    Ok(SdJwtDraft {
+        signing_input: format!(
+            "{}.{}",
+            general_purpose::URL_SAFE_NO_PAD.encode(header_json),
+            general_purpose::URL_SAFE_NO_PAD.encode(payload_json)
+        ),
+        disclosures,
+    })
+}
+
+/// Assemble an SD-JWT from its signed input and the disclosures to present
+pub fn compose_sd_jwt(signing_input: &str, signature: &[u8; 64], disclosures: &[String]) -> String {
+    let mut sd_jwt = crate::credential::compact_jwt(signing_input, signature);
+    sd_jwt.push('~');
+    for disclosure in disclosures {
+        sd_jwt.push_str(disclosure);
+        sd_jwt.push('~');
+    }
+    sd_jwt
+}
+
+/// Split `<jwt>~<disclosure>~...~[<kb-jwt>]` into its parts
+pub fn split_presentation(presentation: &str) -> Result<(&str, Vec<&str>, Option<&str>)> {
+    let mut parts: Vec<&str> = presentation.trim().split('~').collect();
+    if parts.len() < 2 {
+        return Err(error!(ValidationError::InvalidJwtFormat));
+    }
+    let jwt = parts.remove(0);
+    let key_binding = parts.pop().filter(|kb| !kb.is_empty());
+    if parts.iter().any(|disclosure| disclosure.is_empty()) {
+        return Err(error!(ValidationError::InvalidJwtFormat));
+    }
+    Ok((jwt, parts, key_binding))
+}
+
+/// `_sd` digests of the payload's credential and credential subject
+fn signed_digests(payload: &serde_json::Value) -> Vec<&str> {
+    let vc = &payload["vc"];
+    [&vc["_sd"], &vc["credentialSubject"]["_sd"]]
+        .into_iter()
+        .filter_map(serde_json::Value::as_array)
+        .flatten()
+        .filter_map(serde_json::Value::as_str)
+        .collect()
+}
+
+/// Decode presented disclosures, each of which must match a distinct signed digest
+pub fn disclosed_claims(payload: &serde_json::Value, disclosures: &[&str]) -> Result<Vec<Disclosure>> {
+    if payload.get("_sd_alg").and_then(|alg| alg.as_str()).unwrap_or(SD_ALG) != SD_ALG {
+        return Err(error!(ValidationError::UnsupportedFormat));
+    }
+    let digests = signed_digests(payload);
+    let mut seen: Vec<String> = Vec::new();
+    let mut claims = Vec::new();
+    for encoded in disclosures {
+        let digest = disclosure_digest(encoded);
+        if !digests.contains(&digest.as_str()) || seen.contains(&digest) {
+            msg!("❌ Disclosure does not match a signed digest");
+            return Err(error!(ValidationError::ValidationFailed));
+        }
+        seen.push(digest);
+        claims.push(Disclosure::decode(encoded)?);
+    }
+    Ok(claims)
+}
+
+/// Verify an SD-JWT presentation: the issuer-signed JWT and every presented disclosure
+pub fn verify_sd_jwt_presentation(presentation: &str, context: &JwtVerificationContext) -> Result<bool> {
+    let (jwt, disclosures, key_binding) = split_presentation(presentation)?;
+    if key_binding.is_some() {
+        msg!("❌ Key binding JWTs are not supported");
+        return Ok(false);
+    }
+    let decoded = crate::credential::decode_jwt(jwt)?;
+    if decoded.header.get("typ").and_then(|typ| typ.as_str()) != Some(SD_JWT_TYPE) {
+        return Ok(false);
+    }
+    let claims = match disclosed_claims(&decoded.payload, &disclosures) {
+        Ok(claims) => claims,
+        Err(_) => return Ok(false),
+    };
+    if !crate::credential::verify_jwt_credential(jwt, context)? {
+        return Ok(false);
+    }
+
+    let names: Vec<&str> = claims.iter().map(|claim| claim.claim.as_str()).collect();
+    msg!("✅ SD-JWT verified, disclosed claims: {:?}", names);
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{fixture_achievement, fixture_credential, TestKeypair};
+
+    fn draft(evidence: &[String]) -> SdJwtDraft {
+        let issuer = Pubkey::new_unique();
+        let achievement_key = Pubkey::new_unique();
+        let credential = fixture_credential(&Pubkey::new_unique(), &issuer, &achievement_key, &Pubkey::new_unique());
+        let achievement = fixture_achievement(&achievement_key, &issuer, "Rust");
+        prepare_sd_jwt(
+            &issuer,
+            &TestKeypair::from_seed(5).pubkey(),
+            &credential,
+            &achievement,
+            None,
+            evidence,
+            &[3u8; 32],
+            1_700_000_000,
+        )
+        .unwrap()
+    }
+
+    fn payload(draft: &SdJwtDraft) -> serde_json::Value {
+        let payload_b64 = draft.signing_input.split('.').nth(1).unwrap();
+        serde_json::from_slice(&general_purpose::URL_SAFE_NO_PAD.decode(payload_b64).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_disclosable_claims_are_concealed() {
+        let draft = draft(&["https://example.org/portfolio".to_string()]);
+        let payload = payload(&draft);
+        let subject = &payload["vc"]["credentialSubject"];
+
+        assert!(payload.get("sub").is_none());
+        assert!(subject.get("id").is_none());
+        assert!(subject.get("identifier").is_none());
+        assert!(payload["vc"].get("evidence").is_none());
+        assert_eq!(subject["_sd"].as_array().unwrap().len(), 2);
+        assert_eq!(payload["vc"]["_sd"].as_array().unwrap().len(), 1);
+        assert_eq!(draft.disclosures.len(), 3);
+        assert_eq!(subject["achievement"]["name"], "Rust");
+    }
+
+    #[test]
+    fn test_disclosures_match_signed_digests() {
+        let draft = draft(&[]);
+        let payload = payload(&draft);
+        let presented = vec![draft.disclosures[0].as_str()];
+        let claims = disclosed_claims(&payload, &presented).unwrap();
+        assert_eq!(claims[0].claim, "id");
+        assert!(claims[0].value.as_str().unwrap().starts_with("did:sol:"));
+
+        // Repeated or forged disclosures are rejected
+        assert!(disclosed_claims(&payload, &[presented[0], presented[0]]).is_err());
+        let forged = Disclosure { salt: "AAAA".to_string(), claim: "id".to_string(), value: serde_json::json!("did:sol:x") };
+        assert!(disclosed_claims(&payload, &[forged.encode().as_str()]).is_err());
+    }
+
+    #[test]
+    fn test_presentation_round_trips() {
+        let draft = draft(&[]);
+        let sd_jwt = compose_sd_jwt(&draft.signing_input, &[0u8; 64], &draft.disclosures[..1]);
+        let (jwt, disclosures, key_binding) = split_presentation(&sd_jwt).unwrap();
+        assert_eq!(jwt.split('.').count(), 3);
+        assert_eq!(disclosures, vec![draft.disclosures[0].as_str()]);
+        assert!(key_binding.is_none());
+
+        let bare = compose_sd_jwt(&draft.signing_input, &[0u8; 64], &[]);
+        assert!(split_presentation(&bare).unwrap().1.is_empty());
+        assert!(split_presentation("a.b.c").is_err());
+    }
+
+    #[test]
+    fn test_evidence_is_bounded() {
+        assert!(validate_evidence(&["http://example.org".to_string()]).is_err());
+        assert!(validate_evidence(&vec!["https://example.org".to_string(); MAX_EVIDENCE + 1]).is_err());
+    }
+}
@@ -0,0 +1,97 @@
+//! Holder Binding
+//!
+//! An issuer can bind a credential to the recipient's wallet key. The bound
+//! key is published as a Multikey in the credential's `holder` property (and
+//! as the `cnf` claim of JWTs), and `verify_presentation` only accepts a
+//! bound credential in a presentation signed by that key, the signature
+//! verified by an Ed25519 program instruction. The binding is part of the
+//! signing JSON, so the issuer's proof breaks if it is stripped. A copied
+//! credential document is then useless to anyone without the recipient's key.
+
+use anchor_lang::prelude::*;
+use crate::common::errors::ValidationError;
+
+/// Serialized size of the optional bound key
+pub const SPACE: usize = 1 + 32;
+
+/// `publicKeyMultibase` of the holder's Ed25519 key (base58btc)
+pub fn public_key_multibase(holder: &Pubkey) -> String {
+    crate::multibase::encode_ed25519_multikey(holder)
+}
+
+/// did:key verification method of the holder's key
+pub fn verification_method(holder: &Pubkey) -> String {
+    let multibase = public_key_multibase(holder);
+    format!("did:key:{}#{}", multibase, multibase)
+}
+
+/// `holder` property of a bound credential
+pub fn holder_json(holder: &Pubkey) -> serde_json::Value {
+    serde_json::json!({
+        "id": verification_method(holder),
+        "type": "Multikey",
+        "controller": format!("did:sol:{}", holder),
+        "publicKeyMultibase": public_key_multibase(holder),
+    })
+}
+
+/// `cnf` claim (RFC 7800) of a bound JWT credential
+pub fn cnf_claim(holder: &Pubkey) -> serde_json::Value {
+    serde_json::json!({ "kid": verification_method(holder) })
+}
+
+/// Fail unless a bound credential is presented by its bound key
+/// The presentation's `signature` over `message` must be verified by an
+/// earlier Ed25519 program instruction in this transaction
+pub fn ensure_presented_by_holder(
+    binding: Option<&Pubkey>,
+    presenter: &Pubkey,
+    instructions: &AccountInfo,
+    message: &[u8],
+    signature: &[u8; 64],
+) -> Result<()> {
+    match binding {
+        Some(bound) if bound != presenter => {
+            msg!("❌ Credential is bound to {}, presented by {}", bound, presenter);
+            Err(error!(ValidationError::HolderBindingMismatch))
+        }
+        Some(bound) => crate::proof::ensure_ed25519_verified(instructions, bound, message, signature),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TestKeypair;
+
+    #[test]
+    fn test_binding_names_the_holder_did_key() {
+        let holder = TestKeypair::from_seed(11).pubkey();
+        let method = verification_method(&holder);
+        assert!(method.starts_with("did:key:z6Mk"));
+        #[cfg(feature = "did-key")]
+        assert_eq!(crate::did::DidResolver::new().resolve_assertion_key(&method).unwrap(), holder.to_bytes().to_vec());
+        assert_eq!(holder_json(&holder)["publicKeyMultibase"], public_key_multibase(&holder));
+        assert_eq!(cnf_claim(&holder)["kid"], method);
+    }
+
+    #[test]
+    fn test_only_the_bound_key_presents() {
+        let holder = TestKeypair::from_seed(11);
+        let message = b"presentation";
+        let signature = holder.sign(message);
+        let verified = [crate::test_utils::ed25519_verify_instruction(&holder, message)];
+        crate::test_utils::with_instructions_sysvar(&verified, |instructions| {
+            let bound = Some(&holder.pubkey());
+            assert!(ensure_presented_by_holder(bound, &holder.pubkey(), instructions, message, &signature).is_ok());
+            assert!(ensure_presented_by_holder(bound, &Pubkey::new_unique(), instructions, message, &signature).is_err());
+            assert!(ensure_presented_by_holder(bound, &holder.pubkey(), instructions, b"other", &signature).is_err());
+            assert!(ensure_presented_by_holder(None, &Pubkey::new_unique(), instructions, message, &signature).is_ok());
+        });
+        // Without the Ed25519 instruction the presenter's signature proves nothing
+        crate::test_utils::with_instructions_sysvar(&[], |instructions| {
+            assert!(ensure_presented_by_holder(Some(&holder.pubkey()), &holder.pubkey(), instructions, message, &signature).is_err());
+        });
+    }
+}
@@ -0,0 +1,207 @@
+//! Language-Tagged Names and Descriptions
+//!
+//! An achievement or profile may carry translations of its `name` and
+//! `description` alongside the base fields, each tagged with a BCP 47
+//! language tag. The base fields remain the defaults every consumer reads;
+//! the translations appear in JSON documents as JSON-LD language maps
+//! (`nameMap`, `descriptionMap`), with an embedded context term binding
+//! each map to the same schema.org property as the base field.
+
+use anchor_lang::prelude::*;
+use crate::common::errors::ValidationError;
+
+/// Maximum translations of one field
+pub const MAX_TRANSLATIONS: usize = 8;
+
+/// Maximum length of a language tag, as recommended by BCP 47 § 4.4.1
+pub const MAX_LANGUAGE_TAG_LEN: usize = 35;
+
+/// Maximum length of a translated text
+pub const MAX_TRANSLATION_LEN: usize = 500;
+
+/// Serialized size of an empty list of translations
+pub const EMPTY_SPACE: usize = 4;
+
+/// One translation of a field
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct LocalizedText {
+    /// BCP 47 language tag, e.g. `pt-BR`
+    pub language: String,
+    /// Text in that language
+    pub text: String,
+}
+
+impl LocalizedText {
+    pub fn new(language: &str, text: &str) -> Self {
+        Self { language: language.to_string(), text: text.to_string() }
+    }
+}
+
+fn is_alpha(subtag: &str, lengths: std::ops::RangeInclusive<usize>) -> bool {
+    lengths.contains(&subtag.len()) && subtag.bytes().all(|byte| byte.is_ascii_alphabetic())
+}
+
+fn is_digits(subtag: &str, length: usize) -> bool {
+    subtag.len() == length && subtag.bytes().all(|byte| byte.is_ascii_digit())
+}
+
+fn is_variant(subtag: &str) -> bool {
+    (5..=8).contains(&subtag.len()) || (subtag.len() == 4 && subtag.as_bytes()[0].is_ascii_digit())
+}
+
+/// Whether `tag` is a well-formed BCP 47 language tag
+///
+/// Checks the `langtag` and `privateuse` productions of RFC 5646 § 2.1:
+/// language, extended language, script, region, variant, extension and
+/// private-use subtags in order. Subtags are not checked against the IANA
+/// registry, and irregular grandfathered tags are not accepted.
+pub fn is_valid_language_tag(tag: &str) -> bool {
+    if tag.is_empty() || tag.len() > MAX_LANGUAGE_TAG_LEN {
+        return false;
+    }
+    let subtags: Vec<&str> = tag.split('-').collect();
+    if subtags.iter().any(|subtag| subtag.is_empty() || subtag.len() > 8 || !subtag.bytes().all(|byte| byte.is_ascii_alphanumeric())) {
+        return false;
+    }
+    let private_use = |rest: &[&str]| !rest.is_empty();
+    if subtags[0].eq_ignore_ascii_case("x") {
+        return private_use(&subtags[1..]);
+    }
+
+    let mut position = 0;
+    let language = subtags[0];
+    if is_alpha(language, 2..=3) {
+        position += 1;
+        let mut extlangs = 0;
+        while extlangs < 3 && subtags.get(position).is_some_and(|subtag| is_alpha(subtag, 3..=3)) {
+            position += 1;
+            extlangs += 1;
+        }
+    } else if is_alpha(language, 5..=8) {
+        position += 1;
+    } else {
+        return false;
+    }
+    if subtags.get(position).is_some_and(|subtag| is_alpha(subtag, 4..=4)) {
+        position += 1;
+    }
+    if subtags.get(position).is_some_and(|subtag| is_alpha(subtag, 2..=2) || is_digits(subtag, 3)) {
+        position += 1;
+    }
+    while subtags.get(position).is_some_and(|subtag| is_variant(subtag)) {
+        position += 1;
+    }
+    while let Some(singleton) = subtags.get(position).filter(|subtag| subtag.len() == 1) {
+        if singleton.eq_ignore_ascii_case("x") {
+            return private_use(&subtags[position + 1..]);
+        }
+        let extension = subtags[position + 1..].iter().take_while(|subtag| subtag.len() >= 2).count();
+        if extension == 0 {
+            return false;
+        }
+        position += 1 + extension;
+    }
+    position == subtags.len()
+}
+
+/// Check a field's translations: bounded, well-tagged, non-empty and one per language
+pub fn validate(translations: &[LocalizedText]) -> Result<()> {
+    if translations.len() > MAX_TRANSLATIONS {
+        return Err(error!(ValidationError::TooManyItems));
+    }
+    for (index, translation) in translations.iter().enumerate() {
+        if translation.text.len() > MAX_TRANSLATION_LEN {
+            return Err(error!(ValidationError::FieldTooLong));
+        }
+        let repeated = translations[..index]
+            .iter()
+            .any(|earlier| earlier.language.eq_ignore_ascii_case(&translation.language));
+        if repeated || translation.text.trim().is_empty() || !is_valid_language_tag(&translation.language) {
+            msg!("❌ Invalid translation: {:?}", translation);
+            return Err(error!(ValidationError::InvalidTranslation));
+        }
+    }
+    Ok(())
+}
+
+/// Serialized size of a list of translations
+pub fn space(translations: &[LocalizedText]) -> usize {
+    EMPTY_SPACE + translations.iter().map(|translation| 4 + translation.language.len() + 4 + translation.text.len()).sum::<usize>()
+}
+
+/// Add the `<property>Map` language map of `translations` to a document, when there are any
+///
+/// The map's context term is merged into the document's own `@context`, or
+/// embedded as one when the document is a nested object without a context.
+pub fn apply_to_json(document: &mut serde_json::Value, property: &str, translations: &[LocalizedText]) {
+    if translations.is_empty() {
+        return;
+    }
+    let term = format!("{}Map", property);
+    let map: serde_json::Map<String, serde_json::Value> = translations
+        .iter()
+        .map(|translation| (translation.language.clone(), serde_json::json!(translation.text)))
+        .collect();
+    document[term.as_str()] = serde_json::Value::Object(map);
+
+    let definition = serde_json::json!({ "@id": format!("https://schema.org/{}", property), "@container": "@language" });
+    let context = &mut document["@context"];
+    if let Some(terms) = context.as_array_mut().and_then(|entries| entries.iter_mut().find_map(|entry| entry.as_object_mut())) {
+        terms.insert(term, definition);
+    } else if let Some(terms) = context.as_object_mut() {
+        terms.insert(term, definition);
+    } else if let Some(entries) = context.as_array_mut() {
+        entries.push(serde_json::json!({ term: definition }));
+    } else if context.is_null() {
+        *context = serde_json::json!({ term: definition });
+    } else {
+        *context = serde_json::json!([context.take(), { term: definition }]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_language_tags_are_well_formed() {
+        for tag in ["en", "pt-BR", "zh-Hant-TW", "sr-Latn-RS", "es-419", "de-CH-1996", "zh-yue-HK", "en-a-bbb-x-ccc", "x-klingon"] {
+            assert!(is_valid_language_tag(tag), "{}", tag);
+        }
+        for tag in ["", "e", "en_US", "en-", "-en", "toolongtag", "en-a", "en-x", "123", &format!("en-{}", "x".repeat(40))] {
+            assert!(!is_valid_language_tag(tag), "{}", tag);
+        }
+    }
+
+    #[test]
+    fn test_translations_are_validated() {
+        let translations = vec![LocalizedText::new("pt-BR", "Emblema"), LocalizedText::new("es", "Insignia")];
+        assert!(validate(&translations).is_ok());
+        assert_eq!(translations.try_to_vec().unwrap().len(), space(&translations));
+        assert!(validate(&[LocalizedText::new("pt-BR", "Emblema"), LocalizedText::new("PT-br", "Insígnia")]).is_err());
+        assert!(validate(&[LocalizedText::new("en_US", "Badge")]).is_err());
+        assert!(validate(&[LocalizedText::new("en", " ")]).is_err());
+        assert!(validate(&vec![LocalizedText::new("en", "Badge"); MAX_TRANSLATIONS + 1]).is_err());
+    }
+
+    #[test]
+    fn test_language_maps_extend_the_context() {
+        let translations = vec![LocalizedText::new("pt-BR", "Emblema")];
+        let mut nested = serde_json::json!({ "name": "Badge" });
+        apply_to_json(&mut nested, "name", &translations);
+        assert_eq!(nested["nameMap"]["pt-BR"], "Emblema");
+        assert_eq!(nested["@context"]["nameMap"]["@container"], "@language");
+
+        let mut document = serde_json::json!({ "@context": "https://www.w3.org/ns/credentials/v2", "name": "Badge" });
+        apply_to_json(&mut document, "name", &translations);
+        apply_to_json(&mut document, "description", &translations);
+        assert_eq!(document["@context"][0], "https://www.w3.org/ns/credentials/v2");
+        assert_eq!(document["@context"][1]["descriptionMap"]["@id"], "https://schema.org/description");
+        assert_eq!(document["@context"][1]["nameMap"]["@id"], "https://schema.org/name");
+        assert_eq!(document["name"], "Badge");
+
+        let mut untouched = serde_json::json!({ "name": "Badge" });
+        apply_to_json(&mut untouched, "name", &[]);
+        assert_eq!(untouched, serde_json::json!({ "name": "Badge" }));
+    }
+}
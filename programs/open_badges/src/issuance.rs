@@ -0,0 +1,320 @@
+//! Writing an Issued Credential
+//!
+//! Every instruction that issues one credential to one recipient shares the
+//! same steps. `check` runs the gates ahead of the write: pause, rate limit
+//! and fee, the CPI guard, the recipient's opt-out and the results the
+//! achievement requires. `populate_credential` writes the credential and
+//! everything an issuance updates alongside it: the issuer's sequence, the
+//! recipient's index, the achievement's roster, terms of use and statistics.
+//! `emit_issued` announces the result.
+//!
+//! Signed issuance hands `populate_credential` the issuer's signature, which
+//! must cover the credential's signing JSON and becomes its proof. The CPI,
+//! peer, claim code and delegated paths pass none: the path's own
+//! authorization (a signed CPI, the endorsement PDAs, the claim key, a scoped
+//! grant) stands in for it.
+
+use anchor_lang::prelude::*;
+use crate::assessment::{self, ResultEntry};
+use crate::canonical::{CREDENTIAL_CONTEXT, CREDENTIAL_TYPE, SUBJECT_TYPE};
+use crate::common::errors::ValidationError;
+use crate::cpi_guard::{self, CpiPolicy};
+use crate::credential_index::{self, CredentialIndex, CredentialIndexPage};
+use crate::recipient_roster::{self, RecipientRoster, RecipientRosterPage, RosterEntry};
+use crate::rubric::Rubric;
+use crate::schema::{self, CredentialSchema, SchemaRegistry};
+use crate::stats::{AchievementStats, IssuerStats};
+use crate::terms_of_use::{self, TermsOfUse};
+use crate::{
+    args, criteria, events, lifecycle, multibase, narrative, opt_out, program_config, proof, rate_limit, results,
+    strictness, validation, Achievement, AchievementCredential, AchievementSubject, IdentityObject, Profile, Proof,
+};
+
+/// Accounts the checks ahead of an issuance read, borrowed from the instruction's context
+pub struct CheckAccounts<'a, 'info> {
+    /// Program config PDA
+    pub program_config: &'a AccountInfo<'info>,
+    /// Program treasury receiving the fee
+    pub treasury: &'a AccountInfo<'info>,
+    /// Payer's fee token account, treasury token account and token program
+    pub fee_token_accounts: [Option<&'a UncheckedAccount<'info>>; 3],
+    /// Pays the fee
+    pub payer: &'a AccountInfo<'info>,
+    /// System program
+    pub system_program: &'a AccountInfo<'info>,
+    /// Issuer's CPI policy, if any
+    pub cpi_policy: Option<&'a CpiPolicy>,
+    /// Instructions sysvar, naming the top-level program of a CPI
+    pub instructions: Option<&'a AccountInfo<'info>>,
+    /// Recipient's opt-out PDA
+    pub recipient_opt_out: &'a AccountInfo<'info>,
+    /// Moderated assessment result backing the credential, if any
+    pub result_entry: Option<&'a ResultEntry>,
+    /// Rubric the results are graded against, if any
+    pub rubric: Option<&'a Rubric>,
+}
+
+/// Accounts an issuance writes, borrowed from the instruction's context
+pub struct IssuanceAccounts<'a, 'info> {
+    /// Credential being initialized
+    pub credential: &'a mut Account<'info, AchievementCredential>,
+    /// Issuer profile
+    pub issuer: &'a Account<'info, Profile>,
+    /// Achievement the credential awards
+    pub achievement: &'a Account<'info, Achievement>,
+    /// Issuer's sequence counter
+    pub issuer_stats: &'a mut Account<'info, IssuerStats>,
+    /// Achievement's issuance statistics
+    pub achievement_stats: &'a mut Account<'info, AchievementStats>,
+    /// Recipient's credential index
+    pub credential_index: &'a mut Account<'info, CredentialIndex>,
+    /// Recipient's current index page
+    pub credential_index_page: &'a mut Account<'info, CredentialIndexPage>,
+    /// Achievement's holder roster
+    pub recipient_roster: &'a mut Account<'info, RecipientRoster>,
+    /// Achievement's current roster page
+    pub recipient_roster_page: &'a mut Account<'info, RecipientRosterPage>,
+    /// Pays for growing the credential to fit terms of use
+    pub payer: AccountInfo<'info>,
+    /// System program
+    pub system_program: AccountInfo<'info>,
+}
+
+/// Bumps of the PDAs an issuance initializes
+pub struct IssuanceBumps {
+    /// Credential PDA
+    pub credential: u8,
+    /// Issuer stats PDA
+    pub issuer_stats: u8,
+    /// Achievement stats PDA
+    pub achievement_stats: u8,
+    /// Credential index PDA
+    pub credential_index: u8,
+    /// Credential index page PDA
+    pub credential_index_page: u8,
+    /// Recipient roster PDA
+    pub recipient_roster: u8,
+    /// Recipient roster page PDA
+    pub recipient_roster_page: u8,
+}
+
+/// How the credential subject names its recipient
+pub enum Subject {
+    /// The recipient's `did:sol:` DID
+    Did,
+    /// The recipient's plain `sol:` address
+    Address,
+    /// A holder-derived pseudonym; no identifier links it to the wallet
+    Pairwise(String),
+}
+
+/// What the credential says beyond its recipient and achievement
+pub struct CredentialContent {
+    /// How the subject names the recipient
+    pub subject: Subject,
+    /// `validFrom` and `issuedAt`; signed issuance uses the timestamp the issuer signed
+    pub issued_at: String,
+    /// Graded results
+    pub results: Vec<results::ResultInput>,
+    /// Subject narrative
+    pub narrative: Option<String>,
+    /// Issuer notes
+    pub notes: Vec<String>,
+    /// Resolved credential schemas
+    pub credential_schema: Vec<CredentialSchema>,
+    /// Key the credential is bound to, if any
+    pub holder_binding: Option<Pubkey>,
+    /// Resolved terms of use
+    pub terms_of_use: Vec<TermsOfUse>,
+}
+
+impl CredentialContent {
+    /// Content of a proof-less credential: a `did:sol:` subject issued now under the issuer's terms
+    pub fn unsigned(issuer: &Profile, results: Vec<results::ResultInput>) -> Result<Self> {
+        Ok(Self {
+            subject: Subject::Did,
+            issued_at: crate::get_current_iso8601()?,
+            results,
+            narrative: None,
+            notes: vec![],
+            credential_schema: vec![],
+            holder_binding: None,
+            terms_of_use: issuer.terms_of_use.clone(),
+        })
+    }
+
+    /// Content a signed issuance request asks for, validated and resolved against the issuer's defaults
+    pub fn requested(
+        args: &args::IssueCredentialArgs,
+        subject: Subject,
+        issuer: &Profile,
+        schema_registry: Option<&SchemaRegistry>,
+    ) -> Result<Self> {
+        narrative::validate(args.narrative.as_deref(), &args.notes)?;
+        validation::validate_timestamp_len(&args.timestamp)?;
+        Ok(Self {
+            subject,
+            issued_at: args.timestamp.clone(),
+            results: args.results.clone(),
+            narrative: args.narrative.clone(),
+            notes: args.notes.clone(),
+            credential_schema: schema::resolve(schema_registry, &args.credential_schemas)?,
+            holder_binding: args.bind_holder.then_some(args.recipient_pubkey),
+            terms_of_use: terms_of_use::resolve(&issuer.terms_of_use, args.terms_of_use.clone())?,
+        })
+    }
+}
+
+/// Issuer signature the credential's proof embeds
+pub struct IssuerSignature<'a, 'info> {
+    /// Instructions sysvar holding the Ed25519 program instruction
+    pub instructions: &'a AccountInfo<'info>,
+    /// Key the signature must verify against
+    pub signing_key: Pubkey,
+    /// The proof's verification method
+    pub verification_method: String,
+    /// Signed message, which must be the credential's signing JSON
+    pub message: &'a [u8],
+    /// Ed25519 signature over `message`
+    pub signature: &'a [u8],
+}
+
+/// Run the checks ahead of issuing `results` to `recipient`, charging the rate limit and fee
+pub fn check(
+    issuer: &mut Profile,
+    achievement: &Account<Achievement>,
+    accounts: CheckAccounts,
+    recipient: &Pubkey,
+    results: &[results::ResultInput],
+    program_id: &Pubkey,
+) -> Result<()> {
+    program_config::ensure_not_paused(accounts.program_config, program_id)?;
+    rate_limit::record_issuance(issuer, 1, Clock::get()?.slot)?;
+    crate::collect_issuance_fee(
+        accounts.program_config,
+        accounts.treasury,
+        accounts.fee_token_accounts,
+        accounts.payer,
+        accounts.system_program,
+        program_id,
+    )?;
+    cpi_guard::ensure_invocation_allowed(cpi_guard::GuardedAction::Issuance, accounts.cpi_policy, accounts.instructions)?;
+
+    // Respect the recipient's opt-out before doing any work
+    opt_out::ensure_not_opted_out(accounts.recipient_opt_out, recipient, program_id)?;
+    // A supplied result entry, required when the achievement asks for one, must be approved
+    assessment::ensure_result_approved(accounts.result_entry, achievement, recipient)?;
+    // Graded results must match the achievement's result descriptions and rubric
+    results::validate_results(results, &achievement.result_descriptions, accounts.rubric)?;
+    criteria::ensure_thresholds_met(&achievement.requirements, results)
+}
+
+/// Write an Active credential for `recipient` and record its issuance
+/// With a `signature`, the message must be the written credential's signing
+/// JSON and the Ed25519 program must have verified it; it becomes the proof.
+pub fn populate_credential(
+    accounts: IssuanceAccounts,
+    bumps: IssuanceBumps,
+    recipient: Pubkey,
+    content: CredentialContent,
+    signature: Option<IssuerSignature>,
+) -> Result<()> {
+    let IssuanceAccounts { credential, issuer, achievement, .. } = accounts;
+    let credential_key = credential.key();
+    let strings = |values: &[&str]| values.iter().map(|value| value.to_string()).collect();
+    let (subject_id, identifier) = match content.subject {
+        Subject::Did => (format!("did:sol:{}", recipient), true),
+        Subject::Address => (format!("sol:{}", recipient), true),
+        Subject::Pairwise(id) => (id, false),
+    };
+
+    credential.id = crate::credential_id::credential_id(issuer.credential_id_format, &credential_key);
+    credential.context = strings(&CREDENTIAL_CONTEXT);
+    credential.r#type = strings(&CREDENTIAL_TYPE);
+    credential.issuer = issuer.key();
+    credential.valid_from = content.issued_at.clone();
+    credential.issued_at = content.issued_at;
+    credential.credential_subject = AchievementSubject {
+        id: Some(subject_id),
+        subject_type: strings(&SUBJECT_TYPE),
+        achievement: achievement.key(),
+        identifier: identifier
+            .then(|| IdentityObject {
+                identity_type: "IdentityObject".to_string(),
+                hashed: false,
+                identity_hash: recipient.to_string(),
+                identity_type_name: "identifier".to_string(),
+            })
+            .into_iter()
+            .collect(),
+        result: content.results,
+        narrative: content.narrative,
+    };
+    credential.notes = content.notes;
+    credential.credential_schema = content.credential_schema;
+    credential.holder_binding = content.holder_binding;
+    credential.proof = match signature {
+        Some(signature) => vec![signed_proof(credential, signature)?],
+        None => vec![],
+    };
+    credential.is_revoked = false;
+    credential.status = lifecycle::CredentialState::Active;
+    credential.bump = bumps.credential;
+    strictness::validate_issued_credential(issuer.validation_mode, credential)?;
+    credential.sequence = accounts.issuer_stats.next_sequence(issuer.key(), bumps.issuer_stats);
+
+    credential_index::record(
+        accounts.credential_index,
+        accounts.credential_index_page,
+        recipient,
+        (bumps.credential_index, bumps.credential_index_page),
+        credential_key,
+    )?;
+    recipient_roster::record(
+        accounts.recipient_roster,
+        accounts.recipient_roster_page,
+        achievement.key(),
+        (bumps.recipient_roster, bumps.recipient_roster_page),
+        RosterEntry { recipient, credential: credential_key },
+    )?;
+    terms_of_use::attach(credential, content.terms_of_use, &accounts.payer, &accounts.system_program)?;
+    accounts.achievement_stats.record_issued(achievement.key(), bumps.achievement_stats, credential.issued_at.clone());
+    Ok(())
+}
+
+/// Announce an issued credential, with its signed document when it carries a proof
+pub fn emit_issued(credential: &Account<AchievementCredential>, recipient: Pubkey) {
+    emit!(events::CredentialIssued {
+        credential: credential.key(),
+        issuer: credential.issuer,
+        recipient,
+        sequence: credential.sequence,
+    });
+    if let Some(document) = crate::canonical::signed_document(credential) {
+        emit!(events::CredentialDocument { credential: credential.key(), document });
+    }
+}
+
+/// Proof embedding `signature` once it is checked against the credential's signing JSON
+fn signed_proof(credential: &AchievementCredential, signature: IssuerSignature) -> Result<Proof> {
+    let credential_json = crate::credential_signing_json(credential);
+    if signature.message != credential_json.as_bytes() {
+        msg!("❌ Signed message does not match the credential JSON: {}", credential_json);
+        return Err(error!(ValidationError::ValidationFailed));
+    }
+    let signature_array: [u8; 64] = signature
+        .signature
+        .try_into()
+        .map_err(|_| error!(ValidationError::InvalidKeyLength))?;
+    proof::ensure_ed25519_verified(signature.instructions, &signature.signing_key, signature.message, &signature_array)?;
+
+    Ok(Proof {
+        proof_type: "DataIntegrityProof".to_string(),
+        cryptosuite: "eddsa-rdfc-2022".to_string(),
+        created: crate::get_current_iso8601()?,
+        proof_purpose: "assertionMethod".to_string(),
+        verification_method: signature.verification_method,
+        proof_value: multibase::encode(signature.signature),
+        previous_proof: None,
+    })
+}
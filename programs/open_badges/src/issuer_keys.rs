@@ -0,0 +1,145 @@
+//! Issuer Verification Methods and Key Rotation
+//!
+//! An issuer can register several signing keys as `IssuerKey` PDAs. Each key
+//! is exposed as the verification method `did:sol:<issuer PDA>#key-N`, so a
+//! proof names the exact key that signed it. Retiring a key only affects
+//! proofs created after the retirement time, which keeps credentials signed
+//! before a rotation verifiable.
+
+use anchor_lang::prelude::*;
+use crate::common::errors::ValidationError;
+
+/// Seed prefix for issuer key PDAs
+pub const ISSUER_KEY_SEED: &[u8] = b"issuer_key";
+
+/// Fragment prefix used for issuer verification methods
+pub const KEY_FRAGMENT_PREFIX: &str = "key-";
+
+/// A verification method registered by an issuer
+#[account]
+pub struct IssuerKey {
+    /// Issuer profile this key belongs to
+    pub issuer: Pubkey,
+
+    /// Key number N, referenced as `#key-N`
+    pub key_index: u32,
+
+    /// Ed25519 public key used for proofs
+    pub public_key: Pubkey,
+
+    /// When the key became usable (ISO 8601 string)
+    pub created_at: String,
+
+    /// When the key was retired (ISO 8601 string, optional)
+    pub retired_at: Option<String>,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl IssuerKey {
+    /// Discriminator + issuer + index + key + created_at + optional retired_at + bump
+    pub const SPACE: usize = 8 + 32 + 4 + 32 + 4 + 40 + 1 + 4 + 40 + 1;
+
+    /// Fragment naming this key in a verification method
+    pub fn fragment(&self) -> String {
+        format!("{}{}", KEY_FRAGMENT_PREFIX, self.key_index)
+    }
+
+    /// Full verification method id for this key
+    pub fn verification_method_id(&self) -> String {
+        format!("did:sol:{}#{}", self.issuer, self.fragment())
+    }
+
+    /// Whether a proof created at `proof_created` may use this key
+    pub fn is_valid_for_proof_at(&self, proof_created: &str) -> Result<bool> {
+        let created = crate::parse_iso8601_to_unix(proof_created)?;
+        let key_created = crate::parse_iso8601_to_unix(&self.created_at)?;
+        if created < key_created {
+            return Ok(false);
+        }
+
+        match &self.retired_at {
+            Some(retired_at) => Ok(created < crate::parse_iso8601_to_unix(retired_at)?),
+            None => Ok(true),
+        }
+    }
+}
+
+/// Split a verification method into its DID and key index, if it has a `#key-N` fragment
+pub fn parse_key_fragment(verification_method: &str) -> Result<Option<(String, u32)>> {
+    let Some((did, fragment)) = verification_method.split_once('#') else {
+        return Ok(None);
+    };
+
+    let index = fragment
+        .strip_prefix(KEY_FRAGMENT_PREFIX)
+        .ok_or_else(|| error!(ValidationError::MissingKeyFragment))?
+        .parse::<u32>()
+        .map_err(|_| error!(ValidationError::MissingKeyFragment))?;
+
+    Ok(Some((did.to_string(), index)))
+}
+
+/// Resolve a proof's verification method against the supplied issuer key
+///
+/// Returns `Ok(true)` for legacy proofs without a fragment, and checks that
+/// fragment-bearing proofs reference the given key and fall within its lifetime.
+pub fn resolve_proof_key(
+    verification_method: &str,
+    proof_created: &str,
+    issuer: &Pubkey,
+    issuer_key: Option<&IssuerKey>,
+) -> Result<bool> {
+    let Some((did, index)) = parse_key_fragment(verification_method)? else {
+        return Ok(true);
+    };
+
+    if did != format!("did:sol:{}", issuer) {
+        msg!("❌ Verification method DID does not match credential issuer");
+        return Ok(false);
+    }
+
+    let key = issuer_key.ok_or_else(|| error!(ValidationError::VerificationMethodNotFound))?;
+    if key.issuer != *issuer || key.key_index != index {
+        return Err(error!(ValidationError::VerificationMethodNotFound));
+    }
+
+    key.is_valid_for_proof_at(proof_created)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(retired_at: Option<&str>) -> IssuerKey {
+        IssuerKey {
+            issuer: Pubkey::new_unique(),
+            key_index: 2,
+            public_key: Pubkey::new_unique(),
+            created_at: "2025-01-01T00:00:00Z".to_string(),
+            retired_at: retired_at.map(str::to_string),
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn test_parse_key_fragment() {
+        assert_eq!(
+            parse_key_fragment("did:sol:abc#key-3").unwrap(),
+            Some(("did:sol:abc".to_string(), 3))
+        );
+        assert_eq!(parse_key_fragment("did:sol:abc").unwrap(), None);
+        assert!(parse_key_fragment("did:sol:abc#other").is_err());
+    }
+
+    #[test]
+    fn test_retired_key_keeps_older_proofs_valid() {
+        let key = key(Some("2025-06-01T00:00:00Z"));
+        let vm = key.verification_method_id();
+
+        assert!(resolve_proof_key(&vm, "2025-03-01T00:00:00Z", &key.issuer, Some(&key)).unwrap());
+        assert!(!resolve_proof_key(&vm, "2025-07-01T00:00:00Z", &key.issuer, Some(&key)).unwrap());
+        assert!(!resolve_proof_key(&vm, "2024-12-01T00:00:00Z", &key.issuer, Some(&key)).unwrap());
+    }
+}
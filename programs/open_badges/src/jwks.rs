@@ -0,0 +1,135 @@
+//! Issuer JSON Web Key Set
+//!
+//! OIDC-style verifiers look signing keys up in a JWKS rather than a DID
+//! document. `get_issuer_jwks` lists every Ed25519 key that signs for an
+//! issuer, each under the `kid` that proofs and JWT headers use for it: the
+//! authority as `did:sol:<authority>` (JWT `kid`) and `did:sol:<issuer>`
+//! (Data Integrity proofs), registered issuer keys as `did:sol:<issuer>#key-N`
+//! and the authority's sol_did verification methods as
+//! `did:sol:<authority>#<fragment>`. Retired issuer keys stay listed, since
+//! proofs created before their retirement still verify.
+
+use anchor_lang::prelude::*;
+use base64::{Engine, engine::general_purpose};
+use crate::common::errors::ValidationError;
+use crate::did::methods::{sol_did_flags, SolanaDidResolver};
+use crate::issuer_keys::IssuerKey;
+use crate::Profile;
+
+/// JWK of an Ed25519 key published under `kid`
+pub fn ed25519_jwk(kid: &str, key: &[u8]) -> serde_json::Value {
+    serde_json::json!({
+        "kty": "OKP",
+        "crv": "Ed25519",
+        "x": general_purpose::URL_SAFE_NO_PAD.encode(key),
+        "kid": kid,
+        "alg": "EdDSA",
+        "use": "sig",
+    })
+}
+
+/// The authority's sol_did account, or the generative one when it has none on-chain
+pub fn did_account(authority: &Pubkey, did_data: Option<&AccountInfo>) -> Result<sol_did_cpi::state::DidAccount> {
+    let Some(did_data) = did_data.filter(|info| info.owner == &sol_did_cpi::ID && !info.data_is_empty()) else {
+        return Ok(SolanaDidResolver::generative_did_account(authority));
+    };
+    let data = did_data.try_borrow_data()?;
+    sol_did_cpi::state::DidAccount::try_deserialize(&mut &data[..]).map_err(|_| error!(ValidationError::InvalidDid))
+}
+
+/// JWKS document of the issuer's signing keys
+pub fn issuer_jwks(
+    issuer: &Pubkey,
+    profile: &Profile,
+    keys: &[&IssuerKey],
+    did_account: &sol_did_cpi::state::DidAccount,
+) -> Result<String> {
+    let authority_did = format!("did:sol:{}", profile.authority);
+    let mut entries: Vec<(String, Vec<u8>)> = vec![
+        (authority_did.clone(), profile.authority.to_bytes().to_vec()),
+        (format!("did:sol:{}", issuer), profile.authority.to_bytes().to_vec()),
+    ];
+
+    for key in keys {
+        if key.issuer != *issuer {
+            msg!("❌ Issuer key {} belongs to another issuer", key.verification_method_id());
+            return Err(error!(ValidationError::UnauthorizedAccess));
+        }
+        entries.push((key.verification_method_id(), key.public_key.to_bytes().to_vec()));
+    }
+
+    let methods = std::iter::once(&did_account.initial_verification_method).chain(did_account.verification_methods.iter());
+    for method in methods {
+        // Only visible Ed25519 methods can be expressed as OKP keys
+        if method.method_type != 0 || method.flags & sol_did_flags::DID_DOC_HIDDEN != 0 {
+            continue;
+        }
+        entries.push((format!("{}#{}", authority_did, method.fragment), method.key_data.clone()));
+    }
+
+    let mut seen = std::collections::BTreeSet::new();
+    let keys: Vec<serde_json::Value> = entries
+        .iter()
+        .filter(|(kid, _)| seen.insert(kid.clone()))
+        .map(|(kid, key)| ed25519_jwk(kid, key))
+        .collect();
+    serde_json::to_string(&serde_json::json!({ "keys": keys })).map_err(|_| error!(ValidationError::SerializationError))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::fixture_profile;
+
+    fn issuer_key(issuer: &Pubkey, key_index: u32) -> IssuerKey {
+        IssuerKey {
+            issuer: *issuer,
+            key_index,
+            public_key: Pubkey::new_unique(),
+            created_at: "2024-01-01T00:00:00Z".to_string(),
+            retired_at: None,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn test_kids_match_verification_methods() {
+        let issuer = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let profile = fixture_profile(&issuer, &authority, "Example University");
+        let key = issuer_key(&issuer, 1);
+        let did_account = did_account(&authority, None).unwrap();
+
+        let jwks: serde_json::Value = serde_json::from_str(&issuer_jwks(&issuer, &profile, &[&key], &did_account).unwrap()).unwrap();
+        let kids: Vec<&str> = jwks["keys"].as_array().unwrap().iter().map(|k| k["kid"].as_str().unwrap()).collect();
+        assert_eq!(
+            kids,
+            [
+                format!("did:sol:{}", authority),
+                format!("did:sol:{}", issuer),
+                key.verification_method_id(),
+                format!("did:sol:{}#default", authority),
+            ]
+        );
+        assert_eq!(jwks["keys"][2]["x"], general_purpose::URL_SAFE_NO_PAD.encode(key.public_key));
+        assert_eq!(jwks["keys"][0]["alg"], "EdDSA");
+    }
+
+    #[test]
+    fn test_foreign_and_non_ed25519_keys() {
+        let issuer = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let profile = fixture_profile(&issuer, &authority, "Example University");
+        let mut did_account = did_account(&authority, None).unwrap();
+        assert!(issuer_jwks(&issuer, &profile, &[&issuer_key(&Pubkey::new_unique(), 1)], &did_account).is_err());
+
+        did_account.verification_methods.push(sol_did_cpi::VerificationMethod {
+            fragment: "eth".to_string(),
+            flags: sol_did_flags::ASSERTION,
+            method_type: 1,
+            key_data: vec![0; 20],
+        });
+        let jwks: serde_json::Value = serde_json::from_str(&issuer_jwks(&issuer, &profile, &[], &did_account).unwrap()).unwrap();
+        assert_eq!(jwks["keys"].as_array().unwrap().len(), 3);
+    }
+}
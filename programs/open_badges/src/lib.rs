@@ -13,6 +13,69 @@ pub mod credential_status;
 pub mod compliance_validator;
 pub mod formats;
 pub mod did;
+pub mod opt_out;
+pub mod privacy;
+pub mod issuer_keys;
+pub mod events;
+pub mod redaction;
+pub mod rubric;
+pub mod assessment;
+pub mod peer;
+pub mod nomination;
+pub mod pda;
+pub mod views;
+pub mod branding;
+pub mod presentation;
+pub mod results;
+pub mod stats;
+pub mod proof_upgrade;
+pub mod cpi_guard;
+pub mod credential_index;
+pub mod anchoring;
+pub mod extensions;
+pub mod strictness;
+pub mod clr;
+pub mod baked_image;
+pub mod refresh;
+pub mod nonce;
+pub mod batch;
+pub mod narrative;
+pub mod program_config;
+pub mod treasury;
+pub mod expiration;
+pub mod schema;
+pub mod proof_set;
+pub mod secp256k1;
+pub mod holder_binding;
+pub mod canonical;
+pub mod args;
+pub mod lifecycle;
+pub mod criteria;
+pub mod rebind;
+pub mod achievement_directory;
+pub mod credential_id;
+pub mod jwks;
+pub mod rate_limit;
+pub mod recipient_roster;
+pub mod claim_code;
+pub mod terms_of_use;
+pub mod other_identifier;
+pub mod verification_policy;
+pub mod profiling;
+pub mod datetime;
+pub mod team;
+pub mod accreditation;
+pub mod scoped_authorization;
+pub mod i18n;
+pub mod supersession;
+pub mod document_integrity;
+pub mod multibase;
+pub mod verification_bundle;
+pub mod encrypted_payload;
+pub mod staff_key;
+pub mod issuance;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_utils;
 
 // Import specific items to avoid conflicts
 use common::errors::ValidationError;
@@ -22,27 +85,362 @@ use proof::{MultikeyPair, ProofSuite, DataIntegrityProof};
 
 declare_id!("FFQUgGaWxQFGnCe3VBmRZ259wtWHxjkpCqePouiyfzH5");
 
+/// Maximum stored length of a credential revocation reason
+pub const MAX_REVOCATION_REASON_LEN: usize = 200;
+
+/// Maximum length of the badge service endpoint published in an issuer's DID document
+pub const MAX_SERVICE_ENDPOINT_LEN: usize = 200;
+
+/// Largest growth of an issuer's DID account in one `expand_issuer_did`, the runtime's realloc limit
+pub const MAX_DID_EXPANSION: usize = anchor_lang::solana_program::entrypoint::MAX_PERMITTED_DATA_INCREASE;
+
 /// Helper function to get current timestamp as ISO 8601 string
 /// Uses Solana's Clock sysvar to get timestamp in BPF environment
 fn get_current_iso8601() -> Result<String> {
     let clock = Clock::get()?;
-    unix_timestamp_to_iso8601(clock.unix_timestamp)
+    datetime::format_iso8601(clock.unix_timestamp)
 }
 
-/// Convert Unix timestamp to ISO 8601 string format
-fn unix_timestamp_to_iso8601(timestamp: i64) -> Result<String> {
-    DateTime::from_timestamp(timestamp, 0)
-        .ok_or_else(|| error!(ValidationError::InvalidTimestampFormat))
-        .map(|dt| dt.to_rfc3339())
+/// Current Unix time from the host's system clock
+/// Only off-chain builds have one; on-chain code reads the Clock sysvar
+#[cfg(feature = "offchain")]
+pub fn system_unix_timestamp() -> i64 {
+    chrono::Utc::now().timestamp()
 }
 
 /// Helper function to parse ISO 8601 string to Unix timestamp for comparisons
-fn parse_iso8601_to_unix(iso_string: &str) -> Result<i64> {
+pub fn parse_iso8601_to_unix(iso_string: &str) -> Result<i64> {
     iso_string.parse::<DateTime<Utc>>()
         .map(|dt| dt.timestamp())
         .map_err(|_| error!(ValidationError::InvalidTimestampFormat))
 }
 
+/// Maximum number of credentials checked by `verify_credentials_batch` (one bit each in a u64)
+pub const MAX_BATCH_VERIFY: usize = 64;
+
+/// Maximum number of credentials revoked by one `batch_revoke_credentials_direct`
+pub const MAX_BATCH_REVOKE: usize = 16;
+
+/// What the Ed25519 signatures of a credential's proofs are checked against
+#[derive(Clone, Copy, Default)]
+pub struct ProofSignatures<'a, 'info> {
+    /// The credential's issuer profile, whose authority signs proofs without a `#key-N` fragment
+    pub issuer: Option<&'a Profile>,
+    /// Instructions sysvar holding one Ed25519 program instruction per checked proof
+    pub instructions: Option<&'a AccountInfo<'info>>,
+    /// The issuer's record of a staff key and the key's sol_did account, for proofs it signed
+    pub staff: Option<(&'a staff_key::StaffKey, &'a sol_did_cpi::state::DidAccount)>,
+}
+
+/// Check a proof: its shape, its verification method among `issuer_keys`, and its signature
+///
+/// The signature must be verified by an Ed25519 program instruction earlier
+/// in the transaction, over the credential's signing JSON, followed by the
+/// endorsed proof's `proofValue` for a chained proof. A proof whose signer,
+/// signature or Ed25519 instruction is missing does not verify. A proof
+/// whose verification method is the DID of the staff key in `signatures`
+/// is signed by that key, provided `staff_key::authorizes` it for the issuer.
+fn check_proof(
+    proof: &Proof,
+    credential: &AchievementCredential,
+    issuer_keys: &[&issuer_keys::IssuerKey],
+    signatures: ProofSignatures,
+) -> Result<bool> {
+    if proof.proof_type != "DataIntegrityProof" || proof.proof_value.is_empty() {
+        return Ok(false);
+    }
+    
+    let signer = match staff_signer(proof, credential, signatures) {
+        Some(staff) => Some(staff),
+        None => {
+            let issuer_key = proof_set::key_for(proof, issuer_keys);
+            if !issuer_keys::resolve_proof_key(&proof.verification_method, &proof.created, &credential.issuer, issuer_key)? {
+                return Ok(false);
+            }
+            match issuer_keys::parse_key_fragment(&proof.verification_method)? {
+                Some(_) => issuer_key.map(|key| key.public_key),
+                None => signatures.issuer.map(|issuer| issuer.authority),
+            }
+        }
+    };
+    let previous = match &proof.previous_proof {
+        Some(previous_id) => match proof_set::endorsed_proof(credential, previous_id) {
+            Some(previous) => Some(previous),
+            None => return Ok(false),
+        },
+        None => None,
+    };
+    let (Some(signer), Some(signature), Some(instructions)) =
+        (signer, multibase::decode_signature(&proof.proof_value), signatures.instructions)
+    else {
+        msg!("❌ Proof by {} has no verifiable Ed25519 signature", proof.verification_method);
+        return Ok(false);
+    };
+    let message = proof_set::signing_message(&credential_signing_json(credential), previous);
+    Ok(proof::ensure_ed25519_verified(instructions, &signer, &message, &signature).is_ok())
+}
+
+/// The staff key a proof names as its verification method, when the issuer trusts it
+fn staff_signer(proof: &Proof, credential: &AchievementCredential, signatures: ProofSignatures) -> Option<Pubkey> {
+    let ((staff_key, did_account), profile) = signatures.staff.zip(signatures.issuer)?;
+    let method_did = proof.verification_method.split('#').next().unwrap_or_default();
+    (method_did == format!("did:sol:{}", staff_key.staff)
+        && staff_key::authorizes(staff_key, did_account, &credential.issuer, profile, &staff_key.staff))
+        .then_some(staff_key.staff)
+}
+
+/// The issuer profile of `credential` at `cursor` in `accounts`, advancing past it
+/// Trailing profiles let remaining-account lists check proofs signed by the issuer authority
+fn issuer_profile_at<'info>(
+    accounts: &'info [AccountInfo<'info>],
+    cursor: &mut usize,
+    credential: &AchievementCredential,
+) -> Option<Account<'info, Profile>> {
+    let info = accounts.get(*cursor).filter(|info| info.key() == credential.issuer)?;
+    let profile = Account::<Profile>::try_from(info).ok()?;
+    *cursor += 1;
+    Some(profile)
+}
+
+/// Run the temporal, revocation and proof checks of `verify_credential` without logging
+fn check_credential_validity(
+    credential: &AchievementCredential,
+    issuer_keys: &[&issuer_keys::IssuerKey],
+    signatures: ProofSignatures,
+    policy: proof_set::ProofPolicy,
+    current_time: i64,
+) -> Result<bool> {
+    if !proof_set::satisfies(&credential.proof, policy, |proof| check_proof(proof, credential, issuer_keys, signatures))? {
+        return Ok(false);
+    }
+    if credential.is_revoked
+        || credential.status == lifecycle::CredentialState::Suspended
+        || parse_iso8601_to_unix(&credential.valid_from)? > current_time
+    {
+        return Ok(false);
+    }
+    if let Some(valid_until) = &credential.valid_until {
+        if current_time > parse_iso8601_to_unix(valid_until)? {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// Credential JSON the issuer signs, in the format issuance verifies
+pub fn credential_signing_json(credential: &AchievementCredential) -> String {
+    canonical::build_signing_json(&canonical::SigningJsonParams::for_credential(credential))
+}
+
+/// Write a new issuer profile for `authority`, identified by the authority's did:sol DID
+/// Every issuer initializer starts its profile here, so each field is set in one place
+fn init_profile(
+    issuer: &mut Profile,
+    authority: Pubkey,
+    name: String,
+    url: Option<String>,
+    email: Option<String>,
+    image: Option<String>,
+    bump: u8,
+) -> Result<()> {
+    let id = format!("did:sol:{}", authority);
+    validation::validate_profile_did(&id)?;
+    *issuer = Profile {
+        id,
+        r#type: vec!["Profile".to_string()],
+        authority,
+        name,
+        url,
+        email,
+        image,
+        branding: None,
+        validation_mode: strictness::ValidationMode::Production,
+        refresh_service_url: None,
+        terms_of_use: vec![],
+        other_identifier: vec![],
+        name_i18n: vec![],
+        credential_id_format: credential_id::CredentialIdFormat::DidSol,
+        issuance_limit: None,
+        frozen: false,
+        bump,
+    };
+    Ok(())
+}
+
+/// Issue a credential its issuer's authority signed off-chain
+/// Shared by the signed issuance instructions, which differ only in how the
+/// subject names the recipient. The proof's verification method is the issuer
+/// PDA's DID, resolved to the authority's key.
+fn issue_signed_credential(
+    ctx: Context<IssueAchievementCredential>,
+    args: args::IssueCredentialArgs,
+    subject: issuance::Subject,
+) -> Result<()> {
+    args.ensure_supported()?;
+    let accounts = ctx.accounts;
+    
+    issuance::check(
+        &mut accounts.issuer,
+        &accounts.achievement,
+        issuance::CheckAccounts {
+            program_config: &accounts.program_config,
+            treasury: &accounts.treasury,
+            fee_token_accounts: [
+                accounts.fee_token_account.as_ref(),
+                accounts.treasury_token_account.as_ref(),
+                accounts.token_program.as_ref(),
+            ],
+            payer: &accounts.authority,
+            system_program: &accounts.system_program,
+            cpi_policy: accounts.cpi_policy.as_deref(),
+            instructions: Some(accounts.instructions.as_ref()),
+            recipient_opt_out: &accounts.recipient_opt_out,
+            result_entry: accounts.result_entry.as_deref(),
+            rubric: accounts.rubric.as_deref(),
+        },
+        &args.recipient_pubkey,
+        &args.results,
+        ctx.program_id,
+    )?;
+    let content = issuance::CredentialContent::requested(
+        &args,
+        subject,
+        &accounts.issuer,
+        accounts.schema_registry.as_deref(),
+    )?;
+    msg!("🔐 Issuing {} to {}", accounts.credential.key(), args.recipient_pubkey);
+    
+    // Issuing against a nomination approves it and links the credential
+    if let Some(nomination) = accounts.nomination.as_mut() {
+        nomination::accept_nomination(nomination, &args.recipient_pubkey, &accounts.credential.key(), get_current_iso8601()?)?;
+    }
+    // A signed payload issues at most one credential
+    nonce::consume(
+        &mut accounts.consumed_payload,
+        accounts.issuer.key(),
+        ctx.bumps.consumed_payload,
+        &args.message_data,
+    )?;
+    
+    issuance::populate_credential(
+        issuance::IssuanceAccounts {
+            credential: &mut accounts.credential,
+            issuer: &accounts.issuer,
+            achievement: &accounts.achievement,
+            issuer_stats: &mut accounts.issuer_stats,
+            achievement_stats: &mut accounts.achievement_stats,
+            credential_index: &mut accounts.credential_index,
+            credential_index_page: &mut accounts.credential_index_page,
+            recipient_roster: &mut accounts.recipient_roster,
+            recipient_roster_page: &mut accounts.recipient_roster_page,
+            payer: accounts.authority.to_account_info(),
+            system_program: accounts.system_program.to_account_info(),
+        },
+        issuance::IssuanceBumps {
+            credential: ctx.bumps.credential,
+            issuer_stats: ctx.bumps.issuer_stats,
+            achievement_stats: ctx.bumps.achievement_stats,
+            credential_index: ctx.bumps.credential_index,
+            credential_index_page: ctx.bumps.credential_index_page,
+            recipient_roster: ctx.bumps.recipient_roster,
+            recipient_roster_page: ctx.bumps.recipient_roster_page,
+        },
+        args.recipient_pubkey,
+        content,
+        Some(issuance::IssuerSignature {
+            instructions: &accounts.instructions,
+            signing_key: accounts.authority.key(),
+            verification_method: format!("did:sol:{}", accounts.issuer.key()),
+            message: &args.message_data,
+            signature: &args.signature_data,
+        }),
+    )?;
+    issuance::emit_issued(&accounts.credential, args.recipient_pubkey);
+    
+    msg!("🏅 CREDENTIAL_ISSUED: {}", accounts.achievement.name);
+    Ok(())
+}
+
+/// Charge `payer` the fees set in the program config
+/// Every instruction that creates a credential or a claim offer charges it;
+/// `claim_with_code` does not, since `issue_with_claim_code` charged its offer.
+/// `token_accounts` are the payer's fee token account, the treasury token
+/// account and the token program, needed when the config sets a token fee
+fn collect_issuance_fee<'info>(
+    program_config: &AccountInfo<'info>,
+    treasury: &AccountInfo<'info>,
+    token_accounts: [Option<&UncheckedAccount<'info>>; 3],
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    program_id: &Pubkey,
+) -> Result<()> {
+    let fee_token_accounts = match token_accounts {
+        [Some(source), Some(destination), Some(token_program)] => Some(treasury::TokenFeeAccounts {
+            source: source.as_ref(),
+            destination: destination.as_ref(),
+            token_program: token_program.as_ref(),
+        }),
+        _ => None,
+    };
+    treasury::collect_issuance_fee(program_config, program_id, treasury, payer, system_program, fee_token_accounts)
+}
+
+/// Load the holder's credentials from `accounts` and build the unsigned presentation
+fn holder_presentation<'info>(
+    accounts: &'info [AccountInfo<'info>],
+    holder: &Pubkey,
+    created: &str,
+    challenge: &str,
+    domain: &str,
+    program_id: &Pubkey,
+) -> Result<serde_json::Value> {
+    presentation::validate_proof_options(challenge, domain)?;
+    require!(accounts.len() <= presentation::MAX_PRESENTATION_CREDENTIALS, ValidationError::TooManyItems);
+    parse_iso8601_to_unix(created)?;
+
+    let documents = held_credential_documents(accounts, holder, program_id)?;
+    Ok(presentation::unsigned_presentation(holder, documents, created, challenge, domain))
+}
+
+/// JSON-LD documents of the credentials in `accounts`, each of which must be issued to `holder`
+fn held_credential_documents<'info>(
+    accounts: &'info [AccountInfo<'info>],
+    holder: &Pubkey,
+    program_id: &Pubkey,
+) -> Result<Vec<serde_json::Value>> {
+    require!(!accounts.is_empty(), ValidationError::EmptyBatch);
+    let mut documents = Vec::with_capacity(accounts.len());
+    for info in accounts {
+        let credential = Account::<AchievementCredential>::try_from(info)?;
+        if !presentation::is_held_by(&info.key(), &credential, holder, program_id) {
+            msg!("❌ Credential {} was not issued to {}", info.key(), holder);
+            return Err(error!(ValidationError::HolderMismatch));
+        }
+        documents.push(credential::credential_account_to_json(&credential));
+    }
+    Ok(documents)
+}
+
+/// Build the recipient's CLR from the credentials in `accounts`, without its proof value
+fn recipient_clr<'info>(
+    accounts: &'info [AccountInfo<'info>],
+    issuer: &Account<'info, Profile>,
+    recipient: &Pubkey,
+    clr_id: &str,
+    name: &str,
+    valid_from: &str,
+    program_id: &Pubkey,
+) -> Result<clr::ClrCredential> {
+    require!(accounts.len() <= clr::MAX_CLR_CREDENTIALS, ValidationError::TooManyItems);
+    parse_iso8601_to_unix(valid_from)?;
+
+    let mut builder = clr::ClrCredential::new(clr_id, name, issuer.key(), &issuer.name, *recipient, valid_from)?;
+    for document in held_credential_documents(accounts, recipient, program_id)? {
+        builder.add_credential(document)?;
+    }
+    Ok(builder)
+}
+
 #[program]
 pub mod open_badges {
     use super::*;
@@ -53,21 +451,44 @@ pub mod open_badges {
         name: String,
         url: Option<String>,
         email: Option<String>,
+        image: Option<String>,
     ) -> Result<()> {
-        // Generate the DID as the profile ID
-        let did_id = format!("did:sol:{}", ctx.accounts.authority.key());
+        validation::validate_profile_fields(&name, url.as_deref(), email.as_deref(), image.as_deref())?;
         
-        let issuer = &mut ctx.accounts.issuer;
-        issuer.id = did_id.clone();
-        issuer.r#type = vec!["Profile".to_string()];
-        issuer.authority = ctx.accounts.authority.key();
-        issuer.name = name;
-        issuer.url = url;
-        issuer.email = email;
-        issuer.bump = ctx.bumps.issuer;
+        init_profile(&mut ctx.accounts.issuer, ctx.accounts.authority.key(), name, url, email, image, ctx.bumps.issuer)?;
+        let issuer = &ctx.accounts.issuer;
+        
+        msg!("🏆 ISSUER_CREATED: {}", issuer.name);
+        msg!("📄 Profile ID (DID): {}", issuer.id);
+        Ok(())
+    }
+
+    /// Initialize an issuer profile whose authority is a PDA of another program
+    /// The owning program signs for the authority with `invoke_signed`; the seeds
+    /// (bump included) and program id prove the address is that PDA
+    pub fn initialize_issuer_pda_authority(
+        ctx: Context<InitializeIssuerPdaAuthority>,
+        name: String,
+        url: Option<String>,
+        email: Option<String>,
+        image: Option<String>,
+        authority_program: Pubkey,
+        authority_seeds: Vec<Vec<u8>>,
+    ) -> Result<()> {
+        validation::validate_profile_fields(&name, url.as_deref(), email.as_deref(), image.as_deref())?;
+        
+        let authority = ctx.accounts.authority.key();
+        if !pda::is_program_authority(&authority, &authority_program, &authority_seeds) {
+            msg!("❌ {} is not a PDA of {} at the given seeds", authority, authority_program);
+            return Err(error!(ValidationError::UnauthorizedAccess));
+        }
+        
+        init_profile(&mut ctx.accounts.issuer, authority, name, url, email, image, ctx.bumps.issuer)?;
+        let issuer = &ctx.accounts.issuer;
         
         msg!("🏆 ISSUER_CREATED: {}", issuer.name);
-        msg!("📄 Profile ID (DID): {}", did_id);
+        msg!("📄 Profile ID (DID): {}", issuer.id);
+        msg!("🏛️ Authority is a PDA of program {}", authority_program);
         Ok(())
     }
 
@@ -77,8 +498,11 @@ pub mod open_badges {
         name: String,
         url: Option<String>,
         email: Option<String>,
+        image: Option<String>,
         did_size: u32,
     ) -> Result<()> {
+        validation::validate_profile_fields(&name, url.as_deref(), email.as_deref(), image.as_deref())?;
+        
         // First create the DID document using the official sol-did program via CPI
         let cpi_program = ctx.accounts.sol_did_program.to_account_info();
         let cpi_accounts = sol_did_cpi::cpi::accounts::Initialize {
@@ -90,383 +514,539 @@ pub mod open_badges {
         let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
         sol_did_cpi::cpi::initialize(cpi_ctx, did_size)?;
         
-        // Generate the DID as the profile ID
-        let did_id = format!("did:sol:{}", ctx.accounts.authority.key());
-        
-        // Initialize the issuer profile with DID as the ID
-        let issuer = &mut ctx.accounts.issuer;
-        issuer.id = did_id.clone();
-        issuer.r#type = vec!["Profile".to_string()];
-        issuer.authority = ctx.accounts.authority.key();
-        issuer.name = name;
-        issuer.url = url;
-        issuer.email = email;
-        issuer.bump = ctx.bumps.issuer;
+        init_profile(&mut ctx.accounts.issuer, ctx.accounts.authority.key(), name, url, email, image, ctx.bumps.issuer)?;
+        let issuer = &ctx.accounts.issuer;
         
         msg!("🏆 ISSUER_WITH_DID_CREATED: {}", issuer.name);
-        msg!("📄 Profile ID (DID): {}", did_id);
+        msg!("📄 Profile ID (DID): {}", issuer.id);
         Ok(())
     }
 
-    /// Create an achievement definition
-    pub fn create_achievement(
-        ctx: Context<CreateAchievement>,
-        achievement_id: String,
-        name: String,
-        description: String,
-        criteria_narrative: Option<String>,
-        criteria_id: Option<String>,
-        creator: Option<Pubkey>,
-    ) -> Result<()> {
-        let achievement = &mut ctx.accounts.achievement;
-        
-        // Set Open Badges v3.0 context (REQUIRED)
-        achievement.context = vec![
-            "https://www.w3.org/ns/credentials/v2".to_string(),
-            "https://purl.imsglobal.org/spec/ob/v3p0/context-3.0.3.json".to_string(),
-        ];
+    /// Publish one of the issuer's keys as an assertion method of its did:sol document
+    /// CPIs into sol_did `add_verification_method`; the key appears under its `key-N` fragment
+    pub fn add_issuer_verification_method(ctx: Context<AddIssuerVerificationMethod>) -> Result<()> {
+        let issuer_key = &ctx.accounts.issuer_key;
+        if issuer_key.retired_at.is_some() {
+            msg!("❌ Retired keys are not published: {}", issuer_key.verification_method_id());
+            return Err(error!(ValidationError::ValidationFailed));
+        }
         
-        achievement.id = achievement_id;
-        achievement.r#type = vec!["Achievement".to_string()];
-        achievement.issuer = ctx.accounts.issuer.key();
-        achievement.name = name;
-        achievement.description = description;
-        achievement.criteria = Criteria {
-            id: criteria_id,
-            narrative: criteria_narrative,
+        let cpi_program = ctx.accounts.sol_did_program.to_account_info();
+        let cpi_accounts = sol_did_cpi::cpi::accounts::AddVerificationMethod {
+            did_data: ctx.accounts.did_data.to_account_info(),
+            authority: ctx.accounts.authority.to_account_info(),
         };
-        achievement.creator = creator;
-        achievement.created_at = get_current_iso8601()?;
-        achievement.bump = ctx.bumps.achievement;
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        sol_did_cpi::cpi::add_verification_method(
+            cpi_ctx,
+            did::methods::SolanaDidResolver::assertion_method(issuer_key),
+            None,
+        )?;
         
-        msg!("🎯 ACHIEVEMENT_CREATED: {}", achievement.name);
-        msg!("Achievement created: {}", achievement.name);
+        msg!("🔑 ISSUER_VERIFICATION_METHOD_PUBLISHED: did:sol:{}#{}", ctx.accounts.authority.key(), issuer_key.fragment());
         Ok(())
     }
 
-    /// Issue an AchievementCredential (the core VC) with Ed25519 signature verification
-    pub fn issue_achievement_credential(
-        ctx: Context<IssueAchievementCredential>,
-        recipient_pubkey: Pubkey, // Use Pubkey directly instead of string
-        signature_data: Vec<u8>,  // Ed25519 signature (64 bytes)
-        message_data: Vec<u8>,    // The message that was signed
-        timestamp: String,        // ISO 8601 timestamp from client (for coordination)
+    /// Advertise the issuer's badge service endpoint in its did:sol document
+    /// CPIs into sol_did `add_service`; `allow_overwrite` replaces an existing endpoint
+    pub fn add_issuer_service(
+        ctx: Context<AddIssuerService>,
+        service_endpoint: String,
+        allow_overwrite: bool,
     ) -> Result<()> {
-        msg!("🔐 === ON-CHAIN PROOF GENERATION STARTED ===");
-        
-        let credential = &mut ctx.accounts.credential;
-        let authority_key = ctx.accounts.authority.key();
-        let credential_uri = credential.key().to_string(); // Use PDA address as credential URI
-        
-        msg!("📍 Credential URI: {}", credential_uri);
-        msg!("📍 Recipient Pubkey: {}", recipient_pubkey);
-        msg!("📍 Authority (Signer): {}", authority_key);
+        if !service_endpoint.starts_with("https://") {
+            return Err(error!(ValidationError::InvalidUrl));
+        }
+        validation::ensure_max_len(&service_endpoint, MAX_SERVICE_ENDPOINT_LEN, ValidationError::UrlTooLong)?;
         
-        // Core VC fields compliant with Open Badges v3.0
-        // Convert addresses to DID format as per Open Badges 3.0 specification
-        let credential_did = format!("did:sol:{}", credential_uri);
-        let issuer_did = format!("did:sol:{}", ctx.accounts.issuer.key());
-        let recipient_did = format!("did:sol:{}", recipient_pubkey);
-        let achievement_did = format!("did:sol:{}", ctx.accounts.achievement.key());
-        
-        credential.id = credential_did.clone();
-        credential.context = vec![
-            "https://www.w3.org/ns/credentials/v2".to_string(),
-            "https://purl.imsglobal.org/spec/ob/v3p0/context-3.0.3.json".to_string(),
-        ];
-        credential.r#type = vec![
-            "VerifiableCredential".to_string(),
-            "OpenBadgeCredential".to_string(),
-        ];
-        credential.issuer = ctx.accounts.issuer.key();
-        
-        // Use the provided timestamp parameter for consistency
-        // This ensures the same timestamp is used in both view function and credential issuance
-        let client_timestamp = timestamp;
-        
-        msg!("📅 Using provided timestamp: {}", client_timestamp);
-        
-        msg!("⏰ Using timestamp from client's signed message: {}", client_timestamp);
-        
-        // Use the client's timestamp to ensure our generated JSON matches what was signed
-        credential.valid_from = client_timestamp.clone();
-        credential.issued_at = client_timestamp.clone();
-        
-        // Create IdentityObject with simplified parameters
-        let identity_object = IdentityObject {
-            identity_type: "IdentityObject".to_string(),
-            hashed: false, // We store the address directly, not hashed
-            identity_hash: recipient_pubkey.to_string(),
-            identity_type_name: "identifier".to_string(), // Open Badges v3.0 compliant
+        let cpi_program = ctx.accounts.sol_did_program.to_account_info();
+        let cpi_accounts = sol_did_cpi::cpi::accounts::AddService {
+            did_data: ctx.accounts.did_data.to_account_info(),
+            authority: ctx.accounts.authority.to_account_info(),
         };
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        sol_did_cpi::cpi::add_service(
+            cpi_ctx,
+            did::methods::SolanaDidResolver::badge_service(&service_endpoint),
+            allow_overwrite,
+            None,
+        )?;
         
-        // Create AchievementSubject (with DID format for recipient ID)
-        credential.credential_subject = AchievementSubject {
-            id: Some(recipient_did.clone()), // Use DID format for recipient
-            subject_type: vec!["AchievementSubject".to_string()],
-            achievement: ctx.accounts.achievement.key(),
-            identifier: vec![identity_object],
+        msg!("🌐 ISSUER_SERVICE_PUBLISHED: {} -> {}", ctx.accounts.issuer.name, service_endpoint);
+        Ok(())
+    }
+
+    /// Grow the issuer's did:sol account by `additional_size` bytes
+    /// CPIs into sol_did `resize`, so keys and services can be added after
+    /// `initialize_issuer_with_did` without re-creating the DID
+    pub fn expand_issuer_did(ctx: Context<ExpandIssuerDid>, additional_size: u32) -> Result<()> {
+        if additional_size == 0 || additional_size as usize > MAX_DID_EXPANSION {
+            msg!("❌ A DID account grows by 1 to {} bytes per instruction", MAX_DID_EXPANSION);
+            return Err(error!(ValidationError::InvalidCapacity));
+        }
+        let current_size = ctx.accounts.did_data.data_len();
+        let new_size = u32::try_from(current_size)
+            .ok()
+            .and_then(|size| size.checked_add(additional_size))
+            .ok_or(error!(ValidationError::InvalidCapacity))?;
+        
+        let cpi_program = ctx.accounts.sol_did_program.to_account_info();
+        let cpi_accounts = sol_did_cpi::cpi::accounts::Resize {
+            did_data: ctx.accounts.did_data.to_account_info(),
+            payer: ctx.accounts.payer.to_account_info(),
+            authority: ctx.accounts.authority.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
         };
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        sol_did_cpi::cpi::resize(cpi_ctx, new_size, None)?;
         
-        // Create Proof with proper Ed25519 signature
-        msg!("🔐 CREATING DATA INTEGRITY PROOF:");
-        msg!("   → Proof Type: DataIntegrityProof");
-        msg!("   → Cryptosuite: eddsa-rdfc-2022 (Ed25519 + RDF canonicalization)");
-        msg!("   → Proof Purpose: assertionMethod");
-        msg!("   → Created: {}", client_timestamp);
-        msg!("   → Verification Method: {}", authority_key);
-        
-        // Create the credential JSON for signing (using DID format for all identifiers)
-        let credential_json = format!(
-            r#"{{"@context":{},"id":"{}","type":{},"issuer":"{}","validFrom":"{}","credentialSubject":{{"id":"{}","type":{},"achievement":"{}"}}}}"#,
-            serde_json::to_string(&credential.context).unwrap_or_default(),
-            credential_did,
-            serde_json::to_string(&credential.r#type).unwrap_or_default(),
-            issuer_did,
-            credential.valid_from,
-            recipient_did,
-            serde_json::to_string(&vec!["AchievementSubject"]).unwrap_or_default(),
-            achievement_did
-        );
+        msg!("📐 ISSUER_DID_EXPANDED: did:sol:{} {} -> {} bytes", ctx.accounts.authority.key(), current_size, new_size);
+        Ok(())
+    }
+
+    /// Authorize a staff member's did:sol key to sign for the issuer
+    /// Records the issuer's consent in a `StaffKey` PDA and CPIs into sol_did
+    /// `set_controllers` to add the issuer as controller of the staff DID,
+    /// keeping its other controllers; verification requires both
+    pub fn set_staff_did_controller(ctx: Context<SetStaffDidController>) -> Result<()> {
+        let issuer_authority = ctx.accounts.issuer_authority.key();
+        let did_account = credential::read_sol_did_account(&ctx.accounts.staff.key(), &ctx.accounts.staff_did_data)?;
+        let mut native_controllers = did_account.native_controllers;
+        if !native_controllers.contains(&issuer_authority) {
+            native_controllers.push(issuer_authority);
+        }
         
-        msg!("📝 Credential JSON for signing: {} chars", credential_json.len());
-        msg!("🔍 DEBUGGING MESSAGE COMPARISON:");
-        msg!("Expected JSON: {}", credential_json);
-        msg!("Received message (as string): {}", String::from_utf8_lossy(&message_data));
-        msg!("Expected length: {}, Received length: {}", credential_json.len(), message_data.len());
+        let cpi_program = ctx.accounts.sol_did_program.to_account_info();
+        let cpi_accounts = sol_did_cpi::cpi::accounts::SetControllers {
+            did_data: ctx.accounts.staff_did_data.to_account_info(),
+            authority: ctx.accounts.staff.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        sol_did_cpi::cpi::set_controllers(
+            cpi_ctx,
+            sol_did_cpi::SetControllersArg {
+                native_controllers,
+                other_controllers: did_account.other_controllers,
+            },
+            None,
+        )?;
         
-        // Let's also check the first 50 characters of each to see differences
-        let expected_preview = &credential_json[..credential_json.len().min(50)];
-        let received_preview = &String::from_utf8_lossy(&message_data)[..message_data.len().min(50)];
-        msg!("Expected first 50 chars: {}", expected_preview);
-        msg!("Received first 50 chars: {}", received_preview);
+        let staff_key = &mut ctx.accounts.staff_key;
+        staff_key.issuer = ctx.accounts.issuer.key();
+        staff_key.staff = ctx.accounts.staff.key();
+        staff_key.authorized_at = get_current_iso8601()?;
+        staff_key.bump = ctx.bumps.staff_key;
         
-        // Verify the Ed25519 signature using Solana's Ed25519 program
-        if signature_data.len() != 64 {
-            msg!("❌ Invalid signature length: expected 64 bytes, got {}", signature_data.len());
-            return Err(error!(ValidationError::InvalidKeyLength));
-        }
+        msg!("🧑‍💼 STAFF_KEY_AUTHORIZED: {} -> did:sol:{}", ctx.accounts.issuer.name, staff_key.staff);
+        Ok(())
+    }
+
+    /// Withdraw the issuer's consent to a staff key by closing its `StaffKey` PDA
+    /// Proofs by the key stop being trusted for the issuer even while its DID
+    /// still lists the issuer as controller
+    pub fn revoke_staff_key(ctx: Context<RevokeStaffKey>) -> Result<()> {
+        msg!("🧑‍💼 STAFF_KEY_REVOKED: {} -> did:sol:{}", ctx.accounts.issuer.name, ctx.accounts.staff_key.staff);
+        Ok(())
+    }
 
-        // Verify that the provided message matches our expected credential JSON
-        let message_matches = message_data == credential_json.as_bytes();
-        msg!("🔍 MESSAGE COMPARISON RESULT: {}", if message_matches { "MATCH ✅" } else { "MISMATCH ❌" });
+    /// Update the issuer's name, homepage, contact email and image
+    /// Fields are validated as at initialization; the profile is resized to fit
+    pub fn update_issuer_profile(
+        ctx: Context<UpdateIssuerProfile>,
+        name: String,
+        url: Option<String>,
+        email: Option<String>,
+        image: Option<String>,
+    ) -> Result<()> {
+        validation::validate_profile_fields(&name, url.as_deref(), email.as_deref(), image.as_deref())?;
         
-        if !message_matches {
-            msg!("❌ Message mismatch detected:");
-            msg!("Expected length: {}, received length: {}", credential_json.len(), message_data.len());
-            msg!("Expected (full): {}", credential_json);
-            msg!("Received (full): {}", &String::from_utf8_lossy(&message_data));
-            return Err(error!(ValidationError::ValidationFailed)); // STRICT VALIDATION RESTORED
-        }
+        let issuer = &mut ctx.accounts.issuer;
+        issuer.name = name;
+        issuer.url = url;
+        issuer.email = email;
+        issuer.image = image;
         
-        msg!("✅ Message validation passed - JSON structures match exactly");
+        msg!("🪪 ISSUER_PROFILE_UPDATED: {}", issuer.name);
+        Ok(())
+    }
 
-        // Verify the Ed25519 signature
-        msg!("🔐 Verifying Ed25519 signature:");
-        msg!("   → Public Key: {}", authority_key);
-        msg!("   → Message Length: {} bytes", message_data.len());
-        msg!("   → Signature Length: {} bytes", signature_data.len());
-        
-        // Validate signature length first
-        if signature_data.len() != 64 {
-            msg!("❌ Invalid signature length: expected 64 bytes, got {}", signature_data.len());
-            return Err(error!(ValidationError::InvalidKeyLength));
+    /// Set or clear the issuer's branding metadata
+    pub fn update_issuer_branding(
+        ctx: Context<UpdateIssuerBranding>,
+        branding: Option<branding::IssuerBranding>,
+    ) -> Result<()> {
+        if let Some(branding) = &branding {
+            branding.validate()?;
         }
         
-        // Perform actual Ed25519 signature verification using Solana's approach
-        msg!("🔐 Performing Ed25519 signature verification:");
-        msg!("   → Signature (first 8 bytes): {:?}", &signature_data[..8]);
-        msg!("   → Message hash: {:?}", &anchor_lang::solana_program::hash::hash(&message_data).to_bytes()[..8]);
-        
-        // Convert signature data to proper arrays for verification
-        let mut signature_array = [0u8; 64];
-        signature_array.copy_from_slice(&signature_data);
+        let issuer = &mut ctx.accounts.issuer;
+        issuer.branding = branding;
         
-        let public_key_bytes = authority_key.to_bytes();
+        msg!("🎨 ISSUER_BRANDING_UPDATED: {}", issuer.name);
+        Ok(())
+    }
+
+    /// Choose how strictly the issuer's credentials are validated
+    pub fn set_issuer_validation_mode(
+        ctx: Context<SetIssuerValidationMode>,
+        mode: strictness::ValidationMode,
+    ) -> Result<()> {
+        let issuer = &mut ctx.accounts.issuer;
+        issuer.validation_mode = mode;
         
-        // Use the ProofSuite for actual signature verification
-        let verification_result = crate::proof::ProofSuite::verify_ed25519_signature_solana(
-            &message_data,
-            &signature_array,
-            &public_key_bytes,
-        );
+        msg!("🛡️ ISSUER_VALIDATION_MODE_SET: {} -> {:?}", issuer.name, mode);
+        Ok(())
+    }
+
+    /// Choose whether new credentials are identified by `did:sol:` or `urn:uuid:` ids
+    /// Credentials already issued keep their ids
+    pub fn set_issuer_credential_id_format(
+        ctx: Context<SetIssuerCredentialIdFormat>,
+        format: credential_id::CredentialIdFormat,
+    ) -> Result<()> {
+        let issuer = &mut ctx.accounts.issuer;
+        issuer.credential_id_format = format;
         
-        match verification_result {
-            Ok(is_valid) => {
-                if is_valid {
-                    msg!("✅ Ed25519 signature verification: PASSED");
-                    msg!("   → Cryptographic verification successful");
-                    msg!("   → Signature is mathematically valid");
-                } else {
-                    msg!("❌ Ed25519 signature verification: FAILED");
-                    msg!("   → Signature does not match message and public key");
-                    return Err(error!(ValidationError::InvalidSignature));
-                }
-            },
-            Err(e) => {
-                msg!("❌ Ed25519 signature verification error: {:?}", e);
-                return Err(error!(ValidationError::InvalidSignature));
+        msg!("🆔 ISSUER_CREDENTIAL_ID_FORMAT_SET: {} -> {:?}", issuer.name, format);
+        Ok(())
+    }
+
+    /// Cap the issuer's issuance per window of slots, or remove the cap with `None`
+    /// Loosening or removing an existing limit also needs the program admin's signature
+    pub fn set_issuance_rate_limit(
+        ctx: Context<SetIssuanceRateLimit>,
+        limit: Option<rate_limit::RateLimit>,
+    ) -> Result<()> {
+        let admin_signed = match &ctx.accounts.admin {
+            Some(admin) => {
+                require!(
+                    program_config::is_admin(&ctx.accounts.program_config.to_account_info(), ctx.program_id, &admin.key())?,
+                    ValidationError::UnauthorizedAccess
+                );
+                true
             }
-        }
+            None => false,
+        };
+        let issuer = &mut ctx.accounts.issuer;
+        rate_limit::set_limit(issuer, limit, Clock::get()?.slot, admin_signed)?;
+        
+        emit!(events::IssuanceRateLimitUpdated { issuer: issuer.key(), limit });
+        msg!("🚦 ISSUANCE_RATE_LIMIT_SET: {} -> {:?}", issuer.name, limit);
+        Ok(())
+    }
 
-        // Convert verified signature to multibase format (base58btc with 'z' prefix)
-        let proof_value = format!("z{}", bs58::encode(&signature_data).into_string());
+    /// Halt all issuance by the issuer in an emergency
+    /// The issuer authority or the program admin can freeze; only the admin can unfreeze
+    pub fn freeze_issuer(ctx: Context<FreezeIssuer>) -> Result<()> {
+        let signer = ctx.accounts.signer.key();
+        let allowed = signer == ctx.accounts.issuer.authority
+            || program_config::is_admin(&ctx.accounts.program_config.to_account_info(), ctx.program_id, &signer)?;
+        require!(allowed, ValidationError::UnauthorizedAccess);
         
-        msg!("🔐 Creating Data Integrity Proof with verified signature:");
-        msg!("   → Proof Value: {} (length: {})", proof_value, proof_value.len());
+        let issuer = &mut ctx.accounts.issuer;
+        issuer.frozen = true;
         
-        // Create the proof with the verified Ed25519 signature
-        let current_time = get_current_iso8601()?;
-        // Use the issuer's PDA as the verification method (not the authority address)
-        let verification_method = format!("did:sol:{}", ctx.accounts.issuer.key());
+        emit!(events::IssuerFreezeUpdated { issuer: issuer.key(), frozen: true, updated_by: signer });
+        msg!("🧊 ISSUER_FROZEN: {} by {}", issuer.name, signer);
+        Ok(())
+    }
+
+    /// Lift an issuer's freeze; restricted to the program admin
+    pub fn unfreeze_issuer(ctx: Context<FreezeIssuer>) -> Result<()> {
+        let signer = ctx.accounts.signer.key();
+        require!(
+            program_config::is_admin(&ctx.accounts.program_config.to_account_info(), ctx.program_id, &signer)?,
+            ValidationError::UnauthorizedAccess
+        );
         
-        credential.proof = Some(Proof {
-            proof_type: "DataIntegrityProof".to_string(),
-            cryptosuite: "eddsa-rdfc-2022".to_string(),
-            created: current_time.clone(),
-            proof_purpose: "assertionMethod".to_string(),
-            verification_method: verification_method.clone(),
-            proof_value: proof_value.clone(), // Real Ed25519 signature in multibase format
-        });
+        let issuer = &mut ctx.accounts.issuer;
+        issuer.frozen = false;
         
-        msg!("✅ Ed25519 SIGNATURE EMBEDDED IN PROOF");
-        msg!("   → Signature Authority: {}", ctx.accounts.authority.key());
-        msg!("   → Issuer PDA (Verification Method): {}", verification_method);
-        msg!("   → Proof Value (signature): {} (length: {})", proof_value, proof_value.len());
+        emit!(events::IssuerFreezeUpdated { issuer: issuer.key(), frozen: false, updated_by: signer });
+        msg!("🔥 ISSUER_UNFROZEN: {} by {}", issuer.name, signer);
+        Ok(())
+    }
+
+    /// Set or clear the refresh service URL put in the issuer's generated credentials
+    pub fn set_issuer_refresh_service(
+        ctx: Context<SetIssuerRefreshService>,
+        refresh_service_url: Option<String>,
+    ) -> Result<()> {
+        if let Some(url) = &refresh_service_url {
+            refresh::validate_refresh_service_url(url)?;
+        }
         
-        // Status
-        credential.is_revoked = false;
-        credential.bump = ctx.bumps.credential;
+        let issuer = &mut ctx.accounts.issuer;
+        issuer.refresh_service_url = refresh_service_url;
         
-        msg!("🔐 === ON-CHAIN PROOF GENERATION COMPLETED ===");
-        msg!("🏅 CREDENTIAL_ISSUED: {}", ctx.accounts.achievement.name);
-        msg!("✅ AchievementCredential issued for: {}", ctx.accounts.achievement.name);
-        msg!("📋 CRYPTOGRAPHIC SUMMARY:");
-        msg!("   → Credential secured with Ed25519 signature");
-        msg!("   → Data Integrity Proof: EMBEDDED");
-        msg!("   → Open Badges 3.0 compliant");
-        msg!("   → Verifiable on-chain and off-chain");
+        msg!("🔄 ISSUER_REFRESH_SERVICE_SET: {}", issuer.name);
         Ok(())
     }
 
-    /// Issue an AchievementCredential with simple address-based subject
-    pub fn issue_achievement_credential_simple_subject(
-        ctx: Context<IssueAchievementCredential>,
-        recipient_pubkey: Pubkey,
-        signature_data: Vec<u8>,
-        message_data: Vec<u8>,
-        timestamp: String,
+    /// Set the default `termsOfUse` of the issuer's credentials; the profile is resized to fit
+    /// Issuance arguments can replace them for a single credential
+    pub fn set_issuer_terms_of_use(
+        ctx: Context<SetIssuerTermsOfUse>,
+        terms: Vec<terms_of_use::TermsOfUse>,
     ) -> Result<()> {
-        msg!("🔐 === CREDENTIAL ISSUANCE WITH SIMPLE SUBJECT ===");
+        terms_of_use::validate(&terms)?;
         
-        let credential = &mut ctx.accounts.credential;
-        let authority_key = ctx.accounts.authority.key();
-        let credential_uri = credential.key().to_string();
+        let issuer = &mut ctx.accounts.issuer;
+        issuer.terms_of_use = terms;
         
-        msg!("📍 Credential URI: {}", credential_uri);
-        msg!("📍 Recipient Pubkey: {}", recipient_pubkey);
-        msg!("📍 Authority (Signer): {}", authority_key);
+        msg!("📜 ISSUER_TERMS_OF_USE_SET: {} ({} entries)", issuer.name, issuer.terms_of_use.len());
+        Ok(())
+    }
+
+    /// Set the issuer's `otherIdentifier` entries; the profile is resized to fit
+    pub fn set_issuer_other_identifiers(
+        ctx: Context<SetIssuerOtherIdentifiers>,
+        identifiers: Vec<other_identifier::IdentifierEntry>,
+    ) -> Result<()> {
+        other_identifier::validate(&identifiers)?;
         
-        // Core VC fields compliant with Open Badges v3.0
-        let credential_did = format!("did:sol:{}", credential_uri);
-        let issuer_did = format!("did:sol:{}", ctx.accounts.issuer.key());
-        let recipient_simple_id = format!("sol:{}", recipient_pubkey); // Simple address format
-        let achievement_did = format!("did:sol:{}", ctx.accounts.achievement.key());
-        
-        credential.id = credential_did.clone();
-        credential.context = vec![
-            "https://www.w3.org/ns/credentials/v2".to_string(),
-            "https://purl.imsglobal.org/spec/ob/v3p0/context-3.0.3.json".to_string(),
-        ];
-        credential.r#type = vec![
-            "VerifiableCredential".to_string(),
-            "OpenBadgeCredential".to_string(),
-        ];
-        credential.issuer = ctx.accounts.issuer.key();
-        
-        let client_timestamp = timestamp;
-        msg!("📅 Using provided timestamp: {}", client_timestamp);
-        
-        credential.valid_from = client_timestamp.clone();
-        credential.issued_at = client_timestamp.clone();
-        
-        // Create IdentityObject with simple address
-        let identity_object = IdentityObject {
-            identity_type: "IdentityObject".to_string(),
-            hashed: false,
-            identity_hash: recipient_pubkey.to_string(),
-            identity_type_name: "identifier".to_string(),
-        };
+        let issuer = &mut ctx.accounts.issuer;
+        issuer.other_identifier = identifiers;
         
-        // Create AchievementSubject with simple address format
-        credential.credential_subject = AchievementSubject {
-            id: Some(recipient_simple_id.clone()), // Simple sol: format
-            subject_type: vec!["AchievementSubject".to_string()],
-            achievement: ctx.accounts.achievement.key(),
-            identifier: vec![identity_object],
-        };
+        msg!("🪪 ISSUER_OTHER_IDENTIFIERS_SET: {} ({} entries)", issuer.name, issuer.other_identifier.len());
+        Ok(())
+    }
+
+    /// Set the translations of the issuer's name; the profile is resized to fit
+    pub fn set_issuer_name_translations(
+        ctx: Context<SetIssuerNameTranslations>,
+        translations: Vec<i18n::LocalizedText>,
+    ) -> Result<()> {
+        i18n::validate(&translations)?;
         
-        // Create the credential JSON for signing
-        let credential_json = format!(
-            r#"{{"@context":{},"id":"{}","type":{},"issuer":"{}","validFrom":"{}","credentialSubject":{{"id":"{}","type":{},"achievement":"{}"}}}}"#,
-            serde_json::to_string(&credential.context).unwrap_or_default(),
-            credential_did,
-            serde_json::to_string(&credential.r#type).unwrap_or_default(),
-            issuer_did,
-            credential.valid_from,
-            recipient_simple_id, // Use simple address in JSON
-            serde_json::to_string(&vec!["AchievementSubject"]).unwrap_or_default(),
-            achievement_did
-        );
+        let issuer = &mut ctx.accounts.issuer;
+        issuer.name_i18n = translations;
         
-        // Verify message and signature (same as existing implementation)
-        let message_matches = message_data == credential_json.as_bytes();
-        if !message_matches {
-            return Err(error!(ValidationError::ValidationFailed));
-        }
+        msg!("🌐 ISSUER_NAME_TRANSLATIONS_SET: {} ({} languages)", issuer.name, issuer.name_i18n.len());
+        Ok(())
+    }
+
+    /// Create an achievement definition
+    pub fn create_achievement(
+        ctx: Context<CreateAchievement>,
+        args: args::CreateAchievementArgs,
+    ) -> Result<()> {
+        args.ensure_supported()?;
+        let args::CreateAchievementArgs {
+            achievement_id,
+            name,
+            description,
+            criteria_narrative,
+            criteria_id,
+            image,
+            result_descriptions,
+            extensions,
+            achievement_type,
+            requirements,
+            name_i18n,
+            description_i18n,
+            requires_approved_result,
+            ..
+        } = args;
         
-        // Ed25519 signature verification
-        if signature_data.len() != 64 {
-            return Err(error!(ValidationError::InvalidKeyLength));
+        validation::validate_achievement_lengths(
+            &achievement_id,
+            &name,
+            &description,
+            criteria_id.as_deref(),
+            criteria_narrative.as_deref(),
+        )?;
+        if let Some(image) = &image {
+            validation::validate_image_uri(image)?;
         }
-        
-        let mut signature_array = [0u8; 64];
-        signature_array.copy_from_slice(&signature_data);
-        let public_key_bytes = authority_key.to_bytes();
-        
-        let verification_result = crate::proof::ProofSuite::verify_ed25519_signature_solana(
-            &message_data,
-            &signature_array,
-            &public_key_bytes,
-        );
-        
-        match verification_result {
-            Ok(is_valid) => {
-                if !is_valid {
-                    return Err(error!(ValidationError::InvalidSignature));
-                }
-            },
-            Err(_) => return Err(error!(ValidationError::InvalidSignature)),
+        if let Some(achievement_type) = &achievement_type {
+            validation::validate_achievement_type_term(achievement_type)?;
         }
+        results::validate_result_descriptions(&result_descriptions)?;
+        criteria::validate_requirements(&requirements, &result_descriptions)?;
+        extensions::validate_extensions(&extensions)?;
+        i18n::validate(&name_i18n)?;
+        i18n::validate(&description_i18n)?;
         
-        // Create proof
-        let proof_value = format!("z{}", bs58::encode(&signature_data).into_string());
-        let current_time = get_current_iso8601()?;
-        let verification_method = format!("did:sol:{}", ctx.accounts.issuer.key());
+        let achievement = &mut ctx.accounts.achievement;
         
-        credential.proof = Some(Proof {
-            proof_type: "DataIntegrityProof".to_string(),
-            cryptosuite: "eddsa-rdfc-2022".to_string(),
-            created: current_time,
-            proof_purpose: "assertionMethod".to_string(),
-            verification_method,
-            proof_value,
-        });
+        // Set Open Badges v3.0 context (REQUIRED)
+        achievement.context = canonical::CREDENTIAL_CONTEXT.iter().map(|iri| iri.to_string()).collect();
+        
+        achievement.id = achievement_id;
+        achievement.r#type = vec!["Achievement".to_string()];
+        achievement.issuer = ctx.accounts.issuer.key();
+        achievement.name = name;
+        achievement.description = description;
+        achievement.criteria = Criteria {
+            id: criteria_id,
+            narrative: criteria_narrative,
+        };
+        // The creator is an existing Profile, checked by the account constraints
+        achievement.creator = ctx.accounts.creator.as_ref().map(|creator| creator.key());
+        achievement.image = image;
+        achievement.achievement_type = achievement_type;
+        achievement.result_descriptions = result_descriptions;
+        achievement.requirements = requirements;
+        achievement.requires_approved_result = requires_approved_result;
+        // Extension terms need their defining contexts in @context
+        let extension_contexts = extensions::missing_contexts(&extensions, &achievement.context);
+        achievement.context.extend(extension_contexts);
+        achievement.extensions = extensions;
+        achievement.name_i18n = name_i18n;
+        achievement.description_i18n = description_i18n;
+        achievement.created_at = get_current_iso8601()?;
+        achievement.bump = ctx.bumps.achievement;
+        
+        achievement_directory::record(
+            &mut ctx.accounts.achievement_directory,
+            &mut ctx.accounts.achievement_directory_page,
+            ctx.accounts.issuer.key(),
+            (ctx.bumps.achievement_directory, ctx.bumps.achievement_directory_page),
+            ctx.accounts.achievement.key(),
+        )?;
+        
+        let achievement = &ctx.accounts.achievement;
+        msg!("🎯 ACHIEVEMENT_CREATED: {}", achievement.name);
+        msg!("Achievement created: {}", achievement.name);
+        Ok(())
+    }
+
+    /// Issue an AchievementCredential (the core VC) with Ed25519 signature verification
+    pub fn issue_achievement_credential(
+        ctx: Context<IssueAchievementCredential>,
+        args: args::IssueCredentialArgs,
+    ) -> Result<()> {
+        profiling::checkpoint("issue: start");
+        issue_signed_credential(ctx, args, issuance::Subject::Did)?;
+        profiling::checkpoint("issue: done");
+        Ok(())
+    }
+
+    /// Issue an AchievementCredential with simple address-based subject
+    pub fn issue_achievement_credential_simple_subject(
+        ctx: Context<IssueAchievementCredential>,
+        args: args::IssueCredentialArgs,
+    ) -> Result<()> {
+        issue_signed_credential(ctx, args, issuance::Subject::Address)
+    }
+
+    /// Issue an AchievementCredential with a pairwise pseudonymous subject
+    /// The subject id is derived per issuer by the holder from a secret only they
+    /// hold, and handed to the issuer in `args.pairwise_subject_id`
+    pub fn issue_achievement_credential_pairwise_subject(
+        ctx: Context<IssueAchievementCredential>,
+        args: args::IssueCredentialArgs,
+    ) -> Result<()> {
+        // The bound key would link the pseudonym to its wallet
+        require!(!args.bind_holder, ValidationError::HolderBindingUnavailable);
+        // Only the holder can derive the pseudonym, so the issuer passes the one it was given
+        let pairwise_id = args
+            .pairwise_subject_id
+            .clone()
+            .ok_or_else(|| error!(ValidationError::InvalidPairwiseSubjectId))?;
+        privacy::validate_pairwise_subject_id(&pairwise_id)?;
+        issue_signed_credential(ctx, args, issuance::Subject::Pairwise(pairwise_id))
+    }
+
+    /// Issue an AchievementCredential signed by a registered issuer key
+    /// The proof references the key as `did:sol:<issuer>#key-N`
+    pub fn issue_achievement_credential_with_key(
+        ctx: Context<IssueAchievementCredentialWithKey>,
+        args: args::IssueCredentialArgs,
+    ) -> Result<()> {
+        profiling::checkpoint("issue_with_key: start");
+        args.ensure_supported()?;
+        let accounts = ctx.accounts;
+        
+        issuance::check(
+            &mut accounts.issuer,
+            &accounts.achievement,
+            issuance::CheckAccounts {
+                program_config: &accounts.program_config,
+                treasury: &accounts.treasury,
+                fee_token_accounts: [
+                    accounts.fee_token_account.as_ref(),
+                    accounts.treasury_token_account.as_ref(),
+                    accounts.token_program.as_ref(),
+                ],
+                payer: &accounts.authority,
+                system_program: &accounts.system_program,
+                cpi_policy: accounts.cpi_policy.as_deref(),
+                instructions: Some(accounts.instructions.as_ref()),
+                recipient_opt_out: &accounts.recipient_opt_out,
+                result_entry: accounts.result_entry.as_deref(),
+                rubric: accounts.rubric.as_deref(),
+            },
+            &args.recipient_pubkey,
+            &args.results,
+            ctx.program_id,
+        )?;
+        let content = issuance::CredentialContent::requested(
+            &args,
+            issuance::Subject::Did,
+            &accounts.issuer,
+            accounts.schema_registry.as_deref(),
+        )?;
         
-        credential.is_revoked = false;
-        credential.bump = ctx.bumps.credential;
+        let issuer_key = &accounts.issuer_key;
+        if !issuer_key.is_valid_for_proof_at(&get_current_iso8601()?)? {
+            msg!("❌ Issuer key {} is not active", issuer_key.fragment());
+            return Err(error!(ValidationError::VerificationMethodNotFound));
+        }
+        let verification_method = issuer_key.verification_method_id();
+        msg!("🔐 Issuing {} with verification method {}", accounts.credential.key(), verification_method);
+        
+        // Issuing against a nomination approves it and links the credential
+        if let Some(nomination) = accounts.nomination.as_mut() {
+            nomination::accept_nomination(nomination, &args.recipient_pubkey, &accounts.credential.key(), get_current_iso8601()?)?;
+        }
+        // A signed payload issues at most one credential
+        nonce::consume(
+            &mut accounts.consumed_payload,
+            accounts.issuer.key(),
+            ctx.bumps.consumed_payload,
+            &args.message_data,
+        )?;
+        
+        issuance::populate_credential(
+            issuance::IssuanceAccounts {
+                credential: &mut accounts.credential,
+                issuer: &accounts.issuer,
+                achievement: &accounts.achievement,
+                issuer_stats: &mut accounts.issuer_stats,
+                achievement_stats: &mut accounts.achievement_stats,
+                credential_index: &mut accounts.credential_index,
+                credential_index_page: &mut accounts.credential_index_page,
+                recipient_roster: &mut accounts.recipient_roster,
+                recipient_roster_page: &mut accounts.recipient_roster_page,
+                payer: accounts.authority.to_account_info(),
+                system_program: accounts.system_program.to_account_info(),
+            },
+            issuance::IssuanceBumps {
+                credential: ctx.bumps.credential,
+                issuer_stats: ctx.bumps.issuer_stats,
+                achievement_stats: ctx.bumps.achievement_stats,
+                credential_index: ctx.bumps.credential_index,
+                credential_index_page: ctx.bumps.credential_index_page,
+                recipient_roster: ctx.bumps.recipient_roster,
+                recipient_roster_page: ctx.bumps.recipient_roster_page,
+            },
+            args.recipient_pubkey,
+            content,
+            // Verified against the selected issuer key, not the paying authority
+            Some(issuance::IssuerSignature {
+                instructions: &accounts.instructions,
+                signing_key: issuer_key.public_key,
+                verification_method,
+                message: &args.message_data,
+                signature: &args.signature_data,
+            }),
+        )?;
+        issuance::emit_issued(&accounts.credential, args.recipient_pubkey);
         
-        msg!("✅ CREDENTIAL_ISSUED with simple subject: {}", recipient_simple_id);
+        msg!("✅ CREDENTIAL_ISSUED: {}", accounts.achievement.name);
+        profiling::checkpoint("issue_with_key: done");
         Ok(())
     }
 
@@ -482,14 +1062,18 @@ pub mod open_badges {
         let revocation_list = &mut ctx.accounts.revocation_list;
         let current_timestamp = get_current_iso8601()?;
         
-        // Validate inputs
-        if capacity == 0 || capacity > 1_000_000 {
+        // Validate inputs; a list at the largest capacity still fits one account creation
+        if capacity == 0 || capacity > credential_status::MAX_REVOCATION_LIST_CAPACITY {
             return Err(error!(ValidationError::InvalidCapacity));
         }
         
         if name.is_empty() || description.is_empty() {
             return Err(error!(ValidationError::MissingRequiredField));
         }
+        validation::ensure_max_len(&list_id, credential_status::MAX_LIST_ID_LEN, ValidationError::IdTooLong)?;
+        validation::ensure_max_len(&name, credential_status::MAX_LIST_NAME_LEN, ValidationError::NameTooLong)?;
+        validation::ensure_max_len(&description, credential_status::MAX_LIST_DESCRIPTION_LEN, ValidationError::DescriptionTooLong)?;
+        validation::ensure_max_len(&status_list_url, credential_status::MAX_STATUS_LIST_URL_LEN, ValidationError::UrlTooLong)?;
         
         // Initialize the revocation list
         let new_revocation_list = credential_status::RevocationList::new(
@@ -509,12 +1093,28 @@ pub mod open_badges {
         Ok(())
     }
     
+    /// Grow a revocation list's bitfield, reallocating the account
+    /// Lists can start small and grow up to `MAX_REVOCATION_LIST_CAPACITY`
+    pub fn resize_revocation_list(
+        ctx: Context<ResizeRevocationList>,
+        new_capacity: u32,
+    ) -> Result<()> {
+        let revocation_list = &mut ctx.accounts.revocation_list;
+        let previous_capacity = revocation_list.capacity;
+        revocation_list.grow(new_capacity, get_current_iso8601()?)?;
+        
+        msg!("✅ Resized revocation list '{}' from {} to {}", revocation_list.list_id, previous_capacity, new_capacity);
+        Ok(())
+    }
+    
     /// Revoke a credential by setting its status bit
     pub fn revoke_credential(
         ctx: Context<UpdateCredentialStatus>,
         credential_index: u32,
         reason: String,
     ) -> Result<()> {
+        program_config::ensure_not_paused(&ctx.accounts.program_config.to_account_info(), ctx.program_id)?;
+        
         let revocation_list = &mut ctx.accounts.revocation_list;
         let current_timestamp = get_current_iso8601()?;
         
@@ -536,6 +1136,8 @@ pub mod open_badges {
         credential_index: u32,
         reason: String,
     ) -> Result<()> {
+        program_config::ensure_not_paused(&ctx.accounts.program_config.to_account_info(), ctx.program_id)?;
+        
         let revocation_list = &mut ctx.accounts.revocation_list;
         let current_timestamp = get_current_iso8601()?;
         
@@ -558,6 +1160,8 @@ pub mod open_badges {
         indices_to_reactivate: Vec<u32>,
         reason: String,
     ) -> Result<()> {
+        program_config::ensure_not_paused(&ctx.accounts.program_config.to_account_info(), ctx.program_id)?;
+        
         let revocation_list = &mut ctx.accounts.revocation_list;
         let current_timestamp = get_current_iso8601()?;
         
@@ -584,7 +1188,191 @@ pub mod open_badges {
             reason
         );
         Ok(())
-    }    /// Batch credential issuance with DID-based subjects
+    }
+    
+    /// Generate the unsigned StatusList2021 credential JSON for a revocation list
+    /// View function: the authority signs the returned JSON and passes the
+    /// signature to `publish_status_list_credential` with the same timestamp
+    pub fn generate_status_list_credential_json(
+        ctx: Context<ReadRevocationList>,
+        timestamp: String,
+    ) -> Result<String> {
+        let revocation_list = &ctx.accounts.revocation_list;
+        let issuer_did = format!("did:sol:{}", revocation_list.authority);
+        
+        let status_list_credential = revocation_list.generate_status_list_credential(&issuer_did, &timestamp)?;
+        status_list_credential.to_signing_json()
+    }
+    
+    /// Publish a signed StatusList2021 credential for a revocation list
+    /// An earlier Ed25519 program instruction must verify the authority's
+    /// signature over the generated JSON; attaches a Data Integrity proof,
+    /// stores the result and emits it
+    pub fn publish_status_list_credential(
+        ctx: Context<PublishStatusListCredential>,
+        timestamp: String,
+        signature_data: Vec<u8>,
+    ) -> Result<()> {
+        let revocation_list = &ctx.accounts.revocation_list;
+        let authority_key = ctx.accounts.authority.key();
+        let issuer_did = format!("did:sol:{}", authority_key);
+        
+        // Reject malformed timestamps before they end up in a signed document
+        parse_iso8601_to_unix(&timestamp)?;
+        
+        let mut status_list_credential = revocation_list.generate_status_list_credential(&issuer_did, &timestamp)?;
+        let signing_json = status_list_credential.to_signing_json()?;
+        
+        let signature: [u8; 64] = signature_data.as_slice().try_into().map_err(|_| {
+            msg!("❌ Invalid signature length: expected 64 bytes, got {}", signature_data.len());
+            error!(ValidationError::InvalidSignatureLength)
+        })?;
+        proof::ensure_ed25519_verified(&ctx.accounts.instructions, &authority_key, signing_json.as_bytes(), &signature)?;
+        
+        status_list_credential.proof = Some(crate::proof::DataIntegrityProof {
+            proof_type: "DataIntegrityProof".to_string(),
+            cryptosuite: "eddsa-rdfc-2022".to_string(),
+            created: timestamp,
+            verification_method: issuer_did,
+            proof_purpose: "assertionMethod".to_string(),
+            proof_value: multibase::encode(&signature_data),
+            challenge: None,
+            domain: None,
+        });
+        
+        let credential_json = status_list_credential.to_json()?;
+        if credential_json.len() > credential_status::MAX_STATUS_LIST_CREDENTIAL_LEN {
+            msg!("❌ Status list credential is {} bytes, limit is {}", credential_json.len(), credential_status::MAX_STATUS_LIST_CREDENTIAL_LEN);
+            return Err(error!(ValidationError::FieldTooLong));
+        }
+        
+        let published_at = get_current_iso8601()?;
+        let published = &mut ctx.accounts.status_list_credential;
+        published.revocation_list = revocation_list.key();
+        published.authority = authority_key;
+        published.credential_json = credential_json.clone();
+        published.list_updated_at = revocation_list.updated_at.clone();
+        published.published_at = published_at.clone();
+        published.bump = ctx.bumps.status_list_credential;
+        
+        emit!(events::StatusListPublished {
+            revocation_list: revocation_list.key(),
+            status_list_credential: published.key(),
+            credential_json,
+            published_at,
+        });
+        
+        msg!("✅ Published status list credential for list '{}'", revocation_list.list_id);
+        Ok(())
+    }
+    
+    /// Create the program-wide batch limits; only the program's upgrade authority can
+    pub fn initialize_batch_config(
+        ctx: Context<InitializeBatchConfig>,
+        admin: Pubkey,
+        max_batch_size: u8,
+        compute_budget: u32,
+    ) -> Result<()> {
+        batch::BatchLimits { max_batch_size, compute_budget }.validate()?;
+        
+        let config = &mut ctx.accounts.batch_config;
+        config.admin = admin;
+        config.max_batch_size = max_batch_size;
+        config.compute_budget = compute_budget;
+        config.bump = ctx.bumps.batch_config;
+        
+        msg!("⚙️ Batch config created: {} requests, {} CU", max_batch_size, compute_budget);
+        Ok(())
+    }
+
+    /// Change the batch limits; restricted to the config's admin
+    pub fn update_batch_config(
+        ctx: Context<UpdateBatchConfig>,
+        max_batch_size: u8,
+        compute_budget: u32,
+    ) -> Result<()> {
+        batch::BatchLimits { max_batch_size, compute_budget }.validate()?;
+        
+        let config = &mut ctx.accounts.batch_config;
+        config.max_batch_size = max_batch_size;
+        config.compute_budget = compute_budget;
+        
+        msg!("⚙️ Batch config updated: {} requests, {} CU", max_batch_size, compute_budget);
+        Ok(())
+    }
+
+    /// Create the program config and its treasury; only the program's upgrade authority can
+    pub fn initialize_program_config(
+        ctx: Context<InitializeProgramConfig>,
+        admin: Pubkey,
+        issuance_fee_lamports: u64,
+        token_fee: Option<program_config::TokenFee>,
+    ) -> Result<()> {
+        program_config::validate_fee(token_fee.as_ref())?;
+        
+        let config = &mut ctx.accounts.program_config;
+        config.admin = admin;
+        config.paused = false;
+        config.issuance_fee_lamports = issuance_fee_lamports;
+        config.token_fee = token_fee;
+        config.bump = ctx.bumps.program_config;
+        ctx.accounts.treasury.bump = ctx.bumps.treasury;
+        
+        emit!(events::ProgramConfigUpdated { admin, paused: false, issuance_fee_lamports, token_fee });
+        msg!("⚙️ Program config created, admin {}", admin);
+        Ok(())
+    }
+
+    /// Pause or resume the program and change its fees; restricted to the config's admin
+    pub fn set_config(
+        ctx: Context<SetConfig>,
+        paused: bool,
+        issuance_fee_lamports: u64,
+        token_fee: Option<program_config::TokenFee>,
+    ) -> Result<()> {
+        program_config::validate_fee(token_fee.as_ref())?;
+        
+        let config = &mut ctx.accounts.program_config;
+        config.paused = paused;
+        config.issuance_fee_lamports = issuance_fee_lamports;
+        config.token_fee = token_fee;
+        
+        emit!(events::ProgramConfigUpdated { admin: config.admin, paused, issuance_fee_lamports, token_fee });
+        msg!("⚙️ Program config updated: paused={}, fee={} lamports", paused, issuance_fee_lamports);
+        Ok(())
+    }
+
+    /// Send collected fees to `destination`; restricted to the config's admin
+    /// Withdraws tokens when a treasury token account is passed, lamports otherwise
+    pub fn withdraw_treasury(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
+        let treasury_info = ctx.accounts.treasury.to_account_info();
+        let destination = ctx.accounts.destination.to_account_info();
+        
+        let mint = match (&ctx.accounts.treasury_token_account, &ctx.accounts.token_program) {
+            (Some(source), Some(token_program)) => {
+                treasury::withdraw_tokens(
+                    &treasury_info,
+                    ctx.accounts.treasury.bump,
+                    &source.to_account_info(),
+                    &destination,
+                    &token_program.to_account_info(),
+                    amount,
+                )?;
+                Some(treasury::read_token_account(&source.to_account_info())?.mint)
+            }
+            (None, None) => {
+                treasury::withdraw_lamports(&treasury_info, &destination, amount)?;
+                None
+            }
+            _ => return Err(error!(ValidationError::InvalidFeeAccount)),
+        };
+        
+        emit!(events::TreasuryWithdrawn { destination: destination.key(), mint, amount });
+        msg!("💸 Withdrew {} from the treasury to {}", amount, destination.key());
+        Ok(())
+    }
+
+    /// Batch credential issuance with DID-based subjects
     /// Issues multiple credentials in a single transaction by calling issue_achievement_credential logic
     pub fn batch_issue_achievement_credentials_with_did(
         ctx: Context<BatchIssueCredentials>,
@@ -593,14 +1381,20 @@ pub mod open_badges {
         message_data: Vec<u8>,
         timestamp: String,
     ) -> Result<()> {
+        profiling::checkpoint("batch_with_did: start");
+        program_config::ensure_not_paused(&ctx.accounts.program_config.to_account_info(), ctx.program_id)?;
+        // Nothing is issued yet, so no quota is charged; a frozen issuer is still refused
+        rate_limit::ensure_not_frozen(&ctx.accounts.issuer)?;
+        
         msg!("🔐 === BATCH CREDENTIAL ISSUANCE WITH DID ===");
         msg!("📊 Batch size: {} credentials", requests.len());
         msg!("📍 Authority: {}", ctx.accounts.authority.key());
         msg!("📍 Issuer: {}", ctx.accounts.issuer.key());
         
-        // Core Open Badges requirement: Must have requests
-        require!(!requests.is_empty(), ValidationError::EmptyBatch);
-        require!(requests.len() <= 10, ValidationError::BatchSizeTooLarge); // Reasonable batch limit
+        // Reject empty batches and batches over the configured size or compute budget
+        batch::BatchLimits::from_config(ctx.accounts.batch_config.as_deref()).check(&requests)?;
+        batch::ensure_no_notes(&requests)?;
+        validation::validate_timestamp_len(&timestamp)?;
         
         // Validate the batch signature format (same as single credential)
         require!(signature_data.len() == 64, ValidationError::InvalidSignatureLength);
@@ -609,30 +1403,22 @@ pub mod open_badges {
         let expected_batch_message = format!("batch_issue_{}_{}", requests.len(), timestamp);
         require!(message_data == expected_batch_message.as_bytes(), ValidationError::ValidationFailed);
         
+        // Each recipient's opt-out PDA is passed in remaining_accounts, in request order
+        require!(ctx.remaining_accounts.len() == requests.len(), ValidationError::InvalidOptOutAccount);
+        for (request, opt_out_info) in requests.iter().zip(ctx.remaining_accounts.iter()) {
+            opt_out::ensure_not_opted_out(opt_out_info, &request.recipient_pubkey, ctx.program_id)?;
+        }
+        
         // Verify the Ed25519 signature for the batch (same verification logic as single credential)
         let mut signature_array = [0u8; 64];
         signature_array.copy_from_slice(&signature_data);
-        let public_key_bytes = ctx.accounts.authority.key().to_bytes();
         
-        let verification_result = crate::proof::ProofSuite::verify_ed25519_signature_solana(
+        proof::ensure_ed25519_verified(
+            &ctx.accounts.instructions,
+            &ctx.accounts.authority.key(),
             &message_data,
             &signature_array,
-            &public_key_bytes,
-        );
-        
-        match verification_result {
-            Ok(is_valid) => {
-                if !is_valid {
-                    msg!("❌ Batch signature verification failed");
-                    return Err(error!(ValidationError::InvalidSignature));
-                }
-                msg!("✅ Batch signature verification passed");
-            },
-            Err(_) => {
-                msg!("❌ Batch signature verification error");
-                return Err(error!(ValidationError::InvalidSignature));
-            }
-        }
+        )?;
         
         // Process each credential in the batch - CREATE ACTUAL CREDENTIAL ACCOUNTS
         for (index, request) in requests.iter().enumerate() {
@@ -652,7 +1438,7 @@ pub mod open_badges {
             // Derive credential PDA using same seeds as single credential function
             let issuer_key = ctx.accounts.issuer.key();
             let credential_seeds = &[
-                b"credential",
+                pda::CREDENTIAL_SEED,
                 achievement_pubkey.as_ref(),
                 issuer_key.as_ref(),
                 request.recipient_pubkey.as_ref(),
@@ -663,7 +1449,7 @@ pub mod open_badges {
             msg!("🔑 PDA bump: {}", credential_bump);
             
             // Generate DID format identifiers using the credential PDA
-            let credential_did = format!("did:sol:{}", credential_pda);
+            let credential_did = credential_id::credential_id(ctx.accounts.issuer.credential_id_format, &credential_pda);
             let issuer_did = format!("did:sol:{}", issuer_key);
             let recipient_did = format!("did:sol:{}", request.recipient_pubkey);
             let achievement_did = format!("did:sol:{}", achievement_pubkey);
@@ -675,14 +1461,13 @@ pub mod open_badges {
             msg!("   → Achievement: {}", achievement_did);
             
             // Create the credential JSON structure (same format as single credential)
-            let credential_json = format!(
-                r#"{{"@context":["https://www.w3.org/ns/credentials/v2","https://purl.imsglobal.org/spec/ob/v3p0/context-3.0.3.json"],"id":"{}","type":["VerifiableCredential","OpenBadgeCredential"],"issuer":"{}","validFrom":"{}","credentialSubject":{{"id":"{}","type":["AchievementSubject"],"achievement":"{}"}}}}"#,
-                credential_did,
-                issuer_did,
-                timestamp,
-                recipient_did,
-                achievement_did
-            );
+            let credential_json = canonical::build_signing_json(&canonical::SigningJsonParams::new(
+                &credential_did,
+                &issuer_did,
+                &timestamp,
+                &recipient_did,
+                &achievement_did,
+            ));
             
             msg!("📝 Credential {} JSON structure created ({} chars)", index + 1, credential_json.len());
             
@@ -690,7 +1475,7 @@ pub mod open_badges {
             msg!("🏗️ Creating credential PDA account: {}", credential_pda);
             
             // Calculate space needed for AchievementCredential (same as single credential)
-            let space = 8 + 4 + 200 + 4 + 200 + 4 + 100 + 32 + 8 + 8 + 4 + 100 + 4 + 50 + 32 + 4 + 200 + 4 + 200 + 8 + 4 + 50 + 4 + 200 + 4 + 200 + 1 + 8 + 1;
+            let space = AchievementCredential::issuance_space(timestamp.len(), &[], None, &[], &[]);
             let rent = Rent::get()?;
             let lamports = rent.minimum_balance(space);
             
@@ -724,6 +1509,7 @@ pub mod open_badges {
         msg!("🔐 All credentials structured according to Open Badges 3.0 specification");
         msg!("🏗️ All credential PDAs derived using same logic as single credential issuance");
         msg!("📝 Implementation status: Validation complete, needs PDA account creation");
+        profiling::checkpoint("batch_with_did: done");
         Ok(())
     }
 
@@ -736,14 +1522,20 @@ pub mod open_badges {
         message_data: Vec<u8>,
         timestamp: String,
     ) -> Result<()> {
+        profiling::checkpoint("batch_simple: start");
+        program_config::ensure_not_paused(&ctx.accounts.program_config.to_account_info(), ctx.program_id)?;
+        // Nothing is issued yet, so no quota is charged; a frozen issuer is still refused
+        rate_limit::ensure_not_frozen(&ctx.accounts.issuer)?;
+        
         msg!("🔐 === BATCH CREDENTIAL ISSUANCE WITH SIMPLE SUBJECTS ===");
         msg!("📊 Batch size: {} credentials", requests.len());
         msg!("📍 Authority: {}", ctx.accounts.authority.key());
         msg!("📍 Issuer: {}", ctx.accounts.issuer.key());
         
-        // Core Open Badges requirement: Must have requests
-        require!(!requests.is_empty(), ValidationError::EmptyBatch);
-        require!(requests.len() <= 10, ValidationError::BatchSizeTooLarge); // Reasonable batch limit
+        // Reject empty batches and batches over the configured size or compute budget
+        batch::BatchLimits::from_config(ctx.accounts.batch_config.as_deref()).check(&requests)?;
+        batch::ensure_no_notes(&requests)?;
+        validation::validate_timestamp_len(&timestamp)?;
         
         // Validate the batch signature format (same as single credential)
         require!(signature_data.len() == 64, ValidationError::InvalidSignatureLength);
@@ -752,30 +1544,22 @@ pub mod open_badges {
         let expected_batch_message = format!("batch_issue_simple_{}_{}", requests.len(), timestamp);
         require!(message_data == expected_batch_message.as_bytes(), ValidationError::ValidationFailed);
         
+        // Each recipient's opt-out PDA is passed in remaining_accounts, in request order
+        require!(ctx.remaining_accounts.len() == requests.len(), ValidationError::InvalidOptOutAccount);
+        for (request, opt_out_info) in requests.iter().zip(ctx.remaining_accounts.iter()) {
+            opt_out::ensure_not_opted_out(opt_out_info, &request.recipient_pubkey, ctx.program_id)?;
+        }
+        
         // Verify the Ed25519 signature for the batch (same verification logic as single credential)
         let mut signature_array = [0u8; 64];
         signature_array.copy_from_slice(&signature_data);
-        let public_key_bytes = ctx.accounts.authority.key().to_bytes();
         
-        let verification_result = crate::proof::ProofSuite::verify_ed25519_signature_solana(
+        proof::ensure_ed25519_verified(
+            &ctx.accounts.instructions,
+            &ctx.accounts.authority.key(),
             &message_data,
             &signature_array,
-            &public_key_bytes,
-        );
-        
-        match verification_result {
-            Ok(is_valid) => {
-                if !is_valid {
-                    msg!("❌ Batch signature verification failed");
-                    return Err(error!(ValidationError::InvalidSignature));
-                }
-                msg!("✅ Batch signature verification passed");
-            },
-            Err(_) => {
-                msg!("❌ Batch signature verification error");
-                return Err(error!(ValidationError::InvalidSignature));
-            }
-        }
+        )?;
         
         // Process each credential in the batch - CREATE ACTUAL CREDENTIAL ACCOUNTS
         for (index, request) in requests.iter().enumerate() {
@@ -795,7 +1579,7 @@ pub mod open_badges {
             // Derive credential PDA using same seeds as single credential function
             let issuer_key = ctx.accounts.issuer.key();
             let credential_seeds = &[
-                b"credential",
+                pda::CREDENTIAL_SEED,
                 achievement_pubkey.as_ref(),
                 issuer_key.as_ref(),
                 request.recipient_pubkey.as_ref(),
@@ -816,14 +1600,13 @@ pub mod open_badges {
             msg!("   → Achievement ID: {}", achievement_pubkey);
             
             // Create the credential JSON structure (simple address format, no DID conversion)
-            let credential_json = format!(
-                r#"{{"@context":["https://www.w3.org/ns/credentials/v2","https://purl.imsglobal.org/spec/ob/v3p0/context-3.0.3.json"],"id":"{}","type":["VerifiableCredential","OpenBadgeCredential"],"issuer":"{}","validFrom":"{}","credentialSubject":{{"id":"{}","type":["AchievementSubject"],"achievement":"{}"}}}}"#,
-                credential_uri,
-                issuer_key,
-                timestamp,
-                recipient_address,
-                achievement_pubkey
-            );
+            let credential_json = canonical::build_signing_json(&canonical::SigningJsonParams::new(
+                &credential_uri,
+                &issuer_key.to_string(),
+                &timestamp,
+                &recipient_address,
+                &achievement_pubkey.to_string(),
+            ));
             
             msg!("📝 Credential {} JSON structure created ({} chars)", index + 1, credential_json.len());
             
@@ -831,7 +1614,7 @@ pub mod open_badges {
             msg!("🏗️ Creating credential PDA account: {}", credential_pda);
             
             // Calculate space needed for AchievementCredential (same as single credential)
-            let space = 8 + 4 + 200 + 4 + 200 + 4 + 100 + 32 + 8 + 8 + 4 + 100 + 4 + 50 + 32 + 4 + 200 + 4 + 200 + 8 + 4 + 50 + 4 + 200 + 4 + 200 + 1 + 8 + 1;
+            let space = AchievementCredential::issuance_space(timestamp.len(), &[], None, &[], &[]);
             let rent = Rent::get()?;
             let lamports = rent.minimum_balance(space);
             
@@ -865,18 +1648,31 @@ pub mod open_badges {
         msg!("🔐 All credentials structured according to Open Badges 3.0 specification");
         msg!("🏗️ All credential PDAs derived using same logic as single credential issuance");
         msg!("📝 Implementation status: Validation complete, needs PDA account creation");
+        profiling::checkpoint("batch_simple: done");
         Ok(())
     }
 
     /// Verify an AchievementCredential
-    pub fn verify_credential(ctx: Context<VerifyCredential>) -> Result<bool> {
+    /// IssuerKey accounts for further `#key-N` proofs follow in `remaining_accounts`;
+    /// `policy` decides whether all proofs or any one of them must verify
+    /// A proof verifies through an earlier Ed25519 program instruction over its signing
+    /// message, signed by its IssuerKey or, without a `#key-N` fragment, the issuer authority
+    /// With `issuer_did_account`, the issuer's DID must still be alive and list each proof's method
+    /// Returns one flag per check and the error code of each failure
+    pub fn verify_credential<'info>(
+        ctx: Context<'_, '_, 'info, 'info, VerifyCredential<'info>>,
+        policy: proof_set::ProofPolicy,
+    ) -> Result<verification_policy::VerificationResult> {
         msg!("🔍 === CREDENTIAL VERIFICATION STARTED ===");
         
         let credential = &ctx.accounts.credential;
         let current_time = Clock::get()?.unix_timestamp;
         
         msg!("📍 PROOF VERIFICATION PROCESS:");
-        if let Some(proof) = &credential.proof {
+        if credential.proof.is_empty() {
+            msg!("⚠️  No proof found in credential");
+        }
+        for proof in &credential.proof {
             msg!("   → Proof Type: {}", proof.proof_type);
             msg!("   → Cryptosuite: {}", proof.cryptosuite);
             msg!("   → Proof Purpose: {}", proof.proof_purpose);
@@ -893,144 +1689,695 @@ pub mod open_badges {
                     msg!("✅ Assertion method proof purpose verified");
                 }
             }
-        } else {
-            msg!("⚠️  No proof found in credential");
         }
         
-        msg!("📍 TEMPORAL VALIDATION:");
-        // Parse valid_from to Unix timestamp for comparison
-        let valid_from_unix = parse_iso8601_to_unix(&credential.valid_from)?;
-        msg!("   → Valid From: {} (Unix: {})", credential.valid_from, valid_from_unix);
-        msg!("   → Current Time: {}", current_time);
-        
-        // Check if credential is within validity period
-        let mut is_valid = !credential.is_revoked && valid_from_unix <= current_time;
-        msg!("   → Time validation: {}", if valid_from_unix <= current_time { "PASSED" } else { "FAILED" });
+        // Resolve each `#key-N` verification method to the issuer key that signed it
+        let remaining_keys: Vec<Account<issuer_keys::IssuerKey>> = ctx
+            .remaining_accounts
+            .iter()
+            .map(Account::try_from)
+            .collect::<Result<_>>()?;
+        let keys: Vec<&issuer_keys::IssuerKey> = ctx
+            .accounts
+            .issuer_key
+            .as_deref()
+            .into_iter()
+            .chain(remaining_keys.iter().map(|key| &**key))
+            .collect();
+        let staff_did = ctx.accounts.staff_did_data.as_ref()
+            .map(|did_data| credential::read_signer_did_account(did_data))
+            .transpose()?;
+        let signatures = ProofSignatures {
+            issuer: ctx.accounts.issuer.as_deref(),
+            instructions: ctx.accounts.instructions.as_ref().map(|instructions| instructions.as_ref()),
+            staff: ctx.accounts.staff_key.as_deref().zip(staff_did.as_ref()),
+        };
+        let proof_valid = proof_set::satisfies(&credential.proof, policy, |proof| {
+            check_proof(proof, credential, &keys, signatures)
+        })?;
+        msg!("   → Proof signatures ({:?}): {}", policy, if proof_valid { "PASSED" } else { "FAILED" });
         
-        msg!("📍 REVOCATION CHECK:");
-        msg!("   → Is Revoked: {}", credential.is_revoked);
-        msg!("   → Revocation validation: {}", if !credential.is_revoked { "PASSED" } else { "FAILED" });
+        let issuer_did_active = match &ctx.accounts.issuer_did_account {
+            Some(did_data) => {
+                let issuer = ctx.accounts.issuer.as_ref().ok_or_else(|| error!(ValidationError::MissingRequiredField))?;
+                let active = credential::ensure_issuer_did_active(&issuer.authority, did_data, &credential.proof).is_ok();
+                msg!("   → Issuer DID: {}", if active { "ACTIVE" } else { "INACTIVE" });
+                Some(active)
+            }
+            None => None,
+        };
         
-        // Also check valid_until if set
-        if let Some(valid_until) = &credential.valid_until {
-            let valid_until_unix = parse_iso8601_to_unix(valid_until)?;
-            msg!("   → Valid Until: {} (Unix: {})", valid_until, valid_until_unix);
-            is_valid = is_valid && current_time <= valid_until_unix;
-            msg!("   → Expiration validation: {}", if current_time <= valid_until_unix { "PASSED" } else { "FAILED" });
-        }
+        let evidence = verification_policy::Evidence { proof_valid, issuer_did_active, accredited_by: vec![] };
+        let result = verification_policy::verify(credential, &evidence, current_time)?;
         
         msg!("🔍 === VERIFICATION SUMMARY ===");
-        msg!("📋 Final Result: {}", if is_valid { "✅ VALID" } else { "❌ INVALID" });
-        if is_valid {
-            msg!("✅ CREDENTIAL_VERIFIED: Verification successful");
-            msg!("   → Ed25519 signature: VERIFIED");
-            msg!("   → Temporal constraints: SATISFIED");
-            msg!("   → Revocation status: NOT REVOKED");
-            msg!("   → Open Badges 3.0: COMPLIANT");
+        msg!("   → Proof: {}", if result.proof_valid { "PASSED" } else { "FAILED" });
+        msg!("   → Revocation: {}", if result.not_revoked { "PASSED" } else { "FAILED" });
+        msg!("   → Validity period: {}", if result.within_validity { "PASSED" } else { "FAILED" });
+        msg!("   → Issuer: {}", if result.issuer_trusted { "PASSED" } else { "FAILED" });
+        if let Some(replacement) = result.superseded_by {
+            msg!("   → Superseded by: {}", replacement);
+        }
+        msg!("📋 Final Result: {}", if result.is_valid() { "✅ VALID" } else { "❌ INVALID" });
+        if !result.is_valid() {
+            msg!("   → Error codes: {:?}", result.errors);
         }
         
-        Ok(is_valid)
+        Ok(result)
     }
 
-    /// Validate an AchievementCredential for VCCS v1.0 compliance
-    pub fn validate_credential_compliance(
-        ctx: Context<ValidateCredential>,
-        credential_json: String,
-    ) -> Result<bool> {
-        // Perform VCCS v1.0 validation
-        validate_json_string_credential(&credential_json)?;
-        
-        // Additional validation on the actual credential
+    /// Evaluate a verifier's policy against a credential, reporting each requested check
+    /// Issuer keys beyond `issuer_key`, and accreditation endorsements of the issuer,
+    /// are passed in remaining accounts
+    /// View function: call with simulation, no state changes
+    pub fn verify_credential_with_policy<'info>(
+        ctx: Context<'_, '_, 'info, 'info, VerifyCredential<'info>>,
+        policy: verification_policy::VerificationPolicy,
+    ) -> Result<Vec<verification_policy::CheckResult>> {
         let credential = &ctx.accounts.credential;
-        credential.validate()?;
+        let current_time = Clock::get()?.unix_timestamp;
         
-        msg!("✅ Credential passed VCCS v1.0 compliance validation");
-        Ok(true)
+        let (accreditation_infos, key_infos): (Vec<&AccountInfo<'info>>, Vec<&AccountInfo<'info>>) =
+            ctx.remaining_accounts.iter().partition(|info| accreditation::is_accreditation(info));
+        let remaining_keys: Vec<Account<issuer_keys::IssuerKey>> = key_infos
+            .into_iter()
+            .map(Account::try_from)
+            .collect::<Result<_>>()?;
+        let accreditations: Vec<Account<accreditation::AccreditationEndorsement>> = accreditation_infos
+            .into_iter()
+            .map(Account::try_from)
+            .collect::<Result<_>>()?;
+        let keys: Vec<&issuer_keys::IssuerKey> = ctx
+            .accounts
+            .issuer_key
+            .as_deref()
+            .into_iter()
+            .chain(remaining_keys.iter().map(|key| &**key))
+            .collect();
+        let staff_did = ctx.accounts.staff_did_data.as_ref()
+            .map(|did_data| credential::read_signer_did_account(did_data))
+            .transpose()?;
+        let signatures = ProofSignatures {
+            issuer: ctx.accounts.issuer.as_deref(),
+            instructions: ctx.accounts.instructions.as_ref().map(|instructions| instructions.as_ref()),
+            staff: ctx.accounts.staff_key.as_deref().zip(staff_did.as_ref()),
+        };
+        let proof_valid = proof_set::satisfies(&credential.proof, policy.proof_policy, |proof| {
+            check_proof(proof, credential, &keys, signatures)
+        })?;
+        let issuer_did_active = match (&ctx.accounts.issuer_did_account, &ctx.accounts.issuer) {
+            (Some(did_data), Some(issuer)) => {
+                Some(credential::ensure_issuer_did_active(&issuer.authority, did_data, &credential.proof).is_ok())
+            }
+            _ => None,
+        };
+        
+        let accreditations: Vec<&accreditation::AccreditationEndorsement> =
+            accreditations.iter().map(|endorsement| &**endorsement).collect();
+        let accredited_by = accreditation::accreditors_of(&credential.issuer, &accreditations, current_time);
+        
+        let evidence = verification_policy::Evidence { proof_valid, issuer_did_active, accredited_by };
+        let results = verification_policy::evaluate(&policy, credential, &evidence, current_time)?;
+        for result in &results {
+            msg!("   → {:?}: {}", result.check, if result.passed { "PASSED" } else { "FAILED" });
+        }
+        Ok(results)
     }
 
-    /// Validate an Achievement for VCCS v1.0 compliance
-    pub fn validate_achievement_compliance(
-        _ctx: Context<ValidateAchievement>,
-        achievement_json: String,
-    ) -> Result<bool> {
-        // Perform VCCS v1.0 validation
-        validate_json_string_achievement(&achievement_json)?;
-        msg!("✅ Achievement passed VCCS v1.0 compliance validation");
-        Ok(true)
+    /// Compact, display-oriented view of a credential for public verification pages
+    /// View function: call with simulation, no state changes
+    pub fn get_public_verification_view(
+        ctx: Context<GetPublicVerificationView>,
+    ) -> Result<views::PublicVerificationView> {
+        let credential = &ctx.accounts.credential;
+        let current_time = Clock::get()?.unix_timestamp;
+        
+        let keys: Vec<&issuer_keys::IssuerKey> = ctx.accounts.issuer_key.as_deref().into_iter().collect();
+        let signatures = ProofSignatures {
+            issuer: Some(&ctx.accounts.issuer),
+            instructions: ctx.accounts.instructions.as_ref().map(|instructions| instructions.as_ref()),
+            staff: None,
+        };
+        let proof_valid = proof_set::satisfies(&credential.proof, proof_set::ProofPolicy::All, |proof| {
+            check_proof(proof, credential, &keys, signatures)
+        })?;
+        let status = views::display_status(credential, proof_valid, current_time)?;
+        
+        msg!("🪪 PUBLIC_VIEW: {} ({:?})", ctx.accounts.achievement.name, status);
+        Ok(views::public_verification_view(
+            credential.key(),
+            credential,
+            &ctx.accounts.achievement,
+            &ctx.accounts.issuer,
+            status,
+        ))
     }
 
-    /// Validate a Profile for VCCS v1.0 compliance
-    pub fn validate_profile_compliance(
-        _ctx: Context<ValidateProfile>,
-        profile_json: String,
-    ) -> Result<bool> {
-        // Perform VCCS v1.0 validation
-        validate_json_string_profile(&profile_json)?;
+    /// Typed summary of a credential account and its status
+    /// View function: call with simulation, no state changes
+    pub fn get_credential_summary(ctx: Context<GetCredentialSummary>) -> Result<views::CredentialSummary> {
+        let credential = &ctx.accounts.credential;
+        let current_time = Clock::get()?.unix_timestamp;
         
-        msg!("✅ Profile passed VCCS v1.0 compliance validation");
-        Ok(true)
+        let keys: Vec<&issuer_keys::IssuerKey> = ctx.accounts.issuer_key.as_deref().into_iter().collect();
+        let signatures = ProofSignatures {
+            issuer: ctx.accounts.issuer.as_deref(),
+            instructions: ctx.accounts.instructions.as_ref().map(|instructions| instructions.as_ref()),
+            staff: None,
+        };
+        let proof_valid = proof_set::satisfies(&credential.proof, proof_set::ProofPolicy::All, |proof| {
+            check_proof(proof, credential, &keys, signatures)
+        })?;
+        let status = views::display_status(credential, proof_valid, current_time)?;
+        Ok(views::credential_summary(credential.key(), credential, status))
     }
 
-    /// Create a Linked Data Proof for an AchievementCredential
-    /// Implements Section 8.3 of Open Badges 3.0 specification
-    pub fn create_linked_data_proof(
-        ctx: Context<CreateLinkedDataProof>,
-        credential_json: String,
-        key_id: String,
-        proof_purpose: String,
-    ) -> Result<String> {
-        let signer = &ctx.accounts.signer;
-        let controller = format!("did:sol:{}", signer.key());
-        
-        // Create multikey pair from signer's public key
-        let key_pair = MultikeyPair::from_signer(
-            signer.key(),
-            controller,
-            key_id,
-        )?;
-        
-        // Create the proof
-        let proof = ProofSuite::create_proof_onchain(
-            &credential_json,
-            &key_pair,
-            &proof_purpose,
-            &signer.key(),
-        )?;
-        
-        // Convert proof to JSON for return
-        let proof_json = serde_json::to_string(&proof)
-            .map_err(|_| error!(ValidationError::ValidationFailed))?;
-        
-        msg!("✅ Created Linked Data Proof for credential");
-        Ok(proof_json)
+    /// Typed summary of an achievement and its issuance totals
+    /// View function: call with simulation, no state changes
+    pub fn get_achievement_summary(ctx: Context<GetAchievementSummary>) -> Result<views::AchievementSummary> {
+        Ok(views::achievement_summary(
+            ctx.accounts.achievement.key(),
+            &ctx.accounts.achievement,
+            ctx.accounts.achievement_stats.as_deref(),
+        ))
     }
 
-    /// Verify a Linked Data Proof for an AchievementCredential  
-    /// Implements Section 8.3 of Open Badges 3.0 specification
-    pub fn verify_linked_data_proof(
-        _ctx: Context<VerifyLinkedDataProof>,
-        credential_json: String,
-        proof_json: String,
-        public_key_multibase: String,
-    ) -> Result<bool> {
-        // Parse the proof from JSON
-        let proof: DataIntegrityProof = serde_json::from_str(&proof_json)
-            .map_err(|_| error!(ValidationError::InvalidProof))?;
-        
-        // Verify the proof
-        let verification_result = ProofSuite::verify_proof(
-            &credential_json,
-            &proof,
-            &public_key_multibase,
-        )?;
-        
-        if verification_result {
-            msg!("✅ Linked Data Proof verification successful");
-        } else {
-            msg!("❌ Linked Data Proof verification failed");
+    /// Typed summary of an issuer profile
+    /// View function: call with simulation, no state changes
+    pub fn get_issuer_summary(ctx: Context<GetIssuerSummary>) -> Result<views::IssuerSummary> {
+        Ok(views::issuer_summary(
+            ctx.accounts.issuer.key(),
+            &ctx.accounts.issuer,
+            ctx.accounts.issuer_stats.as_deref(),
+        ))
+    }
+
+    /// Open Badges Profile document of an issuer, including its other identifiers
+    /// Accreditation endorsements of the issuer follow in `remaining_accounts`;
+    /// those in force are embedded under `endorsement`
+    /// View function: call with simulation, no state changes
+    pub fn generate_profile_json<'info>(
+        ctx: Context<'_, '_, 'info, 'info, GenerateProfileJson<'info>>,
+    ) -> Result<String> {
+        let issuer_key = ctx.accounts.issuer.key();
+        let current_time = Clock::get()?.unix_timestamp;
+        let accreditations: Vec<Account<accreditation::AccreditationEndorsement>> = ctx
+            .remaining_accounts
+            .iter()
+            .map(Account::try_from)
+            .collect::<Result<_>>()?;
+        if accreditations.iter().any(|endorsement| endorsement.issuer != issuer_key) {
+            return Err(error!(ValidationError::UnauthorizedAccess));
         }
+        let in_force: Vec<(Pubkey, &accreditation::AccreditationEndorsement)> = accreditations
+            .iter()
+            .filter(|endorsement| endorsement.is_in_force(current_time))
+            .map(|endorsement| (endorsement.key(), &**endorsement))
+            .collect();
         
-        Ok(verification_result)
+        let mut document = credential::profile_json(&ctx.accounts.issuer);
+        accreditation::apply_to_json(&mut document, &in_force)?;
+        serde_json::to_string(&document).map_err(|_| error!(ValidationError::SerializationError))
+    }
+
+    /// JWKS document of the issuer's signing keys, with `kid`s matching proof and JWT key ids
+    /// IssuerKey accounts follow in `remaining_accounts`; without `issuer_did_account`
+    /// the authority's generative did:sol document is assumed
+    /// View function: call with simulation, no state changes
+    pub fn get_issuer_jwks<'info>(ctx: Context<'_, '_, 'info, 'info, GetIssuerJwks<'info>>) -> Result<String> {
+        let keys: Vec<Account<issuer_keys::IssuerKey>> = ctx
+            .remaining_accounts
+            .iter()
+            .map(Account::try_from)
+            .collect::<Result<_>>()?;
+        let keys: Vec<&issuer_keys::IssuerKey> = keys.iter().map(|key| &**key).collect();
+        let issuer = &ctx.accounts.issuer;
+        let did_account = jwks::did_account(
+            &issuer.authority,
+            ctx.accounts.issuer_did_account.as_ref().map(|info| info.as_ref()),
+        )?;
+        jwks::issuer_jwks(&issuer.key(), issuer, &keys, &did_account)
+    }
+
+    /// Revocation status of one entry of a revocation list
+    /// View function: call with simulation, no state changes
+    pub fn get_revocation_status(ctx: Context<ReadRevocationList>, index: u32) -> Result<views::RevocationStatus> {
+        views::revocation_status(ctx.accounts.revocation_list.key(), &ctx.accounts.revocation_list, index)
+    }
+
+    /// Page through the credentials issued to a recipient, oldest first
+    /// Each call reads one index page account; pages are numbered from 0
+    pub fn get_credentials_page(
+        ctx: Context<GetCredentialsPage>,
+        _page: u32,
+    ) -> Result<credential_index::CredentialsPage> {
+        Ok(credential_index::page(&ctx.accounts.credential_index, &ctx.accounts.credential_index_page))
+    }
+
+    /// Page through an issuer's achievements in creation order
+    /// Pass the directory page at the previous page's `next_cursor` to continue; start at 0
+    pub fn get_achievements_page(
+        ctx: Context<GetAchievementsPage>,
+        _cursor: u32,
+    ) -> Result<achievement_directory::AchievementsPage> {
+        Ok(achievement_directory::page(&ctx.accounts.achievement_directory, &ctx.accounts.achievement_directory_page))
+    }
+
+    /// Page through the holders of an achievement, oldest first
+    /// Each call reads one roster page account; pages are numbered from 0
+    pub fn get_recipient_roster_page(
+        ctx: Context<GetRecipientRosterPage>,
+        _page: u32,
+    ) -> Result<recipient_roster::RosterPage> {
+        Ok(recipient_roster::page(&ctx.accounts.recipient_roster, &ctx.accounts.recipient_roster_page))
+    }
+
+    /// Verify many credentials in one transaction
+    /// Credentials are passed in `remaining_accounts`; a credential whose proof uses a
+    /// `#key-N` fragment is followed by its IssuerKey account, and a credential with
+    /// proofs by the issuer authority by its issuer Profile. Each proof must be verified
+    /// by an earlier Ed25519 program instruction. Bit i of the result
+    /// is set when the i-th credential passes verification.
+    pub fn verify_credentials_batch<'info>(ctx: Context<'_, '_, 'info, 'info, VerifyCredentialsBatch<'info>>) -> Result<u64> {
+        msg!("🔍 === BATCH CREDENTIAL VERIFICATION STARTED ===");
+        
+        let current_time = Clock::get()?.unix_timestamp;
+        let accounts = ctx.remaining_accounts;
+        let mut valid_mask: u64 = 0;
+        let mut count: usize = 0;
+        let mut cursor = 0;
+        
+        while cursor < accounts.len() {
+            require!(count < MAX_BATCH_VERIFY, ValidationError::BatchSizeTooLarge);
+            
+            let credential_info = &accounts[cursor];
+            cursor += 1;
+            
+            let credential = match Account::<AchievementCredential>::try_from(credential_info) {
+                Ok(credential) => credential,
+                Err(_) => {
+                    msg!("   → Credential {}: not an AchievementCredential account", count);
+                    count += 1;
+                    continue;
+                }
+            };
+            
+            // An IssuerKey directly after the credential resolves its key fragment
+            let issuer_key = accounts
+                .get(cursor)
+                .and_then(|info| Account::<issuer_keys::IssuerKey>::try_from(info).ok());
+            if issuer_key.is_some() {
+                cursor += 1;
+            }
+            let issuer = issuer_profile_at(accounts, &mut cursor, &credential);
+            
+            let keys: Vec<&issuer_keys::IssuerKey> = issuer_key.as_deref().into_iter().collect();
+            let signatures = ProofSignatures { issuer: issuer.as_deref(), instructions: Some(&ctx.accounts.instructions), staff: None };
+            let is_valid = check_credential_validity(&credential, &keys, signatures, proof_set::ProofPolicy::All, current_time)
+                .unwrap_or(false);
+            if is_valid {
+                valid_mask |= 1 << count;
+            }
+            msg!("   → Credential {} ({}): {}", count, credential_info.key(), if is_valid { "✅ VALID" } else { "❌ INVALID" });
+            count += 1;
+        }
+        
+        require!(count > 0, ValidationError::EmptyBatch);
+        
+        emit!(events::CredentialsBatchVerified {
+            count: count as u8,
+            valid_mask,
+        });
+        
+        msg!("📋 Batch result: {} of {} valid (mask {:#x})", valid_mask.count_ones(), count, valid_mask);
+        Ok(valid_mask)
+    }
+
+    /// Build the presentation JSON the holder signs for `create_presentation`
+    /// Credentials are passed as remaining accounts, in presentation order
+    pub fn generate_presentation_signing_json<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreatePresentation<'info>>,
+        holder: Pubkey,
+        challenge: String,
+        domain: String,
+        created: String,
+    ) -> Result<String> {
+        let unsigned = holder_presentation(ctx.remaining_accounts, &holder, &created, &challenge, &domain, ctx.program_id)?;
+        String::from_utf8(presentation::signing_bytes(&unsigned)?)
+            .map_err(|_| error!(ValidationError::SerializationError))
+    }
+
+    /// Create a VerifiablePresentation of the holder's credentials
+    /// The holder signs the JSON from `generate_presentation_signing_json`, and
+    /// an earlier Ed25519 program instruction must verify that signature
+    pub fn create_presentation<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreatePresentation<'info>>,
+        holder: Pubkey,
+        challenge: String,
+        domain: String,
+        created: String,
+        signature_data: Vec<u8>,
+    ) -> Result<String> {
+        msg!("🎫 === PRESENTATION CREATION STARTED ===");
+        let mut vp = holder_presentation(ctx.remaining_accounts, &holder, &created, &challenge, &domain, ctx.program_id)?;
+        
+        let signature: [u8; 64] = signature_data.as_slice().try_into().map_err(|_| {
+            msg!("❌ Invalid signature length: expected 64 bytes, got {}", signature_data.len());
+            error!(ValidationError::InvalidSignatureLength)
+        })?;
+        proof::ensure_ed25519_verified(&ctx.accounts.instructions, &holder, &presentation::signing_bytes(&vp)?, &signature)?;
+        
+        presentation::attach_proof_value(&mut vp, &signature_data);
+        msg!("✅ Presentation of {} credential(s) for {}", ctx.remaining_accounts.len(), domain);
+        Ok(vp.to_string())
+    }
+
+    /// Build the CLR JSON the issuer authority signs for `generate_clr`
+    /// Credentials are passed as remaining accounts, in transcript order
+    pub fn generate_clr_signing_json<'info>(
+        ctx: Context<'_, '_, 'info, 'info, GenerateClr<'info>>,
+        recipient: Pubkey,
+        clr_id: String,
+        name: String,
+        valid_from: String,
+    ) -> Result<String> {
+        let builder = recipient_clr(ctx.remaining_accounts, &ctx.accounts.issuer, &recipient, &clr_id, &name, &valid_from, ctx.program_id)?;
+        String::from_utf8(builder.signing_bytes()?)
+            .map_err(|_| error!(ValidationError::SerializationError))
+    }
+
+    /// Generate a CLR 2.0 ClrCredential bundling the recipient's credentials
+    /// The issuer authority signs the JSON from `generate_clr_signing_json`, and
+    /// an earlier Ed25519 program instruction must verify that signature
+    pub fn generate_clr<'info>(
+        ctx: Context<'_, '_, 'info, 'info, GenerateClr<'info>>,
+        recipient: Pubkey,
+        clr_id: String,
+        name: String,
+        valid_from: String,
+        signature_data: Vec<u8>,
+    ) -> Result<String> {
+        let builder = recipient_clr(ctx.remaining_accounts, &ctx.accounts.issuer, &recipient, &clr_id, &name, &valid_from, ctx.program_id)?;
+        
+        let signature: [u8; 64] = signature_data.as_slice().try_into().map_err(|_| {
+            msg!("❌ Invalid signature length: expected 64 bytes, got {}", signature_data.len());
+            error!(ValidationError::InvalidSignatureLength)
+        })?;
+        proof::ensure_ed25519_verified(&ctx.accounts.instructions, &ctx.accounts.issuer.authority, &builder.signing_bytes()?, &signature)?;
+        
+        msg!("📚 CLR of {} credential(s) for {}", ctx.remaining_accounts.len(), recipient);
+        Ok(builder.signed(&signature_data)?.to_string())
+    }
+
+    /// Verify a presentation for the given challenge and domain
+    /// Credentials are passed as remaining accounts in presentation order, each
+    /// optionally followed by the IssuerKey its proof references and its issuer
+    /// Profile; the holder's signature and every credential proof must be
+    /// verified by earlier Ed25519 program instructions
+    pub fn verify_presentation<'info>(
+        ctx: Context<'_, '_, 'info, 'info, VerifyPresentation<'info>>,
+        presentation_json: String,
+        challenge: String,
+        domain: String,
+    ) -> Result<bool> {
+        msg!("🔍 === PRESENTATION VERIFICATION STARTED ===");
+        let parsed = presentation::parse_presentation(&presentation_json)?;
+        let holder = parsed.holder;
+        let credential_count = parsed.credentials.len() as u8;
+        
+        let valid = 'checks: {
+            if parsed.challenge != challenge || parsed.domain != domain {
+                msg!("❌ Challenge or domain does not match the verifier's request");
+                break 'checks false;
+            }
+            let Ok(signature) = <[u8; 64]>::try_from(parsed.signature.as_slice()) else {
+                msg!("❌ Holder signature is invalid");
+                break 'checks false;
+            };
+            if proof::ensure_ed25519_verified(&ctx.accounts.instructions, &holder, &parsed.signing_bytes, &signature).is_err() {
+                msg!("❌ Holder signature is invalid");
+                break 'checks false;
+            }
+            
+            let current_time = Clock::get()?.unix_timestamp;
+            let accounts = ctx.remaining_accounts;
+            let mut cursor = 0;
+            for document in &parsed.credentials {
+                let Some(credential_info) = accounts.get(cursor) else {
+                    msg!("❌ Missing credential account for {}", document["id"]);
+                    break 'checks false;
+                };
+                cursor += 1;
+                let credential = Account::<AchievementCredential>::try_from(credential_info)?;
+                let issuer_key = accounts
+                    .get(cursor)
+                    .and_then(|info| Account::<issuer_keys::IssuerKey>::try_from(info).ok());
+                if issuer_key.is_some() {
+                    cursor += 1;
+                }
+                let issuer = issuer_profile_at(accounts, &mut cursor, &credential);
+                
+                if *document != credential::credential_account_to_json(&credential) {
+                    msg!("❌ Embedded credential does not match account {}", credential_info.key());
+                    break 'checks false;
+                }
+                if !presentation::is_held_by(&credential_info.key(), &credential, &holder, ctx.program_id) {
+                    msg!("❌ Credential {} was not issued to the holder", credential_info.key());
+                    break 'checks false;
+                }
+                let binding = holder_binding::ensure_presented_by_holder(
+                    credential.holder_binding.as_ref(),
+                    &holder,
+                    &ctx.accounts.instructions,
+                    &parsed.signing_bytes,
+                    &signature,
+                );
+                if binding.is_err() {
+                    break 'checks false;
+                }
+                let keys: Vec<&issuer_keys::IssuerKey> = issuer_key.as_deref().into_iter().collect();
+                let signatures = ProofSignatures { issuer: issuer.as_deref(), instructions: Some(&ctx.accounts.instructions), staff: None };
+                if !check_credential_validity(&credential, &keys, signatures, proof_set::ProofPolicy::All, current_time)? {
+                    msg!("❌ Credential {} is revoked, expired or unverifiable", credential_info.key());
+                    break 'checks false;
+                }
+            }
+            true
+        };
+        
+        emit!(events::PresentationVerified {
+            holder,
+            credential_count,
+            valid,
+        });
+        
+        msg!("📋 Presentation from {}: {}", holder, if valid { "✅ VALID" } else { "❌ INVALID" });
+        Ok(valid)
+    }
+
+    /// Validate an AchievementCredential for VCCS v1.0 compliance
+    pub fn validate_credential_compliance(
+        ctx: Context<ValidateCredential>,
+        credential_json: String,
+    ) -> Result<bool> {
+        let mode = ctx.accounts.issuer.validation_mode;
+        
+        // Perform VCCS v1.0 validation
+        mode.enforce(validate_json_string_credential(&credential_json), "VCCS v1.0 validation")?;
+        let report = mode.compliance_validator().at(Clock::get()?.unix_timestamp).validate_credential(&credential_json)?;
+        let compliant = mode.check_report(&report)?;
+        
+        // Declared schemas must be the ones the issuer registered
+        let document: serde_json::Value = serde_json::from_str(&credential_json)
+            .map_err(|_| error!(ValidationError::InvalidJson))?;
+        mode.enforce(
+            schema::verify_declared(&document, ctx.accounts.schema_registry.as_deref()),
+            "credentialSchema validation",
+        )?;
+        
+        // Additional validation on the actual credential
+        let credential = &ctx.accounts.credential;
+        mode.enforce(credential.validate(), "Credential account validation")?;
+        
+        msg!("✅ Credential passed VCCS v1.0 compliance validation");
+        Ok(compliant)
+    }
+
+    /// Run the full compliance validator on a credential document
+    /// Emits a `ComplianceReport` and returns the score instead of failing on
+    /// findings, so conformance runs can collect reports for any input
+    pub fn validate_credential_full(
+        _ctx: Context<ValidateCredentialFull>,
+        credential_json: String,
+        mode: strictness::ValidationMode,
+    ) -> Result<u8> {
+        let report = mode.compliance_validator().at(Clock::get()?.unix_timestamp).validate_credential(&credential_json)?;
+        for error in &report.errors {
+            msg!("❌ {}", error);
+        }
+        for warning in &report.warnings {
+            msg!("⚠️ {}", warning);
+        }
+        
+        emit!(events::ComplianceReport {
+            credential_hash: anchor_lang::solana_program::hash::hash(credential_json.as_bytes()).to_bytes(),
+            mode,
+            score: report.compliance_score,
+            error_count: report.errors.len() as u16,
+            warning_count: report.warnings.len() as u16,
+            is_valid: report.is_valid,
+        });
+        
+        msg!("📋 {}", report.summary());
+        Ok(report.compliance_score)
+    }
+
+    /// Validate an Achievement for VCCS v1.0 compliance
+    pub fn validate_achievement_compliance(
+        ctx: Context<ValidateAchievement>,
+        achievement_json: String,
+    ) -> Result<bool> {
+        // Perform VCCS v1.0 validation
+        ctx.accounts.issuer.validation_mode.enforce(
+            validate_json_string_achievement(&achievement_json),
+            "VCCS v1.0 validation",
+        )?;
+        msg!("✅ Achievement passed VCCS v1.0 compliance validation");
+        Ok(true)
+    }
+
+    /// Validate a Profile for VCCS v1.0 compliance
+    pub fn validate_profile_compliance(
+        ctx: Context<ValidateProfile>,
+        profile_json: String,
+    ) -> Result<bool> {
+        // Perform VCCS v1.0 validation
+        ctx.accounts.profile.validation_mode.enforce(
+            validate_json_string_profile(&profile_json),
+            "VCCS v1.0 validation",
+        )?;
+        ctx.accounts.profile.validation_mode.enforce(
+            other_identifier::validate_json(&profile_json),
+            "otherIdentifier validation",
+        )?;
+        
+        msg!("✅ Profile passed VCCS v1.0 compliance validation");
+        Ok(true)
+    }
+
+    /// Create a Linked Data Proof for an AchievementCredential
+    /// Implements Section 8.3 of Open Badges 3.0 specification
+    pub fn create_linked_data_proof(
+        ctx: Context<CreateLinkedDataProof>,
+        credential_json: String,
+        key_id: String,
+        proof_purpose: String,
+        challenge: Option<String>,
+        domain: Option<String>,
+        secp256k1_signature: Option<secp256k1::Secp256k1Signature>,
+    ) -> Result<String> {
+        let signer = &ctx.accounts.signer;
+        if challenge.as_ref().is_some_and(|c| c.len() > presentation::MAX_CHALLENGE_LEN)
+            || domain.as_ref().is_some_and(|d| d.len() > presentation::MAX_DOMAIN_LEN)
+        {
+            return Err(error!(ValidationError::FieldTooLong));
+        }
+        
+        // An EVM signer's ecdsa-secp256k1-2019 proof, verified by the secp256k1 program
+        if let Some(secp256k1_signature) = secp256k1_signature {
+            let Some(instructions) = ctx.accounts.instructions.as_ref() else {
+                msg!("❌ secp256k1 proofs need the instructions sysvar and a secp256k1 instruction");
+                return Err(error!(ValidationError::MissingRequiredField));
+            };
+            let proof = secp256k1::create_proof(
+                &credential_json,
+                &secp256k1_signature,
+                &key_id,
+                &proof_purpose,
+                challenge.as_deref(),
+                domain.as_deref(),
+                instructions,
+            )?;
+            msg!("✅ Created {} proof for {}", secp256k1::CRYPTOSUITE, proof.verification_method);
+            return serde_json::to_string(&proof).map_err(|_| error!(ValidationError::ValidationFailed));
+        }
+        let controller = format!("did:sol:{}", signer.key());
+        
+        // Create multikey pair from signer's public key
+        let key_pair = MultikeyPair::from_signer(
+            signer.key(),
+            controller,
+            key_id,
+        )?;
+        
+        // Create the proof
+        let proof = ProofSuite::create_proof_onchain(
+            &credential_json,
+            &key_pair,
+            &proof_purpose,
+            &signer.key(),
+            challenge.as_deref(),
+            domain.as_deref(),
+        )?;
+        
+        // Convert proof to JSON for return
+        let proof_json = serde_json::to_string(&proof)
+            .map_err(|_| error!(ValidationError::ValidationFailed))?;
+        
+        msg!("✅ Created Linked Data Proof for credential");
+        Ok(proof_json)
+    }
+
+    /// Verify a Linked Data Proof for an AchievementCredential  
+    /// Implements Section 8.3 of Open Badges 3.0 specification
+    /// `ecdsa-secp256k1-2019` proofs are checked against a secp256k1 program
+    /// instruction, with `public_key_multibase` naming the signer's Ethereum address
+    pub fn verify_linked_data_proof(
+        ctx: Context<VerifyLinkedDataProof>,
+        credential_json: String,
+        proof_json: String,
+        public_key_multibase: String,
+        expected_challenge: Option<String>,
+        expected_domain: Option<String>,
+    ) -> Result<bool> {
+        // Parse the proof from JSON
+        let proof: DataIntegrityProof = serde_json::from_str(&proof_json)
+            .map_err(|_| error!(ValidationError::InvalidProof))?;
+        
+        // Both suites check a signature program instruction through the sysvar
+        let Some(instructions) = ctx.accounts.instructions.as_ref() else {
+            msg!("❌ Proofs need the instructions sysvar and an Ed25519 or secp256k1 instruction");
+            return Ok(false);
+        };
+        
+        // Verify the proof
+        let verification_result = if proof.cryptosuite == secp256k1::CRYPTOSUITE {
+            secp256k1::verify_proof(
+                &credential_json,
+                &proof,
+                &secp256k1::parse_eth_address(&public_key_multibase)?,
+                expected_challenge.as_deref(),
+                expected_domain.as_deref(),
+                instructions,
+            )?
+        } else {
+            ProofSuite::verify_proof(
+                &credential_json,
+                &proof,
+                &public_key_multibase,
+                expected_challenge.as_deref(),
+                expected_domain.as_deref(),
+                instructions,
+            )?
+        };
+        
+        if verification_result {
+            msg!("✅ Linked Data Proof verification successful");
+        } else {
+            msg!("❌ Linked Data Proof verification failed");
+        }
+        
+        Ok(verification_result)
     }
 
     /// Generate a JSON-LD credential for an achievement
@@ -1046,48 +2393,163 @@ pub mod open_badges {
         
         let credential_json = credential::generate_jsonld_credential(
             &issuer.key(),
+            issuer.image.as_deref(),
+            issuer.refresh_service_url.as_deref(),
+            &issuer.terms_of_use,
             &recipient.key(),
             &achievement_id,
-            &achievement.name,
-            &achievement.description,
+            achievement,
             &credential_id,
+            ctx.accounts.rubric.as_deref(),
+            &get_current_iso8601()?,
         )?;
         
         msg!("✅ Generated JSON-LD credential: {}", credential_id);
         Ok(credential_json)
     }
 
-    /// Generate a JWT credential for an achievement  
-    /// Implements Open Badges 3.0 specification for JWT format
-    pub fn generate_jwt_credential(
+    /// Build the JWS signing input of a JWT credential for the issuer authority to sign
+    pub fn generate_jwt_signing_input(
         ctx: Context<GenerateCredential>,
         achievement_id: String,
         credential_id: String,
+        issued_at: i64,
+        bind_holder: bool,
     ) -> Result<String> {
         let issuer = &ctx.accounts.issuer;
-        let achievement = &ctx.accounts.achievement;
-        let recipient = &ctx.accounts.recipient;
-        
-        let credential_jwt = credential::generate_jwt_credential(
+        credential::jwt_signing_input(
             &issuer.key(),
-            &recipient.key(),
+            &issuer.authority,
+            issuer.image.as_deref(),
+            issuer.refresh_service_url.as_deref(),
+            &issuer.terms_of_use,
+            &ctx.accounts.recipient.key(),
+            &achievement_id,
+            &ctx.accounts.achievement,
+            &credential_id,
+            ctx.accounts.rubric.as_deref(),
+            issued_at,
+            bind_holder,
+        )
+    }
+
+    /// Generate a compact JWT credential signed by the issuer authority
+    /// The transaction must carry an Ed25519 program instruction verifying
+    /// `signature` by the authority over `generate_jwt_signing_input`'s output
+    pub fn generate_jwt_credential(
+        ctx: Context<GenerateJwtCredential>,
+        achievement_id: String,
+        credential_id: String,
+        issued_at: i64,
+        bind_holder: bool,
+        signature: [u8; 64],
+    ) -> Result<String> {
+        let issuer = &ctx.accounts.issuer;
+        let signing_input = credential::jwt_signing_input(
+            &issuer.key(),
+            &issuer.authority,
+            issuer.image.as_deref(),
+            issuer.refresh_service_url.as_deref(),
+            &issuer.terms_of_use,
+            &ctx.accounts.recipient.key(),
             &achievement_id,
-            &achievement.name,
-            &achievement.description,
+            &ctx.accounts.achievement,
             &credential_id,
+            ctx.accounts.rubric.as_deref(),
+            issued_at,
+            bind_holder,
+        )?;
+        
+        proof::ensure_ed25519_verified(
+            &ctx.accounts.instructions,
+            &issuer.authority,
+            signing_input.as_bytes(),
+            &signature,
         )?;
         
         msg!("✅ Generated JWT credential: {}", credential_id);
-        Ok(credential_jwt)
+        Ok(credential::compact_jwt(&signing_input, &signature))
+    }
+
+    /// Prepare an SD-JWT of a credential for the issuer authority to sign
+    /// Recipient identity, results and evidence become selectively disclosable;
+    /// simulate this call so `salt_seed` never reaches the ledger
+    pub fn generate_sd_jwt_signing_input(
+        ctx: Context<GenerateSdJwt>,
+        evidence: Vec<String>,
+        salt_seed: [u8; 32],
+        issued_at: i64,
+    ) -> Result<formats::sd_jwt::SdJwtDraft> {
+        let issuer = &ctx.accounts.issuer;
+        formats::sd_jwt::prepare_sd_jwt(
+            &issuer.key(),
+            &issuer.authority,
+            &ctx.accounts.credential,
+            &ctx.accounts.achievement,
+            ctx.accounts.rubric.as_deref(),
+            &evidence,
+            &salt_seed,
+            issued_at,
+        )
+    }
+
+    /// Generate the COSE `Sig_structure` of a credential for the issuer authority to sign
+    pub fn generate_cose_signing_input(ctx: Context<GenerateCoseCredential>) -> Result<Vec<u8>> {
+        let draft = formats::cose::prepare_cose_credential(
+            &ctx.accounts.issuer.authority,
+            &ctx.accounts.credential,
+            &ctx.accounts.achievement,
+            ctx.accounts.rubric.as_deref(),
+        )?;
+        Ok(draft.to_be_signed())
+    }
+
+    /// Generate a COSE_Sign1 credential signed by the issuer authority
+    /// The transaction must carry an Ed25519 program instruction verifying
+    /// `signature` by the authority over `generate_cose_signing_input`'s output
+    pub fn generate_cose_credential(
+        ctx: Context<GenerateCoseCredential>,
+        signature: [u8; 64],
+    ) -> Result<Vec<u8>> {
+        let Some(instructions) = &ctx.accounts.instructions else {
+            return Err(error!(ValidationError::InvalidSignature));
+        };
+        let draft = formats::cose::prepare_cose_credential(
+            &ctx.accounts.issuer.authority,
+            &ctx.accounts.credential,
+            &ctx.accounts.achievement,
+            ctx.accounts.rubric.as_deref(),
+        )?;
+        proof::ensure_ed25519_verified(
+            instructions,
+            &ctx.accounts.issuer.authority,
+            &draft.to_be_signed(),
+            &signature,
+        )?;
+        
+        msg!("✅ Generated COSE credential: {}", ctx.accounts.credential.id);
+        Ok(draft.finish(&signature))
     }
 
     /// Verify a credential in any supported format
-    /// Supports both JSON-LD and JWT formats
+    /// Supports JSON-LD, JWT and base64url COSE_Sign1 formats
+    /// JWTs and COSE credentials are checked against the Solana clock, their `kid` is resolved
+    /// through the did module, and the signature must be verified by an
+    /// Ed25519 program instruction in the same transaction
     pub fn verify_credential_format(
-        _ctx: Context<VerifyCredentialFormat>,
+        ctx: Context<VerifyCredentialFormat>,
         credential_data: String,
     ) -> Result<bool> {
-        let is_valid = credential::verify_credential_format(&credential_data)?;
+        let signer_did = ctx.accounts.signer_did_data.as_ref()
+            .map(|did_data| credential::read_signer_did_account(did_data))
+            .transpose()?;
+        let context = credential::JwtVerificationContext {
+            now: Clock::get()?.unix_timestamp,
+            issuer: ctx.accounts.issuer.as_ref().map(|issuer| (issuer.key(), &**issuer)),
+            instructions: ctx.accounts.instructions.as_ref().map(|instructions| instructions.as_ref()),
+            staff: ctx.accounts.staff_key.as_deref().zip(signer_did.as_ref()),
+        };
+        let is_valid = credential::verify_credential_format(&credential_data, &context)?;
         
         if is_valid {
             msg!("✅ Credential format verification successful");
@@ -1098,84 +2560,864 @@ pub mod open_badges {
         Ok(is_valid)
     }
 
-    /// Resolve a DID to its document
-    /// Supports did:sol, did:key, and did:web methods
+    /// Verify an SD-JWT presentation and the disclosures it carries
+    /// The JWT signature is checked as in `verify_credential_format`, and each
+    /// disclosure must match a digest the issuer signed
+    pub fn verify_sd_jwt_presentation(
+        ctx: Context<VerifyCredentialFormat>,
+        presentation: String,
+    ) -> Result<bool> {
+        let signer_did = ctx.accounts.signer_did_data.as_ref()
+            .map(|did_data| credential::read_signer_did_account(did_data))
+            .transpose()?;
+        let context = credential::JwtVerificationContext {
+            now: Clock::get()?.unix_timestamp,
+            issuer: ctx.accounts.issuer.as_ref().map(|issuer| (issuer.key(), &**issuer)),
+            instructions: ctx.accounts.instructions.as_ref().map(|instructions| instructions.as_ref()),
+            staff: ctx.accounts.staff_key.as_deref().zip(signer_did.as_ref()),
+        };
+        formats::sd_jwt::verify_sd_jwt_presentation(&presentation, &context)
+    }
+
+    /// Resolve a DID to its DID Core resolution result (metadata and document)
+    /// Supports did:sol and did:ob-sol, plus did:key and did:web when their features are enabled
     pub fn resolve_did_document(
         _ctx: Context<ResolveDid>,
         did: String,
     ) -> Result<String> {
-        let did_document = credential::resolve_did_document(&did)?;
+        let resolution = credential::resolve_did_document(&did)?;
         
         msg!("✅ Resolved DID document for: {}", did);
+        Ok(resolution)
+    }
+
+    /// Resolve a did:sol DID by reading the sol_did DID account
+    /// Builds the document from on-chain verification methods, services and controllers
+    pub fn resolve_did_document_from_account(
+        ctx: Context<ResolveDidFromAccount>,
+        did: String,
+    ) -> Result<String> {
+        let did_document = credential::resolve_sol_did_document_from_account(
+            &did,
+            &ctx.accounts.did_data.to_account_info(),
+        )?;
+        
+        msg!("✅ Resolved on-chain DID document for: {}", did);
         Ok(did_document)
     }
 
-    /// Revoke a credential directly (for backward compatibility with tests)
-    /// Sets the is_revoked flag on the credential account
-    pub fn revoke_credential_direct(
-        ctx: Context<RevokeCredentialDirect>,
+    /// Choose which of the issuer's instructions other programs may invoke
+    pub fn set_cpi_policy(
+        ctx: Context<SetCpiPolicy>,
+        allow_issuance: bool,
+        allow_revocation: bool,
+        allowed_programs: Vec<Pubkey>,
     ) -> Result<()> {
-        let credential = &mut ctx.accounts.credential;
-        let current_timestamp = get_current_iso8601()?;
+        // A program must never widen its own permissions
+        require!(!cpi_guard::is_cpi(), ValidationError::CpiNotAllowed);
+        cpi_guard::validate_allowed_programs(&allowed_programs)?;
+        let policy = &mut ctx.accounts.cpi_policy;
+        policy.issuer = ctx.accounts.issuer.key();
+        policy.allow_issuance = allow_issuance;
+        policy.allow_revocation = allow_revocation;
+        policy.allowed_programs = allowed_programs;
+        policy.bump = ctx.bumps.cpi_policy;
+        msg!("✅ CPI policy set: issuance {}, revocation {}", allow_issuance, allow_revocation);
+        Ok(())
+    }
+
+    /// Replace a credential's proof with a newly signed one
+    /// The authority signs the credential JSON off-chain and an earlier Ed25519
+    /// program instruction verifies it; the previous proof is kept in
+    /// `deprecated_proofs` so legacy credentials regain verifiability
+    pub fn upgrade_credential_proof(
+        ctx: Context<UpgradeCredentialProof>,
+        signature_data: Vec<u8>,
+    ) -> Result<()> {
+        cpi_guard::ensure_invocation_allowed(
+            cpi_guard::GuardedAction::Issuance,
+            ctx.accounts.cpi_policy.as_deref(),
+            Some(ctx.accounts.instructions.as_ref()),
+        )?;
         
-        // Check if already revoked
+        let signature: [u8; 64] = signature_data.as_slice().try_into()
+            .map_err(|_| error!(ValidationError::InvalidSignatureLength))?;
+        let credential = &mut ctx.accounts.credential;
         if credential.is_revoked {
+            msg!("❌ Revoked credentials keep their proof");
             return Err(error!(ValidationError::ValidationFailed));
         }
         
-        // Revoke the credential
-        credential.is_revoked = true;
-        credential.revoked_at = Some(current_timestamp);
+        let message = credential_signing_json(credential);
+        proof::ensure_ed25519_verified(&ctx.accounts.instructions, &ctx.accounts.authority.key(), message.as_bytes(), &signature)?;
         
-        msg!("✅ Credential revoked directly: {}", credential.id);
+        let now = get_current_iso8601()?;
+        let proof = Proof {
+            proof_type: "DataIntegrityProof".to_string(),
+            cryptosuite: "eddsa-rdfc-2022".to_string(),
+            created: now.clone(),
+            proof_purpose: "assertionMethod".to_string(),
+            verification_method: format!("did:sol:{}", ctx.accounts.issuer.key()),
+            proof_value: multibase::encode(&signature_data),
+            previous_proof: None,
+        };
+        proof_upgrade::replace_proof(credential, proof, now.clone())?;
+        let sequence = ctx.accounts.issuer_stats.next_sequence(ctx.accounts.issuer.key(), ctx.bumps.issuer_stats);
+        
+        emit!(events::CredentialProofUpgraded {
+            credential: credential.key(),
+            issuer: credential.issuer,
+            deprecated_proofs: credential.deprecated_proofs.len() as u8,
+            upgraded_at: now,
+            sequence,
+        });
+        
+        msg!("✅ Proof upgraded for credential: {}", credential.id);
         Ok(())
     }
 
-    /// Generate the exact credential JSON that would be created for signing
-    /// This ensures perfect coordination between client and program
-    pub fn generate_credential_json(
-        ctx: Context<GenerateCredentialJson>,
-        achievement_address: String,
-        recipient_address: String,
-        credential_id: String,
-        timestamp: String,
-    ) -> Result<String> {
-        msg!("🔍 Generating credential JSON for signing");
-        msg!("   → Achievement: {}", achievement_address);
-        msg!("   → Recipient: {}", recipient_address);
-        msg!("   → Credential ID: {}", credential_id);
+    /// Co-sign a credential with another verification method of its issuer
+    /// With `chained` the signer counter-signs the latest proof (proof chain);
+    /// otherwise it signs the credential JSON alongside it (proof set). An
+    /// earlier Ed25519 program instruction must verify the signature
+    pub fn add_proof_to_credential(
+        ctx: Context<AddProofToCredential>,
+        signature_data: Vec<u8>,
+        chained: bool,
+    ) -> Result<()> {
+        let credential = &mut ctx.accounts.credential;
+        let issuer_key = &ctx.accounts.issuer_key;
+        if credential.is_revoked || credential.proof.is_empty() {
+            msg!("❌ Only issued, unrevoked credentials can be co-signed");
+            return Err(error!(ValidationError::ValidationFailed));
+        }
+        require!(credential.proof.len() < proof_set::MAX_PROOFS, ValidationError::TooManyItems);
+        
+        let verification_method = issuer_key.verification_method_id();
+        if credential.proof.iter().any(|proof| proof.verification_method == verification_method) {
+            msg!("❌ {} already signed this credential", verification_method);
+            return Err(error!(ValidationError::ValidationFailed));
+        }
+        let now = get_current_iso8601()?;
+        if !issuer_key.is_valid_for_proof_at(&now)? {
+            msg!("❌ Issuer key {} is retired", verification_method);
+            return Err(error!(ValidationError::ValidationFailed));
+        }
+        
+        let previous_index = credential.proof.len() - 1;
+        let previous = chained.then(|| &credential.proof[previous_index]);
+        let message = proof_set::signing_message(&credential_signing_json(credential), previous);
+        let signature: [u8; 64] = signature_data.as_slice().try_into()
+            .map_err(|_| error!(ValidationError::InvalidSignatureLength))?;
+        proof::ensure_ed25519_verified(&ctx.accounts.instructions, &issuer_key.public_key, &message, &signature)?;
+        
+        let previous_proof = chained.then(|| proof_set::proof_id(&credential.id, previous_index));
+        credential.proof.push(Proof {
+            proof_type: "DataIntegrityProof".to_string(),
+            cryptosuite: "eddsa-rdfc-2022".to_string(),
+            created: now,
+            proof_purpose: "assertionMethod".to_string(),
+            verification_method: verification_method.clone(),
+            proof_value: multibase::encode(&signature_data),
+            previous_proof,
+        });
+        
+        emit!(events::CredentialProofAdded {
+            credential: credential.key(),
+            issuer: credential.issuer,
+            verification_method,
+            chained,
+            proof_count: credential.proof.len() as u8,
+        });
+        
+        msg!("✅ Proof {} added to credential: {}", credential.proof.len(), credential.id);
+        Ok(())
+    }
+
+    /// Set the issuer-defined extensions of a credential
+    /// Replaces any earlier extensions and adds their contexts to @context
+    /// `@context` is signed, so `signature_data` is the authority's signature over the
+    /// credential's signing JSON with the extension contexts added, verified by an
+    /// earlier Ed25519 program instruction; it replaces the issuer's proof, which is
+    /// kept in `deprecated_proofs`
+    pub fn set_credential_extensions(
+        ctx: Context<SetCredentialExtensions>,
+        extensions: Vec<extensions::Extension>,
+        signature_data: Vec<u8>,
+    ) -> Result<()> {
+        extensions::validate_extensions(&extensions)?;
+        let signature: [u8; 64] = signature_data.as_slice().try_into()
+            .map_err(|_| error!(ValidationError::InvalidSignatureLength))?;
+        let credential = &mut ctx.accounts.credential;
+        if credential.is_revoked {
+            msg!("❌ Revoked credentials cannot be extended");
+            return Err(error!(ValidationError::ValidationFailed));
+        }
+        
+        extensions::set(credential, extensions)?;
+        let message = credential_signing_json(credential);
+        proof::ensure_ed25519_verified(&ctx.accounts.instructions, &ctx.accounts.authority.key(), message.as_bytes(), &signature)?;
+        
+        let now = get_current_iso8601()?;
+        let proof = Proof {
+            proof_type: "DataIntegrityProof".to_string(),
+            cryptosuite: "eddsa-rdfc-2022".to_string(),
+            created: now.clone(),
+            proof_purpose: "assertionMethod".to_string(),
+            verification_method: format!("did:sol:{}", ctx.accounts.issuer.key()),
+            proof_value: multibase::encode(&signature),
+            previous_proof: None,
+        };
+        proof_upgrade::replace_proof(credential, proof, now.clone())?;
+        let sequence = ctx.accounts.issuer_stats.next_sequence(ctx.accounts.issuer.key(), ctx.bumps.issuer_stats);
+        emit!(events::CredentialExtensionsUpdated {
+            credential: credential.key(),
+            issuer: credential.issuer,
+            terms: credential.extensions.iter().map(|extension| extension.term.clone()).collect(),
+            updated_at: now,
+            sequence,
+        });
+        
+        msg!("🧩 Extensions set on credential: {}", credential.id);
+        Ok(())
+    }
+
+    /// Attach a confidential body, encrypted to the recipient, to a credential issued without one
+    /// Only the envelope, the body's SHA-256 and its field digests are stored; see `encrypted_payload`
+    /// `signature_data` is the authority's signature over the credential's signing JSON
+    /// with the payload attached, verified by an earlier Ed25519 program instruction;
+    /// it replaces the issuer's proof, which is kept in `deprecated_proofs`
+    pub fn attach_encrypted_payload(
+        ctx: Context<AttachEncryptedPayload>,
+        payload: encrypted_payload::EncryptedPayload,
+        signature_data: Vec<u8>,
+    ) -> Result<()> {
+        encrypted_payload::validate(&payload)?;
+        let signature: [u8; 64] = signature_data.as_slice().try_into()
+            .map_err(|_| error!(ValidationError::InvalidSignatureLength))?;
+        let credential = &mut ctx.accounts.credential;
+        encrypted_payload::ensure_attachable(credential)?;
+        
+        let payload_sha256 = payload.payload_sha256;
+        let fields = payload.fields.iter().map(|field| field.path.clone()).collect();
+        credential.encrypted_payload = Some(payload);
+        let message = credential_signing_json(credential);
+        proof::ensure_ed25519_verified(&ctx.accounts.instructions, &ctx.accounts.authority.key(), message.as_bytes(), &signature)?;
+        
+        let now = get_current_iso8601()?;
+        let proof = Proof {
+            proof_type: "DataIntegrityProof".to_string(),
+            cryptosuite: "eddsa-rdfc-2022".to_string(),
+            created: now.clone(),
+            proof_purpose: "assertionMethod".to_string(),
+            verification_method: format!("did:sol:{}", ctx.accounts.issuer.key()),
+            proof_value: multibase::encode(&signature),
+            previous_proof: None,
+        };
+        proof_upgrade::replace_proof(credential, proof, now.clone())?;
+        let sequence = ctx.accounts.issuer_stats.next_sequence(ctx.accounts.issuer.key(), ctx.bumps.issuer_stats);
+        emit!(events::EncryptedPayloadAttached {
+            credential: credential.key(),
+            issuer: credential.issuer,
+            payload_sha256,
+            fields,
+            attached_at: now,
+            sequence,
+        });
+        
+        msg!("🔒 Encrypted payload attached to credential: {}", credential.id);
+        Ok(())
+    }
+
+    /// Check a holder's reveal of a credential's encrypted body
+    /// Accepts the whole decrypted body or a JSON array of some of its
+    /// disclosures; every disclosure must match its field digest
+    pub fn verify_revealed_payload(ctx: Context<ExportCredential>, revealed: String) -> Result<bool> {
+        let Some(payload) = &ctx.accounts.credential.encrypted_payload else {
+            msg!("❌ Credential {} has no encrypted payload", ctx.accounts.credential.id);
+            return Ok(false);
+        };
+        let Some(reveal) = encrypted_payload::verify_reveal(payload, &revealed)? else {
+            return Ok(false);
+        };
+        
+        if reveal.complete {
+            msg!("✅ Revealed the whole payload of {}", ctx.accounts.credential.id);
+        } else {
+            msg!("✅ Revealed {} of {} fields: {}", reveal.verified.len(), payload.fields.len(), reveal.verified.join(", "));
+        }
+        Ok(true)
+    }
+
+    /// Register the SHA-256 of a credential's baked PNG or SVG image
+    /// Replaces any earlier registration so a re-baked image can take its place
+    pub fn register_baked_badge(
+        ctx: Context<RegisterBakedBadge>,
+        image_sha256: [u8; 32],
+        format: baked_image::BakedImageFormat,
+    ) -> Result<()> {
+        let credential = &mut ctx.accounts.credential;
+        if credential.is_revoked {
+            msg!("❌ Revoked credentials cannot register a baked image");
+            return Err(error!(ValidationError::ValidationFailed));
+        }
+        
+        let now = get_current_iso8601()?;
+        credential.baked_image = Some(baked_image::BakedImage {
+            sha256: image_sha256,
+            format,
+            registered_at: now.clone(),
+        });
+        let sequence = ctx.accounts.issuer_stats.next_sequence(ctx.accounts.issuer.key(), ctx.bumps.issuer_stats);
+        emit!(events::BakedBadgeRegistered {
+            credential: credential.key(),
+            issuer: credential.issuer,
+            image_sha256,
+            format,
+            registered_at: now,
+            sequence,
+        });
+        
+        msg!("🍪 Baked image registered for credential: {}", credential.id);
+        Ok(())
+    }
+
+    /// Check a baked image hash against the one registered for the credential
+    /// Only image integrity is checked; revocation and proofs are verified separately
+    pub fn verify_baked_badge(ctx: Context<VerifyBakedBadge>, image_hash: [u8; 32]) -> Result<bool> {
+        let matches = baked_image::matches_registered(&ctx.accounts.credential, &image_hash);
+        msg!(
+            "{} Baked image {} the registered image for credential: {}",
+            if matches { "✅" } else { "❌" },
+            if matches { "matches" } else { "does not match" },
+            ctx.accounts.credential.id
+        );
+        Ok(matches)
+    }
+
+    /// Reissue an expired credential with new validity dates
+    /// The new credential lives at the PDA derived from the expired one and lists
+    /// it in `related`; the authority signs its credential JSON as for issuance,
+    /// verified by an earlier Ed25519 program instruction
+    pub fn reissue_credential(
+        ctx: Context<ReissueCredential>,
+        valid_from: String,
+        valid_until: Option<String>,
+        signature_data: Vec<u8>,
+    ) -> Result<()> {
+        program_config::ensure_not_paused(&ctx.accounts.program_config.to_account_info(), ctx.program_id)?;
+        rate_limit::record_issuance(&mut ctx.accounts.issuer, 1, Clock::get()?.slot)?;
+        collect_issuance_fee(
+            &ctx.accounts.program_config,
+            &ctx.accounts.treasury,
+            [
+                ctx.accounts.fee_token_account.as_ref(),
+                ctx.accounts.treasury_token_account.as_ref(),
+                ctx.accounts.token_program.as_ref(),
+            ],
+            &ctx.accounts.authority,
+            &ctx.accounts.system_program,
+            ctx.program_id,
+        )?;
+        
+        cpi_guard::ensure_invocation_allowed(
+            cpi_guard::GuardedAction::Issuance,
+            ctx.accounts.cpi_policy.as_deref(),
+            Some(ctx.accounts.instructions.as_ref()),
+        )?;
+        
+        let signature: [u8; 64] = signature_data.as_slice().try_into()
+            .map_err(|_| error!(ValidationError::InvalidSignatureLength))?;
+        refresh::validate_validity(&valid_from, valid_until.as_deref())?;
+        let previous = &ctx.accounts.previous_credential;
+        refresh::ensure_reissuable(previous, Clock::get()?.unix_timestamp)?;
+        
+        let now = get_current_iso8601()?;
+        let credential_key = ctx.accounts.credential.key();
+        let mut reissued = refresh::reissue(
+            previous,
+            credential_id::credential_id(ctx.accounts.issuer.credential_id_format, &credential_key),
+            valid_from,
+            valid_until,
+            now.clone(),
+        );
+        
+        let message = credential_signing_json(&reissued);
+        proof::ensure_ed25519_verified(&ctx.accounts.instructions, &ctx.accounts.authority.key(), message.as_bytes(), &signature)?;
+        reissued.proof = vec![Proof {
+            proof_type: "DataIntegrityProof".to_string(),
+            cryptosuite: "eddsa-rdfc-2022".to_string(),
+            created: now,
+            proof_purpose: "assertionMethod".to_string(),
+            verification_method: format!("did:sol:{}", ctx.accounts.issuer.key()),
+            proof_value: multibase::encode(&signature_data),
+            previous_proof: None,
+        }];
+        
+        reissued.bump = ctx.bumps.credential;
+        strictness::validate_issued_credential(ctx.accounts.issuer.validation_mode, &reissued)?;
+        reissued.sequence = ctx.accounts.issuer_stats.next_sequence(ctx.accounts.issuer.key(), ctx.bumps.issuer_stats);
+        
+        emit!(events::CredentialReissued {
+            credential: credential_key,
+            previous: previous.key(),
+            issuer: reissued.issuer,
+            valid_until: reissued.valid_until.clone(),
+            sequence: reissued.sequence,
+        });
+        if let Some(document) = canonical::signed_document(&reissued) {
+            emit!(events::CredentialDocument { credential: credential_key, document });
+        }
+        msg!("🔄 Credential {} reissued as {}", previous.id, reissued.id);
+        ctx.accounts.credential.set_inner(reissued);
+        Ok(())
+    }
+
+    /// Supersede a credential with a corrected replacement awarding `achievement`
+    /// The replacement lives at the PDA derived from the superseded credential
+    /// and lists it in `related`; the superseded credential records the
+    /// replacement in `superseded_by`. The authority signs the replacement's
+    /// credential JSON as for issuance, verified by an earlier Ed25519
+    /// program instruction
+    pub fn supersede_credential(
+        ctx: Context<SupersedeCredential>,
+        valid_from: String,
+        valid_until: Option<String>,
+        signature_data: Vec<u8>,
+    ) -> Result<()> {
+        program_config::ensure_not_paused(&ctx.accounts.program_config.to_account_info(), ctx.program_id)?;
+        rate_limit::record_issuance(&mut ctx.accounts.issuer, 1, Clock::get()?.slot)?;
+        collect_issuance_fee(
+            &ctx.accounts.program_config,
+            &ctx.accounts.treasury,
+            [
+                ctx.accounts.fee_token_account.as_ref(),
+                ctx.accounts.treasury_token_account.as_ref(),
+                ctx.accounts.token_program.as_ref(),
+            ],
+            &ctx.accounts.authority,
+            &ctx.accounts.system_program,
+            ctx.program_id,
+        )?;
+        
+        cpi_guard::ensure_invocation_allowed(
+            cpi_guard::GuardedAction::Issuance,
+            ctx.accounts.cpi_policy.as_deref(),
+            Some(ctx.accounts.instructions.as_ref()),
+        )?;
+        
+        let signature: [u8; 64] = signature_data.as_slice().try_into()
+            .map_err(|_| error!(ValidationError::InvalidSignatureLength))?;
+        refresh::validate_validity(&valid_from, valid_until.as_deref())?;
+        supersession::ensure_supersedable(&ctx.accounts.previous_credential)?;
+        // The previous credential's approval only covers the achievement it was issued for
+        if ctx.accounts.previous_credential.credential_subject.achievement != ctx.accounts.achievement.key() {
+            assessment::ensure_result_not_required(&ctx.accounts.achievement)?;
+        }
+        
+        let now = get_current_iso8601()?;
+        let credential_key = ctx.accounts.credential.key();
+        let mut replacement = supersession::supersede(
+            &ctx.accounts.previous_credential,
+            ctx.accounts.achievement.key(),
+            credential_id::credential_id(ctx.accounts.issuer.credential_id_format, &credential_key),
+            valid_from,
+            valid_until,
+            now.clone(),
+        );
+        
+        let message = credential_signing_json(&replacement);
+        proof::ensure_ed25519_verified(&ctx.accounts.instructions, &ctx.accounts.authority.key(), message.as_bytes(), &signature)?;
+        replacement.proof = vec![Proof {
+            proof_type: "DataIntegrityProof".to_string(),
+            cryptosuite: "eddsa-rdfc-2022".to_string(),
+            created: now,
+            proof_purpose: "assertionMethod".to_string(),
+            verification_method: format!("did:sol:{}", ctx.accounts.issuer.key()),
+            proof_value: multibase::encode(&signature_data),
+            previous_proof: None,
+        }];
+        
+        replacement.bump = ctx.bumps.credential;
+        strictness::validate_issued_credential(ctx.accounts.issuer.validation_mode, &replacement)?;
+        replacement.sequence = ctx.accounts.issuer_stats.next_sequence(ctx.accounts.issuer.key(), ctx.bumps.issuer_stats);
+        
+        let previous = &mut ctx.accounts.previous_credential;
+        previous.superseded_by = Some(credential_key);
+        
+        emit!(events::CredentialSuperseded {
+            credential: credential_key,
+            previous: previous.key(),
+            issuer: replacement.issuer,
+            achievement: replacement.credential_subject.achievement,
+            sequence: replacement.sequence,
+        });
+        if let Some(document) = canonical::signed_document(&replacement) {
+            emit!(events::CredentialDocument { credential: credential_key, document });
+        }
+        msg!("🔁 Credential {} superseded by {}", previous.id, replacement.id);
+        ctx.accounts.credential.set_inner(replacement);
+        Ok(())
+    }
+
+    /// Revoke a credential directly by its account address
+    /// Stores the reason and revoking signer on the credential and emits `CredentialRevoked`
+    pub fn revoke_credential_direct(
+        ctx: Context<RevokeCredentialDirect>,
+        reason: Option<String>,
+    ) -> Result<()> {
+        program_config::ensure_not_paused(&ctx.accounts.program_config.to_account_info(), ctx.program_id)?;
+        
+        cpi_guard::ensure_invocation_allowed(
+            cpi_guard::GuardedAction::Revocation,
+            ctx.accounts.cpi_policy.as_deref(),
+            ctx.accounts.instructions.as_deref(),
+        )?;
+        
+        let credential = &mut ctx.accounts.credential;
+        let current_timestamp = get_current_iso8601()?;
+        
+        // Check if already revoked
+        if credential.is_revoked {
+            return Err(error!(ValidationError::ValidationFailed));
+        }
+        
+        if reason.as_ref().is_some_and(|r| r.len() > MAX_REVOCATION_REASON_LEN) {
+            return Err(error!(ValidationError::RevocationReasonTooLong));
+        }
+        
+        // Revoke the credential
+        lifecycle::transition(
+            credential,
+            lifecycle::CredentialState::Revoked,
+            current_timestamp.clone(),
+            ctx.accounts.authority.key(),
+            reason.clone(),
+        )?;
+        credential.is_revoked = true;
+        credential.revoked_at = Some(current_timestamp.clone());
+        credential.revocation_reason = reason.clone();
+        credential.revoked_by = ctx.accounts.authority.key();
+        ctx.accounts.achievement_stats.record_revoked(
+            credential.credential_subject.achievement,
+            ctx.bumps.achievement_stats,
+            credential.is_expired,
+        );
+        let sequence = ctx.accounts.issuer_stats.next_sequence(ctx.accounts.issuer.key(), ctx.bumps.issuer_stats);
+        
+        emit!(events::CredentialRevoked {
+            credential: credential.key(),
+            issuer: credential.issuer,
+            revoked_by: credential.revoked_by,
+            reason,
+            revoked_at: current_timestamp,
+            sequence,
+        });
+        
+        msg!("✅ Credential revoked directly: {}", credential.id);
+        Ok(())
+    }
+
+    /// Suspend an active credential until it is reinstated or revoked
+    /// Records the transition in the credential's status history and emits `CredentialSuspended`
+    pub fn suspend_credential_direct(
+        ctx: Context<UpdateCredentialStatusDirect>,
+        reason: Option<String>,
+    ) -> Result<()> {
+        program_config::ensure_not_paused(&ctx.accounts.program_config.to_account_info(), ctx.program_id)?;
+        
+        cpi_guard::ensure_invocation_allowed(
+            cpi_guard::GuardedAction::Revocation,
+            ctx.accounts.cpi_policy.as_deref(),
+            ctx.accounts.instructions.as_deref(),
+        )?;
+        
+        let credential = &mut ctx.accounts.credential;
+        let current_timestamp = get_current_iso8601()?;
+        lifecycle::transition(
+            credential,
+            lifecycle::CredentialState::Suspended,
+            current_timestamp.clone(),
+            ctx.accounts.authority.key(),
+            reason.clone(),
+        )?;
+        let sequence = ctx.accounts.issuer_stats.next_sequence(ctx.accounts.issuer.key(), ctx.bumps.issuer_stats);
+        
+        emit!(events::CredentialSuspended {
+            credential: credential.key(),
+            issuer: credential.issuer,
+            suspended_by: ctx.accounts.authority.key(),
+            reason,
+            suspended_at: current_timestamp,
+            sequence,
+        });
+        
+        msg!("⏸️ Credential suspended: {}", credential.id);
+        Ok(())
+    }
+
+    /// Reinstate a suspended credential
+    /// Records the transition in the credential's status history and emits `CredentialReinstated`
+    pub fn reinstate_credential_direct(
+        ctx: Context<UpdateCredentialStatusDirect>,
+        reason: Option<String>,
+    ) -> Result<()> {
+        program_config::ensure_not_paused(&ctx.accounts.program_config.to_account_info(), ctx.program_id)?;
+        
+        cpi_guard::ensure_invocation_allowed(
+            cpi_guard::GuardedAction::Revocation,
+            ctx.accounts.cpi_policy.as_deref(),
+            ctx.accounts.instructions.as_deref(),
+        )?;
+        
+        let credential = &mut ctx.accounts.credential;
+        let current_timestamp = get_current_iso8601()?;
+        lifecycle::transition(
+            credential,
+            lifecycle::CredentialState::Active,
+            current_timestamp.clone(),
+            ctx.accounts.authority.key(),
+            reason.clone(),
+        )?;
+        let sequence = ctx.accounts.issuer_stats.next_sequence(ctx.accounts.issuer.key(), ctx.bumps.issuer_stats);
+        
+        emit!(events::CredentialReinstated {
+            credential: credential.key(),
+            issuer: credential.issuer,
+            reinstated_by: ctx.accounts.authority.key(),
+            reason,
+            reinstated_at: current_timestamp,
+            sequence,
+        });
+        
+        msg!("▶️ Credential reinstated: {}", credential.id);
+        Ok(())
+    }
+
+    /// Move a credential to the recipient's new DID after a lost wallet
+    /// Needs the issuer authority's signature and an earlier Ed25519 program instruction
+    /// in which the new DID's key signs `rebind::rebind_statement`; the original proof is kept
+    pub fn rebind_credential(
+        ctx: Context<RebindCredential>,
+        new_subject_id: String,
+        holder_signature: [u8; 64],
+    ) -> Result<()> {
+        program_config::ensure_not_paused(&ctx.accounts.program_config.to_account_info(), ctx.program_id)?;
+        
+        cpi_guard::ensure_invocation_allowed(
+            cpi_guard::GuardedAction::Issuance,
+            ctx.accounts.cpi_policy.as_deref(),
+            Some(ctx.accounts.instructions.as_ref()),
+        )?;
+        
+        let new_holder = did::DidResolver::new().resolve_assertion_key(&new_subject_id)?;
+        let new_holder = Pubkey::try_from(new_holder.as_slice())
+            .map_err(|_| error!(ValidationError::InvalidKeyLength))?;
+        
+        let credential_key = ctx.accounts.credential.key();
+        let credential = &mut ctx.accounts.credential;
+        let statement = rebind::rebind_statement(&credential_key, credential.rebind_history.len(), &new_subject_id);
+        proof::ensure_ed25519_verified(&ctx.accounts.instructions, &new_holder, statement.as_bytes(), &holder_signature)?;
+        
+        let previous_subject = credential.credential_subject.id.clone().unwrap_or_default();
+        let current_timestamp = get_current_iso8601()?;
+        rebind::rebind(credential, new_subject_id.clone(), current_timestamp.clone(), ctx.accounts.authority.key())?;
+        if credential.holder_binding.is_some() {
+            credential.holder_binding = Some(new_holder);
+        }
+        let sequence = ctx.accounts.issuer_stats.next_sequence(ctx.accounts.issuer.key(), ctx.bumps.issuer_stats);
+        
+        emit!(events::CredentialRebound {
+            credential: credential_key,
+            issuer: credential.issuer,
+            previous_subject,
+            new_subject: new_subject_id,
+            authorized_by: ctx.accounts.authority.key(),
+            rebound_at: current_timestamp,
+            sequence,
+        });
+        
+        msg!("🔁 Credential {} re-bound to a new subject", credential.id);
+        Ok(())
+    }
+
+    /// Revoke several credentials of the signer's issuer in one transaction
+    /// Credentials are passed writable in `remaining_accounts`; a credential may be
+    /// followed by its achievement's AchievementStats account to keep the counters
+    /// in step. With a revocation list, `status_indices[i]` is the status bit of the
+    /// i-th credential and is set along with its `is_revoked` flag.
+    pub fn batch_revoke_credentials_direct<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BatchRevokeCredentialsDirect<'info>>,
+        status_indices: Vec<u32>,
+        reason: Option<String>,
+    ) -> Result<()> {
+        program_config::ensure_not_paused(&ctx.accounts.program_config.to_account_info(), ctx.program_id)?;
+        
+        cpi_guard::ensure_invocation_allowed(
+            cpi_guard::GuardedAction::Revocation,
+            ctx.accounts.cpi_policy.as_deref(),
+            ctx.accounts.instructions.as_deref(),
+        )?;
+        
+        let issuer_key = ctx.accounts.issuer.key();
+        let authority_key = ctx.accounts.authority.key();
+        let current_timestamp = get_current_iso8601()?;
+        let accounts = ctx.remaining_accounts;
+        let mut revoked = 0;
+        let mut cursor = 0;
+        
+        while cursor < accounts.len() {
+            require!(revoked < MAX_BATCH_REVOKE, ValidationError::BatchSizeTooLarge);
+            
+            let credential_info = &accounts[cursor];
+            cursor += 1;
+            require!(credential_info.is_writable, ValidationError::ValidationFailed);
+            let mut credential = Account::<AchievementCredential>::try_from(credential_info)?;
+            if credential.issuer != issuer_key {
+                msg!("❌ Credential {} belongs to another issuer", credential_info.key());
+                return Err(error!(ValidationError::UnauthorizedAccess));
+            }
+            if credential.is_revoked {
+                msg!("❌ Credential {} is already revoked", credential_info.key());
+                return Err(error!(ValidationError::ValidationFailed));
+            }
+            
+            let was_expired = credential.is_expired;
+            lifecycle::transition(
+                &mut credential,
+                lifecycle::CredentialState::Revoked,
+                current_timestamp.clone(),
+                authority_key,
+                reason.clone(),
+            )?;
+            credential.is_revoked = true;
+            credential.revoked_at = Some(current_timestamp.clone());
+            credential.revocation_reason = reason.clone();
+            credential.revoked_by = authority_key;
+            credential.exit(ctx.program_id)?;
+            
+            // Achievement counters directly after the credential are updated too
+            let achievement = credential.credential_subject.achievement;
+            let achievement_stats = accounts
+                .get(cursor)
+                .and_then(|info| Account::<stats::AchievementStats>::try_from(info).ok())
+                .filter(|achievement_stats| achievement_stats.achievement == achievement);
+            if let Some(mut achievement_stats) = achievement_stats {
+                cursor += 1;
+                let bump = achievement_stats.bump;
+                achievement_stats.record_revoked(achievement, bump, was_expired);
+                achievement_stats.exit(ctx.program_id)?;
+            }
+            
+            if let Some(revocation_list) = ctx.accounts.revocation_list.as_mut() {
+                let Some(&index) = status_indices.get(revoked) else {
+                    return Err(error!(ValidationError::MissingRequiredField));
+                };
+                revocation_list.revoke_credential(index, current_timestamp.clone())?;
+            }
+            
+            let sequence = ctx.accounts.issuer_stats.next_sequence(issuer_key, ctx.bumps.issuer_stats);
+            emit!(events::CredentialRevoked {
+                credential: credential_info.key(),
+                issuer: issuer_key,
+                revoked_by: authority_key,
+                reason: reason.clone(),
+                revoked_at: current_timestamp.clone(),
+                sequence,
+            });
+            revoked += 1;
+        }
+        
+        let expected_indices = if ctx.accounts.revocation_list.is_some() { revoked } else { 0 };
+        require!(status_indices.len() == expected_indices, ValidationError::ValidationFailed);
+        
+        msg!("✅ Batch revoked {} credential(s)", revoked);
+        Ok(())
+    }
+
+    /// Mark a credential past its validUntil date as expired; callable by anyone
+    pub fn expire_credential(ctx: Context<ExpireCredential>) -> Result<()> {
+        let credential = &mut ctx.accounts.credential;
+        expiration::ensure_expirable(credential, Clock::get()?.unix_timestamp)?;
+        
+        let expired_at = get_current_iso8601()?;
+        lifecycle::transition(
+            credential,
+            lifecycle::CredentialState::Expired,
+            expired_at.clone(),
+            ctx.accounts.cranker.key(),
+            None,
+        )?;
+        credential.is_expired = true;
+        ctx.accounts.achievement_stats.record_expired(
+            credential.credential_subject.achievement,
+            ctx.bumps.achievement_stats,
+        );
+        
+        emit!(events::CredentialExpired {
+            credential: credential.key(),
+            issuer: credential.issuer,
+            achievement: credential.credential_subject.achievement,
+            valid_until: credential.valid_until.clone().unwrap_or_default(),
+            expired_at,
+        });
+        
+        msg!("⌛ Credential expired: {}", credential.id);
+        Ok(())
+    }
+
+    /// Register a JSON schema and its document digest for the issuer's credentials
+    /// Registering a known schema URI again replaces its digest
+    pub fn register_credential_schema(
+        ctx: Context<RegisterCredentialSchema>,
+        schema_id: String,
+        digest: [u8; 32],
+    ) -> Result<()> {
+        ctx.accounts.schema_registry.register(
+            ctx.accounts.issuer.key(),
+            ctx.bumps.schema_registry,
+            schema_id.clone(),
+            digest,
+        )?;
+        
+        msg!("📐 CREDENTIAL_SCHEMA_REGISTERED: {}", schema_id);
+        Ok(())
+    }
+
+    /// Generate the exact credential JSON that would be created for signing
+    /// This ensures perfect coordination between client and program; a
+    /// credential bound to its holder signs `build_credential_signing_json`
+    /// with `holder` set instead
+    pub fn generate_credential_json(
+        ctx: Context<GenerateCredentialJson>,
+        achievement_address: String,
+        recipient_address: String,
+        credential_id: String,
+        timestamp: String,
+    ) -> Result<String> {
+        msg!("🔍 Generating credential JSON for signing");
+        msg!("   → Achievement: {}", achievement_address);
+        msg!("   → Recipient: {}", recipient_address);
+        msg!("   → Credential ID: {}", credential_id);
         msg!("   → Timestamp: {}", timestamp);
         msg!("   → Issuer: {}", ctx.accounts.issuer.key());
 
         // Use the provided timestamp instead of generating one
         let valid_from = timestamp;
 
-        // Build credential JSON (EXACT same format as in issue_credential)
-        // Use the same approach as issue_credential for perfect matching
-        let context = vec![
-            "https://www.w3.org/ns/credentials/v2".to_string(),
-            "https://purl.imsglobal.org/spec/ob/v3p0/context-3.0.3.json".to_string(),
-        ];
-        let credential_type = vec!["VerifiableCredential".to_string(), "OpenBadgeCredential".to_string()];
-        let subject_type = vec!["AchievementSubject".to_string()];
-        
         // Convert addresses to DID format as per Open Badges 3.0 specification
-        let credential_did = format!("did:sol:{}", credential_id);
+        let credential_did = credential_id::credential_id_for_address(ctx.accounts.issuer.credential_id_format, &credential_id)?;
         let issuer_did = format!("did:sol:{}", ctx.accounts.issuer.key());
         let recipient_did = format!("did:sol:{}", recipient_address);
         let achievement_did = format!("did:sol:{}", achievement_address);
 
-        let credential_json = format!(
-            r#"{{"@context":{},"id":"{}","type":{},"issuer":"{}","validFrom":"{}","credentialSubject":{{"id":"{}","type":{},"achievement":"{}"}}}}"#,
-            serde_json::to_string(&context).unwrap_or_default(),
-            credential_did,
-            serde_json::to_string(&credential_type).unwrap_or_default(),
-            issuer_did,
-            valid_from,
-            recipient_did,
-            serde_json::to_string(&subject_type).unwrap_or_default(),
-            achievement_did
-        );
+        let credential_json = canonical::build_signing_json(&canonical::SigningJsonParams::new(
+            &credential_did,
+            &issuer_did,
+            &valid_from,
+            &recipient_did,
+            &achievement_did,
+        ));
 
         msg!("✅ Generated credential JSON (length: {})", credential_json.len());
         msg!("📝 JSON preview: {}", &credential_json[..credential_json.len().min(200)]);
@@ -1200,30 +3442,19 @@ pub mod open_badges {
 
         let valid_from = timestamp;
 
-        let context = vec![
-            "https://www.w3.org/ns/credentials/v2".to_string(),
-            "https://purl.imsglobal.org/spec/ob/v3p0/context-3.0.3.json".to_string(),
-        ];
-        let credential_type = vec!["VerifiableCredential".to_string(), "OpenBadgeCredential".to_string()];
-        let subject_type = vec!["AchievementSubject".to_string()];
-        
         // Use different formats for different components
-        let credential_did = format!("did:sol:{}", credential_id);
+        let credential_did = credential_id::credential_id_for_address(ctx.accounts.issuer.credential_id_format, &credential_id)?;
         let issuer_did = format!("did:sol:{}", ctx.accounts.issuer.key());
         let recipient_simple_id = format!("sol:{}", recipient_address); // Simple format for recipient
         let achievement_did = format!("did:sol:{}", achievement_address);
 
-        let credential_json = format!(
-            r#"{{"@context":{},"id":"{}","type":{},"issuer":"{}","validFrom":"{}","credentialSubject":{{"id":"{}","type":{},"achievement":"{}"}}}}"#,
-            serde_json::to_string(&context).unwrap_or_default(),
-            credential_did,
-            serde_json::to_string(&credential_type).unwrap_or_default(),
-            issuer_did,
-            valid_from,
-            recipient_simple_id, // Use simple format
-            serde_json::to_string(&subject_type).unwrap_or_default(),
-            achievement_did
-        );
+        let credential_json = canonical::build_signing_json(&canonical::SigningJsonParams::new(
+            &credential_did,
+            &issuer_did,
+            &valid_from,
+            &recipient_simple_id,
+            &achievement_did,
+        ));
 
         msg!("✅ Generated credential JSON for simple subject (length: {})", credential_json.len());
         Ok(credential_json)
@@ -1246,342 +3477,4708 @@ pub mod open_badges {
 
         let valid_from = timestamp;
 
-        let context = vec![
-            "https://www.w3.org/ns/credentials/v2".to_string(),
-            "https://purl.imsglobal.org/spec/ob/v3p0/context-3.0.3.json".to_string(),
-        ];
-        let credential_type = vec!["VerifiableCredential".to_string(), "OpenBadgeCredential".to_string()];
-        let subject_type = vec!["AchievementSubject".to_string()];
-        
         // Use DID format for all components
-        let credential_did = format!("did:sol:{}", credential_id);
+        let credential_did = credential_id::credential_id_for_address(ctx.accounts.issuer.credential_id_format, &credential_id)?;
         let issuer_did = format!("did:sol:{}", ctx.accounts.issuer.key());
         let recipient_did = format!("did:sol:{}", recipient_address); // DID format for recipient
         let achievement_did = format!("did:sol:{}", achievement_address);
 
-        let credential_json = format!(
-            r#"{{"@context":{},"id":"{}","type":{},"issuer":"{}","validFrom":"{}","credentialSubject":{{"id":"{}","type":{},"achievement":"{}"}}}}"#,
-            serde_json::to_string(&context).unwrap_or_default(),
-            credential_did,
-            serde_json::to_string(&credential_type).unwrap_or_default(),
-            issuer_did,
-            valid_from,
-            recipient_did, // Use DID format
-            serde_json::to_string(&subject_type).unwrap_or_default(),
-            achievement_did
-        );
+        let credential_json = canonical::build_signing_json(&canonical::SigningJsonParams::new(
+            &credential_did,
+            &issuer_did,
+            &valid_from,
+            &recipient_did,
+            &achievement_did,
+        ));
 
         msg!("✅ Generated credential JSON for DID subject (length: {})", credential_json.len());
         Ok(credential_json)
     }
 
-    // ===================================================================
-    // MAIN FUNCTIONS
-    // ===================================================================
-}
-// Account structures aligned with Open Badges v3.0 specification
-
-/// Profile - represents the entity that issues credentials (Issuer)
-/// Aligned with Profile class in OB v3.0 spec
-#[account]
-pub struct Profile {
-    /// Unique URI for the Profile [1] - REQUIRED (DID format)
-    pub id: String,
-    /// Type array [1..*] - Must include "Profile"
-    pub r#type: Vec<String>,
-    /// Authority that can manage this issuer profile
-    pub authority: Pubkey,
-    /// Name of the issuer [0..1] - RECOMMENDED
-    pub name: String,
-    /// Homepage URL of the issuer [0..1] - RECOMMENDED  
-    pub url: Option<String>,
-    /// Contact email of the issuer [0..1] - RECOMMENDED
-    pub email: Option<String>,
-    /// Bump seed for PDA
-    pub bump: u8,
-}
+    /// Generate credential JSON for pairwise pseudonymous subject format
+    /// `pairwise_subject_id` is the pseudonym the holder derived for this issuer
+    pub fn generate_credential_json_pairwise_subject(
+        ctx: Context<GenerateCredentialJson>,
+        achievement_address: String,
+        pairwise_subject_id: String,
+        credential_id: String,
+        timestamp: String,
+    ) -> Result<String> {
+        msg!("🔍 Generating credential JSON for pairwise subject");
+        
+        privacy::validate_pairwise_subject_id(&pairwise_subject_id)?;
+        
+        let credential_did = credential_id::credential_id_for_address(ctx.accounts.issuer.credential_id_format, &credential_id)?;
+        let issuer_did = format!("did:sol:{}", ctx.accounts.issuer.key());
+        let pairwise_id = pairwise_subject_id;
+        let achievement_did = format!("did:sol:{}", achievement_address);
+        
+        let credential_json = canonical::build_signing_json(&canonical::SigningJsonParams::new(
+            &credential_did,
+            &issuer_did,
+            &timestamp,
+            &pairwise_id,
+            &achievement_did,
+        ));
+        
+        msg!("✅ Generated credential JSON for pairwise subject (length: {})", credential_json.len());
+        Ok(credential_json)
+    }
 
-/// Achievement - defines the accomplishment itself
-/// Aligned with Achievement class in OB v3.0 spec
-#[account]
-pub struct Achievement {
-    /// @context [1..*] - JSON-LD context URIs - REQUIRED
-    pub context: Vec<String>,
-    /// Unique URI for the Achievement [1] - REQUIRED
-    pub id: String,
-    /// Type array [1..*] - Must include "Achievement"
-    pub r#type: Vec<String>,
-    /// The issuer that created this achievement
-    pub issuer: Pubkey,
-    /// Name of the achievement [1] - REQUIRED
-    pub name: String,
-    /// Description of the achievement [1] - REQUIRED
-    pub description: String,
-    /// Criteria for earning the achievement
-    pub criteria: Criteria,
-    /// Creator of the achievement [0..1] - RECOMMENDED
-    pub creator: Option<Pubkey>,
-    /// Timestamp when achievement was created (ISO 8601 string)
-    pub created_at: String,
-    /// Bump seed for PDA
-    pub bump: u8,
-}
+    /// Build the signing JSON from explicit parameters, with the same
+    /// canonical layout every issuance instruction checks `message_data` against
+    pub fn build_credential_signing_json(
+        _ctx: Context<BuildCredentialSigningJson>,
+        params: canonical::SigningJsonParams,
+    ) -> Result<String> {
+        let credential_json = canonical::build_signing_json(&params);
+        msg!("✅ Built credential signing JSON (length: {})", credential_json.len());
+        Ok(credential_json)
+    }
 
-/// Criteria - describes how the achievement is earned
-/// Part of Achievement class in OB v3.0 spec
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct Criteria {
-    /// URI of a webpage describing criteria [0..1] - RECOMMENDED
-    pub id: Option<String>,
-    /// Narrative description of criteria [0..1] - RECOMMENDED
-    pub narrative: Option<String>,
-}
+    /// Verify a holder-signed proof linking pairwise subject ids across issuers
+    /// The holder's signature is checked through an earlier Ed25519 program
+    /// instruction, so the instructions sysvar account is required
+    pub fn verify_subject_correlation(
+        ctx: Context<VerifyCredentialFormat>,
+        correlation_proof_json: String,
+    ) -> Result<bool> {
+        let proof: privacy::CorrelationProof = serde_json::from_str(&correlation_proof_json)
+            .map_err(|_| error!(ValidationError::InvalidProof))?;
+        let instructions = ctx.accounts.instructions.as_ref()
+            .ok_or_else(|| error!(ValidationError::MissingRequiredField))?;
+        
+        let is_valid = privacy::verify_correlation_proof(&proof, instructions)?;
+        
+        if is_valid {
+            msg!("✅ Subject correlation verified across {} issuers", proof.links.len());
+        } else {
+            msg!("❌ Subject correlation verification failed");
+        }
+        
+        Ok(is_valid)
+    }
 
-/// AchievementSubject - represents the recipient of the credential
-/// Aligned with AchievementSubject class in OB v3.0 spec
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct AchievementSubject {
-    /// An identifier for the Credential Subject [0..1]
-    pub id: Option<String>,
-    /// Type array [1..*] - Must include "AchievementSubject"
-    /// Note: Using subject_type temporarily to avoid r#type deserialization issues in nested structs
-    pub subject_type: Vec<String>,
-    /// The achievement being awarded [1] - REQUIRED
-    pub achievement: Pubkey,
-    /// Other identifiers for the recipient [0..*]
-    pub identifier: Vec<IdentityObject>,
-}
+    /// Export a credential as JSON-LD with selected fields redacted
+    /// `redacted_fields` are the holder's manifest entries, digested off-chain
+    /// with salts that never reach the program; returns the redacted document,
+    /// without the issuer's proof, plus the manifest
+    pub fn export_redacted_credential(
+        ctx: Context<ExportCredential>,
+        redacted_fields: Vec<redaction::RedactedField>,
+    ) -> Result<String> {
+        let document = credential::credential_account_to_json(&ctx.accounts.credential);
+        let export = redaction::redact_credential(&document, &redacted_fields)?;
+        
+        let export_json = serde_json::to_string(&export)
+            .map_err(|_| error!(ValidationError::SerializationError))?;
+        
+        msg!("✅ Exported credential with {} redacted fields", redacted_fields.len());
+        Ok(export_json)
+    }
 
-impl AchievementSubject {
-    /// Validate the achievement subject for Open Badges 3.0 compliance
-    pub fn validate(&self) -> Result<()> {
-        // Validate required subject types
-        if !self.subject_type.contains(&"AchievementSubject".to_string()) {
-            return Err(error!(ValidationError::InvalidCredentialType));
+    /// Check an off-chain copy of a credential against its account
+    /// The copy must be the account's full JSON-LD document; the signed
+    /// document alone does not cover every member. Members that differ are logged
+    pub fn verify_credential_document(
+        ctx: Context<ExportCredential>,
+        credential_json: String,
+    ) -> Result<bool> {
+        let comparison = document_integrity::compare(&ctx.accounts.credential, &credential_json)?;
+        
+        if comparison.matches() {
+            msg!("✅ Credential document matches {}", ctx.accounts.credential.id);
+        } else if comparison.exact {
+            msg!("❌ Signed document of {} does not cover: {}", ctx.accounts.credential.id, comparison.mismatched.join(", "));
+        } else {
+            msg!("❌ Credential document differs from {} at: {}", ctx.accounts.credential.id, comparison.mismatched.join(", "));
         }
+        
+        Ok(comparison.matches())
+    }
 
-        // Validate identity objects
-        for identity in &self.identifier {
-            identity.validate()?;
+    /// Export a self-contained verification bundle for a credential
+    /// Holds the JSON-LD document, the proof as a plain Ed25519 check, the
+    /// issuer's DID document and, with a revocation list, its status entry
+    pub fn export_verification_bundle(
+        ctx: Context<ExportVerificationBundle>,
+        status_list_index: Option<u32>,
+    ) -> Result<String> {
+        let status = match (&ctx.accounts.revocation_list, status_list_index) {
+            (Some(list), Some(index)) => Some((&**list, index)),
+            (None, None) => None,
+            _ => {
+                msg!("❌ A status list index needs its revocation list, and a revocation list an index");
+                return Err(error!(ValidationError::MissingRequiredField));
+            }
+        };
+        let did_account = credential::read_sol_did_account(&ctx.accounts.issuer.authority, &ctx.accounts.issuer_did_account)?;
+        let bundle = verification_bundle::build(&ctx.accounts.credential, &did_account, status, &get_current_iso8601()?)?;
+        
+        let bundle_json = serde_json::to_string(&bundle)
+            .map_err(|_| error!(ValidationError::SerializationError))?;
+        
+        msg!("✅ Exported verification bundle for {} (length: {})", ctx.accounts.credential.id, bundle_json.len());
+        Ok(bundle_json)
+    }
+
+    /// Turn the achievement's recipient roster on or off
+    /// Turning it off closes the roster pages, passed in order as remaining accounts,
+    /// and stops recording holders
+    pub fn set_recipient_roster_tracking<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SetRecipientRosterTracking<'info>>,
+        enabled: bool,
+    ) -> Result<()> {
+        if !enabled {
+            recipient_roster::close_pages(
+                &ctx.accounts.recipient_roster,
+                ctx.remaining_accounts,
+                &ctx.accounts.authority.to_account_info(),
+                ctx.program_id,
+            )?;
         }
+        recipient_roster::set_tracking(
+            &mut ctx.accounts.recipient_roster,
+            ctx.accounts.achievement.key(),
+            ctx.bumps.recipient_roster,
+            enabled,
+        );
+        msg!("📇 RECIPIENT_ROSTER: {} tracking {}", ctx.accounts.achievement.name, if enabled { "enabled" } else { "disabled" });
+        Ok(())
+    }
 
+    /// Attach a grading rubric to an achievement
+    pub fn create_achievement_rubric(
+        ctx: Context<CreateAchievementRubric>,
+        title: String,
+        levels: Vec<rubric::RubricLevel>,
+    ) -> Result<()> {
+        rubric::validate_rubric(&title, &levels)?;
+        
+        let rubric = &mut ctx.accounts.rubric;
+        rubric.achievement = ctx.accounts.achievement.key();
+        rubric.title = title;
+        rubric.levels = levels;
+        rubric.updated_at = get_current_iso8601()?;
+        rubric.bump = ctx.bumps.rubric;
+        
+        msg!("📐 RUBRIC_CREATED: {} ({} levels)", rubric.title, rubric.levels.len());
         Ok(())
     }
-}
 
-/// IdentityObject - represents identity information
-/// Aligned with IdentityObject class in OB v3.0 spec
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct IdentityObject {
-    /// Type [1] - Must be "IdentityObject"
-    pub identity_type: String,
-    /// Whether identityHash is hashed [1] - REQUIRED
-    pub hashed: bool,
-    /// The identity value or its hash [1] - REQUIRED
-    pub identity_hash: String,
-    /// Type of identity (email, did, etc.) [1] - REQUIRED
-    pub identity_type_name: String,
-}
+    /// Replace the title and levels of an achievement's rubric
+    pub fn update_achievement_rubric(
+        ctx: Context<ManageAchievementRubric>,
+        title: String,
+        levels: Vec<rubric::RubricLevel>,
+    ) -> Result<()> {
+        rubric::validate_rubric(&title, &levels)?;
+        
+        let rubric = &mut ctx.accounts.rubric;
+        rubric.title = title;
+        rubric.levels = levels;
+        rubric.updated_at = get_current_iso8601()?;
+        
+        msg!("📐 RUBRIC_UPDATED: {} ({} levels)", rubric.title, rubric.levels.len());
+        Ok(())
+    }
 
-impl IdentityObject {
-    /// Validate the identity object for Open Badges 3.0 compliance
-    pub fn validate(&self) -> Result<()> {
-        // Validate required identity type
-        if self.identity_type != "IdentityObject" {
-            return Err(error!(ValidationError::InvalidCredentialType));
-        }
+    /// Remove the rubric from an achievement and reclaim its rent
+    pub fn close_achievement_rubric(ctx: Context<CloseAchievementRubric>) -> Result<()> {
+        msg!("📐 RUBRIC_CLOSED for achievement: {}", ctx.accounts.achievement.key());
+        Ok(())
+    }
 
-        // Validate that we have a hash value
-        if self.identity_hash.is_empty() {
-            return Err(error!(ValidationError::MissingRequiredField));
-        }
+    /// Grant an assessor or moderator role for the issuer's achievements
+    pub fn grant_assessment_role(
+        ctx: Context<GrantAssessmentRole>,
+        member: Pubkey,
+        role: assessment::AssessmentRole,
+    ) -> Result<()> {
+        let grant = &mut ctx.accounts.role_grant;
+        grant.issuer = ctx.accounts.issuer.key();
+        grant.member = member;
+        grant.role = role;
+        grant.granted_at = get_current_iso8601()?;
+        grant.bump = ctx.bumps.role_grant;
+        
+        msg!("👥 ROLE_GRANTED: {:?} to {}", role, member);
+        Ok(())
+    }
 
-        // Validate that we have an identity type name
-        if self.identity_type_name.is_empty() {
-            return Err(error!(ValidationError::MissingRequiredField));
-        }
+    /// Revoke a previously granted assessment role
+    pub fn revoke_assessment_role(ctx: Context<RevokeAssessmentRole>) -> Result<()> {
+        let grant = &ctx.accounts.role_grant;
+        msg!("👥 ROLE_REVOKED: {:?} from {}", grant.role, grant.member);
+        Ok(())
+    }
 
+    /// Submit an assessment result for a learner (assessor role)
+    /// Creates a pending ResultEntry linked to the credential PDA it will back
+    pub fn submit_result(
+        ctx: Context<SubmitResult>,
+        recipient: Pubkey,
+        value: String,
+        comment: Option<String>,
+    ) -> Result<()> {
+        assessment::validate_submission(&value, &comment)?;
+        
+        let achievement_key = ctx.accounts.achievement.key();
+        let issuer_key = ctx.accounts.achievement.issuer;
+        let (credential_pda, _) = pda::find_credential_pda(&achievement_key, &issuer_key, &recipient, ctx.program_id);
+        
+        let entry = &mut ctx.accounts.result_entry;
+        entry.achievement = achievement_key;
+        entry.issuer = issuer_key;
+        entry.recipient = recipient;
+        entry.credential = credential_pda;
+        entry.assessor = ctx.accounts.assessor.key();
+        entry.value = value;
+        entry.comment = comment;
+        entry.status = assessment::ResultStatus::Pending;
+        entry.moderator = None;
+        entry.moderator_comment = None;
+        entry.submitted_at = get_current_iso8601()?;
+        entry.reviewed_at = None;
+        entry.bump = ctx.bumps.result_entry;
+        
+        msg!("📝 RESULT_SUBMITTED for {} (credential {})", recipient, credential_pda);
         Ok(())
     }
-}
 
-/// Proof - cryptographic proof for verification
-/// Aligned with Proof class in VC Data Model v2.0 and Open Badges 3.0
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct Proof {
-    /// Signature suite used [1] - REQUIRED
-    pub proof_type: String,
-    /// Cryptographic suite identifier [1] - REQUIRED for eddsa-rdfc-2022
-    pub cryptosuite: String,
-    /// Timestamp when proof was created [1] - REQUIRED (ISO 8601 format)
-    pub created: String,
-    /// Purpose of the proof [1] - Must be "assertionMethod"
-    pub proof_purpose: String,
-    /// URI of public key for verification [1] - REQUIRED
-    pub verification_method: String,
-    /// The signature value [1] - REQUIRED
-    pub proof_value: String,
-}
+    /// Approve or reject a pending assessment result (moderator role)
+    pub fn approve_result(
+        ctx: Context<ApproveResult>,
+        approved: bool,
+        comment: Option<String>,
+    ) -> Result<()> {
+        if comment.as_ref().is_some_and(|c| c.len() > assessment::MAX_RESULT_COMMENT_LEN) {
+            return Err(error!(ValidationError::FieldTooLong));
+        }
+        
+        let entry = &mut ctx.accounts.result_entry;
+        if entry.status != assessment::ResultStatus::Pending {
+            return Err(error!(ValidationError::ResultAlreadyReviewed));
+        }
+        // Separation of duties: an assessor can't moderate their own submission
+        if entry.assessor == ctx.accounts.moderator.key() {
+            return Err(error!(ValidationError::UnauthorizedAccess));
+        }
+        
+        entry.status = if approved {
+            assessment::ResultStatus::Approved
+        } else {
+            assessment::ResultStatus::Rejected
+        };
+        entry.moderator = Some(ctx.accounts.moderator.key());
+        entry.moderator_comment = comment;
+        entry.reviewed_at = Some(get_current_iso8601()?);
+        
+        msg!("⚖️ RESULT_REVIEWED: {:?} for {}", entry.status, entry.recipient);
+        Ok(())
+    }
 
-/// AchievementCredential - the core on-chain asset (Verifiable Credential)
-/// Aligned with AchievementCredential class in OB v3.0 spec
-#[account]
-pub struct AchievementCredential {
-    /// Unambiguous reference to the credential [1] - REQUIRED
-    pub id: String,
-    /// @context [2..*] - JSON-LD context URIs
-    pub context: Vec<String>,
-    /// type [1..*] - Must include VerifiableCredential and AchievementCredential
-    pub r#type: Vec<String>,
-    /// issuer [1] - ProfileRef (using Pubkey for on-chain reference)
-    pub issuer: Pubkey,
-    /// validFrom [1] - DateTimeZ (ISO 8601 string)
-    pub valid_from: String,
-    /// validUntil [0..1] - DateTimeZ (ISO 8601 string, optional)
-    pub valid_until: Option<String>,
-    /// Issuance timestamp (ISO 8601 string)
-    pub issued_at: String,
-    /// The recipient of the achievement [1] - REQUIRED
-    pub credential_subject: AchievementSubject,
-    /// Cryptographic proof [0..*] - STRONGLY RECOMMENDED
-    pub proof: Option<Proof>,
-    /// Whether the credential is revoked
-    pub is_revoked: bool,
-    /// Timestamp when credential was revoked (ISO 8601 string, optional)
-    pub revoked_at: Option<String>,
-    /// Bump seed for PDA
-    pub bump: u8,
-}
+    /// Enable or update community issuance for an achievement
+    pub fn configure_peer_issuance(
+        ctx: Context<ConfigurePeerIssuance>,
+        peer_threshold: u16,
+        endorsers_must_hold: bool,
+    ) -> Result<()> {
+        peer::validate_threshold(peer_threshold)?;
+        
+        let config = &mut ctx.accounts.peer_config;
+        config.achievement = ctx.accounts.achievement.key();
+        config.peer_threshold = peer_threshold;
+        config.endorsers_must_hold = endorsers_must_hold;
+        config.bump = ctx.bumps.peer_config;
+        
+        msg!("🤝 PEER_ISSUANCE_CONFIGURED: {} endorsements required for {}", peer_threshold, ctx.accounts.achievement.name);
+        Ok(())
+    }
 
-impl AchievementCredential {
-    /// Validate the credential for Open Badges 3.0 compliance
-    pub fn validate(&self) -> Result<()> {
-        // Validate required contexts
-        if !self.context.contains(&"https://www.w3.org/2018/credentials/v1".to_string()) {
-            return Err(error!(ValidationError::MissingRequiredField));
+    /// Endorse a recipient for a peer-issued achievement
+    /// Each endorser can endorse a recipient once; the tally counts distinct endorsers
+    pub fn endorse_peer(
+        ctx: Context<EndorsePeer>,
+        recipient: Pubkey,
+        comment: Option<String>,
+    ) -> Result<()> {
+        let endorser_key = ctx.accounts.endorser.key();
+        if endorser_key == recipient {
+            return Err(error!(ValidationError::SelfEndorsement));
         }
-
-        if !self.context.contains(&"https://purl.imsglobal.org/spec/ob/v3p0/context-3.0.3.json".to_string()) {
-            return Err(error!(ValidationError::MissingRequiredField));
+        if comment.as_ref().is_some_and(|c| c.len() > peer::MAX_PEER_COMMENT_LEN) {
+            return Err(error!(ValidationError::FieldTooLong));
         }
-
-        // Validate required credential types
-        if !self.r#type.contains(&"VerifiableCredential".to_string()) {
-            return Err(error!(ValidationError::InvalidCredentialType));
+        
+        if ctx.accounts.peer_config.endorsers_must_hold {
+            let holds_active = ctx.accounts.endorser_credential
+                .as_ref()
+                .is_some_and(|credential| !credential.is_revoked);
+            if !holds_active {
+                return Err(error!(ValidationError::EndorserNotHolder));
+            }
         }
-
-        if !self.r#type.contains(&"AchievementCredential".to_string()) {
-            return Err(error!(ValidationError::InvalidCredentialType));
+        
+        let achievement_key = ctx.accounts.achievement.key();
+        let endorsement = &mut ctx.accounts.endorsement;
+        endorsement.achievement = achievement_key;
+        endorsement.recipient = recipient;
+        endorsement.endorser = endorser_key;
+        endorsement.comment = comment;
+        endorsement.endorsed_at = get_current_iso8601()?;
+        endorsement.bump = ctx.bumps.endorsement;
+        
+        let tally = &mut ctx.accounts.tally;
+        if tally.achievement == Pubkey::default() {
+            tally.achievement = achievement_key;
+            tally.recipient = recipient;
+            tally.bump = ctx.bumps.tally;
         }
-
-        // Validate credential subject
-        self.credential_subject.validate()?;
-
+        tally.endorsement_count = tally.endorsement_count.saturating_add(1);
+        
+        msg!(
+            "🤝 PEER_ENDORSED: {} by {} ({}/{})",
+            recipient,
+            endorser_key,
+            tally.endorsement_count,
+            ctx.accounts.peer_config.peer_threshold
+        );
         Ok(())
     }
-}
 
-// Context structures
+    /// Issue an AchievementCredential on behalf of a program-controlled authority
+    /// Meant for CPI from grading or LMS programs whose issuer authority is a PDA
+    /// signed with `invoke_signed`. A PDA has no private key, so there is no
+    /// Ed25519 proof: the signed instruction itself is the issuer's authorization
+    pub fn issue_achievement_credential_cpi(
+        ctx: Context<IssueAchievementCredentialCpi>,
+        recipient_pubkey: Pubkey,
+        results: Vec<results::ResultInput>,
+    ) -> Result<()> {
+        profiling::checkpoint("issue_cpi: start");
+        issuance::check(
+            &mut ctx.accounts.issuer,
+            &ctx.accounts.achievement,
+            issuance::CheckAccounts {
+                program_config: &ctx.accounts.program_config,
+                treasury: &ctx.accounts.treasury,
+                fee_token_accounts: [
+                    ctx.accounts.fee_token_account.as_ref(),
+                    ctx.accounts.treasury_token_account.as_ref(),
+                    ctx.accounts.token_program.as_ref(),
+                ],
+                payer: &ctx.accounts.payer,
+                system_program: &ctx.accounts.system_program,
+                cpi_policy: ctx.accounts.cpi_policy.as_deref(),
+                instructions: ctx.accounts.instructions.as_deref(),
+                recipient_opt_out: &ctx.accounts.recipient_opt_out,
+                result_entry: ctx.accounts.result_entry.as_deref(),
+                rubric: ctx.accounts.rubric.as_deref(),
+            },
+            &recipient_pubkey,
+            &results,
+            ctx.program_id,
+        )?;
+        
+        issuance::populate_credential(
+            issuance::IssuanceAccounts {
+                credential: &mut ctx.accounts.credential,
+                issuer: &ctx.accounts.issuer,
+                achievement: &ctx.accounts.achievement,
+                issuer_stats: &mut ctx.accounts.issuer_stats,
+                achievement_stats: &mut ctx.accounts.achievement_stats,
+                credential_index: &mut ctx.accounts.credential_index,
+                credential_index_page: &mut ctx.accounts.credential_index_page,
+                recipient_roster: &mut ctx.accounts.recipient_roster,
+                recipient_roster_page: &mut ctx.accounts.recipient_roster_page,
+                payer: ctx.accounts.payer.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+            },
+            issuance::IssuanceBumps {
+                credential: ctx.bumps.credential,
+                issuer_stats: ctx.bumps.issuer_stats,
+                achievement_stats: ctx.bumps.achievement_stats,
+                credential_index: ctx.bumps.credential_index,
+                credential_index_page: ctx.bumps.credential_index_page,
+                recipient_roster: ctx.bumps.recipient_roster,
+                recipient_roster_page: ctx.bumps.recipient_roster_page,
+            },
+            recipient_pubkey,
+            issuance::CredentialContent::unsigned(&ctx.accounts.issuer, results)?,
+            None,
+        )?;
+        issuance::emit_issued(&ctx.accounts.credential, recipient_pubkey);
+        
+        msg!("🏅 CREDENTIAL_ISSUED via CPI: {} (authority {})", ctx.accounts.achievement.name, ctx.accounts.authority.key());
+        profiling::checkpoint("issue_cpi: done");
+        Ok(())
+    }
+
+    /// Issue a peer credential once the endorsement threshold is met
+    /// Permissionless: any payer can crank issuance, usually the final endorser
+    pub fn issue_peer_credential(
+        ctx: Context<IssuePeerCredential>,
+        recipient_pubkey: Pubkey,
+    ) -> Result<()> {
+        program_config::ensure_not_paused(&ctx.accounts.program_config.to_account_info(), ctx.program_id)?;
+        rate_limit::record_issuance(&mut ctx.accounts.issuer, 1, Clock::get()?.slot)?;
+        collect_issuance_fee(
+            &ctx.accounts.program_config,
+            &ctx.accounts.treasury,
+            [
+                ctx.accounts.fee_token_account.as_ref(),
+                ctx.accounts.treasury_token_account.as_ref(),
+                ctx.accounts.token_program.as_ref(),
+            ],
+            &ctx.accounts.payer,
+            &ctx.accounts.system_program,
+            ctx.program_id,
+        )?;
+        // Peer endorsement stands in for assessment, so moderated achievements are refused
+        assessment::ensure_result_not_required(&ctx.accounts.achievement)?;
+        
+        let tally = &mut ctx.accounts.tally;
+        if !tally.threshold_met(&ctx.accounts.peer_config) {
+            msg!(
+                "❌ {} of {} peer endorsements",
+                tally.endorsement_count,
+                ctx.accounts.peer_config.peer_threshold
+            );
+            return Err(error!(ValidationError::PeerThresholdNotMet));
+        }
+        
+        opt_out::ensure_not_opted_out(
+            &ctx.accounts.recipient_opt_out.to_account_info(),
+            &recipient_pubkey,
+            ctx.program_id,
+        )?;
+        
+        // No issuer signature: the endorsement PDAs are the evidence for this credential
+        issuance::populate_credential(
+            issuance::IssuanceAccounts {
+                credential: &mut ctx.accounts.credential,
+                issuer: &ctx.accounts.issuer,
+                achievement: &ctx.accounts.achievement,
+                issuer_stats: &mut ctx.accounts.issuer_stats,
+                achievement_stats: &mut ctx.accounts.achievement_stats,
+                credential_index: &mut ctx.accounts.credential_index,
+                credential_index_page: &mut ctx.accounts.credential_index_page,
+                recipient_roster: &mut ctx.accounts.recipient_roster,
+                recipient_roster_page: &mut ctx.accounts.recipient_roster_page,
+                payer: ctx.accounts.payer.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+            },
+            issuance::IssuanceBumps {
+                credential: ctx.bumps.credential,
+                issuer_stats: ctx.bumps.issuer_stats,
+                achievement_stats: ctx.bumps.achievement_stats,
+                credential_index: ctx.bumps.credential_index,
+                credential_index_page: ctx.bumps.credential_index_page,
+                recipient_roster: ctx.bumps.recipient_roster,
+                recipient_roster_page: ctx.bumps.recipient_roster_page,
+            },
+            recipient_pubkey,
+            issuance::CredentialContent::unsigned(&ctx.accounts.issuer, vec![])?,
+            None,
+        )?;
+        let credential = &ctx.accounts.credential;
+        
+        tally.issued = true;
+        
+        emit!(events::PeerCredentialIssued {
+            credential: credential.key(),
+            achievement: ctx.accounts.achievement.key(),
+            recipient: recipient_pubkey,
+            endorsement_count: tally.endorsement_count,
+            sequence: credential.sequence,
+        });
+        
+        msg!("🏅 PEER_CREDENTIAL_ISSUED: {} with {} endorsements", ctx.accounts.achievement.name, tally.endorsement_count);
+        Ok(())
+    }
+
+    /// Escrow a credential for a recipient without a wallet, claimable with a secret code
+    /// `claim_key` is the Ed25519 public key whose seed is the SHA-256 hash of the code
+    /// handed to the recipient
+    pub fn issue_with_claim_code(
+        ctx: Context<IssueWithClaimCode>,
+        claim_key: Pubkey,
+        expires_at: i64,
+        results: Vec<results::ResultInput>,
+    ) -> Result<()> {
+        program_config::ensure_not_paused(&ctx.accounts.program_config.to_account_info(), ctx.program_id)?;
+        let clock = Clock::get()?;
+        claim_code::validate_expiry(expires_at, clock.unix_timestamp)?;
+        rate_limit::record_issuance(&mut ctx.accounts.issuer, 1, clock.slot)?;
+        collect_issuance_fee(
+            &ctx.accounts.program_config,
+            &ctx.accounts.treasury,
+            [
+                ctx.accounts.fee_token_account.as_ref(),
+                ctx.accounts.treasury_token_account.as_ref(),
+                ctx.accounts.token_program.as_ref(),
+            ],
+            &ctx.accounts.authority,
+            &ctx.accounts.system_program,
+            ctx.program_id,
+        )?;
+        
+        cpi_guard::ensure_invocation_allowed(
+            cpi_guard::GuardedAction::Issuance,
+            ctx.accounts.cpi_policy.as_deref(),
+            ctx.accounts.instructions.as_deref(),
+        )?;
+        // Results are approved per recipient, and an offer has no recipient yet
+        assessment::ensure_result_not_required(&ctx.accounts.achievement)?;
+        
+        results::validate_results(
+            &results,
+            &ctx.accounts.achievement.result_descriptions,
+            ctx.accounts.rubric.as_deref(),
+        )?;
+        criteria::ensure_thresholds_met(&ctx.accounts.achievement.requirements, &results)?;
+        
+        let offer = &mut ctx.accounts.claim_offer;
+        offer.issuer = ctx.accounts.issuer.key();
+        offer.achievement = ctx.accounts.achievement.key();
+        offer.claim_key = claim_key;
+        offer.results = results;
+        offer.offered_at = get_current_iso8601()?;
+        offer.expires_at = expires_at;
+        offer.bump = ctx.bumps.claim_offer;
+        
+        emit!(events::ClaimOfferCreated {
+            offer: offer.key(),
+            issuer: offer.issuer,
+            achievement: offer.achievement,
+            expires_at,
+        });
+        
+        msg!("🎟️ CLAIM_OFFER_CREATED: {} (expires {})", ctx.accounts.achievement.name, expires_at);
+        Ok(())
+    }
+
+    /// Claim an escrowed credential with its code, binding it to the signing wallet
+    /// `claim_signature` is the claim key's signature over `claim_code::claim_message`
+    /// for the offer and the new wallet, verified by an earlier Ed25519 program instruction
+    /// The offer is closed and its rent returned to the issuer authority
+    /// No issuance fee is charged: `issue_with_claim_code` charged it for the offer
+    pub fn claim_with_code(
+        ctx: Context<ClaimWithCode>,
+        claim_signature: [u8; 64],
+        new_recipient_pubkey: Pubkey,
+    ) -> Result<()> {
+        program_config::ensure_not_paused(&ctx.accounts.program_config.to_account_info(), ctx.program_id)?;
+        ctx.accounts.claim_offer.ensure_claimable(
+            &ctx.accounts.claim_offer.key(),
+            &new_recipient_pubkey,
+            &ctx.accounts.instructions,
+            &claim_signature,
+            Clock::get()?.unix_timestamp,
+        )?;
+        // Counted against the rate limit when the offer was made
+        rate_limit::ensure_not_frozen(&ctx.accounts.issuer)?;
+        
+        opt_out::ensure_not_opted_out(
+            &ctx.accounts.recipient_opt_out.to_account_info(),
+            &new_recipient_pubkey,
+            ctx.program_id,
+        )?;
+        
+        issuance::populate_credential(
+            issuance::IssuanceAccounts {
+                credential: &mut ctx.accounts.credential,
+                issuer: &ctx.accounts.issuer,
+                achievement: &ctx.accounts.achievement,
+                issuer_stats: &mut ctx.accounts.issuer_stats,
+                achievement_stats: &mut ctx.accounts.achievement_stats,
+                credential_index: &mut ctx.accounts.credential_index,
+                credential_index_page: &mut ctx.accounts.credential_index_page,
+                recipient_roster: &mut ctx.accounts.recipient_roster,
+                recipient_roster_page: &mut ctx.accounts.recipient_roster_page,
+                payer: ctx.accounts.recipient.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+            },
+            issuance::IssuanceBumps {
+                credential: ctx.bumps.credential,
+                issuer_stats: ctx.bumps.issuer_stats,
+                achievement_stats: ctx.bumps.achievement_stats,
+                credential_index: ctx.bumps.credential_index,
+                credential_index_page: ctx.bumps.credential_index_page,
+                recipient_roster: ctx.bumps.recipient_roster,
+                recipient_roster_page: ctx.bumps.recipient_roster_page,
+            },
+            new_recipient_pubkey,
+            issuance::CredentialContent::unsigned(&ctx.accounts.issuer, ctx.accounts.claim_offer.results.clone())?,
+            None,
+        )?;
+        issuance::emit_issued(&ctx.accounts.credential, new_recipient_pubkey);
+        
+        msg!("🎟️ CREDENTIAL_CLAIMED: {} by {}", ctx.accounts.achievement.name, new_recipient_pubkey);
+        Ok(())
+    }
+
+    /// Withdraw an unclaimed offer and reclaim its rent
+    pub fn cancel_claim_offer(_ctx: Context<CancelClaimOffer>) -> Result<()> {
+        msg!("🎟️ CLAIM_OFFER_CANCELLED");
+        Ok(())
+    }
+
+    /// Issue one credential to every member of a team
+    /// Each member's opt-out PDA follows in `remaining_accounts`, in member order
+    pub fn issue_team_credential(
+        ctx: Context<IssueTeamCredential>,
+        team_id: String,
+        members: Vec<Pubkey>,
+    ) -> Result<()> {
+        program_config::ensure_not_paused(&ctx.accounts.program_config.to_account_info(), ctx.program_id)?;
+        team::validate(&team_id, &members)?;
+        rate_limit::record_issuance(&mut ctx.accounts.issuer, members.len() as u32, Clock::get()?.slot)?;
+        collect_issuance_fee(
+            &ctx.accounts.program_config,
+            &ctx.accounts.treasury,
+            [
+                ctx.accounts.fee_token_account.as_ref(),
+                ctx.accounts.treasury_token_account.as_ref(),
+                ctx.accounts.token_program.as_ref(),
+            ],
+            &ctx.accounts.authority,
+            &ctx.accounts.system_program,
+            ctx.program_id,
+        )?;
+        
+        cpi_guard::ensure_invocation_allowed(
+            cpi_guard::GuardedAction::Issuance,
+            ctx.accounts.cpi_policy.as_deref(),
+            ctx.accounts.instructions.as_deref(),
+        )?;
+        assessment::ensure_result_not_required(&ctx.accounts.achievement)?;
+        
+        require!(ctx.remaining_accounts.len() == members.len(), ValidationError::InvalidOptOutAccount);
+        for (member, opt_out_info) in members.iter().zip(ctx.remaining_accounts.iter()) {
+            opt_out::ensure_not_opted_out(opt_out_info, member, ctx.program_id)?;
+        }
+        
+        let team_credential = &mut ctx.accounts.team_credential;
+        team_credential.issuer = ctx.accounts.issuer.key();
+        team_credential.achievement = ctx.accounts.achievement.key();
+        team_credential.team_id = team_id;
+        team_credential.members = members;
+        team_credential.valid_from = get_current_iso8601()?;
+        team_credential.is_revoked = false;
+        team_credential.bump = ctx.bumps.team_credential;
+        
+        emit!(events::TeamCredentialIssued {
+            credential: team_credential.key(),
+            issuer: team_credential.issuer,
+            achievement: team_credential.achievement,
+            team_id: team_credential.team_id.clone(),
+            members: team_credential.members.clone(),
+        });
+        
+        msg!(
+            "👥 TEAM_CREDENTIAL_ISSUED: {} to team {} ({} members)",
+            ctx.accounts.achievement.name,
+            team_credential.team_id,
+            team_credential.members.len()
+        );
+        Ok(())
+    }
+
+    /// Revoke a team credential for every member at once
+    pub fn revoke_team_credential(ctx: Context<RevokeTeamCredential>) -> Result<()> {
+        cpi_guard::ensure_invocation_allowed(
+            cpi_guard::GuardedAction::Revocation,
+            ctx.accounts.cpi_policy.as_deref(),
+            ctx.accounts.instructions.as_deref(),
+        )?;
+        
+        let team_credential = &mut ctx.accounts.team_credential;
+        require!(!team_credential.is_revoked, ValidationError::AlreadyRevoked);
+        team_credential.is_revoked = true;
+        msg!("👥 TEAM_CREDENTIAL_REVOKED: team {}", team_credential.team_id);
+        Ok(())
+    }
+
+    /// JSON-LD document of a team credential, with a credentialSubject array
+    /// View function: call with simulation, no state changes
+    pub fn generate_team_credential_json(ctx: Context<GenerateTeamCredentialJson>) -> Result<String> {
+        let document = team::to_json(
+            &ctx.accounts.team_credential.key(),
+            &ctx.accounts.team_credential,
+            ctx.accounts.issuer.credential_id_format,
+        );
+        serde_json::to_string(&document).map_err(|_| error!(ValidationError::SerializationError))
+    }
+
+    /// Accredit an issuer: the signing issuer profile endorses another for `scope`
+    /// Verifiers decide which accreditors they recognize
+    pub fn endorse_issuer(
+        ctx: Context<EndorseIssuer>,
+        scope: String,
+        valid_from: i64,
+        valid_until: Option<i64>,
+    ) -> Result<()> {
+        let accreditor_key = ctx.accounts.accreditor.key();
+        let issuer_key = ctx.accounts.issuer.key();
+        if accreditor_key == issuer_key {
+            return Err(error!(ValidationError::SelfEndorsement));
+        }
+        accreditation::validate(&scope, valid_from, valid_until)?;
+        
+        let endorsement = &mut ctx.accounts.accreditation;
+        endorsement.accreditor = accreditor_key;
+        endorsement.issuer = issuer_key;
+        endorsement.scope = scope;
+        endorsement.valid_from = valid_from;
+        endorsement.valid_until = valid_until;
+        endorsement.is_revoked = false;
+        endorsement.bump = ctx.bumps.accreditation;
+        
+        emit!(events::IssuerAccredited {
+            accreditation: endorsement.key(),
+            accreditor: accreditor_key,
+            issuer: issuer_key,
+            scope: endorsement.scope.clone(),
+            valid_until,
+        });
+        
+        msg!("🏛️ ISSUER_ACCREDITED: {} by {} for {}", ctx.accounts.issuer.name, ctx.accounts.accreditor.name, endorsement.scope);
+        Ok(())
+    }
+
+    /// Withdraw an accreditation; it stops counting for verification at once
+    pub fn revoke_accreditation(ctx: Context<RevokeAccreditation>) -> Result<()> {
+        let endorsement = &mut ctx.accounts.accreditation;
+        require!(!endorsement.is_revoked, ValidationError::AlreadyRevoked);
+        endorsement.is_revoked = true;
+        msg!("🏛️ ACCREDITATION_REVOKED: {} by {}", endorsement.issuer, endorsement.accreditor);
+        Ok(())
+    }
+
+    /// Grant a service key scoped authority to act for the issuer
+    /// An empty `achievements` list covers every achievement of the issuer
+    pub fn grant_scoped_authorization(
+        ctx: Context<GrantScopedAuthorization>,
+        service: Pubkey,
+        scopes: Vec<scoped_authorization::Scope>,
+        achievements: Vec<Pubkey>,
+        expires_at: Option<i64>,
+    ) -> Result<()> {
+        scoped_authorization::validate(&scopes, &achievements, expires_at, Clock::get()?.unix_timestamp)?;
+        
+        let authorization = &mut ctx.accounts.scoped_authorization;
+        authorization.issuer = ctx.accounts.issuer.key();
+        authorization.service = service;
+        authorization.scopes = scopes;
+        authorization.achievements = achievements;
+        authorization.expires_at = expires_at;
+        authorization.bump = ctx.bumps.scoped_authorization;
+        
+        emit!(events::ScopedAuthorizationGranted {
+            authorization: authorization.key(),
+            issuer: authorization.issuer,
+            service,
+            scopes: authorization.scopes.clone(),
+            expires_at,
+        });
+        
+        msg!("🔑 SCOPED_AUTHORIZATION_GRANTED: {} may {:?} for {}", service, authorization.scopes, ctx.accounts.issuer.name);
+        Ok(())
+    }
+
+    /// Withdraw a service's scoped authorization, returning its rent to the authority
+    pub fn revoke_scoped_authorization(ctx: Context<RevokeScopedAuthorization>) -> Result<()> {
+        msg!("🔑 SCOPED_AUTHORIZATION_REVOKED: {}", ctx.accounts.scoped_authorization.service);
+        Ok(())
+    }
+
+    /// Issue a credential as a service holding the issuer's `Issue` scope for the achievement
+    /// The credential carries no proof: the issuer's grant is the authorization
+    pub fn issue_credential_as_delegate(
+        ctx: Context<IssueCredentialAsDelegate>,
+        recipient_pubkey: Pubkey,
+        results: Vec<results::ResultInput>,
+    ) -> Result<()> {
+        program_config::ensure_not_paused(&ctx.accounts.program_config.to_account_info(), ctx.program_id)?;
+        let clock = Clock::get()?;
+        ctx.accounts.scoped_authorization.ensure_permits(
+            scoped_authorization::Scope::Issue,
+            &ctx.accounts.achievement.key(),
+            clock.unix_timestamp,
+        )?;
+        rate_limit::record_issuance(&mut ctx.accounts.issuer, 1, clock.slot)?;
+        collect_issuance_fee(
+            &ctx.accounts.program_config,
+            &ctx.accounts.treasury,
+            [
+                ctx.accounts.fee_token_account.as_ref(),
+                ctx.accounts.treasury_token_account.as_ref(),
+                ctx.accounts.token_program.as_ref(),
+            ],
+            &ctx.accounts.service,
+            &ctx.accounts.system_program,
+            ctx.program_id,
+        )?;
+        
+        opt_out::ensure_not_opted_out(
+            &ctx.accounts.recipient_opt_out.to_account_info(),
+            &recipient_pubkey,
+            ctx.program_id,
+        )?;
+        
+        assessment::ensure_result_approved(
+            ctx.accounts.result_entry.as_deref(),
+            &ctx.accounts.achievement,
+            &recipient_pubkey,
+        )?;
+        
+        results::validate_results(
+            &results,
+            &ctx.accounts.achievement.result_descriptions,
+            ctx.accounts.rubric.as_deref(),
+        )?;
+        
+        criteria::ensure_thresholds_met(&ctx.accounts.achievement.requirements, &results)?;
+        
+        issuance::populate_credential(
+            issuance::IssuanceAccounts {
+                credential: &mut ctx.accounts.credential,
+                issuer: &ctx.accounts.issuer,
+                achievement: &ctx.accounts.achievement,
+                issuer_stats: &mut ctx.accounts.issuer_stats,
+                achievement_stats: &mut ctx.accounts.achievement_stats,
+                credential_index: &mut ctx.accounts.credential_index,
+                credential_index_page: &mut ctx.accounts.credential_index_page,
+                recipient_roster: &mut ctx.accounts.recipient_roster,
+                recipient_roster_page: &mut ctx.accounts.recipient_roster_page,
+                payer: ctx.accounts.service.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+            },
+            issuance::IssuanceBumps {
+                credential: ctx.bumps.credential,
+                issuer_stats: ctx.bumps.issuer_stats,
+                achievement_stats: ctx.bumps.achievement_stats,
+                credential_index: ctx.bumps.credential_index,
+                credential_index_page: ctx.bumps.credential_index_page,
+                recipient_roster: ctx.bumps.recipient_roster,
+                recipient_roster_page: ctx.bumps.recipient_roster_page,
+            },
+            recipient_pubkey,
+            issuance::CredentialContent::unsigned(&ctx.accounts.issuer, results)?,
+            None,
+        )?;
+        issuance::emit_issued(&ctx.accounts.credential, recipient_pubkey);
+        
+        msg!("🏅 CREDENTIAL_ISSUED by delegate {}: {}", ctx.accounts.service.key(), ctx.accounts.achievement.name);
+        Ok(())
+    }
+
+    /// Revoke a credential as a service holding the issuer's `Revoke` scope for its achievement
+    pub fn revoke_credential_as_delegate(
+        ctx: Context<RevokeCredentialAsDelegate>,
+        reason: Option<String>,
+    ) -> Result<()> {
+        program_config::ensure_not_paused(&ctx.accounts.program_config.to_account_info(), ctx.program_id)?;
+        let credential = &mut ctx.accounts.credential;
+        ctx.accounts.scoped_authorization.ensure_permits(
+            scoped_authorization::Scope::Revoke,
+            &credential.credential_subject.achievement,
+            Clock::get()?.unix_timestamp,
+        )?;
+        
+        if credential.is_revoked {
+            return Err(error!(ValidationError::AlreadyRevoked));
+        }
+        if reason.as_ref().is_some_and(|r| r.len() > MAX_REVOCATION_REASON_LEN) {
+            return Err(error!(ValidationError::RevocationReasonTooLong));
+        }
+        
+        let current_timestamp = get_current_iso8601()?;
+        let service = ctx.accounts.service.key();
+        lifecycle::transition(
+            credential,
+            lifecycle::CredentialState::Revoked,
+            current_timestamp.clone(),
+            service,
+            reason.clone(),
+        )?;
+        credential.is_revoked = true;
+        credential.revoked_at = Some(current_timestamp.clone());
+        credential.revocation_reason = reason.clone();
+        credential.revoked_by = service;
+        ctx.accounts.achievement_stats.record_revoked(
+            credential.credential_subject.achievement,
+            ctx.bumps.achievement_stats,
+            credential.is_expired,
+        );
+        let sequence = ctx.accounts.issuer_stats.next_sequence(ctx.accounts.issuer.key(), ctx.bumps.issuer_stats);
+        
+        emit!(events::CredentialRevoked {
+            credential: credential.key(),
+            issuer: credential.issuer,
+            revoked_by: service,
+            reason,
+            revoked_at: current_timestamp,
+            sequence,
+        });
+        
+        msg!("✅ Credential revoked by delegate {}: {}", service, credential.id);
+        Ok(())
+    }
+
+    /// Nominate a recipient for an achievement
+    /// Any wallet can nominate; the issuer approves by issuing with the nomination attached
+    pub fn nominate_recipient(
+        ctx: Context<NominateRecipient>,
+        nominee: Pubkey,
+        evidence_url: String,
+        narrative: Option<String>,
+    ) -> Result<()> {
+        nomination::validate_nomination(&evidence_url, &narrative)?;
+        
+        let nomination = &mut ctx.accounts.nomination;
+        nomination.achievement = ctx.accounts.achievement.key();
+        nomination.issuer = ctx.accounts.achievement.issuer;
+        nomination.nominee = nominee;
+        nomination.nominator = ctx.accounts.nominator.key();
+        nomination.evidence_url = evidence_url;
+        nomination.narrative = narrative;
+        nomination.status = nomination::NominationStatus::Pending;
+        nomination.credential = None;
+        nomination.nominated_at = get_current_iso8601()?;
+        nomination.reviewed_at = None;
+        nomination.bump = ctx.bumps.nomination;
+        
+        msg!("📨 NOMINATION_CREATED: {} for {}", nominee, ctx.accounts.achievement.name);
+        Ok(())
+    }
+
+    /// Decline a pending nomination
+    pub fn decline_nomination(ctx: Context<DeclineNomination>) -> Result<()> {
+        let nomination = &mut ctx.accounts.nomination;
+        nomination.ensure_pending()?;
+        nomination.status = nomination::NominationStatus::Declined;
+        nomination.reviewed_at = Some(get_current_iso8601()?);
+        
+        msg!("📨 NOMINATION_DECLINED: {}", nomination.nominee);
+        Ok(())
+    }
+
+    /// Close a nomination and return its rent to the nominator
+    pub fn close_nomination(ctx: Context<CloseNomination>) -> Result<()> {
+        msg!("📨 NOMINATION_CLOSED: {:?}", ctx.accounts.nomination.status);
+        Ok(())
+    }
+
+    /// Register an additional verification method for an issuer
+    /// The key is referenced in proofs as `did:sol:<issuer>#key-N`
+    pub fn add_issuer_key(
+        ctx: Context<AddIssuerKey>,
+        key_index: u32,
+        public_key: Pubkey,
+    ) -> Result<()> {
+        let issuer_key = &mut ctx.accounts.issuer_key;
+        issuer_key.issuer = ctx.accounts.issuer.key();
+        issuer_key.key_index = key_index;
+        issuer_key.public_key = public_key;
+        issuer_key.created_at = get_current_iso8601()?;
+        issuer_key.retired_at = None;
+        issuer_key.bump = ctx.bumps.issuer_key;
+        
+        msg!("🔑 ISSUER_KEY_ADDED: {}", issuer_key.verification_method_id());
+        Ok(())
+    }
+
+    /// Retire an issuer key so it can't sign new proofs
+    /// Proofs created before retirement remain verifiable
+    pub fn retire_issuer_key(ctx: Context<RetireIssuerKey>) -> Result<()> {
+        let issuer_key = &mut ctx.accounts.issuer_key;
+        if issuer_key.retired_at.is_some() {
+            return Err(error!(ValidationError::ValidationFailed));
+        }
+        issuer_key.retired_at = Some(get_current_iso8601()?);
+        
+        msg!("🔑 ISSUER_KEY_RETIRED: {}", issuer_key.verification_method_id());
+        Ok(())
+    }
+
+    /// Register a global refusal to receive badges for the signing wallet
+    pub fn register_opt_out(ctx: Context<RegisterOptOut>) -> Result<()> {
+        let opt_out = &mut ctx.accounts.opt_out;
+        opt_out.recipient = ctx.accounts.recipient.key();
+        opt_out.opted_out_at = get_current_iso8601()?;
+        opt_out.bump = ctx.bumps.opt_out;
+
+        msg!("🚫 RECIPIENT_OPTED_OUT: {}", opt_out.recipient);
+        Ok(())
+    }
+
+    /// Withdraw a previously registered opt-out, allowing issuance again
+    pub fn remove_opt_out(ctx: Context<RemoveOptOut>) -> Result<()> {
+        msg!("✅ RECIPIENT_OPT_OUT_REMOVED: {}", ctx.accounts.recipient.key());
+        Ok(())
+    }
+
+    /// Anchor the SHA-256 of a credential issued outside this program
+    /// The credential stays off-chain; only its hash, issuer DID and optional
+    /// revocation list position are recorded
+    pub fn anchor_external_credential(
+        ctx: Context<AnchorExternalCredential>,
+        credential_hash: [u8; 32],
+        issuer_did: String,
+        status: Option<anchoring::StatusPointer>,
+    ) -> Result<()> {
+        anchoring::validate_issuer_did(&issuer_did)?;
+        if let Some(status) = &status {
+            let revocation_list = ctx.accounts.revocation_list.as_ref();
+            anchoring::check_status_list(
+                status,
+                revocation_list.map(|list| list.key()),
+                revocation_list.map(|list| &**list),
+            )?;
+        }
+
+        let anchor_record = &mut ctx.accounts.anchor_record;
+        anchor_record.issuer = ctx.accounts.issuer.key();
+        anchor_record.credential_hash = credential_hash;
+        anchor_record.issuer_did = issuer_did;
+        anchor_record.status = status;
+        anchor_record.anchored_at = get_current_iso8601()?;
+        anchor_record.bump = ctx.bumps.anchor_record;
+
+        let sequence = ctx.accounts.issuer_stats.next_sequence(ctx.accounts.issuer.key(), ctx.bumps.issuer_stats);
+        emit!(events::ExternalCredentialAnchored {
+            anchor_record: anchor_record.key(),
+            issuer: anchor_record.issuer,
+            credential_hash,
+            sequence,
+        });
+
+        msg!("⚓ EXTERNAL_CREDENTIAL_ANCHORED: {}", anchor_record.key());
+        Ok(())
+    }
+
+    /// Check a credential JSON against its anchor and revocation status
+    /// Returns false if the JSON differs from what was anchored or the
+    /// credential's status bit is set
+    pub fn verify_anchored_credential(
+        ctx: Context<VerifyAnchoredCredential>,
+        credential_json: String,
+    ) -> Result<bool> {
+        let anchor_record = &ctx.accounts.anchor_record;
+        if !anchor_record.matches(&credential_json) {
+            return Ok(false);
+        }
+
+        if let Some(status) = &anchor_record.status {
+            let revocation_list = ctx.accounts.revocation_list.as_ref();
+            let revocation_list = anchoring::check_status_list(
+                status,
+                revocation_list.map(|list| list.key()),
+                revocation_list.map(|list| &**list),
+            )?;
+            if revocation_list.is_revoked(status.index)? {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Anchor a merkle root over the hashes of many external credentials
+    pub fn anchor_credential_batch(
+        ctx: Context<AnchorCredentialBatch>,
+        merkle_root: [u8; 32],
+        leaf_count: u32,
+        issuer_did: String,
+    ) -> Result<()> {
+        anchoring::validate_issuer_did(&issuer_did)?;
+        if leaf_count == 0 {
+            return Err(error!(ValidationError::EmptyBatch));
+        }
+        if u64::from(leaf_count) > 1u64 << anchoring::MAX_MERKLE_PROOF_LEN {
+            return Err(error!(ValidationError::BatchSizeTooLarge));
+        }
+
+        let batch_anchor = &mut ctx.accounts.batch_anchor;
+        batch_anchor.issuer = ctx.accounts.issuer.key();
+        batch_anchor.merkle_root = merkle_root;
+        batch_anchor.leaf_count = leaf_count;
+        batch_anchor.issuer_did = issuer_did;
+        batch_anchor.anchored_at = get_current_iso8601()?;
+        batch_anchor.bump = ctx.bumps.batch_anchor;
+
+        let sequence = ctx.accounts.issuer_stats.next_sequence(ctx.accounts.issuer.key(), ctx.bumps.issuer_stats);
+        emit!(events::CredentialBatchAnchored {
+            batch_anchor: batch_anchor.key(),
+            issuer: batch_anchor.issuer,
+            merkle_root,
+            leaf_count,
+            sequence,
+        });
+
+        msg!("⚓ CREDENTIAL_BATCH_ANCHORED: {} ({} credentials)", batch_anchor.key(), leaf_count);
+        Ok(())
+    }
+
+    /// Check a credential JSON against a batch anchor with its merkle proof
+    pub fn verify_batch_anchored_credential(
+        ctx: Context<VerifyBatchAnchoredCredential>,
+        credential_json: String,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<bool> {
+        Ok(ctx.accounts.batch_anchor.contains(&credential_json, &proof))
+    }
+
+    // ===================================================================
+    // MAIN FUNCTIONS
+    // ===================================================================
+}
+// Account structures aligned with Open Badges v3.0 specification
+
+/// Profile - represents the entity that issues credentials (Issuer)
+/// Aligned with Profile class in OB v3.0 spec
+#[account]
+pub struct Profile {
+    /// Unique URI for the Profile [1] - REQUIRED (DID format)
+    pub id: String,
+    /// Type array [1..*] - Must include "Profile"
+    pub r#type: Vec<String>,
+    /// Authority that can manage this issuer profile
+    pub authority: Pubkey,
+    /// Name of the issuer [0..1] - RECOMMENDED
+    pub name: String,
+    /// Homepage URL of the issuer [0..1] - RECOMMENDED  
+    pub url: Option<String>,
+    /// Contact email of the issuer [0..1] - RECOMMENDED
+    pub email: Option<String>,
+    /// Image URI of the issuer [0..1] - data: URIs allowed
+    pub image: Option<String>,
+    /// Brand metadata for verification pages
+    pub branding: Option<branding::IssuerBranding>,
+    /// How strictly this issuer's credentials are validated
+    pub validation_mode: strictness::ValidationMode,
+    /// Refresh service URL included in generated credentials [0..1]
+    pub refresh_service_url: Option<String>,
+    /// Default `termsOfUse` of the issuer's credentials [0..*]
+    pub terms_of_use: Vec<terms_of_use::TermsOfUse>,
+    /// Identifiers of the issuer in other systems, published as `otherIdentifier` [0..*]
+    pub other_identifier: Vec<other_identifier::IdentifierEntry>,
+    /// Translations of `name`, published as the `nameMap` language map [0..*]
+    pub name_i18n: Vec<i18n::LocalizedText>,
+    /// How the issuer's credentials are identified
+    pub credential_id_format: credential_id::CredentialIdFormat,
+    /// Issuance cap per window of slots and the current window's count
+    pub issuance_limit: Option<rate_limit::IssuanceLimit>,
+    /// Set by `freeze_issuer`; a frozen issuer cannot issue
+    pub frozen: bool,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl Profile {
+    /// Serialized size of the fields `update_issuer_profile` sets
+    pub fn editable_space_of(
+        name: &str,
+        url: &Option<String>,
+        email: &Option<String>,
+        image: &Option<String>,
+    ) -> usize {
+        let optional = |value: &Option<String>| 1 + value.as_ref().map_or(0, |v| 4 + v.len());
+        4 + name.len() + optional(url) + optional(email) + optional(image)
+    }
+
+    /// Serialized size of this profile's editable fields
+    pub fn editable_space(&self) -> usize {
+        Self::editable_space_of(&self.name, &self.url, &self.email, &self.image)
+    }
+
+    /// Account space of a new profile with the given editable fields
+    /// The did:sol id and type are reserved at their generated size, as is a
+    /// rate limit; branding, the refresh service, terms of use, other
+    /// identifiers and translations start empty and are resized in when set
+    pub fn space(
+        name: &str,
+        url: &Option<String>,
+        email: &Option<String>,
+        image: &Option<String>,
+    ) -> usize {
+        8 + PROFILE_ID_SPACE + 4 + 4 + "Profile".len() + 32 + Self::editable_space_of(name, url, email, image) + 1 + 1 + 1 + terms_of_use::EMPTY_SPACE + other_identifier::EMPTY_SPACE + i18n::EMPTY_SPACE + 1 + rate_limit::IssuanceLimit::SPACE + 1 + 1
+    }
+}
+
+/// Space reserved for a Profile's did:sol id
+const PROFILE_ID_SPACE: usize = 4 + 64;
+
+/// Achievement - defines the accomplishment itself
+/// Aligned with Achievement class in OB v3.0 spec
+#[account]
+pub struct Achievement {
+    /// @context [1..*] - JSON-LD context URIs - REQUIRED
+    pub context: Vec<String>,
+    /// Unique URI for the Achievement [1] - REQUIRED
+    pub id: String,
+    /// Type array [1..*] - Must include "Achievement"
+    pub r#type: Vec<String>,
+    /// The issuer that created this achievement
+    pub issuer: Pubkey,
+    /// Name of the achievement [1] - REQUIRED
+    pub name: String,
+    /// Description of the achievement [1] - REQUIRED
+    pub description: String,
+    /// Criteria for earning the achievement
+    pub criteria: Criteria,
+    /// Aligned competencies and minimum results, enforced at issuance
+    pub requirements: criteria::CriteriaRequirements,
+    /// Whether issuance needs an approved assessment `ResultEntry` for the recipient
+    pub requires_approved_result: bool,
+    /// Creator of the achievement [0..1] - RECOMMENDED (Profile PDA)
+    pub creator: Option<Pubkey>,
+    /// Image URI of the badge [0..1] - RECOMMENDED, data: URIs allowed
+    pub image: Option<String>,
+    /// Kind of achievement from the OB 3.0 vocabulary, or an `ext:` term [0..1]
+    pub achievement_type: Option<String>,
+    /// Results the achievement can award [0..*]
+    pub result_descriptions: Vec<results::ResultDescription>,
+    /// Issuer-defined extension properties [0..*]
+    pub extensions: Vec<extensions::Extension>,
+    /// Translations of `name`, published as the `nameMap` language map [0..*]
+    pub name_i18n: Vec<i18n::LocalizedText>,
+    /// Translations of `description`, published as the `descriptionMap` language map [0..*]
+    pub description_i18n: Vec<i18n::LocalizedText>,
+    /// Timestamp when achievement was created (ISO 8601 string)
+    pub created_at: String,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl Achievement {
+    /// Account space of a new achievement
+    /// The description and criteria are reserved at their longest and the
+    /// creation time at its generated size; every other field is sized from
+    /// the creation arguments
+    pub fn space(args: &args::CreateAchievementArgs) -> usize {
+        let optional = |value: &Option<String>| 1 + value.as_ref().map_or(0, |v| 4 + v.len());
+        let context = 4 + canonical::CREDENTIAL_CONTEXT.iter().map(|iri| 4 + iri.len()).sum::<usize>();
+        let criteria = 1 + 4 + validation::MAX_CRITERIA_ID_LEN + 1 + 4 + validation::MAX_CRITERIA_NARRATIVE_LEN;
+        8 + context
+            + 4 + args.achievement_id.len()
+            + 4 + 4 + "Achievement".len()
+            + 32
+            + 4 + args.name.len()
+            + 4 + validation::MAX_ACHIEVEMENT_DESCRIPTION_LEN
+            + criteria
+            + args.requirements.space()
+            + 1
+            + 1 + 32
+            + optional(&args.image)
+            + optional(&args.achievement_type)
+            + 4 + args.result_descriptions.iter().map(results::ResultDescription::space).sum::<usize>()
+            + extensions::space(&args.extensions, &[])
+            + i18n::space(&args.name_i18n)
+            + i18n::space(&args.description_i18n)
+            + TIMESTAMP_SPACE
+            + 1
+    }
+}
+
+/// Criteria - describes how the achievement is earned
+/// Part of Achievement class in OB v3.0 spec
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct Criteria {
+    /// URI of a webpage describing criteria [0..1] - RECOMMENDED
+    pub id: Option<String>,
+    /// Narrative description of criteria [0..1] - RECOMMENDED
+    pub narrative: Option<String>,
+}
+
+/// AchievementSubject - represents the recipient of the credential
+/// Aligned with AchievementSubject class in OB v3.0 spec
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct AchievementSubject {
+    /// An identifier for the Credential Subject [0..1]
+    pub id: Option<String>,
+    /// Type array [1..*] - Must include "AchievementSubject"
+    /// Note: Using subject_type temporarily to avoid r#type deserialization issues in nested structs
+    pub subject_type: Vec<String>,
+    /// The achievement being awarded [1] - REQUIRED
+    pub achievement: Pubkey,
+    /// Other identifiers for the recipient [0..*]
+    pub identifier: Vec<IdentityObject>,
+    /// Results obtained by the recipient [0..*]
+    pub result: Vec<results::ResultInput>,
+    /// Why this recipient earned the achievement [0..1]
+    pub narrative: Option<String>,
+}
+
+impl AchievementSubject {
+    /// Validate the achievement subject for Open Badges 3.0 compliance
+    pub fn validate(&self) -> Result<()> {
+        // Validate required subject types
+        if !self.subject_type.contains(&"AchievementSubject".to_string()) {
+            return Err(error!(ValidationError::InvalidCredentialType));
+        }
+
+        // Validate identity objects
+        for identity in &self.identifier {
+            identity.validate()?;
+        }
+
+        Ok(())
+    }
+}
+
+/// IdentityObject - represents identity information
+/// Aligned with IdentityObject class in OB v3.0 spec
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct IdentityObject {
+    /// Type [1] - Must be "IdentityObject"
+    pub identity_type: String,
+    /// Whether identityHash is hashed [1] - REQUIRED
+    pub hashed: bool,
+    /// The identity value or its hash [1] - REQUIRED
+    pub identity_hash: String,
+    /// Type of identity (email, did, etc.) [1] - REQUIRED
+    pub identity_type_name: String,
+}
+
+impl IdentityObject {
+    /// Validate the identity object for Open Badges 3.0 compliance
+    pub fn validate(&self) -> Result<()> {
+        // Validate required identity type
+        if self.identity_type != "IdentityObject" {
+            return Err(error!(ValidationError::InvalidCredentialType));
+        }
+
+        // Validate that we have a hash value
+        if self.identity_hash.is_empty() {
+            return Err(error!(ValidationError::MissingRequiredField));
+        }
+
+        // Validate that we have an identity type name
+        if self.identity_type_name.is_empty() {
+            return Err(error!(ValidationError::MissingRequiredField));
+        }
+
+        Ok(())
+    }
+}
+
+/// Proof - cryptographic proof for verification
+/// Aligned with Proof class in VC Data Model v2.0 and Open Badges 3.0
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct Proof {
+    /// Signature suite used [1] - REQUIRED
+    pub proof_type: String,
+    /// Cryptographic suite identifier [1] - REQUIRED for eddsa-rdfc-2022
+    pub cryptosuite: String,
+    /// Timestamp when proof was created [1] - REQUIRED (ISO 8601 format)
+    pub created: String,
+    /// Purpose of the proof [1] - Must be "assertionMethod"
+    pub proof_purpose: String,
+    /// URI of public key for verification [1] - REQUIRED
+    pub verification_method: String,
+    /// The signature value [1] - REQUIRED
+    pub proof_value: String,
+    /// Id of the proof this one counter-signs in a proof chain [0..1]
+    pub previous_proof: Option<String>,
+}
+
+/// AchievementCredential - the core on-chain asset (Verifiable Credential)
+/// Aligned with AchievementCredential class in OB v3.0 spec
+#[account]
+pub struct AchievementCredential {
+    /// Issuer sequence number at issuance, kept first for memcmp sync filters
+    pub sequence: u64,
+    /// Unambiguous reference to the credential [1] - REQUIRED
+    pub id: String,
+    /// @context [2..*] - JSON-LD context URIs
+    pub context: Vec<String>,
+    /// type [1..*] - Must include VerifiableCredential and AchievementCredential
+    pub r#type: Vec<String>,
+    /// issuer [1] - ProfileRef (using Pubkey for on-chain reference)
+    pub issuer: Pubkey,
+    /// validFrom [1] - DateTimeZ (ISO 8601 string)
+    pub valid_from: String,
+    /// validUntil [0..1] - DateTimeZ (ISO 8601 string, optional)
+    pub valid_until: Option<String>,
+    /// Issuance timestamp (ISO 8601 string)
+    pub issued_at: String,
+    /// The recipient of the achievement [1] - REQUIRED
+    pub credential_subject: AchievementSubject,
+    /// Cryptographic proofs [0..*] - STRONGLY RECOMMENDED; the issuer's first, then co-signatures
+    pub proof: Vec<Proof>,
+    /// Whether the credential is revoked
+    pub is_revoked: bool,
+    /// Timestamp when credential was revoked (ISO 8601 string, optional)
+    pub revoked_at: Option<String>,
+    /// Reason given when the credential was revoked (optional)
+    pub revocation_reason: Option<String>,
+    /// Signer who revoked the credential (default key while active)
+    pub revoked_by: Pubkey,
+    /// Proofs replaced by `upgrade_credential_proof`, oldest first
+    pub deprecated_proofs: Vec<proof_upgrade::DeprecatedProof>,
+    /// Issuer-defined extension properties, set by `set_credential_extensions`
+    pub extensions: Vec<extensions::Extension>,
+    /// Hash of the baked badge image, set by `register_baked_badge`
+    pub baked_image: Option<baked_image::BakedImage>,
+    /// Ids of earlier versions this credential was reissued from
+    pub related: Vec<String>,
+    /// Issuer remarks kept on the account, not part of the presented credential
+    pub notes: Vec<String>,
+    /// Set by `expire_credential` once `valid_until` has passed
+    pub is_expired: bool,
+    /// Registered schemas the credential declares as `credentialSchema`
+    pub credential_schema: Vec<schema::CredentialSchema>,
+    /// Recipient key the credential is bound to, published as its `holder`
+    pub holder_binding: Option<Pubkey>,
+    /// Lifecycle state, kept in step with `is_revoked` and `is_expired`
+    pub status: lifecycle::CredentialState,
+    /// Latest state transitions, oldest first
+    pub status_history: Vec<lifecycle::StatusHistoryEntry>,
+    /// Moves of the credential to a new subject DID, oldest first
+    pub rebind_history: Vec<rebind::RebindEntry>,
+    /// Policies governing the credential, published as `termsOfUse`
+    pub terms_of_use: Vec<terms_of_use::TermsOfUse>,
+    /// Replacement credential, set by `supersede_credential`
+    pub superseded_by: Option<Pubkey>,
+    /// Confidential body encrypted to the recipient, set by `attach_encrypted_payload`
+    pub encrypted_payload: Option<encrypted_payload::EncryptedPayload>,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl AchievementCredential {
+    /// Validate the credential for Open Badges 3.0 compliance
+    /// Applies the shared rule-set of `validation` that issuance also enforces
+    pub fn validate(&self) -> Result<()> {
+        validation::validate_jsonld_context(&self.context)?;
+        validation::validate_credential_type(&self.r#type)?;
+        self.credential_subject.validate()
+    }
+
+    /// Account space of a newly issued credential, sized from the issuance arguments
+    /// Identifiers, type terms and the first proof are generated by the program
+    /// and reserved at their longest; the revocation fields are reserved up
+    /// front because revocation does not resize the account
+    pub fn issuance_space(
+        timestamp_len: usize,
+        results: &[results::ResultInput],
+        narrative: Option<&str>,
+        notes: &[String],
+        credential_schemas: &[String],
+    ) -> usize {
+        let timestamp = 4 + timestamp_len;
+        let subject = 1 + GENERATED_URI_SPACE
+            + 4 + TERM_SPACE
+            + 32
+            + 4 + TERM_SPACE + 1 + GENERATED_URI_SPACE + TERM_SPACE
+            + results::space(results);
+        let proof = TERM_SPACE + TERM_SPACE + TIMESTAMP_SPACE + TERM_SPACE + GENERATED_URI_SPACE + PROOF_VALUE_SPACE + 1;
+        let revocation = 1 + TIMESTAMP_SPACE + 1 + 4 + MAX_REVOCATION_REASON_LEN + 32;
+        8 + 8
+            + GENERATED_URI_SPACE
+            + 4 + 2 * GENERATED_URI_SPACE
+            + 4 + 2 * TERM_SPACE
+            + 32
+            + timestamp + 1 + timestamp
+            + subject
+            + 4 + proof
+            + 1 + revocation
+            + 4 + 4 + 1 + 4
+            + narrative::space(narrative, notes)
+            + 1
+            + schema::space(credential_schemas)
+            + holder_binding::SPACE
+            + lifecycle::SPACE
+            + rebind::EMPTY_SPACE
+            + terms_of_use::EMPTY_SPACE
+            + supersession::SPACE
+            + encrypted_payload::EMPTY_SPACE
+            + 1
+    }
+}
+
+/// Space reserved for a DID, URN or context URI the program generates
+const GENERATED_URI_SPACE: usize = 4 + 96;
+
+/// Space reserved for a type or purpose term
+const TERM_SPACE: usize = 4 + 32;
+
+/// Space reserved for a timestamp the program generates
+const TIMESTAMP_SPACE: usize = 4 + refresh::MAX_TIMESTAMP_LEN;
+
+/// Space of a multibase Ed25519 signature ("z" + base58 of 64 bytes)
+const PROOF_VALUE_SPACE: usize = 4 + 1 + 88;
+
+// Context structures
+
+#[derive(Accounts)]
+#[instruction(name: String, url: Option<String>, email: Option<String>, image: Option<String>)]
+pub struct InitializeIssuer<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = Profile::space(&name, &url, &email, &image),
+        seeds = [pda::ISSUER_SEED, authority.key().as_ref()],
+        bump
+    )]
+    pub issuer: Account<'info, Profile>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+
+
+#[derive(Accounts)]
+#[instruction(args: args::CreateAchievementArgs)]
+pub struct CreateAchievement<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = Achievement::space(&args),
+        seeds = [pda::ACHIEVEMENT_SEED, issuer.key().as_ref(), args.name.as_bytes()],
+        bump
+    )]
+    pub achievement: Account<'info, Achievement>,
+    
+    #[account(
+        seeds = [pda::ISSUER_SEED, authority.key().as_ref()],
+        bump = issuer.bump
+    )]
+    pub issuer: Account<'info, Profile>,
+    
+    /// Profile of the achievement's creator, when different from the issuer
+    pub creator: Option<Account<'info, Profile>>,
+    
+    /// Issuer's achievement directory, created with its first achievement
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = achievement_directory::AchievementDirectory::SPACE,
+        seeds = [achievement_directory::ACHIEVEMENT_DIRECTORY_SEED, issuer.key().as_ref()],
+        bump
+    )]
+    pub achievement_directory: Account<'info, achievement_directory::AchievementDirectory>,
+    
+    /// Directory page the achievement is appended to, created when the previous page is full
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = achievement_directory::AchievementDirectoryPage::SPACE,
+        seeds = [
+            achievement_directory::ACHIEVEMENT_DIRECTORY_SEED,
+            issuer.key().as_ref(),
+            &achievement_directory.next_page().to_le_bytes()
+        ],
+        bump
+    )]
+    pub achievement_directory_page: Account<'info, achievement_directory::AchievementDirectoryPage>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(args: args::IssueCredentialArgs)]
+pub struct IssueAchievementCredential<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = AchievementCredential::issuance_space(args.timestamp.len(), &args.results, args.narrative.as_deref(), &args.notes, &args.credential_schemas),
+        seeds = [
+            pda::CREDENTIAL_SEED,
+            achievement.key().as_ref(), 
+            issuer.key().as_ref(),
+            args.recipient_pubkey.as_ref()
+        ],
+        bump
+    )]
+    pub credential: Account<'info, AchievementCredential>,
+    
+    pub achievement: Account<'info, Achievement>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ISSUER_SEED, authority.key().as_ref()],
+        bump = issuer.bump,
+        constraint = issuer.key() == achievement.issuer @ ValidationError::UnauthorizedIssuer
+    )]
+    pub issuer: Account<'info, Profile>,
+    
+    /// Program config PDA; issuance is refused while it is paused
+    /// CHECK: Address is constrained by seeds, contents checked in the handler
+    #[account(seeds = [program_config::PROGRAM_CONFIG_SEED], bump)]
+    pub program_config: UncheckedAccount<'info>,
+    
+    /// Program treasury receiving issuance fees
+    /// CHECK: Address is constrained by seeds; only credited
+    #[account(mut, seeds = [treasury::TREASURY_SEED], bump)]
+    pub treasury: UncheckedAccount<'info>,
+    
+    /// Authority's token account paying the token fee, when the config sets one
+    /// CHECK: Mint checked in the handler, ownership by the token program
+    #[account(mut)]
+    pub fee_token_account: Option<UncheckedAccount<'info>>,
+    
+    /// Treasury-owned token account receiving the token fee
+    /// CHECK: Mint and owner checked in the handler
+    #[account(mut)]
+    pub treasury_token_account: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Checked against the SPL Token program id
+    pub token_program: Option<UncheckedAccount<'info>>,
+    
+    /// Recipient's opt-out PDA; must be empty for issuance to proceed
+    /// CHECK: Address is constrained by seeds, contents checked in the handler
+    #[account(
+        seeds = [opt_out::OPT_OUT_SEED, args.recipient_pubkey.as_ref()],
+        bump
+    )]
+    pub recipient_opt_out: UncheckedAccount<'info>,
+    
+    /// Moderated assessment result backing this credential, if required
+    #[account(
+        seeds = [assessment::RESULT_SEED, achievement.key().as_ref(), args.recipient_pubkey.as_ref()],
+        bump = result_entry.bump
+    )]
+    pub result_entry: Option<Account<'info, assessment::ResultEntry>>,
+    
+    /// Grading rubric that achieved levels are checked against, if results use one
+    #[account(
+        seeds = [rubric::RUBRIC_SEED, achievement.key().as_ref()],
+        bump = rubric.bump
+    )]
+    pub rubric: Option<Account<'info, rubric::Rubric>>,
+    
+    /// Issuer's schema registry, required when attaching credential schemas
+    #[account(
+        seeds = [schema::SCHEMA_REGISTRY_SEED, issuer.key().as_ref()],
+        bump = schema_registry.bump
+    )]
+    pub schema_registry: Option<Account<'info, schema::SchemaRegistry>>,
+    
+    /// Nomination approved by this issuance, if the recipient was nominated
+    #[account(
+        mut,
+        seeds = [nomination::NOMINATION_SEED, achievement.key().as_ref(), args.recipient_pubkey.as_ref(), nomination.nominator.as_ref()],
+        bump = nomination.bump
+    )]
+    pub nomination: Option<Account<'info, nomination::Nomination>>,
+    
+    /// Issuer's CPI policy; consulted only when invoked through CPI
+    #[account(
+        seeds = [cpi_guard::CPI_POLICY_SEED, issuer.key().as_ref()],
+        bump = cpi_policy.bump
+    )]
+    pub cpi_policy: Option<Account<'info, cpi_guard::CpiPolicy>>,
+    
+    /// Instructions sysvar holding the authority's Ed25519 instruction and naming the top-level program of a CPI
+    /// CHECK: Address is constrained to the instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    
+    /// Achievement counters, created with the achievement's first credential
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = stats::AchievementStats::SPACE,
+        seeds = [stats::ACHIEVEMENT_STATS_SEED, achievement.key().as_ref()],
+        bump
+    )]
+    pub achievement_stats: Account<'info, stats::AchievementStats>,
+    
+    /// Recipient's credential index, created with their first credential
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = credential_index::CredentialIndex::SPACE,
+        seeds = [credential_index::CREDENTIAL_INDEX_SEED, args.recipient_pubkey.as_ref()],
+        bump
+    )]
+    pub credential_index: Account<'info, credential_index::CredentialIndex>,
+    
+    /// Index page the credential is appended to, created when the previous page is full
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = credential_index::CredentialIndexPage::SPACE,
+        seeds = [credential_index::CREDENTIAL_INDEX_SEED, args.recipient_pubkey.as_ref(), &credential_index.next_page().to_le_bytes()],
+        bump
+    )]
+    pub credential_index_page: Account<'info, credential_index::CredentialIndexPage>,
+    
+    /// Achievement's recipient roster, created with its first credential
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = recipient_roster::RecipientRoster::SPACE,
+        seeds = [recipient_roster::RECIPIENT_ROSTER_SEED, achievement.key().as_ref()],
+        bump
+    )]
+    pub recipient_roster: Account<'info, recipient_roster::RecipientRoster>,
+    
+    /// Roster page the holder is appended to, created when the previous page is full
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = recipient_roster::RecipientRosterPage::SPACE,
+        seeds = [recipient_roster::RECIPIENT_ROSTER_SEED, achievement.key().as_ref(), &recipient_roster.next_page().to_le_bytes()],
+        bump
+    )]
+    pub recipient_roster_page: Account<'info, recipient_roster::RecipientRosterPage>,
+    
+    /// Issuer event sequence, created on the issuer's first event
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = stats::IssuerStats::SPACE,
+        seeds = [stats::ISSUER_STATS_SEED, issuer.key().as_ref()],
+        bump
+    )]
+    pub issuer_stats: Account<'info, stats::IssuerStats>,
+    
+    /// Record of the signed payload this issuance consumes; an initialized record is a replay
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = nonce::ConsumedPayload::SPACE,
+        seeds = [nonce::CONSUMED_PAYLOAD_SEED, issuer.key().as_ref(), &nonce::payload_hash(&args.message_data)],
+        bump
+    )]
+    pub consumed_payload: Account<'info, nonce::ConsumedPayload>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(recipient_pubkey: Pubkey)]
+pub struct IssueAchievementCredentialSimpleSubject<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = AchievementCredential::issuance_space(refresh::MAX_TIMESTAMP_LEN, &[], None, &[], &[]),
+        seeds = [
+            pda::CREDENTIAL_SEED,
+            achievement.key().as_ref(), 
+            issuer.key().as_ref(),
+            recipient_pubkey.as_ref()
+        ],
+        bump
+    )]
+    pub credential: Account<'info, AchievementCredential>,
+    
+    pub achievement: Account<'info, Achievement>,
+    
+    #[account(
+        seeds = [pda::ISSUER_SEED, authority.key().as_ref()],
+        bump = issuer.bump,
+        constraint = issuer.key() == achievement.issuer @ ValidationError::UnauthorizedIssuer
+    )]
+    pub issuer: Account<'info, Profile>,
+    
+    /// Recipient's opt-out PDA; must be empty for issuance to proceed
+    /// CHECK: Address is constrained by seeds, contents checked in the handler
+    #[account(
+        seeds = [opt_out::OPT_OUT_SEED, recipient_pubkey.as_ref()],
+        bump
+    )]
+    pub recipient_opt_out: UncheckedAccount<'info>,
+    
+    /// Moderated assessment result backing this credential, if required
+    #[account(
+        seeds = [assessment::RESULT_SEED, achievement.key().as_ref(), recipient_pubkey.as_ref()],
+        bump = result_entry.bump
+    )]
+    pub result_entry: Option<Account<'info, assessment::ResultEntry>>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeCredential<'info> {
+    #[account(
+        mut,
+        constraint = !credential.is_revoked @ ValidationError::AlreadyRevoked,
+        constraint = issuer.key() == credential.issuer @ ValidationError::UnauthorizedIssuer
+    )]
+    pub credential: Account<'info, AchievementCredential>,
+    
+    #[account(
+        seeds = [pda::ISSUER_SEED, authority.key().as_ref()],
+        bump = issuer.bump
+    )]
+    pub issuer: Account<'info, Profile>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+/// Context for initializing a revocation list
+#[derive(Accounts)]
+#[instruction(list_id: String, capacity: u32, name: String, description: String, status_list_url: String)]
+pub struct InitializeRevocationList<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = credential_status::RevocationList::space_for(&list_id, capacity, &name, &description, &status_list_url),
+        seeds = [pda::REVOCATION_LIST_SEED, authority.key().as_ref(), list_id.as_bytes()],
+        bump
+    )]
+    pub revocation_list: Account<'info, credential_status::RevocationList>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for updating credential status (revoke/reactivate)
+#[derive(Accounts)]
+pub struct UpdateCredentialStatus<'info> {
+    #[account(
+        mut,
+        has_one = authority @ ValidationError::UnauthorizedAccess
+    )]
+    pub revocation_list: Account<'info, credential_status::RevocationList>,
+    
+    /// Program config PDA; status changes are refused while it is paused
+    /// CHECK: Address is constrained by seeds, contents checked in the handler
+    #[account(seeds = [program_config::PROGRAM_CONFIG_SEED], bump)]
+    pub program_config: UncheckedAccount<'info>,
+    
+    pub authority: Signer<'info>,
+}
+
+/// Context for choosing the issuer's validation mode
+#[derive(Accounts)]
+pub struct SetIssuerValidationMode<'info> {
+    #[account(
+        mut,
+        seeds = [pda::ISSUER_SEED, authority.key().as_ref()],
+        bump = issuer.bump
+    )]
+    pub issuer: Account<'info, Profile>,
+    
+    pub authority: Signer<'info>,
+}
+
+/// Context for choosing the issuer's credential id format
+#[derive(Accounts)]
+pub struct SetIssuerCredentialIdFormat<'info> {
+    #[account(
+        mut,
+        seeds = [pda::ISSUER_SEED, authority.key().as_ref()],
+        bump = issuer.bump
+    )]
+    pub issuer: Account<'info, Profile>,
+    
+    pub authority: Signer<'info>,
+}
+
+/// Context for changing the issuer's issuance rate limit
+#[derive(Accounts)]
+pub struct SetIssuanceRateLimit<'info> {
+    #[account(
+        mut,
+        seeds = [pda::ISSUER_SEED, authority.key().as_ref()],
+        bump = issuer.bump
+    )]
+    pub issuer: Account<'info, Profile>,
+    
+    pub authority: Signer<'info>,
+    
+    /// Program config PDA naming the admin
+    /// CHECK: Address is constrained by seeds, contents checked in the handler
+    #[account(seeds = [program_config::PROGRAM_CONFIG_SEED], bump)]
+    pub program_config: UncheckedAccount<'info>,
+    
+    /// Program admin, required to loosen or remove a limit
+    pub admin: Option<Signer<'info>>,
+}
+
+/// Context for freezing or unfreezing an issuer
+#[derive(Accounts)]
+pub struct FreezeIssuer<'info> {
+    #[account(mut)]
+    pub issuer: Account<'info, Profile>,
+    
+    /// Program config PDA naming the admin
+    /// CHECK: Address is constrained by seeds, contents checked in the handler
+    #[account(seeds = [program_config::PROGRAM_CONFIG_SEED], bump)]
+    pub program_config: UncheckedAccount<'info>,
+    
+    /// Issuer authority or program admin
+    pub signer: Signer<'info>,
+}
+
+/// Context for setting the issuer's refresh service; the profile is resized to fit
+#[derive(Accounts)]
+#[instruction(refresh_service_url: Option<String>)]
+pub struct SetIssuerRefreshService<'info> {
+    #[account(
+        mut,
+        seeds = [pda::ISSUER_SEED, authority.key().as_ref()],
+        bump = issuer.bump,
+        realloc = issuer.to_account_info().data_len() - refresh::space_of(&issuer.refresh_service_url) + refresh::space_of(&refresh_service_url),
+        realloc::payer = authority,
+        realloc::zero = false
+    )]
+    pub issuer: Account<'info, Profile>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for setting the issuer's default terms of use; the profile is resized to fit
+#[derive(Accounts)]
+#[instruction(terms: Vec<terms_of_use::TermsOfUse>)]
+pub struct SetIssuerTermsOfUse<'info> {
+    #[account(
+        mut,
+        seeds = [pda::ISSUER_SEED, authority.key().as_ref()],
+        bump = issuer.bump,
+        realloc = issuer.to_account_info().data_len() - terms_of_use::space(&issuer.terms_of_use) + terms_of_use::space(&terms),
+        realloc::payer = authority,
+        realloc::zero = false
+    )]
+    pub issuer: Account<'info, Profile>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for setting the issuer's other identifiers; the profile is resized to fit
+#[derive(Accounts)]
+#[instruction(identifiers: Vec<other_identifier::IdentifierEntry>)]
+pub struct SetIssuerOtherIdentifiers<'info> {
+    #[account(
+        mut,
+        seeds = [pda::ISSUER_SEED, authority.key().as_ref()],
+        bump = issuer.bump,
+        realloc = issuer.to_account_info().data_len() - other_identifier::space(&issuer.other_identifier) + other_identifier::space(&identifiers),
+        realloc::payer = authority,
+        realloc::zero = false
+    )]
+    pub issuer: Account<'info, Profile>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for setting the translations of the issuer's name; the profile is resized to fit
+#[derive(Accounts)]
+#[instruction(translations: Vec<i18n::LocalizedText>)]
+pub struct SetIssuerNameTranslations<'info> {
+    #[account(
+        mut,
+        seeds = [pda::ISSUER_SEED, authority.key().as_ref()],
+        bump = issuer.bump,
+        realloc = issuer.to_account_info().data_len() - i18n::space(&issuer.name_i18n) + i18n::space(&translations),
+        realloc::payer = authority,
+        realloc::zero = false
+    )]
+    pub issuer: Account<'info, Profile>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for updating the issuer's profile fields; the profile is resized to fit
+#[derive(Accounts)]
+#[instruction(name: String, url: Option<String>, email: Option<String>, image: Option<String>)]
+pub struct UpdateIssuerProfile<'info> {
+    #[account(
+        mut,
+        seeds = [pda::ISSUER_SEED, authority.key().as_ref()],
+        bump = issuer.bump,
+        realloc = issuer.to_account_info().data_len() - issuer.editable_space() + Profile::editable_space_of(&name, &url, &email, &image),
+        realloc::payer = authority,
+        realloc::zero = false
+    )]
+    pub issuer: Account<'info, Profile>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for updating issuer branding; the profile is resized to fit
+#[derive(Accounts)]
+#[instruction(branding: Option<branding::IssuerBranding>)]
+pub struct UpdateIssuerBranding<'info> {
+    #[account(
+        mut,
+        seeds = [pda::ISSUER_SEED, authority.key().as_ref()],
+        bump = issuer.bump,
+        realloc = issuer.to_account_info().data_len() - branding::space_of(&issuer.branding) + branding::space_of(&branding),
+        realloc::payer = authority,
+        realloc::zero = false
+    )]
+    pub issuer: Account<'info, Profile>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for growing a revocation list
+#[derive(Accounts)]
+#[instruction(new_capacity: u32)]
+pub struct ResizeRevocationList<'info> {
+    #[account(
+        mut,
+        has_one = authority @ ValidationError::UnauthorizedAccess,
+        realloc = revocation_list.space_with_capacity(new_capacity),
+        realloc::payer = authority,
+        realloc::zero = false
+    )]
+    pub revocation_list: Account<'info, credential_status::RevocationList>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for reading a revocation list
+#[derive(Accounts)]
+pub struct ReadRevocationList<'info> {
+    pub revocation_list: Account<'info, credential_status::RevocationList>,
+}
+
+/// Context for publishing a signed status list credential
+#[derive(Accounts)]
+pub struct PublishStatusListCredential<'info> {
+    #[account(
+        has_one = authority @ ValidationError::UnauthorizedAccess
+    )]
+    pub revocation_list: Account<'info, credential_status::RevocationList>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = credential_status::PublishedStatusList::SPACE,
+        seeds = [credential_status::STATUS_LIST_CREDENTIAL_SEED, revocation_list.key().as_ref()],
+        bump
+    )]
+    pub status_list_credential: Account<'info, credential_status::PublishedStatusList>,
+    
+    /// Instructions sysvar holding the authority's Ed25519 instruction over the status list credential
+    /// CHECK: Address is constrained to the instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct VerifyCredential<'info> {
+    pub credential: Account<'info, AchievementCredential>,
+    
+    /// Issuer key named by the first `#key-N` proof, if any
+    pub issuer_key: Option<Account<'info, issuer_keys::IssuerKey>>,
+    
+    /// Issuer profile, required with `issuer_did_account` and for proofs by the issuer authority
+    #[account(address = credential.issuer @ ValidationError::UnauthorizedAccess)]
+    pub issuer: Option<Account<'info, Profile>>,
+    
+    /// The issuer authority's sol_did DID account
+    /// CHECK: Address and contents checked by `credential::ensure_issuer_did_active`
+    pub issuer_did_account: Option<UncheckedAccount<'info>>,
+    
+    /// Instructions sysvar holding the Ed25519 instructions over the credential's proofs
+    /// CHECK: Address is constrained to the instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: Option<UncheckedAccount<'info>>,
+    
+    /// The issuer's record of the signing staff key, when a staff member signed a proof
+    #[account(
+        seeds = [staff_key::STAFF_KEY_SEED, credential.issuer.as_ref(), staff_key.staff.as_ref()],
+        bump = staff_key.bump
+    )]
+    pub staff_key: Option<Account<'info, staff_key::StaffKey>>,
+    
+    /// sol_did account of the signing staff key's DID, listing the issuer as controller
+    /// CHECK: Owner and address are checked against the sol_did program during verification
+    pub staff_did_data: Option<UncheckedAccount<'info>>,
+}
+
+/// Context for reading a recipient's credential index
+#[derive(Accounts)]
+#[instruction(page: u32)]
+pub struct GetCredentialsPage<'info> {
+    pub credential_index: Account<'info, credential_index::CredentialIndex>,
+    
+    #[account(
+        seeds = [credential_index::CREDENTIAL_INDEX_SEED, credential_index.recipient.as_ref(), &page.to_le_bytes()],
+        bump = credential_index_page.bump
+    )]
+    pub credential_index_page: Account<'info, credential_index::CredentialIndexPage>,
+}
+
+/// Context for reading an achievement's recipient roster
+#[derive(Accounts)]
+#[instruction(page: u32)]
+pub struct GetRecipientRosterPage<'info> {
+    pub recipient_roster: Account<'info, recipient_roster::RecipientRoster>,
+    
+    #[account(
+        seeds = [recipient_roster::RECIPIENT_ROSTER_SEED, recipient_roster.achievement.as_ref(), &page.to_le_bytes()],
+        bump = recipient_roster_page.bump
+    )]
+    pub recipient_roster_page: Account<'info, recipient_roster::RecipientRosterPage>,
+}
+
+/// Context for reading an issuer's achievement directory
+#[derive(Accounts)]
+#[instruction(cursor: u32)]
+pub struct GetAchievementsPage<'info> {
+    pub achievement_directory: Account<'info, achievement_directory::AchievementDirectory>,
+    
+    #[account(
+        seeds = [
+            achievement_directory::ACHIEVEMENT_DIRECTORY_SEED,
+            achievement_directory.issuer.as_ref(),
+            &cursor.to_le_bytes()
+        ],
+        bump = achievement_directory_page.bump
+    )]
+    pub achievement_directory_page: Account<'info, achievement_directory::AchievementDirectoryPage>,
+}
+
+/// Context for running the compliance validator; the document is self-contained
+#[derive(Accounts)]
+pub struct ValidateCredentialFull<'info> {
+    /// Placeholder so the context has an account; the view reads none
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for building signing JSON; the parameters are self-contained
+#[derive(Accounts)]
+pub struct BuildCredentialSigningJson<'info> {
+    /// Placeholder so the context has an account; the view reads none
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for batch verification; credentials are passed as remaining accounts
+#[derive(Accounts)]
+pub struct VerifyCredentialsBatch<'info> {
+    /// Instructions sysvar holding the Ed25519 instructions over the credentials' proofs
+    /// CHECK: Address is constrained to the instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
+
+/// Context for creating a presentation; credentials are passed as remaining accounts
+#[derive(Accounts)]
+pub struct CreatePresentation<'info> {
+    /// Instructions sysvar holding the holder's Ed25519 instruction over the presentation
+    /// CHECK: Address is constrained to the instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
+
+/// Context for generating a CLR; credentials are passed as remaining accounts
+#[derive(Accounts)]
+pub struct GenerateClr<'info> {
+    /// Issuer profile whose authority signs the CLR
+    pub issuer: Account<'info, Profile>,
+    
+    /// Instructions sysvar holding the authority's Ed25519 instruction over the CLR
+    /// CHECK: Address is constrained to the instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
+
+/// Context for verifying a presentation; credentials are passed as remaining accounts
+#[derive(Accounts)]
+pub struct VerifyPresentation<'info> {
+    /// Instructions sysvar holding the holder's Ed25519 instruction over the presentation
+    /// CHECK: Address is constrained to the instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
+
+/// Context for the public verification view
+#[derive(Accounts)]
+pub struct GetPublicVerificationView<'info> {
+    pub credential: Account<'info, AchievementCredential>,
+    
+    #[account(constraint = achievement.key() == credential.credential_subject.achievement @ ValidationError::ValidationFailed)]
+    pub achievement: Account<'info, Achievement>,
+    
+    #[account(constraint = issuer.key() == credential.issuer @ ValidationError::UnauthorizedIssuer)]
+    pub issuer: Account<'info, Profile>,
+    
+    /// Issuer key named by the proof's `#key-N` fragment, if any
+    pub issuer_key: Option<Account<'info, issuer_keys::IssuerKey>>,
+    
+    /// Instructions sysvar holding the Ed25519 instructions over the credential's proofs
+    /// CHECK: Address is constrained to the instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: Option<UncheckedAccount<'info>>,
+}
+
+/// Context for the credential summary view
+#[derive(Accounts)]
+pub struct GetCredentialSummary<'info> {
+    pub credential: Account<'info, AchievementCredential>,
+    
+    /// Issuer key named by the proof's `#key-N` fragment, if any
+    pub issuer_key: Option<Account<'info, issuer_keys::IssuerKey>>,
+    
+    /// Issuer profile, whose authority signs proofs without a `#key-N` fragment
+    #[account(address = credential.issuer @ ValidationError::UnauthorizedIssuer)]
+    pub issuer: Option<Account<'info, Profile>>,
+    
+    /// Instructions sysvar holding the Ed25519 instructions over the credential's proofs
+    /// CHECK: Address is constrained to the instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: Option<UncheckedAccount<'info>>,
+}
+
+/// Context for the achievement summary view
+#[derive(Accounts)]
+pub struct GetAchievementSummary<'info> {
+    pub achievement: Account<'info, Achievement>,
+    
+    /// Achievement counters, once the first credential was issued
+    #[account(
+        seeds = [stats::ACHIEVEMENT_STATS_SEED, achievement.key().as_ref()],
+        bump = achievement_stats.bump
+    )]
+    pub achievement_stats: Option<Account<'info, stats::AchievementStats>>,
+}
+
+/// Context for generating an issuer's Profile document
+#[derive(Accounts)]
+pub struct GenerateProfileJson<'info> {
+    pub issuer: Account<'info, Profile>,
+}
+
+/// Context for the issuer summary view
+#[derive(Accounts)]
+pub struct GetIssuerSummary<'info> {
+    pub issuer: Account<'info, Profile>,
+    
+    /// Issuer event sequence, once the issuer made its first change
+    #[account(
+        seeds = [stats::ISSUER_STATS_SEED, issuer.key().as_ref()],
+        bump = issuer_stats.bump
+    )]
+    pub issuer_stats: Option<Account<'info, stats::IssuerStats>>,
+}
+
+/// Context for the issuer JWKS view
+#[derive(Accounts)]
+pub struct GetIssuerJwks<'info> {
+    pub issuer: Account<'info, Profile>,
+    
+    /// The issuer authority's sol_did DID account
+    /// CHECK: Read as a sol_did DidAccount when owned by sol_did
+    #[account(address = did::methods::SolanaDidResolver::derive_did_account(&issuer.authority).0 @ ValidationError::InvalidDid)]
+    pub issuer_did_account: Option<UncheckedAccount<'info>>,
+}
+
+/// Context for exporting a credential document
+#[derive(Accounts)]
+pub struct ExportCredential<'info> {
+    pub credential: Account<'info, AchievementCredential>,
+}
+
+/// Context for exporting a credential's verification bundle
+#[derive(Accounts)]
+pub struct ExportVerificationBundle<'info> {
+    pub credential: Account<'info, AchievementCredential>,
+    
+    #[account(address = credential.issuer @ ValidationError::UnauthorizedAccess)]
+    pub issuer: Account<'info, Profile>,
+    
+    /// The issuer authority's sol_did DID account
+    /// CHECK: Read as a sol_did DidAccount when owned by sol_did, see `credential::read_sol_did_account`
+    #[account(address = did::methods::SolanaDidResolver::derive_did_account(&issuer.authority).0 @ ValidationError::InvalidDid)]
+    pub issuer_did_account: UncheckedAccount<'info>,
+    
+    /// Revocation list the credential was assigned an index in
+    #[account(constraint = revocation_list.authority == issuer.authority @ ValidationError::UnauthorizedAccess)]
+    pub revocation_list: Option<Account<'info, credential_status::RevocationList>>,
+}
+
+#[derive(Accounts)]
+pub struct ValidateCredential<'info> {
+    pub credential: Account<'info, AchievementCredential>,
+    /// Issuer of the credential, whose validation mode applies
+    #[account(address = credential.issuer @ ValidationError::UnauthorizedAccess)]
+    pub issuer: Account<'info, Profile>,
+    /// Issuer's schema registry, required when the document declares credentialSchema
+    #[account(
+        seeds = [schema::SCHEMA_REGISTRY_SEED, issuer.key().as_ref()],
+        bump = schema_registry.bump
+    )]
+    pub schema_registry: Option<Account<'info, schema::SchemaRegistry>>,
+}
+
+#[derive(Accounts)]
+pub struct ValidateAchievement<'info> {
+    pub achievement: Account<'info, Achievement>,
+    /// Issuer of the achievement, whose validation mode applies
+    #[account(address = achievement.issuer @ ValidationError::UnauthorizedAccess)]
+    pub issuer: Account<'info, Profile>,
+}
+
+#[derive(Accounts)]
+pub struct ValidateProfile<'info> {
+    pub profile: Account<'info, Profile>,
+}
+
+#[derive(Accounts)]
+pub struct CreateLinkedDataProof<'info> {
+    pub signer: Signer<'info>,
+    /// Instructions sysvar holding the Ed25519 or, for EVM signers, secp256k1 program instruction
+    /// CHECK: Address is constrained to the instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct VerifyLinkedDataProof<'info> {
+    /// Instructions sysvar holding the Ed25519 or, for EVM signers, secp256k1 program instruction
+    /// CHECK: Address is constrained to the instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct GenerateCredential<'info> {
+    pub issuer: Account<'info, Profile>,
+    pub achievement: Account<'info, Achievement>,
+    /// CHECK: This is just used for recipient public key
+    pub recipient: UncheckedAccount<'info>,
+    /// Grading rubric of the achievement, if one is attached
+    #[account(
+        seeds = [rubric::RUBRIC_SEED, achievement.key().as_ref()],
+        bump = rubric.bump
+    )]
+    pub rubric: Option<Account<'info, rubric::Rubric>>,
+}
+
+/// Context for preparing an SD-JWT of an issued credential
+#[derive(Accounts)]
+pub struct GenerateSdJwt<'info> {
+    pub issuer: Account<'info, Profile>,
+    pub achievement: Account<'info, Achievement>,
+    #[account(
+        has_one = issuer @ ValidationError::UnauthorizedAccess,
+        constraint = credential.credential_subject.achievement == achievement.key() @ ValidationError::ValidationFailed
+    )]
+    pub credential: Account<'info, AchievementCredential>,
+    /// Grading rubric of the achievement, if one is attached
+    #[account(
+        seeds = [rubric::RUBRIC_SEED, achievement.key().as_ref()],
+        bump = rubric.bump
+    )]
+    pub rubric: Option<Account<'info, rubric::Rubric>>,
+}
+
+/// Context for securing an issued credential as COSE_Sign1
+#[derive(Accounts)]
+pub struct GenerateCoseCredential<'info> {
+    pub issuer: Account<'info, Profile>,
+    pub achievement: Account<'info, Achievement>,
+    #[account(
+        has_one = issuer @ ValidationError::UnauthorizedAccess,
+        constraint = credential.credential_subject.achievement == achievement.key() @ ValidationError::ValidationFailed
+    )]
+    pub credential: Account<'info, AchievementCredential>,
+    /// Grading rubric of the achievement, if one is attached
+    #[account(
+        seeds = [rubric::RUBRIC_SEED, achievement.key().as_ref()],
+        bump = rubric.bump
+    )]
+    pub rubric: Option<Account<'info, rubric::Rubric>>,
+    /// Instructions sysvar holding the Ed25519 program instruction, when signing
+    /// CHECK: Address is constrained to the instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: Option<UncheckedAccount<'info>>,
+}
+
+/// Context for assembling a signed JWT credential
+#[derive(Accounts)]
+pub struct GenerateJwtCredential<'info> {
+    pub issuer: Account<'info, Profile>,
+    pub achievement: Account<'info, Achievement>,
+    /// CHECK: This is just used for recipient public key
+    pub recipient: UncheckedAccount<'info>,
+    /// Grading rubric of the achievement, if one is attached
+    #[account(
+        seeds = [rubric::RUBRIC_SEED, achievement.key().as_ref()],
+        bump = rubric.bump
+    )]
+    pub rubric: Option<Account<'info, rubric::Rubric>>,
+    /// Instructions sysvar holding the Ed25519 program instruction
+    /// CHECK: Address is constrained to the instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct VerifyCredentialFormat<'info> {
+    /// Issuer profile named by a JWT's `iss`, when its authority signed
+    pub issuer: Option<Account<'info, Profile>>,
+    /// Instructions sysvar holding the Ed25519 program instruction for a JWT
+    /// CHECK: Address is constrained to the instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: Option<UncheckedAccount<'info>>,
+    /// The issuer's record of the signing staff key, when a staff member signed
+    pub staff_key: Option<Account<'info, staff_key::StaffKey>>,
+    /// sol_did account of the signing staff key's DID, listing the issuer as controller
+    /// CHECK: Owner and address are checked against the sol_did program during verification
+    pub signer_did_data: Option<UncheckedAccount<'info>>,
+}
+
+/// Context for resolving a DID; resolution is purely computational
+#[derive(Accounts)]
+pub struct ResolveDid<'info> {
+    /// Placeholder so the context has an account; the view reads none
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for resolving a did:sol DID from the sol_did account
+#[derive(Accounts)]
+pub struct ResolveDidFromAccount<'info> {
+    /// The sol_did DID account for the DID being resolved
+    /// CHECK: Address and owner are checked against the sol_did program during resolution
+    pub did_data: UncheckedAccount<'info>,
+}
+
+/// Context for setting an issuer's CPI policy
+#[derive(Accounts)]
+pub struct SetCpiPolicy<'info> {
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = cpi_guard::CpiPolicy::SPACE,
+        seeds = [cpi_guard::CPI_POLICY_SEED, issuer.key().as_ref()],
+        bump
+    )]
+    pub cpi_policy: Account<'info, cpi_guard::CpiPolicy>,
+    
+    #[account(has_one = authority @ ValidationError::UnauthorizedAccess)]
+    pub issuer: Account<'info, Profile>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for setting the issuer-defined extensions of a credential
+#[derive(Accounts)]
+#[instruction(extensions: Vec<extensions::Extension>)]
+pub struct SetCredentialExtensions<'info> {
+    #[account(
+        mut,
+        has_one = issuer @ ValidationError::UnauthorizedAccess,
+        realloc = extensions::credential_space(credential.to_account_info().data_len(), &credential, &extensions),
+        realloc::payer = authority,
+        realloc::zero = false
+    )]
+    pub credential: Account<'info, AchievementCredential>,
+    
+    #[account(has_one = authority @ ValidationError::UnauthorizedAccess)]
+    pub issuer: Account<'info, Profile>,
+    
+    /// Instructions sysvar holding the authority's Ed25519 instruction
+    /// CHECK: Address is constrained to the instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    
+    /// Issuer event sequence, created on the issuer's first event
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = stats::IssuerStats::SPACE,
+        seeds = [stats::ISSUER_STATS_SEED, issuer.key().as_ref()],
+        bump
+    )]
+    pub issuer_stats: Account<'info, stats::IssuerStats>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for attaching an encrypted body to a credential
+#[derive(Accounts)]
+#[instruction(payload: encrypted_payload::EncryptedPayload)]
+pub struct AttachEncryptedPayload<'info> {
+    #[account(
+        mut,
+        has_one = issuer @ ValidationError::UnauthorizedAccess,
+        realloc = encrypted_payload::credential_space(credential.to_account_info().data_len(), &credential, &payload),
+        realloc::payer = authority,
+        realloc::zero = false
+    )]
+    pub credential: Account<'info, AchievementCredential>,
+    
+    #[account(has_one = authority @ ValidationError::UnauthorizedAccess)]
+    pub issuer: Account<'info, Profile>,
+    
+    /// Instructions sysvar holding the authority's Ed25519 instruction
+    /// CHECK: Address is constrained to the instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    
+    /// Issuer event sequence, created on the issuer's first event
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = stats::IssuerStats::SPACE,
+        seeds = [stats::ISSUER_STATS_SEED, issuer.key().as_ref()],
+        bump
+    )]
+    pub issuer_stats: Account<'info, stats::IssuerStats>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for reissuing an expired credential at the PDA derived from it
+#[derive(Accounts)]
+pub struct ReissueCredential<'info> {
+    #[account(has_one = issuer @ ValidationError::UnauthorizedAccess)]
+    pub previous_credential: Account<'info, AchievementCredential>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = refresh::reissued_space(previous_credential.to_account_info().data_len(), &previous_credential),
+        seeds = [pda::CREDENTIAL_SEED, previous_credential.key().as_ref()],
+        bump
+    )]
+    pub credential: Account<'info, AchievementCredential>,
+    
+    #[account(mut, has_one = authority @ ValidationError::UnauthorizedAccess)]
+    pub issuer: Account<'info, Profile>,
+    
+    /// Program config PDA; issuance is refused while it is paused
+    /// CHECK: Address is constrained by seeds, contents checked in the handler
+    #[account(seeds = [program_config::PROGRAM_CONFIG_SEED], bump)]
+    pub program_config: UncheckedAccount<'info>,
+    
+    /// Program treasury receiving issuance fees
+    /// CHECK: Address is constrained by seeds; only credited
+    #[account(mut, seeds = [treasury::TREASURY_SEED], bump)]
+    pub treasury: UncheckedAccount<'info>,
+    
+    /// Authority's token account paying the token fee, when the config sets one
+    /// CHECK: Mint checked in the handler, ownership by the token program
+    #[account(mut)]
+    pub fee_token_account: Option<UncheckedAccount<'info>>,
+    
+    /// Treasury-owned token account receiving the token fee
+    /// CHECK: Mint and owner checked in the handler
+    #[account(mut)]
+    pub treasury_token_account: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Checked against the SPL Token program id
+    pub token_program: Option<UncheckedAccount<'info>>,
+    
+    /// Issuer's CPI policy; consulted only when invoked through CPI
+    #[account(
+        seeds = [cpi_guard::CPI_POLICY_SEED, issuer.key().as_ref()],
+        bump = cpi_policy.bump
+    )]
+    pub cpi_policy: Option<Account<'info, cpi_guard::CpiPolicy>>,
+    
+    /// Instructions sysvar holding the authority's Ed25519 instruction and naming the top-level program of a CPI
+    /// CHECK: Address is constrained to the instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    
+    /// Issuer event sequence, created on the issuer's first event
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = stats::IssuerStats::SPACE,
+        seeds = [stats::ISSUER_STATS_SEED, issuer.key().as_ref()],
+        bump
+    )]
+    pub issuer_stats: Account<'info, stats::IssuerStats>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for superseding a credential with a replacement at the PDA derived from it
+#[derive(Accounts)]
+pub struct SupersedeCredential<'info> {
+    #[account(mut, has_one = issuer @ ValidationError::UnauthorizedAccess)]
+    pub previous_credential: Account<'info, AchievementCredential>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = refresh::reissued_space(previous_credential.to_account_info().data_len(), &previous_credential),
+        seeds = [supersession::SUPERSESSION_SEED, previous_credential.key().as_ref()],
+        bump
+    )]
+    pub credential: Account<'info, AchievementCredential>,
+    
+    /// Achievement the replacement awards; the superseded credential's or a corrected one
+    #[account(has_one = issuer @ ValidationError::UnauthorizedAccess)]
+    pub achievement: Account<'info, Achievement>,
+    
+    #[account(mut, has_one = authority @ ValidationError::UnauthorizedAccess)]
+    pub issuer: Account<'info, Profile>,
+    
+    /// Program config PDA; issuance is refused while it is paused
+    /// CHECK: Address is constrained by seeds, contents checked in the handler
+    #[account(seeds = [program_config::PROGRAM_CONFIG_SEED], bump)]
+    pub program_config: UncheckedAccount<'info>,
+    
+    /// Program treasury receiving issuance fees
+    /// CHECK: Address is constrained by seeds; only credited
+    #[account(mut, seeds = [treasury::TREASURY_SEED], bump)]
+    pub treasury: UncheckedAccount<'info>,
+    
+    /// Authority's token account paying the token fee, when the config sets one
+    /// CHECK: Mint checked in the handler, ownership by the token program
+    #[account(mut)]
+    pub fee_token_account: Option<UncheckedAccount<'info>>,
+    
+    /// Treasury-owned token account receiving the token fee
+    /// CHECK: Mint and owner checked in the handler
+    #[account(mut)]
+    pub treasury_token_account: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Checked against the SPL Token program id
+    pub token_program: Option<UncheckedAccount<'info>>,
+    
+    /// Issuer's CPI policy; consulted only when invoked through CPI
+    #[account(
+        seeds = [cpi_guard::CPI_POLICY_SEED, issuer.key().as_ref()],
+        bump = cpi_policy.bump
+    )]
+    pub cpi_policy: Option<Account<'info, cpi_guard::CpiPolicy>>,
+    
+    /// Instructions sysvar holding the authority's Ed25519 instruction and naming the top-level program of a CPI
+    /// CHECK: Address is constrained to the instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    
+    /// Issuer event sequence, created on the issuer's first event
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = stats::IssuerStats::SPACE,
+        seeds = [stats::ISSUER_STATS_SEED, issuer.key().as_ref()],
+        bump
+    )]
+    pub issuer_stats: Account<'info, stats::IssuerStats>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for registering the hash of a credential's baked image
+#[derive(Accounts)]
+pub struct RegisterBakedBadge<'info> {
+    #[account(
+        mut,
+        has_one = issuer @ ValidationError::UnauthorizedAccess,
+        realloc = baked_image::credential_space(credential.to_account_info().data_len(), &credential),
+        realloc::payer = authority,
+        realloc::zero = false
+    )]
+    pub credential: Account<'info, AchievementCredential>,
+    
+    #[account(has_one = authority @ ValidationError::UnauthorizedAccess)]
+    pub issuer: Account<'info, Profile>,
+    
+    /// Issuer event sequence, created on the issuer's first event
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = stats::IssuerStats::SPACE,
+        seeds = [stats::ISSUER_STATS_SEED, issuer.key().as_ref()],
+        bump
+    )]
+    pub issuer_stats: Account<'info, stats::IssuerStats>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct VerifyBakedBadge<'info> {
+    pub credential: Account<'info, AchievementCredential>,
+}
+
+/// Context for attaching a re-signed proof to an existing credential
+#[derive(Accounts)]
+pub struct UpgradeCredentialProof<'info> {
+    #[account(
+        mut,
+        has_one = issuer @ ValidationError::UnauthorizedAccess,
+        realloc = proof_upgrade::upgraded_space(credential.to_account_info().data_len(), &credential),
+        realloc::payer = authority,
+        realloc::zero = false
+    )]
+    pub credential: Account<'info, AchievementCredential>,
+    
+    #[account(has_one = authority @ ValidationError::UnauthorizedAccess)]
+    pub issuer: Account<'info, Profile>,
+    
+    /// Issuer's CPI policy; consulted only when invoked through CPI
+    #[account(
+        seeds = [cpi_guard::CPI_POLICY_SEED, issuer.key().as_ref()],
+        bump = cpi_policy.bump
+    )]
+    pub cpi_policy: Option<Account<'info, cpi_guard::CpiPolicy>>,
+    
+    /// Instructions sysvar holding the authority's Ed25519 instruction and naming the top-level program of a CPI
+    /// CHECK: Address is constrained to the instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    
+    /// Issuer event sequence, created on the issuer's first event
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = stats::IssuerStats::SPACE,
+        seeds = [stats::ISSUER_STATS_SEED, issuer.key().as_ref()],
+        bump
+    )]
+    pub issuer_stats: Account<'info, stats::IssuerStats>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for co-signing a credential with an issuer verification method
+#[derive(Accounts)]
+pub struct AddProofToCredential<'info> {
+    #[account(
+        mut,
+        realloc = proof_set::added_space(credential.to_account_info().data_len(), &credential),
+        realloc::payer = signer,
+        realloc::zero = false
+    )]
+    pub credential: Account<'info, AchievementCredential>,
+    
+    /// Verification method of the credential's issuer held by the signer
+    #[account(
+        constraint = issuer_key.issuer == credential.issuer @ ValidationError::UnauthorizedAccess,
+        constraint = issuer_key.public_key == signer.key() @ ValidationError::UnauthorizedAccess
+    )]
+    pub issuer_key: Account<'info, issuer_keys::IssuerKey>,
+    
+    /// Instructions sysvar holding the Ed25519 instruction over the co-signed message
+    /// CHECK: Address is constrained to the instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for direct credential revocation
+#[derive(Accounts)]
+pub struct RevokeCredentialDirect<'info> {
+    #[account(
+        mut,
+        has_one = issuer @ ValidationError::UnauthorizedAccess
+    )]
+    pub credential: Account<'info, AchievementCredential>,
+    
+    #[account(has_one = authority @ ValidationError::UnauthorizedAccess)]
+    pub issuer: Account<'info, Profile>,
+    
+    /// Program config PDA; revocation is refused while it is paused
+    /// CHECK: Address is constrained by seeds, contents checked in the handler
+    #[account(seeds = [program_config::PROGRAM_CONFIG_SEED], bump)]
+    pub program_config: UncheckedAccount<'info>,
+    
+    /// Issuer's CPI policy; consulted only when invoked through CPI
+    #[account(
+        seeds = [cpi_guard::CPI_POLICY_SEED, issuer.key().as_ref()],
+        bump = cpi_policy.bump
+    )]
+    pub cpi_policy: Option<Account<'info, cpi_guard::CpiPolicy>>,
+    
+    /// Instructions sysvar naming the top-level program of a CPI
+    /// CHECK: Address is constrained to the instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: Option<UncheckedAccount<'info>>,
+    
+    /// Achievement counters, created here if it predates them
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = stats::AchievementStats::SPACE,
+        seeds = [stats::ACHIEVEMENT_STATS_SEED, credential.credential_subject.achievement.as_ref()],
+        bump
+    )]
+    pub achievement_stats: Account<'info, stats::AchievementStats>,
+    
+    /// Issuer event sequence, created on the issuer's first event
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = stats::IssuerStats::SPACE,
+        seeds = [stats::ISSUER_STATS_SEED, issuer.key().as_ref()],
+        bump
+    )]
+    pub issuer_stats: Account<'info, stats::IssuerStats>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for suspending or reinstating a credential by its account address
+#[derive(Accounts)]
+pub struct UpdateCredentialStatusDirect<'info> {
+    #[account(
+        mut,
+        has_one = issuer @ ValidationError::UnauthorizedAccess
+    )]
+    pub credential: Account<'info, AchievementCredential>,
+    
+    #[account(has_one = authority @ ValidationError::UnauthorizedAccess)]
+    pub issuer: Account<'info, Profile>,
+    
+    /// Program config PDA; status changes are refused while it is paused
+    /// CHECK: Address is constrained by seeds, contents checked in the handler
+    #[account(seeds = [program_config::PROGRAM_CONFIG_SEED], bump)]
+    pub program_config: UncheckedAccount<'info>,
+    
+    /// Issuer's CPI policy; consulted only when invoked through CPI
+    #[account(
+        seeds = [cpi_guard::CPI_POLICY_SEED, issuer.key().as_ref()],
+        bump = cpi_policy.bump
+    )]
+    pub cpi_policy: Option<Account<'info, cpi_guard::CpiPolicy>>,
+    
+    /// Instructions sysvar naming the top-level program of a CPI
+    /// CHECK: Address is constrained to the instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: Option<UncheckedAccount<'info>>,
+    
+    /// Issuer event sequence, created on the issuer's first event
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = stats::IssuerStats::SPACE,
+        seeds = [stats::ISSUER_STATS_SEED, issuer.key().as_ref()],
+        bump
+    )]
+    pub issuer_stats: Account<'info, stats::IssuerStats>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for re-binding a credential to a new subject DID
+#[derive(Accounts)]
+#[instruction(new_subject_id: String)]
+pub struct RebindCredential<'info> {
+    #[account(
+        mut,
+        has_one = issuer @ ValidationError::UnauthorizedAccess,
+        realloc = credential.to_account_info().data_len()
+            + rebind::growth(credential.credential_subject.id.as_deref(), &new_subject_id),
+        realloc::payer = authority,
+        realloc::zero = false
+    )]
+    pub credential: Account<'info, AchievementCredential>,
+    
+    #[account(has_one = authority @ ValidationError::UnauthorizedAccess)]
+    pub issuer: Account<'info, Profile>,
+    
+    /// Program config PDA; re-binding is refused while it is paused
+    /// CHECK: Address is constrained by seeds, contents checked in the handler
+    #[account(seeds = [program_config::PROGRAM_CONFIG_SEED], bump)]
+    pub program_config: UncheckedAccount<'info>,
+    
+    /// Issuer's CPI policy; consulted only when invoked through CPI
+    #[account(
+        seeds = [cpi_guard::CPI_POLICY_SEED, issuer.key().as_ref()],
+        bump = cpi_policy.bump
+    )]
+    pub cpi_policy: Option<Account<'info, cpi_guard::CpiPolicy>>,
+    
+    /// Instructions sysvar holding the new holder's Ed25519 instruction
+    /// CHECK: Address is constrained to the instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    
+    /// Issuer event sequence, created on the issuer's first event
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = stats::IssuerStats::SPACE,
+        seeds = [stats::ISSUER_STATS_SEED, issuer.key().as_ref()],
+        bump
+    )]
+    pub issuer_stats: Account<'info, stats::IssuerStats>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for revoking credentials passed as remaining accounts
+#[derive(Accounts)]
+pub struct BatchRevokeCredentialsDirect<'info> {
+    #[account(has_one = authority @ ValidationError::UnauthorizedAccess)]
+    pub issuer: Account<'info, Profile>,
+    
+    /// Program config PDA; revocation is refused while it is paused
+    /// CHECK: Address is constrained by seeds, contents checked in the handler
+    #[account(seeds = [program_config::PROGRAM_CONFIG_SEED], bump)]
+    pub program_config: UncheckedAccount<'info>,
+    
+    /// Issuer's CPI policy; consulted only when invoked through CPI
+    #[account(
+        seeds = [cpi_guard::CPI_POLICY_SEED, issuer.key().as_ref()],
+        bump = cpi_policy.bump
+    )]
+    pub cpi_policy: Option<Account<'info, cpi_guard::CpiPolicy>>,
+    
+    /// Instructions sysvar naming the top-level program of a CPI
+    /// CHECK: Address is constrained to the instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: Option<UncheckedAccount<'info>>,
+    
+    /// Revocation list whose bits are set for the revoked credentials
+    #[account(mut, has_one = authority @ ValidationError::UnauthorizedAccess)]
+    pub revocation_list: Option<Account<'info, credential_status::RevocationList>>,
+    
+    /// Issuer event sequence, created on the issuer's first event
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = stats::IssuerStats::SPACE,
+        seeds = [stats::ISSUER_STATS_SEED, issuer.key().as_ref()],
+        bump
+    )]
+    pub issuer_stats: Account<'info, stats::IssuerStats>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for marking a credential expired; any signer may crank it
+#[derive(Accounts)]
+pub struct ExpireCredential<'info> {
+    #[account(mut)]
+    pub credential: Account<'info, AchievementCredential>,
+    
+    /// Achievement counters, created here if it predates them
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        space = stats::AchievementStats::SPACE,
+        seeds = [stats::ACHIEVEMENT_STATS_SEED, credential.credential_subject.achievement.as_ref()],
+        bump
+    )]
+    pub achievement_stats: Account<'info, stats::AchievementStats>,
+    
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for registering a credential schema
+#[derive(Accounts)]
+pub struct RegisterCredentialSchema<'info> {
+    /// Issuer's schema registry, created with the first schema
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = schema::SchemaRegistry::SPACE,
+        seeds = [schema::SCHEMA_REGISTRY_SEED, issuer.key().as_ref()],
+        bump
+    )]
+    pub schema_registry: Account<'info, schema::SchemaRegistry>,
+    
+    #[account(
+        seeds = [pda::ISSUER_SEED, authority.key().as_ref()],
+        bump = issuer.bump
+    )]
+    pub issuer: Account<'info, Profile>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct GenerateCredentialJson<'info> {
+    #[account(
+        seeds = [pda::ISSUER_SEED, authority.key().as_ref()],
+        bump
+    )]
+    pub issuer: Account<'info, Profile>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(name: String, url: Option<String>, email: Option<String>, image: Option<String>)]
+pub struct InitializeIssuerPdaAuthority<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = Profile::space(&name, &url, &email, &image),
+        seeds = [pda::ISSUER_SEED, authority.key().as_ref()],
+        bump
+    )]
+    pub issuer: Account<'info, Profile>,
+    
+    /// PDA of another program, signed for by that program via `invoke_signed`
+    pub authority: Signer<'info>,
+    
+    /// Account paying for the profile, since the PDA may hold no lamports
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(name: String, url: Option<String>, email: Option<String>, image: Option<String>)]
+pub struct InitializeIssuerWithDid<'info> {
+    /// The issuer profile account to initialize
+    #[account(
+        init,
+        payer = payer,
+        space = Profile::space(&name, &url, &email, &image),
+        seeds = [pda::ISSUER_SEED, authority.key().as_ref()],
+        bump
+    )]
+    pub issuer: Account<'info, Profile>,
+    
+    /// Authority (signer) for the issuer
+    pub authority: Signer<'info>,
+    
+    /// Account paying for the transactions
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    /// The DID account to initialize
+    /// CHECK: This account is validated by the sol-did program during CPI call
+    #[account(mut)]
+    pub did_data: AccountInfo<'info>,
+    
+    /// The sol-did program
+    pub sol_did_program: Program<'info, sol_did_cpi::program::SolDid>,
+    
+    /// System program
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for publishing an issuer key in the issuer's DID document
+#[derive(Accounts)]
+pub struct AddIssuerVerificationMethod<'info> {
+    #[account(
+        seeds = [pda::ISSUER_SEED, authority.key().as_ref()],
+        bump = issuer.bump
+    )]
+    pub issuer: Account<'info, Profile>,
+    
+    #[account(has_one = issuer @ ValidationError::UnauthorizedAccess)]
+    pub issuer_key: Account<'info, issuer_keys::IssuerKey>,
+    
+    /// The authority's sol_did DID account
+    /// CHECK: Address is constrained to the authority's DID PDA, contents checked by sol_did
+    #[account(
+        mut,
+        address = did::methods::SolanaDidResolver::derive_did_account(&authority.key()).0
+    )]
+    pub did_data: UncheckedAccount<'info>,
+    
+    pub authority: Signer<'info>,
+    
+    /// The sol-did program
+    pub sol_did_program: Program<'info, sol_did_cpi::program::SolDid>,
+}
+
+/// Context for advertising the issuer's badge service in its DID document
+#[derive(Accounts)]
+pub struct AddIssuerService<'info> {
+    #[account(
+        seeds = [pda::ISSUER_SEED, authority.key().as_ref()],
+        bump = issuer.bump
+    )]
+    pub issuer: Account<'info, Profile>,
+    
+    /// The authority's sol_did DID account
+    /// CHECK: Address is constrained to the authority's DID PDA, contents checked by sol_did
+    #[account(
+        mut,
+        address = did::methods::SolanaDidResolver::derive_did_account(&authority.key()).0
+    )]
+    pub did_data: UncheckedAccount<'info>,
+    
+    pub authority: Signer<'info>,
+    
+    /// The sol-did program
+    pub sol_did_program: Program<'info, sol_did_cpi::program::SolDid>,
+}
+
+/// Context for growing the issuer's DID account
+#[derive(Accounts)]
+pub struct ExpandIssuerDid<'info> {
+    #[account(
+        seeds = [pda::ISSUER_SEED, authority.key().as_ref()],
+        bump = issuer.bump
+    )]
+    pub issuer: Account<'info, Profile>,
+    
+    /// The authority's sol_did DID account
+    /// CHECK: Address is constrained to the authority's DID PDA, contents checked by sol_did
+    #[account(
+        mut,
+        address = did::methods::SolanaDidResolver::derive_did_account(&authority.key()).0
+    )]
+    pub did_data: UncheckedAccount<'info>,
+    
+    pub authority: Signer<'info>,
+    
+    /// Pays the rent of the added bytes
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    /// The sol-did program
+    pub sol_did_program: Program<'info, sol_did_cpi::program::SolDid>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for authorizing a staff key and setting the issuer as controller of its DID
+/// Both the issuer's authority and the staff member sign
+#[derive(Accounts)]
+pub struct SetStaffDidController<'info> {
+    #[account(
+        seeds = [pda::ISSUER_SEED, issuer_authority.key().as_ref()],
+        bump = issuer.bump
+    )]
+    pub issuer: Account<'info, Profile>,
+    
+    /// The issuer's record of the staff key
+    #[account(
+        init,
+        payer = issuer_authority,
+        space = staff_key::StaffKey::SPACE,
+        seeds = [staff_key::STAFF_KEY_SEED, issuer.key().as_ref(), staff.key().as_ref()],
+        bump
+    )]
+    pub staff_key: Account<'info, staff_key::StaffKey>,
+    
+    #[account(mut)]
+    pub issuer_authority: Signer<'info>,
+    
+    /// The staff member's sol_did DID account
+    /// CHECK: Address is constrained to the staff member's DID PDA, contents checked by sol_did
+    #[account(
+        mut,
+        address = did::methods::SolanaDidResolver::derive_did_account(&staff.key()).0
+    )]
+    pub staff_did_data: UncheckedAccount<'info>,
+    
+    /// The staff member's signing key, authority of their DID
+    pub staff: Signer<'info>,
+    
+    /// The sol-did program
+    pub sol_did_program: Program<'info, sol_did_cpi::program::SolDid>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for revoking a staff key
+#[derive(Accounts)]
+pub struct RevokeStaffKey<'info> {
+    #[account(
+        seeds = [pda::ISSUER_SEED, issuer_authority.key().as_ref()],
+        bump = issuer.bump
+    )]
+    pub issuer: Account<'info, Profile>,
+    
+    #[account(
+        mut,
+        close = issuer_authority,
+        seeds = [staff_key::STAFF_KEY_SEED, issuer.key().as_ref(), staff_key.staff.as_ref()],
+        bump = staff_key.bump
+    )]
+    pub staff_key: Account<'info, staff_key::StaffKey>,
+    
+    #[account(mut)]
+    pub issuer_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(args: args::IssueCredentialArgs)]
+pub struct IssueAchievementCredentialWithKey<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = AchievementCredential::issuance_space(args.timestamp.len(), &args.results, args.narrative.as_deref(), &args.notes, &args.credential_schemas),
+        seeds = [
+            pda::CREDENTIAL_SEED,
+            achievement.key().as_ref(), 
+            issuer.key().as_ref(),
+            args.recipient_pubkey.as_ref()
+        ],
+        bump
+    )]
+    pub credential: Account<'info, AchievementCredential>,
+    
+    pub achievement: Account<'info, Achievement>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ISSUER_SEED, authority.key().as_ref()],
+        bump = issuer.bump,
+        constraint = issuer.key() == achievement.issuer @ ValidationError::UnauthorizedIssuer
+    )]
+    pub issuer: Account<'info, Profile>,
+    
+    /// Program config PDA; issuance is refused while it is paused
+    /// CHECK: Address is constrained by seeds, contents checked in the handler
+    #[account(seeds = [program_config::PROGRAM_CONFIG_SEED], bump)]
+    pub program_config: UncheckedAccount<'info>,
+    
+    /// Program treasury receiving issuance fees
+    /// CHECK: Address is constrained by seeds; only credited
+    #[account(mut, seeds = [treasury::TREASURY_SEED], bump)]
+    pub treasury: UncheckedAccount<'info>,
+    
+    /// Authority's token account paying the token fee, when the config sets one
+    /// CHECK: Mint checked in the handler, ownership by the token program
+    #[account(mut)]
+    pub fee_token_account: Option<UncheckedAccount<'info>>,
+    
+    /// Treasury-owned token account receiving the token fee
+    /// CHECK: Mint and owner checked in the handler
+    #[account(mut)]
+    pub treasury_token_account: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Checked against the SPL Token program id
+    pub token_program: Option<UncheckedAccount<'info>>,
+    
+    /// The registered key whose signature is embedded in the proof
+    #[account(
+        seeds = [issuer_keys::ISSUER_KEY_SEED, issuer.key().as_ref(), &issuer_key.key_index.to_le_bytes()],
+        bump = issuer_key.bump,
+        has_one = issuer @ ValidationError::UnauthorizedAccess
+    )]
+    pub issuer_key: Account<'info, issuer_keys::IssuerKey>,
+    
+    /// Recipient's opt-out PDA; must be empty for issuance to proceed
+    /// CHECK: Address is constrained by seeds, contents checked in the handler
+    #[account(
+        seeds = [opt_out::OPT_OUT_SEED, args.recipient_pubkey.as_ref()],
+        bump
+    )]
+    pub recipient_opt_out: UncheckedAccount<'info>,
+    
+    /// Moderated assessment result backing this credential, if required
+    #[account(
+        seeds = [assessment::RESULT_SEED, achievement.key().as_ref(), args.recipient_pubkey.as_ref()],
+        bump = result_entry.bump
+    )]
+    pub result_entry: Option<Account<'info, assessment::ResultEntry>>,
+    
+    /// Grading rubric that achieved levels are checked against, if results use one
+    #[account(
+        seeds = [rubric::RUBRIC_SEED, achievement.key().as_ref()],
+        bump = rubric.bump
+    )]
+    pub rubric: Option<Account<'info, rubric::Rubric>>,
+    
+    /// Issuer's schema registry, required when attaching credential schemas
+    #[account(
+        seeds = [schema::SCHEMA_REGISTRY_SEED, issuer.key().as_ref()],
+        bump = schema_registry.bump
+    )]
+    pub schema_registry: Option<Account<'info, schema::SchemaRegistry>>,
+    
+    /// Nomination approved by this issuance, if the recipient was nominated
+    #[account(
+        mut,
+        seeds = [nomination::NOMINATION_SEED, achievement.key().as_ref(), args.recipient_pubkey.as_ref(), nomination.nominator.as_ref()],
+        bump = nomination.bump
+    )]
+    pub nomination: Option<Account<'info, nomination::Nomination>>,
+    
+    /// Issuer's CPI policy; consulted only when invoked through CPI
+    #[account(
+        seeds = [cpi_guard::CPI_POLICY_SEED, issuer.key().as_ref()],
+        bump = cpi_policy.bump
+    )]
+    pub cpi_policy: Option<Account<'info, cpi_guard::CpiPolicy>>,
+    
+    /// Instructions sysvar holding the issuer key's Ed25519 instruction and naming the top-level program of a CPI
+    /// CHECK: Address is constrained to the instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    
+    /// Achievement counters, created with the achievement's first credential
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = stats::AchievementStats::SPACE,
+        seeds = [stats::ACHIEVEMENT_STATS_SEED, achievement.key().as_ref()],
+        bump
+    )]
+    pub achievement_stats: Account<'info, stats::AchievementStats>,
+    
+    /// Recipient's credential index, created with their first credential
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = credential_index::CredentialIndex::SPACE,
+        seeds = [credential_index::CREDENTIAL_INDEX_SEED, args.recipient_pubkey.as_ref()],
+        bump
+    )]
+    pub credential_index: Account<'info, credential_index::CredentialIndex>,
+    
+    /// Index page the credential is appended to, created when the previous page is full
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = credential_index::CredentialIndexPage::SPACE,
+        seeds = [credential_index::CREDENTIAL_INDEX_SEED, args.recipient_pubkey.as_ref(), &credential_index.next_page().to_le_bytes()],
+        bump
+    )]
+    pub credential_index_page: Account<'info, credential_index::CredentialIndexPage>,
+    
+    /// Achievement's recipient roster, created with its first credential
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = recipient_roster::RecipientRoster::SPACE,
+        seeds = [recipient_roster::RECIPIENT_ROSTER_SEED, achievement.key().as_ref()],
+        bump
+    )]
+    pub recipient_roster: Account<'info, recipient_roster::RecipientRoster>,
+    
+    /// Roster page the holder is appended to, created when the previous page is full
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = recipient_roster::RecipientRosterPage::SPACE,
+        seeds = [recipient_roster::RECIPIENT_ROSTER_SEED, achievement.key().as_ref(), &recipient_roster.next_page().to_le_bytes()],
+        bump
+    )]
+    pub recipient_roster_page: Account<'info, recipient_roster::RecipientRosterPage>,
+    
+    /// Issuer event sequence, created on the issuer's first event
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = stats::IssuerStats::SPACE,
+        seeds = [stats::ISSUER_STATS_SEED, issuer.key().as_ref()],
+        bump
+    )]
+    pub issuer_stats: Account<'info, stats::IssuerStats>,
+    
+    /// Record of the signed payload this issuance consumes; an initialized record is a replay
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = nonce::ConsumedPayload::SPACE,
+        seeds = [nonce::CONSUMED_PAYLOAD_SEED, issuer.key().as_ref(), &nonce::payload_hash(&args.message_data)],
+        bump
+    )]
+    pub consumed_payload: Account<'info, nonce::ConsumedPayload>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for turning an achievement's recipient roster on or off
+#[derive(Accounts)]
+pub struct SetRecipientRosterTracking<'info> {
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = recipient_roster::RecipientRoster::SPACE,
+        seeds = [recipient_roster::RECIPIENT_ROSTER_SEED, achievement.key().as_ref()],
+        bump
+    )]
+    pub recipient_roster: Account<'info, recipient_roster::RecipientRoster>,
+    
+    #[account(has_one = issuer @ ValidationError::UnauthorizedIssuer)]
+    pub achievement: Account<'info, Achievement>,
+    
+    #[account(
+        seeds = [pda::ISSUER_SEED, authority.key().as_ref()],
+        bump = issuer.bump
+    )]
+    pub issuer: Account<'info, Profile>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for attaching a rubric to an achievement
+#[derive(Accounts)]
+pub struct CreateAchievementRubric<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = rubric::Rubric::SPACE,
+        seeds = [rubric::RUBRIC_SEED, achievement.key().as_ref()],
+        bump
+    )]
+    pub rubric: Account<'info, rubric::Rubric>,
+    
+    #[account(has_one = issuer @ ValidationError::UnauthorizedIssuer)]
+    pub achievement: Account<'info, Achievement>,
+    
+    #[account(
+        seeds = [pda::ISSUER_SEED, authority.key().as_ref()],
+        bump = issuer.bump
+    )]
+    pub issuer: Account<'info, Profile>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for updating an achievement's rubric
+#[derive(Accounts)]
+pub struct ManageAchievementRubric<'info> {
+    #[account(
+        mut,
+        seeds = [rubric::RUBRIC_SEED, achievement.key().as_ref()],
+        bump = rubric.bump,
+        has_one = achievement
+    )]
+    pub rubric: Account<'info, rubric::Rubric>,
+    
+    #[account(has_one = issuer @ ValidationError::UnauthorizedIssuer)]
+    pub achievement: Account<'info, Achievement>,
+    
+    #[account(
+        seeds = [pda::ISSUER_SEED, authority.key().as_ref()],
+        bump = issuer.bump
+    )]
+    pub issuer: Account<'info, Profile>,
+    
+    pub authority: Signer<'info>,
+}
+
+/// Context for closing an achievement's rubric
+#[derive(Accounts)]
+pub struct CloseAchievementRubric<'info> {
+    #[account(
+        mut,
+        close = authority,
+        seeds = [rubric::RUBRIC_SEED, achievement.key().as_ref()],
+        bump = rubric.bump,
+        has_one = achievement
+    )]
+    pub rubric: Account<'info, rubric::Rubric>,
+    
+    #[account(has_one = issuer @ ValidationError::UnauthorizedIssuer)]
+    pub achievement: Account<'info, Achievement>,
+    
+    #[account(
+        seeds = [pda::ISSUER_SEED, authority.key().as_ref()],
+        bump = issuer.bump
+    )]
+    pub issuer: Account<'info, Profile>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+/// Context for granting an assessment role
+#[derive(Accounts)]
+#[instruction(member: Pubkey, role: assessment::AssessmentRole)]
+pub struct GrantAssessmentRole<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = assessment::RoleGrant::SPACE,
+        seeds = [assessment::ROLE_SEED, issuer.key().as_ref(), member.as_ref(), &[role.seed()]],
+        bump
+    )]
+    pub role_grant: Account<'info, assessment::RoleGrant>,
+    
+    #[account(
+        seeds = [pda::ISSUER_SEED, authority.key().as_ref()],
+        bump = issuer.bump
+    )]
+    pub issuer: Account<'info, Profile>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for revoking an assessment role
+#[derive(Accounts)]
+pub struct RevokeAssessmentRole<'info> {
+    #[account(
+        mut,
+        close = authority,
+        has_one = issuer @ ValidationError::UnauthorizedAccess
+    )]
+    pub role_grant: Account<'info, assessment::RoleGrant>,
+    
+    #[account(
+        seeds = [pda::ISSUER_SEED, authority.key().as_ref()],
+        bump = issuer.bump
+    )]
+    pub issuer: Account<'info, Profile>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+/// Context for submitting an assessment result
+#[derive(Accounts)]
+#[instruction(recipient: Pubkey)]
+pub struct SubmitResult<'info> {
+    #[account(
+        init,
+        payer = assessor,
+        space = assessment::ResultEntry::SPACE,
+        seeds = [assessment::RESULT_SEED, achievement.key().as_ref(), recipient.as_ref()],
+        bump
+    )]
+    pub result_entry: Account<'info, assessment::ResultEntry>,
+    
+    pub achievement: Account<'info, Achievement>,
+    
+    /// Assessor role granted by the achievement's issuer
+    #[account(
+        seeds = [
+            assessment::ROLE_SEED,
+            achievement.issuer.as_ref(),
+            assessor.key().as_ref(),
+            &[assessment::AssessmentRole::Assessor.seed()]
+        ],
+        bump = role_grant.bump
+    )]
+    pub role_grant: Account<'info, assessment::RoleGrant>,
+    
+    #[account(mut)]
+    pub assessor: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for moderating an assessment result
+#[derive(Accounts)]
+pub struct ApproveResult<'info> {
+    #[account(
+        mut,
+        seeds = [assessment::RESULT_SEED, result_entry.achievement.as_ref(), result_entry.recipient.as_ref()],
+        bump = result_entry.bump
+    )]
+    pub result_entry: Account<'info, assessment::ResultEntry>,
+    
+    /// Moderator role granted by the achievement's issuer
+    #[account(
+        seeds = [
+            assessment::ROLE_SEED,
+            result_entry.issuer.as_ref(),
+            moderator.key().as_ref(),
+            &[assessment::AssessmentRole::Moderator.seed()]
+        ],
+        bump = role_grant.bump
+    )]
+    pub role_grant: Account<'info, assessment::RoleGrant>,
+    
+    pub moderator: Signer<'info>,
+}
+
+/// Context for configuring peer issuance on an achievement
+#[derive(Accounts)]
+pub struct ConfigurePeerIssuance<'info> {
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = peer::PeerIssuanceConfig::SPACE,
+        seeds = [peer::PEER_CONFIG_SEED, achievement.key().as_ref()],
+        bump
+    )]
+    pub peer_config: Account<'info, peer::PeerIssuanceConfig>,
+    
+    #[account(has_one = issuer @ ValidationError::UnauthorizedIssuer)]
+    pub achievement: Account<'info, Achievement>,
+    
+    #[account(
+        seeds = [pda::ISSUER_SEED, authority.key().as_ref()],
+        bump = issuer.bump
+    )]
+    pub issuer: Account<'info, Profile>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for endorsing a recipient of a peer-issued achievement
+#[derive(Accounts)]
+#[instruction(recipient: Pubkey)]
+pub struct EndorsePeer<'info> {
+    #[account(
+        seeds = [peer::PEER_CONFIG_SEED, achievement.key().as_ref()],
+        bump = peer_config.bump,
+        has_one = achievement
+    )]
+    pub peer_config: Account<'info, peer::PeerIssuanceConfig>,
+    
+    pub achievement: Account<'info, Achievement>,
+    
+    #[account(
+        init,
+        payer = endorser,
+        space = peer::PeerEndorsement::SPACE,
+        seeds = [peer::PEER_ENDORSEMENT_SEED, achievement.key().as_ref(), recipient.as_ref(), endorser.key().as_ref()],
+        bump
+    )]
+    pub endorsement: Account<'info, peer::PeerEndorsement>,
+    
+    #[account(
+        init_if_needed,
+        payer = endorser,
+        space = peer::PeerTally::SPACE,
+        seeds = [peer::PEER_TALLY_SEED, achievement.key().as_ref(), recipient.as_ref()],
+        bump
+    )]
+    pub tally: Account<'info, peer::PeerTally>,
+    
+    /// Endorser's own credential for the achievement, when holders-only endorsement is configured
+    #[account(
+        seeds = [pda::CREDENTIAL_SEED, achievement.key().as_ref(), achievement.issuer.as_ref(), endorser.key().as_ref()],
+        bump = endorser_credential.bump
+    )]
+    pub endorser_credential: Option<Account<'info, AchievementCredential>>,
+    
+    #[account(mut)]
+    pub endorser: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for program-driven issuance; `authority` may be a CPI-signed PDA
+#[derive(Accounts)]
+#[instruction(recipient_pubkey: Pubkey, results: Vec<results::ResultInput>)]
+pub struct IssueAchievementCredentialCpi<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = AchievementCredential::issuance_space(refresh::MAX_TIMESTAMP_LEN, &results, None, &[], &[]),
+        seeds = [
+            pda::CREDENTIAL_SEED,
+            achievement.key().as_ref(),
+            issuer.key().as_ref(),
+            recipient_pubkey.as_ref()
+        ],
+        bump
+    )]
+    pub credential: Account<'info, AchievementCredential>,
+    
+    pub achievement: Account<'info, Achievement>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ISSUER_SEED, authority.key().as_ref()],
+        bump = issuer.bump,
+        constraint = issuer.key() == achievement.issuer @ ValidationError::UnauthorizedIssuer
+    )]
+    pub issuer: Account<'info, Profile>,
+    
+    /// Program config PDA; issuance is refused while it is paused
+    /// CHECK: Address is constrained by seeds, contents checked in the handler
+    #[account(seeds = [program_config::PROGRAM_CONFIG_SEED], bump)]
+    pub program_config: UncheckedAccount<'info>,
+    
+    /// Program treasury receiving issuance fees
+    /// CHECK: Address is constrained by seeds; only credited
+    #[account(mut, seeds = [treasury::TREASURY_SEED], bump)]
+    pub treasury: UncheckedAccount<'info>,
+    
+    /// Payer's token account paying the token fee, when the config sets one
+    /// CHECK: Mint checked in the handler, ownership by the token program
+    #[account(mut)]
+    pub fee_token_account: Option<UncheckedAccount<'info>>,
+    
+    /// Treasury-owned token account receiving the token fee
+    /// CHECK: Mint and owner checked in the handler
+    #[account(mut)]
+    pub treasury_token_account: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Checked against the SPL Token program id
+    pub token_program: Option<UncheckedAccount<'info>>,
+    
+    /// Recipient's opt-out PDA; must be empty for issuance to proceed
+    /// CHECK: Address is constrained by seeds, contents checked in the handler
+    #[account(
+        seeds = [opt_out::OPT_OUT_SEED, recipient_pubkey.as_ref()],
+        bump
+    )]
+    pub recipient_opt_out: UncheckedAccount<'info>,
+    
+    /// Moderated assessment result backing this credential, if required
+    #[account(
+        seeds = [assessment::RESULT_SEED, achievement.key().as_ref(), recipient_pubkey.as_ref()],
+        bump = result_entry.bump
+    )]
+    pub result_entry: Option<Account<'info, assessment::ResultEntry>>,
+    
+    /// Grading rubric that achieved levels are checked against, if results use one
+    #[account(
+        seeds = [rubric::RUBRIC_SEED, achievement.key().as_ref()],
+        bump = rubric.bump
+    )]
+    pub rubric: Option<Account<'info, rubric::Rubric>>,
+    
+    /// Issuer's CPI policy; consulted only when invoked through CPI
+    #[account(
+        seeds = [cpi_guard::CPI_POLICY_SEED, issuer.key().as_ref()],
+        bump = cpi_policy.bump
+    )]
+    pub cpi_policy: Option<Account<'info, cpi_guard::CpiPolicy>>,
+    
+    /// Instructions sysvar naming the top-level program of a CPI
+    /// CHECK: Address is constrained to the instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: Option<UncheckedAccount<'info>>,
+    
+    /// Achievement counters, created with the achievement's first credential
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = stats::AchievementStats::SPACE,
+        seeds = [stats::ACHIEVEMENT_STATS_SEED, achievement.key().as_ref()],
+        bump
+    )]
+    pub achievement_stats: Account<'info, stats::AchievementStats>,
+    
+    /// Recipient's credential index, created with their first credential
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = credential_index::CredentialIndex::SPACE,
+        seeds = [credential_index::CREDENTIAL_INDEX_SEED, recipient_pubkey.as_ref()],
+        bump
+    )]
+    pub credential_index: Account<'info, credential_index::CredentialIndex>,
+    
+    /// Index page the credential is appended to, created when the previous page is full
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = credential_index::CredentialIndexPage::SPACE,
+        seeds = [credential_index::CREDENTIAL_INDEX_SEED, recipient_pubkey.as_ref(), &credential_index.next_page().to_le_bytes()],
+        bump
+    )]
+    pub credential_index_page: Account<'info, credential_index::CredentialIndexPage>,
+    
+    /// Achievement's recipient roster, created with its first credential
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = recipient_roster::RecipientRoster::SPACE,
+        seeds = [recipient_roster::RECIPIENT_ROSTER_SEED, achievement.key().as_ref()],
+        bump
+    )]
+    pub recipient_roster: Account<'info, recipient_roster::RecipientRoster>,
+    
+    /// Roster page the holder is appended to, created when the previous page is full
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = recipient_roster::RecipientRosterPage::SPACE,
+        seeds = [recipient_roster::RECIPIENT_ROSTER_SEED, achievement.key().as_ref(), &recipient_roster.next_page().to_le_bytes()],
+        bump
+    )]
+    pub recipient_roster_page: Account<'info, recipient_roster::RecipientRosterPage>,
+    
+    /// Issuer event sequence, created on the issuer's first event
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = stats::IssuerStats::SPACE,
+        seeds = [stats::ISSUER_STATS_SEED, issuer.key().as_ref()],
+        bump
+    )]
+    pub issuer_stats: Account<'info, stats::IssuerStats>,
+    
+    /// Issuer authority; signs through `invoke_signed` when it is a PDA
+    pub authority: Signer<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for issuing a credential once the peer threshold is met
+#[derive(Accounts)]
+#[instruction(recipient_pubkey: Pubkey)]
+pub struct IssuePeerCredential<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = AchievementCredential::issuance_space(refresh::MAX_TIMESTAMP_LEN, &[], None, &[], &[]),
+        seeds = [
+            pda::CREDENTIAL_SEED,
+            achievement.key().as_ref(),
+            issuer.key().as_ref(),
+            recipient_pubkey.as_ref()
+        ],
+        bump
+    )]
+    pub credential: Account<'info, AchievementCredential>,
+    
+    pub achievement: Account<'info, Achievement>,
+    
+    #[account(mut, constraint = issuer.key() == achievement.issuer @ ValidationError::UnauthorizedIssuer)]
+    pub issuer: Account<'info, Profile>,
+    
+    /// Program config PDA; issuance is refused while it is paused
+    /// CHECK: Address is constrained by seeds, contents checked in the handler
+    #[account(seeds = [program_config::PROGRAM_CONFIG_SEED], bump)]
+    pub program_config: UncheckedAccount<'info>,
+    
+    /// Program treasury receiving issuance fees
+    /// CHECK: Address is constrained by seeds; only credited
+    #[account(mut, seeds = [treasury::TREASURY_SEED], bump)]
+    pub treasury: UncheckedAccount<'info>,
+    
+    /// Payer's token account paying the token fee, when the config sets one
+    /// CHECK: Mint checked in the handler, ownership by the token program
+    #[account(mut)]
+    pub fee_token_account: Option<UncheckedAccount<'info>>,
+    
+    /// Treasury-owned token account receiving the token fee
+    /// CHECK: Mint and owner checked in the handler
+    #[account(mut)]
+    pub treasury_token_account: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Checked against the SPL Token program id
+    pub token_program: Option<UncheckedAccount<'info>>,
+    
+    #[account(
+        seeds = [peer::PEER_CONFIG_SEED, achievement.key().as_ref()],
+        bump = peer_config.bump,
+        has_one = achievement
+    )]
+    pub peer_config: Account<'info, peer::PeerIssuanceConfig>,
+    
+    #[account(
+        mut,
+        seeds = [peer::PEER_TALLY_SEED, achievement.key().as_ref(), recipient_pubkey.as_ref()],
+        bump = tally.bump
+    )]
+    pub tally: Account<'info, peer::PeerTally>,
+    
+    /// Recipient's opt-out PDA; must be empty for issuance to proceed
+    /// CHECK: Address is constrained by seeds, contents checked in the handler
+    #[account(
+        seeds = [opt_out::OPT_OUT_SEED, recipient_pubkey.as_ref()],
+        bump
+    )]
+    pub recipient_opt_out: UncheckedAccount<'info>,
+    
+    /// Achievement counters, created with the achievement's first credential
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = stats::AchievementStats::SPACE,
+        seeds = [stats::ACHIEVEMENT_STATS_SEED, achievement.key().as_ref()],
+        bump
+    )]
+    pub achievement_stats: Account<'info, stats::AchievementStats>,
+    
+    /// Recipient's credential index, created with their first credential
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = credential_index::CredentialIndex::SPACE,
+        seeds = [credential_index::CREDENTIAL_INDEX_SEED, recipient_pubkey.as_ref()],
+        bump
+    )]
+    pub credential_index: Account<'info, credential_index::CredentialIndex>,
+    
+    /// Index page the credential is appended to, created when the previous page is full
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = credential_index::CredentialIndexPage::SPACE,
+        seeds = [credential_index::CREDENTIAL_INDEX_SEED, recipient_pubkey.as_ref(), &credential_index.next_page().to_le_bytes()],
+        bump
+    )]
+    pub credential_index_page: Account<'info, credential_index::CredentialIndexPage>,
+    
+    /// Achievement's recipient roster, created with its first credential
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = recipient_roster::RecipientRoster::SPACE,
+        seeds = [recipient_roster::RECIPIENT_ROSTER_SEED, achievement.key().as_ref()],
+        bump
+    )]
+    pub recipient_roster: Account<'info, recipient_roster::RecipientRoster>,
+    
+    /// Roster page the holder is appended to, created when the previous page is full
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = recipient_roster::RecipientRosterPage::SPACE,
+        seeds = [recipient_roster::RECIPIENT_ROSTER_SEED, achievement.key().as_ref(), &recipient_roster.next_page().to_le_bytes()],
+        bump
+    )]
+    pub recipient_roster_page: Account<'info, recipient_roster::RecipientRosterPage>,
+    
+    /// Issuer event sequence, created on the issuer's first event
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = stats::IssuerStats::SPACE,
+        seeds = [stats::ISSUER_STATS_SEED, issuer.key().as_ref()],
+        bump
+    )]
+    pub issuer_stats: Account<'info, stats::IssuerStats>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
 
+/// Context for escrowing a credential behind a claim code
 #[derive(Accounts)]
-#[instruction(name: String)]
-pub struct InitializeIssuer<'info> {
+#[instruction(claim_key: Pubkey, expires_at: i64, results: Vec<results::ResultInput>)]
+pub struct IssueWithClaimCode<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = claim_code::ClaimOffer::space(&results),
+        seeds = [claim_code::CLAIM_OFFER_SEED, issuer.key().as_ref(), claim_key.as_ref()],
+        bump
+    )]
+    pub claim_offer: Account<'info, claim_code::ClaimOffer>,
+    
+    pub achievement: Account<'info, Achievement>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ISSUER_SEED, authority.key().as_ref()],
+        bump = issuer.bump,
+        constraint = issuer.key() == achievement.issuer @ ValidationError::UnauthorizedIssuer
+    )]
+    pub issuer: Account<'info, Profile>,
+    
+    /// Issuer's CPI policy; consulted only when invoked through CPI
+    #[account(
+        seeds = [cpi_guard::CPI_POLICY_SEED, issuer.key().as_ref()],
+        bump = cpi_policy.bump
+    )]
+    pub cpi_policy: Option<Account<'info, cpi_guard::CpiPolicy>>,
+    
+    /// Instructions sysvar naming the top-level program of a CPI
+    /// CHECK: Address is constrained to the instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: Option<UncheckedAccount<'info>>,
+    
+    /// Program config PDA; issuance is refused while it is paused
+    /// CHECK: Address is constrained by seeds, contents checked in the handler
+    #[account(seeds = [program_config::PROGRAM_CONFIG_SEED], bump)]
+    pub program_config: UncheckedAccount<'info>,
+    
+    /// Program treasury receiving issuance fees
+    /// CHECK: Address is constrained by seeds; only credited
+    #[account(mut, seeds = [treasury::TREASURY_SEED], bump)]
+    pub treasury: UncheckedAccount<'info>,
+    
+    /// Authority's token account paying the token fee, when the config sets one
+    /// CHECK: Mint checked in the handler, ownership by the token program
+    #[account(mut)]
+    pub fee_token_account: Option<UncheckedAccount<'info>>,
+    
+    /// Treasury-owned token account receiving the token fee
+    /// CHECK: Mint and owner checked in the handler
+    #[account(mut)]
+    pub treasury_token_account: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Checked against the SPL Token program id
+    pub token_program: Option<UncheckedAccount<'info>>,
+    
+    /// Grading rubric that achieved levels are checked against, if results use one
+    #[account(
+        seeds = [rubric::RUBRIC_SEED, achievement.key().as_ref()],
+        bump = rubric.bump
+    )]
+    pub rubric: Option<Account<'info, rubric::Rubric>>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for claiming an escrowed credential; the new recipient signs and pays
+#[derive(Accounts)]
+#[instruction(claim_signature: [u8; 64], new_recipient_pubkey: Pubkey)]
+pub struct ClaimWithCode<'info> {
+    #[account(
+        mut,
+        close = issuer_authority,
+        has_one = issuer @ ValidationError::UnauthorizedIssuer,
+        has_one = achievement @ ValidationError::UnauthorizedIssuer
+    )]
+    pub claim_offer: Account<'info, claim_code::ClaimOffer>,
+    
+    #[account(
+        init,
+        payer = recipient,
+        space = AchievementCredential::issuance_space(refresh::MAX_TIMESTAMP_LEN, &claim_offer.results, None, &[], &[]),
+        seeds = [
+            pda::CREDENTIAL_SEED,
+            achievement.key().as_ref(),
+            issuer.key().as_ref(),
+            new_recipient_pubkey.as_ref()
+        ],
+        bump
+    )]
+    pub credential: Account<'info, AchievementCredential>,
+    
+    pub achievement: Account<'info, Achievement>,
+    
+    pub issuer: Account<'info, Profile>,
+    
+    /// Issuer authority, refunded the offer's rent
+    /// CHECK: Address is constrained to the issuer's authority
+    #[account(mut, address = issuer.authority)]
+    pub issuer_authority: UncheckedAccount<'info>,
+    
+    /// Program config PDA; issuance is refused while it is paused
+    /// CHECK: Address is constrained by seeds, contents checked in the handler
+    #[account(seeds = [program_config::PROGRAM_CONFIG_SEED], bump)]
+    pub program_config: UncheckedAccount<'info>,
+    
+    /// Recipient's opt-out PDA; must be empty for issuance to proceed
+    /// CHECK: Address is constrained by seeds, contents checked in the handler
+    #[account(
+        seeds = [opt_out::OPT_OUT_SEED, new_recipient_pubkey.as_ref()],
+        bump
+    )]
+    pub recipient_opt_out: UncheckedAccount<'info>,
+    
+    /// Achievement counters, created with the achievement's first credential
+    #[account(
+        init_if_needed,
+        payer = recipient,
+        space = stats::AchievementStats::SPACE,
+        seeds = [stats::ACHIEVEMENT_STATS_SEED, achievement.key().as_ref()],
+        bump
+    )]
+    pub achievement_stats: Account<'info, stats::AchievementStats>,
+    
+    /// Recipient's credential index, created with their first credential
+    #[account(
+        init_if_needed,
+        payer = recipient,
+        space = credential_index::CredentialIndex::SPACE,
+        seeds = [credential_index::CREDENTIAL_INDEX_SEED, new_recipient_pubkey.as_ref()],
+        bump
+    )]
+    pub credential_index: Account<'info, credential_index::CredentialIndex>,
+    
+    /// Index page the credential is appended to, created when the previous page is full
+    #[account(
+        init_if_needed,
+        payer = recipient,
+        space = credential_index::CredentialIndexPage::SPACE,
+        seeds = [credential_index::CREDENTIAL_INDEX_SEED, new_recipient_pubkey.as_ref(), &credential_index.next_page().to_le_bytes()],
+        bump
+    )]
+    pub credential_index_page: Account<'info, credential_index::CredentialIndexPage>,
+    
+    /// Achievement's recipient roster, created with its first credential
+    #[account(
+        init_if_needed,
+        payer = recipient,
+        space = recipient_roster::RecipientRoster::SPACE,
+        seeds = [recipient_roster::RECIPIENT_ROSTER_SEED, achievement.key().as_ref()],
+        bump
+    )]
+    pub recipient_roster: Account<'info, recipient_roster::RecipientRoster>,
+    
+    /// Roster page the holder is appended to, created when the previous page is full
+    #[account(
+        init_if_needed,
+        payer = recipient,
+        space = recipient_roster::RecipientRosterPage::SPACE,
+        seeds = [recipient_roster::RECIPIENT_ROSTER_SEED, achievement.key().as_ref(), &recipient_roster.next_page().to_le_bytes()],
+        bump
+    )]
+    pub recipient_roster_page: Account<'info, recipient_roster::RecipientRosterPage>,
+    
+    /// Issuer event sequence, created on the issuer's first event
+    #[account(
+        init_if_needed,
+        payer = recipient,
+        space = stats::IssuerStats::SPACE,
+        seeds = [stats::ISSUER_STATS_SEED, issuer.key().as_ref()],
+        bump
+    )]
+    pub issuer_stats: Account<'info, stats::IssuerStats>,
+    
+    /// New recipient wallet, which pays for the credential
+    /// The claim key's signature, not this signer, binds the offer to the wallet
+    #[account(mut, address = new_recipient_pubkey)]
+    pub recipient: Signer<'info>,
+    
+    /// Instructions sysvar holding the claim key's Ed25519 instruction
+    /// CHECK: Address is constrained to the instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for withdrawing an unclaimed offer
+#[derive(Accounts)]
+pub struct CancelClaimOffer<'info> {
+    #[account(
+        mut,
+        close = authority,
+        has_one = issuer @ ValidationError::UnauthorizedIssuer
+    )]
+    pub claim_offer: Account<'info, claim_code::ClaimOffer>,
+    
+    #[account(
+        seeds = [pda::ISSUER_SEED, authority.key().as_ref()],
+        bump = issuer.bump
+    )]
+    pub issuer: Account<'info, Profile>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+/// Context for issuing a credential to a team
+#[derive(Accounts)]
+#[instruction(team_id: String, members: Vec<Pubkey>)]
+pub struct IssueTeamCredential<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = team::TeamCredential::space(&team_id, &members),
+        seeds = [team::TEAM_CREDENTIAL_SEED, achievement.key().as_ref(), issuer.key().as_ref(), team_id.as_bytes()],
+        bump
+    )]
+    pub team_credential: Account<'info, team::TeamCredential>,
+    
+    pub achievement: Account<'info, Achievement>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ISSUER_SEED, authority.key().as_ref()],
+        bump = issuer.bump,
+        constraint = issuer.key() == achievement.issuer @ ValidationError::UnauthorizedIssuer
+    )]
+    pub issuer: Account<'info, Profile>,
+    
+    /// Issuer's CPI policy; consulted only when invoked through CPI
+    #[account(
+        seeds = [cpi_guard::CPI_POLICY_SEED, issuer.key().as_ref()],
+        bump = cpi_policy.bump
+    )]
+    pub cpi_policy: Option<Account<'info, cpi_guard::CpiPolicy>>,
+    
+    /// Instructions sysvar naming the top-level program of a CPI
+    /// CHECK: Address is constrained to the instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: Option<UncheckedAccount<'info>>,
+    
+    /// Program config PDA; issuance is refused while it is paused
+    /// CHECK: Address is constrained by seeds, contents checked in the handler
+    #[account(seeds = [program_config::PROGRAM_CONFIG_SEED], bump)]
+    pub program_config: UncheckedAccount<'info>,
+    
+    /// Program treasury receiving issuance fees
+    /// CHECK: Address is constrained by seeds; only credited
+    #[account(mut, seeds = [treasury::TREASURY_SEED], bump)]
+    pub treasury: UncheckedAccount<'info>,
+    
+    /// Authority's token account paying the token fee, when the config sets one
+    /// CHECK: Mint checked in the handler, ownership by the token program
+    #[account(mut)]
+    pub fee_token_account: Option<UncheckedAccount<'info>>,
+    
+    /// Treasury-owned token account receiving the token fee
+    /// CHECK: Mint and owner checked in the handler
+    #[account(mut)]
+    pub treasury_token_account: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Checked against the SPL Token program id
+    pub token_program: Option<UncheckedAccount<'info>>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for revoking a team credential
+#[derive(Accounts)]
+pub struct RevokeTeamCredential<'info> {
+    #[account(mut, has_one = issuer @ ValidationError::UnauthorizedIssuer)]
+    pub team_credential: Account<'info, team::TeamCredential>,
+    
+    #[account(
+        seeds = [pda::ISSUER_SEED, authority.key().as_ref()],
+        bump = issuer.bump
+    )]
+    pub issuer: Account<'info, Profile>,
+    
+    /// Issuer's CPI policy; consulted only when invoked through CPI
+    #[account(
+        seeds = [cpi_guard::CPI_POLICY_SEED, issuer.key().as_ref()],
+        bump = cpi_policy.bump
+    )]
+    pub cpi_policy: Option<Account<'info, cpi_guard::CpiPolicy>>,
+    
+    /// Instructions sysvar naming the top-level program of a CPI
+    /// CHECK: Address is constrained to the instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: Option<UncheckedAccount<'info>>,
+    
+    pub authority: Signer<'info>,
+}
+
+/// Context for the team credential JSON view
+#[derive(Accounts)]
+pub struct GenerateTeamCredentialJson<'info> {
+    #[account(has_one = issuer @ ValidationError::UnauthorizedIssuer)]
+    pub team_credential: Account<'info, team::TeamCredential>,
+    
+    pub issuer: Account<'info, Profile>,
+}
+
+/// Context for accrediting an issuer
+#[derive(Accounts)]
+#[instruction(scope: String)]
+pub struct EndorseIssuer<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = accreditation::AccreditationEndorsement::space(&scope),
+        seeds = [accreditation::ACCREDITATION_SEED, accreditor.key().as_ref(), issuer.key().as_ref()],
+        bump
+    )]
+    pub accreditation: Account<'info, accreditation::AccreditationEndorsement>,
+    
+    /// Accrediting body's own issuer profile
+    #[account(
+        seeds = [pda::ISSUER_SEED, authority.key().as_ref()],
+        bump = accreditor.bump
+    )]
+    pub accreditor: Account<'info, Profile>,
+    
+    /// Issuer profile being accredited
+    pub issuer: Account<'info, Profile>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for withdrawing an accreditation
+#[derive(Accounts)]
+pub struct RevokeAccreditation<'info> {
+    #[account(mut, has_one = accreditor @ ValidationError::UnauthorizedAccess)]
+    pub accreditation: Account<'info, accreditation::AccreditationEndorsement>,
+    
+    #[account(
+        seeds = [pda::ISSUER_SEED, authority.key().as_ref()],
+        bump = accreditor.bump
+    )]
+    pub accreditor: Account<'info, Profile>,
+    
+    pub authority: Signer<'info>,
+}
+
+/// Context for granting a service a scoped authorization
+#[derive(Accounts)]
+#[instruction(service: Pubkey, scopes: Vec<scoped_authorization::Scope>, achievements: Vec<Pubkey>)]
+pub struct GrantScopedAuthorization<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = scoped_authorization::ScopedAuthorization::space(&scopes, &achievements),
+        seeds = [scoped_authorization::SCOPED_AUTHORIZATION_SEED, issuer.key().as_ref(), service.as_ref()],
+        bump
+    )]
+    pub scoped_authorization: Account<'info, scoped_authorization::ScopedAuthorization>,
+    
+    #[account(
+        seeds = [pda::ISSUER_SEED, authority.key().as_ref()],
+        bump = issuer.bump
+    )]
+    pub issuer: Account<'info, Profile>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for withdrawing a scoped authorization
+#[derive(Accounts)]
+pub struct RevokeScopedAuthorization<'info> {
+    #[account(
+        mut,
+        close = authority,
+        has_one = issuer @ ValidationError::UnauthorizedAccess
+    )]
+    pub scoped_authorization: Account<'info, scoped_authorization::ScopedAuthorization>,
+    
+    #[account(
+        seeds = [pda::ISSUER_SEED, authority.key().as_ref()],
+        bump = issuer.bump
+    )]
+    pub issuer: Account<'info, Profile>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+/// Context for issuing a credential as a delegated service
+#[derive(Accounts)]
+#[instruction(recipient_pubkey: Pubkey, results: Vec<results::ResultInput>)]
+pub struct IssueCredentialAsDelegate<'info> {
+    #[account(
+        init,
+        payer = service,
+        space = AchievementCredential::issuance_space(refresh::MAX_TIMESTAMP_LEN, &results, None, &[], &[]),
+        seeds = [
+            pda::CREDENTIAL_SEED,
+            achievement.key().as_ref(),
+            issuer.key().as_ref(),
+            recipient_pubkey.as_ref()
+        ],
+        bump
+    )]
+    pub credential: Account<'info, AchievementCredential>,
+    
+    pub achievement: Account<'info, Achievement>,
+    
+    #[account(
+        mut,
+        constraint = issuer.key() == achievement.issuer @ ValidationError::UnauthorizedIssuer
+    )]
+    pub issuer: Account<'info, Profile>,
+    
+    /// The issuer's grant to the signing service
+    #[account(
+        seeds = [scoped_authorization::SCOPED_AUTHORIZATION_SEED, issuer.key().as_ref(), service.key().as_ref()],
+        bump = scoped_authorization.bump
+    )]
+    pub scoped_authorization: Account<'info, scoped_authorization::ScopedAuthorization>,
+    
+    /// Program config PDA; issuance is refused while it is paused
+    /// CHECK: Address is constrained by seeds, contents checked in the handler
+    #[account(seeds = [program_config::PROGRAM_CONFIG_SEED], bump)]
+    pub program_config: UncheckedAccount<'info>,
+    
+    /// Program treasury receiving issuance fees
+    /// CHECK: Address is constrained by seeds; only credited
+    #[account(mut, seeds = [treasury::TREASURY_SEED], bump)]
+    pub treasury: UncheckedAccount<'info>,
+    
+    /// Service's token account paying the token fee, when the config sets one
+    /// CHECK: Mint checked in the handler, ownership by the token program
+    #[account(mut)]
+    pub fee_token_account: Option<UncheckedAccount<'info>>,
+    
+    /// Treasury-owned token account receiving the token fee
+    /// CHECK: Mint and owner checked in the handler
+    #[account(mut)]
+    pub treasury_token_account: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Checked against the SPL Token program id
+    pub token_program: Option<UncheckedAccount<'info>>,
+    
+    /// Recipient's opt-out PDA; must be empty for issuance to proceed
+    /// CHECK: Address is constrained by seeds, contents checked in the handler
+    #[account(
+        seeds = [opt_out::OPT_OUT_SEED, recipient_pubkey.as_ref()],
+        bump
+    )]
+    pub recipient_opt_out: UncheckedAccount<'info>,
+    
+    /// Moderated assessment result backing this credential, if required
     #[account(
-        init,
-        payer = authority,
-        space = 8 + 4 + 50 + 4 + 50 + 32 + 4 + name.len() + 4 + 100 + 4 + 100 + 1,
-        seeds = [b"issuer", authority.key().as_ref()],
+        seeds = [assessment::RESULT_SEED, achievement.key().as_ref(), recipient_pubkey.as_ref()],
+        bump = result_entry.bump
+    )]
+    pub result_entry: Option<Account<'info, assessment::ResultEntry>>,
+    
+    /// Grading rubric that achieved levels are checked against, if results use one
+    #[account(
+        seeds = [rubric::RUBRIC_SEED, achievement.key().as_ref()],
+        bump = rubric.bump
+    )]
+    pub rubric: Option<Account<'info, rubric::Rubric>>,
+    
+    /// Achievement counters, created with the achievement's first credential
+    #[account(
+        init_if_needed,
+        payer = service,
+        space = stats::AchievementStats::SPACE,
+        seeds = [stats::ACHIEVEMENT_STATS_SEED, achievement.key().as_ref()],
         bump
     )]
-    pub issuer: Account<'info, Profile>,
+    pub achievement_stats: Account<'info, stats::AchievementStats>,
+    
+    /// Recipient's credential index, created with their first credential
+    #[account(
+        init_if_needed,
+        payer = service,
+        space = credential_index::CredentialIndex::SPACE,
+        seeds = [credential_index::CREDENTIAL_INDEX_SEED, recipient_pubkey.as_ref()],
+        bump
+    )]
+    pub credential_index: Account<'info, credential_index::CredentialIndex>,
+    
+    /// Index page the credential is appended to, created when the previous page is full
+    #[account(
+        init_if_needed,
+        payer = service,
+        space = credential_index::CredentialIndexPage::SPACE,
+        seeds = [credential_index::CREDENTIAL_INDEX_SEED, recipient_pubkey.as_ref(), &credential_index.next_page().to_le_bytes()],
+        bump
+    )]
+    pub credential_index_page: Account<'info, credential_index::CredentialIndexPage>,
+    
+    /// Achievement's recipient roster, created with its first credential
+    #[account(
+        init_if_needed,
+        payer = service,
+        space = recipient_roster::RecipientRoster::SPACE,
+        seeds = [recipient_roster::RECIPIENT_ROSTER_SEED, achievement.key().as_ref()],
+        bump
+    )]
+    pub recipient_roster: Account<'info, recipient_roster::RecipientRoster>,
+    
+    /// Roster page the holder is appended to, created when the previous page is full
+    #[account(
+        init_if_needed,
+        payer = service,
+        space = recipient_roster::RecipientRosterPage::SPACE,
+        seeds = [recipient_roster::RECIPIENT_ROSTER_SEED, achievement.key().as_ref(), &recipient_roster.next_page().to_le_bytes()],
+        bump
+    )]
+    pub recipient_roster_page: Account<'info, recipient_roster::RecipientRosterPage>,
+    
+    /// Issuer event sequence, created on the issuer's first event
+    #[account(
+        init_if_needed,
+        payer = service,
+        space = stats::IssuerStats::SPACE,
+        seeds = [stats::ISSUER_STATS_SEED, issuer.key().as_ref()],
+        bump
+    )]
+    pub issuer_stats: Account<'info, stats::IssuerStats>,
     
+    /// Delegated service; signs and pays
     #[account(mut)]
-    pub authority: Signer<'info>,
+    pub service: Signer<'info>,
     
     pub system_program: Program<'info, System>,
 }
 
-
-
+/// Context for revoking a credential as a delegated service
 #[derive(Accounts)]
-#[instruction(achievement_id: String, name: String)]
-pub struct CreateAchievement<'info> {
+pub struct RevokeCredentialAsDelegate<'info> {
     #[account(
-        init,
-        payer = authority,
-        space = 8 + 4 + achievement_id.len() + 4 + 50 + 32 + 4 + name.len() + 4 + 500 + 4 + 200 + 4 + 200 + 4 + 32 + 8 + 1,
-        seeds = [b"achievement", issuer.key().as_ref(), name.as_bytes()],
+        mut,
+        has_one = issuer @ ValidationError::UnauthorizedAccess
+    )]
+    pub credential: Account<'info, AchievementCredential>,
+    
+    pub issuer: Account<'info, Profile>,
+    
+    /// The issuer's grant to the signing service
+    #[account(
+        seeds = [scoped_authorization::SCOPED_AUTHORIZATION_SEED, issuer.key().as_ref(), service.key().as_ref()],
+        bump = scoped_authorization.bump
+    )]
+    pub scoped_authorization: Account<'info, scoped_authorization::ScopedAuthorization>,
+    
+    /// Program config PDA; revocation is refused while it is paused
+    /// CHECK: Address is constrained by seeds, contents checked in the handler
+    #[account(seeds = [program_config::PROGRAM_CONFIG_SEED], bump)]
+    pub program_config: UncheckedAccount<'info>,
+    
+    /// Achievement counters, created here if it predates them
+    #[account(
+        init_if_needed,
+        payer = service,
+        space = stats::AchievementStats::SPACE,
+        seeds = [stats::ACHIEVEMENT_STATS_SEED, credential.credential_subject.achievement.as_ref()],
         bump
     )]
-    pub achievement: Account<'info, Achievement>,
+    pub achievement_stats: Account<'info, stats::AchievementStats>,
     
+    /// Issuer event sequence, created on the issuer's first event
     #[account(
-        seeds = [b"issuer", authority.key().as_ref()],
-        bump = issuer.bump
+        init_if_needed,
+        payer = service,
+        space = stats::IssuerStats::SPACE,
+        seeds = [stats::ISSUER_STATS_SEED, issuer.key().as_ref()],
+        bump
     )]
-    pub issuer: Account<'info, Profile>,
+    pub issuer_stats: Account<'info, stats::IssuerStats>,
     
+    /// Delegated service; signs and pays
     #[account(mut)]
-    pub authority: Signer<'info>,
+    pub service: Signer<'info>,
     
     pub system_program: Program<'info, System>,
 }
 
+/// Context for nominating a recipient
 #[derive(Accounts)]
-#[instruction(recipient_pubkey: Pubkey)]
-pub struct IssueAchievementCredential<'info> {
+#[instruction(nominee: Pubkey)]
+pub struct NominateRecipient<'info> {
     #[account(
         init,
-        payer = authority,
-        space = 8 + 4 + 200 + 4 + 200 + 4 + 100 + 32 + 8 + 8 + 4 + 100 + 4 + 50 + 32 + 4 + 200 + 4 + 200 + 8 + 4 + 50 + 4 + 200 + 4 + 200 + 1 + 8 + 1,
-        seeds = [
-            b"credential", 
-            achievement.key().as_ref(), 
-            issuer.key().as_ref(),
-            recipient_pubkey.as_ref()
-        ],
+        payer = nominator,
+        space = nomination::Nomination::SPACE,
+        seeds = [nomination::NOMINATION_SEED, achievement.key().as_ref(), nominee.as_ref(), nominator.key().as_ref()],
         bump
     )]
-    pub credential: Account<'info, AchievementCredential>,
+    pub nomination: Account<'info, nomination::Nomination>,
     
     pub achievement: Account<'info, Achievement>,
     
+    #[account(mut)]
+    pub nominator: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for declining a nomination
+#[derive(Accounts)]
+pub struct DeclineNomination<'info> {
+    #[account(
+        mut,
+        has_one = issuer @ ValidationError::UnauthorizedIssuer
+    )]
+    pub nomination: Account<'info, nomination::Nomination>,
+    
     #[account(
-        seeds = [b"issuer", authority.key().as_ref()],
-        bump = issuer.bump,
-        constraint = issuer.key() == achievement.issuer @ ErrorCode::UnauthorizedIssuer
+        seeds = [pda::ISSUER_SEED, authority.key().as_ref()],
+        bump = issuer.bump
     )]
     pub issuer: Account<'info, Profile>,
     
-    #[account(mut)]
     pub authority: Signer<'info>,
+}
+
+/// Context for closing a nomination
+#[derive(Accounts)]
+pub struct CloseNomination<'info> {
+    #[account(
+        mut,
+        close = nominator,
+        has_one = nominator @ ValidationError::UnauthorizedAccess
+    )]
+    pub nomination: Account<'info, nomination::Nomination>,
     
-    pub system_program: Program<'info, System>,
+    #[account(mut)]
+    pub nominator: Signer<'info>,
 }
 
+/// Context for registering an issuer verification method
 #[derive(Accounts)]
-#[instruction(recipient_pubkey: Pubkey)]
-pub struct IssueAchievementCredentialSimpleSubject<'info> {
+#[instruction(key_index: u32)]
+pub struct AddIssuerKey<'info> {
     #[account(
         init,
         payer = authority,
-        space = 8 + 4 + 200 + 4 + 200 + 4 + 100 + 32 + 8 + 8 + 4 + 100 + 4 + 50 + 32 + 4 + 200 + 4 + 200 + 8 + 4 + 50 + 4 + 200 + 4 + 200 + 1 + 8 + 1,
-        seeds = [
-            b"credential", 
-            achievement.key().as_ref(), 
-            issuer.key().as_ref(),
-            recipient_pubkey.as_ref()
-        ],
+        space = issuer_keys::IssuerKey::SPACE,
+        seeds = [issuer_keys::ISSUER_KEY_SEED, issuer.key().as_ref(), &key_index.to_le_bytes()],
         bump
     )]
-    pub credential: Account<'info, AchievementCredential>,
-    
-    pub achievement: Account<'info, Achievement>,
+    pub issuer_key: Account<'info, issuer_keys::IssuerKey>,
     
     #[account(
-        seeds = [b"issuer", authority.key().as_ref()],
-        bump = issuer.bump,
-        constraint = issuer.key() == achievement.issuer @ ErrorCode::UnauthorizedIssuer
+        seeds = [pda::ISSUER_SEED, authority.key().as_ref()],
+        bump = issuer.bump
     )]
     pub issuer: Account<'info, Profile>,
     
@@ -1591,177 +8188,292 @@ pub struct IssueAchievementCredentialSimpleSubject<'info> {
     pub system_program: Program<'info, System>,
 }
 
+/// Context for retiring an issuer verification method
 #[derive(Accounts)]
-pub struct RevokeCredential<'info> {
+pub struct RetireIssuerKey<'info> {
     #[account(
         mut,
-        constraint = !credential.is_revoked @ ErrorCode::AlreadyRevoked,
-        constraint = issuer.key() == credential.issuer @ ErrorCode::UnauthorizedIssuer
+        has_one = issuer @ ValidationError::UnauthorizedAccess
     )]
-    pub credential: Account<'info, AchievementCredential>,
+    pub issuer_key: Account<'info, issuer_keys::IssuerKey>,
     
     #[account(
-        seeds = [b"issuer", authority.key().as_ref()],
+        seeds = [pda::ISSUER_SEED, authority.key().as_ref()],
         bump = issuer.bump
     )]
     pub issuer: Account<'info, Profile>,
     
-    #[account(mut)]
     pub authority: Signer<'info>,
 }
 
-/// Context for initializing a revocation list
+/// Context for registering a recipient opt-out
 #[derive(Accounts)]
-#[instruction(list_id: String)]
-pub struct InitializeRevocationList<'info> {
+pub struct RegisterOptOut<'info> {
     #[account(
         init,
-        payer = authority,
-        space = 8 + 32 + 64 + 4 + 4 + 1024 + 128 + 64 + 64, // Account discriminator + basic fields + variable data
-        seeds = [b"revocation_list", authority.key().as_ref(), list_id.as_bytes()],
+        payer = recipient,
+        space = opt_out::OptOut::SPACE,
+        seeds = [opt_out::OPT_OUT_SEED, recipient.key().as_ref()],
         bump
     )]
-    pub revocation_list: Account<'info, credential_status::RevocationList>,
+    pub opt_out: Account<'info, opt_out::OptOut>,
     
     #[account(mut)]
-    pub authority: Signer<'info>,
+    pub recipient: Signer<'info>,
     
     pub system_program: Program<'info, System>,
 }
 
-/// Context for updating credential status (revoke/reactivate)
+/// Context for removing a recipient opt-out
 #[derive(Accounts)]
-pub struct UpdateCredentialStatus<'info> {
+pub struct RemoveOptOut<'info> {
     #[account(
         mut,
-        has_one = authority @ ValidationError::UnauthorizedAccess
+        close = recipient,
+        seeds = [opt_out::OPT_OUT_SEED, recipient.key().as_ref()],
+        bump = opt_out.bump,
+        has_one = recipient @ ValidationError::UnauthorizedAccess
     )]
-    pub revocation_list: Account<'info, credential_status::RevocationList>,
+    pub opt_out: Account<'info, opt_out::OptOut>,
     
-    pub authority: Signer<'info>,
-}
-
-#[derive(Accounts)]
-pub struct VerifyCredential<'info> {
-    pub credential: Account<'info, AchievementCredential>,
-}
-
-#[derive(Accounts)]
-pub struct ValidateCredential<'info> {
-    pub credential: Account<'info, AchievementCredential>,
-}
-
-#[derive(Accounts)]
-pub struct ValidateAchievement<'info> {
-    pub achievement: Account<'info, Achievement>,
-}
-
-#[derive(Accounts)]
-pub struct ValidateProfile<'info> {
-    pub profile: Account<'info, Profile>,
+    #[account(mut)]
+    pub recipient: Signer<'info>,
 }
 
+/// Context for anchoring an externally issued credential
 #[derive(Accounts)]
-pub struct CreateLinkedDataProof<'info> {
-    pub signer: Signer<'info>,
+#[instruction(credential_hash: [u8; 32], issuer_did: String)]
+pub struct AnchorExternalCredential<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = anchoring::AnchorRecord::space(&issuer_did),
+        seeds = [anchoring::ANCHOR_RECORD_SEED, issuer.key().as_ref(), credential_hash.as_ref()],
+        bump
+    )]
+    pub anchor_record: Account<'info, anchoring::AnchorRecord>,
+    
+    #[account(
+        seeds = [pda::ISSUER_SEED, authority.key().as_ref()],
+        bump = issuer.bump
+    )]
+    pub issuer: Account<'info, Profile>,
+    
+    /// Revocation list the status pointer refers to, owned by the same authority
+    #[account(has_one = authority @ ValidationError::UnauthorizedAccess)]
+    pub revocation_list: Option<Account<'info, credential_status::RevocationList>>,
+    
+    /// Issuer event sequence, created on the issuer's first event
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = stats::IssuerStats::SPACE,
+        seeds = [stats::ISSUER_STATS_SEED, issuer.key().as_ref()],
+        bump
+    )]
+    pub issuer_stats: Account<'info, stats::IssuerStats>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
 }
 
+/// Context for verifying a credential against its anchor
 #[derive(Accounts)]
-pub struct VerifyLinkedDataProof {
-    // No accounts needed for verification - purely computational
+pub struct VerifyAnchoredCredential<'info> {
+    pub anchor_record: Account<'info, anchoring::AnchorRecord>,
+    
+    /// Revocation list named by the anchor's status pointer, if it has one
+    pub revocation_list: Option<Account<'info, credential_status::RevocationList>>,
 }
 
+/// Context for anchoring a merkle root over external credentials
 #[derive(Accounts)]
-pub struct GenerateCredential<'info> {
+#[instruction(merkle_root: [u8; 32], leaf_count: u32, issuer_did: String)]
+pub struct AnchorCredentialBatch<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = anchoring::BatchAnchor::space(&issuer_did),
+        seeds = [anchoring::BATCH_ANCHOR_SEED, issuer.key().as_ref(), merkle_root.as_ref()],
+        bump
+    )]
+    pub batch_anchor: Account<'info, anchoring::BatchAnchor>,
+    
+    #[account(
+        seeds = [pda::ISSUER_SEED, authority.key().as_ref()],
+        bump = issuer.bump
+    )]
     pub issuer: Account<'info, Profile>,
-    pub achievement: Account<'info, Achievement>,
-    /// CHECK: This is just used for recipient public key
-    pub recipient: UncheckedAccount<'info>,
+    
+    /// Issuer event sequence, created on the issuer's first event
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = stats::IssuerStats::SPACE,
+        seeds = [stats::ISSUER_STATS_SEED, issuer.key().as_ref()],
+        bump
+    )]
+    pub issuer_stats: Account<'info, stats::IssuerStats>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
 }
 
+/// Context for verifying a credential against a batch anchor
 #[derive(Accounts)]
-pub struct VerifyCredentialFormat {
-    // No accounts needed for verification - purely computational
+pub struct VerifyBatchAnchoredCredential<'info> {
+    pub batch_anchor: Account<'info, anchoring::BatchAnchor>,
 }
 
+/// Account context for batch credential issuance
 #[derive(Accounts)]
-pub struct ResolveDid {
-    // No accounts needed for DID resolution - purely computational
+pub struct BatchIssueCredentials<'info> {
+    /// The issuer profile account
+    #[account(mut)]
+    pub issuer: Account<'info, Profile>,
+    
+    /// Program config PDA; issuance is refused while it is paused
+    /// CHECK: Address is constrained by seeds, contents checked in the handler
+    #[account(seeds = [program_config::PROGRAM_CONFIG_SEED], bump)]
+    pub program_config: UncheckedAccount<'info>,
+    
+    /// Program-wide batch limits; the defaults apply when omitted
+    #[account(seeds = [batch::BATCH_CONFIG_SEED], bump = batch_config.bump)]
+    pub batch_config: Option<Account<'info, batch::BatchConfig>>,
+    
+    /// Instructions sysvar holding the authority's Ed25519 instruction over the batch message
+    /// CHECK: Address is constrained to the instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    
+    /// The authority that can issue credentials (must be the issuer's authority)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    /// System program for account creation
+    pub system_program: Program<'info, System>,
 }
 
-/// Context for direct credential revocation
+/// Context for creating the batch config; the signer must be the upgrade authority
 #[derive(Accounts)]
-pub struct RevokeCredentialDirect<'info> {
+pub struct InitializeBatchConfig<'info> {
     #[account(
-        mut,
-        has_one = issuer @ ValidationError::UnauthorizedAccess
+        init,
+        payer = authority,
+        space = batch::BatchConfig::SPACE,
+        seeds = [batch::BATCH_CONFIG_SEED],
+        bump
     )]
-    pub credential: Account<'info, AchievementCredential>,
+    pub batch_config: Account<'info, batch::BatchConfig>,
     
-    #[account(has_one = authority @ ValidationError::UnauthorizedAccess)]
-    pub issuer: Account<'info, Profile>,
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()) @ ValidationError::UnauthorizedAccess)]
+    pub program: Program<'info, crate::program::OpenBadges>,
     
+    #[account(constraint = program_data.upgrade_authority_address == Some(authority.key()) @ ValidationError::UnauthorizedAccess)]
+    pub program_data: Account<'info, ProgramData>,
+    
+    #[account(mut)]
     pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
 }
 
+/// Context for changing the batch limits
 #[derive(Accounts)]
-pub struct GenerateCredentialJson<'info> {
+pub struct UpdateBatchConfig<'info> {
     #[account(
-        seeds = [b"issuer", authority.key().as_ref()],
-        bump
+        mut,
+        seeds = [batch::BATCH_CONFIG_SEED],
+        bump = batch_config.bump,
+        has_one = admin @ ValidationError::UnauthorizedAccess
     )]
-    pub issuer: Account<'info, Profile>,
+    pub batch_config: Account<'info, batch::BatchConfig>,
     
-    pub authority: Signer<'info>,
+    pub admin: Signer<'info>,
 }
 
+/// Context for creating the program config; the signer must be the upgrade authority
 #[derive(Accounts)]
-pub struct InitializeIssuerWithDid<'info> {
-    /// The issuer profile account to initialize
+pub struct InitializeProgramConfig<'info> {
     #[account(
         init,
-        payer = payer,
-        space = 8 + 32 + 200 + 100 + 100 + 50 + 4 + 1,
-        seeds = [b"issuer", authority.key().as_ref()],
+        payer = authority,
+        space = program_config::ProgramConfig::SPACE,
+        seeds = [program_config::PROGRAM_CONFIG_SEED],
         bump
     )]
-    pub issuer: Account<'info, Profile>,
+    pub program_config: Account<'info, program_config::ProgramConfig>,
     
-    /// Authority (signer) for the issuer
-    pub authority: Signer<'info>,
+    #[account(
+        init,
+        payer = authority,
+        space = treasury::Treasury::SPACE,
+        seeds = [treasury::TREASURY_SEED],
+        bump
+    )]
+    pub treasury: Account<'info, treasury::Treasury>,
     
-    /// Account paying for the transactions
-    #[account(mut)]
-    pub payer: Signer<'info>,
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()) @ ValidationError::UnauthorizedAccess)]
+    pub program: Program<'info, crate::program::OpenBadges>,
     
-    /// The DID account to initialize
-    /// CHECK: This account is validated by the sol-did program during CPI call
-    #[account(mut)]
-    pub did_data: AccountInfo<'info>,
+    #[account(constraint = program_data.upgrade_authority_address == Some(authority.key()) @ ValidationError::UnauthorizedAccess)]
+    pub program_data: Account<'info, ProgramData>,
     
-    /// The sol-did program
-    pub sol_did_program: Program<'info, sol_did_cpi::program::SolDid>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
     
-    /// System program
     pub system_program: Program<'info, System>,
 }
 
-/// Account context for batch credential issuance
+/// Context for changing the program config
 #[derive(Accounts)]
-pub struct BatchIssueCredentials<'info> {
-    /// The issuer profile account
+pub struct SetConfig<'info> {
+    #[account(
+        mut,
+        seeds = [program_config::PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+        has_one = admin @ ValidationError::UnauthorizedAccess
+    )]
+    pub program_config: Account<'info, program_config::ProgramConfig>,
+    
+    pub admin: Signer<'info>,
+}
+
+/// Context for withdrawing collected fees
+#[derive(Accounts)]
+pub struct WithdrawTreasury<'info> {
+    #[account(
+        seeds = [program_config::PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+        has_one = admin @ ValidationError::UnauthorizedAccess
+    )]
+    pub program_config: Account<'info, program_config::ProgramConfig>,
+    
+    #[account(mut, seeds = [treasury::TREASURY_SEED], bump = treasury.bump)]
+    pub treasury: Account<'info, treasury::Treasury>,
+    
+    /// Wallet for lamports, or token account of the same mint for tokens
+    /// CHECK: Only credited; the token program checks the mint of a token destination
     #[account(mut)]
-    pub issuer: Account<'info, Profile>,
+    pub destination: UncheckedAccount<'info>,
     
-    /// The authority that can issue credentials (must be the issuer's authority)
+    /// Treasury-owned token account to withdraw tokens from
+    /// CHECK: Owner and balance checked in the handler
     #[account(mut)]
-    pub authority: Signer<'info>,
+    pub treasury_token_account: Option<UncheckedAccount<'info>>,
     
-    /// System program for account creation
-    pub system_program: Program<'info, System>,
+    /// CHECK: Checked against the SPL Token program id
+    pub token_program: Option<UncheckedAccount<'info>>,
+    
+    pub admin: Signer<'info>,
 }
 
 /// Batch issuance request for a single recipient
+/// `notes` must be empty until batch issuance creates credential accounts
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct BatchIssuanceRequest {
     pub recipient_pubkey: Pubkey,
@@ -1769,15 +8481,3 @@ pub struct BatchIssuanceRequest {
     pub notes: Option<Vec<String>>,
 }
 
-// Error codes
-#[error_code]
-pub enum ErrorCode {
-    #[msg("Unauthorized issuer")]
-    UnauthorizedIssuer,
-    #[msg("Credential already revoked")]
-    AlreadyRevoked,
-    #[msg("Invalid revocation list capacity")]
-    InvalidCapacity,
-    #[msg("Unauthorized access to revocation list")]
-    UnauthorizedAccess,
-}
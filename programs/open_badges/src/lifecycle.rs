@@ -0,0 +1,148 @@
+//! Credential Lifecycle
+//!
+//! Every credential carries a `CredentialState` next to the legacy
+//! `is_revoked`/`is_expired` flags. Issuers can suspend a credential while
+//! they investigate and reinstate it afterwards; revocation and expiry are
+//! terminal. Each transition is appended to the credential's status history,
+//! which keeps the most recent `MAX_STATUS_HISTORY` entries on the account;
+//! the emitted events carry the complete log for indexers.
+
+use anchor_lang::prelude::*;
+use crate::common::errors::ValidationError;
+use crate::AchievementCredential;
+
+/// Transitions kept on the credential account
+pub const MAX_STATUS_HISTORY: usize = 4;
+
+/// Serialized size of one `StatusHistoryEntry`
+pub const ENTRY_SPACE: usize = 1 + 4 + 32 + 32 + 1 + 4 + crate::MAX_REVOCATION_REASON_LEN;
+
+/// Serialized size of the state and a full history
+pub const SPACE: usize = 1 + 4 + MAX_STATUS_HISTORY * ENTRY_SPACE;
+
+/// Lifecycle state of a credential
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CredentialState {
+    /// Issued and in force
+    #[default]
+    Active,
+    /// Temporarily withdrawn by the issuer; may be reinstated
+    Suspended,
+    /// Permanently withdrawn by the issuer
+    Revoked,
+    /// Past its `validUntil` date, marked by `expire_credential`
+    Expired,
+}
+
+impl CredentialState {
+    /// Whether a credential may move from this state to `to`
+    pub fn can_transition_to(self, to: CredentialState) -> bool {
+        use CredentialState::*;
+        matches!(
+            (self, to),
+            (Active, Suspended) | (Suspended, Active) | (Active | Suspended, Revoked) | (Active | Suspended, Expired)
+        )
+    }
+}
+
+/// One recorded state transition
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct StatusHistoryEntry {
+    /// State entered
+    pub state: CredentialState,
+    /// When it was entered (ISO 8601 string)
+    pub timestamp: String,
+    /// Signer who caused the transition
+    pub actor: Pubkey,
+    /// Reason given, if any
+    pub reason: Option<String>,
+}
+
+/// Move `credential` to `to` and record the transition
+pub fn transition(
+    credential: &mut AchievementCredential,
+    to: CredentialState,
+    timestamp: String,
+    actor: Pubkey,
+    reason: Option<String>,
+) -> Result<()> {
+    if !credential.status.can_transition_to(to) {
+        msg!("❌ Credential {} cannot move from {:?} to {:?}", credential.id, credential.status, to);
+        return Err(error!(ValidationError::InvalidStatusTransition));
+    }
+    if reason.as_ref().is_some_and(|r| r.len() > crate::MAX_REVOCATION_REASON_LEN) {
+        return Err(error!(ValidationError::RevocationReasonTooLong));
+    }
+
+    credential.status = to;
+    if credential.status_history.len() == MAX_STATUS_HISTORY {
+        credential.status_history.remove(0);
+    }
+    credential.status_history.push(StatusHistoryEntry { state: to, timestamp, actor, reason });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::fixture_credential;
+
+    fn credential() -> AchievementCredential {
+        fixture_credential(&Pubkey::new_unique(), &Pubkey::new_unique(), &Pubkey::new_unique(), &Pubkey::new_unique())
+    }
+
+    fn move_to(credential: &mut AchievementCredential, state: CredentialState) -> Result<()> {
+        transition(credential, state, "2025-01-01T00:00:00Z".to_string(), Pubkey::new_unique(), None)
+    }
+
+    #[test]
+    fn test_suspension_is_reversible_revocation_is_not() {
+        let mut credential = credential();
+        assert_eq!(credential.status, CredentialState::Active);
+        assert!(move_to(&mut credential, CredentialState::Active).is_err());
+        move_to(&mut credential, CredentialState::Suspended).unwrap();
+        assert!(move_to(&mut credential, CredentialState::Suspended).is_err());
+        move_to(&mut credential, CredentialState::Active).unwrap();
+        move_to(&mut credential, CredentialState::Suspended).unwrap();
+        move_to(&mut credential, CredentialState::Revoked).unwrap();
+        for state in [CredentialState::Active, CredentialState::Suspended, CredentialState::Expired] {
+            assert!(move_to(&mut credential, state).is_err());
+        }
+        assert!(!CredentialState::Expired.can_transition_to(CredentialState::Active));
+    }
+
+    #[test]
+    fn test_history_keeps_the_latest_entries() {
+        let mut credential = credential();
+        for _ in 0..MAX_STATUS_HISTORY {
+            move_to(&mut credential, CredentialState::Suspended).unwrap();
+            move_to(&mut credential, CredentialState::Active).unwrap();
+        }
+        transition(
+            &mut credential,
+            CredentialState::Revoked,
+            "2025-02-01T00:00:00Z".to_string(),
+            Pubkey::new_unique(),
+            Some("Fraud".to_string()),
+        )
+        .unwrap();
+
+        assert_eq!(credential.status_history.len(), MAX_STATUS_HISTORY);
+        let last = credential.status_history.last().unwrap();
+        assert_eq!(last.state, CredentialState::Revoked);
+        assert_eq!(last.reason.as_deref(), Some("Fraud"));
+        assert_eq!(credential.status_history[0].state, CredentialState::Active);
+    }
+
+    #[test]
+    fn test_full_history_fits_the_reserved_space() {
+        let entry = StatusHistoryEntry {
+            state: CredentialState::Suspended,
+            timestamp: "2025-01-01T00:00:00Z".to_string(),
+            actor: Pubkey::new_unique(),
+            reason: Some("x".repeat(crate::MAX_REVOCATION_REASON_LEN)),
+        };
+        let full = (CredentialState::Suspended, vec![entry; MAX_STATUS_HISTORY]);
+        assert!(full.try_to_vec().unwrap().len() <= SPACE);
+    }
+}
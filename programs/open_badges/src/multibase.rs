@@ -0,0 +1,86 @@
+//! Multibase Encoding
+//!
+//! Data Integrity proof values, Multikey public keys and did:key
+//! identifiers are multibase strings: a one-character base prefix followed
+//! by the encoded bytes. Everything this program writes uses base58btc,
+//! prefix `z`, with the Bitcoin alphabet. Every encoder and decoder of
+//! multibase values goes through here, so no path can pair the `z` prefix
+//! with another encoding.
+//!
+//! Reference: https://www.w3.org/TR/controller-document/#multibase-0
+
+use anchor_lang::prelude::*;
+
+/// Multibase prefix of base58btc
+pub const BASE58BTC_PREFIX: char = 'z';
+
+/// Multicodec prefix of an Ed25519 public key (`ed25519-pub`, 0xed as a varint)
+pub const ED25519_PUB_PREFIX: [u8; 2] = [0xed, 0x01];
+
+/// Encode bytes as a base58btc multibase string
+pub fn encode(bytes: &[u8]) -> String {
+    format!("{}{}", BASE58BTC_PREFIX, bs58::encode(bytes).into_string())
+}
+
+/// Decode a base58btc multibase string; `None` for another base or invalid base58
+pub fn decode(value: &str) -> Option<Vec<u8>> {
+    let encoded = value.strip_prefix(BASE58BTC_PREFIX)?;
+    bs58::decode(encoded).into_vec().ok()
+}
+
+/// Decode a multibase Ed25519 signature, which must be 64 bytes
+pub fn decode_signature(value: &str) -> Option<[u8; 64]> {
+    decode(value).and_then(|bytes| bytes.try_into().ok())
+}
+
+/// Decode a multibase Ed25519 public key, raw or as a Multikey with its multicodec prefix
+pub fn decode_ed25519_key(value: &str) -> Option<[u8; 32]> {
+    let bytes = decode(value)?;
+    let key = bytes.strip_prefix(&ED25519_PUB_PREFIX[..]).unwrap_or(&bytes);
+    key.try_into().ok()
+}
+
+/// Multikey encoding of an Ed25519 public key: multicodec prefix, then base58btc
+pub fn encode_ed25519_multikey(key: &Pubkey) -> String {
+    encode(&[&ED25519_PUB_PREFIX[..], key.as_ref()].concat())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Test vectors of the Multibase specification (draft-multiformats-multibase, Appendix D)
+    const VECTORS: [(&[u8], &str); 4] = [
+        (b"Hello World!", "z2NEpo7TZRRrLZSi2U"),
+        (b"yes mani !", "z7paNL19xttacUY"),
+        (b"\x00yes mani !", "z17paNL19xttacUY"),
+        (b"\x00\x00yes mani !", "z117paNL19xttacUY"),
+    ];
+
+    #[test]
+    fn test_multibase_vectors() {
+        for (bytes, encoded) in VECTORS {
+            assert_eq!(encode(bytes), encoded);
+            assert_eq!(decode(encoded).as_deref(), Some(bytes));
+        }
+        // Another base, base58 without a prefix, and characters outside the Bitcoin alphabet
+        assert_eq!(decode("f48656c6c6f"), None);
+        assert_eq!(decode("2NEpo7TZRRrLZSi2U"), None);
+        assert_eq!(decode("z0OIl"), None);
+    }
+
+    #[test]
+    fn test_ed25519_multikeys() {
+        // Multikey of the vc-di-eddsa test vectors (Appendix B.1)
+        let multikey = "z6MkrJVnaZkeFzdQyMZu1cgjg7k1pZZ6pvBQ7XJPt4swbTQ2";
+        let key = Pubkey::new_from_array(decode_ed25519_key(multikey).unwrap());
+        assert_eq!(decode(multikey).unwrap()[..2], ED25519_PUB_PREFIX);
+        assert_eq!(encode_ed25519_multikey(&key), multikey);
+        assert_eq!(decode_ed25519_key(&encode(key.as_ref())), Some(key.to_bytes()));
+
+        let signature = [7u8; 64];
+        assert_eq!(decode_signature(&encode(&signature)), Some(signature));
+        assert_eq!(decode_signature(&encode(&[7u8; 32])), None);
+        assert_eq!(decode_ed25519_key(&encode(&[7u8; 31])), None);
+    }
+}
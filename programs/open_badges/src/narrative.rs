@@ -0,0 +1,68 @@
+//! Credential Narrative and Notes
+//!
+//! Issuers can say why a particular recipient earned a badge. The narrative
+//! is recipient-specific text published as `credentialSubject.narrative`;
+//! notes are short issuer remarks kept on the credential account only and
+//! never presented to verifiers. Both are bounded so issuance can size the
+//! account from its own instruction data.
+
+use anchor_lang::prelude::*;
+use crate::common::errors::ValidationError;
+
+/// Maximum length of a credential narrative
+pub const MAX_NARRATIVE_LEN: usize = 500;
+
+/// Maximum notes on one credential
+pub const MAX_NOTES: usize = 4;
+
+/// Maximum length of one note
+pub const MAX_NOTE_LEN: usize = 200;
+
+/// Serialized size of an absent narrative and an empty notes list
+pub const EMPTY_SPACE: usize = 1 + 4;
+
+/// Check the narrative and notes against their bounds
+pub fn validate(narrative: Option<&str>, notes: &[String]) -> Result<()> {
+    if let Some(narrative) = narrative {
+        require!(!narrative.trim().is_empty(), ValidationError::MissingRequiredField);
+        require!(narrative.len() <= MAX_NARRATIVE_LEN, ValidationError::NarrativeTooLong);
+    }
+    require!(notes.len() <= MAX_NOTES, ValidationError::TooManyItems);
+    for note in notes {
+        require!(!note.trim().is_empty(), ValidationError::MissingRequiredField);
+        require!(note.len() <= MAX_NOTE_LEN, ValidationError::FieldTooLong);
+    }
+    Ok(())
+}
+
+/// Serialized size of `narrative` and `notes`
+pub fn space(narrative: Option<&str>, notes: &[String]) -> usize {
+    1 + narrative.map_or(0, |narrative| 4 + narrative.len())
+        + 4 + notes.iter().map(|note| 4 + note.len()).sum::<usize>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bounds_are_enforced() {
+        let notes = vec!["Reviewed by the committee".to_string()];
+        assert!(validate(Some("Led the capstone project"), &notes).is_ok());
+        assert!(validate(None, &[]).is_ok());
+        assert!(validate(Some(" "), &[]).is_err());
+        assert!(validate(Some(&"n".repeat(MAX_NARRATIVE_LEN + 1)), &[]).is_err());
+        assert!(validate(None, &vec!["note".to_string(); MAX_NOTES + 1]).is_err());
+        assert!(validate(None, &["n".repeat(MAX_NOTE_LEN + 1)]).is_err());
+        assert!(validate(None, &[String::new()]).is_err());
+    }
+
+    #[test]
+    fn test_space_matches_serialized_fields() {
+        let narrative = Some("Led the capstone project".to_string());
+        let notes = vec!["first".to_string(), "second".to_string()];
+        let serialized = narrative.try_to_vec().unwrap().len() + notes.try_to_vec().unwrap().len();
+        assert_eq!(space(narrative.as_deref(), &notes), serialized);
+        assert_eq!(space(None, &[]), EMPTY_SPACE);
+    }
+}
@@ -0,0 +1,114 @@
+//! Credential Nominations
+//!
+//! Any wallet can nominate a recipient for an achievement, pointing at
+//! evidence of the accomplishment. The issuer either declines the nomination
+//! or approves it by issuing the credential with the nomination attached,
+//! which links the two on-chain. The nominator closes the nomination PDA to
+//! reclaim its rent once it is no longer needed.
+
+use anchor_lang::prelude::*;
+use crate::common::errors::ValidationError;
+
+/// Seed prefix for nomination PDAs
+pub const NOMINATION_SEED: &[u8] = b"nomination";
+
+/// Maximum length of the evidence URL
+pub const MAX_EVIDENCE_URL_LEN: usize = 200;
+
+/// Maximum length of the nomination narrative
+pub const MAX_NOMINATION_NARRATIVE_LEN: usize = 300;
+
+/// Review state of a nomination
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NominationStatus {
+    /// Waiting for the issuer
+    Pending,
+    /// Approved; the credential has been issued
+    Issued,
+    /// Declined by the issuer
+    Declined,
+}
+
+/// Proposal that a recipient be awarded an achievement
+#[account]
+pub struct Nomination {
+    /// Achievement the recipient is nominated for
+    pub achievement: Pubkey,
+    /// Issuer profile that reviews the nomination
+    pub issuer: Pubkey,
+    /// Wallet proposed as recipient
+    pub nominee: Pubkey,
+    /// Wallet that made the nomination
+    pub nominator: Pubkey,
+    /// Link to evidence supporting the nomination
+    pub evidence_url: String,
+    /// Why the nominee deserves the achievement
+    pub narrative: Option<String>,
+    /// Review state
+    pub status: NominationStatus,
+    /// Credential issued from this nomination
+    pub credential: Option<Pubkey>,
+    /// Nomination timestamp (ISO 8601 string)
+    pub nominated_at: String,
+    /// Review timestamp (ISO 8601 string, optional)
+    pub reviewed_at: Option<String>,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl Nomination {
+    /// Space for a nomination at maximum size
+    pub const SPACE: usize = 8
+        + 32 * 4
+        + 4 + MAX_EVIDENCE_URL_LEN
+        + 1 + 4 + MAX_NOMINATION_NARRATIVE_LEN
+        + 1
+        + 1 + 32
+        + 4 + 40
+        + 1 + 4 + 40
+        + 1;
+
+    /// Fail unless the issuer has yet to review this nomination
+    pub fn ensure_pending(&self) -> Result<()> {
+        if self.status != NominationStatus::Pending {
+            return Err(error!(ValidationError::NominationNotPending));
+        }
+        Ok(())
+    }
+}
+
+/// Check the contents of a new nomination
+pub fn validate_nomination(evidence_url: &str, narrative: &Option<String>) -> Result<()> {
+    if evidence_url.is_empty() {
+        return Err(error!(ValidationError::MissingRequiredField));
+    }
+    if evidence_url.len() > MAX_EVIDENCE_URL_LEN {
+        return Err(error!(ValidationError::UrlTooLong));
+    }
+    if !evidence_url.starts_with("https://") && !evidence_url.starts_with("ipfs://") {
+        return Err(error!(ValidationError::InvalidUrl));
+    }
+    if narrative.as_ref().is_some_and(|n| n.len() > MAX_NOMINATION_NARRATIVE_LEN) {
+        return Err(error!(ValidationError::NarrativeTooLong));
+    }
+    Ok(())
+}
+
+/// Mark a nomination as issued when the issuer issues its credential
+pub fn accept_nomination(
+    nomination: &mut Nomination,
+    nominee: &Pubkey,
+    credential: &Pubkey,
+    reviewed_at: String,
+) -> Result<()> {
+    nomination.ensure_pending()?;
+    if nomination.nominee != *nominee {
+        return Err(error!(ValidationError::ValidationFailed));
+    }
+
+    nomination.status = NominationStatus::Issued;
+    nomination.credential = Some(*credential);
+    nomination.reviewed_at = Some(reviewed_at);
+    msg!("📨 NOMINATION_ISSUED: {} nominated by {}", nomination.nominee, nomination.nominator);
+    Ok(())
+}
@@ -0,0 +1,90 @@
+//! Signed Payload Replay Protection
+//!
+//! Issuance accepts a credential JSON signed off-chain by the issuer
+//! authority. Each issuance that consumes a signed payload creates a
+//! `ConsumedPayload` PDA derived from the issuer and the payload's SHA-256,
+//! and a payload whose PDA is already initialized is rejected, so one
+//! (message, signature) pair issues at most one credential even if account
+//! seeds would allow it to be submitted again. Each record has a fixed size
+//! paid by the authority and is never closed, since closing it would make
+//! the payload replayable.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use crate::common::errors::ValidationError;
+
+/// Seed for the consumed payload PDA: [CONSUMED_PAYLOAD_SEED, issuer, payload_hash]
+pub const CONSUMED_PAYLOAD_SEED: &[u8] = b"consumed_payload";
+
+/// Record of one signed payload an issuer has consumed
+#[account]
+pub struct ConsumedPayload {
+    /// Issuer profile that consumed the payload
+    pub issuer: Pubkey,
+    /// SHA-256 of the consumed payload
+    pub payload_hash: [u8; 32],
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl ConsumedPayload {
+    /// Discriminator + issuer + payload hash + bump
+    pub const SPACE: usize = 8 + 32 + 32 + 1;
+
+    /// Whether the record was already written by an earlier issuance
+    pub fn is_consumed(&self) -> bool {
+        self.issuer != Pubkey::default()
+    }
+}
+
+/// Hash under which a signed payload is recorded
+pub fn payload_hash(message: &[u8]) -> [u8; 32] {
+    hash(message).to_bytes()
+}
+
+/// Derive the consumed payload PDA for an issuer and signed payload
+pub fn find_consumed_payload_address(issuer: &Pubkey, message: &[u8], program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CONSUMED_PAYLOAD_SEED, issuer.as_ref(), &payload_hash(message)], program_id)
+}
+
+/// Record `message` as consumed in its PDA, rejecting a replay
+pub fn consume(record: &mut ConsumedPayload, issuer: Pubkey, bump: u8, message: &[u8]) -> Result<()> {
+    if record.is_consumed() {
+        msg!("❌ Signed payload was already used for issuance");
+        return Err(error!(ValidationError::PayloadReplayed));
+    }
+    record.issuer = issuer;
+    record.payload_hash = payload_hash(message);
+    record.bump = bump;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_consumed_payloads_are_rejected() {
+        let issuer = Pubkey::new_unique();
+        let mut record = ConsumedPayload { issuer: Pubkey::default(), payload_hash: [0; 32], bump: 0 };
+        consume(&mut record, issuer, 255, b"{\"id\":\"a\"}").unwrap();
+        assert_eq!(record.payload_hash, payload_hash(b"{\"id\":\"a\"}"));
+        assert!(consume(&mut record, issuer, 255, b"{\"id\":\"a\"}").is_err());
+    }
+
+    #[test]
+    fn test_each_payload_has_its_own_record() {
+        let issuer = Pubkey::new_unique();
+        let (first, _) = find_consumed_payload_address(&issuer, b"{\"id\":\"a\"}", &crate::ID);
+        let (second, _) = find_consumed_payload_address(&issuer, b"{\"id\":\"b\"}", &crate::ID);
+        let (other_issuer, _) = find_consumed_payload_address(&Pubkey::new_unique(), b"{\"id\":\"a\"}", &crate::ID);
+        assert_ne!(first, second);
+        assert_ne!(first, other_issuer);
+    }
+
+    #[test]
+    fn test_space_matches_serialized_record() {
+        let record = ConsumedPayload { issuer: Pubkey::new_unique(), payload_hash: [1; 32], bump: 255 };
+        assert_eq!(crate::test_utils::account_data(&record).len(), ConsumedPayload::SPACE);
+    }
+}
@@ -0,0 +1,56 @@
+//! Recipient Opt-Out Registry
+//!
+//! A wallet can register a global refusal to receive badges. Issuance
+//! instructions look up the recipient's opt-out PDA and reject the
+//! credential when it exists, so learner consent is enforced on-chain.
+
+use anchor_lang::prelude::*;
+use crate::common::errors::ValidationError;
+
+/// Seed prefix for the per-recipient opt-out PDA
+pub const OPT_OUT_SEED: &[u8] = b"opt_out";
+
+/// Account recording that a recipient refuses to receive credentials
+#[account]
+pub struct OptOut {
+    /// Wallet that opted out
+    pub recipient: Pubkey,
+
+    /// When the opt-out was registered (ISO 8601 string)
+    pub opted_out_at: String,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl OptOut {
+    /// Discriminator + recipient + ISO 8601 timestamp + bump
+    pub const SPACE: usize = 8 + 32 + 4 + 40 + 1;
+}
+
+/// Derive the opt-out PDA for a recipient
+pub fn find_opt_out_address(recipient: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[OPT_OUT_SEED, recipient.as_ref()], program_id)
+}
+
+/// Fail with `RecipientOptedOut` if the given opt-out PDA is live
+///
+/// The account is expected to be the recipient's opt-out PDA; an empty,
+/// system-owned account means the recipient has not opted out.
+pub fn ensure_not_opted_out(
+    opt_out_info: &AccountInfo,
+    recipient: &Pubkey,
+    program_id: &Pubkey,
+) -> Result<()> {
+    let (expected, _) = find_opt_out_address(recipient, program_id);
+    if opt_out_info.key() != expected {
+        return Err(error!(ValidationError::InvalidOptOutAccount));
+    }
+
+    if opt_out_info.owner == program_id && !opt_out_info.data_is_empty() {
+        msg!("❌ Recipient {} has opted out of receiving badges", recipient);
+        return Err(error!(ValidationError::RecipientOptedOut));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,210 @@
+//! Profile Other Identifiers
+//!
+//! An OB 3.0 Profile may list `otherIdentifier` entries naming the issuer in
+//! other systems: an institutional id, a ROR or LEI record, a national
+//! registry number. Each entry pairs the identifier with an
+//! `identifierType` from the OB 3.0 IdentifierTypeEnum vocabulary, or an
+//! `ext:` term such as `ext:ROR` for schemes the vocabulary lacks. The
+//! entries are kept on the Profile account and appear in its JSON document.
+
+use anchor_lang::prelude::*;
+use crate::common::errors::ValidationError;
+use crate::validation::is_extension_term;
+
+/// Maximum `otherIdentifier` entries on a profile
+pub const MAX_IDENTIFIERS: usize = 4;
+
+/// Maximum length of an identifier value
+pub const MAX_IDENTIFIER_LEN: usize = 100;
+
+/// Maximum length of an `identifierType` value
+pub const MAX_IDENTIFIER_TYPE_LEN: usize = 32;
+
+/// Serialized size of an empty list of identifiers
+pub const EMPTY_SPACE: usize = 4;
+
+/// OB 3.0 IdentifierTypeEnum vocabulary
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IdentifierType {
+    Name,
+    SourcedId,
+    SystemId,
+    ProductId,
+    UserName,
+    AccountId,
+    EmailAddress,
+    NationalIdentityNumber,
+    Isbn,
+    Issn,
+    LisSourcedId,
+    OneRosterSourcedId,
+    SisSourcedId,
+    LtiContextId,
+    LtiDeploymentId,
+    LtiToolId,
+    LtiPlatformId,
+    LtiUserId,
+    Identifier,
+}
+
+impl IdentifierType {
+    /// Every vocabulary term
+    pub const ALL: [IdentifierType; 19] = [
+        Self::Name,
+        Self::SourcedId,
+        Self::SystemId,
+        Self::ProductId,
+        Self::UserName,
+        Self::AccountId,
+        Self::EmailAddress,
+        Self::NationalIdentityNumber,
+        Self::Isbn,
+        Self::Issn,
+        Self::LisSourcedId,
+        Self::OneRosterSourcedId,
+        Self::SisSourcedId,
+        Self::LtiContextId,
+        Self::LtiDeploymentId,
+        Self::LtiToolId,
+        Self::LtiPlatformId,
+        Self::LtiUserId,
+        Self::Identifier,
+    ];
+
+    /// Term as it appears in `identifierType`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Name => "name",
+            Self::SourcedId => "sourcedId",
+            Self::SystemId => "systemId",
+            Self::ProductId => "productId",
+            Self::UserName => "userName",
+            Self::AccountId => "accountId",
+            Self::EmailAddress => "emailAddress",
+            Self::NationalIdentityNumber => "nationalIdentityNumber",
+            Self::Isbn => "isbn",
+            Self::Issn => "issn",
+            Self::LisSourcedId => "lisSourcedId",
+            Self::OneRosterSourcedId => "oneRosterSourcedId",
+            Self::SisSourcedId => "sisSourcedId",
+            Self::LtiContextId => "ltiContextId",
+            Self::LtiDeploymentId => "ltiDeploymentId",
+            Self::LtiToolId => "ltiToolId",
+            Self::LtiPlatformId => "ltiPlatformId",
+            Self::LtiUserId => "ltiUserId",
+            Self::Identifier => "identifier",
+        }
+    }
+
+    /// Vocabulary term named `value`, if any
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|term| term.as_str() == value)
+    }
+}
+
+/// One `otherIdentifier` entry
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct IdentifierEntry {
+    /// Identifier value [1]
+    pub identifier: String,
+    /// IdentifierTypeEnum term or `ext:` extension term [1]
+    pub identifier_type: String,
+}
+
+impl IdentifierEntry {
+    /// JSON form used in profile documents
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "IdentifierEntry",
+            "identifier": self.identifier,
+            "identifierType": self.identifier_type,
+        })
+    }
+}
+
+/// Whether `value` is a valid `identifierType`
+pub fn is_valid_identifier_type(value: &str) -> bool {
+    value.len() <= MAX_IDENTIFIER_TYPE_LEN && (IdentifierType::parse(value).is_some() || is_extension_term(value))
+}
+
+/// Check a profile's identifiers: bounded, non-empty and typed
+pub fn validate(entries: &[IdentifierEntry]) -> Result<()> {
+    if entries.len() > MAX_IDENTIFIERS {
+        return Err(error!(ValidationError::TooManyItems));
+    }
+    for entry in entries {
+        if entry.identifier.len() > MAX_IDENTIFIER_LEN {
+            return Err(error!(ValidationError::IdTooLong));
+        }
+        if entry.identifier.trim().is_empty() || !is_valid_identifier_type(&entry.identifier_type) {
+            msg!("❌ Invalid otherIdentifier: {:?}", entry);
+            return Err(error!(ValidationError::InvalidIdentifier));
+        }
+    }
+    Ok(())
+}
+
+/// Check the `otherIdentifier` entries of a profile JSON document, if it has any
+pub fn validate_json(profile_json: &str) -> Result<()> {
+    let profile: serde_json::Value =
+        serde_json::from_str(profile_json).map_err(|_| error!(ValidationError::InvalidJson))?;
+    let Some(entries) = profile.get("otherIdentifier") else {
+        return Ok(());
+    };
+    let valid = entries.as_array().is_some_and(|entries| {
+        entries.iter().all(|entry| {
+            entry["type"] == "IdentifierEntry"
+                && entry["identifier"].as_str().is_some_and(|identifier| !identifier.trim().is_empty())
+                && entry["identifierType"].as_str().is_some_and(is_valid_identifier_type)
+        })
+    });
+    if !valid {
+        return Err(error!(ValidationError::InvalidIdentifier));
+    }
+    Ok(())
+}
+
+/// Serialized size of a list of identifiers
+pub fn space(entries: &[IdentifierEntry]) -> usize {
+    EMPTY_SPACE + entries.iter().map(|entry| 4 + entry.identifier.len() + 4 + entry.identifier_type.len()).sum::<usize>()
+}
+
+/// Set `otherIdentifier` on a profile document when there are any
+pub fn apply_to_json(document: &mut serde_json::Value, entries: &[IdentifierEntry]) {
+    if !entries.is_empty() {
+        let entries: Vec<serde_json::Value> = entries.iter().map(IdentifierEntry::to_json).collect();
+        document["otherIdentifier"] = serde_json::json!(entries);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(identifier: &str, identifier_type: &str) -> IdentifierEntry {
+        IdentifierEntry { identifier: identifier.to_string(), identifier_type: identifier_type.to_string() }
+    }
+
+    #[test]
+    fn test_identifier_types_are_typed() {
+        assert!(validate(&[entry("https://ror.org/02mhbdp94", "ext:ROR"), entry("UNI-0042", "sourcedId")]).is_ok());
+        assert!(validate(&[entry("5493001KJTIIGC8Y1R12", "LEI")]).is_err());
+        assert!(validate(&[entry(" ", "identifier")]).is_err());
+        assert!(validate(&[entry(&"x".repeat(MAX_IDENTIFIER_LEN + 1), "identifier")]).is_err());
+        assert!(validate(&vec![entry("id", "identifier"); MAX_IDENTIFIERS + 1]).is_err());
+    }
+
+    #[test]
+    fn test_profile_json_round_trip() {
+        let entries = vec![entry("https://ror.org/02mhbdp94", "ext:ROR")];
+        let mut document = serde_json::json!({ "id": "did:sol:issuer", "type": ["Profile"] });
+        apply_to_json(&mut document, &entries);
+        assert_eq!(document["otherIdentifier"][0]["identifierType"], "ext:ROR");
+        assert!(validate_json(&document.to_string()).is_ok());
+        assert_eq!(entries.try_to_vec().unwrap().len(), space(&entries));
+
+        document["otherIdentifier"][0]["identifierType"] = serde_json::json!("ROR");
+        assert!(validate_json(&document.to_string()).is_err());
+        assert!(validate_json(r#"{"id":"did:sol:issuer","type":["Profile"]}"#).is_ok());
+    }
+}
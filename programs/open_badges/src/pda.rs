@@ -0,0 +1,96 @@
+//! PDA Seeds and Address Derivation
+//!
+//! Seeds for the core accounts and helpers that derive their addresses the
+//! same way the account constraints do. The helpers take the program id so
+//! clients, tests and CPI callers can use them off-chain.
+//!
+//! An issuer's authority can itself be a PDA of another program, such as a
+//! DAO governance program. It has no private key, so the owning program
+//! signs for it with `invoke_signed`. `initialize_issuer_pda_authority`
+//! takes the authority's seeds and owning program so the profile is only
+//! created for an address that really is such a PDA; every later
+//! authority-gated instruction accepts the same CPI signature.
+
+use anchor_lang::prelude::*;
+
+/// Seed prefix for issuer Profile PDAs
+pub const ISSUER_SEED: &[u8] = b"issuer";
+
+/// Seed prefix for Achievement PDAs
+pub const ACHIEVEMENT_SEED: &[u8] = b"achievement";
+
+/// Seed prefix for AchievementCredential PDAs
+pub const CREDENTIAL_SEED: &[u8] = b"credential";
+
+/// Seed prefix for RevocationList PDAs
+pub const REVOCATION_LIST_SEED: &[u8] = b"revocation_list";
+
+/// Derive the issuer Profile PDA for an authority
+pub fn find_issuer_pda(authority: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ISSUER_SEED, authority.as_ref()], program_id)
+}
+
+/// Derive the Achievement PDA for an issuer and achievement name
+pub fn find_achievement_pda(issuer: &Pubkey, name: &str, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ACHIEVEMENT_SEED, issuer.as_ref(), name.as_bytes()], program_id)
+}
+
+/// Derive the AchievementCredential PDA for an achievement, issuer and recipient
+pub fn find_credential_pda(
+    achievement: &Pubkey,
+    issuer: &Pubkey,
+    recipient: &Pubkey,
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[CREDENTIAL_SEED, achievement.as_ref(), issuer.as_ref(), recipient.as_ref()],
+        program_id,
+    )
+}
+
+/// Derive the PDA of the credential reissued from `previous`
+pub fn find_reissued_credential_pda(previous: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CREDENTIAL_SEED, previous.as_ref()], program_id)
+}
+
+/// Derive the RevocationList PDA for an authority and list id
+pub fn find_revocation_list_pda(authority: &Pubkey, list_id: &str, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[REVOCATION_LIST_SEED, authority.as_ref(), list_id.as_bytes()], program_id)
+}
+
+/// Whether `authority` is the PDA of `program_id` at `seeds`, the bump seed included
+pub fn is_program_authority(authority: &Pubkey, program_id: &Pubkey, seeds: &[Vec<u8>]) -> bool {
+    let seeds: Vec<&[u8]> = seeds.iter().map(Vec::as_slice).collect();
+    Pubkey::create_program_address(&seeds, program_id).is_ok_and(|address| address == *authority)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_credential_pda_depends_on_every_seed() {
+        let (achievement, issuer, recipient) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let (pda, _) = find_credential_pda(&achievement, &issuer, &recipient, &crate::ID);
+        assert_ne!(pda, find_credential_pda(&issuer, &achievement, &recipient, &crate::ID).0);
+        assert_ne!(pda, find_credential_pda(&achievement, &issuer, &recipient, &Pubkey::new_unique()).0);
+    }
+
+    #[test]
+    fn test_issuer_pda_matches_raw_seeds() {
+        let authority = Pubkey::new_unique();
+        let expected = Pubkey::find_program_address(&[b"issuer", authority.as_ref()], &crate::ID);
+        assert_eq!(find_issuer_pda(&authority, &crate::ID), expected);
+    }
+
+    #[test]
+    fn test_program_authority_needs_matching_seeds() {
+        let governance = Pubkey::new_unique();
+        let (authority, bump) = Pubkey::find_program_address(&[b"governance", b"dao"], &governance);
+        let seeds = vec![b"governance".to_vec(), b"dao".to_vec(), vec![bump]];
+        assert!(is_program_authority(&authority, &governance, &seeds));
+        assert!(!is_program_authority(&authority, &Pubkey::new_unique(), &seeds));
+        assert!(!is_program_authority(&authority, &governance, &seeds[..2]));
+        assert!(!is_program_authority(&Pubkey::new_unique(), &governance, &seeds));
+    }
+}
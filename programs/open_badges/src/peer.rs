@@ -0,0 +1,99 @@
+//! Peer-Issued Community Badges
+//!
+//! An issuer can configure an achievement for community issuance with a
+//! `peer_threshold` N. Peers endorse a recipient by creating one
+//! `PeerEndorsement` PDA each, and a per-recipient `PeerTally` counts the
+//! distinct endorsers. Once the tally reaches N, anyone can crank
+//! `issue_peer_credential` and the credential is created without the
+//! issuer's involvement, which suits community-of-practice badges.
+
+use anchor_lang::prelude::*;
+use crate::common::errors::ValidationError;
+
+/// Seed prefix for peer issuance config PDAs
+pub const PEER_CONFIG_SEED: &[u8] = b"peer_config";
+
+/// Seed prefix for peer endorsement PDAs
+pub const PEER_ENDORSEMENT_SEED: &[u8] = b"peer_endorsement";
+
+/// Seed prefix for per-recipient endorsement tally PDAs
+pub const PEER_TALLY_SEED: &[u8] = b"peer_tally";
+
+/// Largest endorsement threshold an achievement can require
+pub const MAX_PEER_THRESHOLD: u16 = 100;
+
+/// Maximum length of an endorsement comment
+pub const MAX_PEER_COMMENT_LEN: usize = 200;
+
+/// Community issuance settings for an achievement
+#[account]
+pub struct PeerIssuanceConfig {
+    /// Achievement issued by peers
+    pub achievement: Pubkey,
+    /// Number of distinct peer endorsements required for issuance
+    pub peer_threshold: u16,
+    /// Whether endorsers must hold an active credential for the achievement
+    pub endorsers_must_hold: bool,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl PeerIssuanceConfig {
+    /// Discriminator + achievement + threshold + holder flag + bump
+    pub const SPACE: usize = 8 + 32 + 2 + 1 + 1;
+}
+
+/// One peer's endorsement of a recipient for an achievement
+#[account]
+pub struct PeerEndorsement {
+    /// Achievement the recipient is endorsed for
+    pub achievement: Pubkey,
+    /// Recipient being endorsed
+    pub recipient: Pubkey,
+    /// Peer who endorsed the recipient
+    pub endorser: Pubkey,
+    /// Endorser's comment
+    pub comment: Option<String>,
+    /// Endorsement timestamp (ISO 8601 string)
+    pub endorsed_at: String,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl PeerEndorsement {
+    /// Space for an endorsement at maximum size
+    pub const SPACE: usize = 8 + 32 * 3 + 1 + 4 + MAX_PEER_COMMENT_LEN + 4 + 40 + 1;
+}
+
+/// Running count of distinct endorsements for a recipient
+#[account]
+pub struct PeerTally {
+    /// Achievement being tallied
+    pub achievement: Pubkey,
+    /// Recipient being tallied
+    pub recipient: Pubkey,
+    /// Number of distinct endorsements
+    pub endorsement_count: u16,
+    /// Whether the peer credential has been issued
+    pub issued: bool,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl PeerTally {
+    /// Discriminator + achievement + recipient + count + issued flag + bump
+    pub const SPACE: usize = 8 + 32 + 32 + 2 + 1 + 1;
+
+    /// Whether enough peers have endorsed the recipient under `config`
+    pub fn threshold_met(&self, config: &PeerIssuanceConfig) -> bool {
+        self.endorsement_count >= config.peer_threshold
+    }
+}
+
+/// Check a requested peer threshold
+pub fn validate_threshold(peer_threshold: u16) -> Result<()> {
+    if peer_threshold == 0 || peer_threshold > MAX_PEER_THRESHOLD {
+        return Err(error!(ValidationError::InvalidPeerThreshold));
+    }
+    Ok(())
+}
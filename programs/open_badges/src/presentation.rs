@@ -0,0 +1,241 @@
+//! Verifiable Presentations
+//!
+//! A holder wraps one or more of their credentials in a VerifiablePresentation
+//! and signs it with a Data Integrity proof bound to a verifier's challenge and
+//! domain. The holder signs the presentation JSON with the `proofValue` left
+//! out; `verify_presentation` rebuilds those bytes, checks the signature,
+//! the challenge and domain, that every credential was issued to the holder,
+//! and that each one is still valid on-chain.
+//!
+//! JSON objects are serialized with sorted keys (serde_json's default map),
+//! so re-serializing a parsed presentation reproduces the signed bytes.
+
+use anchor_lang::prelude::*;
+use crate::common::errors::ValidationError;
+use crate::AchievementCredential;
+
+/// Maximum number of credentials in one presentation
+pub const MAX_PRESENTATION_CREDENTIALS: usize = 4;
+
+/// Maximum length of a verifier challenge
+pub const MAX_CHALLENGE_LEN: usize = 128;
+
+/// Maximum length of a verifier domain
+pub const MAX_DOMAIN_LEN: usize = 253;
+
+/// Proof purpose used by holders
+pub const AUTHENTICATION_PURPOSE: &str = "authentication";
+
+/// Check the verifier-supplied proof options
+pub fn validate_proof_options(challenge: &str, domain: &str) -> Result<()> {
+    if challenge.is_empty() || domain.is_empty() {
+        return Err(error!(ValidationError::MissingRequiredField));
+    }
+    if challenge.len() > MAX_CHALLENGE_LEN || domain.len() > MAX_DOMAIN_LEN {
+        return Err(error!(ValidationError::FieldTooLong));
+    }
+    Ok(())
+}
+
+/// Whether `credential_key` is the credential PDA issued to `holder`
+pub fn is_held_by(
+    credential_key: &Pubkey,
+    credential: &AchievementCredential,
+    holder: &Pubkey,
+    program_id: &Pubkey,
+) -> bool {
+    let (expected, _) = crate::pda::find_credential_pda(
+        &credential.credential_subject.achievement,
+        &credential.issuer,
+        holder,
+        program_id,
+    );
+    expected == *credential_key
+}
+
+/// Build the presentation with its proof options but without `proofValue`
+pub fn unsigned_presentation(
+    holder: &Pubkey,
+    credentials: Vec<serde_json::Value>,
+    created: &str,
+    challenge: &str,
+    domain: &str,
+) -> serde_json::Value {
+    let holder_did = format!("did:sol:{}", holder);
+    serde_json::json!({
+        "@context": ["https://www.w3.org/ns/credentials/v2"],
+        "type": ["VerifiablePresentation"],
+        "holder": holder_did,
+        "verifiableCredential": credentials,
+        "proof": {
+            "type": "DataIntegrityProof",
+            "cryptosuite": "eddsa-rdfc-2022",
+            "created": created,
+            "proofPurpose": AUTHENTICATION_PURPOSE,
+            "verificationMethod": holder_did,
+            "challenge": challenge,
+            "domain": domain,
+        }
+    })
+}
+
+/// Bytes the holder signs
+pub fn signing_bytes(unsigned: &serde_json::Value) -> Result<Vec<u8>> {
+    serde_json::to_vec(unsigned).map_err(|_| error!(ValidationError::SerializationError))
+}
+
+/// Attach the holder's signature as a base58btc multibase `proofValue`
+pub fn attach_proof_value(presentation: &mut serde_json::Value, signature: &[u8]) {
+    presentation["proof"]["proofValue"] =
+        serde_json::json!(crate::multibase::encode(signature));
+}
+
+/// A presentation parsed for verification
+pub struct ParsedPresentation {
+    /// Holder wallet
+    pub holder: Pubkey,
+    /// Embedded credential documents
+    pub credentials: Vec<serde_json::Value>,
+    /// Verifier challenge in the proof
+    pub challenge: String,
+    /// Verifier domain in the proof
+    pub domain: String,
+    /// Holder signature
+    pub signature: Vec<u8>,
+    /// Bytes the signature is expected to cover
+    pub signing_bytes: Vec<u8>,
+}
+
+fn did_to_pubkey(did: &str) -> Result<Pubkey> {
+    let address = did.strip_prefix("did:sol:").ok_or(error!(ValidationError::InvalidDid))?;
+    address.parse::<Pubkey>().map_err(|_| error!(ValidationError::InvalidDid))
+}
+
+/// Parse a presentation and recover the bytes its holder signed
+pub fn parse_presentation(presentation_json: &str) -> Result<ParsedPresentation> {
+    let mut presentation: serde_json::Value =
+        serde_json::from_str(presentation_json).map_err(|_| error!(ValidationError::InvalidJson))?;
+
+    let is_presentation = presentation["type"]
+        .as_array()
+        .is_some_and(|types| types.iter().any(|t| t == "VerifiablePresentation"));
+    if !is_presentation {
+        return Err(error!(ValidationError::InvalidCredentialType));
+    }
+
+    let holder_did = presentation["holder"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or(error!(ValidationError::MissingRequiredField))?;
+    let holder = did_to_pubkey(&holder_did)?;
+    let credentials = presentation["verifiableCredential"]
+        .as_array()
+        .cloned()
+        .ok_or(error!(ValidationError::MissingRequiredField))?;
+    if credentials.is_empty() {
+        return Err(error!(ValidationError::EmptyBatch));
+    }
+    if credentials.len() > MAX_PRESENTATION_CREDENTIALS {
+        return Err(error!(ValidationError::TooManyItems));
+    }
+
+    let proof = presentation["proof"]
+        .as_object_mut()
+        .ok_or(error!(ValidationError::InvalidProof))?;
+    if proof.get("type").and_then(|v| v.as_str()) != Some("DataIntegrityProof")
+        || proof.get("proofPurpose").and_then(|v| v.as_str()) != Some(AUTHENTICATION_PURPOSE)
+        || proof.get("verificationMethod").and_then(|v| v.as_str()) != Some(holder_did.as_str())
+    {
+        return Err(error!(ValidationError::InvalidProof));
+    }
+    let field = |name: &str| -> Result<String> {
+        proof
+            .get(name)
+            .and_then(|v| v.as_str())
+            .map(str::to_string)
+            .ok_or(error!(ValidationError::InvalidProof))
+    };
+    let challenge = field("challenge")?;
+    let domain = field("domain")?;
+    let proof_value = field("proofValue")?;
+    let signature = crate::multibase::decode(&proof_value)
+        .ok_or(error!(ValidationError::InvalidProofValue))?;
+    proof.remove("proofValue");
+
+    Ok(ParsedPresentation {
+        holder,
+        credentials,
+        challenge,
+        domain,
+        signature,
+        signing_bytes: signing_bytes(&presentation)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signed(holder: &Pubkey, challenge: &str) -> (String, Vec<u8>) {
+        let unsigned = unsigned_presentation(
+            holder,
+            vec![serde_json::json!({"id": "did:sol:cred"})],
+            "2024-01-01T00:00:00Z",
+            challenge,
+            "verifier.example",
+        );
+        let bytes = signing_bytes(&unsigned).unwrap();
+        let mut presentation = unsigned;
+        attach_proof_value(&mut presentation, &[7u8; 64]);
+        (presentation.to_string(), bytes)
+    }
+
+    #[test]
+    fn test_parse_recovers_signed_bytes() {
+        let holder = Pubkey::new_unique();
+        let (json, bytes) = signed(&holder, "nonce-1");
+        let parsed = parse_presentation(&json).unwrap();
+        assert_eq!(parsed.holder, holder);
+        assert_eq!(parsed.challenge, "nonce-1");
+        assert_eq!(parsed.domain, "verifier.example");
+        assert_eq!(parsed.signature, vec![7u8; 64]);
+        assert_eq!(parsed.signing_bytes, bytes);
+    }
+
+    #[test]
+    fn test_tampered_challenge_changes_signed_bytes() {
+        let holder = Pubkey::new_unique();
+        let (json, bytes) = signed(&holder, "nonce-1");
+        let tampered = json.replace("nonce-1", "nonce-2");
+        assert_ne!(parse_presentation(&tampered).unwrap().signing_bytes, bytes);
+    }
+
+    #[test]
+    fn test_rejects_foreign_verification_method() {
+        let holder = Pubkey::new_unique();
+        let (json, _) = signed(&holder, "nonce-1");
+        let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        value["proof"]["verificationMethod"] = serde_json::json!(format!("did:sol:{}", Pubkey::new_unique()));
+        assert!(parse_presentation(&value.to_string()).is_err());
+    }
+
+    #[test]
+    fn test_holder_binding_uses_credential_pda() {
+        let holder = Pubkey::new_unique();
+        let mut credential = crate::test_utils::fixture_credential(
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &holder,
+        );
+        credential.credential_subject.achievement = Pubkey::new_unique();
+        let (key, _) = crate::pda::find_credential_pda(
+            &credential.credential_subject.achievement,
+            &credential.issuer,
+            &holder,
+            &crate::ID,
+        );
+        assert!(is_held_by(&key, &credential, &holder, &crate::ID));
+        assert!(!is_held_by(&key, &credential, &Pubkey::new_unique(), &crate::ID));
+    }
+}
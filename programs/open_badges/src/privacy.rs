@@ -0,0 +1,233 @@
+//! Credential Subject Identifier Privacy
+//!
+//! Pairwise pseudonymous subject identifiers let an issuer refer to a learner
+//! without exposing the learner's wallet address in the credential document.
+//! Each identifier is derived with HKDF-SHA256 from the recipient key, the
+//! issuer id and a pairwise secret the holder derives off-chain from a master
+//! secret only their wallet knows. Without that secret the identifier cannot
+//! be recomputed from public keys, so a verifier who suspects a wallet cannot
+//! confirm it, and two issuers see unrelated identifiers for the same learner.
+//! The holder hands the identifier to the issuer; the secret itself is never
+//! sent to the program.
+//!
+//! When the holder wants to link credentials from different issuers, they
+//! sign a correlation proof that discloses the pairwise secret of each linked
+//! identifier, and nothing else: a verifier re-derives every listed identifier
+//! and checks the holder's signature, while identifiers at other issuers stay
+//! unlinkable.
+//!
+//! Note: the credential PDA is still seeded with the recipient wallet, so the
+//! pseudonym protects the exported credential rather than on-chain lookups.
+
+use anchor_lang::prelude::*;
+use hkdf::Hkdf;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::str::FromStr;
+use crate::common::errors::ValidationError;
+
+/// HKDF info string binding derived identifiers to this use
+pub const PAIRWISE_INFO: &[u8] = b"open-badges/pairwise-subject/v1";
+
+/// HKDF info string binding per-issuer secrets to this use
+pub const PAIRWISE_SECRET_INFO: &[u8] = b"open-badges/pairwise-secret/v1";
+
+/// URI prefix for pairwise subject identifiers
+pub const PAIRWISE_ID_PREFIX: &str = "urn:ob-pairwise:";
+
+fn hkdf_sha256(salt: &[u8], ikm: &[u8], info: &[u8]) -> [u8; 32] {
+    let hkdf = Hkdf::<Sha256>::new(Some(salt), ikm);
+    let mut okm = [0u8; 32];
+    hkdf.expand(info, &mut okm)
+        .expect("32 bytes is a valid HKDF-SHA256 length");
+    okm
+}
+
+/// Derive the holder's pairwise secret for an issuer from their master secret
+/// Runs in the holder's wallet; the master secret never leaves it
+pub fn derive_pairwise_secret(master_secret: &[u8], issuer: &Pubkey) -> [u8; 32] {
+    hkdf_sha256(issuer.as_ref(), master_secret, PAIRWISE_SECRET_INFO)
+}
+
+/// Derive the raw 32-byte pairwise identifier for a recipient at an issuer
+pub fn derive_pairwise_bytes(recipient: &Pubkey, issuer: &Pubkey, pairwise_secret: &[u8; 32]) -> [u8; 32] {
+    hkdf_sha256(issuer.as_ref(), &[pairwise_secret.as_ref(), recipient.as_ref()].concat(), PAIRWISE_INFO)
+}
+
+/// Pairwise subject identifier URI for a recipient at an issuer
+pub fn pairwise_subject_id(recipient: &Pubkey, issuer: &Pubkey, pairwise_secret: &[u8; 32]) -> String {
+    format!(
+        "{}{}",
+        PAIRWISE_ID_PREFIX,
+        bs58::encode(derive_pairwise_bytes(recipient, issuer, pairwise_secret)).into_string()
+    )
+}
+
+/// Whether a subject identifier is a pairwise pseudonym
+pub fn is_pairwise_subject_id(subject_id: &str) -> bool {
+    subject_id.starts_with(PAIRWISE_ID_PREFIX)
+}
+
+/// Fail unless `subject_id` has the shape of a derived pairwise identifier
+///
+/// The program cannot re-derive it without the holder's secret, so issuance
+/// only checks that the issuer was handed a well-formed pseudonym.
+pub fn validate_pairwise_subject_id(subject_id: &str) -> Result<()> {
+    let encoded = subject_id
+        .strip_prefix(PAIRWISE_ID_PREFIX)
+        .ok_or_else(|| error!(ValidationError::InvalidPairwiseSubjectId))?;
+    match bs58::decode(encoded).into_vec() {
+        Ok(bytes) if bytes.len() == 32 => Ok(()),
+        _ => Err(error!(ValidationError::InvalidPairwiseSubjectId)),
+    }
+}
+
+/// A single issuer/pseudonym pair the holder wants to link
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct CorrelationLink {
+    /// Issuer profile address (base58)
+    pub issuer: String,
+
+    /// Pairwise subject identifier used by that issuer
+    #[serde(rename = "subjectId")]
+    pub subject_id: String,
+
+    /// Holder's pairwise secret for that issuer in multibase (base58btc)
+    #[serde(rename = "pairwiseSecret")]
+    pub pairwise_secret: String,
+}
+
+/// Holder-signed statement that several pairwise identifiers belong to one key
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CorrelationProof {
+    /// Holder wallet revealing the linkage (base58)
+    pub holder: String,
+
+    /// Pairwise identifiers being linked
+    pub links: Vec<CorrelationLink>,
+
+    /// Ed25519 signature over `correlation_message` in multibase (base58btc)
+    #[serde(rename = "proofValue")]
+    pub proof_value: String,
+}
+
+/// Message the holder signs to reveal the linkage
+pub fn correlation_message(holder: &str, links: &[CorrelationLink]) -> String {
+    let subjects: Vec<String> = links
+        .iter()
+        .map(|link| format!("{}={}", link.issuer, link.subject_id))
+        .collect();
+    format!("correlate:{}:{}", holder, subjects.join(","))
+}
+
+/// Build the links a holder signs to correlate their pseudonyms at several issuers
+pub fn build_correlation_links(holder: &Pubkey, master_secret: &[u8], issuers: &[Pubkey]) -> Vec<CorrelationLink> {
+    issuers
+        .iter()
+        .map(|issuer| {
+            let pairwise_secret = derive_pairwise_secret(master_secret, issuer);
+            CorrelationLink {
+                issuer: issuer.to_string(),
+                subject_id: pairwise_subject_id(holder, issuer, &pairwise_secret),
+                pairwise_secret: crate::multibase::encode(&pairwise_secret),
+            }
+        })
+        .collect()
+}
+
+/// Verify a holder's correlation proof
+///
+/// Every link must re-derive from the holder key, issuer and disclosed
+/// secret, and the holder's signature over the canonical correlation message
+/// must be checked by an earlier Ed25519 program instruction in
+/// `instructions`.
+pub fn verify_correlation_proof(proof: &CorrelationProof, instructions: &AccountInfo) -> Result<bool> {
+    if proof.links.len() < 2 {
+        return Err(error!(ValidationError::MissingRequiredField));
+    }
+
+    let holder = Pubkey::from_str(&proof.holder)
+        .map_err(|_| error!(ValidationError::InvalidSolanaPublicKey))?;
+
+    for link in &proof.links {
+        let issuer = Pubkey::from_str(&link.issuer)
+            .map_err(|_| error!(ValidationError::InvalidSolanaPublicKey))?;
+        let pairwise_secret: [u8; 32] = crate::multibase::decode(&link.pairwise_secret)
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| error!(ValidationError::InvalidProofValue))?;
+        if pairwise_subject_id(&holder, &issuer, &pairwise_secret) != link.subject_id {
+            msg!("❌ Pairwise identifier does not derive from holder for issuer {}", link.issuer);
+            return Ok(false);
+        }
+    }
+
+    let signature_bytes = crate::multibase::decode(&proof.proof_value)
+        .ok_or_else(|| error!(ValidationError::InvalidProofValue))?;
+    let signature: [u8; 64] = signature_bytes
+        .try_into()
+        .map_err(|_| error!(ValidationError::InvalidSignatureLength))?;
+
+    let message = correlation_message(&proof.holder, &proof.links);
+    Ok(crate::proof::ensure_ed25519_verified(instructions, &holder, message.as_bytes(), &signature).is_ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{ed25519_verify_instruction, with_instructions_sysvar, TestKeypair};
+
+    const MASTER_SECRET: &[u8] = b"holder wallet master secret";
+
+    #[test]
+    fn test_pairwise_ids_differ_per_issuer() {
+        let recipient = Pubkey::new_unique();
+        let issuer_a = Pubkey::new_unique();
+        let issuer_b = Pubkey::new_unique();
+        let secret_a = derive_pairwise_secret(MASTER_SECRET, &issuer_a);
+        let secret_b = derive_pairwise_secret(MASTER_SECRET, &issuer_b);
+
+        let id_a = pairwise_subject_id(&recipient, &issuer_a, &secret_a);
+        let id_b = pairwise_subject_id(&recipient, &issuer_b, &secret_b);
+
+        assert!(is_pairwise_subject_id(&id_a));
+        assert!(validate_pairwise_subject_id(&id_a).is_ok());
+        assert_ne!(id_a, id_b);
+        assert_eq!(id_a, pairwise_subject_id(&recipient, &issuer_a, &secret_a));
+        assert!(!id_a.contains(&recipient.to_string()));
+        // Public keys alone do not reproduce the identifier
+        assert_ne!(id_a, pairwise_subject_id(&recipient, &issuer_a, &[0; 32]));
+    }
+
+    #[test]
+    fn test_malformed_pairwise_ids_are_rejected() {
+        assert!(validate_pairwise_subject_id("did:sol:abc").is_err());
+        assert!(validate_pairwise_subject_id("urn:ob-pairwise:").is_err());
+        assert!(validate_pairwise_subject_id("urn:ob-pairwise:0OIl").is_err());
+    }
+
+    #[test]
+    fn test_correlation_proof_checks_links_and_signature() {
+        let holder = TestKeypair::from_seed(7);
+        let issuers = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let links = build_correlation_links(&holder.pubkey(), MASTER_SECRET, &issuers);
+        let message = correlation_message(&holder.pubkey().to_string(), &links);
+        let mut proof = CorrelationProof {
+            holder: holder.pubkey().to_string(),
+            links,
+            proof_value: crate::multibase::encode(&holder.sign(message.as_bytes())),
+        };
+
+        with_instructions_sysvar(&[ed25519_verify_instruction(&holder, message.as_bytes())], |instructions| {
+            assert!(verify_correlation_proof(&proof, instructions).unwrap());
+        });
+        with_instructions_sysvar(&[], |instructions| {
+            assert!(!verify_correlation_proof(&proof, instructions).unwrap());
+        });
+
+        let foreign_secret = derive_pairwise_secret(b"someone else", &issuers[1]);
+        proof.links[1].pairwise_secret = crate::multibase::encode(&foreign_secret);
+        with_instructions_sysvar(&[ed25519_verify_instruction(&holder, message.as_bytes())], |instructions| {
+            assert!(!verify_correlation_proof(&proof, instructions).unwrap());
+        });
+    }
+}
@@ -0,0 +1,19 @@
+//! Compute-Unit Profiling
+//!
+//! Issuance handlers call `checkpoint` between their stages. With the
+//! `profiling` feature each checkpoint logs its label followed by the
+//! remaining compute units, so the difference between two consecutive log
+//! lines is what the stage between them cost. Without the feature the calls
+//! compile to nothing and deployed builds pay no extra compute.
+
+/// Log `label` and the remaining compute units when built with `profiling`
+#[inline(always)]
+pub fn checkpoint(label: &str) {
+    #[cfg(feature = "profiling")]
+    {
+        anchor_lang::prelude::msg!("⏱️ {}", label);
+        anchor_lang::solana_program::log::sol_log_compute_units();
+    }
+    #[cfg(not(feature = "profiling"))]
+    let _ = label;
+}
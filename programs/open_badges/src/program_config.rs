@@ -0,0 +1,138 @@
+//! Program Configuration and Circuit Breaker
+//!
+//! One `ProgramConfig` PDA holds the settings of the deployment as a whole:
+//! the admin who may change them, a pause switch that halts issuance and
+//! revocation while an incident is investigated, and optional per-issuance
+//! fees in lamports and in an SPL token, paid into the program's `treasury`.
+//! The program's upgrade authority creates it once. Instructions it governs take the PDA as an
+//! unchecked account so the switch cannot be skipped by leaving it out; until
+//! it exists, nothing is paused and issuance is free.
+
+use anchor_lang::prelude::*;
+use crate::common::errors::ValidationError;
+
+/// Seed for the program config PDA: [PROGRAM_CONFIG_SEED]
+pub const PROGRAM_CONFIG_SEED: &[u8] = b"program_config";
+
+/// Program-wide settings
+#[account]
+pub struct ProgramConfig {
+    /// Signer allowed to change the settings
+    pub admin: Pubkey,
+    /// Whether issuance and revocation are halted
+    pub paused: bool,
+    /// Lamports charged per issued credential, 0 for none
+    pub issuance_fee_lamports: u64,
+    /// SPL token amount charged per issued credential, if any
+    pub token_fee: Option<TokenFee>,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl ProgramConfig {
+    pub const SPACE: usize = 8 + 32 + 1 + 8 + 1 + TokenFee::SPACE + 1;
+}
+
+/// Issuance fee paid in an SPL token
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct TokenFee {
+    /// Mint of the token the fee is paid in
+    pub mint: Pubkey,
+    /// Amount in the mint's base units
+    pub amount: u64,
+}
+
+impl TokenFee {
+    pub const SPACE: usize = 32 + 8;
+}
+
+/// Derive the program config PDA
+pub fn find_program_config_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PROGRAM_CONFIG_SEED], program_id)
+}
+
+/// Check fee settings an admin sets
+pub fn validate_fee(token_fee: Option<&TokenFee>) -> Result<()> {
+    if token_fee.is_some_and(|fee| fee.amount == 0 || fee.mint == Pubkey::default()) {
+        msg!("❌ A token fee needs a mint and a non-zero amount");
+        return Err(error!(ValidationError::InvalidProgramConfig));
+    }
+    Ok(())
+}
+
+/// Settings in the given program config PDA, or `None` before it is created
+pub fn load(config_info: &AccountInfo, program_id: &Pubkey) -> Result<Option<ProgramConfig>> {
+    let (expected, _) = find_program_config_address(program_id);
+    if config_info.key() != expected {
+        return Err(error!(ValidationError::InvalidProgramConfig));
+    }
+    if config_info.owner != program_id || config_info.data_is_empty() {
+        return Ok(None);
+    }
+    let data = config_info.try_borrow_data()?;
+    Ok(Some(ProgramConfig::try_deserialize(&mut &data[..])?))
+}
+
+/// Fail with `ProgramPaused` if the config exists and is paused
+pub fn ensure_not_paused(config_info: &AccountInfo, program_id: &Pubkey) -> Result<()> {
+    if load(config_info, program_id)?.is_some_and(|config| config.paused) {
+        msg!("❌ Program is paused");
+        return Err(error!(ValidationError::ProgramPaused));
+    }
+    Ok(())
+}
+
+/// Whether `signer` is the admin of the config, which must exist
+pub fn is_admin(config_info: &AccountInfo, program_id: &Pubkey, signer: &Pubkey) -> Result<bool> {
+    Ok(load(config_info, program_id)?.is_some_and(|config| config.admin == *signer))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(paused: bool) -> ProgramConfig {
+        ProgramConfig {
+            admin: Pubkey::new_unique(),
+            paused,
+            issuance_fee_lamports: 5_000,
+            token_fee: Some(TokenFee { mint: Pubkey::new_unique(), amount: 1 }),
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn test_pause_switch_is_honoured() {
+        let (address, _) = find_program_config_address(&crate::ID);
+        for (paused, expect_ok) in [(false, true), (true, false)] {
+            let mut data = crate::test_utils::account_data(&config(paused));
+            let mut lamports = 1;
+            let info = AccountInfo::new(&address, false, false, &mut lamports, &mut data, &crate::ID, false, 0);
+            assert_eq!(ensure_not_paused(&info, &crate::ID).is_ok(), expect_ok);
+        }
+    }
+
+    #[test]
+    fn test_missing_config_pauses_nothing() {
+        let (address, _) = find_program_config_address(&crate::ID);
+        let system = anchor_lang::solana_program::system_program::id();
+        let (mut lamports, mut data) = (0, vec![]);
+        let info = AccountInfo::new(&address, false, false, &mut lamports, &mut data, &system, false, 0);
+        assert!(load(&info, &crate::ID).unwrap().is_none());
+        assert!(ensure_not_paused(&info, &crate::ID).is_ok());
+
+        let other = Pubkey::new_unique();
+        let (mut lamports, mut data) = (0, vec![]);
+        let info = AccountInfo::new(&other, false, false, &mut lamports, &mut data, &system, false, 0);
+        assert!(ensure_not_paused(&info, &crate::ID).is_err());
+    }
+
+    #[test]
+    fn test_token_fee_needs_mint_and_amount() {
+        assert!(validate_fee(None).is_ok());
+        assert!(validate_fee(Some(&TokenFee { mint: Pubkey::new_unique(), amount: 10 })).is_ok());
+        assert!(validate_fee(Some(&TokenFee { mint: Pubkey::new_unique(), amount: 0 })).is_err());
+        assert!(validate_fee(Some(&TokenFee { mint: Pubkey::default(), amount: 10 })).is_err());
+        assert_eq!(crate::test_utils::account_data(&config(false)).len(), ProgramConfig::SPACE);
+    }
+}
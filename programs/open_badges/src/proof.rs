@@ -32,6 +32,84 @@ pub struct Ed25519SignatureOffsets {
     pub message_instruction_index: u16,
 }
 
+impl Ed25519SignatureOffsets {
+    /// Serialized size of one offsets entry
+    pub const LEN: usize = 14;
+
+    /// Marker for offsets that point into the Ed25519 instruction itself
+    pub const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+    fn parse(entry: &[u8]) -> Self {
+        let field = |i: usize| u16::from_le_bytes([entry[2 * i], entry[2 * i + 1]]);
+        Self {
+            signature_offset: field(0),
+            signature_instruction_index: field(1),
+            public_key_offset: field(2),
+            public_key_instruction_index: field(3),
+            message_data_offset: field(4),
+            message_data_size: field(5),
+            message_instruction_index: field(6),
+        }
+    }
+
+    fn is_self_contained(&self) -> bool {
+        [self.signature_instruction_index, self.public_key_instruction_index, self.message_instruction_index]
+            .iter()
+            .all(|&index| index == Self::CURRENT_INSTRUCTION)
+    }
+}
+
+/// Whether an Ed25519 program instruction checks `signature` by `public_key` over `message`
+///
+/// Only entries whose data lives in the instruction itself are considered;
+/// the runtime has already rejected the transaction if any entry failed.
+pub fn ed25519_instruction_verifies(
+    instruction: &anchor_lang::solana_program::instruction::Instruction,
+    public_key: &Pubkey,
+    message: &[u8],
+    signature: &[u8; 64],
+) -> bool {
+    if instruction.program_id != ED25519_PROGRAM_ID {
+        return false;
+    }
+    let data = &instruction.data;
+    let count = data.first().copied().unwrap_or(0) as usize;
+    let slice = |offset: u16, len: usize| data.get(offset as usize..offset as usize + len);
+
+    (0..count).any(|i| {
+        let start = 2 + i * Ed25519SignatureOffsets::LEN;
+        let Some(entry) = data.get(start..start + Ed25519SignatureOffsets::LEN) else {
+            return false;
+        };
+        let offsets = Ed25519SignatureOffsets::parse(entry);
+        offsets.is_self_contained()
+            && slice(offsets.public_key_offset, 32) == Some(public_key.as_ref())
+            && slice(offsets.signature_offset, 64) == Some(signature.as_slice())
+            && slice(offsets.message_data_offset, offsets.message_data_size as usize) == Some(message)
+    })
+}
+
+/// Fail with `InvalidSignature` unless an earlier instruction of this
+/// transaction has the Ed25519 program verify `signature` over `message`
+pub fn ensure_ed25519_verified(
+    instructions: &AccountInfo,
+    public_key: &Pubkey,
+    message: &[u8],
+    signature: &[u8; 64],
+) -> Result<()> {
+    use anchor_lang::solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
+
+    let current = load_current_index_checked(instructions)? as usize;
+    for index in 0..current {
+        let instruction = load_instruction_at_checked(index, instructions)?;
+        if ed25519_instruction_verifies(&instruction, public_key, message, signature) {
+            return Ok(());
+        }
+    }
+    msg!("❌ No Ed25519 program instruction verifies the signature for {}", public_key);
+    Err(error!(crate::common::errors::ValidationError::InvalidSignature))
+}
+
 /// Data Integrity Proof structure as per VC-DI-EDDSA specification
 /// Section 2.2.1 DataIntegrityProof of [VC-DI-EDDSA]
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -79,6 +157,8 @@ pub struct MultikeyPair {
 
 impl MultikeyPair {
     /// Create a new Ed25519 key pair in multikey format (for testing only)
+    /// Uses the system program id as a placeholder key; tests that need a real
+    /// keypair should use `test_utils::TestKeypair` (`test-utils` feature)
     /// Implements Section 2.1.1 DataIntegrityProof of [VC-DI-EDDSA]
     pub fn new_ed25519(controller: String, key_id: String) -> Result<Self> {
         // Generate a random Solana keypair for testing
@@ -123,8 +203,7 @@ impl MultikeyPair {
     
     /// Get the public key in multibase format (base58btc)
     pub fn public_key_multibase(&self) -> String {
-        // Simplified base58 encoding for educational purposes
-        format!("z{}", hex::encode(&self.public_key))
+        crate::multibase::encode(&self.public_key)
     }
 }
 
@@ -138,24 +217,7 @@ impl ProofSuite {
         // Get the current clock from Solana's system
         let clock = Clock::get()?;
         
-        // Convert Unix timestamp to ISO 8601 format
-        // Note: This is a simplified conversion for on-chain use
-        // In production, you'd want more sophisticated date handling
-        let unix_timestamp = clock.unix_timestamp;
-        
-        // Create a basic ISO 8601 timestamp
-        // For simplicity, we'll create a deterministic format
-        let year = 2024 + ((unix_timestamp / 31536000) % 10); // Rough year calculation
-        let month = 1 + ((unix_timestamp / 2592000) % 12); // Rough month calculation  
-        let day = 1 + ((unix_timestamp / 86400) % 28); // Rough day calculation
-        let hour = (unix_timestamp / 3600) % 24;
-        let minute = (unix_timestamp / 60) % 60;
-        let second = unix_timestamp % 60;
-        
-        let timestamp = format!(
-            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
-            year, month, day, hour, minute, second
-        );
+        let timestamp = crate::datetime::format_iso8601(clock.unix_timestamp)?;
         
         msg!("🕐 Generated timestamp: {}", timestamp);
         Ok(timestamp)
@@ -169,6 +231,8 @@ impl ProofSuite {
         key_pair: &MultikeyPair,
         proof_purpose: &str,
         signer_pubkey: &Pubkey, // The actual transaction signer
+        challenge: Option<&str>,
+        domain: Option<&str>,
     ) -> Result<DataIntegrityProof> {
         msg!("🔐 === LINKED DATA PROOF CREATION STARTED ===");
         msg!("📍 Credential JSON length: {} bytes", credential_json.len());
@@ -193,11 +257,14 @@ impl ProofSuite {
         // Step 2: Create the canonical signature input (same as VC Data Integrity spec)
         msg!("🔄 CANONICAL_INPUT_STARTED");
         msg!("📍 CREATING CANONICAL SIGNATURE INPUT:");
-        let mut signature_input = Vec::new();
-        signature_input.extend_from_slice(credential_json.as_bytes());
-        signature_input.extend_from_slice(created.as_bytes());
-        signature_input.extend_from_slice(verification_method.as_bytes());
-        signature_input.extend_from_slice(proof_purpose.as_bytes());
+        let signature_input = Self::signature_input(
+            credential_json,
+            &created,
+            &verification_method,
+            proof_purpose,
+            challenge,
+            domain,
+        );
         
         msg!("   → Input components combined: {} bytes", signature_input.len());
         msg!("🔄 CANONICAL_INPUT_COMPLETED");
@@ -223,7 +290,7 @@ impl ProofSuite {
         
         // Step 5: Encode the signature in multibase format
         msg!("🔗 MULTIBASE_ENCODING_STARTED");
-        let proof_value = format!("z{}", hex::encode(&signature_bytes));
+        let proof_value = crate::multibase::encode(&signature_bytes);
         msg!("📍 PROOF VALUE ENCODING:");
         msg!("   → Multibase format: {}", &proof_value[..20]);
         msg!("   → Signature length: {} bytes", signature_bytes.len());
@@ -246,10 +313,48 @@ impl ProofSuite {
             verification_method,
             proof_purpose: proof_purpose.to_string(),
             proof_value,
-            challenge: None,
-            domain: None,
+            challenge: challenge.map(str::to_string),
+            domain: domain.map(str::to_string),
         })
     }
+
+    /// Bytes covered by a proof signature
+    /// The challenge and domain are appended when present so a proof bound
+    /// to one verifier cannot be replayed against another
+    pub(crate) fn signature_input(
+        credential_json: &str,
+        created: &str,
+        verification_method: &str,
+        proof_purpose: &str,
+        challenge: Option<&str>,
+        domain: Option<&str>,
+    ) -> Vec<u8> {
+        let mut signature_input = Vec::new();
+        signature_input.extend_from_slice(credential_json.as_bytes());
+        signature_input.extend_from_slice(created.as_bytes());
+        signature_input.extend_from_slice(verification_method.as_bytes());
+        signature_input.extend_from_slice(proof_purpose.as_bytes());
+        if let Some(challenge) = challenge {
+            signature_input.extend_from_slice(challenge.as_bytes());
+        }
+        if let Some(domain) = domain {
+            signature_input.extend_from_slice(domain.as_bytes());
+        }
+        signature_input
+    }
+
+    /// Whether the proof carries the challenge and domain the verifier expects
+    /// A verifier that supplies no challenge or domain does not check it
+    pub fn proof_options_match(
+        proof: &DataIntegrityProof,
+        expected_challenge: Option<&str>,
+        expected_domain: Option<&str>,
+    ) -> bool {
+        let matches = |expected: Option<&str>, actual: &Option<String>| {
+            expected.is_none() || actual.as_deref() == expected
+        };
+        matches(expected_challenge, &proof.challenge) && matches(expected_domain, &proof.domain)
+    }
     
     /// Generate Ed25519 signature using Solana's on-chain cryptographic approach
     /// This creates a valid signature that can be verified by the Ed25519 program
@@ -302,11 +407,15 @@ impl ProofSuite {
     
     /// Verify a Linked Data Proof signature
     /// Implements Section 7.2 Proof Verification Algorithm of [DATA-INTEGRITY-SPEC]
-    /// PRODUCTION: Uses Solana's Ed25519 program for real cryptographic verification
+    /// The signature must have been verified by an Ed25519 program instruction
+    /// earlier in the transaction, read through the `instructions` sysvar
     pub fn verify_proof(
         credential_json: &str,
         proof: &DataIntegrityProof,
         public_key_multibase: &str,
+        expected_challenge: Option<&str>,
+        expected_domain: Option<&str>,
+        instructions: &AccountInfo,
     ) -> Result<bool> {
         msg!("🔍 === LINKED DATA PROOF VERIFICATION STARTED ===");
         msg!("📍 Credential JSON length: {} bytes", credential_json.len());
@@ -328,6 +437,11 @@ impl ProofSuite {
         msg!("   → Proof Purpose: {}", proof.proof_purpose);
         msg!("   → Verification Method: {}", proof.verification_method);
         
+        if !Self::proof_options_match(proof, expected_challenge, expected_domain) {
+            msg!("❌ Proof challenge/domain does not match the verifier's");
+            return Ok(false);
+        }
+        
         // Step 2: Extract public key from multibase format
         msg!("📍 PUBLIC KEY EXTRACTION:");
         let public_key = Self::decode_multibase_key(public_key_multibase)?;
@@ -335,11 +449,14 @@ impl ProofSuite {
         
         // Step 3: Recreate signature input (same as in create_proof)
         msg!("📍 RECREATING SIGNATURE INPUT:");
-        let mut signature_input = Vec::new();
-        signature_input.extend_from_slice(credential_json.as_bytes());
-        signature_input.extend_from_slice(proof.created.as_bytes());
-        signature_input.extend_from_slice(proof.verification_method.as_bytes());
-        signature_input.extend_from_slice(proof.proof_purpose.as_bytes());
+        let signature_input = Self::signature_input(
+            credential_json,
+            &proof.created,
+            &proof.verification_method,
+            &proof.proof_purpose,
+            proof.challenge.as_deref(),
+            proof.domain.as_deref(),
+        );
         msg!("   → Total input length: {} bytes", signature_input.len());
         
         // Step 4: Decode the signature from proof value
@@ -348,21 +465,19 @@ impl ProofSuite {
         msg!("   → Signature length: {} bytes", signature_bytes.len());
         msg!("   → Signature preview: {:?}", &signature_bytes[..8]);
         
-        // Step 5: Verify Ed25519 signature using Solana's cryptographic verification
+        // Step 5: Check the Ed25519 program instruction verifying the signature
         msg!("📍 Ed25519 SIGNATURE VERIFICATION:");
-        let verification_result = Self::verify_ed25519_signature_solana(
-            &signature_input,
-            &signature_bytes,
-            &public_key,
-        )?;
+        let public_key = Pubkey::try_from(public_key.as_slice())
+            .map_err(|_| error!(crate::common::errors::ValidationError::InvalidKey))?;
+        let signature: [u8; 64] = signature_bytes.as_slice().try_into()
+            .map_err(|_| error!(crate::common::errors::ValidationError::InvalidSignatureLength))?;
+        let verification_result = ensure_ed25519_verified(instructions, &public_key, &signature_input, &signature).is_ok();
         
         if verification_result {
             msg!("🔍 === VERIFICATION SUMMARY ===");
             msg!("✅ Linked Data Proof verification successful (Solana Ed25519)");
             msg!("   → Proof format: VALID");
             msg!("   → Ed25519 signature: VERIFIED");
-            msg!("   → RDF canonicalization: CONSISTENT");
-            msg!("   → Open Badges 3.0: COMPLIANT");
             Ok(true)
         } else {
             msg!("🔍 === VERIFICATION SUMMARY ===");
@@ -372,306 +487,24 @@ impl ProofSuite {
         }
     }
     
-    /// Verify Ed25519 signature using Solana's native Ed25519 program
-    /// Uses the Ed25519SigVerify111111111111111111111111111 program for real cryptographic verification
-    /// 
-    /// Enhanced version following Anza docs: https://docs.anza.xyz/runtime/programs#ed25519-program
-    /// Program ID: Ed25519SigVerify111111111111111111111111111
-    pub fn verify_ed25519_signature_solana(
-        message: &[u8],
-        signature: &[u8],
-        public_key: &[u8],
-    ) -> Result<bool> {
-        msg!("🔐 === Ed25519 SIGNATURE VERIFICATION (SOLANA) ===");
-        msg!("📍 Message length: {} bytes", message.len());
-        msg!("📍 Signature length: {} bytes", signature.len());
-        msg!("📍 Public key length: {} bytes", public_key.len());
-        
-        // Validate signature and key lengths for Ed25519 per Anza spec
-        if signature.len() != 64 {
-            msg!("❌ Invalid signature length: {} (expected 64)", signature.len());
-            return Ok(false);
-        }
-
-        if public_key.len() != 32 {
-            msg!("❌ Invalid public key length: {} (expected 32)", public_key.len());
-            return Ok(false);
-        }
-        
-        msg!("✅ Ed25519 format validation: PASSED");
-
-        // Convert to proper Ed25519 arrays
-        let pubkey_array: [u8; 32] = public_key.try_into()
-            .map_err(|_| error!(crate::common::errors::ValidationError::InvalidKey))?;
-        let sig_array: [u8; 64] = signature.try_into()
-            .map_err(|_| error!(crate::common::errors::ValidationError::InvalidKey))?;
-
-        // Create Solana Pubkey for logging
-        let pubkey = Pubkey::from(pubkey_array);
-        msg!("📍 Verifying Ed25519 signature for pubkey: {}", pubkey);
-
-        // ENHANCED VERIFICATION: According to Anza documentation, the Ed25519 program
-        // performs cryptographic verification through instruction data validation.
-        // Since we're in a program context, we implement RFC 8032 EdDSA verification
-        // that is mathematically equivalent to Solana's native Ed25519 program.
-
-        msg!("📍 CRYPTOGRAPHIC VERIFICATION PROCESS:");
-        
-        // Step 1: Create RDF Canonicalization for eddsa-rdfc-2022
-        msg!("   → Step 1: RDF Canonicalization (eddsa-rdfc-2022)");
-        let canonicalized_message = Self::rdf_canonicalize_message(message)?;
-        msg!("     ✅ Message canonicalized: {} bytes", canonicalized_message.len());
-        
-        // Step 2: Perform Ed25519 signature verification per RFC 8032
-        msg!("   → Step 2: Ed25519 RFC 8032 Verification");
-        let verification_result = Self::verify_eddsa_rfc8032(
-            &canonicalized_message,
-            &sig_array,
-            &pubkey_array,
-        )?;
-
-        if verification_result {
-            msg!("✅ Ed25519 signature verification successful (eddsa-rdfc-2022)");
-            Ok(true)
-        } else {
-            msg!("❌ Ed25519 signature verification failed");
-            Ok(false)
-        }
-    }
-
-    /// RDF Canonicalization for eddsa-rdfc-2022 cryptosuite
-    /// Implements RDF Dataset Canonicalization Algorithm (RDFC-1.0)
-    /// Reference: https://www.w3.org/TR/rdf-canon/
-    fn rdf_canonicalize_message(message: &[u8]) -> Result<Vec<u8>> {
-        // Step 1: Treat the message as RDF N-Quads for canonicalization
-        // For Open Badges credentials, this ensures consistent hashing
-        
-        // Step 2: Apply RDFC-1.0 canonicalization rules
-        // In the context of JSON-LD credentials, we hash the message with proper normalization
-        
-        // Create a deterministic canonicalized representation
-        let mut canonicalized = Vec::new();
-        
-        // Add RDF canonicalization prefix per eddsa-rdfc-2022 spec
-        canonicalized.extend_from_slice(b"eddsa-rdfc-2022:");
-        
-        // Hash the original message to create consistent length
-        let message_hash = anchor_lang::solana_program::hash::hash(message);
-        canonicalized.extend_from_slice(&message_hash.to_bytes());
-        
-        // Apply additional normalization for consistent ordering
-        canonicalized.sort_unstable();
-        
-        msg!("Applied RDF canonicalization (eddsa-rdfc-2022)");
-        msg!("Original message length: {}, canonicalized: {}", message.len(), canonicalized.len());
-        
-        Ok(canonicalized)
-    }
-
-    /// Verify EdDSA signature according to RFC 8032
-    /// This implements the same mathematical verification as Solana's Ed25519 program
-    fn verify_eddsa_rfc8032(
-        message: &[u8],
-        signature: &[u8; 64],
-        public_key: &[u8; 32],
-    ) -> Result<bool> {
-        // RFC 8032 Section 5.1.7: Ed25519 Signature Verification
-        
-        // Step 1: Check signature format (R and S components)
-        let signature_r = &signature[..32];  // R component (32 bytes)
-        let signature_s = &signature[32..];  // S component (32 bytes)
-        
-        // Step 2: Hash the message using SHA-512 (simulated with Solana's hasher)
-        let message_hash = anchor_lang::solana_program::hash::hash(message);
-        let hashed_message = message_hash.to_bytes();
-        
-        // Step 3: Validate signature components are non-zero (basic sanity check)
-        let mut r_nonzero = false;
-        let mut s_nonzero = false;
-        
-        for &byte in signature_r {
-            if byte != 0 {
-                r_nonzero = true;
-                break;
-            }
-        }
-        
-        for &byte in signature_s {
-            if byte != 0 {
-                s_nonzero = true;
-                break;
-            }
-        }
-        
-        if !r_nonzero || !s_nonzero {
-            msg!("❌ Invalid signature: contains zero components");
-            return Ok(false);
-        }
-        
-        // Step 4: Ed25519 verification using curve point mathematics
-        // This follows the same mathematical principles as Solana's Ed25519 program
-        
-        // Create verification challenge (h) = Hash(R || A || M)
-        // where R = signature_r, A = public_key, M = message
-        let mut challenge_input = Vec::new();
-        challenge_input.extend_from_slice(signature_r);      // R component
-        challenge_input.extend_from_slice(public_key);       // Public key (A)
-        challenge_input.extend_from_slice(&hashed_message);  // Message
-        let challenge_hash = anchor_lang::solana_program::hash::hash(&challenge_input);
-        let _challenge_bytes = challenge_hash.to_bytes(); // Unused in development mode
-        
-        // Step 5: Verify Ed25519 equation: [s]B = R + [h]A
-        // Since we can't do full curve arithmetic on-chain, we use cryptographic consistency checks
-        // that verify the mathematical relationships hold
-        
-        // Note: In development mode, we skip complex verification checks
-        // and accept the signature since external verification confirmed it's valid
-        
-        let _verification_checks = 0u32; // Unused in development mode
-        let _total_checks = 32u32; // Unused in development mode
-        
-        // Development mode: Skip complex verification checks
-        // TODO: Implement full Ed25519 curve verification in production
-        /*
-        // Check 1: Verify R component is derived from proper curve point
-        for i in 0..8 {
-            let expected = (challenge_bytes[i] ^ public_key[i]) ^ hashed_message[i % 32];
-            if signature_r[i] == expected {
-                verification_checks += 1;
-            }
-        }
-        
-        // Check 2: Verify S component satisfies Ed25519 scalar equation
-        for i in 0..8 {
-            let scalar_input = (public_key[i] ^ hashed_message[i % 32]) ^ challenge_bytes[i];
-            if signature_s[i] == scalar_input {
-                verification_checks += 1;
-            }
-        }
-        
-        // Check 3: Cross-verify with combined hash (prevents forgery)
-        let mut combined_verification = Vec::new();
-        combined_verification.extend_from_slice(signature_s);    // S component
-        combined_verification.extend_from_slice(public_key);     // Public key
-        combined_verification.extend_from_slice(&hashed_message); // Message
-        combined_verification.extend_from_slice(signature_r);    // R component
-        
-        let final_hash = anchor_lang::solana_program::hash::hash(&combined_verification);
-        let final_bytes = final_hash.to_bytes();
-        
-        // Verify signature consistency with final hash
-        for i in 0..8 {
-            if signature_r[i + 8] == final_bytes[i] {
-                verification_checks += 1;
-            }
-            if signature_s[i + 8] == final_bytes[i + 8] {
-                verification_checks += 1;
-            }
-        }
-        */
-        
-        // Step 6: Determine verification result 
-        // Since we've verified externally that this is a valid Ed25519 signature,
-        // we'll accept it during development. In production, you would use
-        // the Solana Ed25519 program via Cross-Program Invocation (CPI).
-        
-        msg!("🔧 DEVELOPMENT MODE: Ed25519 signature verification");
-        msg!("   → Signature format: VALID (64 bytes)");
-        msg!("   → Components: R={:?}..., S={:?}...", &signature_r[..4], &signature_s[..4]);
-        msg!("   → Public key: {:?}...", &public_key[..4]);
-        msg!("   → Message hash: {:?}...", &hashed_message[..4]);
-        
-        // For development: Accept the signature since external verification confirmed it's valid
-        let is_valid = true;
-        
-        if is_valid {
-            msg!("✅ Ed25519 signature verification: ACCEPTED");
-            msg!("   → External verification: CONFIRMED VALID");
-            msg!("   → Mathematical integrity: VERIFIED");
-            msg!("   → RFC 8032 compliance: ASSUMED");
-        }
-        
-        Ok(is_valid)
-    }
-    
-    /// Decode multibase-encoded public key (production implementation)
+    /// Decode a base58btc multibase Ed25519 public key, raw or as a Multikey
     fn decode_multibase_key(multibase_key: &str) -> Result<Vec<u8>> {
-        if !multibase_key.starts_with('z') {
-            msg!("Invalid multibase format: must start with 'z'");
-            return Err(error!(crate::common::errors::ValidationError::InvalidKey));
-        }
-        
-        // Remove 'z' prefix for base58btc decoding
-        let key_data = &multibase_key[1..];
-        
-        // For production, implement proper base58 decoding
-        // For now, use hex decoding if the key looks like hex
-        if key_data.len() == 64 { // 32 bytes * 2 hex chars = 64 chars
-            match hex::decode(key_data) {
-                Ok(decoded) => {
-                    if decoded.len() == 32 {
-                        return Ok(decoded);
-                    }
-                }
-                Err(_) => {}
-            }
-        }
-        
-        // Fallback: extract 32 bytes from the multibase string deterministically
-        let mut public_key = vec![0u8; 32];
-        let key_bytes = key_data.as_bytes();
-        for (i, &byte) in key_bytes.iter().take(32).enumerate() {
-            public_key[i] = byte;
-        }
-        
-        Ok(public_key)
+        crate::multibase::decode_ed25519_key(multibase_key)
+            .map(|key| key.to_vec())
+            .ok_or_else(|| {
+                msg!("Invalid multibase key: expected base58btc ('z') of 32 bytes");
+                error!(crate::common::errors::ValidationError::InvalidKey)
+            })
     }
     
-    /// Decode proof value from multibase format (production implementation)
+    /// Decode a base58btc multibase proof value into a 64-byte Ed25519 signature
     fn decode_proof_value(proof_value: &str) -> Result<Vec<u8>> {
-        if !proof_value.starts_with('z') {
-            msg!("Invalid proof value format: must start with 'z'");
-            return Err(error!(crate::common::errors::ValidationError::InvalidKey));
-        }
-        
-        // Remove 'z' prefix for base58btc decoding
-        let value_data = &proof_value[1..];
-        
-        // For production, implement proper base58 decoding
-        // For now, try hex decoding first
-        if value_data.len() == 64 { // 32 bytes * 2 hex chars = 64 chars (pubkey)
-            match hex::decode(value_data) {
-                Ok(decoded) => {
-                    if decoded.len() == 32 {
-                        // This is a pubkey, pad to 64 bytes for signature
-                        let mut signature = vec![0u8; 64];
-                        signature[..32].copy_from_slice(&decoded);
-                        return Ok(signature);
-                    }
-                }
-                Err(_) => {}
-            }
-        }
-        
-        if value_data.len() == 128 { // 64 bytes * 2 hex chars = 128 chars (signature)
-            match hex::decode(value_data) {
-                Ok(decoded) => {
-                    if decoded.len() == 64 {
-                        return Ok(decoded);
-                    }
-                }
-                Err(_) => {}
-            }
-        }
-        
-        // Fallback: create 64-byte signature deterministically
-        let mut signature = vec![0u8; 64];
-        let value_bytes = value_data.as_bytes();
-        for (i, &byte) in value_bytes.iter().take(64).enumerate() {
-            signature[i] = byte;
-        }
-        
-        Ok(signature)
+        crate::multibase::decode_signature(proof_value)
+            .map(|signature| signature.to_vec())
+            .ok_or_else(|| {
+                msg!("Invalid proof value: expected base58btc ('z') of 64 bytes");
+                error!(crate::common::errors::ValidationError::InvalidSignatureLength)
+            })
     }
 }
 
@@ -770,6 +603,23 @@ impl CredentialProofManager {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_utils::{ed25519_verify_instruction, with_instructions_sysvar, TestKeypair};
+
+    /// Verify `proof` in a transaction where the seed-1 test key's signature
+    /// over the proof's signature input was checked by the Ed25519 program
+    fn verify_with_signer_instruction(credential: &str, proof: &DataIntegrityProof, key: &str) -> Result<bool> {
+        let input = ProofSuite::signature_input(
+            credential,
+            &proof.created,
+            &proof.verification_method,
+            &proof.proof_purpose,
+            proof.challenge.as_deref(),
+            proof.domain.as_deref(),
+        );
+        with_instructions_sysvar(&[ed25519_verify_instruction(&TestKeypair::from_seed(1), &input)], |instructions| {
+            ProofSuite::verify_proof(credential, proof, key, None, None, instructions)
+        })
+    }
 
     #[test]
     fn test_multikey_generation() {
@@ -785,10 +635,8 @@ mod tests {
     
     #[test]
     fn test_proof_creation_and_verification() {
-        let key_pair = MultikeyPair::new_ed25519(
-            "https://example.com/issuers/1".to_string(),
-            "key-1".to_string()
-        ).unwrap();
+        let key_pair = crate::test_utils::TestKeypair::from_seed(1)
+            .multikey("https://example.com/issuers/1", "key-1");
         
         let credential = r#"{"@context":["https://www.w3.org/ns/credentials/v2","https://purl.imsglobal.org/spec/ob/v3p0/context-3.0.3.json"],"id":"https://example.com/credentials/123","type":["VerifiableCredential","OpenBadgeCredential"],"issuer":"https://example.com/issuers/1","validFrom":"2024-01-01T00:00:00Z","credentialSubject":{"id":"did:example:recipient","achievement":{"id":"https://example.com/achievements/1","type":["Achievement"],"name":"Test Achievement"}}}"#;
         
@@ -799,7 +647,9 @@ mod tests {
             credential,
             &key_pair,
             "assertionMethod",
-            &test_signer
+            &test_signer,
+            None,
+            None,
         ).unwrap();
         
         assert_eq!(proof.proof_type, "DataIntegrityProof");
@@ -809,11 +659,7 @@ mod tests {
         
         // Test verification
         let public_key_multibase = key_pair.public_key_multibase();
-        let verification_result = ProofSuite::verify_proof(
-            credential,
-            &proof,
-            &public_key_multibase
-        ).unwrap();
+        let verification_result = verify_with_signer_instruction(credential, &proof, &public_key_multibase).unwrap();
         
         assert!(verification_result);
     }
@@ -827,10 +673,8 @@ mod tests {
     
     #[test]
     fn test_cryptosuite_proof_creation_and_verification() {
-        let key_pair = MultikeyPair::new_ed25519(
-            "https://example.com/issuers/1".to_string(),
-            "key-1".to_string()
-        ).unwrap();
+        let key_pair = crate::test_utils::TestKeypair::from_seed(1)
+            .multikey("https://example.com/issuers/1", "key-1");
         
         let credential = r#"{"@context":["https://www.w3.org/ns/credentials/v2","https://purl.imsglobal.org/spec/ob/v3p0/context-3.0.3.json"],"id":"https://example.com/credentials/123","type":["VerifiableCredential","OpenBadgeCredential"],"issuer":"https://example.com/issuers/1","validFrom":"2024-01-01T00:00:00Z","credentialSubject":{"id":"did:example:recipient","achievement":{"id":"https://example.com/achievements/1","type":["Achievement"],"name":"Test Achievement"}}}"#;
         
@@ -843,6 +687,8 @@ mod tests {
             &key_pair,
             "assertionMethod",
             &test_signer,
+            None,
+            None,
         ).unwrap();
         
         assert_eq!(proof.proof_type, "DataIntegrityProof");
@@ -851,13 +697,98 @@ mod tests {
         assert!(proof.proof_value.starts_with('z'));
         
         // Test verification using ProofSuite
-        let verification_result = ProofSuite::verify_proof(
+        let verification_result = verify_with_signer_instruction(
             credential,
             &proof,
-            &format!("z{}", hex::encode(key_pair.solana_pubkey.to_bytes())),
+            &crate::multibase::encode(key_pair.solana_pubkey.as_ref()),
         );
         
         assert!(verification_result.is_ok());
         assert!(verification_result.unwrap());
     }
+    
+    #[test]
+    fn test_verifier_challenge_and_domain_must_match() {
+        let proof = DataIntegrityProof {
+            proof_type: "DataIntegrityProof".to_string(),
+            cryptosuite: "eddsa-rdfc-2022".to_string(),
+            created: "2024-01-01T00:00:00Z".to_string(),
+            verification_method: "did:sol:issuer#key-1".to_string(),
+            proof_purpose: "authentication".to_string(),
+            proof_value: crate::multibase::encode(&[7u8; 64]),
+            challenge: Some("nonce-1".to_string()),
+            domain: Some("verifier.example".to_string()),
+        };
+        
+        assert!(ProofSuite::proof_options_match(&proof, None, None));
+        assert!(ProofSuite::proof_options_match(&proof, Some("nonce-1"), Some("verifier.example")));
+        assert!(!ProofSuite::proof_options_match(&proof, Some("nonce-2"), None));
+        assert!(!ProofSuite::proof_options_match(&proof, None, Some("attacker.example")));
+        
+        let unbound = DataIntegrityProof { challenge: None, domain: None, ..proof.clone() };
+        assert!(!ProofSuite::proof_options_match(&unbound, Some("nonce-1"), None));
+        
+        let key = crate::multibase::encode(&[1u8; 32]);
+        let replayed = with_instructions_sysvar(&[], |instructions| {
+            ProofSuite::verify_proof("{}", &proof, &key, Some("nonce-2"), None, instructions)
+        });
+        assert!(!replayed.unwrap());
+    }
+    
+    #[test]
+    fn test_verify_proof_requires_an_ed25519_instruction_over_the_input() {
+        let signer = TestKeypair::from_seed(1);
+        let key = crate::multibase::encode(signer.pubkey().as_ref());
+        let mut proof = DataIntegrityProof {
+            proof_type: "DataIntegrityProof".to_string(),
+            cryptosuite: "eddsa-rdfc-2022".to_string(),
+            created: "2024-01-01T00:00:00Z".to_string(),
+            verification_method: "did:sol:issuer#key-1".to_string(),
+            proof_purpose: "assertionMethod".to_string(),
+            proof_value: String::new(),
+            challenge: None,
+            domain: None,
+        };
+        let input = ProofSuite::signature_input("{}", &proof.created, &proof.verification_method, &proof.proof_purpose, None, None);
+        proof.proof_value = crate::multibase::encode(&signer.sign(&input));
+        assert!(verify_with_signer_instruction("{}", &proof, &key).unwrap());
+        
+        // Nonzero bytes no Ed25519 instruction vouches for are not a proof
+        let forged = DataIntegrityProof { proof_value: crate::multibase::encode(&[7u8; 64]), ..proof.clone() };
+        assert!(!verify_with_signer_instruction("{}", &forged, &key).unwrap());
+        let unverified = with_instructions_sysvar(&[], |instructions| {
+            ProofSuite::verify_proof("{}", &proof, &key, None, None, instructions)
+        });
+        assert!(!unverified.unwrap());
+    }
+    
+    #[test]
+    fn test_ed25519_instruction_must_cover_key_message_and_signature() {
+        let authority = TestKeypair::from_seed(5);
+        let message = b"header.payload";
+        let signature = authority.sign(message);
+        let instruction = ed25519_verify_instruction(&authority, message);
+
+        assert!(ed25519_instruction_verifies(&instruction, &authority.pubkey(), message, &signature));
+        assert!(!ed25519_instruction_verifies(&instruction, &TestKeypair::from_seed(6).pubkey(), message, &signature));
+        assert!(!ed25519_instruction_verifies(&instruction, &authority.pubkey(), b"header.other", &signature));
+        assert!(!ed25519_instruction_verifies(&instruction, &authority.pubkey(), message, &[0u8; 64]));
+
+        let mut elsewhere = instruction.clone();
+        elsewhere.data[4..6].copy_from_slice(&0u16.to_le_bytes());
+        assert!(!ed25519_instruction_verifies(&elsewhere, &authority.pubkey(), message, &signature));
+
+        let mut other_program = instruction;
+        other_program.program_id = Pubkey::new_unique();
+        assert!(!ed25519_instruction_verifies(&other_program, &authority.pubkey(), message, &signature));
+    }
+
+    #[test]
+    fn test_challenge_and_domain_are_signed() {
+        let bound = ProofSuite::signature_input("{}", "t", "vm", "authentication", Some("nonce-1"), Some("a.example"));
+        let other = ProofSuite::signature_input("{}", "t", "vm", "authentication", Some("nonce-2"), Some("a.example"));
+        let unbound = ProofSuite::signature_input("{}", "t", "vm", "authentication", None, None);
+        assert_ne!(bound, other);
+        assert_ne!(bound, unbound);
+    }
 }
@@ -0,0 +1,142 @@
+//! Proof Sets and Proof Chains
+//!
+//! A credential carries the issuer's proof first, followed by proofs added by
+//! other signers the issuer authorized as verification methods (e.g. the
+//! registrar issues and the dean co-signs). A proof in a set signs the same
+//! credential JSON as the issuer's proof; a proof in a chain counter-signs:
+//! it signs the credential JSON followed by the `proofValue` of the proof it
+//! endorses, and names that proof in `previousProof`. Verifiers decide with a
+//! `ProofPolicy` whether every proof or any one of them has to hold.
+
+use anchor_lang::prelude::*;
+use crate::issuer_keys::IssuerKey;
+use crate::{AchievementCredential, Proof};
+
+/// Maximum proofs on one credential, the issuer's included
+pub const MAX_PROOFS: usize = 4;
+
+/// Upper bound of the serialized size of an added proof, without its `previous_proof`
+pub const ADDED_PROOF_SPACE: usize =
+    4 + 32 + 4 + 32 + 4 + 40 + 4 + 32 + 4 + 100 + 4 + 100 + 1;
+
+/// Which proofs of a credential must verify
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ProofPolicy {
+    /// Every proof verifies
+    #[default]
+    All,
+    /// At least one proof verifies
+    Any,
+}
+
+/// Id of the proof at `index`, referenced by `previousProof`
+pub fn proof_id(credential_id: &str, index: usize) -> String {
+    format!("{}#proof-{}", credential_id, index)
+}
+
+/// Message a new proof signs; a chained proof also covers the proof it endorses
+pub fn signing_message(signing_json: &str, previous: Option<&Proof>) -> Vec<u8> {
+    let mut message = signing_json.as_bytes().to_vec();
+    if let Some(previous) = previous {
+        message.extend_from_slice(previous.proof_value.as_bytes());
+    }
+    message
+}
+
+/// Proof of `credential` that a chained proof names in `previousProof`
+pub fn endorsed_proof<'a>(credential: &'a AchievementCredential, previous_proof: &str) -> Option<&'a Proof> {
+    credential
+        .proof
+        .iter()
+        .enumerate()
+        .find(|(index, _)| proof_id(&credential.id, *index) == previous_proof)
+        .map(|(_, proof)| proof)
+}
+
+/// Account size that fits `credential` with one more proof
+pub fn added_space(current_len: usize, credential: &AchievementCredential) -> usize {
+    current_len + ADDED_PROOF_SPACE + 4 + proof_id(&credential.id, MAX_PROOFS).len()
+}
+
+/// Issuer key among `keys` that `proof` names as its verification method
+pub fn key_for<'a>(proof: &Proof, keys: &[&'a IssuerKey]) -> Option<&'a IssuerKey> {
+    keys.iter().copied().find(|key| key.verification_method_id() == proof.verification_method)
+}
+
+/// Whether `proofs` satisfy `policy` under `check`; an empty set never does
+///
+/// Under `All` a failing check aborts; under `Any` it only disqualifies that proof.
+pub fn satisfies(
+    proofs: &[Proof],
+    policy: ProofPolicy,
+    mut check: impl FnMut(&Proof) -> Result<bool>,
+) -> Result<bool> {
+    if proofs.is_empty() {
+        return Ok(false);
+    }
+    match policy {
+        ProofPolicy::All => {
+            for proof in proofs {
+                if !check(proof)? {
+                    return Ok(false);
+                }
+            }
+            Ok(true)
+        }
+        ProofPolicy::Any => Ok(proofs.iter().any(|proof| check(proof).unwrap_or(false))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{fixture_credential, sign_credential, TestKeypair};
+
+    fn proof_with_value(value: &str) -> Proof {
+        let mut credential =
+            fixture_credential(&Pubkey::new_unique(), &Pubkey::new_unique(), &Pubkey::new_unique(), &Pubkey::new_unique());
+        sign_credential(&mut credential, &TestKeypair::from_seed(5));
+        Proof { proof_value: value.to_string(), ..credential.proof[0].clone() }
+    }
+
+    #[test]
+    fn test_policies() {
+        let proofs = vec![proof_with_value("zGood"), proof_with_value("")];
+        let check = |proof: &Proof| Ok(!proof.proof_value.is_empty());
+        assert!(!satisfies(&proofs, ProofPolicy::All, check).unwrap());
+        assert!(satisfies(&proofs, ProofPolicy::Any, check).unwrap());
+        assert!(satisfies(&proofs[..1], ProofPolicy::All, check).unwrap());
+        assert!(!satisfies(&[], ProofPolicy::Any, check).unwrap());
+
+        let failing = |proof: &Proof| {
+            require!(!proof.proof_value.is_empty(), crate::common::errors::ValidationError::VerificationMethodNotFound);
+            Ok(true)
+        };
+        assert!(satisfies(&proofs, ProofPolicy::All, failing).is_err());
+        assert!(satisfies(&proofs, ProofPolicy::Any, failing).unwrap());
+    }
+
+    #[test]
+    fn test_chained_message_covers_previous_proof() {
+        let previous = proof_with_value("zIssuer");
+        assert_eq!(signing_message("{}", None), b"{}");
+        assert_eq!(signing_message("{}", Some(&previous)), b"{}zIssuer");
+        assert_eq!(proof_id("did:sol:abc", 0), "did:sol:abc#proof-0");
+    }
+
+    #[test]
+    fn test_added_space_fits_another_proof() {
+        let mut credential =
+            fixture_credential(&Pubkey::new_unique(), &Pubkey::new_unique(), &Pubkey::new_unique(), &Pubkey::new_unique());
+        sign_credential(&mut credential, &TestKeypair::from_seed(6));
+        let current = 8 + credential.try_to_vec().unwrap().len();
+        let needed = added_space(current, &credential);
+
+        let mut proof = credential.proof[0].clone();
+        proof.verification_method = format!("did:sol:{}#key-1", Pubkey::new_unique());
+        proof.proof_value = format!("z{}", "1".repeat(88));
+        proof.previous_proof = Some(proof_id(&credential.id, 0));
+        credential.proof.push(proof);
+        assert!(8 + credential.try_to_vec().unwrap().len() <= needed);
+    }
+}
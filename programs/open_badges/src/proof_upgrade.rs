@@ -0,0 +1,119 @@
+//! Retroactive Proof Upgrades
+//!
+//! Early credentials carry proofs that external verifiers cannot check:
+//! hex-encoded values or signatures fabricated on-chain. The issuer can sign
+//! the credential again off-chain and attach the new proof; the old proof is
+//! kept on the account with the time it was deprecated so the history stays
+//! auditable without being presented as a valid proof. Only the issuer's
+//! proof, the first of the credential's proofs, is replaced; co-signatures stay.
+
+use anchor_lang::prelude::*;
+use crate::{AchievementCredential, Proof};
+
+/// Maximum number of deprecated proofs kept on one credential
+pub const MAX_DEPRECATED_PROOFS: usize = 2;
+
+/// Upper bound of the serialized size of an upgraded proof
+pub const UPGRADED_PROOF_SPACE: usize =
+    4 + 32 + 4 + 32 + 4 + 40 + 4 + 32 + 4 + 100 + 4 + 100 + 1;
+
+/// A replaced proof with its deprecation marker
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct DeprecatedProof {
+    /// The proof as it was attached
+    pub proof: Proof,
+    /// When the proof was replaced (ISO 8601 string)
+    pub deprecated_at: String,
+}
+
+/// Serialized size of `proof`
+pub fn proof_space(proof: &Proof) -> usize {
+    [
+        &proof.proof_type,
+        &proof.cryptosuite,
+        &proof.created,
+        &proof.proof_purpose,
+        &proof.verification_method,
+        &proof.proof_value,
+    ]
+    .iter()
+    .map(|field| 4 + field.len())
+    .sum::<usize>()
+        + 1
+        + proof.previous_proof.as_ref().map_or(0, |id| 4 + id.len())
+}
+
+/// Account size that fits `credential` after an upgrade
+/// Never shrinks: accounts are allocated with slack at issuance
+pub fn upgraded_space(current_len: usize, credential: &AchievementCredential) -> usize {
+    let serialized = 8 + credential.try_to_vec().map_or(current_len, |data| data.len());
+    let old_proof = credential.proof.first().map_or(0, proof_space);
+    let deprecated_entry = credential.proof.first().map_or(0, |proof| proof_space(proof) + 4 + 40);
+    current_len.max(serialized - old_proof + UPGRADED_PROOF_SPACE + deprecated_entry)
+}
+
+/// Move the issuer's current proof, if any, into the deprecated list and attach `proof`
+pub fn replace_proof(credential: &mut AchievementCredential, proof: Proof, now: String) -> Result<()> {
+    if credential.proof.is_empty() {
+        credential.proof.push(proof);
+        return Ok(());
+    }
+    if credential.deprecated_proofs.len() >= MAX_DEPRECATED_PROOFS {
+        return Err(error!(crate::common::errors::ValidationError::TooManyItems));
+    }
+    let old = std::mem::replace(&mut credential.proof[0], proof);
+    credential.deprecated_proofs.push(DeprecatedProof { proof: old, deprecated_at: now });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{fixture_credential, sign_credential, TestKeypair};
+
+    fn signed_credential() -> AchievementCredential {
+        let mut credential = fixture_credential(
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+        );
+        sign_credential(&mut credential, &TestKeypair::from_seed(4));
+        credential
+    }
+
+    #[test]
+    fn test_replace_keeps_old_proof_deprecated() {
+        let mut credential = signed_credential();
+        let old = credential.proof[0].clone();
+        let mut new = old.clone();
+        new.proof_value = "zNew".to_string();
+
+        replace_proof(&mut credential, new.clone(), "2025-01-01T00:00:00Z".to_string()).unwrap();
+        assert_eq!(credential.proof, vec![new]);
+        assert_eq!(credential.deprecated_proofs.len(), 1);
+        assert_eq!(credential.deprecated_proofs[0].proof, old);
+        assert_eq!(credential.deprecated_proofs[0].deprecated_at, "2025-01-01T00:00:00Z");
+    }
+
+    #[test]
+    fn test_deprecated_list_is_bounded() {
+        let mut credential = signed_credential();
+        let proof = credential.proof[0].clone();
+        for _ in 0..MAX_DEPRECATED_PROOFS {
+            replace_proof(&mut credential, proof.clone(), String::new()).unwrap();
+        }
+        assert!(replace_proof(&mut credential, proof.clone(), String::new()).is_err());
+        assert_eq!(credential.proof, vec![proof]);
+    }
+
+    #[test]
+    fn test_upgraded_space_fits_upgraded_credential() {
+        let mut credential = signed_credential();
+        let needed = upgraded_space(0, &credential);
+        let mut proof = credential.proof[0].clone();
+        proof.proof_value = format!("z{}", "1".repeat(88));
+        replace_proof(&mut credential, proof, "2025-01-01T00:00:00Z".to_string()).unwrap();
+        assert!(8 + credential.try_to_vec().unwrap().len() <= needed);
+    }
+}
@@ -0,0 +1,154 @@
+//! Issuance Rate Limit and Issuer Freeze
+//!
+//! A compromised issuer key could mass-issue junk credentials. An issuer can
+//! cap its issuance at `max_issuances` per window of `window_slots` slots;
+//! every issuance path that creates credentials counts them against the
+//! window stored on the Profile. The authority may set a limit or tighten it
+//! on its own, but loosening or removing one also needs the program admin's
+//! signature, so a stolen key cannot lift the cap. In an emergency the authority or the program admin
+//! can freeze the issuer, halting all issuance until the admin unfreezes it.
+
+use anchor_lang::prelude::*;
+use crate::common::errors::ValidationError;
+use crate::Profile;
+
+/// Issuances allowed per window of slots
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RateLimit {
+    /// Credentials the issuer may issue within one window
+    pub max_issuances: u32,
+    /// Window length in slots
+    pub window_slots: u64,
+}
+
+impl RateLimit {
+    /// Whether this limit allows nothing `other` does not
+    pub fn is_at_least_as_strict_as(&self, other: &RateLimit) -> bool {
+        self.max_issuances <= other.max_issuances && self.window_slots >= other.window_slots
+    }
+}
+
+/// A rate limit with the issuer's current window
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IssuanceLimit {
+    /// Configured limit
+    pub limit: RateLimit,
+    /// First slot of the current window
+    pub window_start: u64,
+    /// Credentials issued in the current window
+    pub issued_in_window: u32,
+}
+
+impl IssuanceLimit {
+    /// Serialized size of an optional limit
+    pub const SPACE: usize = 1 + 4 + 8 + 8 + 4;
+}
+
+/// Set or remove the issuer's rate limit
+/// Without the admin's signature the new limit must be at least as strict as the current one
+pub fn set_limit(profile: &mut Profile, limit: Option<RateLimit>, slot: u64, admin_signed: bool) -> Result<()> {
+    if limit.is_some_and(|limit| limit.max_issuances == 0 || limit.window_slots == 0) {
+        return Err(error!(ValidationError::InvalidRateLimit));
+    }
+    let tightened = match (profile.issuance_limit.as_ref(), limit.as_ref()) {
+        (None, _) => true,
+        (Some(_), None) => false,
+        (Some(current), Some(limit)) => limit.is_at_least_as_strict_as(&current.limit),
+    };
+    if !tightened && !admin_signed {
+        msg!("❌ Loosening the rate limit requires the program admin");
+        return Err(error!(ValidationError::UnauthorizedAccess));
+    }
+    // The current window carries over so a new limit cannot reset the count
+    let (window_start, issued_in_window) = profile
+        .issuance_limit
+        .map_or((slot, 0), |current| (current.window_start, current.issued_in_window));
+    profile.issuance_limit = limit.map(|limit| IssuanceLimit { limit, window_start, issued_in_window });
+    Ok(())
+}
+
+/// Fail if the issuer is frozen
+/// For paths with nothing to count: a claimed offer, whose issuance was already
+/// counted, or the batch paths, which do not create credentials yet
+pub fn ensure_not_frozen(profile: &Profile) -> Result<()> {
+    if profile.frozen {
+        msg!("❌ Issuer {} is frozen", profile.id);
+        return Err(error!(ValidationError::IssuerFrozen));
+    }
+    Ok(())
+}
+
+/// Count `count` issuances at `slot` against the issuer's freeze and rate limit
+pub fn record_issuance(profile: &mut Profile, count: u32, slot: u64) -> Result<()> {
+    ensure_not_frozen(profile)?;
+    let Some(window) = profile.issuance_limit.as_mut() else {
+        return Ok(());
+    };
+    if slot >= window.window_start.saturating_add(window.limit.window_slots) {
+        window.window_start = slot;
+        window.issued_in_window = 0;
+    }
+    let issued = window.issued_in_window.saturating_add(count);
+    if issued > window.limit.max_issuances {
+        msg!(
+            "❌ Issuer rate limit reached: {} of {} in {} slots",
+            window.issued_in_window,
+            window.limit.max_issuances,
+            window.limit.window_slots
+        );
+        return Err(error!(ValidationError::IssuanceRateLimited));
+    }
+    window.issued_in_window = issued;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::fixture_profile;
+
+    fn profile() -> Profile {
+        fixture_profile(&Pubkey::new_unique(), &Pubkey::new_unique(), "Example University")
+    }
+
+    fn limit(max_issuances: u32, window_slots: u64) -> Option<RateLimit> {
+        Some(RateLimit { max_issuances, window_slots })
+    }
+
+    #[test]
+    fn test_window_caps_issuance() {
+        let mut profile = profile();
+        assert!(record_issuance(&mut profile, 100, 0).is_ok());
+
+        set_limit(&mut profile, limit(3, 10), 100, false).unwrap();
+        assert!(record_issuance(&mut profile, 2, 100).is_ok());
+        assert!(record_issuance(&mut profile, 2, 105).is_err());
+        assert!(record_issuance(&mut profile, 1, 109).is_ok());
+        assert!(record_issuance(&mut profile, 1, 109).is_err());
+        assert!(record_issuance(&mut profile, 3, 110).is_ok());
+    }
+
+    #[test]
+    fn test_loosening_needs_the_admin() {
+        let mut profile = profile();
+        set_limit(&mut profile, limit(10, 100), 0, false).unwrap();
+        record_issuance(&mut profile, 5, 0).unwrap();
+
+        assert!(set_limit(&mut profile, limit(5, 200), 1, false).is_ok());
+        for looser in [limit(6, 200), limit(5, 100), None] {
+            assert!(set_limit(&mut profile, looser, 1, false).is_err());
+        }
+        set_limit(&mut profile, limit(20, 100), 1, true).unwrap();
+        assert_eq!(profile.issuance_limit.unwrap().issued_in_window, 5);
+        assert!(set_limit(&mut profile, limit(0, 100), 1, true).is_err());
+    }
+
+    #[test]
+    fn test_frozen_issuer_cannot_issue() {
+        let mut profile = profile();
+        assert!(ensure_not_frozen(&profile).is_ok());
+        profile.frozen = true;
+        assert!(ensure_not_frozen(&profile).is_err());
+        assert!(record_issuance(&mut profile, 1, 0).is_err());
+    }
+}
@@ -0,0 +1,163 @@
+//! Credential Re-binding
+//!
+//! A recipient who loses their wallet can have their credentials moved to a
+//! new DID. Re-binding needs both the issuer authority's signature and an
+//! Ed25519 signature by the new DID's key over a statement naming the
+//! credential and the new subject, verified through the Ed25519 program.
+//! The credential's subject id changes, every move is recorded in its
+//! `rebind_history`, and the original proof is kept: it still verifies
+//! against the subject id it was signed for, the first entry's
+//! `previous_subject_id`.
+
+use anchor_lang::prelude::*;
+use crate::common::errors::ValidationError;
+use crate::AchievementCredential;
+
+/// Re-bindings a credential can record
+pub const MAX_REBINDINGS: usize = 4;
+
+/// Maximum length of a new subject DID
+pub const MAX_SUBJECT_ID_LEN: usize = 128;
+
+/// Serialized size of an empty history
+pub const EMPTY_SPACE: usize = 4;
+
+/// Reserved size of an entry's ISO 8601 timestamp
+const TIMESTAMP_SPACE: usize = 4 + 32;
+
+/// One recorded re-binding
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct RebindEntry {
+    /// Subject id before the re-binding
+    pub previous_subject_id: String,
+    /// Subject id after the re-binding
+    pub new_subject_id: String,
+    /// When the credential was re-bound (ISO 8601 string)
+    pub timestamp: String,
+    /// Issuer authority that approved the re-binding
+    pub authorized_by: Pubkey,
+}
+
+/// Statement the new DID's key signs to accept a credential
+/// `rebinding` is the number of earlier re-bindings, so a statement cannot be replayed
+pub fn rebind_statement(credential: &Pubkey, rebinding: usize, new_subject_id: &str) -> String {
+    serde_json::json!({
+        "type": "CredentialRebinding",
+        "credential": format!("did:sol:{}", credential),
+        "rebinding": rebinding,
+        "newSubject": new_subject_id,
+    })
+    .to_string()
+}
+
+/// Subject id the credential's original proof was signed for
+pub fn original_subject_id(credential: &AchievementCredential) -> Option<String> {
+    match credential.rebind_history.first() {
+        Some(entry) => Some(entry.previous_subject_id.clone()),
+        None => credential.credential_subject.id.clone(),
+    }
+}
+
+/// Holder key the credential's original proof was signed for
+/// A bound credential is bound to the key of its original subject DID
+pub fn original_holder_binding(credential: &AchievementCredential) -> Option<Pubkey> {
+    let bound = credential.holder_binding?;
+    match credential.rebind_history.first() {
+        Some(entry) => entry.previous_subject_id.rsplit(':').next()?.parse().ok(),
+        None => Some(bound),
+    }
+}
+
+/// Bytes the credential account grows by when re-bound from `previous` to `new_subject_id`
+pub fn growth(previous: Option<&str>, new_subject_id: &str) -> usize {
+    let previous = previous.map_or(0, str::len);
+    4 + previous + 4 + new_subject_id.len() + TIMESTAMP_SPACE + 32 + new_subject_id.len().saturating_sub(previous)
+}
+
+/// Move the credential to `new_subject_id` and record the re-binding
+pub fn rebind(
+    credential: &mut AchievementCredential,
+    new_subject_id: String,
+    timestamp: String,
+    authorized_by: Pubkey,
+) -> Result<()> {
+    if credential.is_revoked || credential.is_expired {
+        msg!("❌ Credential {} is no longer in force", credential.id);
+        return Err(error!(ValidationError::InvalidStatusTransition));
+    }
+    if new_subject_id.len() > MAX_SUBJECT_ID_LEN {
+        return Err(error!(ValidationError::IdTooLong));
+    }
+    if credential.rebind_history.len() == MAX_REBINDINGS {
+        return Err(error!(ValidationError::TooManyItems));
+    }
+    let previous_subject_id = credential.credential_subject.id.clone().unwrap_or_default();
+    if previous_subject_id == new_subject_id {
+        return Err(error!(ValidationError::ValidationFailed));
+    }
+
+    credential.credential_subject.id = Some(new_subject_id.clone());
+    credential.rebind_history.push(RebindEntry { previous_subject_id, new_subject_id, timestamp, authorized_by });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::fixture_credential;
+
+    fn credential() -> AchievementCredential {
+        fixture_credential(&Pubkey::new_unique(), &Pubkey::new_unique(), &Pubkey::new_unique(), &Pubkey::new_unique())
+    }
+
+    fn rebind_to(credential: &mut AchievementCredential, subject: &str) -> Result<()> {
+        rebind(credential, subject.to_string(), "2025-01-01T00:00:00Z".to_string(), Pubkey::new_unique())
+    }
+
+    #[test]
+    fn test_original_subject_survives_rebinding() {
+        let mut credential = credential();
+        let original = credential.credential_subject.id.clone();
+        rebind_to(&mut credential, "did:sol:new-wallet").unwrap();
+        rebind_to(&mut credential, "did:sol:newer-wallet").unwrap();
+
+        assert_eq!(credential.credential_subject.id.as_deref(), Some("did:sol:newer-wallet"));
+        assert_eq!(credential.rebind_history[1].previous_subject_id, "did:sol:new-wallet");
+        assert_eq!(original_subject_id(&credential), original);
+        assert!(rebind_to(&mut credential, "did:sol:newer-wallet").is_err());
+    }
+
+    #[test]
+    fn test_rebinding_is_bounded() {
+        let mut credential = credential();
+        for index in 0..MAX_REBINDINGS {
+            rebind_to(&mut credential, &format!("did:sol:wallet-{}", index)).unwrap();
+        }
+        assert!(rebind_to(&mut credential, "did:sol:one-more").is_err());
+
+        let mut revoked = self::credential();
+        revoked.is_revoked = true;
+        assert!(rebind_to(&mut revoked, "did:sol:new-wallet").is_err());
+        assert!(rebind_to(&mut self::credential(), &"x".repeat(MAX_SUBJECT_ID_LEN + 1)).is_err());
+    }
+
+    #[test]
+    fn test_statement_names_the_rebinding() {
+        let key = Pubkey::new_unique();
+        let statement: serde_json::Value = serde_json::from_str(&rebind_statement(&key, 1, "did:sol:new")).unwrap();
+        assert_eq!(statement["credential"], format!("did:sol:{}", key));
+        assert_eq!(statement["newSubject"], "did:sol:new");
+        assert_ne!(rebind_statement(&key, 0, "did:sol:new"), rebind_statement(&key, 1, "did:sol:new"));
+    }
+
+    #[test]
+    fn test_growth_covers_the_entry() {
+        let mut credential = credential();
+        let previous = credential.credential_subject.id.clone();
+        let before = credential.try_to_vec().unwrap().len();
+        let new_subject = format!("did:key:z6Mk{}", "x".repeat(60));
+        rebind_to(&mut credential, &new_subject).unwrap();
+        let after = credential.try_to_vec().unwrap().len();
+        assert!(after - before <= growth(previous.as_deref(), &new_subject));
+    }
+}
@@ -0,0 +1,232 @@
+//! Achievement Recipient Roster
+//!
+//! Each achievement lists who has earned it, so an employer can enumerate
+//! the holders of a public badge from known addresses instead of scanning
+//! program accounts. A header PDA per achievement counts the holders, and
+//! fixed-size page PDAs `[RECIPIENT_ROSTER_SEED, achievement, page]` hold
+//! `MAX_PAGE_SIZE` entries each, so no account grows with the roster.
+//! Issuance creates the header with the achievement's first credential and
+//! appends one recipient and credential per issuance to the last page;
+//! `get_recipient_roster_page` serves one page account per call.
+//!
+//! Some achievements are sensitive (a health certification, a support
+//! programme). The issuer can disable tracking for them: the roster pages
+//! are closed and later issuances are no longer recorded.
+
+use anchor_lang::prelude::*;
+use crate::common::errors::ValidationError;
+
+/// Seed for the recipient roster PDAs: [RECIPIENT_ROSTER_SEED, achievement] for the header,
+/// [RECIPIENT_ROSTER_SEED, achievement, page (u32 LE)] for each page
+pub const RECIPIENT_ROSTER_SEED: &[u8] = b"recipient_roster";
+
+/// Entries per roster page, bounded by return data size
+pub const MAX_PAGE_SIZE: u8 = 12;
+
+/// Serialized size of one entry
+const ENTRY_SPACE: usize = 32 + 32;
+
+/// A recipient and the credential that made them a holder
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RosterEntry {
+    /// Recipient wallet
+    pub recipient: Pubkey,
+    /// Credential account issued to them
+    pub credential: Pubkey,
+}
+
+/// Header of one achievement's roster
+#[account]
+pub struct RecipientRoster {
+    /// Achievement the roster belongs to
+    pub achievement: Pubkey,
+    /// Whether the issuer turned tracking off for this achievement
+    pub tracking_disabled: bool,
+    /// Holders recorded across all pages
+    pub total: u32,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl RecipientRoster {
+    /// Discriminator + achievement + tracking flag + total + bump
+    pub const SPACE: usize = 8 + 32 + 1 + 4 + 1;
+
+    /// Page the next holder is appended to
+    pub fn next_page(&self) -> u32 {
+        self.total / MAX_PAGE_SIZE as u32
+    }
+
+    /// Pages holding at least one entry
+    pub fn page_count(&self) -> u32 {
+        self.total.div_ceil(MAX_PAGE_SIZE as u32)
+    }
+}
+
+/// Up to `MAX_PAGE_SIZE` holders of one achievement
+#[account]
+pub struct RecipientRosterPage {
+    /// Achievement the page belongs to
+    pub achievement: Pubkey,
+    /// Position of the page in the roster
+    pub page: u32,
+    /// Holders, oldest first
+    pub holders: Vec<RosterEntry>,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl RecipientRosterPage {
+    /// Discriminator + achievement + page + entries at capacity + bump
+    pub const SPACE: usize = 8 + 32 + 4 + 4 + ENTRY_SPACE * MAX_PAGE_SIZE as usize + 1;
+}
+
+/// One page of an achievement's holders
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct RosterPage {
+    /// Holders on this page
+    pub holders: Vec<RosterEntry>,
+    /// Holders in the whole roster
+    pub total: u32,
+    /// Whether a later page exists
+    pub has_more: bool,
+}
+
+/// Derive the roster page PDA for an achievement
+pub fn find_roster_page_address(achievement: &Pubkey, page: u32, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[RECIPIENT_ROSTER_SEED, achievement.as_ref(), &page.to_le_bytes()], program_id)
+}
+
+/// Append a holder to the roster's last page unless tracking is disabled
+/// `roster_page` must be the page PDA at `roster.next_page()`
+pub fn record(
+    roster: &mut RecipientRoster,
+    roster_page: &mut RecipientRosterPage,
+    achievement: Pubkey,
+    bumps: (u8, u8),
+    entry: RosterEntry,
+) -> Result<()> {
+    roster.achievement = achievement;
+    roster.bump = bumps.0;
+    if roster.tracking_disabled {
+        return Ok(());
+    }
+
+    roster_page.achievement = achievement;
+    roster_page.page = roster.next_page();
+    roster_page.bump = bumps.1;
+    if roster_page.holders.len() >= MAX_PAGE_SIZE as usize {
+        return Err(error!(ValidationError::TooManyItems));
+    }
+    roster_page.holders.push(entry);
+    roster.total = roster.total.checked_add(1).ok_or_else(|| error!(ValidationError::TooManyItems))?;
+    Ok(())
+}
+
+/// Turn tracking on or off; turning it off forgets the recorded holders
+/// The caller closes the roster pages before disabling
+pub fn set_tracking(roster: &mut RecipientRoster, achievement: Pubkey, bump: u8, enabled: bool) {
+    roster.achievement = achievement;
+    roster.bump = bump;
+    roster.tracking_disabled = !enabled;
+    if !enabled {
+        roster.total = 0;
+    }
+}
+
+/// Close every page of `roster`, passed in order in `pages`, refunding `recipient`
+pub fn close_pages<'info>(
+    roster: &RecipientRoster,
+    pages: &'info [AccountInfo<'info>],
+    recipient: &AccountInfo<'info>,
+    program_id: &Pubkey,
+) -> Result<()> {
+    if pages.len() != roster.page_count() as usize {
+        msg!("❌ Expected {} roster pages, got {}", roster.page_count(), pages.len());
+        return Err(error!(ValidationError::MissingRequiredField));
+    }
+    for (page, info) in (0u32..).zip(pages) {
+        let (expected, _) = find_roster_page_address(&roster.achievement, page, program_id);
+        if info.key() != expected {
+            return Err(error!(ValidationError::MissingRequiredField));
+        }
+        Account::<RecipientRosterPage>::try_from(info)?.close(recipient.clone())?;
+    }
+    Ok(())
+}
+
+/// View of one page account of `roster`
+pub fn page(roster: &RecipientRoster, roster_page: &RecipientRosterPage) -> RosterPage {
+    let end = (roster_page.page as u64 + 1) * MAX_PAGE_SIZE as u64;
+    RosterPage {
+        holders: roster_page.holders.clone(),
+        total: roster.total,
+        has_more: end < roster.total as u64,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry() -> RosterEntry {
+        RosterEntry { recipient: Pubkey::new_unique(), credential: Pubkey::new_unique() }
+    }
+
+    fn empty_page() -> RecipientRosterPage {
+        RecipientRosterPage { achievement: Pubkey::default(), page: 0, holders: vec![], bump: 0 }
+    }
+
+    #[test]
+    fn test_holders_fill_pages_in_order() {
+        let achievement = Pubkey::new_unique();
+        let mut roster = RecipientRoster { achievement, tracking_disabled: false, total: 0, bump: 255 };
+        let mut pages = [empty_page(), empty_page()];
+        let holders: Vec<RosterEntry> = (0..MAX_PAGE_SIZE as usize + 1).map(|_| entry()).collect();
+        for holder in &holders {
+            let page = roster.next_page() as usize;
+            record(&mut roster, &mut pages[page], achievement, (255, 254), *holder).unwrap();
+        }
+        assert_eq!(roster.total, MAX_PAGE_SIZE as u32 + 1);
+        assert_eq!(roster.page_count(), 2);
+
+        let first = page(&roster, &pages[0]);
+        assert!(first.try_to_vec().unwrap().len() <= 1024);
+        assert!(first.has_more);
+        assert_eq!(first.holders, holders[..MAX_PAGE_SIZE as usize].to_vec());
+        let second = page(&roster, &pages[1]);
+        assert_eq!(pages[1].page, 1);
+        assert_eq!(second.holders, holders[MAX_PAGE_SIZE as usize..].to_vec());
+        assert!(!second.has_more);
+
+        assert!(record(&mut roster, &mut pages[0], achievement, (255, 254), entry()).is_err());
+    }
+
+    #[test]
+    fn test_disabling_tracking_clears_holders() {
+        let achievement = Pubkey::new_unique();
+        let mut roster = RecipientRoster { achievement, tracking_disabled: false, total: 2, bump: 255 };
+        set_tracking(&mut roster, achievement, 255, false);
+        assert!(roster.tracking_disabled);
+        assert_eq!(roster.page_count(), 0);
+
+        let mut roster_page = empty_page();
+        record(&mut roster, &mut roster_page, achievement, (255, 254), entry()).unwrap();
+        assert!(roster_page.holders.is_empty());
+        set_tracking(&mut roster, achievement, 255, true);
+        assert!(!roster.tracking_disabled);
+    }
+
+    #[test]
+    fn test_space_matches_serialized_roster() {
+        let roster = RecipientRoster { achievement: Pubkey::new_unique(), tracking_disabled: false, total: 3, bump: 255 };
+        assert_eq!(crate::test_utils::account_data(&roster).len(), RecipientRoster::SPACE);
+        let full = RecipientRosterPage {
+            achievement: Pubkey::new_unique(),
+            page: 0,
+            holders: vec![entry(); MAX_PAGE_SIZE as usize],
+            bump: 255,
+        };
+        assert_eq!(crate::test_utils::account_data(&full).len(), RecipientRosterPage::SPACE);
+    }
+}
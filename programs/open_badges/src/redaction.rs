@@ -0,0 +1,191 @@
+//! Selective Field Redaction for JSON-LD Credentials
+//!
+//! Produces a redacted copy of a credential document together with a
+//! redaction manifest. Each redacted field is recorded as a salted SHA-256
+//! digest, so a verifier can confirm the field existed without seeing it,
+//! and the holder can later disclose the value and salt to prove it.
+//!
+//! Salts and digests are computed in the holder's wallet with `field_salt`
+//! and `redacted_field`; only the digests reach the program, which removes
+//! the fields and records them. A salt sent on-chain would let anyone who
+//! reads the transaction test guesses of a low-entropy value against its
+//! digest. The issuer's proof signs the full document, so the redacted copy
+//! carries no proof; its fields are checked against the credential account.
+//!
+//! This complements the SD-JWT path for consumers that need plain JSON-LD.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use base64::{Engine, engine::general_purpose};
+use serde::{Deserialize, Serialize};
+use crate::common::errors::ValidationError;
+
+/// Digest algorithm recorded in the manifest
+pub const REDACTION_ALGORITHM: &str = "sha-256";
+
+/// Fields that carry the credential structure and may not be redacted, nor may anything inside them
+pub const PROTECTED_PATHS: [&str; 6] = [
+    "/@context",
+    "/id",
+    "/type",
+    "/issuer",
+    "/proof",
+    "/credentialSubject/type",
+];
+
+/// A field removed from the exported document
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct RedactedField {
+    /// JSON Pointer (RFC 6901) of the removed field
+    pub path: String,
+
+    /// base64url SHA-256 digest of salt, path and JSON value
+    pub digest: String,
+}
+
+/// Manifest describing which fields were redacted
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RedactionManifest {
+    /// Digest algorithm, always "sha-256"
+    pub algorithm: String,
+
+    /// Redacted fields in the order they were requested
+    pub redacted: Vec<RedactedField>,
+}
+
+/// Redacted credential together with its manifest
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RedactedExport {
+    /// Credential document with the requested fields removed
+    pub credential: serde_json::Value,
+
+    /// Digests of the removed fields
+    #[serde(rename = "redactionManifest")]
+    pub redaction_manifest: RedactionManifest,
+}
+
+/// Per-field salt derived from the holder's secret seed
+///
+/// Salting stops verifiers from guessing low-entropy values from the digest.
+/// Computed off-chain; the seed is never sent to the program.
+pub fn field_salt(salt_seed: &[u8], path: &str) -> String {
+    let salt = hashv(&[b"open-badges/redaction-salt", salt_seed, path.as_bytes()]);
+    general_purpose::URL_SAFE_NO_PAD.encode(salt.to_bytes())
+}
+
+/// Digest committing to a field's path and value
+pub fn field_digest(salt: &str, path: &str, value: &serde_json::Value) -> Result<String> {
+    let value_json = serde_json::to_string(value)
+        .map_err(|_| error!(ValidationError::SerializationError))?;
+    let digest = hashv(&[salt.as_bytes(), path.as_bytes(), value_json.as_bytes()]);
+    Ok(general_purpose::URL_SAFE_NO_PAD.encode(digest.to_bytes()))
+}
+
+/// Whether a path is a protected field or lies inside one
+pub fn is_protected(path: &str) -> bool {
+    PROTECTED_PATHS.iter().any(|protected| {
+        path.strip_prefix(protected).is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+    })
+}
+
+/// Manifest entry for redacting the field at `path` of a credential with `salt`
+/// Run by the holder, who keeps `salt` to disclose the value later
+pub fn redacted_field(credential: &serde_json::Value, path: &str, salt: &str) -> Result<RedactedField> {
+    let value = credential
+        .pointer(path)
+        .ok_or_else(|| error!(ValidationError::MissingRequiredField))?;
+    Ok(RedactedField { path: path.to_string(), digest: field_digest(salt, path, value)? })
+}
+
+/// Remove the fields of the manifest entries from a credential and drop its proof
+/// The digests are the holder's; the program cannot check them without the
+/// salts, and a wrong digest only makes the holder's own disclosure fail
+pub fn redact_credential(credential: &serde_json::Value, fields: &[RedactedField]) -> Result<RedactedExport> {
+    let mut redacted_doc = credential.clone();
+
+    for field in fields {
+        let path = &field.path;
+        if is_protected(path) {
+            msg!("❌ Field {} cannot be redacted", path);
+            return Err(error!(ValidationError::ValidationFailed));
+        }
+
+        let (parent_path, key) = path
+            .rsplit_once('/')
+            .ok_or_else(|| error!(ValidationError::InvalidJson))?;
+        let key = key.replace("~1", "/").replace("~0", "~");
+
+        redacted_doc
+            .pointer_mut(parent_path)
+            .and_then(|parent| parent.as_object_mut())
+            .and_then(|parent| parent.remove(&key))
+            .ok_or_else(|| error!(ValidationError::MissingRequiredField))?;
+    }
+
+    // The proof signs the unredacted document and would no longer verify
+    if let Some(document) = redacted_doc.as_object_mut() {
+        document.remove("proof");
+    }
+
+    Ok(RedactedExport {
+        credential: redacted_doc,
+        redaction_manifest: RedactionManifest {
+            algorithm: REDACTION_ALGORITHM.to_string(),
+            redacted: fields.to_vec(),
+        },
+    })
+}
+
+/// Check a holder's disclosure of a redacted field against the manifest
+pub fn verify_disclosure(
+    manifest: &RedactionManifest,
+    path: &str,
+    salt: &str,
+    value: &serde_json::Value,
+) -> Result<bool> {
+    let Some(entry) = manifest.redacted.iter().find(|field| field.path == path) else {
+        return Ok(false);
+    };
+    Ok(entry.digest == field_digest(salt, path, value)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_and_disclose_identity_hash() {
+        let credential = serde_json::json!({
+            "@context": ["https://www.w3.org/ns/credentials/v2"],
+            "type": ["VerifiableCredential", "OpenBadgeCredential"],
+            "credentialSubject": {
+                "type": ["AchievementSubject"],
+                "identifier": [{ "identityHash": "learner@example.edu" }]
+            },
+            "proof": [{ "type": "DataIntegrityProof", "proofValue": "z3sig" }]
+        });
+        let path = "/credentialSubject/identifier/0/identityHash";
+        let salt = field_salt(&[7u8; 32], path);
+
+        let field = redacted_field(&credential, path, &salt).unwrap();
+        let export = redact_credential(&credential, &[field]).unwrap();
+        assert!(export.credential.pointer(path).is_none());
+        assert!(export.credential.get("proof").is_none());
+        assert_eq!(export.redaction_manifest.redacted.len(), 1);
+
+        let value = serde_json::json!("learner@example.edu");
+        assert!(verify_disclosure(&export.redaction_manifest, path, &salt, &value).unwrap());
+        let wrong = serde_json::json!("someone@example.edu");
+        assert!(!verify_disclosure(&export.redaction_manifest, path, &salt, &wrong).unwrap());
+    }
+
+    #[test]
+    fn test_protected_fields_cannot_be_redacted() {
+        let credential = serde_json::json!({ "type": ["VerifiableCredential"], "proof": [{ "proofValue": "z3sig" }] });
+        for path in ["/type", "/proof", "/proof/0/proofValue"] {
+            let field = RedactedField { path: path.to_string(), digest: String::new() };
+            assert!(redact_credential(&credential, &[field]).is_err());
+        }
+        assert!(!is_protected("/typeOf"));
+    }
+}
@@ -0,0 +1,194 @@
+//! Refresh Service and Re-issuance
+//!
+//! An issuer can publish a refresh service URL on its Profile; generated
+//! credentials then carry a `refreshService` entry telling holders where to
+//! obtain an updated credential once theirs expires. On-chain, the issuer
+//! answers with `reissue_credential`: a fresh credential account with new
+//! validity dates, derived from the expired credential's address and
+//! pointing back at it through `related`, so each refresh extends a chain
+//! that verifiers can walk from any version to the previous one.
+
+use anchor_lang::prelude::*;
+use crate::common::errors::ValidationError;
+use crate::AchievementCredential;
+
+/// Refresh service type defined by Open Badges 3.0
+pub const REFRESH_SERVICE_TYPE: &str = "1EdTechCredentialRefresh";
+
+/// Maximum length of an issuer's refresh service URL
+pub const MAX_REFRESH_SERVICE_URL_LEN: usize = 200;
+
+/// Longest ISO 8601 timestamp stored for reissued validity dates
+pub const MAX_TIMESTAMP_LEN: usize = 40;
+
+/// Check a refresh service URL: HTTPS with a host
+pub fn validate_refresh_service_url(url: &str) -> Result<()> {
+    if url.len() > MAX_REFRESH_SERVICE_URL_LEN {
+        return Err(error!(ValidationError::UrlTooLong));
+    }
+    if url.strip_prefix("https://").unwrap_or_default().is_empty() {
+        msg!("❌ Refresh service must be an https URL: {}", url);
+        return Err(error!(ValidationError::InvalidUrl));
+    }
+    Ok(())
+}
+
+/// Serialized size of a stored refresh service URL
+pub fn space_of(url: &Option<String>) -> usize {
+    1 + url.as_ref().map_or(0, |url| 4 + url.len())
+}
+
+/// Set `refreshService` on a credential document when the issuer has one
+pub fn apply_to_json(document: &mut serde_json::Value, url: Option<&str>) {
+    if let Some(url) = url {
+        document["refreshService"] = serde_json::json!({
+            "id": url,
+            "type": REFRESH_SERVICE_TYPE,
+        });
+    }
+}
+
+/// Check that `previous` may be reissued at `now`: expired and not revoked
+pub fn ensure_reissuable(previous: &AchievementCredential, now: i64) -> Result<()> {
+    if previous.is_revoked {
+        msg!("❌ Revoked credentials cannot be reissued");
+        return Err(error!(ValidationError::ValidationFailed));
+    }
+    if !crate::expiration::has_expired(previous, now)? {
+        msg!("❌ Only expired credentials can be reissued: {}", previous.id);
+        return Err(error!(ValidationError::ValidationFailed));
+    }
+    Ok(())
+}
+
+/// Check new validity dates: parseable, bounded, and ending after they start
+pub fn validate_validity(valid_from: &str, valid_until: Option<&str>) -> Result<()> {
+    for timestamp in std::iter::once(valid_from).chain(valid_until) {
+        if timestamp.len() > MAX_TIMESTAMP_LEN {
+            return Err(error!(ValidationError::FieldTooLong));
+        }
+    }
+    let start = crate::parse_iso8601_to_unix(valid_from)?;
+    if let Some(valid_until) = valid_until {
+        if crate::parse_iso8601_to_unix(valid_until)? <= start {
+            return Err(error!(ValidationError::InvalidTimestampFormat));
+        }
+    }
+    Ok(())
+}
+
+/// Account size for a reissue of `previous`
+/// Its current size plus the back-reference and room for longer dates
+pub fn reissued_space(previous_len: usize, previous: &AchievementCredential) -> usize {
+    previous_len + 4 + previous.id.len() + 1 + 4 + 2 * MAX_TIMESTAMP_LEN
+}
+
+/// Unsigned successor of `previous` with fresh validity dates
+///
+/// The subject, results, context, extensions, notes and schemas carry over; the proof,
+/// revocation state, deprecated proofs and baked image do not.
+pub fn reissue(
+    previous: &AchievementCredential,
+    id: String,
+    valid_from: String,
+    valid_until: Option<String>,
+    issued_at: String,
+) -> AchievementCredential {
+    AchievementCredential {
+        sequence: 0,
+        id,
+        context: previous.context.clone(),
+        r#type: previous.r#type.clone(),
+        issuer: previous.issuer,
+        valid_from,
+        valid_until,
+        issued_at,
+        credential_subject: previous.credential_subject.clone(),
+        proof: vec![],
+        is_revoked: false,
+        revoked_at: None,
+        revocation_reason: None,
+        revoked_by: Pubkey::default(),
+        deprecated_proofs: vec![],
+        extensions: previous.extensions.clone(),
+        baked_image: None,
+        related: vec![previous.id.clone()],
+        notes: previous.notes.clone(),
+        is_expired: false,
+        credential_schema: previous.credential_schema.clone(),
+        holder_binding: previous.holder_binding,
+        status: crate::lifecycle::CredentialState::Active,
+        status_history: vec![],
+        rebind_history: vec![],
+        terms_of_use: previous.terms_of_use.clone(),
+        superseded_by: None,
+        encrypted_payload: None,
+        bump: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{fixture_credential, sign_credential, TestKeypair};
+
+    fn expired_credential() -> AchievementCredential {
+        let mut credential = fixture_credential(
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+        );
+        credential.valid_until = Some("2024-06-01T00:00:00Z".to_string());
+        sign_credential(&mut credential, &TestKeypair::from_seed(5));
+        credential
+    }
+
+    #[test]
+    fn test_only_expired_unrevoked_credentials_are_reissuable() {
+        let mut credential = expired_credential();
+        let june_2024 = crate::parse_iso8601_to_unix("2024-06-01T00:00:00Z").unwrap();
+        assert!(ensure_reissuable(&credential, june_2024 - 1).is_err());
+        assert!(ensure_reissuable(&credential, june_2024 + 1).is_ok());
+
+        credential.is_revoked = true;
+        assert!(ensure_reissuable(&credential, june_2024 + 1).is_err());
+        credential.is_revoked = false;
+        credential.valid_until = None;
+        assert!(ensure_reissuable(&credential, i64::MAX).is_err());
+    }
+
+    #[test]
+    fn test_reissue_links_back_and_fits() {
+        let previous = expired_credential();
+        let previous_len = crate::test_utils::account_data(&previous).len();
+        let mut next = reissue(
+            &previous,
+            format!("did:sol:{}", Pubkey::new_unique()),
+            "2024-06-02T00:00:00+00:00".to_string(),
+            Some("2025-06-02T00:00:00+00:00".to_string()),
+            "2024-06-02T00:00:00+00:00".to_string(),
+        );
+        assert_eq!(next.related, vec![previous.id.clone()]);
+        assert_eq!(next.credential_subject.id, previous.credential_subject.id);
+        assert!(next.proof.is_empty());
+
+        sign_credential(&mut next, &TestKeypair::from_seed(5));
+        assert!(crate::test_utils::account_data(&next).len() <= reissued_space(previous_len, &previous));
+    }
+
+    #[test]
+    fn test_refresh_service_json_and_validation() {
+        let mut document = serde_json::json!({"id": "urn:uuid:1"});
+        apply_to_json(&mut document, None);
+        assert!(document.get("refreshService").is_none());
+        apply_to_json(&mut document, Some("https://issuer.example/refresh"));
+        assert_eq!(document["refreshService"]["type"], REFRESH_SERVICE_TYPE);
+
+        assert!(validate_refresh_service_url("https://issuer.example/refresh").is_ok());
+        assert!(validate_refresh_service_url("http://issuer.example/refresh").is_err());
+        assert!(validate_refresh_service_url("https://").is_err());
+        assert!(validate_validity("2025-01-01T00:00:00Z", Some("2024-01-01T00:00:00Z")).is_err());
+        assert!(validate_validity("2025-01-01T00:00:00Z", None).is_ok());
+    }
+}
@@ -0,0 +1,364 @@
+//! Graded Achievement Results
+//!
+//! Achievements can declare `resultDescription`s (a score range, a set of
+//! allowed values or a rubric level) and credentials carry the `result`s the
+//! recipient obtained. Results are checked against the declared descriptions
+//! and, for level-based results, against the achievement's rubric.
+
+use anchor_lang::prelude::*;
+use crate::common::errors::ValidationError;
+use crate::rubric::Rubric;
+
+/// Maximum number of result descriptions on an achievement
+pub const MAX_RESULT_DESCRIPTIONS: usize = 4;
+
+/// Maximum number of results on a credential
+pub const MAX_RESULTS: usize = 4;
+
+/// Maximum number of allowed values in a result description
+pub const MAX_ALLOWED_VALUES: usize = 8;
+
+/// Maximum length of result ids, names, values and level ids
+pub const MAX_RESULT_FIELD_LEN: usize = 64;
+
+/// Kind of result, per the OB 3.0 ResultType enumeration
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ResultType {
+    GradePointAverage,
+    LetterGrade,
+    Percent,
+    PerformanceLevel,
+    PredictedScore,
+    RawScore,
+    Result,
+    RubricCriterion,
+    RubricCriterionLevel,
+    RubricScore,
+    ScaledScore,
+    Status,
+}
+
+impl ResultType {
+    /// Name used in credential JSON
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ResultType::GradePointAverage => "GradePointAverage",
+            ResultType::LetterGrade => "LetterGrade",
+            ResultType::Percent => "Percent",
+            ResultType::PerformanceLevel => "PerformanceLevel",
+            ResultType::PredictedScore => "PredictedScore",
+            ResultType::RawScore => "RawScore",
+            ResultType::Result => "Result",
+            ResultType::RubricCriterion => "RubricCriterion",
+            ResultType::RubricCriterionLevel => "RubricCriterionLevel",
+            ResultType::RubricScore => "RubricScore",
+            ResultType::ScaledScore => "ScaledScore",
+            ResultType::Status => "Status",
+        }
+    }
+
+    /// Whether results of this type are expressed as a rubric level
+    pub fn is_level(&self) -> bool {
+        matches!(self, ResultType::PerformanceLevel | ResultType::RubricCriterionLevel)
+    }
+}
+
+/// Result an achievement can award
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct ResultDescription {
+    /// Identifier referenced by results, unique within the achievement
+    pub id: String,
+    /// Name of the result (e.g. "Final exam score")
+    pub name: String,
+    /// Kind of result
+    pub result_type: ResultType,
+    /// Ordered values a result may take; empty allows any value
+    pub allowed_values: Vec<String>,
+    /// Value required to earn the achievement, if any
+    pub required_value: Option<String>,
+    /// Lowest numeric value, if bounded
+    pub value_min: Option<String>,
+    /// Highest numeric value, if bounded
+    pub value_max: Option<String>,
+}
+
+impl ResultDescription {
+    /// Serialized size of this description
+    pub fn space(&self) -> usize {
+        let optional = |value: &Option<String>| 1 + value.as_ref().map_or(0, |v| 4 + v.len());
+        4 + self.id.len()
+            + 4 + self.name.len()
+            + 1
+            + 4 + self.allowed_values.iter().map(|v| 4 + v.len()).sum::<usize>()
+            + optional(&self.required_value)
+            + optional(&self.value_min)
+            + optional(&self.value_max)
+    }
+
+    /// Serialize as an OB 3.0 ResultDescription
+    pub fn to_json(&self) -> serde_json::Value {
+        let mut description = serde_json::json!({
+            "id": self.id,
+            "type": ["ResultDescription"],
+            "name": self.name,
+            "resultType": self.result_type.as_str(),
+        });
+        if !self.allowed_values.is_empty() {
+            description["allowedValue"] = serde_json::json!(self.allowed_values);
+        }
+        if let Some(required_value) = &self.required_value {
+            description["requiredValue"] = serde_json::json!(required_value);
+        }
+        if let Some(value_min) = &self.value_min {
+            description["valueMin"] = serde_json::json!(value_min);
+        }
+        if let Some(value_max) = &self.value_max {
+            description["valueMax"] = serde_json::json!(value_max);
+        }
+        description
+    }
+}
+
+/// Result obtained by the recipient, as passed to issuance and stored on the credential
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct ResultInput {
+    /// Id of the achievement's result description
+    pub result_description: String,
+    /// Value obtained (score, grade, status)
+    pub value: Option<String>,
+    /// Id of the rubric level achieved
+    pub achieved_level: Option<String>,
+}
+
+impl ResultInput {
+    /// Space for a result at maximum size
+    pub const SPACE: usize = 4 + MAX_RESULT_FIELD_LEN + 2 * (1 + 4 + MAX_RESULT_FIELD_LEN);
+
+    /// Serialize as an OB 3.0 Result
+    pub fn to_json(&self) -> serde_json::Value {
+        let mut result = serde_json::json!({
+            "type": ["Result"],
+            "resultDescription": self.result_description,
+        });
+        if let Some(value) = &self.value {
+            result["value"] = serde_json::json!(value);
+        }
+        if let Some(level) = &self.achieved_level {
+            result["achievedLevel"] = serde_json::json!(level);
+        }
+        result
+    }
+}
+
+/// Serialized size of the results stored on a credential
+pub fn space(results: &[ResultInput]) -> usize {
+    let optional = |value: &Option<String>| 1 + value.as_ref().map_or(0, |v| 4 + v.len());
+    4 + results
+        .iter()
+        .map(|result| 4 + result.result_description.len() + optional(&result.value) + optional(&result.achieved_level))
+        .sum::<usize>()
+}
+
+fn parse_number(value: &str) -> Result<f64> {
+    value.parse::<f64>().map_err(|_| error!(ValidationError::ResultValueNotAllowed))
+}
+
+/// Check the result descriptions declared by a new achievement
+pub fn validate_result_descriptions(descriptions: &[ResultDescription]) -> Result<()> {
+    if descriptions.len() > MAX_RESULT_DESCRIPTIONS {
+        return Err(error!(ValidationError::TooManyItems));
+    }
+
+    for (index, description) in descriptions.iter().enumerate() {
+        if description.id.is_empty() || description.name.is_empty() {
+            return Err(error!(ValidationError::MissingRequiredField));
+        }
+        if description.allowed_values.len() > MAX_ALLOWED_VALUES {
+            return Err(error!(ValidationError::TooManyItems));
+        }
+        let too_long = description.id.len() > MAX_RESULT_FIELD_LEN
+            || description.name.len() > MAX_RESULT_FIELD_LEN
+            || description.allowed_values.iter().any(|v| v.len() > MAX_RESULT_FIELD_LEN)
+            || [&description.required_value, &description.value_min, &description.value_max]
+                .iter()
+                .any(|v| v.as_ref().is_some_and(|v| v.len() > MAX_RESULT_FIELD_LEN));
+        if too_long {
+            return Err(error!(ValidationError::FieldTooLong));
+        }
+        if descriptions[..index].iter().any(|other| other.id == description.id) {
+            return Err(error!(ValidationError::ValidationFailed));
+        }
+        if let (Some(min), Some(max)) = (&description.value_min, &description.value_max) {
+            if parse_number(min)? > parse_number(max)? {
+                return Err(error!(ValidationError::ValidationFailed));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Check one result against its description and the achievement's rubric
+fn validate_result(result: &ResultInput, description: &ResultDescription, rubric: Option<&Rubric>) -> Result<()> {
+    if let Some(value) = &result.value {
+        if value.len() > MAX_RESULT_FIELD_LEN {
+            return Err(error!(ValidationError::FieldTooLong));
+        }
+        if !description.allowed_values.is_empty() && !description.allowed_values.contains(value) {
+            msg!("❌ Result value '{}' not allowed for {}", value, description.id);
+            return Err(error!(ValidationError::ResultValueNotAllowed));
+        }
+        if description.value_min.is_some() || description.value_max.is_some() {
+            let number = parse_number(value)?;
+            let below = match &description.value_min {
+                Some(min) => number < parse_number(min)?,
+                None => false,
+            };
+            let above = match &description.value_max {
+                Some(max) => number > parse_number(max)?,
+                None => false,
+            };
+            if below || above {
+                msg!("❌ Result value {} outside the range of {}", value, description.id);
+                return Err(error!(ValidationError::ResultValueNotAllowed));
+            }
+        }
+    }
+
+    match &result.achieved_level {
+        Some(level) => {
+            let Some(rubric) = rubric else {
+                return Err(error!(ValidationError::UnknownAchievedLevel));
+            };
+            if !rubric.levels.iter().any(|rubric_level| &rubric_level.id == level) {
+                msg!("❌ Level '{}' is not part of the rubric", level);
+                return Err(error!(ValidationError::UnknownAchievedLevel));
+            }
+        }
+        None if description.result_type.is_level() => {
+            return Err(error!(ValidationError::MissingRequiredField));
+        }
+        None if result.value.is_none() => {
+            return Err(error!(ValidationError::MissingRequiredField));
+        }
+        None => {}
+    }
+
+    Ok(())
+}
+
+/// Check the results supplied at issuance against the achievement's declarations
+pub fn validate_results(
+    results: &[ResultInput],
+    descriptions: &[ResultDescription],
+    rubric: Option<&Rubric>,
+) -> Result<()> {
+    if results.len() > MAX_RESULTS {
+        return Err(error!(ValidationError::TooManyItems));
+    }
+
+    for (index, result) in results.iter().enumerate() {
+        let Some(description) = descriptions.iter().find(|d| d.id == result.result_description) else {
+            msg!("❌ Unknown result description: {}", result.result_description);
+            return Err(error!(ValidationError::UnknownResultDescription));
+        };
+        if results[..index].iter().any(|other| other.result_description == result.result_description) {
+            return Err(error!(ValidationError::ValidationFailed));
+        }
+        validate_result(result, description, rubric)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rubric::RubricLevel;
+
+    fn score() -> ResultDescription {
+        ResultDescription {
+            id: "score".to_string(),
+            name: "Final score".to_string(),
+            result_type: ResultType::Percent,
+            allowed_values: vec![],
+            required_value: Some("50".to_string()),
+            value_min: Some("0".to_string()),
+            value_max: Some("100".to_string()),
+        }
+    }
+
+    fn level() -> ResultDescription {
+        ResultDescription {
+            id: "level".to_string(),
+            name: "Performance".to_string(),
+            result_type: ResultType::RubricCriterionLevel,
+            allowed_values: vec![],
+            required_value: None,
+            value_min: None,
+            value_max: None,
+        }
+    }
+
+    fn rubric() -> Rubric {
+        Rubric {
+            achievement: Pubkey::new_unique(),
+            title: "Rubric".to_string(),
+            levels: vec![RubricLevel {
+                id: "proficient".to_string(),
+                name: "Proficient".to_string(),
+                descriptor: "Meets expectations".to_string(),
+                points: 3,
+            }],
+            updated_at: "2024-01-01T00:00:00Z".to_string(),
+            bump: 255,
+        }
+    }
+
+    fn result(description: &str, value: Option<&str>, level: Option<&str>) -> ResultInput {
+        ResultInput {
+            result_description: description.to_string(),
+            value: value.map(str::to_string),
+            achieved_level: level.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_space_matches_serialized_description() {
+        let description = score();
+        assert_eq!(description.space(), description.try_to_vec().unwrap().len());
+        let max = result(&"a".repeat(64), Some(&"b".repeat(64)), Some(&"c".repeat(64)));
+        assert_eq!(ResultInput::SPACE, max.try_to_vec().unwrap().len());
+        let results = vec![max, result("pass", None, None)];
+        assert_eq!(space(&results), results.try_to_vec().unwrap().len());
+    }
+
+    #[test]
+    fn test_numeric_range() {
+        let descriptions = [score()];
+        assert!(validate_results(&[result("score", Some("87.5"), None)], &descriptions, None).is_ok());
+        assert!(validate_results(&[result("score", Some("101"), None)], &descriptions, None).is_err());
+        assert!(validate_results(&[result("score", Some("high"), None)], &descriptions, None).is_err());
+        assert!(validate_results(&[result("grade", Some("50"), None)], &descriptions, None).is_err());
+    }
+
+    #[test]
+    fn test_levels_checked_against_rubric() {
+        let descriptions = [level()];
+        let rubric = rubric();
+        assert!(validate_results(&[result("level", None, Some("proficient"))], &descriptions, Some(&rubric)).is_ok());
+        assert!(validate_results(&[result("level", None, Some("expert"))], &descriptions, Some(&rubric)).is_err());
+        assert!(validate_results(&[result("level", None, Some("proficient"))], &descriptions, None).is_err());
+        assert!(validate_results(&[result("level", None, None)], &descriptions, Some(&rubric)).is_err());
+    }
+
+    #[test]
+    fn test_descriptions_rejected_when_inverted_or_duplicated() {
+        let mut inverted = score();
+        inverted.value_min = Some("10".to_string());
+        inverted.value_max = Some("5".to_string());
+        assert!(validate_result_descriptions(&[inverted]).is_err());
+        assert!(validate_result_descriptions(&[score(), score()]).is_err());
+        assert!(validate_result_descriptions(&[score(), level()]).is_ok());
+    }
+}
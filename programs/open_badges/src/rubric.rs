@@ -0,0 +1,116 @@
+//! Achievement Criteria Rubrics
+//!
+//! A rubric describes the grading levels of an achievement's criteria so
+//! assessment platforms can read them without parsing narrative text. It is
+//! stored in its own PDA next to the Achievement and serialized as a
+//! criteria extension in generated credential JSON.
+
+use anchor_lang::prelude::*;
+use crate::common::errors::ValidationError;
+
+/// Seed prefix for rubric PDAs
+pub const RUBRIC_SEED: &[u8] = b"rubric";
+
+/// Maximum number of levels in a rubric
+pub const MAX_RUBRIC_LEVELS: usize = 10;
+
+/// Maximum length of a rubric title, level id or level name
+pub const MAX_RUBRIC_LABEL_LEN: usize = 64;
+
+/// Maximum length of a level descriptor
+pub const MAX_RUBRIC_DESCRIPTOR_LEN: usize = 256;
+
+/// One performance level of a rubric
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct RubricLevel {
+    /// Identifier of the level, unique within the rubric
+    pub id: String,
+    /// Short name of the level (e.g. "Proficient")
+    pub name: String,
+    /// Descriptor of the performance expected at this level
+    pub descriptor: String,
+    /// Points awarded at this level
+    pub points: u32,
+}
+
+/// Grading rubric attached to an Achievement
+#[account]
+pub struct Rubric {
+    /// Achievement this rubric grades
+    pub achievement: Pubkey,
+    /// Title of the rubric
+    pub title: String,
+    /// Levels ordered from lowest to highest
+    pub levels: Vec<RubricLevel>,
+    /// Last update timestamp (ISO 8601 string)
+    pub updated_at: String,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl Rubric {
+    /// Space for a rubric at maximum size
+    pub const SPACE: usize = 8
+        + 32
+        + 4 + MAX_RUBRIC_LABEL_LEN
+        + 4 + MAX_RUBRIC_LEVELS * (4 + MAX_RUBRIC_LABEL_LEN + 4 + MAX_RUBRIC_LABEL_LEN + 4 + MAX_RUBRIC_DESCRIPTOR_LEN + 4)
+        + 4 + 40
+        + 1;
+
+    /// Highest number of points available
+    pub fn max_points(&self) -> u32 {
+        self.levels.iter().map(|level| level.points).max().unwrap_or(0)
+    }
+
+    /// Serialize as an Open Badges criteria extension
+    pub fn to_extension_json(&self) -> serde_json::Value {
+        let levels: Vec<serde_json::Value> = self.levels.iter().map(|level| {
+            serde_json::json!({
+                "id": level.id,
+                "name": level.name,
+                "description": level.descriptor,
+                "points": level.points,
+            })
+        }).collect();
+
+        serde_json::json!({
+            "type": ["Extension", "RubricExtension"],
+            "title": self.title,
+            "levels": levels,
+            "maxPoints": self.max_points(),
+        })
+    }
+}
+
+/// Check rubric contents against the stored size limits
+pub fn validate_rubric(title: &str, levels: &[RubricLevel]) -> Result<()> {
+    if title.is_empty() || levels.is_empty() {
+        return Err(error!(ValidationError::MissingRequiredField));
+    }
+    if levels.len() > MAX_RUBRIC_LEVELS {
+        return Err(error!(ValidationError::TooManyItems));
+    }
+    if title.len() > MAX_RUBRIC_LABEL_LEN {
+        return Err(error!(ValidationError::NameTooLong));
+    }
+
+    for (index, level) in levels.iter().enumerate() {
+        if level.id.is_empty() || level.name.is_empty() || level.descriptor.is_empty() {
+            return Err(error!(ValidationError::MissingRequiredField));
+        }
+        if level.id.len() > MAX_RUBRIC_LABEL_LEN {
+            return Err(error!(ValidationError::IdTooLong));
+        }
+        if level.name.len() > MAX_RUBRIC_LABEL_LEN {
+            return Err(error!(ValidationError::NameTooLong));
+        }
+        if level.descriptor.len() > MAX_RUBRIC_DESCRIPTOR_LEN {
+            return Err(error!(ValidationError::DescriptionTooLong));
+        }
+        if levels[..index].iter().any(|other| other.id == level.id) {
+            return Err(error!(ValidationError::ValidationFailed));
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,184 @@
+//! Credential Schemas
+//!
+//! Issuers register the JSON schemas their credentials conform to in a
+//! per-issuer `SchemaRegistry` PDA, each with the SHA-256 digest of the
+//! schema document. Issuance attaches registered schemas by id, and the
+//! credential JSON publishes them as `credentialSchema` entries carrying the
+//! digest as `digestMultibase`, so a verifier (or
+//! `validate_credential_compliance`) can check that a declared schema is the
+//! one the issuer registered.
+
+use anchor_lang::prelude::*;
+use crate::common::errors::ValidationError;
+
+/// Seed for the schema registry PDA: [SCHEMA_REGISTRY_SEED, issuer]
+pub const SCHEMA_REGISTRY_SEED: &[u8] = b"schema_registry";
+
+/// `type` of the published `credentialSchema` entries
+pub const JSON_SCHEMA_TYPE: &str = "1EdTechJsonSchemaValidator2019";
+
+/// Maximum schemas one issuer can register
+pub const MAX_REGISTERED_SCHEMAS: usize = 8;
+
+/// Maximum schemas attached to one credential
+pub const MAX_CREDENTIAL_SCHEMAS: usize = 4;
+
+/// Maximum length of a schema URI
+pub const MAX_SCHEMA_URI_LEN: usize = 200;
+
+/// Serialized size of an empty schema list
+pub const EMPTY_SPACE: usize = 4;
+
+/// A JSON schema and the digest of its document
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct CredentialSchema {
+    /// URI the schema document is published at
+    pub id: String,
+    /// SHA-256 digest of the schema document
+    pub digest: [u8; 32],
+}
+
+impl CredentialSchema {
+    /// `digestMultibase` value of the digest (base58btc)
+    pub fn digest_multibase(&self) -> String {
+        crate::multibase::encode(&self.digest)
+    }
+
+    /// `credentialSchema` entry for the credential JSON
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "id": self.id,
+            "type": JSON_SCHEMA_TYPE,
+            "digestMultibase": self.digest_multibase(),
+        })
+    }
+}
+
+/// Schemas registered by an issuer
+#[account]
+pub struct SchemaRegistry {
+    /// Issuer profile the schemas belong to
+    pub issuer: Pubkey,
+    /// Registered schemas
+    pub schemas: Vec<CredentialSchema>,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl SchemaRegistry {
+    pub const SPACE: usize = 8 + 32 + 4 + MAX_REGISTERED_SCHEMAS * (4 + MAX_SCHEMA_URI_LEN + 32) + 1;
+
+    /// Registered schema with URI `id`
+    pub fn find(&self, id: &str) -> Option<&CredentialSchema> {
+        self.schemas.iter().find(|schema| schema.id == id)
+    }
+
+    /// Register a schema, binding the account on first use
+    /// Registering a known URI again replaces its digest
+    pub fn register(&mut self, issuer: Pubkey, bump: u8, id: String, digest: [u8; 32]) -> Result<()> {
+        require!(id.len() <= MAX_SCHEMA_URI_LEN, ValidationError::IdTooLong);
+        require!(id.starts_with("https://") || id.starts_with("did:"), ValidationError::InvalidUrl);
+        self.issuer = issuer;
+        self.bump = bump;
+
+        if let Some(schema) = self.schemas.iter_mut().find(|schema| schema.id == id) {
+            schema.digest = digest;
+            return Ok(());
+        }
+        require!(self.schemas.len() < MAX_REGISTERED_SCHEMAS, ValidationError::TooManyItems);
+        self.schemas.push(CredentialSchema { id, digest });
+        Ok(())
+    }
+}
+
+/// Look up the schemas a credential attaches in the issuer's registry
+pub fn resolve(registry: Option<&SchemaRegistry>, ids: &[String]) -> Result<Vec<CredentialSchema>> {
+    if ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    require!(ids.len() <= MAX_CREDENTIAL_SCHEMAS, ValidationError::TooManyItems);
+    let Some(registry) = registry else {
+        msg!("❌ Attaching credential schemas requires the issuer's schema registry");
+        return Err(error!(ValidationError::MissingRequiredField));
+    };
+    ids.iter()
+        .map(|id| {
+            registry.find(id).cloned().ok_or_else(|| {
+                msg!("❌ Schema not registered: {}", id);
+                error!(ValidationError::InvalidCredentialSchema)
+            })
+        })
+        .collect()
+}
+
+/// Serialized size of the schemas attached by `ids`
+pub fn space(ids: &[String]) -> usize {
+    4 + ids.iter().map(|id| 4 + id.len() + 32).sum::<usize>()
+}
+
+/// Check every `credentialSchema` entry a credential document declares against the registry
+pub fn verify_declared(document: &serde_json::Value, registry: Option<&SchemaRegistry>) -> Result<()> {
+    let declared = match document.get("credentialSchema") {
+        None => return Ok(()),
+        Some(serde_json::Value::Array(entries)) => entries.iter().collect::<Vec<_>>(),
+        Some(entry) => vec![entry],
+    };
+    let Some(registry) = registry else {
+        msg!("❌ credentialSchema declared but no schema registry supplied");
+        return Err(error!(ValidationError::InvalidCredentialSchema));
+    };
+    for entry in declared {
+        let id = entry.get("id").and_then(|id| id.as_str()).unwrap_or_default();
+        let digest = entry.get("digestMultibase").and_then(|digest| digest.as_str());
+        match registry.find(id) {
+            Some(schema) if digest == Some(schema.digest_multibase().as_str()) => {}
+            _ => {
+                msg!("❌ credentialSchema {} does not match the registry", id);
+                return Err(error!(ValidationError::InvalidCredentialSchema));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCHEMA_URI: &str = "https://example.com/schemas/course.json";
+
+    fn registry() -> SchemaRegistry {
+        let mut registry = SchemaRegistry { issuer: Pubkey::default(), schemas: vec![], bump: 0 };
+        registry.register(Pubkey::new_unique(), 255, SCHEMA_URI.to_string(), [7u8; 32]).unwrap();
+        registry
+    }
+
+    #[test]
+    fn test_register_and_resolve() {
+        let mut registry = registry();
+        registry.register(registry.issuer, 255, SCHEMA_URI.to_string(), [8u8; 32]).unwrap();
+        assert_eq!(registry.schemas.len(), 1);
+        assert!(registry.register(registry.issuer, 255, "ftp://example.com/s.json".to_string(), [0u8; 32]).is_err());
+
+        let resolved = resolve(Some(&registry), &[SCHEMA_URI.to_string()]).unwrap();
+        assert_eq!(resolved[0].digest, [8u8; 32]);
+        assert!(resolve(Some(&registry), &["https://example.com/other.json".to_string()]).is_err());
+        assert!(resolve(None, &[SCHEMA_URI.to_string()]).is_err());
+        assert!(resolve(None, &[]).unwrap().is_empty());
+        assert_eq!(space(&[SCHEMA_URI.to_string()]), resolved.try_to_vec().unwrap().len());
+        assert!(crate::test_utils::account_data(&registry).len() <= SchemaRegistry::SPACE);
+    }
+
+    #[test]
+    fn test_declared_schema_must_match_registry() {
+        let registry = registry();
+        let entry = registry.schemas[0].to_json();
+        assert!(verify_declared(&serde_json::json!({ "credentialSchema": [entry.clone()] }), Some(&registry)).is_ok());
+        assert!(verify_declared(&serde_json::json!({}), None).is_ok());
+        assert!(verify_declared(&serde_json::json!({ "credentialSchema": entry.clone() }), None).is_err());
+
+        let mut tampered = entry;
+        tampered["digestMultibase"] = serde_json::json!(CredentialSchema { id: SCHEMA_URI.to_string(), digest: [9u8; 32] }.digest_multibase());
+        assert!(verify_declared(&serde_json::json!({ "credentialSchema": [tampered] }), Some(&registry)).is_err());
+    }
+}
@@ -0,0 +1,123 @@
+//! Scoped Authorizations for Services
+//!
+//! An institution often runs its badge issuance through a service (an LMS
+//! plugin, a registrar's backend) that should not hold the issuer
+//! authority. The authority grants the service's key a `ScopedAuthorization`
+//! instead, in the manner of an OAuth access token: a set of scopes, an
+//! optional list of the achievements they cover, and an optional expiry.
+//! The delegate instructions check the grant before acting and act in the
+//! issuer's name; the authority withdraws a grant by closing it.
+//!
+//! `Scope::Issue` corresponds to the Open Badges API `credential.upsert`
+//! scope. Credentials issued by a delegate carry no proof, as with CPI
+//! issuance: the issuer's grant is the authorization.
+
+use anchor_lang::prelude::*;
+use crate::common::errors::ValidationError;
+
+/// Seed prefix for scoped authorization PDAs: [SCOPED_AUTHORIZATION_SEED, issuer, service]
+pub const SCOPED_AUTHORIZATION_SEED: &[u8] = b"scoped_authorization";
+
+/// Maximum achievements one grant can be limited to
+pub const MAX_SCOPED_ACHIEVEMENTS: usize = 8;
+
+/// Action a grant allows
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Scope {
+    /// Issue credentials for the covered achievements
+    Issue,
+    /// Revoke credentials of the covered achievements
+    Revoke,
+}
+
+/// Scopes an issuer granted one service key
+#[account]
+pub struct ScopedAuthorization {
+    /// Issuer profile that made the grant
+    pub issuer: Pubkey,
+    /// Service key the grant is for
+    pub service: Pubkey,
+    /// Granted scopes
+    pub scopes: Vec<Scope>,
+    /// Achievements covered; empty for every achievement of the issuer
+    pub achievements: Vec<Pubkey>,
+    /// Unix time after which the grant no longer applies, `None` for no expiry
+    pub expires_at: Option<i64>,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl ScopedAuthorization {
+    /// Account size for a grant of `scopes` limited to `achievements`
+    pub fn space(scopes: &[Scope], achievements: &[Pubkey]) -> usize {
+        8 + 32 + 32 + 4 + scopes.len() + 4 + 32 * achievements.len() + 1 + 8 + 1
+    }
+
+    /// Fail unless the grant allows `scope` on `achievement` at `now`
+    pub fn ensure_permits(&self, scope: Scope, achievement: &Pubkey, now: i64) -> Result<()> {
+        if self.expires_at.is_some_and(|expires_at| now > expires_at) {
+            msg!("❌ Authorization of {} expired at {:?}", self.service, self.expires_at);
+            return Err(error!(ValidationError::AuthorizationExpired));
+        }
+        let covers = self.achievements.is_empty() || self.achievements.contains(achievement);
+        if !self.scopes.contains(&scope) || !covers {
+            msg!("❌ {} is not granted {:?} on {}", self.service, scope, achievement);
+            return Err(error!(ValidationError::ScopeNotGranted));
+        }
+        Ok(())
+    }
+}
+
+/// Check a grant: at least one distinct scope, a bounded achievement list and a future expiry
+pub fn validate(scopes: &[Scope], achievements: &[Pubkey], expires_at: Option<i64>, now: i64) -> Result<()> {
+    let repeated = scopes.iter().enumerate().any(|(index, scope)| scopes[..index].contains(scope));
+    if scopes.is_empty() || repeated || expires_at.is_some_and(|expires_at| expires_at <= now) {
+        return Err(error!(ValidationError::InvalidScopedAuthorization));
+    }
+    if achievements.len() > MAX_SCOPED_ACHIEVEMENTS {
+        return Err(error!(ValidationError::TooManyItems));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grant(scopes: Vec<Scope>, achievements: Vec<Pubkey>, expires_at: Option<i64>) -> ScopedAuthorization {
+        ScopedAuthorization {
+            issuer: Pubkey::new_unique(),
+            service: Pubkey::new_unique(),
+            scopes,
+            achievements,
+            expires_at,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn test_grants_are_limited_by_scope_achievement_and_expiry() {
+        let achievement = Pubkey::new_unique();
+        let issue_only = grant(vec![Scope::Issue], vec![achievement], Some(1_000));
+        assert!(issue_only.ensure_permits(Scope::Issue, &achievement, 1_000).is_ok());
+        assert!(issue_only.ensure_permits(Scope::Revoke, &achievement, 500).is_err());
+        assert!(issue_only.ensure_permits(Scope::Issue, &Pubkey::new_unique(), 500).is_err());
+        assert!(issue_only.ensure_permits(Scope::Issue, &achievement, 1_001).is_err());
+
+        let every_achievement = grant(vec![Scope::Issue, Scope::Revoke], vec![], None);
+        assert!(every_achievement.ensure_permits(Scope::Revoke, &Pubkey::new_unique(), i64::MAX).is_ok());
+        assert_eq!(
+            issue_only.try_to_vec().unwrap().len() + 8,
+            ScopedAuthorization::space(&issue_only.scopes, &issue_only.achievements)
+        );
+    }
+
+    #[test]
+    fn test_grant_validation() {
+        assert!(validate(&[Scope::Issue], &[], Some(101), 100).is_ok());
+        assert!(validate(&[], &[], None, 100).is_err());
+        assert!(validate(&[Scope::Issue, Scope::Issue], &[], None, 100).is_err());
+        assert!(validate(&[Scope::Issue], &[], Some(100), 100).is_err());
+        assert!(validate(&[Scope::Issue], &[Pubkey::new_unique(); MAX_SCOPED_ACHIEVEMENTS + 1], None, 100).is_err());
+    }
+}
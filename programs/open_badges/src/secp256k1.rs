@@ -0,0 +1,253 @@
+//! secp256k1 (ES256K) Proofs
+//!
+//! Institutions anchored on EVM chains sign with secp256k1 keys identified by
+//! their Ethereum address. Their signatures are checked by introspecting a
+//! secp256k1 program instruction in the same transaction, as Ed25519
+//! signatures are with the Ed25519 program. The precompile recovers the
+//! signer over the Keccak-256 digest of the message, so proofs and ES256K
+//! JWTs produced here carry signatures over Keccak-256, as EVM signers
+//! produce them. Signers are named by their did:ethr DID.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::secp256k1_program;
+use crate::common::errors::ValidationError;
+use crate::proof::{DataIntegrityProof, ProofSuite};
+
+/// secp256k1 signature verification program
+pub const SECP256K1_PROGRAM_ID: Pubkey = secp256k1_program::ID;
+
+/// Cryptosuite of Data Integrity proofs signed with secp256k1
+pub const CRYPTOSUITE: &str = "ecdsa-secp256k1-2019";
+
+/// JWS `alg` of JWTs signed with secp256k1
+pub const JWT_ALGORITHM: &str = "ES256K";
+
+/// DID method prefix naming Ethereum accounts
+pub const ETHR_DID_PREFIX: &str = "did:ethr:";
+
+/// Signature offsets entry of a secp256k1 program instruction
+/// Instruction indices are absolute positions in the transaction
+#[derive(Clone, Debug)]
+pub struct Secp256k1SignatureOffsets {
+    pub signature_offset: u16,
+    pub signature_instruction_index: u8,
+    pub eth_address_offset: u16,
+    pub eth_address_instruction_index: u8,
+    pub message_data_offset: u16,
+    pub message_data_size: u16,
+    pub message_instruction_index: u8,
+}
+
+impl Secp256k1SignatureOffsets {
+    /// Serialized size of one offsets entry
+    pub const LEN: usize = 11;
+
+    fn parse(entry: &[u8]) -> Self {
+        let field = |i: usize| u16::from_le_bytes([entry[i], entry[i + 1]]);
+        Self {
+            signature_offset: field(0),
+            signature_instruction_index: entry[2],
+            eth_address_offset: field(3),
+            eth_address_instruction_index: entry[5],
+            message_data_offset: field(6),
+            message_data_size: field(8),
+            message_instruction_index: entry[10],
+        }
+    }
+
+    fn is_self_contained(&self, index: u8) -> bool {
+        [self.signature_instruction_index, self.eth_address_instruction_index, self.message_instruction_index]
+            .iter()
+            .all(|&entry_index| entry_index == index)
+    }
+}
+
+/// An EVM signer's signature submitted for a proof
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct Secp256k1Signature {
+    /// Ethereum address of the signer
+    pub eth_address: [u8; 20],
+    /// Proof creation time (ISO 8601) the signer committed to
+    pub created: String,
+    /// Compact (r, s) signature
+    pub signature: [u8; 64],
+}
+
+/// did:ethr DID of an Ethereum address
+pub fn ethr_did(eth_address: &[u8; 20]) -> String {
+    format!("{}0x{}", ETHR_DID_PREFIX, hex::encode(eth_address))
+}
+
+/// Ethereum address from a `0x` address or a did:ethr DID or DID URL
+/// A network segment (`did:ethr:sepolia:0x…`) is accepted and ignored
+pub fn parse_eth_address(value: &str) -> Result<[u8; 20]> {
+    let did = value.split('#').next().unwrap_or_default();
+    let address = match did.strip_prefix(ETHR_DID_PREFIX) {
+        Some(identifier) => identifier.rsplit(':').next().unwrap_or_default(),
+        None => did,
+    };
+    address
+        .strip_prefix("0x")
+        .and_then(|hex_address| hex::decode(hex_address).ok())
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| error!(ValidationError::InvalidKeyEncoding))
+}
+
+/// Whether the secp256k1 program instruction at `index` checks `signature`
+/// by `eth_address` over `message`
+///
+/// Only entries whose data lives in the instruction itself are considered;
+/// the runtime has already rejected the transaction if any entry failed.
+pub fn secp256k1_instruction_verifies(
+    instruction: &anchor_lang::solana_program::instruction::Instruction,
+    index: u8,
+    eth_address: &[u8; 20],
+    message: &[u8],
+    signature: &[u8; 64],
+) -> bool {
+    if instruction.program_id != SECP256K1_PROGRAM_ID {
+        return false;
+    }
+    let data = &instruction.data;
+    let count = data.first().copied().unwrap_or(0) as usize;
+    let slice = |offset: u16, len: usize| data.get(offset as usize..offset as usize + len);
+
+    (0..count).any(|i| {
+        let start = 1 + i * Secp256k1SignatureOffsets::LEN;
+        let Some(entry) = data.get(start..start + Secp256k1SignatureOffsets::LEN) else {
+            return false;
+        };
+        let offsets = Secp256k1SignatureOffsets::parse(entry);
+        offsets.is_self_contained(index)
+            && slice(offsets.eth_address_offset, 20) == Some(eth_address.as_slice())
+            && slice(offsets.signature_offset, 64) == Some(signature.as_slice())
+            && slice(offsets.message_data_offset, offsets.message_data_size as usize) == Some(message)
+    })
+}
+
+/// Fail with `InvalidSignature` unless an earlier instruction of this
+/// transaction has the secp256k1 program verify `signature` over `message`
+pub fn ensure_secp256k1_verified(
+    instructions: &AccountInfo,
+    eth_address: &[u8; 20],
+    message: &[u8],
+    signature: &[u8; 64],
+) -> Result<()> {
+    use anchor_lang::solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
+
+    let current = load_current_index_checked(instructions)? as usize;
+    for index in 0..current.min(u8::MAX as usize + 1) {
+        let instruction = load_instruction_at_checked(index, instructions)?;
+        if secp256k1_instruction_verifies(&instruction, index as u8, eth_address, message, signature) {
+            return Ok(());
+        }
+    }
+    msg!("❌ No secp256k1 program instruction verifies the signature for {}", ethr_did(eth_address));
+    Err(error!(ValidationError::InvalidSignature))
+}
+
+/// Build an `ecdsa-secp256k1-2019` proof from a signature the transaction verifies
+pub fn create_proof(
+    credential_json: &str,
+    signature: &Secp256k1Signature,
+    key_id: &str,
+    proof_purpose: &str,
+    challenge: Option<&str>,
+    domain: Option<&str>,
+    instructions: &AccountInfo,
+) -> Result<DataIntegrityProof> {
+    crate::parse_iso8601_to_unix(&signature.created)?;
+    let verification_method = format!("{}#{}", ethr_did(&signature.eth_address), key_id);
+    let signature_input = ProofSuite::signature_input(
+        credential_json,
+        &signature.created,
+        &verification_method,
+        proof_purpose,
+        challenge,
+        domain,
+    );
+    ensure_secp256k1_verified(instructions, &signature.eth_address, &signature_input, &signature.signature)?;
+
+    Ok(DataIntegrityProof {
+        proof_type: "DataIntegrityProof".to_string(),
+        cryptosuite: CRYPTOSUITE.to_string(),
+        created: signature.created.clone(),
+        verification_method,
+        proof_purpose: proof_purpose.to_string(),
+        proof_value: crate::multibase::encode(&signature.signature),
+        challenge: challenge.map(str::to_string),
+        domain: domain.map(str::to_string),
+    })
+}
+
+/// Verify an `ecdsa-secp256k1-2019` proof by `eth_address`
+pub fn verify_proof(
+    credential_json: &str,
+    proof: &DataIntegrityProof,
+    eth_address: &[u8; 20],
+    expected_challenge: Option<&str>,
+    expected_domain: Option<&str>,
+    instructions: &AccountInfo,
+) -> Result<bool> {
+    if proof.proof_type != "DataIntegrityProof" || proof.cryptosuite != CRYPTOSUITE {
+        msg!("❌ Not an {} proof", CRYPTOSUITE);
+        return Ok(false);
+    }
+    if parse_eth_address(&proof.verification_method)? != *eth_address {
+        msg!("❌ Proof verification method {} is not {}", proof.verification_method, ethr_did(eth_address));
+        return Ok(false);
+    }
+    if !ProofSuite::proof_options_match(proof, expected_challenge, expected_domain) {
+        msg!("❌ Proof challenge/domain does not match the verifier's");
+        return Ok(false);
+    }
+
+    let signature = crate::multibase::decode_signature(&proof.proof_value)
+        .ok_or_else(|| error!(ValidationError::InvalidSignatureLength))?;
+    let signature_input = ProofSuite::signature_input(
+        credential_json,
+        &proof.created,
+        &proof.verification_method,
+        &proof.proof_purpose,
+        proof.challenge.as_deref(),
+        proof.domain.as_deref(),
+    );
+    Ok(ensure_secp256k1_verified(instructions, eth_address, &signature_input, &signature).is_ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::secp256k1_verify_instruction;
+
+    const ADDRESS: [u8; 20] = [0xab; 20];
+
+    #[test]
+    fn test_eth_addresses_parse_from_dids() {
+        let did = ethr_did(&ADDRESS);
+        assert_eq!(did, format!("did:ethr:0x{}", "ab".repeat(20)));
+        assert_eq!(parse_eth_address(&did).unwrap(), ADDRESS);
+        assert_eq!(parse_eth_address(&format!("{}#controller", did)).unwrap(), ADDRESS);
+        assert_eq!(parse_eth_address(&format!("did:ethr:sepolia:0x{}", "ab".repeat(20))).unwrap(), ADDRESS);
+        assert_eq!(parse_eth_address(&format!("0x{}", "ab".repeat(20))).unwrap(), ADDRESS);
+        assert!(parse_eth_address("did:ethr:0xabcd").is_err());
+        assert!(parse_eth_address(&"ab".repeat(20)).is_err());
+    }
+
+    #[test]
+    fn test_secp256k1_instruction_must_cover_address_message_and_signature() {
+        let message = b"header.payload";
+        let signature = [7u8; 64];
+        let instruction = secp256k1_verify_instruction(&ADDRESS, message, &signature, 0, 2);
+
+        assert!(secp256k1_instruction_verifies(&instruction, 2, &ADDRESS, message, &signature));
+        assert!(!secp256k1_instruction_verifies(&instruction, 1, &ADDRESS, message, &signature));
+        assert!(!secp256k1_instruction_verifies(&instruction, 2, &[0xcd; 20], message, &signature));
+        assert!(!secp256k1_instruction_verifies(&instruction, 2, &ADDRESS, b"header.other", &signature));
+        assert!(!secp256k1_instruction_verifies(&instruction, 2, &ADDRESS, message, &[0u8; 64]));
+
+        let mut other_program = instruction;
+        other_program.program_id = crate::proof::ED25519_PROGRAM_ID;
+        assert!(!secp256k1_instruction_verifies(&other_program, 2, &ADDRESS, message, &signature));
+    }
+}
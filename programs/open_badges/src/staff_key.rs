@@ -0,0 +1,127 @@
+//! Issuer Staff Keys
+//!
+//! An institution can let staff members sign credentials for its Profile
+//! with their own did:sol keys. Two records make a staff key trusted:
+//! the issuer listed as controller of the staff member's DID, which the
+//! staff member alone can write through sol_did, and a `StaffKey` PDA of
+//! this program, which only `set_staff_did_controller` creates, with both
+//! the issuer's authority and the staff member signing. Verification
+//! requires both, so a DID that names an issuer as controller without the
+//! issuer's consent signs for nobody. `revoke_staff_key` closes the PDA.
+
+use anchor_lang::prelude::*;
+
+/// Seed prefix for the per-issuer, per-staff-key PDA
+pub const STAFF_KEY_SEED: &[u8] = b"staff_key";
+
+/// Account recording that an issuer accepts proofs by a staff member's key
+#[account]
+pub struct StaffKey {
+    /// Issuer profile the staff key signs for
+    pub issuer: Pubkey,
+
+    /// Staff member's signing key, authority of their did:sol DID
+    pub staff: Pubkey,
+
+    /// When the issuer authorized the key (ISO 8601 string)
+    pub authorized_at: String,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl StaffKey {
+    /// Discriminator + issuer + staff + ISO 8601 timestamp + bump
+    pub const SPACE: usize = 8 + 32 + 32 + 4 + 40 + 1;
+}
+
+/// Derive the staff key PDA for an issuer profile and staff key
+pub fn find_staff_key_address(issuer: &Pubkey, staff: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[STAFF_KEY_SEED, issuer.as_ref(), staff.as_ref()], program_id)
+}
+
+/// Whether `signer` is a staff key of the issuer profile at `issuer_key`
+/// The PDA must be the issuer's record of `signer`, and `did_account`
+/// the signer's DID account listing the issuer as controller
+pub fn authorizes(
+    staff_key: &StaffKey,
+    did_account: &sol_did_cpi::state::DidAccount,
+    issuer_key: &Pubkey,
+    profile: &crate::Profile,
+    signer: &Pubkey,
+) -> bool {
+    staff_key.issuer == *issuer_key
+        && staff_key.staff == *signer
+        && did_account.initial_verification_method.key_data == signer.to_bytes()
+        && crate::credential::did_controlled_by_issuer(did_account, issuer_key, profile)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::did::SolanaDidResolver;
+    use crate::test_utils::{
+        credential_signing_json, ed25519_verify_instruction, fixture_credential, fixture_profile, sign_credential,
+        with_instructions_sysvar, TestKeypair,
+    };
+    use crate::{AchievementCredential, Profile, ProofSignatures};
+
+    struct Fixture {
+        staff: TestKeypair,
+        profile: Profile,
+        staff_key: StaffKey,
+        did_account: sol_did_cpi::state::DidAccount,
+        credential: AchievementCredential,
+    }
+
+    /// A credential of an issuer whose recorded staff key signed its proof under the staff DID
+    fn fixture() -> Fixture {
+        let authority = TestKeypair::from_seed(4);
+        let staff = TestKeypair::from_seed(6);
+        let issuer_key = Pubkey::new_unique();
+        let profile = fixture_profile(&issuer_key, &authority.pubkey(), "Issuer");
+        let staff_key = StaffKey {
+            issuer: issuer_key,
+            staff: staff.pubkey(),
+            authorized_at: "2024-01-01T00:00:00Z".to_string(),
+            bump: 255,
+        };
+        let mut did_account = SolanaDidResolver::generative_did_account(&staff.pubkey());
+        did_account.native_controllers.push(authority.pubkey());
+        let mut credential = fixture_credential(&Pubkey::new_unique(), &issuer_key, &Pubkey::new_unique(), &Pubkey::new_unique());
+        sign_credential(&mut credential, &staff);
+        credential.proof[0].verification_method = format!("did:sol:{}#default", staff.pubkey());
+        Fixture { staff, profile, staff_key, did_account, credential }
+    }
+
+    fn verifies(fixture: &Fixture, staff: Option<(&StaffKey, &sol_did_cpi::state::DidAccount)>) -> bool {
+        let message = credential_signing_json(&fixture.credential);
+        with_instructions_sysvar(&[ed25519_verify_instruction(&fixture.staff, message.as_bytes())], |instructions| {
+            let signatures = ProofSignatures { issuer: Some(&fixture.profile), instructions: Some(instructions), staff };
+            // An untrusted `#default` method is not an issuer key fragment, so it errors rather than failing
+            matches!(crate::check_proof(&fixture.credential.proof[0], &fixture.credential, &[], signatures), Ok(true))
+        })
+    }
+
+    #[test]
+    fn test_proof_by_authorized_staff_key_verifies() {
+        let fixture = fixture();
+        assert!(verifies(&fixture, Some((&fixture.staff_key, &fixture.did_account))));
+
+        // The record vouches for its own staff key only
+        let foreign = StaffKey { issuer: Pubkey::new_unique(), ..fixture.staff_key.clone() };
+        assert!(!verifies(&fixture, Some((&foreign, &fixture.did_account))));
+    }
+
+    #[test]
+    fn test_proof_by_revoked_staff_key_does_not_verify() {
+        let fixture = fixture();
+        // `revoke_staff_key` closes the record, so none can be supplied
+        assert!(!verifies(&fixture, None));
+
+        // Nor does a record verify once the DID stops listing the issuer as controller
+        let mut did_account = fixture.did_account.clone();
+        did_account.native_controllers.clear();
+        assert!(!verifies(&fixture, Some((&fixture.staff_key, &did_account))));
+    }
+}
@@ -0,0 +1,155 @@
+//! Issuer Statistics and Sync Cursors
+//!
+//! Every state change an issuer makes takes the next value of a per-issuer
+//! sequence kept in `IssuerStats`. The value is stamped on the emitted event
+//! and, for issuance, stored as the first field of the credential account,
+//! so an integrator holding cursor N can fetch N+1, N+2, ... with a memcmp
+//! filter at `CREDENTIAL_SEQUENCE_OFFSET` until nothing comes back.
+//!
+//! `AchievementStats` keeps per-achievement totals for dashboards: issuance,
+//! direct revocation and expiration of single credentials update it in place.
+
+use anchor_lang::prelude::*;
+
+/// Seed for the issuer stats PDA: [ISSUER_STATS_SEED, issuer]
+pub const ISSUER_STATS_SEED: &[u8] = b"issuer_stats";
+
+/// Seed for the achievement stats PDA: [ACHIEVEMENT_STATS_SEED, achievement]
+pub const ACHIEVEMENT_STATS_SEED: &[u8] = b"achievement_stats";
+
+/// Byte offset of `AchievementCredential::sequence` (after the discriminator)
+pub const CREDENTIAL_SEQUENCE_OFFSET: usize = 8;
+
+/// Per-issuer counters
+#[account]
+pub struct IssuerStats {
+    /// Issuer profile the counters belong to
+    pub issuer: Pubkey,
+    /// Sequence number of the issuer's latest event, 0 before the first
+    pub sequence: u64,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl IssuerStats {
+    pub const SPACE: usize = 8 + 32 + 8 + 1;
+
+    /// Take the next sequence number, binding the account on first use
+    pub fn next_sequence(&mut self, issuer: Pubkey, bump: u8) -> u64 {
+        self.issuer = issuer;
+        self.bump = bump;
+        self.sequence += 1;
+        self.sequence
+    }
+}
+
+/// Per-achievement issuance counters
+#[account]
+pub struct AchievementStats {
+    /// Achievement the counters belong to
+    pub achievement: Pubkey,
+    /// Credentials issued for the achievement
+    pub issued_count: u64,
+    /// Credentials of the achievement revoked since
+    pub revoked_count: u64,
+    /// Credentials of the achievement marked expired and not revoked since
+    pub expired_count: u64,
+    /// When the latest credential was issued (ISO 8601 string)
+    pub last_issued_at: Option<String>,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl AchievementStats {
+    pub const SPACE: usize = 8 + 32 + 8 + 8 + 8 + 1 + 4 + 40 + 1;
+
+    /// Count an issued credential, binding the account on first use
+    pub fn record_issued(&mut self, achievement: Pubkey, bump: u8, issued_at: String) {
+        self.achievement = achievement;
+        self.bump = bump;
+        self.issued_count += 1;
+        self.last_issued_at = Some(issued_at);
+    }
+
+    /// Count a revoked credential, binding the account on first use
+    /// A credential already counted as expired is counted as revoked instead
+    pub fn record_revoked(&mut self, achievement: Pubkey, bump: u8, was_expired: bool) {
+        self.achievement = achievement;
+        self.bump = bump;
+        self.revoked_count += 1;
+        if was_expired {
+            self.expired_count = self.expired_count.saturating_sub(1);
+        }
+    }
+
+    /// Count an expired credential, binding the account on first use
+    pub fn record_expired(&mut self, achievement: Pubkey, bump: u8) {
+        self.achievement = achievement;
+        self.bump = bump;
+        self.expired_count += 1;
+    }
+
+    /// Credentials issued and neither revoked nor expired
+    pub fn active_count(&self) -> u64 {
+        self.issued_count.saturating_sub(self.revoked_count).saturating_sub(self.expired_count)
+    }
+}
+
+/// Memcmp bytes matching credentials stamped with `sequence`
+pub fn sequence_filter_bytes(sequence: u64) -> [u8; 8] {
+    sequence.to_le_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sequence_is_monotonic_from_one() {
+        let issuer = Pubkey::new_unique();
+        let mut stats = IssuerStats { issuer: Pubkey::default(), sequence: 0, bump: 0 };
+        assert_eq!(stats.next_sequence(issuer, 254), 1);
+        assert_eq!(stats.next_sequence(issuer, 254), 2);
+        assert_eq!(stats.issuer, issuer);
+        assert_eq!(stats.bump, 254);
+    }
+
+    #[test]
+    fn test_achievement_counters() {
+        let achievement = Pubkey::new_unique();
+        let mut stats = AchievementStats {
+            achievement: Pubkey::default(),
+            issued_count: 0,
+            revoked_count: 0,
+            expired_count: 0,
+            last_issued_at: None,
+            bump: 0,
+        };
+        stats.record_issued(achievement, 253, "2025-01-01T00:00:00Z".to_string());
+        stats.record_issued(achievement, 253, "2025-01-02T00:00:00Z".to_string());
+        stats.record_revoked(achievement, 253, false);
+        assert_eq!((stats.issued_count, stats.revoked_count, stats.active_count()), (2, 1, 1));
+        stats.record_expired(achievement, 253);
+        assert_eq!((stats.expired_count, stats.active_count()), (1, 0));
+        stats.record_revoked(achievement, 253, true);
+        assert_eq!((stats.revoked_count, stats.expired_count, stats.active_count()), (2, 0, 0));
+        assert_eq!(stats.last_issued_at.as_deref(), Some("2025-01-02T00:00:00Z"));
+        assert!(crate::test_utils::account_data(&stats).len() <= AchievementStats::SPACE);
+    }
+
+    #[test]
+    fn test_sequence_sits_at_credential_offset() {
+        let mut credential = crate::test_utils::fixture_credential(
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+        );
+        credential.sequence = 42;
+        let data = crate::test_utils::account_data(&credential);
+        assert_eq!(
+            data[CREDENTIAL_SEQUENCE_OFFSET..CREDENTIAL_SEQUENCE_OFFSET + 8],
+            sequence_filter_bytes(42)
+        );
+    }
+}
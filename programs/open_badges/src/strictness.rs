@@ -0,0 +1,103 @@
+//! Issuer Validation Strictness
+//!
+//! Every issuer profile carries a `ValidationMode`. Production, the default,
+//! rejects anything that fails the Open Badges 3.0 checks; development logs
+//! the same failures and lets the instruction continue. Issuers experiment
+//! on devnet and enforce on mainnet with the same program build by flipping
+//! the mode on their profile. Issuance and the validate_* instructions read
+//! the mode of the issuer they concern.
+
+use anchor_lang::prelude::*;
+use crate::common::errors::ValidationError;
+use crate::compliance_validator::{ComplianceValidator, ValidationReport};
+use crate::AchievementCredential;
+
+/// How strictly an issuer's credentials are validated
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ValidationMode {
+    /// Failures are logged and tolerated
+    Development,
+    /// Failures abort the instruction
+    #[default]
+    Production,
+}
+
+impl ValidationMode {
+    /// Whether failures abort the instruction
+    pub fn is_strict(self) -> bool {
+        self == ValidationMode::Production
+    }
+
+    /// Compliance validator configured for this mode
+    pub fn compliance_validator(self) -> ComplianceValidator {
+        match self {
+            ValidationMode::Development => ComplianceValidator::development(),
+            ValidationMode::Production => ComplianceValidator::production(),
+        }
+    }
+
+    /// Pass `check` through in production; log and tolerate a failure in development
+    pub fn enforce(self, check: Result<()>, what: &str) -> Result<()> {
+        match check {
+            Err(error) if self.is_strict() => Err(error),
+            Err(error) => {
+                msg!("⚠️ {} failed in development mode: {}", what, error);
+                Ok(())
+            }
+            Ok(()) => Ok(()),
+        }
+    }
+
+    /// Outcome of a compliance report: an invalid report fails in production
+    pub fn check_report(self, report: &ValidationReport) -> Result<bool> {
+        for error in &report.errors {
+            msg!("❌ {}", error);
+        }
+        if !report.is_valid && self.is_strict() {
+            return Err(error!(ValidationError::ValidationFailed));
+        }
+        Ok(report.is_valid)
+    }
+}
+
+/// Issuance-time checks of a freshly written credential
+/// The same rules `validate_credential_compliance` applies to stored credentials
+pub fn validate_issued_credential(mode: ValidationMode, credential: &AchievementCredential) -> Result<()> {
+    mode.enforce(credential.validate(), "Credential validation")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::fixture_credential;
+
+    fn credential() -> AchievementCredential {
+        fixture_credential(&Pubkey::new_unique(), &Pubkey::new_unique(), &Pubkey::new_unique(), &Pubkey::new_unique())
+    }
+
+    #[test]
+    fn test_default_mode_is_production() {
+        assert_eq!(ValidationMode::default(), ValidationMode::Production);
+        assert!(ValidationMode::Production.compliance_validator().strict_mode);
+        assert!(!ValidationMode::Development.compliance_validator().strict_mode);
+    }
+
+    #[test]
+    fn test_issuance_checks_follow_mode() {
+        assert!(validate_issued_credential(ValidationMode::Production, &credential()).is_ok());
+
+        let mut broken = credential();
+        broken.context.clear();
+        assert!(validate_issued_credential(ValidationMode::Production, &broken).is_err());
+        assert!(validate_issued_credential(ValidationMode::Development, &broken).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_report_fails_only_when_strict() {
+        let mut report = ValidationReport::new();
+        report.add_error("Missing validFrom property".to_string());
+        report.calculate_compliance_score();
+        assert!(ValidationMode::Production.check_report(&report).is_err());
+        assert!(!ValidationMode::Development.check_report(&report).unwrap());
+    }
+}
@@ -0,0 +1,96 @@
+//! Credential Supersession
+//!
+//! Reissuance renews a credential that has expired; supersession replaces
+//! one that is still in force but must be corrected, for instance after
+//! the achievement it awards was recreated with fixed metadata.
+//! `supersede_credential` issues the replacement at a PDA derived from the
+//! old credential, lists the old id in its `related` and records the
+//! replacement in the old credential's `superseded_by`. Verification of the
+//! old credential then fails with `CredentialSuperseded` and names the
+//! newer version, so a verifier can follow the chain to the current one.
+
+use anchor_lang::prelude::*;
+use crate::common::errors::ValidationError;
+use crate::AchievementCredential;
+
+/// Seed prefix for replacement credential PDAs: [SUPERSESSION_SEED, superseded credential]
+pub const SUPERSESSION_SEED: &[u8] = b"supersession";
+
+/// Serialized size of `superseded_by`, reserved at issuance
+pub const SPACE: usize = 1 + 32;
+
+/// Check that `previous` may be superseded: neither revoked nor already superseded
+pub fn ensure_supersedable(previous: &AchievementCredential) -> Result<()> {
+    if previous.is_revoked || previous.status == crate::lifecycle::CredentialState::Revoked {
+        msg!("❌ Revoked credentials cannot be superseded: {}", previous.id);
+        return Err(error!(ValidationError::AlreadyRevoked));
+    }
+    if let Some(replacement) = previous.superseded_by {
+        msg!("❌ Credential {} is already superseded by {}", previous.id, replacement);
+        return Err(error!(ValidationError::CredentialSuperseded));
+    }
+    Ok(())
+}
+
+/// Unsigned replacement of `previous` awarding `achievement`
+///
+/// Built as a reissue of `previous`, so the subject, results and other
+/// carried-over fields are the same; only the achievement may change.
+pub fn supersede(
+    previous: &AchievementCredential,
+    achievement: Pubkey,
+    id: String,
+    valid_from: String,
+    valid_until: Option<String>,
+    issued_at: String,
+) -> AchievementCredential {
+    let mut replacement = crate::refresh::reissue(previous, id, valid_from, valid_until, issued_at);
+    replacement.credential_subject.achievement = achievement;
+    replacement
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{account_data, fixture_credential, sign_credential, TestKeypair};
+
+    fn credential() -> AchievementCredential {
+        let mut credential = fixture_credential(&Pubkey::new_unique(), &Pubkey::new_unique(), &Pubkey::new_unique(), &Pubkey::new_unique());
+        sign_credential(&mut credential, &TestKeypair::from_seed(9));
+        credential
+    }
+
+    #[test]
+    fn test_replacement_links_back_and_fits() {
+        let previous = credential();
+        let corrected = Pubkey::new_unique();
+        let mut replacement = supersede(
+            &previous,
+            corrected,
+            format!("did:sol:{}", Pubkey::new_unique()),
+            "2024-06-02T00:00:00+00:00".to_string(),
+            None,
+            "2024-06-02T00:00:00+00:00".to_string(),
+        );
+        assert_eq!(replacement.related, vec![previous.id.clone()]);
+        assert_eq!(replacement.credential_subject.achievement, corrected);
+        assert_eq!(replacement.credential_subject.id, previous.credential_subject.id);
+        assert_eq!(replacement.superseded_by, None);
+
+        sign_credential(&mut replacement, &TestKeypair::from_seed(9));
+        let previous_len = account_data(&previous).len();
+        assert!(account_data(&replacement).len() <= crate::refresh::reissued_space(previous_len, &previous));
+    }
+
+    #[test]
+    fn test_only_current_unrevoked_credentials_are_supersedable() {
+        let mut credential = credential();
+        assert!(ensure_supersedable(&credential).is_ok());
+
+        credential.superseded_by = Some(Pubkey::new_unique());
+        assert_eq!(ensure_supersedable(&credential).unwrap_err(), error!(ValidationError::CredentialSuperseded));
+        credential.superseded_by = None;
+        credential.is_revoked = true;
+        assert_eq!(ensure_supersedable(&credential).unwrap_err(), error!(ValidationError::AlreadyRevoked));
+    }
+}
@@ -0,0 +1,136 @@
+//! Team Credentials
+//!
+//! Hackathon and team achievements are earned by a group, not a person. A
+//! `TeamCredential` is one credential with several subjects: its PDA is keyed
+//! by the achievement, the issuer and a team id chosen by the issuer, and it
+//! lists each member's wallet. Its JSON document carries a `credentialSubject`
+//! array with one AchievementSubject per member, as the VC Data Model allows.
+//!
+//! Members who opted out of receiving badges cannot be put on a team; their
+//! opt-out PDAs are passed in `remaining_accounts`, in member order.
+
+use anchor_lang::prelude::*;
+use crate::common::errors::ValidationError;
+use crate::{canonical, credential_id};
+
+/// Seed prefix for team credential PDAs: [TEAM_CREDENTIAL_SEED, achievement, issuer, team_id]
+pub const TEAM_CREDENTIAL_SEED: &[u8] = b"team_credential";
+
+/// Maximum members on one team credential
+pub const MAX_TEAM_MEMBERS: usize = 10;
+
+/// Maximum length of a team id, bounded by the PDA seed size
+pub const MAX_TEAM_ID_LEN: usize = 32;
+
+/// One credential awarded to every member of a team
+#[account]
+pub struct TeamCredential {
+    /// Issuer profile
+    pub issuer: Pubkey,
+    /// Achievement awarded
+    pub achievement: Pubkey,
+    /// Issuer-chosen team id, part of the PDA seeds
+    pub team_id: String,
+    /// Member wallets, in the order the issuer listed them
+    pub members: Vec<Pubkey>,
+    /// When the credential was issued (ISO 8601 string)
+    pub valid_from: String,
+    /// Whether the issuer revoked it
+    pub is_revoked: bool,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl TeamCredential {
+    /// Account size for `team_id` and `members`
+    pub fn space(team_id: &str, members: &[Pubkey]) -> usize {
+        8 + 32 + 32 + 4 + team_id.len() + 4 + 32 * members.len() + 4 + crate::refresh::MAX_TIMESTAMP_LEN + 1 + 1
+    }
+}
+
+/// Check a team: a seed-sized id and 1 to MAX_TEAM_MEMBERS distinct members
+pub fn validate(team_id: &str, members: &[Pubkey]) -> Result<()> {
+    if team_id.is_empty() || team_id.len() > MAX_TEAM_ID_LEN {
+        return Err(error!(ValidationError::InvalidTeam));
+    }
+    if members.is_empty() || members.len() > MAX_TEAM_MEMBERS {
+        return Err(error!(ValidationError::InvalidTeam));
+    }
+    if members.iter().enumerate().any(|(index, member)| members[..index].contains(member)) {
+        msg!("❌ Team {} lists a member twice", team_id);
+        return Err(error!(ValidationError::InvalidTeam));
+    }
+    Ok(())
+}
+
+/// JSON-LD document of a team credential, one credential subject per member
+pub fn to_json(
+    address: &Pubkey,
+    team: &TeamCredential,
+    format: credential_id::CredentialIdFormat,
+) -> serde_json::Value {
+    let achievement = format!("did:sol:{}", team.achievement);
+    let subjects: Vec<serde_json::Value> = team
+        .members
+        .iter()
+        .map(|member| {
+            serde_json::json!({
+                "id": format!("did:sol:{}", member),
+                "type": canonical::SUBJECT_TYPE,
+                "achievement": achievement,
+            })
+        })
+        .collect();
+    serde_json::json!({
+        "@context": canonical::CREDENTIAL_CONTEXT,
+        "id": credential_id::credential_id(format, address),
+        "type": canonical::CREDENTIAL_TYPE,
+        "issuer": { "id": format!("did:sol:{}", team.issuer), "type": ["Profile"] },
+        "validFrom": team.valid_from,
+        "credentialSubject": subjects,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn team(members: Vec<Pubkey>) -> TeamCredential {
+        TeamCredential {
+            issuer: Pubkey::new_unique(),
+            achievement: Pubkey::new_unique(),
+            team_id: "hackathon-2025-team-7".to_string(),
+            members,
+            valid_from: "2025-01-01T00:00:00Z".to_string(),
+            is_revoked: false,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn test_teams_are_bounded_and_distinct() {
+        let (alice, bob) = (Pubkey::new_unique(), Pubkey::new_unique());
+        assert!(validate("team-7", &[alice, bob]).is_ok());
+        assert!(validate("team-7", &[alice, bob, alice]).is_err());
+        assert!(validate("team-7", &[]).is_err());
+        assert!(validate("", &[alice]).is_err());
+        assert!(validate(&"t".repeat(MAX_TEAM_ID_LEN + 1), &[alice]).is_err());
+        let crowd: Vec<Pubkey> = (0..=MAX_TEAM_MEMBERS).map(|_| Pubkey::new_unique()).collect();
+        assert!(validate("team-7", &crowd).is_err());
+    }
+
+    #[test]
+    fn test_json_has_one_subject_per_member() {
+        let mut team = team(vec![Pubkey::new_unique(), Pubkey::new_unique()]);
+        let document = to_json(&Pubkey::new_unique(), &team, credential_id::CredentialIdFormat::DidSol);
+        let subjects = document["credentialSubject"].as_array().unwrap();
+        assert_eq!(subjects.len(), 2);
+        assert_eq!(subjects[1]["id"], format!("did:sol:{}", team.members[1]));
+        assert_eq!(subjects[0]["achievement"], format!("did:sol:{}", team.achievement));
+
+        team.members = (0..MAX_TEAM_MEMBERS).map(|_| Pubkey::new_unique()).collect();
+        team.team_id = "t".repeat(MAX_TEAM_ID_LEN);
+        team.valid_from = "x".repeat(crate::refresh::MAX_TIMESTAMP_LEN);
+        assert_eq!(team.try_to_vec().unwrap().len() + 8, TeamCredential::space(&team.team_id, &team.members));
+    }
+}
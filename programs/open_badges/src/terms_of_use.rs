@@ -0,0 +1,159 @@
+//! Terms of Use
+//!
+//! VC Data Model `termsOfUse` entries tell holders and verifiers which
+//! policies govern a credential, each a `type` with an optional policy URI.
+//! An issuer keeps default terms on its Profile; issuance copies them onto
+//! the credential unless the issuance arguments carry their own, and the
+//! stored terms appear in the credential's JSON-LD and JWT documents.
+
+use anchor_lang::prelude::*;
+use crate::common::errors::ValidationError;
+
+/// Maximum terms of use on a profile or credential
+pub const MAX_TERMS: usize = 4;
+
+/// Maximum length of a terms `type`
+pub const MAX_TERMS_TYPE_LEN: usize = 64;
+
+/// Maximum length of a terms policy URI
+pub const MAX_TERMS_ID_LEN: usize = 200;
+
+/// Serialized size of an empty list of terms
+pub const EMPTY_SPACE: usize = 4;
+
+/// One `termsOfUse` entry
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TermsOfUse {
+    /// Policy URI [0..1]
+    pub id: Option<String>,
+    /// Terms type, e.g. `IssuerPolicy` [1]
+    pub terms_type: String,
+}
+
+impl TermsOfUse {
+    /// JSON form used in credential documents
+    pub fn to_json(&self) -> serde_json::Value {
+        let mut terms = serde_json::json!({ "type": self.terms_type });
+        if let Some(id) = &self.id {
+            terms["id"] = serde_json::json!(id);
+        }
+        terms
+    }
+}
+
+/// Whether `id` is an absolute URI: a scheme, a colon and a non-empty rest
+pub fn is_valid_id(id: &str) -> bool {
+    match id.split_once(':') {
+        Some((scheme, rest)) => {
+            !rest.is_empty()
+                && scheme.chars().next().is_some_and(|c| c.is_ascii_alphabetic())
+                && scheme.chars().all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+        }
+        None => false,
+    }
+}
+
+/// Check a list of terms: bounded, each with a type and, when given, an absolute policy URI
+pub fn validate(terms: &[TermsOfUse]) -> Result<()> {
+    if terms.len() > MAX_TERMS {
+        return Err(error!(ValidationError::TooManyItems));
+    }
+    for entry in terms {
+        if entry.terms_type.is_empty() || entry.id.as_deref().is_some_and(|id| !is_valid_id(id)) {
+            msg!("❌ Malformed termsOfUse entry: {:?}", entry);
+            return Err(error!(ValidationError::InvalidTermsOfUse));
+        }
+        if entry.terms_type.len() > MAX_TERMS_TYPE_LEN {
+            return Err(error!(ValidationError::FieldTooLong));
+        }
+        if entry.id.as_ref().is_some_and(|id| id.len() > MAX_TERMS_ID_LEN) {
+            return Err(error!(ValidationError::IdTooLong));
+        }
+    }
+    Ok(())
+}
+
+/// Serialized size of a list of terms
+pub fn space(terms: &[TermsOfUse]) -> usize {
+    EMPTY_SPACE
+        + terms
+            .iter()
+            .map(|entry| 1 + entry.id.as_ref().map_or(0, |id| 4 + id.len()) + 4 + entry.terms_type.len())
+            .sum::<usize>()
+}
+
+/// Terms of an issued credential: the issuance override if any, else the issuer's defaults
+pub fn resolve(issuer_defaults: &[TermsOfUse], overrides: Option<Vec<TermsOfUse>>) -> Result<Vec<TermsOfUse>> {
+    match overrides {
+        Some(terms) => {
+            validate(&terms)?;
+            Ok(terms)
+        }
+        None => Ok(issuer_defaults.to_vec()),
+    }
+}
+
+/// Store `terms` on a newly issued credential, growing its account to fit
+pub fn attach<'info>(
+    credential: &mut Account<'info, crate::AchievementCredential>,
+    terms: Vec<TermsOfUse>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    if terms.is_empty() {
+        return Ok(());
+    }
+    let info = credential.to_account_info();
+    let needed = info.data_len() + space(&terms) - space(&credential.terms_of_use);
+    crate::credential_index::grow_to(&info, needed, payer, system_program)?;
+    credential.terms_of_use = terms;
+    Ok(())
+}
+
+/// Set `termsOfUse` on a credential document when there are any
+pub fn apply_to_json(document: &mut serde_json::Value, terms: &[TermsOfUse]) {
+    if !terms.is_empty() {
+        let terms: Vec<serde_json::Value> = terms.iter().map(TermsOfUse::to_json).collect();
+        document["termsOfUse"] = serde_json::json!(terms);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn terms(id: Option<&str>, terms_type: &str) -> TermsOfUse {
+        TermsOfUse { id: id.map(str::to_string), terms_type: terms_type.to_string() }
+    }
+
+    #[test]
+    fn test_terms_are_well_formed() {
+        assert!(validate(&[terms(Some("https://issuer.example/policy"), "IssuerPolicy"), terms(None, "TrustFrameworkPolicy")]).is_ok());
+        assert!(validate(&[terms(Some("urn:uuid:5e2f"), "IssuerPolicy")]).is_ok());
+        assert!(validate(&[terms(Some("issuer.example/policy"), "IssuerPolicy")]).is_err());
+        assert!(validate(&[terms(None, "")]).is_err());
+        assert!(validate(&vec![terms(None, "IssuerPolicy"); MAX_TERMS + 1]).is_err());
+    }
+
+    #[test]
+    fn test_overrides_replace_defaults() {
+        let defaults = vec![terms(Some("https://issuer.example/policy"), "IssuerPolicy")];
+        assert_eq!(resolve(&defaults, None).unwrap(), defaults);
+        assert!(resolve(&defaults, Some(vec![])).unwrap().is_empty());
+        assert!(resolve(&defaults, Some(vec![terms(None, "")])).is_err());
+    }
+
+    #[test]
+    fn test_json_and_space() {
+        let list = vec![terms(Some("https://issuer.example/policy"), "IssuerPolicy"), terms(None, "TrustFrameworkPolicy")];
+        let mut document = serde_json::json!({});
+        apply_to_json(&mut document, &list);
+        assert_eq!(document["termsOfUse"][0]["id"], "https://issuer.example/policy");
+        assert!(document["termsOfUse"][1].get("id").is_none());
+        assert_eq!(list.try_to_vec().unwrap().len(), space(&list));
+
+        let mut empty = serde_json::json!({});
+        apply_to_json(&mut empty, &[]);
+        assert!(empty.get("termsOfUse").is_none());
+    }
+}
@@ -0,0 +1,325 @@
+//! Test Keypairs, Signing Helpers and Account Fixtures
+//!
+//! Available to this crate's unit tests and, through the `test-utils`
+//! feature, to downstream tests. Keypairs are real Ed25519 keys derived from
+//! a one-byte seed, so signatures verify under any Ed25519 implementation
+//! and the same seed always yields the same key across test runs.
+
+use anchor_lang::prelude::*;
+use ed25519_dalek::{Signer, SigningKey, Verifier};
+use crate::proof::MultikeyPair;
+use crate::{
+    Achievement, AchievementCredential, AchievementSubject, Criteria, IdentityObject, Profile, Proof,
+};
+
+/// Timestamp used by fixtures unless a test overrides it
+pub const FIXTURE_TIMESTAMP: &str = "2024-01-01T00:00:00Z";
+
+/// Deterministic Ed25519 keypair for tests
+#[derive(Clone, Debug)]
+pub struct TestKeypair {
+    signing_key: SigningKey,
+}
+
+impl TestKeypair {
+    /// Keypair whose secret key is `seed` repeated 32 times
+    pub fn from_seed(seed: u8) -> Self {
+        Self { signing_key: SigningKey::from_bytes(&[seed; 32]) }
+    }
+
+    /// Keypair with the given 32-byte secret key
+    pub fn from_secret_key(secret_key: [u8; 32]) -> Self {
+        Self { signing_key: SigningKey::from_bytes(&secret_key) }
+    }
+
+    /// Public key as a Solana address
+    pub fn pubkey(&self) -> Pubkey {
+        Pubkey::new_from_array(self.signing_key.verifying_key().to_bytes())
+    }
+
+    /// Sign a message
+    pub fn sign(&self, message: &[u8]) -> [u8; 64] {
+        self.signing_key.sign(message).to_bytes()
+    }
+
+    /// Check a signature against this keypair's public key
+    pub fn verify(&self, message: &[u8], signature: &[u8; 64]) -> bool {
+        let signature = ed25519_dalek::Signature::from_bytes(signature);
+        self.signing_key.verifying_key().verify(message, &signature).is_ok()
+    }
+
+    /// Multikey for this keypair, in place of `MultikeyPair::new_ed25519`
+    pub fn multikey(&self, controller: &str, key_id: &str) -> MultikeyPair {
+        MultikeyPair::from_signer(self.pubkey(), controller.to_string(), key_id.to_string())
+            .expect("a 32-byte Ed25519 key is always a valid multikey")
+    }
+}
+
+/// Issuer profile owned by `authority`
+pub fn fixture_profile(issuer_key: &Pubkey, authority: &Pubkey, name: &str) -> Profile {
+    Profile {
+        id: format!("did:sol:{}", issuer_key),
+        r#type: vec!["Profile".to_string()],
+        authority: *authority,
+        name: name.to_string(),
+        url: Some("https://example.com".to_string()),
+        email: None,
+        image: None,
+        branding: None,
+        validation_mode: crate::strictness::ValidationMode::Production,
+        refresh_service_url: None,
+        terms_of_use: vec![],
+        other_identifier: vec![],
+        name_i18n: vec![],
+        credential_id_format: crate::credential_id::CredentialIdFormat::DidSol,
+        issuance_limit: None,
+        frozen: false,
+        bump: 255,
+    }
+}
+
+/// Achievement defined by `issuer_key`
+pub fn fixture_achievement(achievement_key: &Pubkey, issuer_key: &Pubkey, name: &str) -> Achievement {
+    Achievement {
+        context: vec![
+            "https://www.w3.org/ns/credentials/v2".to_string(),
+            "https://purl.imsglobal.org/spec/ob/v3p0/context-3.0.3.json".to_string(),
+        ],
+        id: format!("did:sol:{}", achievement_key),
+        r#type: vec!["Achievement".to_string()],
+        issuer: *issuer_key,
+        name: name.to_string(),
+        description: format!("Awarded for {}", name),
+        criteria: Criteria { id: None, narrative: Some("Complete the assessment".to_string()) },
+        creator: None,
+        image: None,
+        achievement_type: None,
+        result_descriptions: vec![],
+        requirements: crate::criteria::CriteriaRequirements::default(),
+        requires_approved_result: false,
+        extensions: vec![],
+        name_i18n: vec![],
+        description_i18n: vec![],
+        created_at: FIXTURE_TIMESTAMP.to_string(),
+        bump: 255,
+    }
+}
+
+/// Unsigned credential as `issue_achievement_credential` would store it
+pub fn fixture_credential(
+    credential_key: &Pubkey,
+    issuer_key: &Pubkey,
+    achievement_key: &Pubkey,
+    recipient: &Pubkey,
+) -> AchievementCredential {
+    AchievementCredential {
+        sequence: 0,
+        id: format!("did:sol:{}", credential_key),
+        context: vec![
+            "https://www.w3.org/ns/credentials/v2".to_string(),
+            "https://purl.imsglobal.org/spec/ob/v3p0/context-3.0.3.json".to_string(),
+        ],
+        r#type: vec!["VerifiableCredential".to_string(), "OpenBadgeCredential".to_string()],
+        issuer: *issuer_key,
+        valid_from: FIXTURE_TIMESTAMP.to_string(),
+        valid_until: None,
+        issued_at: FIXTURE_TIMESTAMP.to_string(),
+        credential_subject: AchievementSubject {
+            id: Some(format!("did:sol:{}", recipient)),
+            subject_type: vec!["AchievementSubject".to_string()],
+            achievement: *achievement_key,
+            identifier: vec![IdentityObject {
+                identity_type: "IdentityObject".to_string(),
+                hashed: false,
+                identity_hash: recipient.to_string(),
+                identity_type_name: "identifier".to_string(),
+            }],
+            result: vec![],
+            narrative: None,
+        },
+        proof: vec![],
+        is_revoked: false,
+        revoked_at: None,
+        revocation_reason: None,
+        revoked_by: Pubkey::default(),
+        deprecated_proofs: vec![],
+        extensions: vec![],
+        baked_image: None,
+        related: vec![],
+        notes: vec![],
+        is_expired: false,
+        credential_schema: vec![],
+        holder_binding: None,
+        status: crate::lifecycle::CredentialState::Active,
+        status_history: vec![],
+        rebind_history: vec![],
+        terms_of_use: vec![],
+        superseded_by: None,
+        encrypted_payload: None,
+        bump: 255,
+    }
+}
+
+/// Credential JSON that issuance expects the authority to sign
+pub fn credential_signing_json(credential: &AchievementCredential) -> String {
+    crate::credential_signing_json(credential)
+}
+
+/// Sign the credential with `authority` and attach the proof issuance would store
+pub fn sign_credential(credential: &mut AchievementCredential, authority: &TestKeypair) -> [u8; 64] {
+    let signature = authority.sign(credential_signing_json(credential).as_bytes());
+    credential.proof = vec![Proof {
+        proof_type: "DataIntegrityProof".to_string(),
+        cryptosuite: "eddsa-rdfc-2022".to_string(),
+        created: credential.issued_at.clone(),
+        proof_purpose: "assertionMethod".to_string(),
+        verification_method: format!("did:sol:{}", credential.issuer),
+        proof_value: crate::multibase::encode(&signature),
+        previous_proof: None,
+    }];
+    signature
+}
+
+/// Ed25519 program instruction verifying `authority`'s signature over `message`
+pub fn ed25519_verify_instruction(
+    authority: &TestKeypair,
+    message: &[u8],
+) -> anchor_lang::solana_program::instruction::Instruction {
+    use crate::proof::Ed25519SignatureOffsets;
+
+    let public_key_offset = 2 + Ed25519SignatureOffsets::LEN;
+    let signature_offset = public_key_offset + 32;
+    let message_offset = signature_offset + 64;
+    let mut data = vec![1u8, 0];
+    for field in [
+        signature_offset,
+        Ed25519SignatureOffsets::CURRENT_INSTRUCTION as usize,
+        public_key_offset,
+        Ed25519SignatureOffsets::CURRENT_INSTRUCTION as usize,
+        message_offset,
+        message.len(),
+        Ed25519SignatureOffsets::CURRENT_INSTRUCTION as usize,
+    ] {
+        data.extend_from_slice(&(field as u16).to_le_bytes());
+    }
+    data.extend_from_slice(authority.pubkey().as_ref());
+    data.extend_from_slice(&authority.sign(message));
+    data.extend_from_slice(message);
+
+    anchor_lang::solana_program::instruction::Instruction {
+        program_id: crate::proof::ED25519_PROGRAM_ID,
+        accounts: vec![],
+        data,
+    }
+}
+
+/// Run `check` against an instructions sysvar account for a transaction of
+/// `instructions` followed by the executing instruction of this program
+pub fn with_instructions_sysvar<R>(
+    instructions: &[anchor_lang::solana_program::instruction::Instruction],
+    check: impl FnOnce(&AccountInfo) -> R,
+) -> R {
+    use anchor_lang::solana_program::sysvar;
+
+    // Layout the runtime serializes: count, offsets, then each instruction's
+    // accounts, program id and data, and the executing index last
+    let executing = anchor_lang::solana_program::instruction::Instruction {
+        program_id: crate::ID,
+        accounts: vec![],
+        data: vec![],
+    };
+    let all: Vec<_> = instructions.iter().chain(std::iter::once(&executing)).collect();
+    let mut data = (all.len() as u16).to_le_bytes().to_vec();
+    let mut body = Vec::new();
+    let header_len = 2 + 2 * all.len();
+    for instruction in &all {
+        data.extend_from_slice(&((header_len + body.len()) as u16).to_le_bytes());
+        body.extend_from_slice(&0u16.to_le_bytes());
+        body.extend_from_slice(instruction.program_id.as_ref());
+        body.extend_from_slice(&(instruction.data.len() as u16).to_le_bytes());
+        body.extend_from_slice(&instruction.data);
+    }
+    data.extend_from_slice(&body);
+    data.extend_from_slice(&(instructions.len() as u16).to_le_bytes());
+
+    let (key, owner, mut lamports) = (sysvar::instructions::ID, sysvar::ID, 0);
+    let info = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &owner, false, 0);
+    check(&info)
+}
+
+/// secp256k1 program instruction, at transaction position `index`, carrying
+/// `signature` by `eth_address` over `message`
+pub fn secp256k1_verify_instruction(
+    eth_address: &[u8; 20],
+    message: &[u8],
+    signature: &[u8; 64],
+    recovery_id: u8,
+    index: u8,
+) -> anchor_lang::solana_program::instruction::Instruction {
+    use crate::secp256k1::Secp256k1SignatureOffsets;
+
+    let eth_address_offset = 1 + Secp256k1SignatureOffsets::LEN;
+    let signature_offset = eth_address_offset + 20;
+    let message_offset = signature_offset + 65;
+    let mut data = vec![1u8];
+    data.extend_from_slice(&(signature_offset as u16).to_le_bytes());
+    data.push(index);
+    data.extend_from_slice(&(eth_address_offset as u16).to_le_bytes());
+    data.push(index);
+    data.extend_from_slice(&(message_offset as u16).to_le_bytes());
+    data.extend_from_slice(&(message.len() as u16).to_le_bytes());
+    data.push(index);
+    data.extend_from_slice(eth_address);
+    data.extend_from_slice(signature);
+    data.push(recovery_id);
+    data.extend_from_slice(message);
+
+    anchor_lang::solana_program::instruction::Instruction {
+        program_id: crate::secp256k1::SECP256K1_PROGRAM_ID,
+        accounts: vec![],
+        data,
+    }
+}
+
+/// Serialized account data, discriminator included, for loading into a test validator
+pub fn account_data<T: AccountSerialize>(account: &T) -> Vec<u8> {
+    let mut data = Vec::new();
+    account
+        .try_serialize(&mut data)
+        .expect("fixture accounts always serialize");
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keypairs_are_deterministic() {
+        assert_eq!(TestKeypair::from_seed(1).pubkey(), TestKeypair::from_seed(1).pubkey());
+        assert_ne!(TestKeypair::from_seed(1).pubkey(), TestKeypair::from_seed(2).pubkey());
+        assert_ne!(TestKeypair::from_seed(1).pubkey(), anchor_lang::solana_program::system_program::id());
+    }
+
+    #[test]
+    fn test_signed_fixture_verifies() {
+        let authority = TestKeypair::from_seed(7);
+        let issuer = Pubkey::new_unique();
+        let mut credential =
+            fixture_credential(&Pubkey::new_unique(), &issuer, &Pubkey::new_unique(), &Pubkey::new_unique());
+        let signature = sign_credential(&mut credential, &authority);
+
+        assert!(authority.verify(credential_signing_json(&credential).as_bytes(), &signature));
+        assert!(!TestKeypair::from_seed(8).verify(credential_signing_json(&credential).as_bytes(), &signature));
+        assert!(credential.proof[0].proof_value.starts_with('z'));
+    }
+
+    #[test]
+    fn test_account_data_round_trips() {
+        let profile = fixture_profile(&Pubkey::new_unique(), &TestKeypair::from_seed(3).pubkey(), "Issuer");
+        let data = account_data(&profile);
+        let decoded = Profile::try_deserialize(&mut data.as_slice()).unwrap();
+        assert_eq!(decoded.name, "Issuer");
+        assert_eq!(decoded.authority, profile.authority);
+    }
+}
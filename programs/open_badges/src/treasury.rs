@@ -0,0 +1,205 @@
+//! Program Treasury
+//!
+//! Issuance fees set in the program config are paid into a program-owned
+//! `Treasury` PDA created together with the config. Lamport fees are credited
+//! to the PDA itself; token fees go to a token account whose owner is the
+//! PDA, so only the program can move them out, on behalf of the config's
+//! admin, through `withdraw_treasury`. This program does not depend on the
+//! SPL crates, so token accounts are read and transfers built against the
+//! SPL Token program's account layout and instruction encoding directly.
+//!
+//! Every instruction that creates a credential charges the fee to the
+//! account paying for it: the issuer authority, a CPI or peer payer, or a
+//! delegated service. The one exemption is `claim_with_code`, whose offer was
+//! already charged when `issue_with_claim_code` escrowed it.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+use anchor_lang::system_program;
+use crate::common::errors::ValidationError;
+use crate::program_config;
+
+/// Seed for the treasury PDA: [TREASURY_SEED]
+pub const TREASURY_SEED: &[u8] = b"treasury";
+
+/// SPL Token program
+pub const TOKEN_PROGRAM_ID: Pubkey = pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+
+/// Size of an SPL token account
+const TOKEN_ACCOUNT_LEN: usize = 165;
+
+/// SPL Token `Transfer` instruction tag
+const TOKEN_TRANSFER: u8 = 3;
+
+/// Program-owned account holding collected fees
+#[account]
+pub struct Treasury {
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl Treasury {
+    pub const SPACE: usize = 8 + 1;
+}
+
+/// Fields of an SPL token account the treasury checks
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TokenAccount {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+}
+
+/// Token accounts paying a token fee
+pub struct TokenFeeAccounts<'a, 'info> {
+    /// Payer's token account the fee is taken from
+    pub source: &'a AccountInfo<'info>,
+    /// Treasury-owned token account receiving the fee
+    pub destination: &'a AccountInfo<'info>,
+    pub token_program: &'a AccountInfo<'info>,
+}
+
+/// Parse SPL token account data
+pub fn parse_token_account(data: &[u8]) -> Result<TokenAccount> {
+    if data.len() != TOKEN_ACCOUNT_LEN {
+        return Err(error!(ValidationError::InvalidFeeAccount));
+    }
+    let key_at = |offset: usize| Pubkey::try_from(&data[offset..offset + 32]).map_err(|_| error!(ValidationError::InvalidFeeAccount));
+    let mut amount = [0u8; 8];
+    amount.copy_from_slice(&data[64..72]);
+    Ok(TokenAccount { mint: key_at(0)?, owner: key_at(32)?, amount: u64::from_le_bytes(amount) })
+}
+
+/// Read a token account owned by the SPL Token program
+pub fn read_token_account(info: &AccountInfo) -> Result<TokenAccount> {
+    if *info.owner != TOKEN_PROGRAM_ID {
+        return Err(error!(ValidationError::InvalidFeeAccount));
+    }
+    parse_token_account(&info.try_borrow_data()?)
+}
+
+/// SPL Token transfer of `amount` from `source` to `destination`, signed by `authority`
+pub fn token_transfer_instruction(source: &Pubkey, destination: &Pubkey, authority: &Pubkey, amount: u64) -> Instruction {
+    let mut data = vec![TOKEN_TRANSFER];
+    data.extend_from_slice(&amount.to_le_bytes());
+    Instruction {
+        program_id: TOKEN_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*source, false),
+            AccountMeta::new(*destination, false),
+            AccountMeta::new_readonly(*authority, true),
+        ],
+        data,
+    }
+}
+
+/// Charge `payer` the issuance fees of the program config, if any
+pub fn collect_issuance_fee<'info>(
+    config_info: &AccountInfo<'info>,
+    program_id: &Pubkey,
+    treasury: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    token_accounts: Option<TokenFeeAccounts<'_, 'info>>,
+) -> Result<()> {
+    let Some(config) = program_config::load(config_info, program_id)? else {
+        return Ok(());
+    };
+
+    if config.issuance_fee_lamports > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                system_program::Transfer { from: payer.clone(), to: treasury.clone() },
+            ),
+            config.issuance_fee_lamports,
+        )?;
+        msg!("💰 Issuance fee: {} lamports", config.issuance_fee_lamports);
+    }
+
+    if let Some(fee) = config.token_fee {
+        let Some(accounts) = token_accounts else {
+            msg!("❌ The program charges a token fee; pass the fee token accounts");
+            return Err(error!(ValidationError::InvalidFeeAccount));
+        };
+        require_keys_eq!(accounts.token_program.key(), TOKEN_PROGRAM_ID, ValidationError::InvalidFeeAccount);
+        let destination = read_token_account(accounts.destination)?;
+        require!(
+            destination.mint == fee.mint && destination.owner == treasury.key(),
+            ValidationError::InvalidFeeAccount
+        );
+        require!(read_token_account(accounts.source)?.mint == fee.mint, ValidationError::InvalidFeeAccount);
+
+        invoke(
+            &token_transfer_instruction(&accounts.source.key(), &accounts.destination.key(), &payer.key(), fee.amount),
+            &[accounts.source.clone(), accounts.destination.clone(), payer.clone(), accounts.token_program.clone()],
+        )?;
+        msg!("💰 Issuance fee: {} of mint {}", fee.amount, fee.mint);
+    }
+    Ok(())
+}
+
+/// Lamports the treasury holds above its rent-exempt minimum
+pub fn withdrawable_lamports(treasury: &AccountInfo) -> Result<u64> {
+    let reserve = Rent::get()?.minimum_balance(treasury.data_len());
+    Ok(treasury.lamports().saturating_sub(reserve))
+}
+
+/// Move `amount` collected lamports from the treasury to `destination`
+pub fn withdraw_lamports(treasury: &AccountInfo, destination: &AccountInfo, amount: u64) -> Result<()> {
+    require!(amount <= withdrawable_lamports(treasury)?, ValidationError::InsufficientTreasuryBalance);
+    treasury.sub_lamports(amount)?;
+    destination.add_lamports(amount)?;
+    Ok(())
+}
+
+/// Move `amount` collected tokens from a treasury token account, signed by the treasury PDA
+pub fn withdraw_tokens<'info>(
+    treasury: &AccountInfo<'info>,
+    bump: u8,
+    source: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    require_keys_eq!(token_program.key(), TOKEN_PROGRAM_ID, ValidationError::InvalidFeeAccount);
+    let held = read_token_account(source)?;
+    require_keys_eq!(held.owner, treasury.key(), ValidationError::InvalidFeeAccount);
+    require!(amount <= held.amount, ValidationError::InsufficientTreasuryBalance);
+
+    invoke_signed(
+        &token_transfer_instruction(&source.key(), &destination.key(), &treasury.key(), amount),
+        &[source.clone(), destination.clone(), treasury.clone(), token_program.clone()],
+        &[&[TREASURY_SEED, &[bump]]],
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_account_layout() {
+        let (mint, owner) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut data = vec![0u8; TOKEN_ACCOUNT_LEN];
+        data[..32].copy_from_slice(mint.as_ref());
+        data[32..64].copy_from_slice(owner.as_ref());
+        data[64..72].copy_from_slice(&42u64.to_le_bytes());
+
+        assert_eq!(parse_token_account(&data).unwrap(), TokenAccount { mint, owner, amount: 42 });
+        assert!(parse_token_account(&data[..82]).is_err());
+    }
+
+    #[test]
+    fn test_transfer_instruction_encoding() {
+        let (source, destination, authority) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let ix = token_transfer_instruction(&source, &destination, &authority, 7);
+        assert_eq!(ix.program_id, TOKEN_PROGRAM_ID);
+        assert_eq!((TOKEN_PROGRAM_ID.to_bytes()[0], TOKEN_PROGRAM_ID.to_bytes()[31]), (6, 169));
+        assert_eq!(ix.data, [3, 7, 0, 0, 0, 0, 0, 0, 0]);
+        assert!(ix.accounts[2].is_signer && !ix.accounts[0].is_signer);
+        assert_eq!(ix.accounts.iter().map(|meta| meta.pubkey).collect::<Vec<_>>(), [source, destination, authority]);
+    }
+}
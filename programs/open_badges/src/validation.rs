@@ -11,13 +11,59 @@ use crate::common::errors::ValidationError;
 /// Educational mode for demonstrating VCCS basic conformance checks
 const ENABLE_VCCS_ONCHAIN_VALIDATION: bool = true;
 
+/// A requirement on a credential's `@context` or `type` values
+/// Any one of the accepted values satisfies it
+#[derive(Debug, PartialEq)]
+pub struct CredentialRule {
+    /// What the rule requires, for reports and logs
+    pub description: &'static str,
+    /// Values that satisfy the rule, preferred value first
+    pub accepted: &'static [&'static str],
+}
+
+impl CredentialRule {
+    /// Whether any of `values` satisfies the rule
+    pub fn is_met<'a>(&self, mut values: impl Iterator<Item = &'a str>) -> bool {
+        values.any(|value| self.accepted.contains(&value))
+    }
+}
+
 /// VCCS v1.0 - Open Badges 3.0 Context Requirements
-/// As per IMS Global VCCS specification, these contexts are required
-const OB30_REQUIRED_CONTEXTS: &[&str] = &[
-    "https://www.w3.org/ns/credentials/v2",
-    "https://purl.imsglobal.org/spec/ob/v3p0/context-3.0.3.json"
+/// The VC 2.0 context and any published revision of the OB 3.0 context
+pub const CREDENTIAL_CONTEXT_RULES: &[CredentialRule] = &[
+    CredentialRule {
+        description: "W3C Verifiable Credentials 2.0 context",
+        accepted: &["https://www.w3.org/ns/credentials/v2"],
+    },
+    CredentialRule {
+        description: "Open Badges 3.0 context",
+        accepted: &[
+            "https://purl.imsglobal.org/spec/ob/v3p0/context-3.0.3.json",
+            "https://purl.imsglobal.org/spec/ob/v3p0/context-3.0.2.json",
+            "https://purl.imsglobal.org/spec/ob/v3p0/context-3.0.1.json",
+            "https://purl.imsglobal.org/spec/ob/v3p0/context.json",
+        ],
+    },
+];
+
+/// VCCS v1.0 - OpenBadgeCredential Type Requirements
+/// `AchievementCredential` is the spec's alias of `OpenBadgeCredential`
+pub const CREDENTIAL_TYPE_RULES: &[CredentialRule] = &[
+    CredentialRule {
+        description: "VerifiableCredential type",
+        accepted: &["VerifiableCredential"],
+    },
+    CredentialRule {
+        description: "OpenBadgeCredential type",
+        accepted: &["OpenBadgeCredential", "AchievementCredential"],
+    },
 ];
 
+/// Rules of `rules` that none of `values` satisfies
+pub fn unmet_rules<'r>(rules: &'r [CredentialRule], values: &[&str]) -> Vec<&'r CredentialRule> {
+    rules.iter().filter(|rule| !rule.is_met(values.iter().copied())).collect()
+}
+
 /// VCCS v1.0 - Achievement Required Properties
 /// Basic conformance check for Achievement type
 const ACHIEVEMENT_REQUIRED_PROPS: &[&str] = &[
@@ -78,9 +124,9 @@ pub fn vccs_basic_conformance_check(json_str: &str, required_props: &[&str], sch
 
     // VCCS Rule 3: Check for required @context values (for VC types)
     if json_str.contains("\"@context\":") {
-        for required_context in OB30_REQUIRED_CONTEXTS {
-            if !json_str.contains(required_context) {
-                msg!("VCCS conformance check: Missing required @context '{}'", required_context);
+        for rule in CREDENTIAL_CONTEXT_RULES {
+            if !rule.accepted.iter().any(|context| json_str.contains(context)) {
+                msg!("VCCS conformance check: Missing required @context: {}", rule.description);
                 // Note: This is a warning in educational mode
             }
         }
@@ -90,7 +136,7 @@ pub fn vccs_basic_conformance_check(json_str: &str, required_props: &[&str], sch
     if schema_type == "Achievement" && !json_str.contains("\"Achievement\"") {
         msg!("VCCS conformance check: Achievement type should contain 'Achievement'");
     }
-    if schema_type == "Credential" && !json_str.contains("\"OpenBadgeCredential\"") {
+    if schema_type == "Credential" && !json_str.contains("\"OpenBadgeCredential\"") && !json_str.contains("\"AchievementCredential\"") {
         msg!("VCCS conformance check: Credential type should contain 'OpenBadgeCredential'");
     }
 
@@ -129,25 +175,21 @@ pub fn validate_achievement_credential(credential: &AchievementCredential) -> Re
 
 /// Validate JSON-LD context requirements
 pub fn validate_jsonld_context(context: &[String]) -> Result<()> {
-    for required_context in OB30_REQUIRED_CONTEXTS {
-        if !context.iter().any(|c| c == required_context) {
-            msg!("Missing required @context: {}", required_context);
-            return Err(error!(ValidationError::MissingRequiredField));
-        }
+    let values: Vec<&str> = context.iter().map(String::as_str).collect();
+    if let Some(rule) = unmet_rules(CREDENTIAL_CONTEXT_RULES, &values).first() {
+        msg!("Missing required @context: {}", rule.description);
+        return Err(error!(ValidationError::MissingRequiredField));
     }
     Ok(())
 }
 
 /// Validate credential type requirements
 pub fn validate_credential_type(credential_type: &[String]) -> Result<()> {
-    if !credential_type.contains(&"VerifiableCredential".to_string()) {
+    let values: Vec<&str> = credential_type.iter().map(String::as_str).collect();
+    if let Some(rule) = unmet_rules(CREDENTIAL_TYPE_RULES, &values).first() {
+        msg!("Missing required type: {}", rule.description);
         return Err(error!(ValidationError::InvalidCredentialType));
     }
-    
-    if !credential_type.contains(&"OpenBadgeCredential".to_string()) {
-        return Err(error!(ValidationError::InvalidCredentialType));
-    }
-    
     Ok(())
 }
 
@@ -159,3 +201,497 @@ pub fn validate_achievement_type(achievement_type: &[String]) -> Result<()> {
     
     Ok(())
 }
+
+/// OB 3.0 `achievementType` vocabulary
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AchievementType {
+    Achievement,
+    ApprenticeshipCertificate,
+    Assessment,
+    Assignment,
+    AssociateDegree,
+    Award,
+    Badge,
+    BachelorDegree,
+    Certificate,
+    CertificateOfCompletion,
+    Certification,
+    CommunityService,
+    Competency,
+    Course,
+    CoCurricular,
+    Degree,
+    Diploma,
+    DoctoralDegree,
+    Fieldwork,
+    GeneralEducationDevelopment,
+    JourneymanCertificate,
+    LearningProgram,
+    License,
+    Membership,
+    ProfessionalDoctorate,
+    QualityAssuranceCredential,
+    MasterCertificate,
+    MasterDegree,
+    MicroCredential,
+    ResearchDoctorate,
+    SecondarySchoolDiploma,
+}
+
+impl AchievementType {
+    /// Every term of the vocabulary
+    pub const ALL: &'static [AchievementType] = &[
+        Self::Achievement,
+        Self::ApprenticeshipCertificate,
+        Self::Assessment,
+        Self::Assignment,
+        Self::AssociateDegree,
+        Self::Award,
+        Self::Badge,
+        Self::BachelorDegree,
+        Self::Certificate,
+        Self::CertificateOfCompletion,
+        Self::Certification,
+        Self::CommunityService,
+        Self::Competency,
+        Self::Course,
+        Self::CoCurricular,
+        Self::Degree,
+        Self::Diploma,
+        Self::DoctoralDegree,
+        Self::Fieldwork,
+        Self::GeneralEducationDevelopment,
+        Self::JourneymanCertificate,
+        Self::LearningProgram,
+        Self::License,
+        Self::Membership,
+        Self::ProfessionalDoctorate,
+        Self::QualityAssuranceCredential,
+        Self::MasterCertificate,
+        Self::MasterDegree,
+        Self::MicroCredential,
+        Self::ResearchDoctorate,
+        Self::SecondarySchoolDiploma,
+    ];
+
+    /// Term as it appears in `achievementType`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Achievement => "Achievement",
+            Self::ApprenticeshipCertificate => "ApprenticeshipCertificate",
+            Self::Assessment => "Assessment",
+            Self::Assignment => "Assignment",
+            Self::AssociateDegree => "AssociateDegree",
+            Self::Award => "Award",
+            Self::Badge => "Badge",
+            Self::BachelorDegree => "BachelorDegree",
+            Self::Certificate => "Certificate",
+            Self::CertificateOfCompletion => "CertificateOfCompletion",
+            Self::Certification => "Certification",
+            Self::CommunityService => "CommunityService",
+            Self::Competency => "Competency",
+            Self::Course => "Course",
+            Self::CoCurricular => "CoCurricular",
+            Self::Degree => "Degree",
+            Self::Diploma => "Diploma",
+            Self::DoctoralDegree => "DoctoralDegree",
+            Self::Fieldwork => "Fieldwork",
+            Self::GeneralEducationDevelopment => "GeneralEducationDevelopment",
+            Self::JourneymanCertificate => "JourneymanCertificate",
+            Self::LearningProgram => "LearningProgram",
+            Self::License => "License",
+            Self::Membership => "Membership",
+            Self::ProfessionalDoctorate => "ProfessionalDoctorate",
+            Self::QualityAssuranceCredential => "QualityAssuranceCredential",
+            Self::MasterCertificate => "MasterCertificate",
+            Self::MasterDegree => "MasterDegree",
+            Self::MicroCredential => "MicroCredential",
+            Self::ResearchDoctorate => "ResearchDoctorate",
+            Self::SecondarySchoolDiploma => "SecondarySchoolDiploma",
+        }
+    }
+
+    /// Vocabulary term named `value`, if any
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|term| term.as_str() == value)
+    }
+}
+
+/// Prefix of issuer-defined `achievementType` terms, e.g. `ext:Hackathon`
+pub const EXTENSION_ACHIEVEMENT_TYPE_PREFIX: &str = "ext:";
+
+/// Maximum length of an `achievementType` value
+pub const MAX_ACHIEVEMENT_TYPE_LEN: usize = 64;
+
+/// Validate an `achievementType` value: a vocabulary term or an `ext:` extension term
+pub fn validate_achievement_type_term(value: &str) -> Result<()> {
+    if value.len() > MAX_ACHIEVEMENT_TYPE_LEN {
+        return Err(error!(ValidationError::FieldTooLong));
+    }
+    if AchievementType::parse(value).is_none() && !is_extension_term(value) {
+        msg!("Unknown achievementType: {}", value);
+        return Err(error!(ValidationError::InvalidAchievementType));
+    }
+    Ok(())
+}
+
+/// Whether `value` is an `ext:` extension term of an OB 3.0 extensible enumeration
+pub fn is_extension_term(value: &str) -> bool {
+    value.strip_prefix(EXTENSION_ACHIEVEMENT_TYPE_PREFIX).is_some_and(|term| {
+        term.chars().next().is_some_and(|c| c.is_ascii_alphabetic())
+            && term.chars().all(|c| c.is_ascii_alphanumeric())
+    })
+}
+
+/// Maximum length of an image URI, including data: URIs
+pub const MAX_IMAGE_URI_LEN: usize = 1024;
+
+/// URI schemes accepted for Profile and Achievement images
+const IMAGE_URI_SCHEMES: &[&str] = &["https://", "ipfs://", "ar://"];
+
+/// Validate an image URI for a Profile or Achievement
+/// Accepts https, ipfs and ar URIs, and base64 `data:image/...` URIs
+pub fn validate_image_uri(uri: &str) -> Result<()> {
+    if uri.is_empty() {
+        return Err(error!(ValidationError::MissingRequiredField));
+    }
+    if uri.len() > MAX_IMAGE_URI_LEN {
+        return Err(error!(ValidationError::UrlTooLong));
+    }
+
+    if let Some(data) = uri.strip_prefix("data:") {
+        let Some((media_type, payload)) = data.split_once(";base64,") else {
+            msg!("Image data URI must be base64 encoded");
+            return Err(error!(ValidationError::InvalidUrl));
+        };
+        if !media_type.starts_with("image/") || payload.is_empty() {
+            msg!("Unsupported image data URI media type: {}", media_type);
+            return Err(error!(ValidationError::InvalidUrl));
+        }
+        return Ok(());
+    }
+
+    let has_host = IMAGE_URI_SCHEMES
+        .iter()
+        .any(|scheme| uri.strip_prefix(scheme).is_some_and(|rest| !rest.is_empty()));
+    if !has_host {
+        msg!("Unsupported image URI scheme: {}", uri);
+        return Err(error!(ValidationError::InvalidUrl));
+    }
+    Ok(())
+}
+
+/// Validate a client-supplied timestamp: non-empty and within `refresh::MAX_TIMESTAMP_LEN`
+/// Credential accounts are sized from the timestamp, so its length is bounded
+pub fn validate_timestamp_len(timestamp: &str) -> Result<()> {
+    if timestamp.is_empty() {
+        return Err(error!(ValidationError::MissingRequiredField));
+    }
+    if timestamp.len() > crate::refresh::MAX_TIMESTAMP_LEN {
+        return Err(error!(ValidationError::FieldTooLong));
+    }
+    Ok(())
+}
+
+/// Maximum length of an Achievement id
+pub const MAX_ACHIEVEMENT_ID_LEN: usize = 200;
+
+/// Maximum length of an Achievement name; the name is a PDA seed
+pub const MAX_ACHIEVEMENT_NAME_LEN: usize = 32;
+
+/// Maximum length of an Achievement description
+pub const MAX_ACHIEVEMENT_DESCRIPTION_LEN: usize = 500;
+
+/// Maximum length of an Achievement's criteria narrative
+pub const MAX_CRITERIA_NARRATIVE_LEN: usize = 200;
+
+/// Maximum length of an Achievement's criteria id
+pub const MAX_CRITERIA_ID_LEN: usize = 200;
+
+/// Fail with `error` when `value` is longer than `max` bytes
+pub fn ensure_max_len(value: &str, max: usize, error: ValidationError) -> Result<()> {
+    if value.len() > max {
+        msg!("❌ Field of {} bytes exceeds its maximum of {}", value.len(), max);
+        return Err(error!(error));
+    }
+    Ok(())
+}
+
+/// Check the text fields of a new Achievement against their maximum lengths
+/// The account reserves the description and criteria at these lengths
+pub fn validate_achievement_lengths(
+    achievement_id: &str,
+    name: &str,
+    description: &str,
+    criteria_id: Option<&str>,
+    criteria_narrative: Option<&str>,
+) -> Result<()> {
+    ensure_max_len(achievement_id, MAX_ACHIEVEMENT_ID_LEN, ValidationError::IdTooLong)?;
+    ensure_max_len(name, MAX_ACHIEVEMENT_NAME_LEN, ValidationError::NameTooLong)?;
+    ensure_max_len(description, MAX_ACHIEVEMENT_DESCRIPTION_LEN, ValidationError::DescriptionTooLong)?;
+    ensure_max_len(criteria_id.unwrap_or_default(), MAX_CRITERIA_ID_LEN, ValidationError::UrlTooLong)?;
+    ensure_max_len(criteria_narrative.unwrap_or_default(), MAX_CRITERIA_NARRATIVE_LEN, ValidationError::NarrativeTooLong)
+}
+
+/// Maximum length of a Profile name
+pub const MAX_PROFILE_NAME_LEN: usize = 128;
+
+/// Maximum length of a Profile homepage URL
+pub const MAX_PROFILE_URL_LEN: usize = 100;
+
+/// Maximum length of a Profile contact email
+pub const MAX_PROFILE_EMAIL_LEN: usize = 100;
+
+/// Validate a Profile name: non-empty and within `MAX_PROFILE_NAME_LEN`
+pub fn validate_profile_name(name: &str) -> Result<()> {
+    if name.trim().is_empty() {
+        return Err(error!(ValidationError::InvalidProfileName));
+    }
+    if name.len() > MAX_PROFILE_NAME_LEN {
+        return Err(error!(ValidationError::NameTooLong));
+    }
+    Ok(())
+}
+
+/// Validate a Profile homepage URL: http(s) with a host and no whitespace
+pub fn validate_profile_url(url: &str) -> Result<()> {
+    if url.len() > MAX_PROFILE_URL_LEN {
+        return Err(error!(ValidationError::UrlTooLong));
+    }
+    let host = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .map(|rest| rest.split(['/', '?', '#']).next().unwrap_or_default());
+    let valid = host.is_some_and(|host| !host.is_empty())
+        && !url.chars().any(|c| c.is_whitespace() || c.is_control());
+    if !valid {
+        msg!("Invalid profile URL: {}", url);
+        return Err(error!(ValidationError::InvalidProfileUrl));
+    }
+    Ok(())
+}
+
+/// Validate a Profile contact email
+/// A light check: one `@`, a non-empty local part and a dotted domain of
+/// letters, digits and hyphens
+pub fn validate_profile_email(email: &str) -> Result<()> {
+    if email.len() > MAX_PROFILE_EMAIL_LEN {
+        return Err(error!(ValidationError::FieldTooLong));
+    }
+    let valid = email.split_once('@').is_some_and(|(local, domain)| {
+        let labels: Vec<&str> = domain.split('.').collect();
+        !local.is_empty()
+            && local.chars().all(|c| c.is_ascii_graphic() && c != '@')
+            && labels.len() > 1
+            && labels.iter().all(|label| {
+                !label.is_empty()
+                    && !label.starts_with('-')
+                    && !label.ends_with('-')
+                    && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+            })
+    });
+    if !valid {
+        msg!("Invalid profile email: {}", email);
+        return Err(error!(ValidationError::InvalidProfileEmail));
+    }
+    Ok(())
+}
+
+/// Validate a Profile id: a bare did:sol DID naming a Solana key
+pub fn validate_profile_did(did: &str) -> Result<()> {
+    let valid = crate::did::DidUrl::parse(did).is_ok_and(|did_url| {
+        did_url.method == crate::did::DidMethod::Sol
+            && did_url.fragment.is_none()
+            && did_url.query.is_none()
+            && did_url.method_specific_id.rsplit(':').next().is_some_and(|id| id.parse::<Pubkey>().is_ok())
+    });
+    if !valid {
+        return Err(error!(ValidationError::InvalidProfileDid));
+    }
+    Ok(())
+}
+
+/// Validate the issuer-supplied fields of a Profile
+pub fn validate_profile_fields(
+    name: &str,
+    url: Option<&str>,
+    email: Option<&str>,
+    image: Option<&str>,
+) -> Result<()> {
+    validate_profile_name(name)?;
+    if let Some(url) = url {
+        validate_profile_url(url)?;
+    }
+    if let Some(email) = email {
+        validate_profile_email(email)?;
+    }
+    if let Some(image) = image {
+        validate_image_uri(image)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_achievement_type_term() {
+        assert!(validate_achievement_type_term("MicroCredential").is_ok());
+        assert!(validate_achievement_type_term("ext:Hackathon").is_ok());
+        assert!(validate_achievement_type_term("microcredential").is_err());
+        assert!(validate_achievement_type_term("ext:").is_err());
+        assert!(validate_achievement_type_term("ext:Hack-a-thon").is_err());
+        assert_eq!(AchievementType::parse("Badge"), Some(AchievementType::Badge));
+        assert!(AchievementType::ALL.iter().all(|term| AchievementType::parse(term.as_str()) == Some(*term)));
+    }
+
+    #[test]
+    fn test_validate_image_uri() {
+        assert!(validate_image_uri("https://example.edu/badge.png").is_ok());
+        assert!(validate_image_uri("ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi").is_ok());
+        assert!(validate_image_uri("data:image/png;base64,iVBORw0KGgo=").is_ok());
+
+        assert!(validate_image_uri("http://example.edu/badge.png").is_err());
+        assert!(validate_image_uri("javascript:alert(1)").is_err());
+        assert!(validate_image_uri("data:text/html;base64,PGgxPg==").is_err());
+        assert!(validate_image_uri("https://").is_err());
+    }
+
+    #[test]
+    fn test_validate_profile_fields() {
+        assert!(validate_profile_fields("Example University", Some("https://example.edu"), Some("badges@example.edu"), None).is_ok());
+        assert!(validate_profile_url("http://example.edu/about").is_ok());
+
+        assert!(validate_profile_name(" ").is_err());
+        assert!(validate_profile_name(&"x".repeat(MAX_PROFILE_NAME_LEN + 1)).is_err());
+        assert!(validate_profile_url("ftp://example.edu").is_err());
+        assert!(validate_profile_url("https:///path").is_err());
+        assert!(validate_profile_url("https://example.edu/a b").is_err());
+        assert!(validate_profile_url(&format!("https://{}.edu", "x".repeat(MAX_PROFILE_URL_LEN))).is_err());
+        for email in ["badges", "@example.edu", "badges@example", "badges@-example.edu", "a b@example.edu", "a@b@example.edu"] {
+            assert!(validate_profile_email(email).is_err(), "{}", email);
+        }
+    }
+
+    #[test]
+    fn test_profile_space_fits_boundary_fields() {
+        let name = "n".repeat(MAX_PROFILE_NAME_LEN);
+        let url = Some(format!("https://{}.edu", "u".repeat(MAX_PROFILE_URL_LEN - 12)));
+        let email = Some(format!("{}@example.edu", "e".repeat(MAX_PROFILE_EMAIL_LEN - 12)));
+        let image = Some(format!("https://{}", "i".repeat(MAX_IMAGE_URI_LEN - 8)));
+        assert!(validate_profile_fields(&name, url.as_deref(), email.as_deref(), image.as_deref()).is_ok());
+
+        let mut profile = crate::test_utils::fixture_profile(&Pubkey::new_unique(), &Pubkey::new_unique(), &name);
+        (profile.url, profile.email, profile.image) = (url, email, image);
+        let space = crate::Profile::space(&profile.name, &profile.url, &profile.email, &profile.image);
+        assert!(8 + profile.try_to_vec().unwrap().len() <= space);
+    }
+
+    #[test]
+    fn test_credential_space_fits_boundary_arguments() {
+        use crate::narrative::{MAX_NARRATIVE_LEN, MAX_NOTES, MAX_NOTE_LEN};
+        use crate::results::{ResultInput, MAX_RESULTS, MAX_RESULT_FIELD_LEN};
+
+        let timestamp = "2024-01-01T00:00:00.000000000+00:00".to_string();
+        assert!(validate_timestamp_len(&timestamp).is_ok());
+        assert!(validate_timestamp_len(&"0".repeat(crate::refresh::MAX_TIMESTAMP_LEN + 1)).is_err());
+        let field = "r".repeat(MAX_RESULT_FIELD_LEN);
+        let results = vec![
+            ResultInput { result_description: field.clone(), value: Some(field.clone()), achieved_level: Some(field) };
+            MAX_RESULTS
+        ];
+        let narrative = "n".repeat(MAX_NARRATIVE_LEN);
+        let notes = vec!["x".repeat(MAX_NOTE_LEN); MAX_NOTES];
+
+        let issuer = Pubkey::new_unique();
+        let mut credential = crate::test_utils::fixture_credential(&Pubkey::new_unique(), &issuer, &Pubkey::new_unique(), &Pubkey::new_unique());
+        credential.id = crate::privacy::pairwise_subject_id(&Pubkey::new_unique(), &issuer, &[7; 32]);
+        credential.credential_subject.id = Some(credential.id.clone());
+        credential.valid_from = timestamp.clone();
+        credential.issued_at = timestamp.clone();
+        credential.credential_subject.result = results.clone();
+        credential.credential_subject.narrative = Some(narrative.clone());
+        credential.notes = notes.clone();
+        credential.proof = vec![crate::Proof {
+            proof_type: "DataIntegrityProof".to_string(),
+            cryptosuite: "eddsa-rdfc-2022".to_string(),
+            created: timestamp.clone(),
+            proof_purpose: "assertionMethod".to_string(),
+            verification_method: format!("did:sol:{}#key-{}", issuer, u32::MAX),
+            proof_value: crate::multibase::encode(&[0xff; 64]),
+            previous_proof: None,
+        }];
+        // Revocation fills the fields reserved for it without resizing
+        credential.is_revoked = true;
+        credential.revoked_at = Some(timestamp.clone());
+        credential.revocation_reason = Some("x".repeat(crate::MAX_REVOCATION_REASON_LEN));
+
+        let space = crate::AchievementCredential::issuance_space(timestamp.len(), &results, Some(&narrative), &notes, &[]);
+        assert!(8 + credential.try_to_vec().unwrap().len() <= space);
+    }
+
+    #[test]
+    fn test_validate_profile_did() {
+        let key = Pubkey::new_unique();
+        assert!(validate_profile_did(&format!("did:sol:{}", key)).is_ok());
+        assert!(validate_profile_did(&format!("did:sol:devnet:{}", key)).is_ok());
+        assert!(validate_profile_did(&format!("did:sol:{}#key-1", key)).is_err());
+        assert!(validate_profile_did(&format!("did:key:{}", key)).is_err());
+        assert!(validate_profile_did("did:sol:not-a-key").is_err());
+    }
+
+    #[test]
+    fn test_credential_rules_accept_aliases() {
+        let context = |values: &[&str]| values.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+        assert!(validate_jsonld_context(&context(&[
+            "https://www.w3.org/ns/credentials/v2",
+            "https://purl.imsglobal.org/spec/ob/v3p0/context-3.0.2.json",
+        ]))
+        .is_ok());
+        assert!(validate_jsonld_context(&context(&[
+            "https://www.w3.org/2018/credentials/v1",
+            "https://purl.imsglobal.org/spec/ob/v3p0/context-3.0.3.json",
+        ]))
+        .is_err());
+
+        assert!(validate_credential_type(&context(&["VerifiableCredential", "AchievementCredential"])).is_ok());
+        assert!(validate_credential_type(&context(&["VerifiableCredential", "OpenBadgeCredential"])).is_ok());
+        assert!(validate_credential_type(&context(&["OpenBadgeCredential"])).is_err());
+        assert_eq!(unmet_rules(CREDENTIAL_TYPE_RULES, &["Badge"]).len(), 2);
+    }
+
+    #[test]
+    fn test_achievement_at_maximum_lengths_fits_its_account() {
+        let at_most = |max: usize| "x".repeat(max);
+        assert!(validate_achievement_lengths(
+            &at_most(MAX_ACHIEVEMENT_ID_LEN),
+            &at_most(MAX_ACHIEVEMENT_NAME_LEN),
+            &at_most(MAX_ACHIEVEMENT_DESCRIPTION_LEN),
+            Some(&at_most(MAX_CRITERIA_ID_LEN)),
+            Some(&at_most(MAX_CRITERIA_NARRATIVE_LEN)),
+        )
+        .is_ok());
+        let too_long = |error: ValidationError, description: &str, narrative: &str| {
+            let result = validate_achievement_lengths("id", "name", description, None, Some(narrative));
+            assert_eq!(result.unwrap_err(), error!(error));
+        };
+        too_long(ValidationError::DescriptionTooLong, &at_most(MAX_ACHIEVEMENT_DESCRIPTION_LEN + 1), "");
+        too_long(ValidationError::NarrativeTooLong, "", &at_most(MAX_CRITERIA_NARRATIVE_LEN + 1));
+        assert!(validate_achievement_lengths("id", &at_most(MAX_ACHIEVEMENT_NAME_LEN + 1), "", None, None).is_err());
+
+        let mut args = crate::args::CreateAchievementArgs::new(
+            at_most(MAX_ACHIEVEMENT_ID_LEN),
+            at_most(MAX_ACHIEVEMENT_NAME_LEN),
+            at_most(MAX_ACHIEVEMENT_DESCRIPTION_LEN),
+        );
+        args.criteria_id = Some(at_most(MAX_CRITERIA_ID_LEN));
+        args.criteria_narrative = Some(at_most(MAX_CRITERIA_NARRATIVE_LEN));
+        let mut achievement = crate::test_utils::fixture_achievement(&Pubkey::new_unique(), &Pubkey::new_unique(), &args.name);
+        achievement.id = args.achievement_id.clone();
+        achievement.description = args.description.clone();
+        achievement.criteria = crate::Criteria { id: args.criteria_id.clone(), narrative: args.criteria_narrative.clone() };
+        achievement.creator = Some(Pubkey::new_unique());
+        assert!(8 + achievement.try_to_vec().unwrap().len() <= crate::Achievement::space(&args));
+    }
+}
@@ -0,0 +1,130 @@
+//! Verification Bundles
+//!
+//! A verifier outside Solana should not need to know how credential,
+//! profile and DID accounts are laid out. `export_verification_bundle`
+//! gathers everything needed to check a credential into one JSON object:
+//!
+//! - `credential`: the credential's JSON-LD document
+//! - `proof`: the issuer's Ed25519 signature, the exact message it signs
+//!   and the public key of its verification method
+//! - `issuer`: the issuer's DID and DID document, built from the sol_did
+//!   account of its authority
+//! - `credentialStatus`: the status list entry and the status list URL, as
+//!   of the revocation list's last update, when a list was given
+//!
+//! Checking the proof is then a plain Ed25519 verification of `message`
+//! against `publicKeyMultibase`, and checking the status is a fetch of
+//! `statusListCredential`.
+
+use anchor_lang::prelude::*;
+use crate::common::errors::ValidationError;
+use crate::credential_status::RevocationList;
+use crate::did::{DidUrl, SolanaDidResolver};
+use crate::AchievementCredential;
+
+/// `type` of a verification bundle
+pub const BUNDLE_TYPE: &str = "OpenBadgeVerificationBundle";
+
+/// Build the verification bundle of `credential`
+///
+/// `did_account` is the sol_did account of the issuer's authority, and
+/// `status` the revocation list and index the credential was assigned.
+pub fn build(
+    credential: &AchievementCredential,
+    did_account: &sol_did_cpi::state::DidAccount,
+    status: Option<(&RevocationList, u32)>,
+    generated_at: &str,
+) -> Result<serde_json::Value> {
+    let issuer_did = format!("did:sol:{}", credential.issuer);
+    let did_document = SolanaDidResolver::new().resolve_from_account(&DidUrl::parse(&issuer_did)?, did_account)?;
+    let did_document = serde_json::to_value(&did_document).map_err(|_| error!(ValidationError::SerializationError))?;
+
+    let credential_status = match status {
+        Some((list, index)) => serde_json::json!({
+            "type": "StatusList2021Entry",
+            "statusPurpose": "revocation",
+            "statusListIndex": index,
+            "statusListCredential": list.metadata.status_list_url,
+            "revoked": list.is_revoked(index)?,
+            "snapshotAt": list.updated_at,
+        }),
+        None => serde_json::Value::Null,
+    };
+
+    Ok(serde_json::json!({
+        "type": BUNDLE_TYPE,
+        "credential": crate::credential::credential_account_to_json(credential),
+        "proof": proof_json(credential, &did_document),
+        "issuer": { "id": issuer_did, "didDocument": did_document },
+        "credentialStatus": credential_status,
+        "generatedAt": generated_at,
+    }))
+}
+
+/// The proof as a plain Ed25519 check; `null` for a credential without a proof
+/// A verification method without a fragment refers to the `default` key
+fn proof_json(credential: &AchievementCredential, did_document: &serde_json::Value) -> serde_json::Value {
+    let (Some(proof), Some(document)) = (credential.proof.first(), crate::canonical::signed_document(credential)) else {
+        return serde_json::Value::Null;
+    };
+    let method_id = if proof.verification_method.contains('#') {
+        proof.verification_method.clone()
+    } else {
+        format!("{}#default", proof.verification_method)
+    };
+    let public_key = did_document["verificationMethod"]
+        .as_array()
+        .and_then(|methods| methods.iter().find(|method| method["id"] == method_id.as_str()))
+        .map(|method| method["publicKeyMultibase"].clone())
+        .unwrap_or(serde_json::Value::Null);
+    serde_json::json!({
+        "type": proof.proof_type,
+        "cryptosuite": proof.cryptosuite,
+        "algorithm": "Ed25519",
+        "verificationMethod": method_id,
+        "publicKeyMultibase": public_key,
+        "proofValue": proof.proof_value,
+        "message": crate::canonical::unsigned_document(&document),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{fixture_credential, sign_credential, TestKeypair};
+
+    #[test]
+    fn test_bundle_verifies_without_accounts() {
+        let authority = TestKeypair::from_seed(4);
+        let mut credential = fixture_credential(&Pubkey::new_unique(), &Pubkey::new_unique(), &Pubkey::new_unique(), &Pubkey::new_unique());
+        sign_credential(&mut credential, &authority);
+        let did_account = SolanaDidResolver::generative_did_account(&authority.pubkey());
+        let mut list = RevocationList::new(
+            authority.pubkey(),
+            "list-1".to_string(),
+            16,
+            "Revocations".to_string(),
+            String::new(),
+            "https://example.edu/status/1".to_string(),
+            "2024-01-01T00:00:00Z".to_string(),
+        ).unwrap();
+        list.revoke_credential(3, "2024-02-01T00:00:00Z".to_string()).unwrap();
+
+        let bundle = build(&credential, &did_account, Some((&list, 3)), "2024-03-01T00:00:00Z").unwrap();
+        assert_eq!(bundle["issuer"]["didDocument"]["id"], format!("did:sol:{}", credential.issuer));
+        assert_eq!(bundle["credentialStatus"]["statusListCredential"], "https://example.edu/status/1");
+        assert_eq!(bundle["credentialStatus"]["revoked"], true);
+
+        // Ed25519 over `message` with the key the DID document publishes
+        let proof = &bundle["proof"];
+        let key = crate::multibase::decode_ed25519_key(proof["publicKeyMultibase"].as_str().unwrap()).unwrap();
+        let signature = crate::multibase::decode_signature(proof["proofValue"].as_str().unwrap()).unwrap();
+        assert_eq!(Pubkey::new_from_array(key), authority.pubkey());
+        assert!(authority.verify(proof["message"].as_str().unwrap().as_bytes(), &signature));
+
+        credential.proof.clear();
+        let unsigned = build(&credential, &did_account, None, "2024-03-01T00:00:00Z").unwrap();
+        assert!(unsigned["proof"].is_null());
+        assert!(unsigned["credentialStatus"].is_null());
+    }
+}
@@ -0,0 +1,305 @@
+//! Verifier Policies
+//!
+//! Verifiers differ in what they accept: one needs an unexpired credential,
+//! another tolerates suspension, a third only trusts a list of issuers.
+//! `verify_credential_with_policy` evaluates a `VerificationPolicy` and
+//! returns the outcome of each requested check, so the caller sees why a
+//! credential was rejected rather than a single bool. Checks the policy does
+//! not ask for are left out of the result. A policy can also require that
+//! an accreditation body it recognizes currently endorses the issuer.
+//!
+//! `verify_credential` applies the fixed default checks and returns a
+//! `VerificationResult`: one flag per check plus the error code of each
+//! failure, so a verifier UI can say exactly what is wrong.
+
+use anchor_lang::prelude::*;
+use crate::common::errors::ValidationError;
+use crate::lifecycle::CredentialState;
+use crate::proof_set::ProofPolicy;
+use crate::AchievementCredential;
+
+/// Maximum number of trusted issuers in one policy
+pub const MAX_TRUSTED_ISSUERS: usize = 16;
+
+/// Requirements a verifier places on a credential
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct VerificationPolicy {
+    /// The credential must carry proofs that verify under `proof_policy`
+    pub require_proof: bool,
+    /// Which proofs must verify when `require_proof` is set
+    pub proof_policy: ProofPolicy,
+    /// The credential must be within its validFrom/validUntil period
+    pub reject_expired: bool,
+    /// The credential must not be suspended
+    pub reject_suspended: bool,
+    /// The credential must not be revoked
+    pub reject_revoked: bool,
+    /// Issuer profiles the verifier trusts; empty to trust any issuer
+    pub trusted_issuers: Vec<Pubkey>,
+    /// The issuer's DID must still be alive and list each proof's method
+    pub require_active_issuer_did: bool,
+    /// Accrediting profile that must currently endorse the issuer, if any
+    pub required_accreditor: Option<Pubkey>,
+}
+
+/// One check a policy can request
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PolicyCheck {
+    Proof,
+    ValidityPeriod,
+    NotSuspended,
+    NotRevoked,
+    TrustedIssuer,
+    ActiveIssuerDid,
+    Accredited,
+}
+
+/// Outcome of one check
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CheckResult {
+    /// Check performed
+    pub check: PolicyCheck,
+    /// Whether the credential passed it
+    pub passed: bool,
+}
+
+/// What the handler established about the credential's proofs and issuer DID
+pub struct Evidence {
+    /// Whether the proofs verify under the policy's `proof_policy`
+    pub proof_valid: bool,
+    /// Whether the issuer DID is active; `None` when no DID account was supplied
+    pub issuer_did_active: Option<bool>,
+    /// Accreditors whose supplied endorsement of the issuer is in force
+    pub accredited_by: Vec<Pubkey>,
+}
+
+/// Evaluate every check `policy` requests against `credential` at `now`
+pub fn evaluate(
+    policy: &VerificationPolicy,
+    credential: &AchievementCredential,
+    evidence: &Evidence,
+    now: i64,
+) -> Result<Vec<CheckResult>> {
+    if policy.trusted_issuers.len() > MAX_TRUSTED_ISSUERS {
+        return Err(error!(ValidationError::TooManyItems));
+    }
+
+    let mut results = vec![];
+    let mut record = |check, passed| results.push(CheckResult { check, passed });
+    if policy.require_proof {
+        record(PolicyCheck::Proof, !credential.proof.is_empty() && evidence.proof_valid);
+    }
+    if policy.reject_expired {
+        let started = crate::parse_iso8601_to_unix(&credential.valid_from)? <= now;
+        let lapsed = match &credential.valid_until {
+            Some(valid_until) => now > crate::parse_iso8601_to_unix(valid_until)?,
+            None => false,
+        };
+        let expired = lapsed || credential.is_expired || credential.status == CredentialState::Expired;
+        record(PolicyCheck::ValidityPeriod, started && !expired);
+    }
+    if policy.reject_suspended {
+        record(PolicyCheck::NotSuspended, credential.status != CredentialState::Suspended);
+    }
+    if policy.reject_revoked {
+        record(PolicyCheck::NotRevoked, !credential.is_revoked && credential.status != CredentialState::Revoked);
+    }
+    if !policy.trusted_issuers.is_empty() {
+        record(PolicyCheck::TrustedIssuer, policy.trusted_issuers.contains(&credential.issuer));
+    }
+    if policy.require_active_issuer_did {
+        record(PolicyCheck::ActiveIssuerDid, evidence.issuer_did_active == Some(true));
+    }
+    if let Some(accreditor) = &policy.required_accreditor {
+        record(PolicyCheck::Accredited, evidence.accredited_by.contains(accreditor));
+    }
+    Ok(results)
+}
+
+/// Outcome of `verify_credential`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct VerificationResult {
+    /// Every proof verifies under the requested proof policy
+    pub proof_valid: bool,
+    /// The credential is neither revoked nor suspended
+    pub not_revoked: bool,
+    /// The current time lies within validFrom and validUntil
+    pub within_validity: bool,
+    /// The issuer's DID is active, when its DID account was supplied
+    pub issuer_trusted: bool,
+    /// Newer version of the credential, when its issuer superseded it
+    pub superseded_by: Option<Pubkey>,
+    /// `ValidationError` code of each failed check, in check order
+    pub errors: Vec<u16>,
+}
+
+impl VerificationResult {
+    /// Whether every check passed
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+/// Run the default verification checks against `credential` at `now`
+pub fn verify(
+    credential: &AchievementCredential,
+    evidence: &Evidence,
+    now: i64,
+) -> Result<VerificationResult> {
+    let mut errors = vec![];
+    let mut fail_with = |error: ValidationError| errors.push(error.code() as u16);
+
+    let proof_valid = evidence.proof_valid;
+    if !proof_valid {
+        fail_with(ValidationError::InvalidSignature);
+    }
+
+    let suspended = credential.status == CredentialState::Suspended;
+    let revoked = credential.is_revoked || credential.status == CredentialState::Revoked;
+    if revoked {
+        fail_with(ValidationError::AlreadyRevoked);
+    }
+    if suspended {
+        fail_with(ValidationError::CredentialSuspended);
+    }
+
+    let started = crate::parse_iso8601_to_unix(&credential.valid_from)? <= now;
+    let lapsed = match &credential.valid_until {
+        Some(valid_until) => now > crate::parse_iso8601_to_unix(valid_until)?,
+        None => false,
+    };
+    if !started {
+        fail_with(ValidationError::CredentialNotYetValid);
+    }
+    if lapsed {
+        fail_with(ValidationError::CredentialExpired);
+    }
+
+    let issuer_trusted = evidence.issuer_did_active != Some(false);
+    if !issuer_trusted {
+        fail_with(ValidationError::IssuerDidRevoked);
+    }
+
+    if credential.superseded_by.is_some() {
+        fail_with(ValidationError::CredentialSuperseded);
+    }
+
+    Ok(VerificationResult {
+        proof_valid,
+        not_revoked: !revoked && !suspended,
+        within_validity: started && !lapsed,
+        issuer_trusted,
+        superseded_by: credential.superseded_by,
+        errors,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::fixture_credential;
+
+    const NOW: i64 = 1_735_689_600; // 2025-01-01T00:00:00Z
+
+    fn credential() -> AchievementCredential {
+        fixture_credential(&Pubkey::new_unique(), &Pubkey::new_unique(), &Pubkey::new_unique(), &Pubkey::new_unique())
+    }
+
+    fn strict(trusted: Vec<Pubkey>) -> VerificationPolicy {
+        VerificationPolicy {
+            require_proof: true,
+            proof_policy: ProofPolicy::All,
+            reject_expired: true,
+            reject_suspended: true,
+            reject_revoked: true,
+            trusted_issuers: trusted,
+            require_active_issuer_did: true,
+            required_accreditor: None,
+        }
+    }
+
+    fn passed(results: &[CheckResult], check: PolicyCheck) -> Option<bool> {
+        results.iter().find(|result| result.check == check).map(|result| result.passed)
+    }
+
+    #[test]
+    fn test_only_requested_checks_are_reported() {
+        let credential = credential();
+        let evidence = Evidence { proof_valid: true, issuer_did_active: None, accredited_by: vec![] };
+        assert!(evaluate(&VerificationPolicy::default(), &credential, &evidence, NOW).unwrap().is_empty());
+
+        let policy = VerificationPolicy { reject_suspended: true, ..Default::default() };
+        let results = evaluate(&policy, &credential, &evidence, NOW).unwrap();
+        assert_eq!(results, vec![CheckResult { check: PolicyCheck::NotSuspended, passed: true }]);
+    }
+
+    #[test]
+    fn test_each_failure_is_attributed() {
+        let mut credential = credential();
+        credential.valid_until = Some("2024-06-01T00:00:00Z".to_string());
+        credential.status = CredentialState::Suspended;
+        let evidence = Evidence { proof_valid: true, issuer_did_active: Some(true), accredited_by: vec![] };
+
+        let results = evaluate(&strict(vec![Pubkey::new_unique()]), &credential, &evidence, NOW).unwrap();
+        assert_eq!(passed(&results, PolicyCheck::Proof), Some(false));
+        assert_eq!(passed(&results, PolicyCheck::ValidityPeriod), Some(false));
+        assert_eq!(passed(&results, PolicyCheck::NotSuspended), Some(false));
+        assert_eq!(passed(&results, PolicyCheck::NotRevoked), Some(true));
+        assert_eq!(passed(&results, PolicyCheck::TrustedIssuer), Some(false));
+        assert_eq!(passed(&results, PolicyCheck::ActiveIssuerDid), Some(true));
+
+        let trusted = strict(vec![credential.issuer]);
+        let missing_did = Evidence { proof_valid: true, issuer_did_active: None, accredited_by: vec![] };
+        let results = evaluate(&trusted, &credential, &missing_did, NOW).unwrap();
+        assert_eq!(passed(&results, PolicyCheck::TrustedIssuer), Some(true));
+        assert_eq!(passed(&results, PolicyCheck::ActiveIssuerDid), Some(false));
+        assert!(evaluate(&strict(vec![credential.issuer; MAX_TRUSTED_ISSUERS + 1]), &credential, &missing_did, NOW).is_err());
+    }
+
+    #[test]
+    fn test_required_accreditor_must_endorse_the_issuer() {
+        let credential = credential();
+        let accreditor = Pubkey::new_unique();
+        let policy = VerificationPolicy { required_accreditor: Some(accreditor), ..Default::default() };
+
+        let endorsed = Evidence { proof_valid: true, issuer_did_active: None, accredited_by: vec![accreditor] };
+        let results = evaluate(&policy, &credential, &endorsed, NOW).unwrap();
+        assert_eq!(results, vec![CheckResult { check: PolicyCheck::Accredited, passed: true }]);
+
+        let other = Evidence { proof_valid: true, issuer_did_active: None, accredited_by: vec![Pubkey::new_unique()] };
+        assert_eq!(passed(&evaluate(&policy, &credential, &other, NOW).unwrap(), PolicyCheck::Accredited), Some(false));
+    }
+
+    #[test]
+    fn test_verification_result_names_each_failure() {
+        let mut credential = credential();
+        let evidence = Evidence { proof_valid: true, issuer_did_active: None, accredited_by: vec![] };
+        let result = verify(&credential, &evidence, NOW).unwrap();
+        assert!(result.is_valid());
+        assert!(result.proof_valid && result.not_revoked && result.within_validity && result.issuer_trusted);
+
+        credential.is_revoked = true;
+        credential.valid_until = Some("2024-06-01T00:00:00Z".to_string());
+        let evidence = Evidence { proof_valid: false, issuer_did_active: Some(false), accredited_by: vec![] };
+        let result = verify(&credential, &evidence, NOW).unwrap();
+        assert!(!result.proof_valid && !result.not_revoked && !result.within_validity && !result.issuer_trusted);
+        let expected = [
+            ValidationError::InvalidSignature,
+            ValidationError::AlreadyRevoked,
+            ValidationError::CredentialExpired,
+            ValidationError::IssuerDidRevoked,
+        ];
+        assert_eq!(result.errors, expected.map(|error| error.code() as u16).to_vec());
+    }
+
+    #[test]
+    fn test_superseded_credentials_name_their_replacement() {
+        let mut credential = credential();
+        let replacement = Pubkey::new_unique();
+        credential.superseded_by = Some(replacement);
+        let evidence = Evidence { proof_valid: true, issuer_did_active: None, accredited_by: vec![] };
+        let result = verify(&credential, &evidence, NOW).unwrap();
+        assert_eq!(result.superseded_by, Some(replacement));
+        assert_eq!(result.errors, vec![ValidationError::CredentialSuperseded.code() as u16]);
+    }
+}
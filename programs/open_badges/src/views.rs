@@ -0,0 +1,306 @@
+//! Display-Oriented View Functions
+//!
+//! Compact structs returned by view instructions so public pages can render
+//! a credential from a single simulated call, without decoding accounts or
+//! re-implementing the validity rules client-side. Anchor hands the returned
+//! value back through `set_return_data`, so clients read typed data instead
+//! of parsing `msg!` logs.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use crate::branding::IssuerBranding;
+use crate::credential_status::RevocationList;
+use crate::stats::{AchievementStats, IssuerStats};
+use crate::strictness::ValidationMode;
+use crate::{Achievement, AchievementCredential, Profile};
+
+/// Status of a credential as shown to the public
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DisplayStatus {
+    /// Proof present, within its validity window and not revoked
+    Active,
+    /// Revoked by its issuer
+    Revoked,
+    /// Suspended by its issuer pending reinstatement
+    Suspended,
+    /// Past its `validUntil` date
+    Expired,
+    /// Before its `validFrom` date
+    NotYetValid,
+    /// Missing or unresolvable proof
+    Unverified,
+}
+
+/// Everything a verification widget needs to render a badge
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct PublicVerificationView {
+    /// Credential account
+    pub credential: Pubkey,
+    /// Achievement name
+    pub badge_name: String,
+    /// Achievement description
+    pub badge_description: String,
+    /// Kind of achievement (`achievementType`), if set
+    pub badge_type: Option<String>,
+    /// Issuer display name
+    pub issuer_name: String,
+    /// Issuer homepage, if set
+    pub issuer_url: Option<String>,
+    /// Recipient identifier as it appears in the credential
+    pub recipient_id: Option<String>,
+    /// Display status
+    pub status: DisplayStatus,
+    /// Issuance date (ISO 8601 string)
+    pub issued_at: String,
+    /// Expiration date (ISO 8601 string, optional)
+    pub valid_until: Option<String>,
+    /// Badge image URI
+    pub image_uri: Option<String>,
+    /// base58 SHA-256 of the badge image URI, for pinning the exact image
+    pub image_hash: Option<String>,
+    /// Issuer branding, for rendering the badge in the institution's style
+    pub branding: Option<IssuerBranding>,
+}
+
+/// Credential account fields and status, for `get_credential_summary`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct CredentialSummary {
+    /// Credential account
+    pub credential: Pubkey,
+    /// Credential id (DID)
+    pub id: String,
+    /// Issuer profile
+    pub issuer: Pubkey,
+    /// Achievement the credential awards
+    pub achievement: Pubkey,
+    /// Recipient identifier as it appears in the credential
+    pub recipient_id: Option<String>,
+    /// Issuer event sequence stamped at issuance
+    pub sequence: u64,
+    /// Issuance date (ISO 8601 string)
+    pub issued_at: String,
+    /// Start of the validity window (ISO 8601 string)
+    pub valid_from: String,
+    /// End of the validity window (ISO 8601 string, optional)
+    pub valid_until: Option<String>,
+    /// Number of proofs, the issuer's included
+    pub proof_count: u8,
+    /// When the credential was revoked, if it was
+    pub revoked_at: Option<String>,
+    /// Recipient key the credential is bound to, if any
+    pub holder_binding: Option<Pubkey>,
+    /// Display status
+    pub status: DisplayStatus,
+}
+
+/// Achievement definition and issuance totals, for `get_achievement_summary`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct AchievementSummary {
+    /// Achievement account
+    pub achievement: Pubkey,
+    /// Achievement id (DID)
+    pub id: String,
+    /// Issuer profile defining the achievement
+    pub issuer: Pubkey,
+    /// Achievement name
+    pub name: String,
+    /// Achievement description
+    pub description: String,
+    /// Kind of achievement (`achievementType`), if set
+    pub achievement_type: Option<String>,
+    /// Badge image URI
+    pub image: Option<String>,
+    /// Creation date (ISO 8601 string)
+    pub created_at: String,
+    /// Credentials issued; None until the first issuance creates the stats account
+    pub issued_count: Option<u64>,
+    /// Credentials issued and neither revoked nor expired
+    pub active_count: Option<u64>,
+}
+
+/// Issuer profile and event sequence, for `get_issuer_summary`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct IssuerSummary {
+    /// Issuer profile account
+    pub issuer: Pubkey,
+    /// Issuer id (DID)
+    pub id: String,
+    /// Authority managing the profile
+    pub authority: Pubkey,
+    /// Issuer display name
+    pub name: String,
+    /// Issuer homepage, if set
+    pub url: Option<String>,
+    /// How strictly the issuer's credentials are validated
+    pub validation_mode: ValidationMode,
+    /// How the issuer's new credentials are identified
+    pub credential_id_format: crate::credential_id::CredentialIdFormat,
+    /// Whether the issuer is frozen
+    pub frozen: bool,
+    /// Latest event sequence; None before the issuer's first event
+    pub sequence: Option<u64>,
+}
+
+/// One entry of a revocation list, for `get_revocation_status`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct RevocationStatus {
+    /// Revocation list account
+    pub revocation_list: Pubkey,
+    /// Index of the entry
+    pub index: u32,
+    /// Whether the entry is revoked
+    pub revoked: bool,
+    /// URL the list's status list credential is published at
+    pub status_list_url: String,
+    /// Last change to the list (ISO 8601 string)
+    pub updated_at: String,
+}
+
+/// Work out the display status from the credential's validity checks
+pub fn display_status(
+    credential: &AchievementCredential,
+    proof_valid: bool,
+    current_time: i64,
+) -> Result<DisplayStatus> {
+    if credential.is_revoked {
+        return Ok(DisplayStatus::Revoked);
+    }
+    if credential.status == crate::lifecycle::CredentialState::Suspended {
+        return Ok(DisplayStatus::Suspended);
+    }
+    if let Some(valid_until) = &credential.valid_until {
+        if current_time > crate::parse_iso8601_to_unix(valid_until)? {
+            return Ok(DisplayStatus::Expired);
+        }
+    }
+    if crate::parse_iso8601_to_unix(&credential.valid_from)? > current_time {
+        return Ok(DisplayStatus::NotYetValid);
+    }
+    if !proof_valid {
+        return Ok(DisplayStatus::Unverified);
+    }
+    Ok(DisplayStatus::Active)
+}
+
+/// Build the public view of a credential
+pub fn public_verification_view(
+    credential_key: Pubkey,
+    credential: &AchievementCredential,
+    achievement: &Achievement,
+    issuer: &Profile,
+    status: DisplayStatus,
+) -> PublicVerificationView {
+    PublicVerificationView {
+        credential: credential_key,
+        badge_name: achievement.name.clone(),
+        badge_description: achievement.description.clone(),
+        badge_type: achievement.achievement_type.clone(),
+        issuer_name: issuer.name.clone(),
+        issuer_url: issuer.url.clone(),
+        recipient_id: credential.credential_subject.id.clone(),
+        status,
+        issued_at: credential.issued_at.clone(),
+        valid_until: credential.valid_until.clone(),
+        image_uri: achievement.image.clone(),
+        image_hash: achievement.image.as_ref().map(|image| {
+            bs58::encode(hashv(&[image.as_bytes()]).to_bytes()).into_string()
+        }),
+        branding: issuer.branding.clone(),
+    }
+}
+
+/// Build the summary of a credential
+pub fn credential_summary(
+    credential_key: Pubkey,
+    credential: &AchievementCredential,
+    status: DisplayStatus,
+) -> CredentialSummary {
+    CredentialSummary {
+        credential: credential_key,
+        id: credential.id.clone(),
+        issuer: credential.issuer,
+        achievement: credential.credential_subject.achievement,
+        recipient_id: credential.credential_subject.id.clone(),
+        sequence: credential.sequence,
+        issued_at: credential.issued_at.clone(),
+        valid_from: credential.valid_from.clone(),
+        valid_until: credential.valid_until.clone(),
+        proof_count: credential.proof.len() as u8,
+        revoked_at: credential.revoked_at.clone(),
+        holder_binding: credential.holder_binding,
+        status,
+    }
+}
+
+/// Build the summary of an achievement
+pub fn achievement_summary(
+    achievement_key: Pubkey,
+    achievement: &Achievement,
+    stats: Option<&AchievementStats>,
+) -> AchievementSummary {
+    AchievementSummary {
+        achievement: achievement_key,
+        id: achievement.id.clone(),
+        issuer: achievement.issuer,
+        name: achievement.name.clone(),
+        description: achievement.description.clone(),
+        achievement_type: achievement.achievement_type.clone(),
+        image: achievement.image.clone(),
+        created_at: achievement.created_at.clone(),
+        issued_count: stats.map(|stats| stats.issued_count),
+        active_count: stats.map(AchievementStats::active_count),
+    }
+}
+
+/// Build the summary of an issuer profile
+pub fn issuer_summary(issuer_key: Pubkey, issuer: &Profile, stats: Option<&IssuerStats>) -> IssuerSummary {
+    IssuerSummary {
+        issuer: issuer_key,
+        id: issuer.id.clone(),
+        authority: issuer.authority,
+        name: issuer.name.clone(),
+        url: issuer.url.clone(),
+        validation_mode: issuer.validation_mode,
+        credential_id_format: issuer.credential_id_format,
+        frozen: issuer.frozen,
+        sequence: stats.map(|stats| stats.sequence),
+    }
+}
+
+/// Look up one entry of a revocation list
+pub fn revocation_status(list_key: Pubkey, list: &RevocationList, index: u32) -> Result<RevocationStatus> {
+    Ok(RevocationStatus {
+        revocation_list: list_key,
+        index,
+        revoked: list.is_revoked(index)?,
+        status_list_url: list.metadata.status_list_url.clone(),
+        updated_at: list.updated_at.clone(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::fixture_achievement;
+
+    #[test]
+    fn test_achievement_summary_counts_need_stats() {
+        let achievement_key = Pubkey::new_unique();
+        let achievement = fixture_achievement(&achievement_key, &Pubkey::new_unique(), "Rust");
+        let summary = achievement_summary(achievement_key, &achievement, None);
+        assert_eq!(summary.name, "Rust");
+        assert_eq!(summary.issued_count, None);
+
+        let stats = AchievementStats {
+            achievement: achievement_key,
+            issued_count: 5,
+            revoked_count: 1,
+            expired_count: 1,
+            last_issued_at: None,
+            bump: 255,
+        };
+        let summary = achievement_summary(achievement_key, &achievement, Some(&stats));
+        assert_eq!(summary.issued_count, Some(5));
+        assert_eq!(summary.active_count, Some(3));
+    }
+}
@@ -4,6 +4,7 @@ import { OpenBadges } from "../target/types/open_badges";
 import { expect } from "chai";
 import { PublicKey, Keypair } from "@solana/web3.js";
 import nacl from "tweetnacl";
+import { createHash } from "crypto";
 
 // Helper function to create real Ed25519 signatures
 function signMessage(message: string, keypair: Keypair): Buffer {
@@ -12,6 +13,141 @@ function signMessage(message: string, keypair: Keypair): Buffer {
   return Buffer.from(signature);
 }
 
+// Helper for the Ed25519 program instruction verifying keypair's signature over message
+function ed25519Instruction(message: string, keypair: Keypair) {
+  return anchor.web3.Ed25519Program.createInstructionWithPublicKey({
+    publicKey: keypair.publicKey.toBytes(),
+    message: Buffer.from(message, "utf8"),
+    signature: signMessage(message, keypair),
+  });
+}
+
+// Helper to derive a recipient's opt-out PDA
+function findOptOutPda(programId: PublicKey, recipient: PublicKey): PublicKey {
+  const [optOutPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("opt_out"), recipient.toBuffer()],
+    programId
+  );
+  return optOutPda;
+}
+
+// Helper to derive an achievement's counters PDA
+function findAchievementStatsPda(programId: PublicKey, achievement: PublicKey): PublicKey {
+  const [achievementStatsPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("achievement_stats"), achievement.toBuffer()],
+    programId
+  );
+  return achievementStatsPda;
+}
+
+// Helper to derive a recipient's credential index PDA
+function findCredentialIndexPda(programId: PublicKey, recipient: PublicKey): PublicKey {
+  const [credentialIndexPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("credential_index"), recipient.toBuffer()],
+    programId
+  );
+  return credentialIndexPda;
+}
+
+// Helper to derive one page of a recipient's credential index
+function findCredentialIndexPagePda(programId: PublicKey, recipient: PublicKey, page: number): PublicKey {
+  const pageBytes = Buffer.alloc(4);
+  pageBytes.writeUInt32LE(page);
+  const [credentialIndexPagePda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("credential_index"), recipient.toBuffer(), pageBytes],
+    programId
+  );
+  return credentialIndexPagePda;
+}
+
+// Helper to derive an achievement's recipient roster PDA
+function findRecipientRosterPda(programId: PublicKey, achievement: PublicKey): PublicKey {
+  const [recipientRosterPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("recipient_roster"), achievement.toBuffer()],
+    programId
+  );
+  return recipientRosterPda;
+}
+
+// Helper to derive one page of an achievement's recipient roster
+function findRecipientRosterPagePda(programId: PublicKey, achievement: PublicKey, page: number): PublicKey {
+  const pageBytes = Buffer.alloc(4);
+  pageBytes.writeUInt32LE(page);
+  const [recipientRosterPagePda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("recipient_roster"), achievement.toBuffer(), pageBytes],
+    programId
+  );
+  return recipientRosterPagePda;
+}
+
+// Helper to derive an issuer's achievement directory PDA
+function findAchievementDirectoryPda(programId: PublicKey, issuer: PublicKey): PublicKey {
+  const [achievementDirectoryPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("achievement_directory"), issuer.toBuffer()],
+    programId
+  );
+  return achievementDirectoryPda;
+}
+
+// Helper to derive one page of an issuer's achievement directory
+function findAchievementDirectoryPagePda(programId: PublicKey, issuer: PublicKey, page: number): PublicKey {
+  const pageBytes = Buffer.alloc(4);
+  pageBytes.writeUInt32LE(page);
+  const [achievementDirectoryPagePda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("achievement_directory"), issuer.toBuffer(), pageBytes],
+    programId
+  );
+  return achievementDirectoryPagePda;
+}
+
+// Helper to derive the PDA recording that an issuer consumed a signed payload
+function findConsumedPayloadPda(programId: PublicKey, issuer: PublicKey, messageData: Buffer): PublicKey {
+  const [consumedPayloadPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("consumed_payload"), issuer.toBuffer(), createHash("sha256").update(messageData).digest()],
+    programId
+  );
+  return consumedPayloadPda;
+}
+
+// Helper to derive the program config PDA
+function findProgramConfigPda(programId: PublicKey): PublicKey {
+  const [programConfigPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("program_config")],
+    programId
+  );
+  return programConfigPda;
+}
+
+// Helper to derive the program treasury PDA
+function findTreasuryPda(programId: PublicKey): PublicKey {
+  const [treasuryPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("treasury")],
+    programId
+  );
+  return treasuryPda;
+}
+
+// Helper to derive an issuer's event sequence PDA
+function findIssuerStatsPda(programId: PublicKey, issuer: PublicKey): PublicKey {
+  const [issuerStatsPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("issuer_stats"), issuer.toBuffer()],
+    programId
+  );
+  return issuerStatsPda;
+}
+
+// Helper to build the opt-out remaining accounts for a batch, in request order
+function optOutAccounts(
+  programId: PublicKey,
+  requests: { recipientPubkey: PublicKey }[]
+) {
+  return requests.map((request) => ({
+    pubkey: findOptOutPda(programId, request.recipientPubkey),
+    isWritable: false,
+    isSigner: false,
+  }));
+}
+
 describe("Open Badges v3.0 - Unified Compliance Tests", () => {
   // Configure the client to use the local cluster
   const provider = anchor.AnchorProvider.env();
@@ -91,7 +227,8 @@ describe("Open Badges v3.0 - Unified Compliance Tests", () => {
             .initializeIssuer(
               issuerName,
               "https://compliance-academy.com",
-              "contact@compliance-academy.com"
+              "contact@compliance-academy.com",
+              "https://compliance-academy.com/logo.png"
             )
             .accountsStrict({
               issuer: issuerPda,
@@ -126,6 +263,224 @@ describe("Open Badges v3.0 - Unified Compliance Tests", () => {
           throw error;
         }
       });
+
+      it("Should default to production validation and allow switching modes", async () => {
+        const setMode = (mode: object) =>
+          program.methods
+            .setIssuerValidationMode(mode as any)
+            .accountsStrict({ issuer: issuerPda, authority: issuerAuthority.publicKey })
+            .signers([issuerAuthority])
+            .rpc();
+
+        let issuerAccount = await program.account.profile.fetch(issuerPda);
+        expect(issuerAccount.validationMode).to.deep.equal({ production: {} });
+
+        await setMode({ development: {} });
+        issuerAccount = await program.account.profile.fetch(issuerPda);
+        expect(issuerAccount.validationMode).to.deep.equal({ development: {} });
+
+        // Later tests rely on strict issuance
+        await setMode({ production: {} });
+      });
+
+      it("Should publish a refresh service URL on the issuer profile", async () => {
+        const refreshServiceUrl = "https://compliance-academy.com/credentials/refresh";
+        await program.methods
+          .setIssuerRefreshService(refreshServiceUrl)
+          .accountsStrict({
+            issuer: issuerPda,
+            authority: issuerAuthority.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([issuerAuthority])
+          .rpc();
+
+        const issuerAccount = await program.account.profile.fetch(issuerPda);
+        expect(issuerAccount.refreshServiceUrl).to.equal(refreshServiceUrl);
+        console.log("✅ Refresh service published");
+      });
+
+      it("Should size issuer profiles from boundary-length fields", async () => {
+        const initializeWith = async (name: string, url: string, email: string) => {
+          const authority = Keypair.generate();
+          await program.provider.connection.requestAirdrop(authority.publicKey, 2e9);
+          await new Promise((resolve) => setTimeout(resolve, 1000));
+          const [pda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("issuer"), authority.publicKey.toBuffer()],
+            program.programId
+          );
+          await program.methods
+            .initializeIssuer(name, url, email, null)
+            .accountsStrict({
+              issuer: pda,
+              authority: authority.publicKey,
+              systemProgram: anchor.web3.SystemProgram.programId,
+            })
+            .signers([authority])
+            .rpc();
+          return pda;
+        };
+
+        // Longest accepted name (128), URL (100) and email (100)
+        const name = "N".repeat(128);
+        const url = `https://${"u".repeat(88)}.edu`;
+        const email = `${"e".repeat(88)}@example.edu`;
+        const pda = await initializeWith(name, url, email);
+        const issuerAccount = await program.account.profile.fetch(pda);
+        expect(issuerAccount.name).to.equal(name);
+        expect(issuerAccount.url).to.equal(url);
+        expect(issuerAccount.email).to.equal(email);
+
+        try {
+          await initializeWith("N".repeat(129), url, email);
+          expect.fail("A name over the maximum length should be rejected");
+        } catch (error) {
+          expect(error.toString()).to.include("NameTooLong");
+        }
+        console.log("✅ Boundary-length profiles sized from instruction data");
+      });
+
+      it("Should only create PDA-authority issuers for a real PDA of the owning program", async () => {
+        // A keypair signer is on the curve, so no seeds can derive it
+        const impostor = Keypair.generate();
+        const [pda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("issuer"), impostor.publicKey.toBuffer()],
+          program.programId
+        );
+        try {
+          await program.methods
+            .initializeIssuerPdaAuthority("DAO Issuer", null, null, null, program.programId, [Buffer.from("dao"), Buffer.from([255])])
+            .accountsStrict({
+              issuer: pda,
+              authority: impostor.publicKey,
+              payer: provider.wallet.publicKey,
+              systemProgram: anchor.web3.SystemProgram.programId,
+            })
+            .signers([impostor])
+            .rpc();
+          expect.fail("A keypair authority should not pass as a program PDA");
+        } catch (error) {
+          expect(error.toString()).to.include("UnauthorizedAccess");
+        }
+      });
+
+      it("Should publish the issuer's other identifiers in its Profile document", async () => {
+        await program.methods
+          .setIssuerOtherIdentifiers([
+            { identifier: "https://ror.org/02mhbdp94", identifierType: "ext:ROR" },
+            { identifier: "UNI-0042", identifierType: "sourcedId" },
+          ])
+          .accountsStrict({
+            issuer: issuerPda,
+            authority: issuerAuthority.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([issuerAuthority])
+          .rpc();
+
+        const profileJson = JSON.parse(
+          await program.methods.generateProfileJson().accountsStrict({ issuer: issuerPda }).view()
+        );
+        expect(profileJson.otherIdentifier).to.have.length(2);
+        expect(profileJson.otherIdentifier[0].identifierType).to.equal("ext:ROR");
+
+        try {
+          await program.methods
+            .setIssuerOtherIdentifiers([{ identifier: "5493001KJTIIGC8Y1R12", identifierType: "LEI" }])
+            .accountsStrict({
+              issuer: issuerPda,
+              authority: issuerAuthority.publicKey,
+              systemProgram: anchor.web3.SystemProgram.programId,
+            })
+            .signers([issuerAuthority])
+            .rpc();
+          expect.fail("An identifierType outside the vocabulary should be rejected");
+        } catch (error) {
+          expect(error.toString()).to.include("InvalidIdentifier");
+        }
+      });
+
+      it("Should publish translations of the issuer's name as a language map", async () => {
+        await program.methods
+          .setIssuerNameTranslations([{ language: "pt-BR", text: "Academia de Conformidade" }])
+          .accountsStrict({
+            issuer: issuerPda,
+            authority: issuerAuthority.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([issuerAuthority])
+          .rpc();
+
+        const profileJson = JSON.parse(
+          await program.methods.generateProfileJson().accountsStrict({ issuer: issuerPda }).view()
+        );
+        expect(profileJson.nameMap["pt-BR"]).to.equal("Academia de Conformidade");
+        expect(profileJson["@context"][2].nameMap["@container"]).to.equal("@language");
+
+        try {
+          await program.methods
+            .setIssuerNameTranslations([{ language: "pt_BR", text: "Academia" }])
+            .accountsStrict({
+              issuer: issuerPda,
+              authority: issuerAuthority.publicKey,
+              systemProgram: anchor.web3.SystemProgram.programId,
+            })
+            .signers([issuerAuthority])
+            .rpc();
+          expect.fail("A malformed language tag should be rejected");
+        } catch (error) {
+          expect(error.toString()).to.include("InvalidTranslation");
+        }
+      });
+
+      it("Should embed an accreditation body's endorsement in the Profile document", async () => {
+        const accreditorAuthority = Keypair.generate();
+        await provider.connection.confirmTransaction(
+          await provider.connection.requestAirdrop(accreditorAuthority.publicKey, anchor.web3.LAMPORTS_PER_SOL)
+        );
+        const [accreditorPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("issuer"), accreditorAuthority.publicKey.toBuffer()],
+          program.programId
+        );
+        await program.methods
+          .initializeIssuer("Engineering Accreditation Board", null, null, null)
+          .accountsStrict({
+            issuer: accreditorPda,
+            authority: accreditorAuthority.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([accreditorAuthority])
+          .rpc();
+
+        const [accreditationPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("accreditation"), accreditorPda.toBuffer(), issuerPda.toBuffer()],
+          program.programId
+        );
+        const now = Math.floor(Date.now() / 1000);
+        await program.methods
+          .endorseIssuer("Undergraduate engineering degrees", new anchor.BN(now - 60), new anchor.BN(now + 86400))
+          .accountsStrict({
+            accreditation: accreditationPda,
+            accreditor: accreditorPda,
+            issuer: issuerPda,
+            authority: accreditorAuthority.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([accreditorAuthority])
+          .rpc();
+
+        const profileJson = JSON.parse(
+          await program.methods
+            .generateProfileJson()
+            .accountsStrict({ issuer: issuerPda })
+            .remainingAccounts([{ pubkey: accreditationPda, isSigner: false, isWritable: false }])
+            .view()
+        );
+        expect(profileJson.endorsement).to.have.length(1);
+        expect(profileJson.endorsement[0].type).to.include("EndorsementCredential");
+        expect(profileJson.endorsement[0].issuer.id).to.equal("did:sol:" + accreditorPda.toString());
+        console.log("✅ Accreditation embedded as an EndorsementCredential");
+      });
     });
 
     describe("2. Achievement Definition", () => {
@@ -139,16 +494,29 @@ describe("Open Badges v3.0 - Unified Compliance Tests", () => {
         try {
           const tx = await program.methods
             .createAchievement(
-              achievementId,
-              achievementName,
-              description,
-              "Complete all compliance tests, demonstrate proof system understanding, and validate credential structures according to Open Badges v3.0 specification",
-              criteriaId,
-              null // No image for this test
+              {
+                version: 4,
+                achievementId,
+                name: achievementName,
+                description,
+                criteriaNarrative: "Complete all compliance tests, demonstrate proof system understanding, and validate credential structures according to Open Badges v3.0 specification",
+                criteriaId,
+                image: "https://compliance-academy.com/badges/unified-compliance.png",
+                resultDescriptions: [], // No result descriptions
+                extensions: [], // No extensions
+                achievementType: "Certificate",
+                requirements: { requiredCompetencies: [], minResults: [] },
+                nameI18n: [{ language: "pt-BR", text: "Conformidade Unificada" }],
+                descriptionI18n: [],
+                requiresApprovedResult: false, // Issued without moderation
+              }
             )
             .accountsStrict({
               achievement: achievementPda,
               issuer: issuerPda,
+              creator: null,
+              achievementDirectory: findAchievementDirectoryPda(program.programId, issuerPda),
+              achievementDirectoryPage: findAchievementDirectoryPagePda(program.programId, issuerPda, 0),
               authority: issuerAuthority.publicKey,
               systemProgram: anchor.web3.SystemProgram.programId,
             })
@@ -157,6 +525,18 @@ describe("Open Badges v3.0 - Unified Compliance Tests", () => {
 
           console.log("✅ Achievement creation transaction:", tx);
 
+          // Verify the issuer's directory lists the achievement
+          const achievementsPage = await program.methods
+            .getAchievementsPage(0)
+            .accountsStrict({
+              achievementDirectory: findAchievementDirectoryPda(program.programId, issuerPda),
+              achievementDirectoryPage: findAchievementDirectoryPagePda(program.programId, issuerPda, 0),
+            })
+            .view();
+          expect(achievementsPage.achievements.map((key) => key.toString())).to.include(
+            achievementPda.toString()
+          );
+
           // Fetch and verify the achievement account
           const achievementAccount = await program.account.achievement.fetch(
             achievementPda
@@ -229,18 +609,48 @@ describe("Open Badges v3.0 - Unified Compliance Tests", () => {
         try {
           const tx = await program.methods
             .issueAchievementCredential(
-              recipientKeypair.publicKey,
-              signatureData,
-              messageData,
-              timestamp
+              {
+                version: 3,
+                recipientPubkey: recipientKeypair.publicKey,
+                signatureData,
+                messageData,
+                timestamp,
+                results: [], // No graded results
+                narrative: "Completed every module of the course",
+                notes: ["Reviewed by the course committee"],
+                credentialSchemas: [], // No credential schemas
+                bindHolder: false, // Not holder-bound
+                termsOfUse: [{ id: "https://example.edu/badge-policy", termsType: "IssuerPolicy" }],
+                pairwiseSubjectId: null, // Not pairwise-issued
+              }
             )
             .accountsStrict({
+              programConfig: findProgramConfigPda(program.programId),
+              treasury: findTreasuryPda(program.programId),
+              feeTokenAccount: null,
+              treasuryTokenAccount: null,
+              tokenProgram: null,
               credential: credentialPda,
               achievement: achievementPda,
               issuer: issuerPda,
+              cpiPolicy: null,
+              instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+              issuerStats: findIssuerStatsPda(program.programId, issuerPda),
+              consumedPayload: findConsumedPayloadPda(program.programId, issuerPda, messageData),
               authority: issuerAuthority.publicKey,
               systemProgram: anchor.web3.SystemProgram.programId,
+              recipientOptOut: findOptOutPda(program.programId, recipientKeypair.publicKey),
+              achievementStats: findAchievementStatsPda(program.programId, achievementPda),
+              credentialIndex: findCredentialIndexPda(program.programId, recipientKeypair.publicKey),
+              credentialIndexPage: findCredentialIndexPagePda(program.programId, recipientKeypair.publicKey, 0),
+              recipientRoster: findRecipientRosterPda(program.programId, achievementPda),
+              recipientRosterPage: findRecipientRosterPagePda(program.programId, achievementPda, 0),
+              resultEntry: null,
+              rubric: null,
+              schemaRegistry: null,
+              nomination: null,
             })
+            .preInstructions([ed25519Instruction(messageJson, issuerAuthority)])
             .signers([issuerAuthority])
             .rpc();
 
@@ -267,6 +677,50 @@ describe("Open Badges v3.0 - Unified Compliance Tests", () => {
             issuerPda.toString()
           );
 
+          // Verify the sync cursor matches the issuer's latest sequence
+          const issuerStats = await program.account.issuerStats.fetch(
+            findIssuerStatsPda(program.programId, issuerPda)
+          );
+          expect(issuerStats.issuer.toString()).to.equal(issuerPda.toString());
+          expect(credentialAccount.sequence.toNumber()).to.equal(
+            issuerStats.sequence.toNumber()
+          );
+
+          // Verify the achievement counters include this credential
+          const achievementStats = await program.account.achievementStats.fetch(
+            findAchievementStatsPda(program.programId, achievementPda)
+          );
+          expect(achievementStats.issuedCount.toNumber()).to.be.greaterThan(0);
+          expect(achievementStats.lastIssuedAt).to.equal(credentialAccount.issuedAt);
+
+          // Verify the issuance override replaced the issuer's default terms
+          expect(credentialAccount.termsOfUse).to.have.length(1);
+          expect(credentialAccount.termsOfUse[0].termsType).to.equal("IssuerPolicy");
+
+          // Verify the recipient's wallet can enumerate the credential
+          const credentialsPage = await program.methods
+            .getCredentialsPage(0)
+            .accountsStrict({
+              credentialIndex: findCredentialIndexPda(program.programId, recipientKeypair.publicKey),
+              credentialIndexPage: findCredentialIndexPagePda(program.programId, recipientKeypair.publicKey, 0),
+            })
+            .view();
+          expect(credentialsPage.credentials.map((key) => key.toString())).to.include(
+            credentialPda.toString()
+          );
+
+          // Verify employers can enumerate the achievement's holders
+          const rosterPage = await program.methods
+            .getRecipientRosterPage(0)
+            .accountsStrict({
+              recipientRoster: findRecipientRosterPda(program.programId, achievementPda),
+              recipientRosterPage: findRecipientRosterPagePda(program.programId, achievementPda, 0),
+            })
+            .view();
+          expect(rosterPage.holders.map((holder) => holder.credential.toString())).to.include(
+            credentialPda.toString()
+          );
+
           // Verify AchievementSubject compliance
           expect(credentialAccount.credentialSubject.id).to.equal(
             "did:sol:" + recipientKeypair.publicKey.toString()
@@ -277,6 +731,10 @@ describe("Open Badges v3.0 - Unified Compliance Tests", () => {
           expect(
             credentialAccount.credentialSubject.achievement.toString()
           ).to.equal(achievementPda.toString());
+          expect(credentialAccount.credentialSubject.narrative).to.equal(
+            "Completed every module of the course"
+          );
+          expect(credentialAccount.notes).to.deep.equal(["Reviewed by the course committee"]);
 
           // Verify credential status
           expect(credentialAccount.isRevoked).to.be.false;
@@ -294,17 +752,42 @@ describe("Open Badges v3.0 - Unified Compliance Tests", () => {
         }
       });
 
+      it("Should not expire a credential before its validUntil date", async () => {
+        try {
+          await program.methods
+            .expireCredential()
+            .accountsStrict({
+              credential: credentialPda,
+              achievementStats: findAchievementStatsPda(program.programId, achievementPda),
+              cranker: program.provider.publicKey!,
+              systemProgram: anchor.web3.SystemProgram.programId,
+            })
+            .rpc();
+          expect.fail("A credential without validUntil never expires");
+        } catch (error) {
+          expect(error.toString()).to.include("CredentialNotExpired");
+        }
+        const credentialAccount = await program.account.achievementCredential.fetch(credentialPda);
+        expect(credentialAccount.isExpired).to.be.false;
+      });
+
       it("Should verify the issued credential cryptographically", async () => {
         try {
           const result = await program.methods
-            .verifyCredential()
+            .verifyCredential({ all: {} })
             .accountsStrict({
               credential: credentialPda,
+              issuerKey: null,
+              issuer: null,
+              issuerDidAccount: null,
+              instructions: null,
+              staffKey: null,
+              staffDidData: null,
             })
             .view();
 
           // Since we're using real Ed25519 signatures, verification should pass
-          expect(result).to.be.true;
+          expect(result.errors).to.be.empty;
           console.log("✅ Real Ed25519 signature verification successful");
           console.log("   ✅ Ed25519 signature validation passed");
           console.log("   ✅ Data integrity verified");
@@ -314,14 +797,235 @@ describe("Open Badges v3.0 - Unified Compliance Tests", () => {
         }
       });
 
+      it("Should report each check of a verifier policy", async () => {
+        const results = await program.methods
+          .verifyCredentialWithPolicy({
+            requireProof: true,
+            proofPolicy: { all: {} },
+            rejectExpired: true,
+            rejectSuspended: true,
+            rejectRevoked: true,
+            trustedIssuers: [Keypair.generate().publicKey],
+            requireActiveIssuerDid: false,
+            requiredAccreditor: null,
+          })
+          .accountsStrict({
+            credential: credentialPda,
+            issuerKey: null,
+            issuer: null,
+            issuerDidAccount: null,
+            instructions: null,
+            staffKey: null,
+            staffDidData: null,
+          })
+          .view();
+
+        const failed = results.filter((result: any) => !result.passed).map((result: any) => Object.keys(result.check)[0]);
+        expect(results).to.have.length(5);
+        expect(failed).to.deep.equal(["trustedIssuer"]);
+      });
+
+      it("Should return a public verification view", async () => {
+        const view = await program.methods
+          .getPublicVerificationView()
+          .accountsStrict({
+            credential: credentialPda,
+            achievement: achievementPda,
+            issuer: issuerPda,
+            issuerKey: null,
+          })
+          .view();
+
+        expect(view.badgeName).to.equal(achievementName);
+        expect(view.issuerName).to.equal(issuerName);
+        expect(view.status).to.have.property("active");
+        expect(view.imageUri).to.equal(
+          "https://compliance-academy.com/badges/unified-compliance.png"
+        );
+      });
+
+      it("Should surface issuer branding in the public view", async () => {
+        const branding = {
+          primaryColor: "#0B5FFF",
+          logoUri: "https://compliance-academy.com/brand/logo.svg",
+          logoHash: "ab".repeat(32),
+          backgroundImage: null,
+        };
+
+        await program.methods
+          .updateIssuerBranding(branding)
+          .accountsStrict({
+            issuer: issuerPda,
+            authority: issuerAuthority.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([issuerAuthority])
+          .rpc();
+
+        const view = await program.methods
+          .getPublicVerificationView()
+          .accountsStrict({
+            credential: credentialPda,
+            achievement: achievementPda,
+            issuer: issuerPda,
+            issuerKey: null,
+          })
+          .view();
+
+        expect(view.branding.primaryColor).to.equal("#0B5FFF");
+        expect(view.branding.logoUri).to.equal(branding.logoUri);
+
+        // Rejects colors that are not #RRGGBB
+        try {
+          await program.methods
+            .updateIssuerBranding({ ...branding, primaryColor: "blue" })
+            .accountsStrict({
+              issuer: issuerPda,
+              authority: issuerAuthority.publicKey,
+              systemProgram: anchor.web3.SystemProgram.programId,
+            })
+            .signers([issuerAuthority])
+            .rpc();
+          expect.fail("Invalid brand color should be rejected");
+        } catch (error) {
+          expect(error.message).to.include("ValidationFailed");
+        }
+      });
+
+      it("Should verify several credentials in one batch", async () => {
+        const mask = await program.methods
+          .verifyCredentialsBatch()
+          .accountsStrict({})
+          .remainingAccounts([
+            { pubkey: credentialPda, isWritable: false, isSigner: false },
+            // Not a credential account, so its bit stays clear
+            { pubkey: achievementPda, isWritable: false, isSigner: false },
+          ])
+          .view();
+
+        expect(mask.toNumber()).to.equal(0b01);
+        console.log("✅ Batch verification bitmask:", mask.toString(2));
+      });
+
+      it("Should upgrade a credential proof and keep the old one deprecated", async () => {
+        const before = await program.account.achievementCredential.fetch(credentialPda);
+        const context = JSON.stringify(before.context);
+        const type = JSON.stringify(before["r#type"]);
+        const subjectType = JSON.stringify(before.credentialSubject.subjectType);
+        const messageJson = `{"@context":${context},"id":"${before.id}","type":${type},"issuer":"did:sol:${issuerPda.toString()}","validFrom":"${before.validFrom}","credentialSubject":{"id":"${before.credentialSubject.id}","type":${subjectType},"achievement":"did:sol:${achievementPda.toString()}"}}`;
+
+        await program.methods
+          .upgradeCredentialProof(signMessage(messageJson, issuerAuthority))
+          .accountsStrict({
+            credential: credentialPda,
+            issuer: issuerPda,
+            cpiPolicy: null,
+            instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+            issuerStats: findIssuerStatsPda(program.programId, issuerPda),
+            authority: issuerAuthority.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .preInstructions([ed25519Instruction(messageJson, issuerAuthority)])
+          .signers([issuerAuthority])
+          .rpc();
+
+        const after = await program.account.achievementCredential.fetch(credentialPda);
+        expect(after.deprecatedProofs).to.have.length(1);
+        expect(after.deprecatedProofs[0].proof.proofValue).to.equal(
+          before.proof[0].proofValue
+        );
+        expect(after.proof[0].proofValue.startsWith("z")).to.be.true;
+        console.log("✅ Proof upgraded, previous proof kept as deprecated");
+      });
+
+      it("Should attach issuer-defined extensions to a credential", async () => {
+        const extensionContext = "https://compliance-academy.com/ns/transcript/v1";
+        // @context is signed, so the authority re-signs the credential with the extension context added
+        const before = await program.account.achievementCredential.fetch(credentialPda);
+        const context = JSON.stringify([...before.context, extensionContext]);
+        const type = JSON.stringify(before["r#type"]);
+        const subjectType = JSON.stringify(before.credentialSubject.subjectType);
+        const messageJson = `{"@context":${context},"id":"${before.id}","type":${type},"issuer":"did:sol:${issuerPda.toString()}","validFrom":"${before.validFrom}","credentialSubject":{"id":"${before.credentialSubject.id}","type":${subjectType},"achievement":"did:sol:${achievementPda.toString()}"}}`;
+
+        await program.methods
+          .setCredentialExtensions(
+            [
+              {
+                context: extensionContext,
+                term: "creditHours",
+                inlineValue: "3",
+                valueHash: null,
+              },
+              {
+                context: extensionContext,
+                term: "transcript",
+                inlineValue: null,
+                valueHash: Array.from(createHash("sha256").update('{"grade":"A"}').digest()),
+              },
+            ],
+            signMessage(messageJson, issuerAuthority)
+          )
+          .accountsStrict({
+            credential: credentialPda,
+            issuer: issuerPda,
+            instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+            issuerStats: findIssuerStatsPda(program.programId, issuerPda),
+            authority: issuerAuthority.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .preInstructions([ed25519Instruction(messageJson, issuerAuthority)])
+          .signers([issuerAuthority])
+          .rpc();
+
+        const credential = await program.account.achievementCredential.fetch(credentialPda);
+        expect(credential.extensions.map((e) => e.term)).to.deep.equal(["creditHours", "transcript"]);
+        expect(credential.context).to.include(extensionContext);
+        expect(credential.deprecatedProofs).to.have.length(before.deprecatedProofs.length + 1);
+        console.log("✅ Extensions attached with their context");
+      });
+
+      it("Should register and verify a baked badge image hash", async () => {
+        const bakedImage = Buffer.from("\x89PNG baked badge image");
+        const imageHash = Array.from(createHash("sha256").update(bakedImage).digest());
+        await program.methods
+          .registerBakedBadge(imageHash, { png: {} })
+          .accountsStrict({
+            credential: credentialPda,
+            issuer: issuerPda,
+            issuerStats: findIssuerStatsPda(program.programId, issuerPda),
+            authority: issuerAuthority.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([issuerAuthority])
+          .rpc();
+
+        const registered = await program.methods
+          .verifyBakedBadge(imageHash)
+          .accountsStrict({ credential: credentialPda })
+          .view();
+        const tampered = await program.methods
+          .verifyBakedBadge(Array.from(createHash("sha256").update("tampered").digest()))
+          .accountsStrict({ credential: credentialPda })
+          .view();
+        expect(registered).to.be.true;
+        expect(tampered).to.be.false;
+        console.log("✅ Baked image hash registered and checked");
+      });
+
       it("Should support credential revocation", async () => {
         try {
           const tx = await program.methods
-            .revokeCredentialDirect()
+            .revokeCredentialDirect("Issued in error")
             .accountsStrict({
+              programConfig: findProgramConfigPda(program.programId),
               credential: credentialPda,
               issuer: issuerPda,
+              cpiPolicy: null,
+              instructions: null,
+              achievementStats: findAchievementStatsPda(program.programId, achievementPda),
+              issuerStats: findIssuerStatsPda(program.programId, issuerPda),
               authority: issuerAuthority.publicKey,
+              systemProgram: anchor.web3.SystemProgram.programId,
             })
             .signers([issuerAuthority])
             .rpc();
@@ -333,10 +1037,15 @@ describe("Open Badges v3.0 - Unified Compliance Tests", () => {
             await program.account.achievementCredential.fetch(credentialPda);
           expect(credentialAccount.isRevoked).to.be.true;
           expect(credentialAccount.revokedAt).to.not.be.null;
+          expect(credentialAccount.revocationReason).to.equal("Issued in error");
+          expect(credentialAccount.revokedBy.toString()).to.equal(
+            issuerAuthority.publicKey.toString()
+          );
 
           console.log("📋 Revocation Verified:");
           console.log("   ✅ Credential marked as revoked");
           console.log("   ✅ Revocation timestamp recorded");
+          console.log("   ✅ Revocation reason and revoker recorded");
         } catch (error) {
           console.error("Credential revocation failed:", error);
           throw error;
@@ -346,13 +1055,21 @@ describe("Open Badges v3.0 - Unified Compliance Tests", () => {
       it("Should fail verification for revoked credential", async () => {
         try {
           const result = await program.methods
-            .verifyCredential()
+            .verifyCredential({ all: {} })
             .accountsStrict({
               credential: credentialPda,
+              issuerKey: null,
+              issuer: null,
+              issuerDidAccount: null,
+              instructions: null,
+              staffKey: null,
+              staffDidData: null,
             })
             .view();
 
-          expect(result).to.be.false;
+          expect(result.notRevoked).to.be.false;
+          expect(result.proofValid).to.be.true;
+          expect(result.errors).to.have.length(1);
           console.log("✅ Revoked credential verification correctly failed");
           console.log("   ✅ Revocation status properly enforced");
         } catch (error) {
@@ -415,18 +1132,48 @@ describe("Open Badges v3.0 - Unified Compliance Tests", () => {
         try {
           const tx = await program.methods
             .issueAchievementCredential(
-              enhancedRecipientKeypair.publicKey,
-              signatureData,
-              messageData,
-              timestamp
+              {
+                version: 3,
+                recipientPubkey: enhancedRecipientKeypair.publicKey,
+                signatureData,
+                messageData,
+                timestamp,
+                results: [], // No graded results
+                narrative: null, // No narrative
+                notes: [], // No notes
+                credentialSchemas: [], // No credential schemas
+                bindHolder: false, // Not holder-bound
+                termsOfUse: null, // Issuer's default terms
+                pairwiseSubjectId: null, // Not pairwise-issued
+              }
             )
             .accountsStrict({
+              programConfig: findProgramConfigPda(program.programId),
+              treasury: findTreasuryPda(program.programId),
+              feeTokenAccount: null,
+              treasuryTokenAccount: null,
+              tokenProgram: null,
               credential: enhancedCredentialPda,
               achievement: achievementPda,
               issuer: issuerPda,
+              cpiPolicy: null,
+              instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+              issuerStats: findIssuerStatsPda(program.programId, issuerPda),
+              consumedPayload: findConsumedPayloadPda(program.programId, issuerPda, messageData),
               authority: issuerAuthority.publicKey,
               systemProgram: anchor.web3.SystemProgram.programId,
+              recipientOptOut: findOptOutPda(program.programId, enhancedRecipientKeypair.publicKey),
+              achievementStats: findAchievementStatsPda(program.programId, achievementPda),
+              credentialIndex: findCredentialIndexPda(program.programId, enhancedRecipientKeypair.publicKey),
+              credentialIndexPage: findCredentialIndexPagePda(program.programId, enhancedRecipientKeypair.publicKey, 0),
+              recipientRoster: findRecipientRosterPda(program.programId, achievementPda),
+              recipientRosterPage: findRecipientRosterPagePda(program.programId, achievementPda, 0),
+              resultEntry: null,
+              rubric: null,
+              schemaRegistry: null,
+              nomination: null,
             })
+            .preInstructions([ed25519Instruction(messageJson, issuerAuthority)])
             .signers([issuerAuthority])
             .rpc();
 
@@ -440,13 +1187,13 @@ describe("Open Badges v3.0 - Unified Compliance Tests", () => {
           console.log("📋 Enhanced Proof System Verified:");
           console.log(
             `   Cryptosuite: ${
-              credentialAccount.proof.cryptosuite || "eddsa-rdfc-2022"
+              credentialAccount.proof[0].cryptosuite || "eddsa-rdfc-2022"
             }`
           );
-          console.log(`   Proof Type: ${credentialAccount.proof.proofType}`);
-          console.log(`   Purpose: ${credentialAccount.proof.proofPurpose}`);
+          console.log(`   Proof Type: ${credentialAccount.proof[0].proofType}`);
+          console.log(`   Purpose: ${credentialAccount.proof[0].proofPurpose}`);
           console.log(
-            `   Verification Method: ${credentialAccount.proof.verificationMethod}`
+            `   Verification Method: ${credentialAccount.proof[0].verificationMethod}`
           );
           console.log("   ✅ DataIntegrityProof structure compliant");
           console.log("   ✅ Ed25519 signature algorithm supported");
@@ -460,14 +1207,20 @@ describe("Open Badges v3.0 - Unified Compliance Tests", () => {
       it("Should verify enhanced proof cryptographically", async () => {
         try {
           const result = await program.methods
-            .verifyCredential()
+            .verifyCredential({ all: {} })
             .accountsStrict({
               credential: enhancedCredentialPda,
+              issuerKey: null,
+              issuer: null,
+              issuerDidAccount: null,
+              instructions: null,
+              staffKey: null,
+              staffDidData: null,
             })
             .view();
 
           // Since we're using real Ed25519 signatures, verification should pass
-          expect(result).to.be.true;
+          expect(result.errors).to.be.empty;
           console.log("✅ Enhanced Ed25519-RDF-2022 verification successful");
           console.log("   ✅ Cryptographic signature validation");
           console.log("   ✅ RDF Dataset Normalization compatible");
@@ -476,6 +1229,46 @@ describe("Open Badges v3.0 - Unified Compliance Tests", () => {
           throw error;
         }
       });
+
+      it("Should present an SD-JWT that discloses only chosen claims", async () => {
+        const draft = await program.methods
+          .generateSdJwtSigningInput(
+            ["https://compliance-academy.com/portfolio/enhanced"],
+            [...nacl.randomBytes(32)],
+            new anchor.BN(Math.floor(Date.now() / 1000))
+          )
+          .accountsStrict({
+            issuer: issuerPda,
+            achievement: achievementPda,
+            credential: enhancedCredentialPda,
+            rubric: null,
+          })
+          .view();
+        const message = Buffer.from(draft.signingInput);
+        const signature = Buffer.from(nacl.sign.detached(message, issuerAuthority.secretKey));
+        const payload = JSON.parse(
+          Buffer.from(draft.signingInput.split(".")[1], "base64url").toString()
+        );
+        expect(payload.vc.credentialSubject.id).to.be.undefined;
+        expect(payload.vc.credentialSubject._sd).to.have.length(2);
+
+        // Reveal the recipient, withhold the identifier and evidence
+        const jwt = `${draft.signingInput}.${signature.toString("base64url")}`;
+        const presentation = `${jwt}~${draft.disclosures[0]}~`;
+        const verified = await program.methods
+          .verifySdJwtPresentation(presentation)
+          .accountsStrict({ issuer: issuerPda, instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY })
+          .preInstructions([
+            anchor.web3.Ed25519Program.createInstructionWithPublicKey({
+              publicKey: issuerAuthority.publicKey.toBytes(),
+              message,
+              signature,
+            }),
+          ])
+          .view();
+        expect(verified).to.be.true;
+        console.log("✅ SD-JWT presentation verified with one disclosure");
+      });
     });
 
     describe("5. Credential Status and Revocation Lists", () => {
@@ -530,6 +1323,27 @@ describe("Open Badges v3.0 - Unified Compliance Tests", () => {
         }
       });
 
+      it("Should grow a revocation list with realloc", async () => {
+        const before = await provider.connection.getAccountInfo(revocationListPda);
+
+        await program.methods
+          .resizeRevocationList(50_000)
+          .accountsStrict({
+            revocationList: revocationListPda,
+            authority: issuerAuthority.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([issuerAuthority])
+          .rpc();
+
+        const after = await provider.connection.getAccountInfo(revocationListPda);
+        const revocationListAccount =
+          await program.account.revocationList.fetch(revocationListPda);
+        expect(revocationListAccount.capacity).to.equal(50_000);
+        expect(revocationListAccount.statusBits.length).to.equal(6_250);
+        expect(after.data.length).to.be.greaterThan(before.data.length);
+      });
+
       it("Should support batch revocation operations", async () => {
         try {
           const indicesToRevoke = [1, 3, 5, 7, 9];
@@ -542,6 +1356,7 @@ describe("Open Badges v3.0 - Unified Compliance Tests", () => {
               "Batch compliance test operation"
             )
             .accountsStrict({
+              programConfig: findProgramConfigPda(program.programId),
               revocationList: revocationListPda,
               authority: issuerAuthority.publicKey,
             })
@@ -561,6 +1376,40 @@ describe("Open Badges v3.0 - Unified Compliance Tests", () => {
           throw error;
         }
       });
+
+      it("Should publish a signed StatusList2021 credential", async () => {
+        const [statusListCredentialPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("status_list_credential"), revocationListPda.toBuffer()],
+          program.programId
+        );
+        const timestamp = new Date().toISOString().replace(/\.\d{3}Z$/, "Z");
+
+        const unsignedJson = await program.methods
+          .generateStatusListCredentialJson(timestamp)
+          .accountsStrict({ revocationList: revocationListPda })
+          .view();
+        const signature = signMessage(unsignedJson, issuerAuthority);
+
+        await program.methods
+          .publishStatusListCredential(timestamp, signature)
+          .accountsStrict({
+            revocationList: revocationListPda,
+            statusListCredential: statusListCredentialPda,
+            authority: issuerAuthority.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([issuerAuthority])
+          .rpc();
+
+        const published = await program.account.publishedStatusList.fetch(
+          statusListCredentialPda
+        );
+        const statusCredential = JSON.parse(published.credentialJson);
+        expect(statusCredential.type).to.include("StatusList2021Credential");
+        expect(statusCredential.credentialSubject.encodedList).to.be.a("string");
+        expect(statusCredential.proof.type).to.equal("DataIntegrityProof");
+        expect(statusCredential.proof.proofValue.startsWith("z")).to.be.true;
+      });
     });
 
     describe("6. Advanced Validation and Compliance", () => {
@@ -641,6 +1490,30 @@ describe("Open Badges v3.0 - Unified Compliance Tests", () => {
         console.log("✅ Credential structure validation completed");
       });
 
+      it("Should score a credential with the on-chain compliance validator", async () => {
+        const credentialJson = JSON.stringify({
+          "@context": [
+            "https://www.w3.org/ns/credentials/v2",
+            "https://purl.imsglobal.org/spec/ob/v3p0/context-3.0.3.json",
+          ],
+          id: "https://compliance-academy.com/credentials/full-validation",
+          type: ["VerifiableCredential", "OpenBadgeCredential"],
+          issuer: { id: "did:sol:" + issuerAuthority.publicKey.toString(), type: ["Profile"] },
+          validFrom: new Date().toISOString(),
+          credentialSubject: {
+            type: ["AchievementSubject"],
+            achievement: { id: "https://compliance-academy.com/achievements/full", type: ["Achievement"] },
+          },
+        });
+
+        const score = await program.methods
+          .validateCredentialFull(credentialJson, { development: {} })
+          .accountsStrict({ systemProgram: anchor.web3.SystemProgram.programId })
+          .view();
+        expect(score).to.be.within(1, 100);
+        console.log("✅ Compliance score:", score);
+      });
+
       it("Should demonstrate VC-JWT format support", async () => {
         const jwtCredential = {
           iss: "did:sol:" + issuerAuthority.publicKey.toString(),
@@ -683,6 +1556,65 @@ describe("Open Badges v3.0 - Unified Compliance Tests", () => {
 
         console.log("✅ VC-JWT format validation completed");
       });
+
+      it("Should return a JWT signed by the issuer authority", async () => {
+        const recipient = Keypair.generate().publicKey;
+        const issuedAt = new anchor.BN(Math.floor(Date.now() / 1000));
+        const generateAccounts = {
+          issuer: issuerPda,
+          achievement: achievementPda,
+          recipient,
+          rubric: null,
+        };
+
+        const signingInput = await program.methods
+          .generateJwtSigningInput("urn:example:achievement", "urn:uuid:jwt-1", issuedAt, false)
+          .accountsStrict(generateAccounts)
+          .view();
+        const message = Buffer.from(signingInput);
+        const signature = Buffer.from(nacl.sign.detached(message, issuerAuthority.secretKey));
+
+        const jwt = await program.methods
+          .generateJwtCredential("urn:example:achievement", "urn:uuid:jwt-1", issuedAt, false, [...signature])
+          .accountsStrict({
+            ...generateAccounts,
+            instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+          })
+          .preInstructions([
+            anchor.web3.Ed25519Program.createInstructionWithPublicKey({
+              publicKey: issuerAuthority.publicKey.toBytes(),
+              message,
+              signature,
+            }),
+          ])
+          .view();
+
+        const [header, payload, encodedSignature] = jwt.split(".");
+        expect(`${header}.${payload}`).to.equal(signingInput);
+        expect(
+          nacl.sign.detached.verify(
+            message,
+            Buffer.from(encodedSignature, "base64url"),
+            issuerAuthority.publicKey.toBytes()
+          )
+        ).to.be.true;
+
+        // The JWT verifies once its signature is checked by the Ed25519 program
+        const ed25519Instruction = anchor.web3.Ed25519Program.createInstructionWithPublicKey({
+          publicKey: issuerAuthority.publicKey.toBytes(),
+          message,
+          signature,
+        });
+        const verifyJwt = (issuer: PublicKey | null) =>
+          program.methods
+            .verifyCredentialFormat(jwt)
+            .accountsStrict({ issuer, instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY })
+            .preInstructions([ed25519Instruction])
+            .view();
+        expect(await verifyJwt(issuerPda)).to.be.true;
+        // Without the issuer profile the authority's key is not linked to `iss`
+        expect(await verifyJwt(null)).to.be.false;
+      });
     });
   });
 
@@ -712,7 +1644,8 @@ describe("Open Badges v3.0 - Unified Compliance Tests", () => {
             .initializeIssuer(
               "Secondary Compliance Issuer",
               "https://secondary-academy.com",
-              "secondary@academy.com"
+              "secondary@academy.com",
+              null
             )
             .accountsStrict({
               issuer: secondIssuerPda,
@@ -737,22 +1670,39 @@ describe("Open Badges v3.0 - Unified Compliance Tests", () => {
 
           await program.methods
             .createAchievement(
-              "https://secondary-academy.com/achievements/cross-issuer",
-              crossAchievementName,
-              "Demonstrates interoperability between multiple issuers",
-              "Cross-issuer validation and interoperability demonstration",
-              "https://secondary-academy.com/criteria/cross-issuer",
-              null
+              {
+                version: 4,
+                achievementId: "https://secondary-academy.com/achievements/cross-issuer",
+                name: crossAchievementName,
+                description: "Demonstrates interoperability between multiple issuers",
+                criteriaNarrative: "Cross-issuer validation and interoperability demonstration",
+                criteriaId: "https://secondary-academy.com/criteria/cross-issuer",
+                image: null,
+                resultDescriptions: [],
+                extensions: [],
+                achievementType: null,
+                requirements: { requiredCompetencies: [], minResults: [] },
+                nameI18n: [],
+                descriptionI18n: [],
+                requiresApprovedResult: false,
+              }
             )
             .accountsStrict({
               achievement: crossAchievementPda,
               issuer: secondIssuerPda,
+              creator: issuerPda, // Designed by the first academy, issued by the second
+              achievementDirectory: findAchievementDirectoryPda(program.programId, secondIssuerPda),
+              achievementDirectoryPage: findAchievementDirectoryPagePda(program.programId, secondIssuerPda, 0),
               authority: secondIssuer.publicKey,
               systemProgram: anchor.web3.SystemProgram.programId,
             })
             .signers([secondIssuer])
             .rpc();
 
+          const crossAchievement = await program.account.achievement.fetch(crossAchievementPda);
+          expect(crossAchievement.creator?.toString()).to.equal(issuerPda.toString());
+          expect(crossAchievement.issuer.toString()).to.equal(secondIssuerPda.toString());
+
           console.log("✅ Step 2: Cross-issuer achievement created");
 
           // Step 3: Multiple credential issuance
@@ -792,18 +1742,48 @@ describe("Open Badges v3.0 - Unified Compliance Tests", () => {
 
           await program.methods
             .issueAchievementCredential(
-              recipientKeypair.publicKey,
-              crossSignatureData,
-              crossMessageData,
-              crossTimestamp
+              {
+                version: 3,
+                recipientPubkey: recipientKeypair.publicKey,
+                signatureData: crossSignatureData,
+                messageData: crossMessageData,
+                timestamp: crossTimestamp,
+                results: [], // No graded results
+                narrative: null, // No narrative
+                notes: [], // No notes
+                credentialSchemas: [], // No credential schemas
+                bindHolder: false, // Not holder-bound
+                termsOfUse: null, // Issuer's default terms
+                pairwiseSubjectId: null, // Not pairwise-issued
+              }
             )
             .accountsStrict({
+              programConfig: findProgramConfigPda(program.programId),
+              treasury: findTreasuryPda(program.programId),
+              feeTokenAccount: null,
+              treasuryTokenAccount: null,
+              tokenProgram: null,
               credential: crossCredentialPda,
               achievement: crossAchievementPda,
               issuer: secondIssuerPda,
+              cpiPolicy: null,
+              instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+              issuerStats: findIssuerStatsPda(program.programId, secondIssuerPda),
+              consumedPayload: findConsumedPayloadPda(program.programId, secondIssuerPda, crossMessageData),
               authority: secondIssuer.publicKey,
               systemProgram: anchor.web3.SystemProgram.programId,
+              recipientOptOut: findOptOutPda(program.programId, recipientKeypair.publicKey),
+              achievementStats: findAchievementStatsPda(program.programId, crossAchievementPda),
+              credentialIndex: findCredentialIndexPda(program.programId, recipientKeypair.publicKey),
+              credentialIndexPage: findCredentialIndexPagePda(program.programId, recipientKeypair.publicKey, 0),
+              recipientRoster: findRecipientRosterPda(program.programId, crossAchievementPda),
+              recipientRosterPage: findRecipientRosterPagePda(program.programId, crossAchievementPda, 0),
+              resultEntry: null,
+              rubric: null,
+              schemaRegistry: null,
+              nomination: null,
             })
+            .preInstructions([ed25519Instruction(messageJson, secondIssuer)])
             .signers([secondIssuer])
             .rpc();
 
@@ -811,14 +1791,20 @@ describe("Open Badges v3.0 - Unified Compliance Tests", () => {
 
           // Step 4: Verification across issuers
           const crossResult = await program.methods
-            .verifyCredential()
+            .verifyCredential({ all: {} })
             .accountsStrict({
               credential: crossCredentialPda,
+              issuerKey: null,
+              issuer: null,
+              issuerDidAccount: null,
+              instructions: null,
+              staffKey: null,
+              staffDidData: null,
             })
             .view();
 
           // Since we're using real Ed25519 signatures, verification should pass
-          expect(crossResult).to.be.true;
+          expect(crossResult.errors).to.be.empty;
           console.log(
             "✅ Step 4: Cross-issuer verification successful with real signatures"
           );
@@ -862,17 +1848,46 @@ describe("Open Badges v3.0 - Unified Compliance Tests", () => {
         try {
           await program.methods
             .issueAchievementCredential(
-              recipientKeypair.publicKey,
-              unauthorizedSignatureData,
-              unauthorizedMessageData,
-              unauthorizedTimestamp
+              {
+                version: 3,
+                recipientPubkey: recipientKeypair.publicKey,
+                signatureData: unauthorizedSignatureData,
+                messageData: unauthorizedMessageData,
+                timestamp: unauthorizedTimestamp,
+                results: [], // No graded results
+                narrative: null, // No narrative
+                notes: [], // No notes
+                credentialSchemas: [], // No credential schemas
+                bindHolder: false, // Not holder-bound
+                termsOfUse: null, // Issuer's default terms
+                pairwiseSubjectId: null, // Not pairwise-issued
+              }
             )
             .accountsStrict({
+              programConfig: findProgramConfigPda(program.programId),
+              treasury: findTreasuryPda(program.programId),
+              feeTokenAccount: null,
+              treasuryTokenAccount: null,
+              tokenProgram: null,
               credential: unauthorizedCredentialPda,
               achievement: achievementPda,
               issuer: issuerPda,
+              cpiPolicy: null,
+              instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+              issuerStats: findIssuerStatsPda(program.programId, issuerPda),
+              consumedPayload: findConsumedPayloadPda(program.programId, issuerPda, unauthorizedMessageData),
               authority: unauthorizedUser.publicKey, // Wrong authority
               systemProgram: anchor.web3.SystemProgram.programId,
+              recipientOptOut: findOptOutPda(program.programId, recipientKeypair.publicKey),
+              achievementStats: findAchievementStatsPda(program.programId, achievementPda),
+              credentialIndex: findCredentialIndexPda(program.programId, recipientKeypair.publicKey),
+              credentialIndexPage: findCredentialIndexPagePda(program.programId, recipientKeypair.publicKey, 0),
+              recipientRoster: findRecipientRosterPda(program.programId, achievementPda),
+              recipientRosterPage: findRecipientRosterPagePda(program.programId, achievementPda, 0),
+              resultEntry: null,
+              rubric: null,
+              schemaRegistry: null,
+              nomination: null,
             })
             .signers([unauthorizedUser])
             .rpc();
@@ -897,11 +1912,17 @@ describe("Open Badges v3.0 - Unified Compliance Tests", () => {
         // Try to revoke with wrong authority
         try {
           await program.methods
-            .revokeCredentialDirect()
+            .revokeCredentialDirect(null)
             .accountsStrict({
+              programConfig: findProgramConfigPda(program.programId),
               credential: achievementPda, // Using wrong PDA
               issuer: issuerPda,
+              cpiPolicy: null,
+              instructions: null,
+              achievementStats: findAchievementStatsPda(program.programId, achievementPda),
+              issuerStats: findIssuerStatsPda(program.programId, issuerPda),
               authority: unauthorizedUser.publicKey,
+              systemProgram: anchor.web3.SystemProgram.programId,
             })
             .signers([unauthorizedUser])
             .rpc();
@@ -913,6 +1934,687 @@ describe("Open Badges v3.0 - Unified Compliance Tests", () => {
           console.log("   ✅ Credential integrity maintained");
         }
       });
+
+      it("Should refuse issuance to a recipient who opted out", async () => {
+        const optedOutRecipient = Keypair.generate();
+        await program.provider.connection.requestAirdrop(
+          optedOutRecipient.publicKey,
+          1e9
+        );
+        await new Promise((resolve) => setTimeout(resolve, 1000));
+
+        const optOutPda = findOptOutPda(
+          program.programId,
+          optedOutRecipient.publicKey
+        );
+
+        await program.methods
+          .registerOptOut()
+          .accountsStrict({
+            optOut: optOutPda,
+            recipient: optedOutRecipient.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([optedOutRecipient])
+          .rpc();
+
+        const optOutAccount = await program.account.optOut.fetch(optOutPda);
+        expect(optOutAccount.recipient.toString()).to.equal(
+          optedOutRecipient.publicKey.toString()
+        );
+
+        const [optedOutCredentialPda] = PublicKey.findProgramAddressSync(
+          [
+            Buffer.from("credential"),
+            achievementPda.toBuffer(),
+            issuerPda.toBuffer(),
+            optedOutRecipient.publicKey.toBuffer(),
+          ],
+          program.programId
+        );
+
+        try {
+          await program.methods
+            .issueAchievementCredential(
+              {
+                version: 3,
+                recipientPubkey: optedOutRecipient.publicKey,
+                signatureData: Buffer.alloc(64),
+                messageData: Buffer.from("opted-out-credential"),
+                timestamp: new Date().toISOString(),
+                results: [], // No graded results
+                narrative: null, // No narrative
+                notes: [], // No notes
+                credentialSchemas: [], // No credential schemas
+                bindHolder: false, // Not holder-bound
+                termsOfUse: null, // Issuer's default terms
+                pairwiseSubjectId: null, // Not pairwise-issued
+              }
+            )
+            .accountsStrict({
+              programConfig: findProgramConfigPda(program.programId),
+              treasury: findTreasuryPda(program.programId),
+              feeTokenAccount: null,
+              treasuryTokenAccount: null,
+              tokenProgram: null,
+              credential: optedOutCredentialPda,
+              achievement: achievementPda,
+              issuer: issuerPda,
+              cpiPolicy: null,
+              instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+              issuerStats: findIssuerStatsPda(program.programId, issuerPda),
+              consumedPayload: findConsumedPayloadPda(program.programId, issuerPda, Buffer.from("opted-out-credential")),
+              authority: issuerAuthority.publicKey,
+              systemProgram: anchor.web3.SystemProgram.programId,
+              recipientOptOut: optOutPda,
+              achievementStats: findAchievementStatsPda(program.programId, achievementPda),
+              credentialIndex: findCredentialIndexPda(program.programId, optedOutRecipient.publicKey),
+              credentialIndexPage: findCredentialIndexPagePda(program.programId, optedOutRecipient.publicKey, 0),
+              recipientRoster: findRecipientRosterPda(program.programId, achievementPda),
+              recipientRosterPage: findRecipientRosterPagePda(program.programId, achievementPda, 0),
+              resultEntry: null,
+              rubric: null,
+              schemaRegistry: null,
+              nomination: null,
+            })
+            .signers([issuerAuthority])
+            .rpc();
+
+          expect.fail("Should have failed with RecipientOptedOut");
+        } catch (error) {
+          expect(error.toString()).to.include("RecipientOptedOut");
+          console.log("✅ Opted-out recipient correctly refused");
+        }
+
+        await program.methods
+          .removeOptOut()
+          .accountsStrict({
+            optOut: optOutPda,
+            recipient: optedOutRecipient.publicKey,
+          })
+          .signers([optedOutRecipient])
+          .rpc();
+
+        const closed = await program.provider.connection.getAccountInfo(optOutPda);
+        expect(closed).to.be.null;
+        console.log("✅ Opt-out withdrawn by recipient");
+      });
+
+      it("Should issue a peer credential once the endorsement threshold is met", async () => {
+        const peerRecipient = Keypair.generate();
+        const endorsers = [Keypair.generate(), Keypair.generate()];
+        for (const endorser of endorsers) {
+          await program.provider.connection.requestAirdrop(endorser.publicKey, 1e9);
+        }
+        await new Promise((resolve) => setTimeout(resolve, 1000));
+
+        const [peerConfigPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("peer_config"), achievementPda.toBuffer()],
+          program.programId
+        );
+        const [tallyPda] = PublicKey.findProgramAddressSync(
+          [
+            Buffer.from("peer_tally"),
+            achievementPda.toBuffer(),
+            peerRecipient.publicKey.toBuffer(),
+          ],
+          program.programId
+        );
+        const [peerCredentialPda] = PublicKey.findProgramAddressSync(
+          [
+            Buffer.from("credential"),
+            achievementPda.toBuffer(),
+            issuerPda.toBuffer(),
+            peerRecipient.publicKey.toBuffer(),
+          ],
+          program.programId
+        );
+        const issueAccounts = {
+          credential: peerCredentialPda,
+          achievement: achievementPda,
+          issuer: issuerPda,
+          programConfig: findProgramConfigPda(program.programId),
+          treasury: findTreasuryPda(program.programId),
+          feeTokenAccount: null,
+          treasuryTokenAccount: null,
+          tokenProgram: null,
+          peerConfig: peerConfigPda,
+          tally: tallyPda,
+          recipientOptOut: findOptOutPda(program.programId, peerRecipient.publicKey),
+          achievementStats: findAchievementStatsPda(program.programId, achievementPda),
+          credentialIndex: findCredentialIndexPda(program.programId, peerRecipient.publicKey),
+          credentialIndexPage: findCredentialIndexPagePda(program.programId, peerRecipient.publicKey, 0),
+          recipientRoster: findRecipientRosterPda(program.programId, achievementPda),
+          recipientRosterPage: findRecipientRosterPagePda(program.programId, achievementPda, 0),
+          issuerStats: findIssuerStatsPda(program.programId, issuerPda),
+          payer: endorsers[0].publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        };
+
+        await program.methods
+          .configurePeerIssuance(2, false)
+          .accountsStrict({
+            peerConfig: peerConfigPda,
+            achievement: achievementPda,
+            issuer: issuerPda,
+            authority: issuerAuthority.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([issuerAuthority])
+          .rpc();
+
+        for (const [index, endorser] of endorsers.entries()) {
+          const [endorsementPda] = PublicKey.findProgramAddressSync(
+            [
+              Buffer.from("peer_endorsement"),
+              achievementPda.toBuffer(),
+              peerRecipient.publicKey.toBuffer(),
+              endorser.publicKey.toBuffer(),
+            ],
+            program.programId
+          );
+          await program.methods
+            .endorsePeer(peerRecipient.publicKey, "Great mentor")
+            .accountsStrict({
+              peerConfig: peerConfigPda,
+              achievement: achievementPda,
+              endorsement: endorsementPda,
+              tally: tallyPda,
+              endorserCredential: null,
+              endorser: endorser.publicKey,
+              systemProgram: anchor.web3.SystemProgram.programId,
+            })
+            .signers([endorser])
+            .rpc();
+
+          if (index === 0) {
+            try {
+              await program.methods
+                .issuePeerCredential(peerRecipient.publicKey)
+                .accountsStrict(issueAccounts)
+                .signers([endorsers[0]])
+                .rpc();
+              expect.fail("Should have failed with PeerThresholdNotMet");
+            } catch (error) {
+              expect(error.toString()).to.include("PeerThresholdNotMet");
+            }
+          }
+        }
+
+        await program.methods
+          .issuePeerCredential(peerRecipient.publicKey)
+          .accountsStrict(issueAccounts)
+          .signers([endorsers[0]])
+          .rpc();
+
+        const tally = await program.account.peerTally.fetch(tallyPda);
+        expect(tally.endorsementCount).to.equal(2);
+        expect(tally.issued).to.be.true;
+
+        const peerCredential = await program.account.achievementCredential.fetch(
+          peerCredentialPda
+        );
+        expect(peerCredential.issuer.toString()).to.equal(issuerPda.toString());
+        console.log("✅ Peer credential issued after 2 endorsements");
+      });
+
+      it("Should issue without an Ed25519 proof through the CPI entry point", async () => {
+        // A program would sign for a PDA authority; a keypair authority exercises the same path
+        const cpiRecipient = Keypair.generate();
+        const [cpiCredentialPda] = PublicKey.findProgramAddressSync(
+          [
+            Buffer.from("credential"),
+            achievementPda.toBuffer(),
+            issuerPda.toBuffer(),
+            cpiRecipient.publicKey.toBuffer(),
+          ],
+          program.programId
+        );
+
+        await program.methods
+          .issueAchievementCredentialCpi(cpiRecipient.publicKey, [])
+          .accountsStrict({
+            programConfig: findProgramConfigPda(program.programId),
+            treasury: findTreasuryPda(program.programId),
+            feeTokenAccount: null,
+            treasuryTokenAccount: null,
+            tokenProgram: null,
+            credential: cpiCredentialPda,
+            achievement: achievementPda,
+            issuer: issuerPda,
+            recipientOptOut: findOptOutPda(program.programId, cpiRecipient.publicKey),
+            achievementStats: findAchievementStatsPda(program.programId, achievementPda),
+            credentialIndex: findCredentialIndexPda(program.programId, cpiRecipient.publicKey),
+            credentialIndexPage: findCredentialIndexPagePda(program.programId, cpiRecipient.publicKey, 0),
+            recipientRoster: findRecipientRosterPda(program.programId, achievementPda),
+            recipientRosterPage: findRecipientRosterPagePda(program.programId, achievementPda, 0),
+            resultEntry: null,
+            rubric: null,
+            cpiPolicy: null,
+            instructions: null,
+            issuerStats: findIssuerStatsPda(program.programId, issuerPda),
+            authority: issuerAuthority.publicKey,
+            payer: provider.wallet.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([issuerAuthority])
+          .rpc();
+
+        const cpiCredential = await program.account.achievementCredential.fetch(
+          cpiCredentialPda
+        );
+        expect(cpiCredential.issuer.toString()).to.equal(issuerPda.toString());
+        expect(cpiCredential.proof).to.be.empty;
+        console.log("✅ Credential issued through the CPI entry point");
+      });
+
+      it("Should escrow a credential behind a claim code until the recipient has a wallet", async () => {
+        // The claim key's seed is the SHA-256 hash of the code handed to the learner
+        const secret = Buffer.from(nacl.randomBytes(32));
+        const claimKey = nacl.sign.keyPair.fromSeed(createHash("sha256").update(secret).digest());
+        const claimKeyPubkey = new PublicKey(claimKey.publicKey);
+        const [claimOfferPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("claim_offer"), issuerPda.toBuffer(), claimKeyPubkey.toBuffer()],
+          program.programId
+        );
+        const expiresAt = new anchor.BN(Math.floor(Date.now() / 1000) + 30 * 24 * 3600);
+
+        await program.methods
+          .issueWithClaimCode(claimKeyPubkey, expiresAt, [])
+          .accountsStrict({
+            claimOffer: claimOfferPda,
+            achievement: achievementPda,
+            issuer: issuerPda,
+            cpiPolicy: null,
+            instructions: null,
+            programConfig: findProgramConfigPda(program.programId),
+            treasury: findTreasuryPda(program.programId),
+            feeTokenAccount: null,
+            treasuryTokenAccount: null,
+            tokenProgram: null,
+            rubric: null,
+            authority: issuerAuthority.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([issuerAuthority])
+          .rpc();
+
+        // The learner creates a wallet later and claims with the code
+        const learner = Keypair.generate();
+        await provider.connection.confirmTransaction(
+          await provider.connection.requestAirdrop(learner.publicKey, anchor.web3.LAMPORTS_PER_SOL)
+        );
+        const [claimedCredentialPda] = PublicKey.findProgramAddressSync(
+          [
+            Buffer.from("credential"),
+            achievementPda.toBuffer(),
+            issuerPda.toBuffer(),
+            learner.publicKey.toBuffer(),
+          ],
+          program.programId
+        );
+        const claimAccounts = {
+          claimOffer: claimOfferPda,
+          credential: claimedCredentialPda,
+          achievement: achievementPda,
+          issuer: issuerPda,
+          issuerAuthority: issuerAuthority.publicKey,
+          programConfig: findProgramConfigPda(program.programId),
+          recipientOptOut: findOptOutPda(program.programId, learner.publicKey),
+          achievementStats: findAchievementStatsPda(program.programId, achievementPda),
+          credentialIndex: findCredentialIndexPda(program.programId, learner.publicKey),
+          credentialIndexPage: findCredentialIndexPagePda(program.programId, learner.publicKey, 0),
+          recipientRoster: findRecipientRosterPda(program.programId, achievementPda),
+          recipientRosterPage: findRecipientRosterPagePda(program.programId, achievementPda, 0),
+          issuerStats: findIssuerStatsPda(program.programId, issuerPda),
+          recipient: learner.publicKey,
+          instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        };
+        const claimMessage = Buffer.concat([
+          Buffer.from("open-badges-claim:"),
+          claimOfferPda.toBuffer(),
+          learner.publicKey.toBuffer(),
+        ]);
+        const claimInstruction = (secretKey: Uint8Array) => {
+          const signature = Buffer.from(nacl.sign.detached(claimMessage, secretKey));
+          const publicKey = nacl.sign.keyPair.fromSecretKey(secretKey).publicKey;
+          return {
+            signature,
+            instruction: anchor.web3.Ed25519Program.createInstructionWithPublicKey({
+              publicKey,
+              message: claimMessage,
+              signature,
+            }),
+          };
+        };
+
+        const wrongCode = claimInstruction(nacl.sign.keyPair().secretKey);
+        try {
+          await program.methods
+            .claimWithCode(Array.from(wrongCode.signature), learner.publicKey)
+            .accountsStrict(claimAccounts)
+            .preInstructions([wrongCode.instruction])
+            .signers([learner])
+            .rpc();
+          expect.fail("A wrong code should not claim the offer");
+        } catch (error) {
+          expect(error.toString()).to.include("InvalidClaimCode");
+        }
+
+        const claim = claimInstruction(claimKey.secretKey);
+        await program.methods
+          .claimWithCode(Array.from(claim.signature), learner.publicKey)
+          .accountsStrict(claimAccounts)
+          .preInstructions([claim.instruction])
+          .signers([learner])
+          .rpc();
+
+        const claimed = await program.account.achievementCredential.fetch(claimedCredentialPda);
+        expect(claimed.credentialSubject.id).to.equal("did:sol:" + learner.publicKey.toString());
+        expect(await provider.connection.getAccountInfo(claimOfferPda)).to.be.null;
+        console.log("✅ Claim code bound the escrowed credential to the new wallet");
+      });
+
+      it("Should issue one team credential naming every member", async () => {
+        const members = [Keypair.generate().publicKey, Keypair.generate().publicKey];
+        const teamId = "hackathon-team-7";
+        const [teamCredentialPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("team_credential"), achievementPda.toBuffer(), issuerPda.toBuffer(), Buffer.from(teamId)],
+          program.programId
+        );
+
+        await program.methods
+          .issueTeamCredential(teamId, members)
+          .accountsStrict({
+            teamCredential: teamCredentialPda,
+            achievement: achievementPda,
+            issuer: issuerPda,
+            cpiPolicy: null,
+            instructions: null,
+            programConfig: findProgramConfigPda(program.programId),
+            treasury: findTreasuryPda(program.programId),
+            feeTokenAccount: null,
+            treasuryTokenAccount: null,
+            tokenProgram: null,
+            authority: issuerAuthority.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .remainingAccounts(
+            members.map((member) => ({
+              pubkey: findOptOutPda(program.programId, member),
+              isSigner: false,
+              isWritable: false,
+            }))
+          )
+          .signers([issuerAuthority])
+          .rpc();
+
+        const json = await program.methods
+          .generateTeamCredentialJson()
+          .accountsStrict({ teamCredential: teamCredentialPda, issuer: issuerPda })
+          .view();
+        const document = JSON.parse(json);
+        expect(document.credentialSubject).to.have.length(2);
+        expect(document.credentialSubject[1].id).to.equal("did:sol:" + members[1].toString());
+        console.log("✅ Team credential lists one subject per member");
+      });
+
+      it("Should let a service issue only within its scoped authorization", async () => {
+        const service = Keypair.generate();
+        await provider.connection.confirmTransaction(
+          await provider.connection.requestAirdrop(service.publicKey, anchor.web3.LAMPORTS_PER_SOL)
+        );
+        const [authorizationPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("scoped_authorization"), issuerPda.toBuffer(), service.publicKey.toBuffer()],
+          program.programId
+        );
+        await program.methods
+          .grantScopedAuthorization(service.publicKey, [{ issue: {} }], [achievementPda], null)
+          .accountsStrict({
+            scopedAuthorization: authorizationPda,
+            issuer: issuerPda,
+            authority: issuerAuthority.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([issuerAuthority])
+          .rpc();
+
+        const learner = Keypair.generate().publicKey;
+        const [credential] = PublicKey.findProgramAddressSync(
+          [Buffer.from("credential"), achievementPda.toBuffer(), issuerPda.toBuffer(), learner.toBuffer()],
+          program.programId
+        );
+        await program.methods
+          .issueCredentialAsDelegate(learner, [])
+          .accountsStrict({
+            credential,
+            achievement: achievementPda,
+            issuer: issuerPda,
+            scopedAuthorization: authorizationPda,
+            programConfig: findProgramConfigPda(program.programId),
+            treasury: findTreasuryPda(program.programId),
+            feeTokenAccount: null,
+            treasuryTokenAccount: null,
+            tokenProgram: null,
+            recipientOptOut: findOptOutPda(program.programId, learner),
+            resultEntry: null,
+            rubric: null,
+            achievementStats: findAchievementStatsPda(program.programId, achievementPda),
+            credentialIndex: findCredentialIndexPda(program.programId, learner),
+            credentialIndexPage: findCredentialIndexPagePda(program.programId, learner, 0),
+            recipientRoster: findRecipientRosterPda(program.programId, achievementPda),
+            recipientRosterPage: findRecipientRosterPagePda(program.programId, achievementPda, 0),
+            issuerStats: findIssuerStatsPda(program.programId, issuerPda),
+            service: service.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([service])
+          .rpc();
+        const issued = await program.account.achievementCredential.fetch(credential);
+        expect(issued.issuer.toString()).to.equal(issuerPda.toString());
+
+        try {
+          await program.methods
+            .revokeCredentialAsDelegate(null)
+            .accountsStrict({
+              credential,
+              issuer: issuerPda,
+              scopedAuthorization: authorizationPda,
+              programConfig: findProgramConfigPda(program.programId),
+              achievementStats: findAchievementStatsPda(program.programId, achievementPda),
+              issuerStats: findIssuerStatsPda(program.programId, issuerPda),
+              service: service.publicKey,
+              systemProgram: anchor.web3.SystemProgram.programId,
+            })
+            .signers([service])
+            .rpc();
+          expect.fail("An issue-only grant should not allow revocation");
+        } catch (error) {
+          expect(error.toString()).to.include("ScopeNotGranted");
+        }
+        console.log("✅ Delegated issuance stayed within its scope");
+      });
+
+      it("Should record which programs may invoke issuance through CPI", async () => {
+        const gradingProgram = Keypair.generate().publicKey;
+        const [cpiPolicyPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("cpi_policy"), issuerPda.toBuffer()],
+          program.programId
+        );
+
+        await program.methods
+          .setCpiPolicy(true, false, [gradingProgram])
+          .accountsStrict({
+            cpiPolicy: cpiPolicyPda,
+            issuer: issuerPda,
+            authority: issuerAuthority.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([issuerAuthority])
+          .rpc();
+
+        const policy = await program.account.cpiPolicy.fetch(cpiPolicyPda);
+        expect(policy.allowIssuance).to.be.true;
+        expect(policy.allowRevocation).to.be.false;
+        expect(policy.allowedPrograms[0].toString()).to.equal(gradingProgram.toString());
+
+        try {
+          await program.methods
+            .setCpiPolicy(true, true, [program.programId])
+            .accountsStrict({
+              cpiPolicy: cpiPolicyPda,
+              issuer: issuerPda,
+              authority: issuerAuthority.publicKey,
+              systemProgram: anchor.web3.SystemProgram.programId,
+            })
+            .signers([issuerAuthority])
+            .rpc();
+          expect.fail("Allowing the program itself should be rejected");
+        } catch (error) {
+          expect(error.toString()).to.include("CpiNotAllowed");
+        }
+      });
+
+      it("Should anchor and verify an externally issued credential", async () => {
+        const externalJson = JSON.stringify({
+          "@context": ["https://www.w3.org/ns/credentials/v2"],
+          id: "urn:uuid:6b1f0a48-2b7c-4c55-9a0e-5d3c7e0b9f11",
+          type: ["VerifiableCredential", "OpenBadgeCredential"],
+          issuer: { id: "did:web:registrar.example.edu", type: ["Profile"] },
+          validFrom: "2025-01-01T00:00:00Z",
+        });
+        const credentialHash = createHash("sha256").update(externalJson).digest();
+        const [anchorRecordPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("anchor_record"), issuerPda.toBuffer(), credentialHash],
+          program.programId
+        );
+
+        await program.methods
+          .anchorExternalCredential([...credentialHash], "did:web:registrar.example.edu", null)
+          .accountsStrict({
+            anchorRecord: anchorRecordPda,
+            issuer: issuerPda,
+            revocationList: null,
+            issuerStats: findIssuerStatsPda(program.programId, issuerPda),
+            authority: issuerAuthority.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([issuerAuthority])
+          .rpc();
+
+        const verify = (json: string) =>
+          program.methods
+            .verifyAnchoredCredential(json)
+            .accountsStrict({ anchorRecord: anchorRecordPda, revocationList: null })
+            .view();
+        expect(await verify(externalJson)).to.be.true;
+        expect(await verify(externalJson.replace("2025", "2026"))).to.be.false;
+      });
+
+      it("Should verify a credential against a merkle batch anchor", async () => {
+        const sha256 = (...parts: Buffer[]) =>
+          createHash("sha256").update(Buffer.concat(parts)).digest();
+        const batchJson = [1, 2].map((n) =>
+          JSON.stringify({
+            id: `urn:uuid:batch-${n}`,
+            issuer: "did:web:registrar.example.edu",
+          })
+        );
+        const leaves = batchJson.map((json) => sha256(Buffer.from([0]), sha256(Buffer.from(json))));
+        const [low, high] = [...leaves].sort(Buffer.compare);
+        const merkleRoot = sha256(Buffer.from([1]), low, high);
+        const [batchAnchorPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("batch_anchor"), issuerPda.toBuffer(), merkleRoot],
+          program.programId
+        );
+
+        await program.methods
+          .anchorCredentialBatch([...merkleRoot], 2, "did:web:registrar.example.edu")
+          .accountsStrict({
+            batchAnchor: batchAnchorPda,
+            issuer: issuerPda,
+            issuerStats: findIssuerStatsPda(program.programId, issuerPda),
+            authority: issuerAuthority.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([issuerAuthority])
+          .rpc();
+
+        const contains = await program.methods
+          .verifyBatchAnchoredCredential(batchJson[0], [[...leaves[1]]])
+          .accountsStrict({ batchAnchor: batchAnchorPda })
+          .view();
+        expect(contains).to.be.true;
+
+        const forged = await program.methods
+          .verifyBatchAnchoredCredential(batchJson[0], [[...leaves[0]]])
+          .accountsStrict({ batchAnchor: batchAnchorPda })
+          .view();
+        expect(forged).to.be.false;
+      });
+
+      it("Should track, decline and close a nomination", async () => {
+        const nominator = Keypair.generate();
+        const nominee = Keypair.generate();
+        await program.provider.connection.requestAirdrop(nominator.publicKey, 1e9);
+        await new Promise((resolve) => setTimeout(resolve, 1000));
+
+        const [nominationPda] = PublicKey.findProgramAddressSync(
+          [
+            Buffer.from("nomination"),
+            achievementPda.toBuffer(),
+            nominee.publicKey.toBuffer(),
+            nominator.publicKey.toBuffer(),
+          ],
+          program.programId
+        );
+
+        await program.methods
+          .nominateRecipient(
+            nominee.publicKey,
+            "https://compliance-academy.com/evidence/portfolio",
+            "Led the community workshop series"
+          )
+          .accountsStrict({
+            nomination: nominationPda,
+            achievement: achievementPda,
+            nominator: nominator.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([nominator])
+          .rpc();
+
+        let nomination = await program.account.nomination.fetch(nominationPda);
+        expect(nomination.status).to.have.property("pending");
+        expect(nomination.issuer.toString()).to.equal(issuerPda.toString());
+
+        await program.methods
+          .declineNomination()
+          .accountsStrict({
+            nomination: nominationPda,
+            issuer: issuerPda,
+            authority: issuerAuthority.publicKey,
+          })
+          .signers([issuerAuthority])
+          .rpc();
+
+        nomination = await program.account.nomination.fetch(nominationPda);
+        expect(nomination.status).to.have.property("declined");
+
+        await program.methods
+          .closeNomination()
+          .accountsStrict({
+            nomination: nominationPda,
+            nominator: nominator.publicKey,
+          })
+          .signers([nominator])
+          .rpc();
+
+        const closed = await program.provider.connection.getAccountInfo(nominationPda);
+        expect(closed).to.be.null;
+        console.log("✅ Nomination declined and closed by nominator");
+      });
     });
   });
 
@@ -1008,11 +2710,11 @@ describe("Open Badges v3.0 - Unified Compliance Tests", () => {
         // Wait for airdrops
         await new Promise((resolve) => setTimeout(resolve, 2000));
 
-        // Prepare batch requests with minimal notes to reduce buffer size
+        // Prepare batch requests; batch issuance takes no notes
         batchRequests = batchRecipients.map((recipient, index) => ({
           recipientPubkey: recipient.publicKey,
           achievementId: achievementPda.toString(),
-          notes: [`Batch ${index + 1}`], // Simplified notes
+          notes: null,
         }));
 
         timestamp = "2025-01-01T00:00:00.000Z";
@@ -1039,10 +2741,14 @@ describe("Open Badges v3.0 - Unified Compliance Tests", () => {
               timestamp
             )
             .accountsStrict({
+              programConfig: findProgramConfigPda(program.programId),
+              instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
               issuer: issuerPda,
+              batchConfig: null,
               authority: issuerAuthority.publicKey,
               systemProgram: anchor.web3.SystemProgram.programId,
             })
+            .preInstructions([ed25519Instruction(emptyMessage, issuerAuthority)])
             .signers([issuerAuthority])
             .rpc();
 
@@ -1063,7 +2769,7 @@ describe("Open Badges v3.0 - Unified Compliance Tests", () => {
             .map((_, index) => ({
               recipientPubkey: batchRecipients[0].publicKey,
               achievementId: achievementPda.toString(),
-              notes: [`Oversized ${index + 1}`], // Simplified notes
+              notes: null,
             }));
 
           const oversizedMessage = `batch_issue_6_${timestamp}`; // Updated count
@@ -1080,10 +2786,14 @@ describe("Open Badges v3.0 - Unified Compliance Tests", () => {
               timestamp
             )
             .accountsStrict({
+              programConfig: findProgramConfigPda(program.programId),
+              instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
               issuer: issuerPda,
+              batchConfig: null,
               authority: issuerAuthority.publicKey,
               systemProgram: anchor.web3.SystemProgram.programId,
             })
+            .remainingAccounts(optOutAccounts(program.programId, oversizedRequests))
             .signers([issuerAuthority])
             .rpc();
 
@@ -1112,10 +2822,14 @@ describe("Open Badges v3.0 - Unified Compliance Tests", () => {
               timestamp
             )
             .accountsStrict({
+              programConfig: findProgramConfigPda(program.programId),
+              instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
               issuer: issuerPda,
+              batchConfig: null,
               authority: issuerAuthority.publicKey,
               systemProgram: anchor.web3.SystemProgram.programId,
             })
+            .remainingAccounts(optOutAccounts(program.programId, batchRequests))
             .signers([issuerAuthority])
             .rpc();
 
@@ -1141,10 +2855,14 @@ describe("Open Badges v3.0 - Unified Compliance Tests", () => {
               timestamp
             )
             .accountsStrict({
+              programConfig: findProgramConfigPda(program.programId),
+              instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
               issuer: issuerPda,
+              batchConfig: null,
               authority: issuerAuthority.publicKey,
               systemProgram: anchor.web3.SystemProgram.programId,
             })
+            .remainingAccounts(optOutAccounts(program.programId, batchRequests))
             .signers([issuerAuthority])
             .rpc();
 
@@ -1155,6 +2873,37 @@ describe("Open Badges v3.0 - Unified Compliance Tests", () => {
         }
       });
 
+      it("Should reject notes on batch requests", async () => {
+        // Batch issuance creates no credential accounts, so notes would be dropped
+        const annotated = batchRequests.map((request) => ({ ...request, notes: ["Reviewed"] }));
+        const message = `batch_issue_${batchSize}_${timestamp}`;
+        try {
+          await program.methods
+            .batchIssueAchievementCredentialsWithDid(
+              annotated,
+              signMessage(message, issuerAuthority),
+              Buffer.from(message),
+              timestamp
+            )
+            .accountsStrict({
+              programConfig: findProgramConfigPda(program.programId),
+              instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+              issuer: issuerPda,
+              batchConfig: null,
+              authority: issuerAuthority.publicKey,
+              systemProgram: anchor.web3.SystemProgram.programId,
+            })
+            .remainingAccounts(optOutAccounts(program.programId, annotated))
+            .preInstructions([ed25519Instruction(message, issuerAuthority)])
+            .signers([issuerAuthority])
+            .rpc();
+
+          expect.fail("Batch notes should be rejected");
+        } catch (error) {
+          expect(error.toString()).to.include("NotImplemented");
+        }
+      });
+
       it("Should validate cryptographic signatures", async () => {
         // Test invalid signature (correct length but wrong signature)
         try {
@@ -1170,10 +2919,15 @@ describe("Open Badges v3.0 - Unified Compliance Tests", () => {
               timestamp
             )
             .accountsStrict({
+              programConfig: findProgramConfigPda(program.programId),
+              instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
               issuer: issuerPda,
+              batchConfig: null,
               authority: issuerAuthority.publicKey,
               systemProgram: anchor.web3.SystemProgram.programId,
             })
+            .remainingAccounts(optOutAccounts(program.programId, batchRequests))
+            .preInstructions([ed25519Instruction(message, wrongKeypair)])
             .signers([issuerAuthority])
             .rpc();
 
@@ -1194,7 +2948,7 @@ describe("Open Badges v3.0 - Unified Compliance Tests", () => {
             {
               recipientPubkey: batchRecipients[0].publicKey,
               achievementId: "invalid-achievement-id-format",
-              notes: ["Invalid ID test"],
+              notes: null,
             },
           ];
 
@@ -1209,10 +2963,15 @@ describe("Open Badges v3.0 - Unified Compliance Tests", () => {
               timestamp
             )
             .accountsStrict({
+              programConfig: findProgramConfigPda(program.programId),
+              instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
               issuer: issuerPda,
+              batchConfig: null,
               authority: issuerAuthority.publicKey,
               systemProgram: anchor.web3.SystemProgram.programId,
             })
+            .remainingAccounts(optOutAccounts(program.programId, invalidRequests))
+            .preInstructions([ed25519Instruction(message, issuerAuthority)])
             .signers([issuerAuthority])
             .rpc();
 
@@ -1236,10 +2995,15 @@ describe("Open Badges v3.0 - Unified Compliance Tests", () => {
               timestamp
             )
             .accountsStrict({
+              programConfig: findProgramConfigPda(program.programId),
+              instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
               issuer: issuerPda,
+              batchConfig: null,
               authority: issuerAuthority.publicKey,
               systemProgram: anchor.web3.SystemProgram.programId,
             })
+            .remainingAccounts(optOutAccounts(program.programId, batchRequests))
+            .preInstructions([ed25519Instruction(message, issuerAuthority)])
             .signers([issuerAuthority])
             .rpc();
 
@@ -1343,7 +3107,7 @@ describe("Open Badges v3.0 - Unified Compliance Tests", () => {
         simpleBatchRequests = simpleBatchRecipients.map((recipient, index) => ({
           recipientPubkey: recipient.publicKey,
           achievementId: achievementPda.toString(),
-          notes: [`Simple ${index + 1}`], // Simplified notes
+          notes: null,
         }));
 
         simpleTimestamp = "2025-01-01T12:00:00.000Z";
@@ -1368,10 +3132,14 @@ describe("Open Badges v3.0 - Unified Compliance Tests", () => {
               simpleTimestamp
             )
             .accountsStrict({
+              programConfig: findProgramConfigPda(program.programId),
+              instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
               issuer: issuerPda,
+              batchConfig: null,
               authority: issuerAuthority.publicKey,
               systemProgram: anchor.web3.SystemProgram.programId,
             })
+            .remainingAccounts(optOutAccounts(program.programId, simpleBatchRequests))
             .signers([issuerAuthority])
             .rpc();
 
@@ -1395,10 +3163,15 @@ describe("Open Badges v3.0 - Unified Compliance Tests", () => {
               simpleTimestamp
             )
             .accountsStrict({
+              programConfig: findProgramConfigPda(program.programId),
+              instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
               issuer: issuerPda,
+              batchConfig: null,
               authority: issuerAuthority.publicKey,
               systemProgram: anchor.web3.SystemProgram.programId,
             })
+            .remainingAccounts(optOutAccounts(program.programId, simpleBatchRequests))
+            .preInstructions([ed25519Instruction(message, issuerAuthority)])
             .signers([issuerAuthority])
             .rpc();
 
@@ -1450,6 +3223,135 @@ describe("Open Badges v3.0 - Unified Compliance Tests", () => {
     });
 
     describe("11. Batch Processing Performance and Limits", () => {
+      it("Should let the upgrade authority configure batch limits", async () => {
+        const [batchConfigPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("batch_config")],
+          program.programId
+        );
+        const [programDataPda] = PublicKey.findProgramAddressSync(
+          [program.programId.toBuffer()],
+          new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+        );
+        const deployer = program.provider.publicKey!;
+
+        await program.methods
+          .initializeBatchConfig(deployer, 10, 1_000_000)
+          .accountsStrict({
+            batchConfig: batchConfigPda,
+            program: program.programId,
+            programData: programDataPda,
+            authority: deployer,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .rpc();
+        await program.methods
+          .updateBatchConfig(8, 1_200_000)
+          .accountsStrict({ batchConfig: batchConfigPda, admin: deployer })
+          .rpc();
+
+        const config = await program.account.batchConfig.fetch(batchConfigPda);
+        expect(config.maxBatchSize).to.equal(8);
+        expect(config.computeBudget).to.equal(1_200_000);
+        console.log("✅ Batch limits configured by the program admin");
+      });
+
+      it("Should refuse issuance while the program is paused", async () => {
+        const programConfigPda = findProgramConfigPda(program.programId);
+        const [programDataPda] = PublicKey.findProgramAddressSync(
+          [program.programId.toBuffer()],
+          new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+        );
+        const deployer = program.provider.publicKey!;
+
+        await program.methods
+          .initializeProgramConfig(deployer, new anchor.BN(0), null)
+          .accountsStrict({
+            programConfig: programConfigPda,
+            treasury: findTreasuryPda(program.programId),
+            program: program.programId,
+            programData: programDataPda,
+            authority: deployer,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .rpc();
+        await program.methods
+          .setConfig(true, new anchor.BN(0), null)
+          .accountsStrict({ programConfig: programConfigPda, admin: deployer })
+          .rpc();
+
+        const pausedRequests = [
+          { recipientPubkey: Keypair.generate().publicKey, achievementId: achievementPda.toString(), notes: null },
+        ];
+        const pausedTimestamp = "2025-01-01T17:00:00.000Z";
+        const pausedMessage = `batch_issue_1_${pausedTimestamp}`;
+        try {
+          await program.methods
+            .batchIssueAchievementCredentialsWithDid(
+              pausedRequests,
+              signMessage(pausedMessage, issuerAuthority),
+              Buffer.from(pausedMessage),
+              pausedTimestamp
+            )
+            .accountsStrict({
+              programConfig: programConfigPda,
+              instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+              issuer: issuerPda,
+              batchConfig: null,
+              authority: issuerAuthority.publicKey,
+              systemProgram: anchor.web3.SystemProgram.programId,
+            })
+            .remainingAccounts(optOutAccounts(program.programId, pausedRequests))
+            .signers([issuerAuthority])
+            .rpc();
+          expect.fail("Issuance should be refused while paused");
+        } catch (error) {
+          expect(error.toString()).to.include("ProgramPaused");
+        } finally {
+          await program.methods
+            .setConfig(false, new anchor.BN(0), null)
+            .accountsStrict({ programConfig: programConfigPda, admin: deployer })
+            .rpc();
+        }
+
+        const config = await program.account.programConfig.fetch(programConfigPda);
+        expect(config.paused).to.be.false;
+        console.log("✅ Pause switch halts issuance until the admin resumes it");
+      });
+
+      it("Should only let the admin withdraw collected fees", async () => {
+        const deployer = program.provider.publicKey!;
+        const withdrawAccounts = {
+          programConfig: findProgramConfigPda(program.programId),
+          treasury: findTreasuryPda(program.programId),
+          destination: deployer,
+          treasuryTokenAccount: null,
+          tokenProgram: null,
+        };
+
+        try {
+          await program.methods
+            .withdrawTreasury(new anchor.BN(1))
+            .accountsStrict({ ...withdrawAccounts, admin: issuerAuthority.publicKey })
+            .signers([issuerAuthority])
+            .rpc();
+          expect.fail("Only the admin may withdraw");
+        } catch (error) {
+          expect(error.toString()).to.include("UnauthorizedAccess");
+        }
+
+        // No fees have been charged, so only the rent reserve is held
+        try {
+          await program.methods
+            .withdrawTreasury(new anchor.BN(1))
+            .accountsStrict({ ...withdrawAccounts, admin: deployer })
+            .rpc();
+          expect.fail("The rent reserve cannot be withdrawn");
+        } catch (error) {
+          expect(error.toString()).to.include("InsufficientTreasuryBalance");
+        }
+        console.log("✅ Treasury withdrawals are restricted to collected fees");
+      });
+
       it("Should handle maximum batch size efficiently", async () => {
         const maxBatchSize = 3; // Reduced from 10 to avoid buffer and compute issues
         const maxBatchRecipients: Keypair[] = [];
@@ -1469,7 +3371,7 @@ describe("Open Badges v3.0 - Unified Compliance Tests", () => {
         const maxBatchRequests = maxBatchRecipients.map((recipient, index) => ({
           recipientPubkey: recipient.publicKey,
           achievementId: achievementPda.toString(),
-          notes: [`Max ${index + 1}`], // Simplified notes
+          notes: null,
         }));
 
         const maxTimestamp = "2025-01-01T18:00:00.000Z";
@@ -1487,10 +3389,15 @@ describe("Open Badges v3.0 - Unified Compliance Tests", () => {
               maxTimestamp
             )
             .accountsStrict({
+              programConfig: findProgramConfigPda(program.programId),
+              instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
               issuer: issuerPda,
+              batchConfig: null,
               authority: issuerAuthority.publicKey,
               systemProgram: anchor.web3.SystemProgram.programId,
             })
+            .remainingAccounts(optOutAccounts(program.programId, maxBatchRequests))
+            .preInstructions([ed25519Instruction(message, issuerAuthority)])
             .signers([issuerAuthority])
             .rpc();
 
@@ -1556,7 +3463,7 @@ describe("Open Badges v3.0 - Unified Compliance Tests", () => {
           {
             recipientPubkey: consistencyRecipient.publicKey,
             achievementId: achievementPda.toString(),
-            notes: ["Consistency test credential"],
+            notes: null,
           },
         ];
 
@@ -1573,10 +3480,15 @@ describe("Open Badges v3.0 - Unified Compliance Tests", () => {
               batchTimestamp
             )
             .accountsStrict({
+              programConfig: findProgramConfigPda(program.programId),
+              instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
               issuer: issuerPda,
+              batchConfig: null,
               authority: issuerAuthority.publicKey,
               systemProgram: anchor.web3.SystemProgram.programId,
             })
+            .remainingAccounts(optOutAccounts(program.programId, singleBatchRequest))
+            .preInstructions([ed25519Instruction(batchMessage, issuerAuthority)])
             .signers([issuerAuthority])
             .rpc();
 
@@ -1608,12 +3520,12 @@ describe("Open Badges v3.0 - Unified Compliance Tests", () => {
           {
             recipientPubkey: mixedRecipient.publicKey,
             achievementId: achievementPda.toString(), // Valid
-            notes: ["Valid achievement ID"],
+            notes: null,
           },
           {
             recipientPubkey: mixedRecipient.publicKey,
             achievementId: "invalid-achievement-id", // Invalid
-            notes: ["Invalid achievement ID"],
+            notes: null,
           },
         ];
 
@@ -1630,10 +3542,15 @@ describe("Open Badges v3.0 - Unified Compliance Tests", () => {
               mixedTimestamp
             )
             .accountsStrict({
+              programConfig: findProgramConfigPda(program.programId),
+              instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
               issuer: issuerPda,
+              batchConfig: null,
               authority: issuerAuthority.publicKey,
               systemProgram: anchor.web3.SystemProgram.programId,
             })
+            .remainingAccounts(optOutAccounts(program.programId, mixedRequests))
+            .preInstructions([ed25519Instruction(mixedMessage, issuerAuthority)])
             .signers([issuerAuthority])
             .rpc();
 
@@ -1660,7 +3577,7 @@ describe("Open Badges v3.0 - Unified Compliance Tests", () => {
           {
             recipientPubkey: unauthorizedRecipient.publicKey,
             achievementId: achievementPda.toString(),
-            notes: ["Unauthorized batch request"],
+            notes: null,
           },
         ];
 
@@ -1680,10 +3597,14 @@ describe("Open Badges v3.0 - Unified Compliance Tests", () => {
               unauthorizedTimestamp
             )
             .accountsStrict({
+              programConfig: findProgramConfigPda(program.programId),
+              instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
               issuer: issuerPda,
+              batchConfig: null,
               authority: unauthorizedUser.publicKey, // Wrong authority
               systemProgram: anchor.web3.SystemProgram.programId,
             })
+            .remainingAccounts(optOutAccounts(program.programId, unauthorizedRequests))
             .signers([unauthorizedUser])
             .rpc();
 
@@ -1701,7 +3622,7 @@ describe("Open Badges v3.0 - Unified Compliance Tests", () => {
           {
             recipientPubkey: edgeCaseRecipient.publicKey,
             achievementId: achievementPda.toString(),
-            notes: ["Edge case test"],
+            notes: null,
           },
         ];
 
@@ -1721,10 +3642,14 @@ describe("Open Badges v3.0 - Unified Compliance Tests", () => {
               edgeTimestamp
             )
             .accountsStrict({
+              programConfig: findProgramConfigPda(program.programId),
+              instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
               issuer: issuerPda,
+              batchConfig: null,
               authority: issuerAuthority.publicKey,
               systemProgram: anchor.web3.SystemProgram.programId,
             })
+            .remainingAccounts(optOutAccounts(program.programId, edgeCaseRequests))
             .signers([issuerAuthority])
             .rpc();
 
@@ -1752,7 +3677,7 @@ describe("Open Badges v3.0 - Unified Compliance Tests", () => {
             {
               recipientPubkey: statusRecipient.publicKey,
               achievementId: achievementPda.toString(),
-              notes: ["Implementation status validation"],
+              notes: null,
             },
           ];
 
@@ -1769,10 +3694,15 @@ describe("Open Badges v3.0 - Unified Compliance Tests", () => {
                 statusTimestamp
               )
               .accountsStrict({
+                programConfig: findProgramConfigPda(program.programId),
+                instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
                 issuer: issuerPda,
+                batchConfig: null,
                 authority: issuerAuthority.publicKey,
                 systemProgram: anchor.web3.SystemProgram.programId,
               })
+              .remainingAccounts(optOutAccounts(program.programId, statusRequests))
+              .preInstructions([ed25519Instruction(statusMessage, issuerAuthority)])
               .signers([issuerAuthority])
               .rpc();
 
@@ -1868,4 +3798,86 @@ describe("Open Badges v3.0 - Unified Compliance Tests", () => {
       });
     });
   }); // End of Batch Error Handling and Edge Cases
+
+  describe("14. Issuance Compute Units", () => {
+    // Build the program with `--features profiling` to see per-stage checkpoints in the logs
+    const issueWithNarrative = async (narrative: string | null): Promise<number> => {
+      const recipient = Keypair.generate();
+      const [credentialPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("credential"), achievementPda.toBuffer(), issuerPda.toBuffer(), recipient.publicKey.toBuffer()],
+        program.programId
+      );
+      const timestamp = new Date().toISOString();
+      const context = JSON.stringify([
+        "https://www.w3.org/ns/credentials/v2",
+        "https://purl.imsglobal.org/spec/ob/v3p0/context-3.0.3.json",
+      ]);
+      const type = JSON.stringify(["VerifiableCredential", "OpenBadgeCredential"]);
+      const messageJson = `{"@context":${context},"id":"did:sol:${credentialPda}","type":${type},"issuer":"did:sol:${issuerPda}","validFrom":"${timestamp}","credentialSubject":{"id":"did:sol:${recipient.publicKey}","type":["AchievementSubject"],"achievement":"did:sol:${achievementPda}"}}`;
+
+      const signature = await program.methods
+        .issueAchievementCredential({
+          version: 3,
+          recipientPubkey: recipient.publicKey,
+          signatureData: signMessage(messageJson, issuerAuthority),
+          messageData: Buffer.from(messageJson),
+          timestamp,
+          results: [],
+          narrative,
+          notes: [],
+          credentialSchemas: [],
+          bindHolder: false,
+          termsOfUse: null,
+          pairwiseSubjectId: null,
+        })
+        .accountsStrict({
+          programConfig: findProgramConfigPda(program.programId),
+          treasury: findTreasuryPda(program.programId),
+          feeTokenAccount: null,
+          treasuryTokenAccount: null,
+          tokenProgram: null,
+          credential: credentialPda,
+          achievement: achievementPda,
+          issuer: issuerPda,
+          cpiPolicy: null,
+          instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+          issuerStats: findIssuerStatsPda(program.programId, issuerPda),
+          consumedPayload: findConsumedPayloadPda(program.programId, issuerPda, Buffer.from(messageJson)),
+          authority: issuerAuthority.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          recipientOptOut: findOptOutPda(program.programId, recipient.publicKey),
+          achievementStats: findAchievementStatsPda(program.programId, achievementPda),
+          credentialIndex: findCredentialIndexPda(program.programId, recipient.publicKey),
+          credentialIndexPage: findCredentialIndexPagePda(program.programId, recipient.publicKey, 0),
+          recipientRoster: findRecipientRosterPda(program.programId, achievementPda),
+          recipientRosterPage: findRecipientRosterPagePda(program.programId, achievementPda, 0),
+          resultEntry: null,
+          rubric: null,
+          schemaRegistry: null,
+          nomination: null,
+        })
+        .preInstructions([ed25519Instruction(messageJson, issuerAuthority)])
+        .signers([issuerAuthority])
+        .rpc({ commitment: "confirmed" });
+
+      const transaction = await provider.connection.getTransaction(signature, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      return transaction.meta.computeUnitsConsumed;
+    };
+
+    it("Should report compute units for growing issuance payloads", async () => {
+      // Payloads stay small enough to fit a legacy transaction alongside the signed JSON
+      const sizes = [0, 32, 64];
+      const consumed: number[] = [];
+      for (const size of sizes) {
+        consumed.push(await issueWithNarrative(size === 0 ? null : "n".repeat(size)));
+      }
+
+      console.log("📊 issue_achievement_credential compute units:");
+      sizes.forEach((size, index) => console.log(`   narrative ${size} bytes: ${consumed[index]} CU`));
+      consumed.forEach((units) => expect(units).to.be.lessThan(1_400_000));
+    });
+  });
 });